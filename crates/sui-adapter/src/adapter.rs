@@ -193,6 +193,7 @@ fn execute_internal<
         .map(|(id, (owner, _))| (*id, (by_value_objects.contains(id), *owner)))
         .collect();
     let mut session = new_session(vm, state_view, input_objects);
+    gas_status.observe_entry(module_id, function.as_str());
     // script visibility checked manually for entry points
     let (
         SerializedReturnValues {
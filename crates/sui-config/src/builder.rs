@@ -10,6 +10,7 @@ use crate::{
 };
 use rand::rngs::OsRng;
 use std::{
+    collections::BTreeMap,
     num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::Arc,
@@ -277,6 +278,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     network_address,
                     metrics_address: utils::available_local_socket_address(),
                     admin_interface_port: utils::get_available_port(),
+                    admin_interface_token: None,
                     json_rpc_address: utils::available_local_socket_address(),
                     websocket_address: None,
                     consensus_config: Some(consensus_config),
@@ -288,6 +290,28 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
                     grpc_concurrency_limit: initial_accounts_config.grpc_concurrency_limit,
                     p2p_config,
+                    admission_control: None,
+                    logging: None,
+                    stats_reporting: None,
+                    read_only: false,
+                    write_through: false,
+                    deny_list: vec![],
+                    shutdown_timeout_secs: None,
+                    grpc_message_size_limits: None,
+                    executed_certificate_cache_size: None,
+                    authority_channel_pool_size: None,
+                    min_supported_version_object: None,
+                    slow_calls: None,
+                    read_fallback: None,
+                    analytics_export: None,
+                    object_cache: None,
+                    input_prefetch: None,
+                    dry_run_sandbox: None,
+                    execution_audit: None,
+                    rpc_response_limits: None,
+                    rpc_endpoints: vec![],
+                    watchdog: None,
+                    db_compression: BTreeMap::new(),
                 }
             })
             .collect();
@@ -8,6 +8,7 @@ use move_binary_format::CompiledModule;
 use move_core_types::ident_str;
 use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
+use multiaddr::Multiaddr;
 use narwhal_crypto::NetworkPublicKey;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
@@ -25,6 +26,7 @@ use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::messages::CallArg;
 use sui_types::messages::InputObjects;
 use sui_types::messages::Transaction;
+use sui_types::network_info::TrustedNetwork;
 use sui_types::sui_serde::{Base64, Encoding};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::temporary_store::{InnerTemporaryStore, TemporaryStore};
@@ -32,7 +34,7 @@ use sui_types::MOVE_STDLIB_ADDRESS;
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 use sui_types::{
     base_types::{encode_bytes_hex, TxContext},
-    committee::{Committee, EpochId},
+    committee::{Committee, CommitteeWithNetAddresses, EpochId},
     error::SuiResult,
     object::Object,
     sui_serde::AuthSignature,
@@ -65,6 +67,35 @@ impl Genesis {
         )
     }
 
+    /// What clients of the network started from this genesis trust before having verified any
+    /// network info bundle.
+    pub fn trusted_network(&self) -> SuiResult<TrustedNetwork> {
+        Ok(TrustedNetwork {
+            genesis_digest: self.sha3(),
+            committee: self.committee()?,
+        })
+    }
+
+    pub fn committee_with_net_addresses(&self) -> SuiResult<CommitteeWithNetAddresses> {
+        Ok(CommitteeWithNetAddresses {
+            committee: self.committee()?,
+            net_addresses: self
+                .validator_set
+                .iter()
+                .map(|validator| {
+                    (
+                        validator.protocol_key(),
+                        validator.network_address().to_vec(),
+                    )
+                })
+                .collect(),
+            backup_net_addresses: self
+                .sui_system_object()
+                .get_current_epoch_committee()
+                .backup_net_addresses,
+        })
+    }
+
     #[allow(clippy::mutable_key_type)]
     pub fn narwhal_committee(&self) -> narwhal_config::SharedCommittee {
         let narwhal_committee = self
@@ -244,6 +275,10 @@ pub struct GenesisValidatorInfo {
     pub info: ValidatorInfo,
     #[serde_as(as = "AuthSignature")]
     pub proof_of_possession: AuthoritySignature,
+    /// Other network addresses of the validator, which clients dial in order when the network
+    /// address is unreachable. Recorded on-chain rather than in `ValidatorInfo`.
+    #[serde(default)]
+    pub backup_network_addresses: Vec<Multiaddr>,
 }
 
 pub struct Builder {
@@ -278,15 +313,26 @@ impl Builder {
     }
 
     pub fn add_validator(
+        self,
+        validator: ValidatorInfo,
+        proof_of_possession: AuthoritySignature,
+    ) -> Self {
+        self.add_validator_with_backup_addresses(validator, proof_of_possession, vec![])
+    }
+
+    /// Like `add_validator`, also registering the backup network addresses of the validator.
+    pub fn add_validator_with_backup_addresses(
         mut self,
         validator: ValidatorInfo,
         proof_of_possession: AuthoritySignature,
+        backup_network_addresses: Vec<Multiaddr>,
     ) -> Self {
         self.validators.insert(
             validator.protocol_key(),
             GenesisValidatorInfo {
                 info: validator,
                 proof_of_possession,
+                backup_network_addresses,
             },
         );
         self
@@ -308,6 +354,10 @@ impl Builder {
             .map(|(_, v)| v)
             .collect::<Vec<_>>();
         let objects = create_genesis_objects(&mut genesis_ctx, &modules, &objects, &validators);
+        let backup_network_addresses = validators
+            .iter()
+            .map(|genesis_info| genesis_info.backup_network_addresses.clone())
+            .collect::<Vec<_>>();
 
         let genesis = Genesis {
             objects,
@@ -321,9 +371,10 @@ impl Builder {
         let system_object = genesis.sui_system_object();
         assert_eq!(system_object.epoch, 0);
 
-        for (validator, onchain_validator) in genesis
+        for ((validator, backup_network_addresses), onchain_validator) in genesis
             .validator_set()
             .iter()
+            .zip(backup_network_addresses)
             .zip(system_object.validators.active_validators.iter())
         {
             assert_eq!(validator.stake(), onchain_validator.stake_amount);
@@ -340,6 +391,13 @@ impl Builder {
                 validator.network_address().to_vec(),
                 onchain_validator.metadata.net_address
             );
+            assert_eq!(
+                backup_network_addresses
+                    .iter()
+                    .map(Multiaddr::to_vec)
+                    .collect::<Vec<_>>(),
+                onchain_validator.metadata.backup_net_addresses
+            );
         }
 
         genesis
@@ -539,6 +597,7 @@ pub fn generate_genesis_system_object(
     let mut proof_of_possessions = Vec::new();
     let mut sui_addresses = Vec::new();
     let mut network_addresses = Vec::new();
+    let mut backup_network_addresses = Vec::new();
     let mut names = Vec::new();
     let mut stakes = Vec::new();
     let mut gas_prices = Vec::new();
@@ -546,6 +605,7 @@ pub fn generate_genesis_system_object(
     for GenesisValidatorInfo {
         info: validator,
         proof_of_possession,
+        backup_network_addresses: backups,
     } in committee
     {
         pubkeys.push(validator.protocol_key());
@@ -553,6 +613,7 @@ pub fn generate_genesis_system_object(
         proof_of_possessions.push(proof_of_possession.as_ref().to_vec());
         sui_addresses.push(validator.sui_address());
         network_addresses.push(validator.network_address());
+        backup_network_addresses.push(backups);
         names.push(validator.name().to_owned().into_bytes());
         stakes.push(validator.stake());
         gas_prices.push(validator.gas_price());
@@ -571,6 +632,7 @@ pub fn generate_genesis_system_object(
             CallArg::Pure(bcs::to_bytes(&sui_addresses).unwrap()),
             CallArg::Pure(bcs::to_bytes(&names).unwrap()),
             CallArg::Pure(bcs::to_bytes(&network_addresses).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&backup_network_addresses).unwrap()),
             CallArg::Pure(bcs::to_bytes(&stakes).unwrap()),
             CallArg::Pure(bcs::to_bytes(&gas_prices).unwrap()),
         ],
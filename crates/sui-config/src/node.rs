@@ -10,10 +10,11 @@ use narwhal_config::Parameters as ConsensusParameters;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::BTreeMap;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use sui_types::base_types::SuiAddress;
+use std::time::Duration;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::committee::StakeUnit;
 use sui_types::crypto::AccountKeyPair;
 use sui_types::crypto::AuthorityKeyPair;
@@ -28,6 +29,9 @@ use sui_types::sui_serde::KeyPairBase64;
 // Default max number of concurrent requests served
 pub const DEFAULT_GRPC_CONCURRENCY_LIMIT: usize = 20000000000;
 
+// Default time given to in-flight requests to complete when shutting down
+pub const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -58,6 +62,11 @@ pub struct NodeConfig {
     pub metrics_address: SocketAddr,
     #[serde(default = "default_admin_interface_port")]
     pub admin_interface_port: u16,
+    /// Token admin requests must carry as `Authorization: Bearer <token>`. Without it, a token is
+    /// generated at each start and written to `admin-token` next to the node config, and only the
+    /// operational routes, e.g. reprocessing certificates, require it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_interface_token: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consensus_config: Option<ConsensusConfig>,
@@ -83,6 +92,129 @@ pub struct NodeConfig {
     #[serde(default)]
     pub p2p_config: P2pConfig,
 
+    /// Prioritization of validator requests under overload. Requests are admitted without
+    /// limits when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admission_control: Option<AdmissionControlConfig>,
+
+    /// Log output format and filters. RUST_LOG and RUST_LOG_JSON are used when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<LoggingConfig>,
+
+    /// Periodic reporting of anonymized node statistics. Nothing is reported when this is not
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats_reporting: Option<StatsReportingConfig>,
+
+    /// Reject transaction submissions while still serving reads and syncing state, e.g. to drain
+    /// write traffic before an upgrade. Can also be toggled at runtime through the admin API.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+
+    /// Execute every transaction submitted to this fullnode locally before responding, whatever
+    /// the request type asks for, so that wallets pointed at the node get back the effects and
+    /// events it executed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write_through: bool,
+
+    /// Packages, as `<package>`, and Move functions, as `<package>::<module>::<function>`, that
+    /// transactions submitted to this node may not call. Can also be changed at runtime through
+    /// the admin API.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_list: Vec<String>,
+
+    /// Time given to in-flight requests to complete when shutting down, after which the node
+    /// exits regardless. Defaults to 30 seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shutdown_timeout_secs: Option<u64>,
+
+    /// Max sizes of the gRPC messages sent and received, per service. The defaults are derived
+    /// from protocol limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_message_size_limits: Option<GrpcMessageSizeLimits>,
+
+    /// Number of responses to recently executed certificates a validator keeps in memory to
+    /// answer retries without reading the store. 0 disables the cache. Defaults to 10000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executed_certificate_cache_size: Option<usize>,
+
+    /// Number of gRPC channels, each with its own HTTP/2 connection, opened to every authority.
+    /// Requests are sent over the channels in round-robin. Defaults to a single channel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authority_channel_pool_size: Option<usize>,
+
+    /// A Move object recording, after its UID, the oldest protocol version supported by the
+    /// network as a u64. If set, the node reads it from the validators on startup and refuses
+    /// to start if its own protocol version is older, i.e. if it must be updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_supported_version_object: Option<ObjectID>,
+
+    /// Latency thresholds above which calls to the JSON-RPC and gRPC servers are logged, counted
+    /// in the metrics and kept for the admin API. Calls slower than 1 second are recorded when
+    /// this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_calls: Option<SlowCallsConfig>,
+
+    /// If set, a full node fetches the transactions and objects it has not synced yet from the
+    /// validators to serve JSON-RPC reads, instead of answering that they are not found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_fallback: Option<ReadFallbackConfig>,
+
+    /// Export of the checkpoints committed on this node into tables for data warehouses.
+    /// Nothing is exported when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analytics_export: Option<AnalyticsExportConfig>,
+
+    /// In-memory cache of the latest versions of recently read objects, consulted before the
+    /// database when signing and executing transactions. No objects are cached when this is not
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_cache: Option<ObjectCacheConfig>,
+
+    /// If set, the input objects of the certificates a node syncs are read while they wait for
+    /// their parents to execute, so that the reads overlap with the execution of the parents.
+    /// Inputs are read when the certificates execute when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_prefetch: Option<InputPrefetchConfig>,
+
+    /// If set, dry runs requested over JSON-RPC execute in a sandbox isolated from the execution
+    /// of certificates, with these resource ceilings. Dry runs share the execution of
+    /// certificates and are only bounded by the protocol limits when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run_sandbox: Option<ExecutionSandboxConfig>,
+
+    /// If set, the node reports the certificates it syncs with their effects whose effects it
+    /// does not reproduce, and executes again a sample of them once committed, as an early
+    /// warning of non-determinism in execution. Nothing is audited when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_audit: Option<ExecutionAuditConfig>,
+
+    /// Bounds on the responses of the JSON-RPC read methods. Pages are cut to fit these limits,
+    /// and other responses larger than them are replaced by an error. The defaults of
+    /// `RpcResponseLimitsConfig` apply when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_response_limits: Option<RpcResponseLimitsConfig>,
+
+    /// The JSON-RPC endpoints of the network, e.g. `https://fullnode.example.com:443`, which a
+    /// validator vouches for in the network info it signs for clients. Validators which should
+    /// reach a quorum on the network info must be configured with the same endpoints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_endpoints: Vec<String>,
+
+    /// If set, the node checks its own health periodically, and takes the configured actions
+    /// when a check fails, e.g. halting the intake of transactions while execution is stalled.
+    /// Nothing is checked when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchdog: Option<WatchdogConfig>,
+
+    /// The compression of the column families of the databases, by column family name. Only
+    /// the `transactions`, `certificates`, `effects`, `checkpoints` and `checkpoint_contents`
+    /// column families can be compressed, and the node fails to start if another one is listed.
+    /// When the compression of a column family changes, its existing data is rewritten in the
+    /// background once the node started.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub db_compression: BTreeMap<String, DbCompressionConfig>,
+
     pub genesis: Genesis,
 }
 
@@ -168,6 +300,13 @@ impl NodeConfig {
     pub fn genesis(&self) -> Result<&genesis::Genesis> {
         self.genesis.genesis()
     }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.shutdown_timeout_secs
+                .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -196,6 +335,501 @@ impl ConsensusConfig {
     }
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GrpcMessageSizeLimits {
+    /// Limits of the validator service, which carries transactions, certificates and
+    /// checkpoints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<ServiceMessageSizeLimits>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServiceMessageSizeLimits {
+    /// Max size in bytes of a message sent to the service, or by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_encoding_message_size: Option<usize>,
+    /// Max size in bytes of a message received by the service, or from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_decoding_message_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdmissionControlConfig {
+    /// Max number of certificates processed concurrently, shared among peer classes by weight.
+    #[serde(default = "default_max_concurrent_certificates")]
+    pub max_concurrent_certificates: usize,
+    /// Max number of transactions signed concurrently, shared among peer classes by weight.
+    #[serde(default = "default_max_concurrent_transactions")]
+    pub max_concurrent_transactions: usize,
+    /// IP addresses of known peers, e.g. the fullnodes run by the validator operator.
+    #[serde(default)]
+    pub known_peers: Vec<IpAddr>,
+    #[serde(default = "default_known_peer_weight")]
+    pub known_peer_weight: u32,
+    #[serde(default = "default_unknown_peer_weight")]
+    pub unknown_peer_weight: u32,
+    /// How long a request waits for admission before it is rejected.
+    #[serde(default = "default_admission_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+}
+
+fn default_max_concurrent_certificates() -> usize {
+    5000
+}
+
+fn default_max_concurrent_transactions() -> usize {
+    2000
+}
+
+fn default_known_peer_weight() -> u32 {
+    4
+}
+
+fn default_unknown_peer_weight() -> u32 {
+    1
+}
+
+fn default_admission_queue_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for AdmissionControlConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_certificates: default_max_concurrent_certificates(),
+            max_concurrent_transactions: default_max_concurrent_transactions(),
+            known_peers: vec![],
+            known_peer_weight: default_known_peer_weight(),
+            unknown_peer_weight: default_unknown_peer_weight(),
+            queue_timeout_ms: default_admission_queue_timeout_ms(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoggingConfig {
+    /// Output logs as JSON, one object per line.
+    #[serde(default)]
+    pub json: bool,
+    /// Log levels by module, e.g. `sui_core::authority_aggregator: debug`, added to the filter
+    /// from RUST_LOG. They can be changed at runtime through the admin API.
+    #[serde(default)]
+    pub module_levels: BTreeMap<String, String>,
+    /// Only create one in every N spans of the given name, e.g. `quorum_map_auth: 100`, for
+    /// spans too frequent to trace in full.
+    #[serde(default)]
+    pub span_sample_intervals: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StatsReportingConfig {
+    /// URL the reports are posted to, as a JSON array.
+    pub endpoint: String,
+    #[serde(default = "default_stats_reporting_interval_secs")]
+    pub interval_secs: u64,
+    /// Max number of reports kept while the endpoint is unreachable.
+    #[serde(default = "default_stats_reporting_buffer_size")]
+    pub buffer_size: usize,
+}
+
+fn default_stats_reporting_interval_secs() -> u64 {
+    3600
+}
+
+fn default_stats_reporting_buffer_size() -> usize {
+    24
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SlowCallsConfig {
+    /// Threshold of the JSON-RPC calls, over HTTP or WebSocket.
+    #[serde(default = "default_slow_call_threshold_ms")]
+    pub json_rpc_threshold_ms: u64,
+    #[serde(default = "default_slow_call_threshold_ms")]
+    pub grpc_threshold_ms: u64,
+    /// Number of the most recent slow calls kept for the admin API.
+    #[serde(default = "default_slow_call_log_size")]
+    pub log_size: usize,
+}
+
+fn default_slow_call_threshold_ms() -> u64 {
+    1000
+}
+
+fn default_slow_call_log_size() -> usize {
+    100
+}
+
+impl Default for SlowCallsConfig {
+    fn default() -> Self {
+        Self {
+            json_rpc_threshold_ms: default_slow_call_threshold_ms(),
+            grpc_threshold_ms: default_slow_call_threshold_ms(),
+            log_size: default_slow_call_log_size(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReadFallbackConfig {
+    /// Number of transactions, and of objects, fetched from the validators that are kept.
+    #[serde(default = "default_read_fallback_cache_size")]
+    pub cache_size: usize,
+    /// How long an object fetched from the validators is served before being fetched again.
+    #[serde(default = "default_read_fallback_object_ttl_ms")]
+    pub object_ttl_ms: u64,
+    /// How long a transaction or object the validators failed to serve is not fetched again.
+    #[serde(default = "default_read_fallback_not_found_ttl_ms")]
+    pub not_found_ttl_ms: u64,
+    /// The most reads fetched from the validators at once. Reads beyond it are not found.
+    #[serde(default = "default_read_fallback_max_concurrent_fetches")]
+    pub max_concurrent_fetches: usize,
+}
+
+fn default_read_fallback_cache_size() -> usize {
+    10_000
+}
+
+fn default_read_fallback_object_ttl_ms() -> u64 {
+    2_000
+}
+
+fn default_read_fallback_not_found_ttl_ms() -> u64 {
+    1_000
+}
+
+fn default_read_fallback_max_concurrent_fetches() -> usize {
+    16
+}
+
+impl Default for ReadFallbackConfig {
+    fn default() -> Self {
+        Self {
+            cache_size: default_read_fallback_cache_size(),
+            object_ttl_ms: default_read_fallback_object_ttl_ms(),
+            not_found_ttl_ms: default_read_fallback_not_found_ttl_ms(),
+            max_concurrent_fetches: default_read_fallback_max_concurrent_fetches(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AnalyticsExportConfig {
+    /// Directory the tables are written to, with a sub directory per table and schema version.
+    pub output_dir: PathBuf,
+    #[serde(default)]
+    pub format: AnalyticsFileFormat,
+    /// Number of checkpoints whose rows are written to each file.
+    #[serde(default = "default_analytics_checkpoints_per_file")]
+    pub checkpoints_per_file: u64,
+    /// Checkpoint the export starts from when the output directory has no export yet.
+    #[serde(default)]
+    pub start_checkpoint: u64,
+}
+
+fn default_analytics_checkpoints_per_file() -> u64 {
+    1000
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnalyticsFileFormat {
+    /// Comma separated values, with a header row naming the columns.
+    #[default]
+    Csv,
+    /// Apache Parquet, with a single row group per file.
+    Parquet,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectCacheConfig {
+    /// Total approximate size of the cached objects, beyond which the least recently used
+    /// objects are evicted.
+    #[serde(default = "default_object_cache_max_size_bytes")]
+    pub max_size_bytes: usize,
+}
+
+impl Default for ObjectCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: default_object_cache_max_size_bytes(),
+        }
+    }
+}
+
+fn default_object_cache_max_size_bytes() -> usize {
+    256 * 1024 * 1024
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InputPrefetchConfig {
+    /// Total approximate size of the prefetched objects, beyond which the inputs of further
+    /// certificates are read when they execute.
+    #[serde(default = "default_input_prefetch_max_size_bytes")]
+    pub max_size_bytes: usize,
+}
+
+impl Default for InputPrefetchConfig {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: default_input_prefetch_max_size_bytes(),
+        }
+    }
+}
+
+fn default_input_prefetch_max_size_bytes() -> usize {
+    64 * 1024 * 1024
+}
+
+/// Resource ceilings of the executions of transactions submitted by untrusted users, e.g. dry
+/// runs, on top of the protocol execution limits.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionSandboxConfig {
+    /// Largest gas budget accepted, which bounds the Move instructions executed.
+    #[serde(default = "default_sandbox_max_gas_budget")]
+    pub max_gas_budget: u64,
+    /// Largest total size of the input objects loaded by an execution.
+    #[serde(default = "default_sandbox_max_input_size_bytes")]
+    pub max_input_size_bytes: usize,
+    /// Largest size of an object written by an execution.
+    #[serde(default = "default_sandbox_max_object_size_bytes")]
+    pub max_object_size_bytes: u64,
+    /// Most objects an execution may create.
+    #[serde(default = "default_sandbox_max_created_objects")]
+    pub max_created_objects: u64,
+    /// Most events an execution may emit.
+    #[serde(default = "default_sandbox_max_events")]
+    pub max_events: u64,
+    /// Largest total size of the modules published by an execution, whose verification is not
+    /// metered by the gas budget.
+    #[serde(default = "default_sandbox_max_package_size_bytes")]
+    pub max_package_size_bytes: usize,
+    /// Most executions running at once. Further executions are rejected.
+    #[serde(default = "default_sandbox_max_concurrent_executions")]
+    pub max_concurrent_executions: usize,
+}
+
+impl Default for ExecutionSandboxConfig {
+    fn default() -> Self {
+        Self {
+            max_gas_budget: default_sandbox_max_gas_budget(),
+            max_input_size_bytes: default_sandbox_max_input_size_bytes(),
+            max_object_size_bytes: default_sandbox_max_object_size_bytes(),
+            max_created_objects: default_sandbox_max_created_objects(),
+            max_events: default_sandbox_max_events(),
+            max_package_size_bytes: default_sandbox_max_package_size_bytes(),
+            max_concurrent_executions: default_sandbox_max_concurrent_executions(),
+        }
+    }
+}
+
+fn default_sandbox_max_gas_budget() -> u64 {
+    1_000_000
+}
+
+fn default_sandbox_max_input_size_bytes() -> usize {
+    4 * 1024 * 1024
+}
+
+fn default_sandbox_max_object_size_bytes() -> u64 {
+    64 * 1024
+}
+
+fn default_sandbox_max_created_objects() -> u64 {
+    256
+}
+
+fn default_sandbox_max_events() -> u64 {
+    64
+}
+
+fn default_sandbox_max_package_size_bytes() -> usize {
+    256 * 1024
+}
+
+fn default_sandbox_max_concurrent_executions() -> usize {
+    4
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionAuditConfig {
+    /// Execute again one in every `sample_interval` synced certificates. 0 disables executing
+    /// them again, but not reporting the divergences of their execution by the node.
+    #[serde(default = "default_execution_audit_sample_interval")]
+    pub sample_interval: u64,
+    /// Directory the context of each divergence is written to, as
+    /// `<transaction digest in hex>.<synced or audit>.json`. Divergences are only logged and
+    /// counted when this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dump_dir: Option<PathBuf>,
+}
+
+impl Default for ExecutionAuditConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: default_execution_audit_sample_interval(),
+            dump_dir: None,
+        }
+    }
+}
+
+fn default_execution_audit_sample_interval() -> u64 {
+    100
+}
+
+/// Bounds on the responses of the JSON-RPC server, which protect the node from building and
+/// serializing responses of unbounded size.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcResponseLimitsConfig {
+    /// Largest serialized response. Pages are cut to fit within it, other responses larger than
+    /// it are replaced by an error.
+    #[serde(default = "default_rpc_max_response_size_bytes")]
+    pub max_response_size_bytes: usize,
+    /// Most items in a page, whatever the limit requested.
+    #[serde(default = "default_rpc_max_page_size")]
+    pub max_page_size: usize,
+}
+
+impl Default for RpcResponseLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_response_size_bytes: default_rpc_max_response_size_bytes(),
+            max_page_size: default_rpc_max_page_size(),
+        }
+    }
+}
+
+fn default_rpc_max_response_size_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+fn default_rpc_max_page_size() -> usize {
+    4096
+}
+
+/// The checks run by the watchdog of a node. Checks which are not set are not run.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WatchdogConfig {
+    #[serde(default = "default_watchdog_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Fails once certificates are pending execution and none was taken off the queue of
+    /// pending certificates, e.g. executed, for `threshold` seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_stall: Option<WatchdogCheckConfig>,
+    /// Fails once certificates are submitted to consensus and no consensus round was committed
+    /// for `threshold` seconds. Only run by validators.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_stall: Option<WatchdogCheckConfig>,
+    /// Fails once at least `threshold` database writes failed since the previous check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db_write_errors: Option<WatchdogCheckConfig>,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: default_watchdog_check_interval_secs(),
+            execution_stall: None,
+            consensus_stall: None,
+            db_write_errors: None,
+        }
+    }
+}
+
+fn default_watchdog_check_interval_secs() -> u64 {
+    10
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WatchdogCheckConfig {
+    /// The seconds or the number of errors from which the check fails.
+    pub threshold: u64,
+    /// Taken when the check starts failing, and again every `retry-interval-secs` while it keeps
+    /// failing. Halted intake resumes once no failing check halts it.
+    #[serde(default = "default_watchdog_actions")]
+    pub actions: Vec<WatchdogAction>,
+    #[serde(default = "default_watchdog_retry_interval_secs")]
+    pub retry_interval_secs: u64,
+}
+
+fn default_watchdog_actions() -> Vec<WatchdogAction> {
+    vec![WatchdogAction::Alert]
+}
+
+fn default_watchdog_retry_interval_secs() -> u64 {
+    300
+}
+
+/// The zstd compression of a column family, whose blocks are compressed with a dictionary
+/// trained on a sample of its blocks.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DbCompressionConfig {
+    #[serde(default = "default_db_compression_level")]
+    pub level: i32,
+    /// The size of the dictionary, or 0 to compress each block on its own.
+    #[serde(default = "default_db_compression_dictionary_bytes")]
+    pub dictionary_bytes: u32,
+    /// The most data sampled to train the dictionary, 100 times the size of the dictionary by
+    /// default. If 0, the sampled data is used as the dictionary without training.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub training_bytes: Option<u32>,
+}
+
+impl DbCompressionConfig {
+    pub fn training_bytes(&self) -> u32 {
+        self.training_bytes
+            .unwrap_or_else(|| self.dictionary_bytes.saturating_mul(100))
+    }
+}
+
+impl Default for DbCompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: default_db_compression_level(),
+            dictionary_bytes: default_db_compression_dictionary_bytes(),
+            training_bytes: None,
+        }
+    }
+}
+
+fn default_db_compression_level() -> i32 {
+    3
+}
+
+fn default_db_compression_dictionary_bytes() -> u32 {
+    16 * 1024
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WatchdogAction {
+    /// Log an error. Failures are counted and listed by the admin interface whatever the
+    /// actions.
+    Alert,
+    /// Reject new transactions with a retryable error while the check fails.
+    HaltIntake,
+    /// Restart the process executing pending certificates.
+    RestartExecution,
+    /// Restart the sync of checkpoints and certificates. Only done by full nodes.
+    RestartNodeSync,
+}
+
 /// Publicly known information about a validator
 /// TODO read most of this from on-chain
 #[serde_as]
@@ -7,6 +7,7 @@ use rand::rngs::OsRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::Arc;
@@ -112,6 +113,7 @@ impl NetworkConfig {
             network_address,
             metrics_address: utils::available_local_socket_address(),
             admin_interface_port: utils::get_available_port(),
+            admin_interface_token: None,
             json_rpc_address: utils::available_local_socket_address(),
             websocket_address: if enable_websocket {
                 Some(utils::available_local_socket_address())
@@ -127,6 +129,28 @@ impl NetworkConfig {
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
             p2p_config,
+            admission_control: None,
+            logging: None,
+            stats_reporting: None,
+            read_only: false,
+            write_through: false,
+            deny_list: vec![],
+            shutdown_timeout_secs: None,
+            grpc_message_size_limits: None,
+            executed_certificate_cache_size: None,
+            authority_channel_pool_size: None,
+            min_supported_version_object: None,
+            slow_calls: None,
+            read_fallback: None,
+            analytics_export: None,
+            object_cache: None,
+            input_prefetch: None,
+            dry_run_sandbox: None,
+            execution_audit: None,
+            rpc_response_limits: None,
+            rpc_endpoints: vec![],
+            watchdog: None,
+            db_compression: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures how many checkpoint certificates, with their contents, are verified per second when
+//! syncing: one by one as they are stored, or in batches verified in parallel with
+//! `verify_checkpoints_in_parallel`.
+
+use fastcrypto::traits::KeyPair;
+use std::sync::Arc;
+use std::time::Instant;
+use sui_core::authority_active::checkpoint_driver::verify_checkpoints_in_parallel;
+use sui_types::base_types::ExecutionDigests;
+use sui_types::committee::Committee;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, SignedCheckpointSummary,
+};
+use sui_types::state_accumulator::StateDigest;
+use test_utils::{test_committee, test_validator_keys};
+use tokio::runtime::Builder;
+
+const NUM_CHECKPOINTS: u64 = 1000;
+const TRANSACTIONS_PER_CHECKPOINT: usize = 100;
+const BATCH_SIZES: &[usize] = &[1, 8, 32];
+
+fn make_checkpoints(
+    committee: &Committee,
+) -> Vec<(CertifiedCheckpointSummary, CheckpointContents)> {
+    let keys = test_validator_keys();
+    (0..NUM_CHECKPOINTS)
+        .map(|seq| {
+            let contents = CheckpointContents::new_with_causally_ordered_transactions(
+                (0..TRANSACTIONS_PER_CHECKPOINT).map(|_| ExecutionDigests::random()),
+            );
+            let signed = keys
+                .iter()
+                .map(|(key, _, _, _)| {
+                    SignedCheckpointSummary::new(
+                        committee.epoch,
+                        seq,
+                        key.public().into(),
+                        key,
+                        &contents,
+                        None,
+                        GasCostSummary::default(),
+                        None,
+                        StateDigest::default(),
+                    )
+                })
+                .collect();
+            let cert = CertifiedCheckpointSummary::aggregate(signed, committee).unwrap();
+            (cert, contents)
+        })
+        .collect()
+}
+
+fn main() {
+    let committee = Arc::new(test_committee());
+    let checkpoints = Arc::new(make_checkpoints(&committee));
+
+    let start = Instant::now();
+    for (checkpoint, contents) in checkpoints.iter() {
+        checkpoint.verify(&committee, Some(contents)).unwrap();
+    }
+    println!(
+        "serial: {:.0} checkpoints/s",
+        NUM_CHECKPOINTS as f64 / start.elapsed().as_secs_f64()
+    );
+
+    let runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+    for &batch_size in BATCH_SIZES {
+        let start = Instant::now();
+        runtime
+            .block_on(verify_checkpoints_in_parallel(
+                committee.clone(),
+                checkpoints.clone(),
+                batch_size,
+            ))
+            .unwrap();
+        println!(
+            "parallel, batches of {batch_size}: {:.0} checkpoints/s",
+            NUM_CHECKPOINTS as f64 / start.elapsed().as_secs_f64()
+        );
+    }
+}
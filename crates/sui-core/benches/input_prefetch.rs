@@ -0,0 +1,107 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures how many synced certificates are executed per second when the input objects of the
+//! certificates waiting for their parents are prefetched while earlier certificates execute,
+//! against reading them when each certificate executes. Execution itself is simulated by a fixed
+//! delay, after the inputs of the certificate are read from the store.
+
+use std::path::Path;
+use std::sync::mpsc::sync_channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use sui_config::node::InputPrefetchConfig;
+use sui_core::authority::input_prefetcher::{InputPrefetchMetrics, InputPrefetcher};
+use sui_core::authority::AuthorityStore;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::messages::CertifiedTransaction;
+use sui_types::object::Object;
+use test_utils::messages::{make_transfer_object_transaction, make_tx_certs_and_signed_effects};
+use tokio::runtime::Builder;
+
+const NUM_CERTIFICATES: usize = 2000;
+/// Time taken to execute a certificate once its inputs are read.
+const EXECUTION_TIME: Duration = Duration::from_micros(200);
+/// Number of certificates waiting for their parents, whose inputs are prefetched.
+const QUEUE_DEPTH: usize = 32;
+
+fn open_store(path: &Path, objects: &[Object], prefetch: bool) -> AuthorityStore {
+    let mut store = AuthorityStore::open(path, None).unwrap();
+    if prefetch {
+        store = store.with_input_prefetcher(InputPrefetcher::new(
+            &InputPrefetchConfig::default(),
+            InputPrefetchMetrics::new_for_tests(),
+        ));
+    }
+    let objects: Vec<_> = objects.iter().collect();
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(store.bulk_object_insert(&objects))
+        .unwrap();
+    store
+}
+
+fn execute(store: &AuthorityStore, certificate: &CertifiedTransaction) {
+    let inputs = certificate.signed_data.data.input_objects().unwrap();
+    store.get_input_objects(&inputs).unwrap();
+    thread::sleep(EXECUTION_TIME);
+}
+
+fn main() {
+    let (sender, key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = SuiAddress::random_for_testing_only();
+    let mut objects = Vec::new();
+    let mut transactions = Vec::new();
+    for _ in 0..NUM_CERTIFICATES {
+        let object = Object::with_owner_for_testing(sender);
+        let gas = Object::with_owner_for_testing(sender);
+        transactions.push(make_transfer_object_transaction(
+            object.compute_object_reference(),
+            gas.compute_object_reference(),
+            sender,
+            &key,
+            recipient,
+        ));
+        objects.extend([object, gas]);
+    }
+    let (certificates, _) = make_tx_certs_and_signed_effects(transactions);
+    let certificates = Arc::new(certificates);
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = open_store(&dir.path().join("serial"), &objects, false);
+    let start = Instant::now();
+    for certificate in certificates.iter() {
+        execute(&store, certificate);
+    }
+    println!(
+        "inputs read at execution: {:.0} certificates/s",
+        NUM_CERTIFICATES as f64 / start.elapsed().as_secs_f64()
+    );
+
+    let store = Arc::new(open_store(&dir.path().join("prefetch"), &objects, true));
+    let (queue, queued) = sync_channel(QUEUE_DEPTH);
+    let start = Instant::now();
+    let prefetching = {
+        let store = store.clone();
+        let certificates = certificates.clone();
+        thread::spawn(move || {
+            for certificate in certificates.iter() {
+                let guard = store.prefetch_input_objects(certificate, &[]).unwrap();
+                queue.send(guard).unwrap();
+            }
+        })
+    };
+    for certificate in certificates.iter() {
+        let _guard = queued.recv().unwrap();
+        execute(&store, certificate);
+    }
+    prefetching.join().unwrap();
+    println!(
+        "inputs prefetched, {QUEUE_DEPTH} certificates ahead: {:.0} certificates/s",
+        NUM_CERTIFICATES as f64 / start.elapsed().as_secs_f64()
+    );
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures how many lightweight requests the runtime completes while certificates are being
+//! verified concurrently, with the verification run inline on the tokio workers or offloaded to
+//! the blocking thread pool through `spawn_verification`.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_core::safe_client::spawn_verification;
+use sui_types::committee::Committee;
+use sui_types::messages::CertifiedTransaction;
+use test_utils::messages::make_random_certified_transaction;
+use test_utils::test_committee;
+use tokio::runtime::Builder;
+
+const NUM_VERIFICATIONS: usize = 20_000;
+const NUM_VERIFYING_TASKS: usize = 64;
+const NUM_REQUEST_TASKS: usize = 256;
+
+fn run(offload: bool, committee: Arc<Committee>, certs: Arc<Vec<CertifiedTransaction>>) {
+    let runtime = Builder::new_multi_thread()
+        .enable_all()
+        .worker_threads(4)
+        .build()
+        .unwrap();
+
+    let (requests, elapsed) = runtime.block_on(async move {
+        let done = Arc::new(AtomicBool::new(false));
+        let requests = Arc::new(AtomicU64::new(0));
+
+        // Each request task stands for a client request that needs the reactor only briefly.
+        for _ in 0..NUM_REQUEST_TASKS {
+            let done = done.clone();
+            let requests = requests.clone();
+            tokio::spawn(async move {
+                while !done.load(Ordering::Relaxed) {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    requests.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+
+        let start = Instant::now();
+        let verifiers: Vec<_> = (0..NUM_VERIFYING_TASKS)
+            .map(|task| {
+                let committee = committee.clone();
+                let certs = certs.clone();
+                tokio::spawn(async move {
+                    for i in (task..NUM_VERIFICATIONS).step_by(NUM_VERIFYING_TASKS) {
+                        let cert = certs[i % certs.len()].clone();
+                        let result = if offload {
+                            let committee = committee.clone();
+                            spawn_verification(move || cert.verify(&committee)).await
+                        } else {
+                            cert.verify(&committee)
+                        };
+                        result.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for verifier in verifiers {
+            verifier.await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        done.store(true, Ordering::Relaxed);
+        (requests.load(Ordering::Relaxed), elapsed)
+    });
+
+    println!(
+        "{}: {:.0} verifications/s, {:.0} concurrent requests/s",
+        if offload { "offloaded" } else { "inline" },
+        NUM_VERIFICATIONS as f64 / elapsed.as_secs_f64(),
+        requests as f64 / elapsed.as_secs_f64(),
+    );
+}
+
+fn main() {
+    let committee = Arc::new(test_committee());
+    let certs = Arc::new(
+        (0..100)
+            .map(|_| make_random_certified_transaction())
+            .collect::<Vec<_>>(),
+    );
+    run(false, committee.clone(), certs.clone());
+    run(true, committee, certs);
+}
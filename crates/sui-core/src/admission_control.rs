@@ -0,0 +1,218 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*
+Admission control bounds the number of requests a validator processes concurrently, and shares
+that capacity among classes of peers by weight, so that a flood of requests from unknown
+clients cannot starve the fullnodes the operator knows about. Certificates and transactions
+have separate capacities, and certificates take priority: while any certificate is waiting to
+be admitted, new transactions are rejected, since finishing certified transactions frees
+resources whereas signing new ones only creates more work.
+*/
+use prometheus::core::{AtomicI64, AtomicU64, GenericCounter, GenericGauge};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::AdmissionControlConfig;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::timeout;
+
+#[cfg(test)]
+#[path = "unit_tests/admission_control_tests.rs"]
+mod admission_control_tests;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerClass {
+    /// Peers listed in the node config, e.g. the operator's fullnodes.
+    Known,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestKind {
+    Transaction,
+    Certificate,
+}
+
+/// Holds a slot of the admission capacity until dropped.
+pub struct AdmissionPermit {
+    _permit: OwnedSemaphorePermit,
+    in_flight: GenericGauge<AtomicI64>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.in_flight.dec();
+    }
+}
+
+struct ClassLimits {
+    transactions: Arc<Semaphore>,
+    certificates: Arc<Semaphore>,
+}
+
+pub struct AdmissionController {
+    known_peers: HashSet<IpAddr>,
+    known: ClassLimits,
+    unknown: ClassLimits,
+    waiting_certificates: AtomicUsize,
+    queue_timeout: Duration,
+    metrics: AdmissionControlMetrics,
+}
+
+impl AdmissionController {
+    pub fn new(config: &AdmissionControlConfig, registry: &Registry) -> Self {
+        let total_weight = (config.known_peer_weight + config.unknown_peer_weight).max(1) as usize;
+        let share =
+            |capacity: usize, weight: u32| (capacity * weight as usize / total_weight).max(1);
+        let limits = |weight: u32| ClassLimits {
+            transactions: Arc::new(Semaphore::new(share(
+                config.max_concurrent_transactions,
+                weight,
+            ))),
+            certificates: Arc::new(Semaphore::new(share(
+                config.max_concurrent_certificates,
+                weight,
+            ))),
+        };
+        Self {
+            known_peers: config.known_peers.iter().cloned().collect(),
+            known: limits(config.known_peer_weight),
+            unknown: limits(config.unknown_peer_weight),
+            waiting_certificates: AtomicUsize::new(0),
+            queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+            metrics: AdmissionControlMetrics::new(registry),
+        }
+    }
+
+    pub fn classify(&self, remote_addr: Option<SocketAddr>) -> PeerClass {
+        match remote_addr {
+            Some(addr) if self.known_peers.contains(&addr.ip()) => PeerClass::Known,
+            _ => PeerClass::Unknown,
+        }
+    }
+
+    /// Wait for a slot for a request of `kind` from a peer of `class`. Returns None if the
+    /// request is rejected, either because no slot was freed within the queue timeout, or
+    /// because it is a transaction and certificates are waiting.
+    pub async fn admit(&self, class: PeerClass, kind: RequestKind) -> Option<AdmissionPermit> {
+        let limits = match class {
+            PeerClass::Known => &self.known,
+            PeerClass::Unknown => &self.unknown,
+        };
+        let permit = match kind {
+            RequestKind::Transaction => {
+                if self.waiting_certificates.load(Ordering::Relaxed) > 0 {
+                    self.metrics.rejected(class, kind).inc();
+                    return None;
+                }
+                match limits.transactions.clone().try_acquire_owned() {
+                    Ok(permit) => Some(permit),
+                    Err(_) => self.wait(&limits.transactions).await,
+                }
+            }
+            RequestKind::Certificate => match limits.certificates.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    self.waiting_certificates.fetch_add(1, Ordering::Relaxed);
+                    let _waiting = scopeguard::guard(&self.waiting_certificates, |waiting| {
+                        waiting.fetch_sub(1, Ordering::Relaxed);
+                    });
+                    self.wait(&limits.certificates).await
+                }
+            },
+        };
+        let permit = match permit {
+            Some(permit) => permit,
+            None => {
+                self.metrics.rejected(class, kind).inc();
+                return None;
+            }
+        };
+        self.metrics.admitted(class, kind).inc();
+        let in_flight = self.metrics.in_flight(class, kind);
+        in_flight.inc();
+        Some(AdmissionPermit {
+            _permit: permit,
+            in_flight,
+        })
+    }
+
+    /// How long rejected clients are asked to wait before retrying: by then, the requests
+    /// which filled the queue have been admitted or rejected in turn.
+    pub fn retry_after(&self) -> Duration {
+        self.queue_timeout
+    }
+
+    async fn wait(&self, semaphore: &Arc<Semaphore>) -> Option<OwnedSemaphorePermit> {
+        timeout(self.queue_timeout, semaphore.clone().acquire_owned())
+            .await
+            .ok()?
+            .ok()
+    }
+}
+
+struct AdmissionControlMetrics {
+    admitted: IntCounterVec,
+    rejected: IntCounterVec,
+    in_flight: IntGaugeVec,
+}
+
+impl AdmissionControlMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            admitted: register_int_counter_vec_with_registry!(
+                "validator_admission_admitted",
+                "Number of requests admitted by admission control, by peer class and request kind",
+                &["peer_class", "request_kind"],
+                registry,
+            )
+            .unwrap(),
+            rejected: register_int_counter_vec_with_registry!(
+                "validator_admission_rejected",
+                "Number of requests rejected by admission control, by peer class and request kind",
+                &["peer_class", "request_kind"],
+                registry,
+            )
+            .unwrap(),
+            in_flight: register_int_gauge_vec_with_registry!(
+                "validator_admission_in_flight",
+                "Number of admitted requests being processed, by peer class and request kind",
+                &["peer_class", "request_kind"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn labels(class: PeerClass, kind: RequestKind) -> [&'static str; 2] {
+        [
+            match class {
+                PeerClass::Known => "known",
+                PeerClass::Unknown => "unknown",
+            },
+            match kind {
+                RequestKind::Transaction => "transaction",
+                RequestKind::Certificate => "certificate",
+            },
+        ]
+    }
+
+    fn admitted(&self, class: PeerClass, kind: RequestKind) -> GenericCounter<AtomicU64> {
+        self.admitted.with_label_values(&Self::labels(class, kind))
+    }
+
+    fn rejected(&self, class: PeerClass, kind: RequestKind) -> GenericCounter<AtomicU64> {
+        self.rejected.with_label_values(&Self::labels(class, kind))
+    }
+
+    fn in_flight(&self, class: PeerClass, kind: RequestKind) -> GenericGauge<AtomicI64> {
+        self.in_flight.with_label_values(&Self::labels(class, kind))
+    }
+}
@@ -0,0 +1,653 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of committed checkpoints into normalized tables, one file per table and batch of
+//! checkpoints, to be loaded into data warehouses. The tables are written under
+//! `<output dir>/<table>/v<schema version>/`, so that the files of an older schema are not mixed
+//! with newer ones. The schema version must be bumped whenever a column is added, removed or
+//! changes meaning.
+//!
+//! Cells use the encodings of the JSON-RPC API: digests are Base64 encoded, addresses and object
+//! IDs are hex encoded, and execution errors are the JSON encoding of the failure status.
+//!
+//! The exporter reads checkpoints from a `CheckpointSource`, which is the store of the node when
+//! it runs inside a node, or an archive of exported checkpoints when it runs offline. The next
+//! checkpoint to export is saved in the output directory after each batch, so the export resumes
+//! where it stopped.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::record::RecordWriter;
+use parquet_derive::ParquetRecordWriter;
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_with_registry, IntCounterVec,
+    IntGauge, Registry,
+};
+use serde::Serialize;
+use sui_config::node::{AnalyticsExportConfig, AnalyticsFileFormat};
+use sui_types::base_types::TransactionDigest;
+use sui_types::error::SuiError;
+use sui_types::event::Event;
+use sui_types::messages::{
+    CertifiedTransaction, ExecutionStatus, SingleTransactionKind, TransactionEffects,
+    TransactionKind,
+};
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointSummary};
+use sui_types::object::Owner;
+use sui_types::object_change::{changed_objects, ObjectChangeKind};
+use sui_types::sui_serde::{Base64, Encoding};
+use tracing::{error, info, warn};
+use typed_store::Map;
+
+use crate::authority::AuthorityState;
+
+/// Version of the schema of the tables.
+pub const ANALYTICS_SCHEMA_VERSION: u32 = 1;
+
+const PROGRESS_FILE: &str = "progress";
+
+/// A committed checkpoint with the certificates and effects of its transactions, in order.
+#[derive(Clone, Debug)]
+pub struct CheckpointData {
+    pub summary: CheckpointSummary,
+    pub transactions: Vec<CertifiedTransaction>,
+    pub effects: Vec<TransactionEffects>,
+}
+
+pub trait CheckpointSource: Send + Sync {
+    /// Returns the checkpoint `seq`, or None if it is not available yet.
+    fn get_checkpoint_data(&self, seq: CheckpointSequenceNumber) -> Result<Option<CheckpointData>>;
+}
+
+impl CheckpointSource for AuthorityState {
+    fn get_checkpoint_data(&self, seq: CheckpointSequenceNumber) -> Result<Option<CheckpointData>> {
+        let (checkpoint, contents) = {
+            let checkpoints = self.checkpoints.lock();
+            (
+                checkpoints.tables.checkpoints.get(&seq)?,
+                checkpoints.tables.checkpoint_contents.get(&seq)?,
+            )
+        };
+        let (checkpoint, contents) = match (checkpoint, contents) {
+            (Some(checkpoint), Some(contents)) => (checkpoint, contents),
+            _ => return Ok(None),
+        };
+        let mut transactions = Vec::with_capacity(contents.size());
+        let mut effects = Vec::with_capacity(contents.size());
+        for digests in contents.iter() {
+            // Full nodes store checkpoints before they are done executing their transactions.
+            match self.database.get_effects(&digests.transaction) {
+                Ok(tx_effects) => effects.push(tx_effects),
+                Err(SuiError::TransactionNotFound { .. }) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            }
+            transactions.push(
+                self.database
+                    .get_certified_transaction(&digests.transaction)?
+                    .ok_or_else(|| anyhow!("Missing certificate of {:?}", digests.transaction))?,
+            );
+        }
+        Ok(Some(CheckpointData {
+            summary: checkpoint.summary().clone(),
+            transactions,
+            effects,
+        }))
+    }
+}
+
+impl CheckpointSource for BTreeMap<CheckpointSequenceNumber, CheckpointData> {
+    fn get_checkpoint_data(&self, seq: CheckpointSequenceNumber) -> Result<Option<CheckpointData>> {
+        Ok(self.get(&seq).cloned())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnalyticsTable {
+    Transactions,
+    Effects,
+    Events,
+    ObjectChanges,
+}
+
+impl AnalyticsTable {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyticsTable::Transactions => "transactions",
+            AnalyticsTable::Effects => "effects",
+            AnalyticsTable::Events => "events",
+            AnalyticsTable::ObjectChanges => "object_changes",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ParquetRecordWriter)]
+pub struct TransactionRow {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub epoch: u64,
+    pub transaction_digest: String,
+    pub sender: String,
+    /// The kind of the transaction, or `Batch` for a batch of transactions.
+    pub kind: &'static str,
+    pub num_commands: u64,
+    /// `<package>::<module>::<function>` for a single Move call.
+    pub move_call: Option<String>,
+    pub gas_object_id: String,
+    pub gas_price: u64,
+    pub gas_budget: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ParquetRecordWriter)]
+pub struct EffectsRow {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_digest: String,
+    pub effects_digest: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub num_shared_objects: u64,
+    pub num_created: u64,
+    pub num_mutated: u64,
+    pub num_unwrapped: u64,
+    pub num_deleted: u64,
+    pub num_wrapped: u64,
+    pub num_events: u64,
+    pub num_dependencies: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ParquetRecordWriter)]
+pub struct EventRow {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_digest: String,
+    /// Index of the event among the events of the transaction.
+    pub event_index: u64,
+    pub event_type: &'static str,
+    pub sender: Option<String>,
+    pub package_id: Option<String>,
+    pub module: Option<String>,
+    pub object_id: Option<String>,
+    /// The Move type of Move events.
+    pub move_event_type: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ParquetRecordWriter)]
+pub struct ObjectChangeRow {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction_digest: String,
+    pub object_id: String,
+    pub version: u64,
+    pub object_digest: String,
+    pub change: &'static str,
+    /// One of `address`, `object`, `shared` or `immutable`, if the effects give the owner.
+    pub owner_kind: Option<&'static str>,
+    pub owner: Option<String>,
+}
+
+/// The rows of the tables for a batch of checkpoints.
+#[derive(Debug, Default)]
+pub struct TableRows {
+    pub transactions: Vec<TransactionRow>,
+    pub effects: Vec<EffectsRow>,
+    pub events: Vec<EventRow>,
+    pub object_changes: Vec<ObjectChangeRow>,
+}
+
+impl TableRows {
+    pub fn add_checkpoint(&mut self, checkpoint: &CheckpointData) -> Result<()> {
+        let seq = checkpoint.summary.sequence_number;
+        for transaction in &checkpoint.transactions {
+            self.transactions
+                .push(transaction_row(seq, checkpoint.summary.epoch, transaction));
+        }
+        for effects in &checkpoint.effects {
+            let tx_digest = digest_string(&effects.transaction_digest);
+            self.effects.push(effects_row(seq, effects)?);
+            for (event_index, event) in effects.events.iter().enumerate() {
+                self.events.push(EventRow {
+                    checkpoint: seq,
+                    transaction_digest: tx_digest.clone(),
+                    event_index: event_index as u64,
+                    event_type: event.variant_name(),
+                    sender: event.sender().map(|sender| sender.to_string()),
+                    package_id: event.package_id().map(|id| id.to_string()),
+                    module: event.module_name().map(str::to_string),
+                    object_id: event.object_id().map(|id| id.to_string()),
+                    move_event_type: match event {
+                        Event::MoveEvent { type_, .. } => Some(type_.to_string()),
+                        _ => None,
+                    },
+                });
+            }
+            for change in changed_objects(effects) {
+                let object_ref = change.object_ref;
+                let (owner_kind, owner) = match change.owner {
+                    Some(Owner::AddressOwner(address)) => {
+                        (Some("address"), Some(address.to_string()))
+                    }
+                    Some(Owner::ObjectOwner(address)) => {
+                        (Some("object"), Some(address.to_string()))
+                    }
+                    Some(Owner::Shared { .. }) => (Some("shared"), None),
+                    Some(Owner::Immutable) => (Some("immutable"), None),
+                    None => (None, None),
+                };
+                self.object_changes.push(ObjectChangeRow {
+                    checkpoint: seq,
+                    transaction_digest: tx_digest.clone(),
+                    object_id: object_ref.0.to_string(),
+                    version: object_ref.1.value(),
+                    object_digest: Base64::encode(object_ref.2),
+                    change: object_change_str(change.kind),
+                    owner_kind,
+                    owner,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn digest_string(digest: &TransactionDigest) -> String {
+    Base64::encode(digest)
+}
+
+fn transaction_row(
+    seq: CheckpointSequenceNumber,
+    epoch: u64,
+    transaction: &CertifiedTransaction,
+) -> TransactionRow {
+    let data = &transaction.signed_data.data;
+    let (kind, num_commands, move_call) = match &data.kind {
+        TransactionKind::Single(single) => (
+            single_kind_str(single),
+            1,
+            match single {
+                SingleTransactionKind::Call(call) => Some(format!(
+                    "{}::{}::{}",
+                    call.package.0, call.module, call.function
+                )),
+                _ => None,
+            },
+        ),
+        TransactionKind::Batch(batch) => ("Batch", batch.len() as u64, None),
+    };
+    TransactionRow {
+        checkpoint: seq,
+        epoch,
+        transaction_digest: digest_string(transaction.digest()),
+        sender: data.signer().to_string(),
+        kind,
+        num_commands,
+        move_call,
+        gas_object_id: data.gas().0.to_string(),
+        gas_price: data.gas_price,
+        gas_budget: data.gas_budget,
+    }
+}
+
+fn single_kind_str(kind: &SingleTransactionKind) -> &'static str {
+    match kind {
+        SingleTransactionKind::TransferObject(_) => "TransferObject",
+        SingleTransactionKind::Publish(_) => "Publish",
+        SingleTransactionKind::Call(_) => "Call",
+        SingleTransactionKind::TransferSui(_) => "TransferSui",
+        SingleTransactionKind::Pay(_) => "Pay",
+        SingleTransactionKind::ChangeEpoch(_) => "ChangeEpoch",
+    }
+}
+
+fn effects_row(seq: CheckpointSequenceNumber, effects: &TransactionEffects) -> Result<EffectsRow> {
+    let (success, error) = match &effects.status {
+        ExecutionStatus::Success => (true, None),
+        // The serde encoding of failures is part of the effects, so it is stable.
+        ExecutionStatus::Failure { error } => (false, Some(serde_json::to_string(error)?)),
+    };
+    Ok(EffectsRow {
+        checkpoint: seq,
+        transaction_digest: digest_string(&effects.transaction_digest),
+        effects_digest: Base64::encode(effects.digest().0),
+        success,
+        error,
+        computation_cost: effects.gas_used.computation_cost,
+        storage_cost: effects.gas_used.storage_cost,
+        storage_rebate: effects.gas_used.storage_rebate,
+        num_shared_objects: effects.shared_objects.len() as u64,
+        num_created: effects.created.len() as u64,
+        num_mutated: effects.mutated.len() as u64,
+        num_unwrapped: effects.unwrapped.len() as u64,
+        num_deleted: effects.deleted.len() as u64,
+        num_wrapped: effects.wrapped.len() as u64,
+        num_events: effects.events.len() as u64,
+        num_dependencies: effects.dependencies.len() as u64,
+    })
+}
+
+fn object_change_str(kind: ObjectChangeKind) -> &'static str {
+    match kind {
+        ObjectChangeKind::Created => "created",
+        ObjectChangeKind::Mutated => "mutated",
+        ObjectChangeKind::Unwrapped => "unwrapped",
+        ObjectChangeKind::Wrapped => "wrapped",
+        ObjectChangeKind::Deleted => "deleted",
+    }
+}
+
+pub struct AnalyticsExportMetrics {
+    last_exported_checkpoint: IntGauge,
+    exported_rows: IntCounterVec,
+}
+
+impl AnalyticsExportMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            last_exported_checkpoint: register_int_gauge_with_registry!(
+                "analytics_export_last_exported_checkpoint",
+                "The last checkpoint exported into the analytics tables",
+                registry,
+            )
+            .unwrap(),
+            exported_rows: register_int_counter_vec_with_registry!(
+                "analytics_export_rows",
+                "Number of rows exported into each analytics table",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Exports the checkpoints of a `CheckpointSource` into tables, in batches of
+/// `checkpoints_per_file` checkpoints.
+pub struct AnalyticsExporter {
+    source: Arc<dyn CheckpointSource>,
+    output_dir: PathBuf,
+    format: AnalyticsFileFormat,
+    checkpoints_per_file: u64,
+    next_checkpoint: CheckpointSequenceNumber,
+    metrics: AnalyticsExportMetrics,
+}
+
+impl AnalyticsExporter {
+    pub fn new(
+        config: &AnalyticsExportConfig,
+        source: Arc<dyn CheckpointSource>,
+        registry: &Registry,
+    ) -> Result<Self> {
+        fs::create_dir_all(&config.output_dir)?;
+        let progress = config.output_dir.join(PROGRESS_FILE);
+        let next_checkpoint = if progress.exists() {
+            fs::read_to_string(&progress)?.trim().parse()?
+        } else {
+            config.start_checkpoint
+        };
+        Ok(Self {
+            source,
+            output_dir: config.output_dir.clone(),
+            format: config.format,
+            checkpoints_per_file: config.checkpoints_per_file.max(1),
+            next_checkpoint,
+            metrics: AnalyticsExportMetrics::new(registry),
+        })
+    }
+
+    /// The next checkpoint to export.
+    pub fn next_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.next_checkpoint
+    }
+
+    /// Exports the available checkpoints, in full batches only unless `flush_partial_batch` is
+    /// set. Returns the number of exported checkpoints.
+    pub fn export_available(&mut self, flush_partial_batch: bool) -> Result<u64> {
+        let mut exported = 0;
+        loop {
+            let start = self.next_checkpoint;
+            let mut rows = TableRows::default();
+            let mut end = start;
+            while end - start < self.checkpoints_per_file {
+                match self.source.get_checkpoint_data(end)? {
+                    Some(checkpoint) => rows.add_checkpoint(&checkpoint)?,
+                    None => break,
+                }
+                end += 1;
+            }
+            let full = end - start == self.checkpoints_per_file;
+            if end == start || !(full || flush_partial_batch) {
+                return Ok(exported);
+            }
+            self.write_batch(start, end - 1, &rows)?;
+            exported += end - start;
+            if !full {
+                return Ok(exported);
+            }
+        }
+    }
+
+    fn write_batch(
+        &mut self,
+        first: CheckpointSequenceNumber,
+        last: CheckpointSequenceNumber,
+        rows: &TableRows,
+    ) -> Result<()> {
+        self.write_table(
+            AnalyticsTable::Transactions,
+            first,
+            last,
+            &rows.transactions,
+        )?;
+        self.write_table(AnalyticsTable::Effects, first, last, &rows.effects)?;
+        self.write_table(AnalyticsTable::Events, first, last, &rows.events)?;
+        self.write_table(
+            AnalyticsTable::ObjectChanges,
+            first,
+            last,
+            &rows.object_changes,
+        )?;
+
+        // Files of a batch exported again after a crash are overwritten, so progress is saved
+        // after the files are written.
+        self.next_checkpoint = last + 1;
+        write_atomically(
+            &self.output_dir.join(PROGRESS_FILE),
+            self.next_checkpoint.to_string().as_bytes(),
+        )?;
+        self.metrics.last_exported_checkpoint.set(last as i64);
+        info!(first, last, "Exported checkpoints to analytics tables");
+        Ok(())
+    }
+
+    fn write_table<R>(
+        &self,
+        table: AnalyticsTable,
+        first: CheckpointSequenceNumber,
+        last: CheckpointSequenceNumber,
+        rows: &[R],
+    ) -> Result<()>
+    where
+        R: Serialize,
+        for<'a> &'a [R]: RecordWriter<R>,
+    {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let dir = self
+            .output_dir
+            .join(table.as_str())
+            .join(format!("v{ANALYTICS_SCHEMA_VERSION}"));
+        fs::create_dir_all(&dir)?;
+        let bytes = match self.format {
+            AnalyticsFileFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(vec![]);
+                for row in rows {
+                    writer.serialize(row)?;
+                }
+                writer.into_inner().map_err(|e| anyhow!(e.to_string()))?
+            }
+            AnalyticsFileFormat::Parquet => {
+                let mut bytes = vec![];
+                let properties = Arc::new(WriterProperties::builder().build());
+                let mut writer = SerializedFileWriter::new(&mut bytes, rows.schema()?, properties)?;
+                let mut row_group = writer.next_row_group()?;
+                rows.write_to_row_group(&mut row_group)?;
+                row_group.close()?;
+                writer.close()?;
+                bytes
+            }
+        };
+        write_atomically(&dir.join(table_file_name(first, last, self.format)), &bytes)?;
+        self.metrics
+            .exported_rows
+            .with_label_values(&[table.as_str()])
+            .inc_by(rows.len() as u64);
+        Ok(())
+    }
+
+    /// Exports checkpoints as they become available, forever. Exports read the store and write
+    /// the files synchronously, so they run on the blocking thread pool.
+    pub async fn run(mut self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            let (exporter, result) = match tokio::task::spawn_blocking(move || {
+                let result = self.export_available(false);
+                (self, result)
+            })
+            .await
+            {
+                Ok(exported) => exported,
+                Err(err) => {
+                    error!("Analytics export stopped: {:?}", err);
+                    return;
+                }
+            };
+            self = exporter;
+            if let Err(err) = result {
+                warn!(
+                    next_checkpoint = self.next_checkpoint,
+                    "Failed to export checkpoints to analytics tables: {err}"
+                );
+            }
+        }
+    }
+}
+
+fn table_file_name(
+    first: CheckpointSequenceNumber,
+    last: CheckpointSequenceNumber,
+    format: AnalyticsFileFormat,
+) -> String {
+    let extension = match format {
+        AnalyticsFileFormat::Csv => "csv",
+        AnalyticsFileFormat::Parquet => "parquet",
+    };
+    format!("{first:020}_{last:020}.{extension}")
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_fake_transaction;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::CheckpointContents;
+
+    fn checkpoint(seq: CheckpointSequenceNumber) -> CheckpointData {
+        let transaction = CertifiedTransaction::new(0, create_fake_transaction());
+        let effects = TransactionEffects {
+            transaction_digest: *transaction.digest(),
+            ..Default::default()
+        };
+        CheckpointData {
+            summary: CheckpointSummary::new(
+                0,
+                seq,
+                &CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty()),
+                None,
+                GasCostSummary::default(),
+                None,
+                [0; 32],
+            ),
+            transactions: vec![transaction],
+            effects: vec![effects],
+        }
+    }
+
+    #[test]
+    fn test_export_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AnalyticsExportConfig {
+            output_dir: dir.path().to_path_buf(),
+            format: AnalyticsFileFormat::Csv,
+            checkpoints_per_file: 2,
+            start_checkpoint: 0,
+        };
+        let source: BTreeMap<_, _> = (0..3).map(|seq| (seq, checkpoint(seq))).collect();
+        let first_digest = *source[&0].transactions[0].digest();
+        let mut exporter =
+            AnalyticsExporter::new(&config, Arc::new(source), &Registry::new()).unwrap();
+
+        // Only the full batch is exported, until partial batches are flushed.
+        assert_eq!(exporter.export_available(false).unwrap(), 2);
+        assert_eq!(exporter.export_available(false).unwrap(), 0);
+        assert_eq!(exporter.export_available(true).unwrap(), 1);
+
+        let transactions = dir.path().join("transactions").join("v1");
+        let file = transactions.join(table_file_name(0, 1, AnalyticsFileFormat::Csv));
+        let mut reader = csv::Reader::from_path(file).unwrap();
+        assert_eq!(&reader.headers().unwrap()[2], "transaction_digest");
+        let records: Vec<_> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 2);
+        // Digests are encoded as in the JSON-RPC API.
+        assert_eq!(&records[0][2], Base64::encode(first_digest));
+        assert!(transactions
+            .join(table_file_name(2, 2, AnalyticsFileFormat::Csv))
+            .exists());
+        // Effects without events have no event rows.
+        assert!(!dir.path().join("events").exists());
+
+        // The export resumes after the last exported checkpoint.
+        let exporter =
+            AnalyticsExporter::new(&config, Arc::new(BTreeMap::new()), &Registry::new()).unwrap();
+        assert_eq!(exporter.next_checkpoint(), 3);
+    }
+
+    #[test]
+    fn test_export_parquet() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AnalyticsExportConfig {
+            output_dir: dir.path().to_path_buf(),
+            format: AnalyticsFileFormat::Parquet,
+            checkpoints_per_file: 2,
+            start_checkpoint: 0,
+        };
+        let source: BTreeMap<_, _> = (0..2).map(|seq| (seq, checkpoint(seq))).collect();
+        let mut exporter =
+            AnalyticsExporter::new(&config, Arc::new(source), &Registry::new()).unwrap();
+        assert_eq!(exporter.export_available(false).unwrap(), 2);
+
+        let file = dir.path().join("effects").join("v1").join(table_file_name(
+            0,
+            1,
+            AnalyticsFileFormat::Parquet,
+        ));
+        let reader = SerializedFileReader::new(fs::File::open(file).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+        let schema = reader.metadata().file_metadata().schema();
+        assert_eq!(schema.get_fields()[1].name(), "transaction_digest");
+    }
+}
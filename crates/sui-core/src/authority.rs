@@ -6,27 +6,32 @@ use std::hash::Hash;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 use anyhow::anyhow;
-use arc_swap::ArcSwap;
+use arc_swap::{ArcSwap, ArcSwapOption};
 
 use chrono::prelude::*;
 use fastcrypto::traits::KeyPair;
 use futures::stream::{self, Stream};
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
+use move_core_types::{
+    language_storage::{ModuleId, TypeTag},
+    resolver::ModuleResolver,
+};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use parking_lot::Mutex;
 use prometheus::{
-    exponential_buckets, register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge,
+    exponential_buckets, register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use tap::TapFallible;
 use tokio::sync::{
@@ -35,10 +40,11 @@ use tokio::sync::{
 };
 use tracing::Instrument;
 use tracing::{debug, error, instrument, warn};
+use typed_store::rocks::DBMap;
 use typed_store::Map;
 
 pub use authority_store::{
-    AuthorityStore, GatewayStore, ResolverWrapper, SuiDataStore, UpdateType,
+    AuthorityStore, GatewayStore, InternalSequenceNumber, ResolverWrapper, SuiDataStore, UpdateType,
 };
 use narwhal_config::{
     Committee as ConsensusCommittee, WorkerCache as ConsensusWorkerCache,
@@ -47,39 +53,57 @@ use narwhal_config::{
 
 use sui_adapter::adapter;
 use sui_config::genesis::Genesis;
-use sui_json_rpc_types::{SuiEventEnvelope, SuiTransactionEffects};
+use sui_json_rpc_types::{
+    SuiCommandGasCost, SuiDryRunTraceResponse, SuiEventEnvelope, SuiExecutionTrace,
+    SuiTransactionEffects,
+};
 use sui_simulator::nondeterministic;
 use sui_storage::{
+    db_metrics::DBMetrics,
     event_store::{EventStore, EventStoreType, StoredEvent},
     node_sync_store::NodeSyncStore,
     write_ahead_log::{DBTxGuard, TxGuard, WriteAheadLog},
-    IndexStore,
+    IndexBatch, IndexStore, IndexedCoin,
+};
+use sui_types::bridge::{
+    BridgeAttestationRequest, BridgeAttestationResponse, BridgeMessage, SignedBridgeAttestation,
 };
-use sui_types::committee::EpochId;
+use sui_types::committee::{CommitteeWithNetAddresses, EpochId};
 use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
+use sui_types::epoch_accounting::EpochAccountingReport;
+use sui_types::execution_limits::{epoch_protocol_version, ExecutionLimits};
 use sui_types::messages_checkpoint::{
     AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointRequest, CheckpointRequestType,
     CheckpointResponse, CheckpointSequenceNumber,
 };
+use sui_types::network_info::{
+    NetworkInfo, NetworkInfoRequest, NetworkInfoResponse, SignedNetworkInfo,
+};
 use sui_types::object::{Owner, PastObjectRead};
-use sui_types::query::TransactionQuery;
+use sui_types::object_change::{changed_objects, ObjectChange};
+use sui_types::query::{AddressActivityRole, TransactionQuery};
+use sui_types::state_accumulator::ObjectInclusionProof;
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::temporary_store::InnerTemporaryStore;
 pub use sui_types::temporary_store::TemporaryStore;
 use sui_types::{
+    balance::{read_changed_objects, BalanceChange},
     base_types::*,
     batch::{TxSequenceNumber, UpdateItem},
+    coin::TreasuryCap,
     committee::Committee,
     crypto::AuthoritySignature,
     error::{SuiError, SuiResult},
     fp_ensure,
+    gas_coin::GAS,
     messages::*,
     object::{Object, ObjectFormatOptions, ObjectRead},
     storage::{BackingPackageStore, DeleteKind},
-    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
+    transaction_checks, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 
 use crate::authority::authority_notifier::TransactionNotifierTicket;
+use crate::authority::authority_store_tables::EPOCH_DB_NAME;
 use crate::checkpoints::ConsensusSender;
 use crate::scoped_counter;
 
@@ -91,10 +115,17 @@ use crate::metrics::TaskUtilizationExt;
 use crate::{
     authority_batch::{BroadcastReceiver, BroadcastSender},
     checkpoints::CheckpointStore,
+    deny_list::{DenyStage, TransactionDenyList},
     event_handler::EventHandler,
+    executed_certificate_cache::{ExecutedCertificateCache, DEFAULT_EXECUTED_CERT_CACHE_SIZE},
+    execution_audit::ExecutionAuditor,
     execution_engine,
+    gas_price_tracker::{CheckpointGasPriceStats, GasPriceTracker},
+    gas_profiler::GasProfiler,
     metrics::start_timer,
+    object_contention::{ObjectContentionTracker, DEFAULT_TRACKED_OBJECTS},
     query_helpers::QueryHelpers,
+    slow_calls::SlowCallLog,
     transaction_input_checker,
     transaction_streamer::TransactionStreamer,
 };
@@ -119,8 +150,16 @@ pub mod authority_store_tables;
 
 pub mod authority_notifier;
 mod authority_store;
+pub mod input_prefetcher;
+pub mod object_cache;
 
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
+/// Maximum length of the chains of objects owned by objects followed when resolving the objects
+/// held, directly or not, by an object.
+pub const MAX_OBJECT_OWNERSHIP_DEPTH: u32 = 16;
+/// Maximum number of versions of an object written after a checkpoint skipped when reading the
+/// object as of that checkpoint.
+pub const MAX_VERSIONS_AFTER_CHECKPOINT: usize = 1_000;
 const BROADCAST_CAPACITY: usize = 10_000;
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
@@ -178,6 +217,7 @@ pub struct AuthorityMetrics {
     post_processing_total_tx_had_event_processed: IntCounter,
     post_processing_total_tx_sent_to_post_processing: IntCounter,
     post_processing_latest_seq_seen: IntGauge,
+    post_processing_last_indexed_checkpoint: IntGauge,
 
     pub num_post_processing_tasks: IntGauge,
     pub num_batch_service_tasks: IntGauge,
@@ -185,6 +225,27 @@ pub struct AuthorityMetrics {
     /// Batch service metrics
     pub(crate) batch_service_total_tx_broadcasted: IntCounter,
     pub(crate) batch_service_latest_seq_broadcasted: IntGauge,
+
+    /// Gas price market metrics
+    checkpoint_gas_price: IntGaugeVec,
+    checkpoint_tx_count: IntGauge,
+    num_pending_certificates: IntGauge,
+
+    /// Object lock table metrics
+    transaction_lock_acquisitions: IntCounter,
+    transaction_lock_conflicts: IntCounter,
+    lock_table_size: IntGauge,
+    lock_table_reset_locks: IntCounter,
+    lock_table_deleted_locks: IntCounter,
+
+    /// Sync watermark metrics
+    current_epoch: IntGauge,
+    highest_known_checkpoint: IntGauge,
+    highest_executed_checkpoint: IntGauge,
+    checkpoint_sync_lag: IntGauge,
+
+    /// Execution limit metrics
+    execution_limits_exceeded: IntCounterVec,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -417,6 +478,12 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            post_processing_last_indexed_checkpoint: register_int_gauge_with_registry!(
+                "post_processing_last_indexed_checkpoint",
+                "Last checkpoint whose transactions are all indexed",
+                registry,
+            )
+            .unwrap(),
             num_post_processing_tasks: register_int_gauge_with_registry!(
                 "num_post_processing_tasks",
                 "Number of post processing tasks currently running.",
@@ -441,6 +508,86 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            checkpoint_gas_price: register_int_gauge_vec_with_registry!(
+                "checkpoint_gas_price",
+                "Gas price percentiles of the transactions in the latest processed checkpoint",
+                &["percentile"],
+                registry,
+            )
+            .unwrap(),
+            checkpoint_tx_count: register_int_gauge_with_registry!(
+                "checkpoint_tx_count",
+                "Number of transactions in the latest processed checkpoint",
+                registry,
+            )
+            .unwrap(),
+            num_pending_certificates: register_int_gauge_with_registry!(
+                "num_pending_certificates",
+                "Number of certificates waiting for execution when the latest checkpoint was processed",
+                registry,
+            )
+            .unwrap(),
+            transaction_lock_acquisitions: register_int_counter_with_registry!(
+                "transaction_lock_acquisitions",
+                "Number of attempts to lock owned input objects to a transaction",
+                registry,
+            )
+            .unwrap(),
+            transaction_lock_conflicts: register_int_counter_with_registry!(
+                "transaction_lock_conflicts",
+                "Number of attempts to lock owned input objects that failed because an object was locked to another transaction",
+                registry,
+            )
+            .unwrap(),
+            lock_table_size: register_int_gauge_with_registry!(
+                "lock_table_size",
+                "Number of entries in the object lock table after the last epoch reset",
+                registry,
+            )
+            .unwrap(),
+            lock_table_reset_locks: register_int_counter_with_registry!(
+                "lock_table_reset_locks",
+                "Number of locks from previous epochs reset at epoch boundaries",
+                registry,
+            )
+            .unwrap(),
+            lock_table_deleted_locks: register_int_counter_with_registry!(
+                "lock_table_deleted_locks",
+                "Number of locks of superseded object versions deleted at epoch boundaries",
+                registry,
+            )
+            .unwrap(),
+            current_epoch: register_int_gauge_with_registry!(
+                "current_epoch",
+                "Epoch of the committee this node is following",
+                registry,
+            )
+            .unwrap(),
+            highest_known_checkpoint: register_int_gauge_with_registry!(
+                "highest_known_certified_checkpoint",
+                "Highest sequence number of a certified checkpoint seen from the network",
+                registry,
+            )
+            .unwrap(),
+            highest_executed_checkpoint: register_int_gauge_with_registry!(
+                "highest_executed_checkpoint",
+                "Highest sequence number of a certified checkpoint whose transactions were executed locally",
+                registry,
+            )
+            .unwrap(),
+            checkpoint_sync_lag: register_int_gauge_with_registry!(
+                "checkpoint_sync_lag",
+                "Number of certified checkpoints known to the network but not executed locally",
+                registry,
+            )
+            .unwrap(),
+            execution_limits_exceeded: register_int_counter_vec_with_registry!(
+                "execution_limits_exceeded",
+                "Number of transactions which failed for exceeding an execution limit, by limit",
+                &["limit"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -454,6 +601,37 @@ impl AuthorityMetrics {
 pub type StableSyncAuthoritySigner =
     Pin<Arc<dyn signature::Signer<AuthoritySignature> + Send + Sync>>;
 
+/// How far this node has caught up with the certified checkpoints of the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncWatermarks {
+    pub epoch: EpochId,
+    pub highest_known_checkpoint: Option<CheckpointSequenceNumber>,
+    pub highest_executed_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl SyncWatermarks {
+    /// Number of known certified checkpoints that were not executed locally yet.
+    pub fn checkpoint_lag(&self) -> u64 {
+        match (
+            self.highest_known_checkpoint,
+            self.highest_executed_checkpoint,
+        ) {
+            (Some(known), Some(executed)) => known.saturating_sub(executed),
+            (Some(known), None) => known + 1,
+            (None, _) => 0,
+        }
+    }
+}
+
+/// What `AuthorityState::reprocess_certificate` did with a certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertificateReprocessing {
+    AlreadyExecuted,
+    Executed,
+    /// This node does not have the certificate, which was queued to be downloaded.
+    Requeued,
+}
+
 pub struct AuthorityState {
     // Fixed size, static, identity of the authority
     /// The name of this authority.
@@ -500,6 +678,41 @@ pub struct AuthorityState {
 
     pub metrics: Arc<AuthorityMetrics>,
 
+    /// Gas prices paid in recently processed checkpoints.
+    pub gas_price_tracker: GasPriceTracker,
+
+    /// Profiles the gas usage of sampled transactions by Move function.
+    pub gas_profiler: Arc<GasProfiler>,
+
+    /// Objects contended by the most transactions.
+    pub object_contention: Arc<ObjectContentionTracker>,
+
+    /// Packages and functions that transactions may not call.
+    pub deny_list: Arc<TransactionDenyList>,
+
+    /// Audits the execution of a sample of the certificates synced with their effects, if set.
+    pub execution_auditor: ArcSwapOption<ExecutionAuditor>,
+
+    /// Recent calls to the JSON-RPC and gRPC servers of the node which exceeded their latency
+    /// threshold.
+    pub slow_calls: Arc<SlowCallLog>,
+
+    /// Responses to the certificates executed most recently, answering retries without reading
+    /// the store.
+    pub executed_certificates: Arc<ExecutedCertificateCache>,
+
+    /// SHA3 digest of the genesis this authority started from.
+    pub genesis_digest: [u8; 32],
+
+    /// The JSON-RPC endpoints of the network this authority signs in network info.
+    pub rpc_endpoints: ArcSwap<Vec<String>>,
+
+    /// Why the intake of new transactions is halted, if it is.
+    intake_halt: ArcSwapOption<String>,
+
+    /// Checkpoints known to the network and executed locally.
+    sync_watermarks: Mutex<SyncWatermarks>,
+
     /// A channel to tell consensus to reconfigure.
     tx_reconfigure_consensus: mpsc::Sender<ReconfigConsensusMessage>,
 }
@@ -552,8 +765,18 @@ impl AuthorityState {
 
         if self.is_halted() {
             // TODO: Do we want to include the new validator set?
-            return Err(SuiError::ValidatorHaltedAtEpochEnd);
+            return Err(self.halted_error());
         }
+        self.check_intake()?;
+
+        // A transaction signed by the sender long ago must not be given a new lease of life.
+        transaction_checks::check_transaction_expiration(
+            &transaction.signed_data.data,
+            self.epoch(),
+        )?;
+
+        self.deny_list
+            .check(&transaction.signed_data.data, DenyStage::Signing)?;
 
         let (_gas_status, input_objects) =
             transaction_input_checker::check_transaction_input(&self.database, &transaction)
@@ -629,7 +852,7 @@ impl AuthorityState {
     ) -> SuiResult {
         let _metrics_guard = start_timer(self.metrics.handle_node_sync_certificate_latency.clone());
         let digest = *certificate.digest();
-        debug!(?digest, "handle_certificate_with_effects");
+        debug!(tx_digest = ?digest, "handle_certificate_with_effects");
         fp_ensure!(
             effects.effects.transaction_digest == digest,
             SuiError::ErrorWhileProcessingConfirmationTransaction {
@@ -647,10 +870,16 @@ impl AuthorityState {
             )?;
         }
 
+        let auditor = self.execution_auditor.load_full();
+        let audit_input = match &auditor {
+            Some(auditor) => auditor.prepare(self, certificate).await,
+            None => None,
+        };
+
         let resp = self
             .process_certificate(tx_guard, certificate, true)
             .await
-            .tap_err(|e| debug!(?digest, "process_certificate failed: {e}"))?;
+            .tap_err(|e| debug!(tx_digest = ?digest, "process_certificate failed: {e}"))?;
 
         let expected_effects_digest = effects.digest();
         let observed_effects_digest = resp.signed_effects.as_ref().map(|e| e.digest());
@@ -663,6 +892,17 @@ impl AuthorityState {
                 input_objects = ?certificate.signed_data.data.input_objects(),
                 "Locally executed effects do not match canonical effects!");
         }
+        if let Some(auditor) = auditor {
+            auditor.report_synced(
+                self.epoch(),
+                certificate,
+                &effects.effects,
+                resp.signed_effects.as_ref().map(|signed| &signed.effects),
+            );
+            if let Some(audit_input) = audit_input {
+                auditor.spawn_audit(self, audit_input, effects.effects.clone());
+            }
+        }
         Ok(())
     }
 
@@ -699,6 +939,10 @@ impl AuthorityState {
         let tx_digest = certificate.digest();
         debug!(?tx_digest, "handle_confirmation_transaction");
 
+        if let Some(response) = self.executed_certificates.get(tx_digest) {
+            return Ok(response);
+        }
+
         // This acquires a lock on the tx digest to prevent multiple concurrent executions of the
         // same tx. While we don't need this for safety (tx sequencing is ultimately atomic), it is
         // very common to receive the same tx multiple times simultaneously due to gossip, so we
@@ -725,9 +969,13 @@ impl AuthorityState {
             return Err(SuiError::SharedObjectLockNotSetError);
         }
 
-        self.process_certificate(tx_guard, certificate, bypass_validator_halt)
+        let response = self
+            .process_certificate(tx_guard, certificate, bypass_validator_halt)
             .await
-            .tap_err(|e| debug!(?tx_digest, "process_certificate failed: {e}"))
+            .tap_err(|e| debug!(?tx_digest, "process_certificate failed: {e}"))?;
+        self.executed_certificates
+            .insert(*tx_digest, response.clone());
+        Ok(response)
     }
 
     #[instrument(level = "trace", skip_all)]
@@ -808,7 +1056,7 @@ impl AuthorityState {
 
         if self.is_halted() && !bypass_validator_halt {
             tx_guard.release();
-            return Err(SuiError::ValidatorHaltedAtEpochEnd);
+            return Err(self.halted_error());
         }
 
         // Check the certificate signatures.
@@ -824,15 +1072,17 @@ impl AuthorityState {
         // non-transient (transaction input is invalid, move vm errors). However, all errors from
         // this function occur before we have written anything to the db, so we commit the tx
         // guard and rely on the client to retry the tx (if it was transient).
-        let (inner_temporary_store, signed_effects) =
-            match self.prepare_certificate(certificate, digest).await {
-                Err(e) => {
-                    debug!(name = ?self.name, ?digest, "Error preparing transaction: {e}");
-                    tx_guard.release();
-                    return Err(e);
-                }
-                Ok(res) => res,
-            };
+        let (inner_temporary_store, signed_effects) = match self
+            .prepare_certificate(certificate, digest)
+            .await
+        {
+            Err(e) => {
+                debug!(authority = ?self.name, tx_digest = ?digest, "Error preparing transaction: {e}");
+                tx_guard.release();
+                return Err(e);
+            }
+            Ok(res) => res,
+        };
 
         let input_object_count = inner_temporary_store.objects.len();
         let shared_object_count = signed_effects.effects.shared_objects.len();
@@ -849,14 +1099,17 @@ impl AuthorityState {
             )
             .await
         {
-            if matches!(err, SuiError::ValidatorHaltedAtEpochEnd) {
+            if matches!(
+                err,
+                SuiError::ValidatorHalted { .. } | SuiError::ValidatorShuttingDown
+            ) {
                 debug!(
                     ?digest,
                     "validator halted and this cert will never be committed"
                 );
                 tx_guard.release();
             } else {
-                error!(?digest, "commit_certificate failed: {}", err);
+                error!(tx_digest = ?digest, "commit_certificate failed: {}", err);
             }
             return Err(err);
         }
@@ -905,8 +1158,12 @@ impl AuthorityState {
         transaction_digest: TransactionDigest,
     ) -> SuiResult<(InnerTemporaryStore, SignedTransactionEffects)> {
         let _metrics_guard = start_timer(self.metrics.prepare_certificate_latency.clone());
-        let (gas_status, input_objects) =
+        let (mut gas_status, input_objects) =
             transaction_input_checker::check_certificate_input(&self.database, certificate).await?;
+        let gas_profile = self.gas_profiler.sample();
+        if let Some(gas_profile) = &gas_profile {
+            gas_status.set_gas_observer(gas_profile.clone());
+        }
 
         // At this point we need to check if any shared objects need locks,
         // and whether they have them.
@@ -931,7 +1188,7 @@ impl AuthorityState {
         let transaction_dependencies = input_objects.transaction_dependencies();
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
-        let (inner_temp_store, effects, _execution_error) =
+        let (inner_temp_store, effects, execution_error, _) =
             execution_engine::execute_transaction_to_effects(
                 shared_object_refs,
                 temporary_store,
@@ -941,8 +1198,21 @@ impl AuthorityState {
                 &self.move_vm,
                 &self._native_functions,
                 gas_status,
+                &self.execution_limits(),
                 self.epoch(),
+                false,
             );
+        if let Some(ExecutionFailureStatus::ExecutionLimitExceeded(exceeded)) =
+            execution_error.as_ref().map(|e| e.kind())
+        {
+            self.metrics
+                .execution_limits_exceeded
+                .with_label_values(&[exceeded.limit.as_str()])
+                .inc();
+        }
+        if let Some(gas_profile) = gas_profile {
+            self.gas_profiler.record(&gas_profile);
+        }
 
         // TODO: Distribute gas charge and rebate, which can be retrieved from effects.
         let signed_effects = effects.to_sign_effects(self.epoch(), &self.name, &*self.secret);
@@ -954,6 +1224,28 @@ impl AuthorityState {
         transaction: &Transaction,
         transaction_digest: TransactionDigest,
     ) -> Result<SuiTransactionEffects, anyhow::Error> {
+        Ok(self
+            .dry_run(transaction, transaction_digest, false)
+            .await?
+            .effects)
+    }
+
+    /// Dry runs `transaction`, tracing where its Move calls stopped if execution failed.
+    pub async fn dry_run_transaction_with_trace(
+        &self,
+        transaction: &Transaction,
+        transaction_digest: TransactionDigest,
+    ) -> Result<SuiDryRunTraceResponse, anyhow::Error> {
+        self.dry_run(transaction, transaction_digest, true).await
+    }
+
+    /// Dry runs `transaction`, only building the trace of a failed execution if `trace` is set.
+    async fn dry_run(
+        &self,
+        transaction: &Transaction,
+        transaction_digest: TransactionDigest,
+        trace: bool,
+    ) -> Result<SuiDryRunTraceResponse, anyhow::Error> {
         transaction.verify()?;
         let (gas_status, input_objects) =
             transaction_input_checker::check_transaction_input(&self.database, transaction).await?;
@@ -962,7 +1254,7 @@ impl AuthorityState {
         let transaction_dependencies = input_objects.transaction_dependencies();
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
-        let (_inner_temp_store, effects, _execution_error) =
+        let (_inner_temp_store, effects, execution_error, command_computation_costs) =
             execution_engine::execute_transaction_to_effects(
                 shared_object_refs,
                 temporary_store,
@@ -972,9 +1264,33 @@ impl AuthorityState {
                 &self.move_vm,
                 &self._native_functions,
                 gas_status,
+                &self.execution_limits(),
                 self.epoch(),
+                true,
             );
-        SuiTransactionEffects::try_from(effects, self.module_cache.as_ref())
+        let mut effects = SuiTransactionEffects::try_from(effects, self.module_cache.as_ref())?;
+        effects.command_gas_costs = SuiCommandGasCost::for_commands(
+            &transaction.signed_data.data,
+            &command_computation_costs,
+        );
+        Ok(SuiDryRunTraceResponse {
+            effects,
+            trace: execution_error
+                .filter(|_| trace)
+                .map(|error| {
+                    SuiExecutionTrace::new(
+                        &error,
+                        &transaction.signed_data.data,
+                        self.module_cache.as_ref(),
+                    )
+                })
+                .transpose()?,
+        })
+    }
+
+    /// The execution limits of the current epoch.
+    fn execution_limits(&self) -> ExecutionLimits {
+        ExecutionLimits::for_epoch(self.epoch())
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
@@ -996,20 +1312,40 @@ impl AuthorityState {
     #[instrument(level = "debug", skip_all, fields(seq = ?seq, tx_digest =? digest), err)]
     fn index_tx(
         &self,
-        indexes: &IndexStore,
+        batch: &mut IndexBatch,
         seq: TxSequenceNumber,
         digest: &TransactionDigest,
         cert: &CertifiedTransaction,
         effects: &SignedTransactionEffects,
         timestamp_ms: u64,
     ) -> SuiResult {
-        indexes.index_tx(
-            cert.sender_address(),
-            cert.signed_data
-                .data
-                .input_objects()?
+        let objects = read_changed_objects(&effects.effects, |id, version| {
+            self.database.get_object_by_key(id, version)
+        })?;
+        let balance_changes = BalanceChange::from_objects(
+            objects
                 .iter()
-                .map(|o| o.object_id()),
+                .map(|(before, after)| (before.as_ref(), after.as_ref())),
+        )?;
+        let indexed_coin = |object: &Option<Object>| match object {
+            Some(object) => IndexedCoin::from_object(object),
+            None => Ok(None),
+        };
+        let coin_changes = objects
+            .iter()
+            .map(|(before, after)| Ok((indexed_coin(before)?, indexed_coin(after)?)))
+            .collect::<SuiResult<Vec<_>>>()?;
+        let treasury_caps: Vec<_> = objects
+            .iter()
+            .filter_map(|(_, after)| {
+                let object = after.as_ref()?;
+                Some((TreasuryCap::coin_type_of(object)?, object.id()))
+            })
+            .collect();
+        let active_inputs = cert.signed_data.data.input_objects()?;
+        batch.add_tx(
+            cert.sender_address(),
+            active_inputs.iter().map(|o| o.object_id()),
             effects
                 .effects
                 .all_mutated()
@@ -1019,12 +1355,151 @@ impl AuthorityState {
                 .move_calls()
                 .iter()
                 .map(|mc| (mc.package.0, mc.module.clone(), mc.function.clone())),
+            balance_changes,
+            effects.effects.gas_object.1,
             seq,
-            digest,
+            &ExecutionDigests::new(*digest, *effects.digest()),
             timestamp_ms,
+        );
+        batch.add_coin_changes(coin_changes);
+        batch.add_treasury_caps(treasury_caps);
+        Ok(())
+    }
+
+    /// Returns the last checkpoint whose transactions are all indexed once `batch` is written,
+    /// starting from `checkpoint`, the last one whose transactions were already indexed.
+    fn last_indexed_checkpoint(
+        &self,
+        indexes: &IndexStore,
+        batch: &IndexBatch,
+        mut checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        loop {
+            let next = checkpoint.map_or(0, |seq| seq + 1);
+            let contents = match self
+                .checkpoints
+                .lock()
+                .tables
+                .checkpoint_contents
+                .get(&next)?
+            {
+                Some(contents) => contents,
+                None => return Ok(checkpoint),
+            };
+            for digests in contents.iter() {
+                if !batch.contains(&digests.transaction)
+                    && !indexes.is_indexed(&digests.transaction)?
+                {
+                    return Ok(checkpoint);
+                }
+            }
+            checkpoint = Some(next);
+        }
+    }
+
+    /// Indexes up to `max_transactions` executed transactions following the watermark of the
+    /// indexes, and writes them in a single batch along with the new watermark. Returns the
+    /// number of transactions indexed.
+    pub async fn index_executed_transactions(
+        &self,
+        indexes: &IndexStore,
+        max_transactions: usize,
+    ) -> SuiResult<usize> {
+        let watermark = indexes.watermark()?;
+        // Transactions below the low watermark of the notifier are executed without gaps.
+        let last_safe = self.batch_notifier.low_watermark();
+        let executed: Vec<_> = self
+            .database
+            .perpetual_tables
+            .executed_sequence
+            .iter()
+            .skip_to(&watermark.next_sequence)?
+            .take_while(|(seq, _)| *seq < last_safe)
+            .take(max_transactions)
+            .collect();
+
+        let mut batch = IndexBatch::new(watermark);
+        let timestamp_ms = Self::unixtime_now_ms();
+        for (seq, digests) in &executed {
+            let digest = &digests.transaction;
+            let indexed = match self.make_transaction_info(digest).await? {
+                TransactionInfoResponse {
+                    certified_transaction: Some(cert),
+                    signed_effects: Some(effects),
+                    ..
+                } => self.index_tx(&mut batch, *seq, digest, &cert, &effects, timestamp_ms),
+                _ => Err(SuiError::CertificateNotfound {
+                    certificate_digest: *digest,
+                }),
+            };
+            if let Err(e) = indexed {
+                warn!(tx_digest=?digest, "Couldn't index tx: {e}");
+                batch.skip_tx(*seq, digest);
+            }
+        }
+
+        let checkpoint = self.last_indexed_checkpoint(indexes, &batch, watermark.checkpoint)?;
+        if batch.is_empty() && checkpoint == watermark.checkpoint {
+            return Ok(0);
+        }
+        batch.set_checkpoint(checkpoint);
+        indexes.write_batch(batch)?;
+
+        self.metrics
+            .post_processing_total_tx_indexed
+            .inc_by(executed.len() as u64);
+        if let Some(checkpoint) = checkpoint {
+            self.metrics
+                .post_processing_last_indexed_checkpoint
+                .set(checkpoint as i64);
+        }
+        Ok(executed.len())
+    }
+
+    /// Builds the coin index from the live coins unless it was built already, after indexing
+    /// the executed transactions. Must run before the indexing process starts, but transactions
+    /// may execute meanwhile: those executed after the watermark are read by the live coins and
+    /// indexed again once the indexing process starts, which sets each coin they changed to its
+    /// latest version. Returns the number of coins indexed.
+    pub async fn build_coin_index(&self, max_batch_transactions: usize) -> SuiResult<usize> {
+        let indexes = self.get_indexes()?;
+        if indexes.is_coin_index_built()? {
+            return Ok(0);
+        }
+        while self
+            .index_executed_transactions(&indexes, max_batch_transactions)
+            .await?
+            > 0
+        {}
+        indexes.build_coin_index(
+            self.database.iter_live_coins(),
+            self.database.iter_live_treasury_caps(),
         )
     }
 
+    /// Indexes executed transactions in batches of up to `max_batch_transactions`, resuming from
+    /// the watermark of the indexes, and polls every `poll_interval` once caught up: a transaction
+    /// may only show up in the indexes `poll_interval` after it is executed.
+    pub async fn run_indexing_process(
+        &self,
+        max_batch_transactions: usize,
+        poll_interval: Duration,
+    ) -> SuiResult {
+        let indexes = self.get_indexes()?;
+        indexes.initialize_watermark()?;
+        loop {
+            match self
+                .index_executed_transactions(&indexes, max_batch_transactions)
+                .await
+            {
+                Ok(indexed) if indexed == max_batch_transactions => continue,
+                Ok(_) => (),
+                Err(e) => warn!("Couldn't index executed transactions: {e}"),
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     #[instrument(level = "debug", skip_all, fields(seq=?seq, tx_digest=?digest), err)]
     async fn post_process_one_tx(
         &self,
@@ -1048,14 +1523,6 @@ impl AuthorityState {
 
         let timestamp_ms = Self::unixtime_now_ms();
 
-        // Index tx
-        if let Some(indexes) = &self.indexes {
-            let _ = self
-                .index_tx(indexes.as_ref(), seq, digest, &cert, &effects, timestamp_ms)
-                .tap_ok(|_| self.metrics.post_processing_total_tx_indexed.inc())
-                .tap_err(|e| warn!(tx_digest=?digest, "Post processing - Couldn't index tx: {e}"));
-        }
-
         // Stream transaction
         if let Some(transaction_streamer) = &self.transaction_streamer {
             transaction_streamer.enqueue((cert, effects.clone())).await;
@@ -1102,7 +1569,7 @@ impl AuthorityState {
                             .post_processing_total_tx_sent_to_post_processing
                             .inc();
                         if let Err(e) = self.post_process_one_tx(seq, &digest).await {
-                            warn!(?digest, "Couldn't process tx: {e}");
+                            warn!(tx_digest = ?digest, "Couldn't process tx: {e}");
                         }
                     }
                 }
@@ -1140,6 +1607,42 @@ impl AuthorityState {
             .await
     }
 
+    pub async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        let num_digests = request.transaction_digests.len();
+        fp_ensure!(
+            num_digests <= MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS,
+            SuiError::TooManyTransactionDigests {
+                num_digests,
+                max_digests: MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS,
+            }
+        );
+        let mut responses = Vec::with_capacity(num_digests);
+        let mut response_size = 0;
+        for digest in &request.transaction_digests {
+            let info = self.make_transaction_info(digest).await?;
+            let info_size = bincode::serialized_size(&info)
+                .expect("Serializing a transaction info response cannot fail");
+            response_size += info_size as usize;
+            // Leave the remaining transactions out of a response that would get too large, but
+            // always answer with the first one so that the requester makes progress.
+            if response_size > MAX_TRANSACTIONS_INFO_RESPONSE_SIZE && !responses.is_empty() {
+                break;
+            }
+            responses.push(info);
+        }
+        Ok(TransactionsInfoResponse { responses })
+    }
+
+    pub async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        self.database.get_effects_info(&request.effects_digest)
+    }
+
     pub async fn handle_account_info_request(
         &self,
         request: AccountInfoRequest,
@@ -1151,6 +1654,7 @@ impl AuthorityState {
         &self,
         request: ObjectInfoRequest,
     ) -> Result<ObjectInfoResponse, SuiError> {
+        let latest_content = request.latest_content();
         let ref_and_digest = match request.request_kind {
             ObjectInfoRequestKind::PastObjectInfo(seq)
             | ObjectInfoRequestKind::PastObjectInfoDebug(seq, _) => {
@@ -1159,16 +1663,20 @@ impl AuthorityState {
                     .await?
                     .next()
             }
-            ObjectInfoRequestKind::LatestObjectInfo(_) => {
+            ObjectInfoRequestKind::LatestObjectInfo(_)
+            | ObjectInfoRequestKind::LatestObjectContent(_) => {
                 // Or get the latest object_reference and transaction entry.
                 self.get_latest_parent_entry(request.object_id).await?
             }
         };
+        let with_lock_and_certificate =
+            latest_content.map_or(true, |content| content.with_lock_and_certificate());
 
         let (requested_object_reference, parent_certificate) = match ref_and_digest {
             Some((object_ref, transaction_digest)) => (
                 Some(object_ref),
-                if transaction_digest == TransactionDigest::genesis() {
+                if transaction_digest == TransactionDigest::genesis() || !with_lock_and_certificate
+                {
                     None
                 } else {
                     // Get the cert from the transaction digest
@@ -1184,17 +1692,23 @@ impl AuthorityState {
 
         // Return the latest version of the object and the current lock if any, if requested.
         let object_and_lock = match request.request_kind {
-            ObjectInfoRequestKind::LatestObjectInfo(request_layout) => {
+            ObjectInfoRequestKind::LatestObjectInfo(_)
+            | ObjectInfoRequestKind::LatestObjectContent(_) => {
+                // Always set for requests of the latest version.
+                let content = latest_content.unwrap_or(ObjectContentOptions::BcsOnly);
                 match self.get_object(&request.object_id).await {
                     Ok(Some(object)) => {
-                        let lock = if !object.is_owned_or_quasi_shared() {
-                            // Unowned objects have no locks.
+                        let lock = if !object.is_owned_or_quasi_shared()
+                            || !content.with_lock_and_certificate()
+                        {
+                            // Unowned objects have no locks, and the lock of others is only
+                            // returned if requested.
                             None
                         } else {
                             self.get_transaction_lock(&object.compute_object_reference())
                                 .await?
                         };
-                        let layout = match request_layout {
+                        let layout = match content.layout_format() {
                             Some(format) => {
                                 object.get_layout(format, self.module_cache.as_ref())?
                             }
@@ -1329,6 +1843,9 @@ impl AuthorityState {
             CheckpointRequestType::CheckpointProposal => {
                 checkpoint_store.handle_proposal(request.detail)
             }
+            CheckpointRequestType::TransactionCheckpoint(digests) => {
+                checkpoint_store.handle_transaction_checkpoint(digests, request.detail)
+            }
         }
     }
 
@@ -1417,6 +1934,73 @@ impl AuthorityState {
         })
     }
 
+    /// Sign a bridge message for an event emitted by a transaction this authority executed.
+    /// Executed certificates are final, so a quorum of such signatures attests that the event
+    /// happened on Sui.
+    pub fn handle_bridge_attestation_request(
+        &self,
+        request: &BridgeAttestationRequest,
+    ) -> SuiResult<BridgeAttestationResponse> {
+        let effects = self.database.get_effects(&request.tx_digest)?;
+        let message = BridgeMessage::from_effects(&effects, request.event_seq)?;
+        Ok(BridgeAttestationResponse {
+            attestation: SignedBridgeAttestation::new(
+                self.epoch(),
+                message,
+                self.name,
+                &*self.secret,
+            ),
+        })
+    }
+
+    /// Sign the network info of the current epoch, which clients check the nodes they connect
+    /// to against.
+    pub fn handle_network_info_request(
+        &self,
+        _request: &NetworkInfoRequest,
+    ) -> SuiResult<NetworkInfoResponse> {
+        // Validators sign the protocol version of the epoch, which they agree on even while they
+        // run builds speaking different versions.
+        let committee = self.committee.load();
+        let info = NetworkInfo::new(
+            self.genesis_digest,
+            &committee,
+            epoch_protocol_version(committee.epoch),
+            self.rpc_endpoints.load().to_vec(),
+        );
+        Ok(NetworkInfoResponse {
+            info: SignedNetworkInfo::new(info, self.name, &*self.secret),
+        })
+    }
+
+    /// The certified last checkpoint of each epoch from `from_epoch` until `to_epoch`, excluded,
+    /// which announce the committee of the next epoch.
+    pub fn get_epoch_change_checkpoints(
+        &self,
+        from_epoch: EpochId,
+        to_epoch: EpochId,
+    ) -> SuiResult<Vec<CertifiedCheckpointSummary>> {
+        let checkpoints = self.checkpoints.lock();
+        (from_epoch..to_epoch)
+            .map(|epoch| {
+                checkpoints
+                    .get_checkpoints_of_epoch(epoch)
+                    .into_iter()
+                    .find_map(|checkpoint| match checkpoint {
+                        AuthenticatedCheckpoint::Certified(checkpoint)
+                            if checkpoint.summary.next_epoch_committee.is_some() =>
+                        {
+                            Some(checkpoint)
+                        }
+                        _ => None,
+                    })
+                    .ok_or_else(|| SuiError::CheckpointingError {
+                        error: format!("missing the certified last checkpoint of epoch {epoch}"),
+                    })
+            })
+            .collect()
+    }
+
     // TODO: This function takes both committee and genesis as parameter.
     // Technically genesis already contains committee information. Could consider merging them.
     pub async fn new(
@@ -1450,6 +2034,18 @@ impl AuthorityState {
                 .await
                 .expect("Cannot bulk insert genesis objects");
         }
+        // The state of the first checkpoint builds on the genesis objects. They are recorded
+        // whatever the state of the database, as the checkpoint store may not have them yet, e.g.
+        // when it was created after the object store.
+        let genesis_refs: Vec<_> = genesis
+            .objects()
+            .iter()
+            .map(|o| o.compute_object_reference())
+            .collect();
+        checkpoints
+            .lock()
+            .set_genesis_state(&genesis_refs)
+            .expect("Cannot store genesis state");
 
         let committee = committee_store.get_latest_committee();
 
@@ -1478,8 +2074,26 @@ impl AuthorityState {
             ),
             consensus_guardrail: AtomicUsize::new(0),
             metrics: Arc::new(AuthorityMetrics::new(prometheus_registry)),
+            gas_price_tracker: GasPriceTracker::default(),
+            gas_profiler: Arc::new(GasProfiler::new(prometheus_registry)),
+            object_contention: Arc::new(ObjectContentionTracker::new(
+                DEFAULT_TRACKED_OBJECTS,
+                prometheus_registry,
+            )),
+            deny_list: Arc::new(TransactionDenyList::new(prometheus_registry)),
+            execution_auditor: ArcSwapOption::empty(),
+            slow_calls: Arc::new(SlowCallLog::new(prometheus_registry)),
+            executed_certificates: Arc::new(ExecutedCertificateCache::new(
+                DEFAULT_EXECUTED_CERT_CACHE_SIZE,
+                prometheus_registry,
+            )),
+            genesis_digest: genesis.sha3(),
+            rpc_endpoints: ArcSwap::from_pointee(vec![]),
+            intake_halt: ArcSwapOption::empty(),
+            sync_watermarks: Mutex::new(SyncWatermarks::default()),
             tx_reconfigure_consensus,
         };
+        state.metrics.current_epoch.set(state.epoch() as i64);
 
         // Process tx recovery log first, so that the batch and checkpoint recovery (below)
         // don't observe partially-committed txes.
@@ -1624,7 +2238,7 @@ impl AuthorityState {
             limit -= 1;
             if let Some((cert, tx_guard)) = self.database.wal.read_one_recoverable_tx().await? {
                 let digest = tx_guard.tx_id();
-                debug!(?digest, "replaying failed cert from log");
+                debug!(tx_digest = ?digest, "replaying failed cert from log");
 
                 if tx_guard.retry_num() >= MAX_TX_RECOVERY_RETRY {
                     // This tx will be only partially executed, however the store will be in a safe
@@ -1643,7 +2257,7 @@ impl AuthorityState {
                 }
 
                 if let Err(e) = self.process_certificate(tx_guard, &cert, false).await {
-                    warn!(?digest, "Failed to process in-progress certificate: {e}");
+                    warn!(tx_digest = ?digest, "Failed to process in-progress certificate: {e}");
                 }
             } else {
                 break;
@@ -1657,32 +2271,147 @@ impl AuthorityState {
         self.checkpoints.clone()
     }
 
-    pub(crate) fn update_committee(&self, new_committee: Committee) -> SuiResult {
+    /// Record the gas prices paid by the transactions of a checkpoint that has just been signed
+    /// or synced, together with the current execution queue depth.
+    pub(crate) fn record_checkpoint_gas_prices<'a>(
+        &self,
+        seq: CheckpointSequenceNumber,
+        transactions: impl Iterator<Item = &'a ExecutionDigests>,
+    ) -> SuiResult {
+        let digests: Vec<_> = transactions.map(|d| d.transaction).collect();
+        let gas_prices = self
+            .database
+            .multi_get_certified_transaction(&digests)?
+            .into_iter()
+            .flatten()
+            .filter(|cert| !cert.is_system_tx())
+            .map(|cert| cert.signed_data.data.gas_price)
+            .collect();
+        let pending_certificates = self.database.pending_count();
+        let stats = CheckpointGasPriceStats::new(seq, gas_prices, pending_certificates);
+
+        for (percentile, value) in [
+            ("min", stats.min),
+            ("p25", stats.p25),
+            ("p50", stats.p50),
+            ("p75", stats.p75),
+            ("p90", stats.p90),
+            ("max", stats.max),
+        ] {
+            self.metrics
+                .checkpoint_gas_price
+                .with_label_values(&[percentile])
+                .set(value as i64);
+        }
+        self.metrics.checkpoint_tx_count.set(stats.tx_count as i64);
+        self.metrics
+            .num_pending_certificates
+            .set(stats.pending_certificates as i64);
+
+        self.gas_price_tracker.record(stats);
+        Ok(())
+    }
+
+    pub(crate) fn update_committee(&self, new_committee: CommitteeWithNetAddresses) -> SuiResult {
         // TODO: It's likely safer to do the following operations atomically, in case this function
         // gets called from different threads. It cannot happen today, but worth the caution.
         fp_ensure!(
-            self.epoch() + 1 == new_committee.epoch,
+            self.epoch() + 1 == new_committee.committee.epoch,
             SuiError::from("Invalid new epoch to sign and update")
         );
 
-        self.committee_store.insert_new_committee(&new_committee)?;
+        self.committee_store
+            .insert_new_committee_with_net_addresses(&new_committee)?;
+        let new_committee = new_committee.committee;
         // TODO: Do we want to make it possible to subscribe to committee changes?
+        self.metrics.current_epoch.set(new_committee.epoch as i64);
         self.committee.swap(Arc::new(new_committee));
+        // The cached responses carry signatures of the previous epoch.
+        self.executed_certificates.clear();
+        Ok(())
+    }
+
+    /// Revert the effects of the executed transaction `tx_digest`, e.g. because it was not
+    /// included in a checkpoint of its epoch.
+    pub fn revert_state_update(&self, tx_digest: &TransactionDigest) -> SuiResult {
+        self.database.revert_state_update(tx_digest)?;
+        self.executed_certificates.remove(tx_digest);
         Ok(())
     }
 
+    /// Record the sequence number of a certified checkpoint seen from the network.
+    pub(crate) fn observe_highest_known_checkpoint(&self, seq: CheckpointSequenceNumber) {
+        let mut watermarks = self.sync_watermarks.lock();
+        if watermarks.highest_known_checkpoint < Some(seq) {
+            watermarks.highest_known_checkpoint = Some(seq);
+            self.metrics.highest_known_checkpoint.set(seq as i64);
+        }
+        self.update_checkpoint_sync_lag(&watermarks);
+    }
+
+    /// Record the sequence number of a certified checkpoint whose transactions were executed.
+    pub(crate) fn observe_executed_checkpoint(&self, seq: CheckpointSequenceNumber) {
+        let mut watermarks = self.sync_watermarks.lock();
+        if watermarks.highest_executed_checkpoint < Some(seq) {
+            watermarks.highest_executed_checkpoint = Some(seq);
+            self.metrics.highest_executed_checkpoint.set(seq as i64);
+        }
+        // An executed checkpoint is known too.
+        if watermarks.highest_known_checkpoint < Some(seq) {
+            watermarks.highest_known_checkpoint = Some(seq);
+            self.metrics.highest_known_checkpoint.set(seq as i64);
+        }
+        self.update_checkpoint_sync_lag(&watermarks);
+    }
+
+    fn update_checkpoint_sync_lag(&self, watermarks: &SyncWatermarks) {
+        self.metrics
+            .checkpoint_sync_lag
+            .set(watermarks.checkpoint_lag() as i64);
+    }
+
+    pub fn sync_watermarks(&self) -> SyncWatermarks {
+        SyncWatermarks {
+            epoch: self.epoch(),
+            ..*self.sync_watermarks.lock()
+        }
+    }
+
     pub(crate) fn is_halted(&self) -> bool {
         self.batch_notifier.is_paused()
     }
 
+    /// Stops taking transactions and certificates until the committee of the next epoch runs.
     pub(crate) fn halt_validator(&self) {
-        self.batch_notifier.pause();
+        self.batch_notifier.pause(self.epoch() + 1);
+    }
+
+    /// The error transactions and certificates are rejected with while halted.
+    pub(crate) fn halted_error(&self) -> SuiError {
+        self.batch_notifier.paused_error()
     }
 
     pub(crate) fn unhalt_validator(&self) {
         self.batch_notifier.unpause();
     }
 
+    /// Rejects new transactions, giving `reason`, until called again with None. Certificates
+    /// are still executed, unlike when the validator is halted.
+    pub fn set_intake_halt(&self, reason: Option<String>) {
+        self.intake_halt.store(reason.map(Arc::new));
+    }
+
+    /// Fails with a retryable error while the intake of new transactions is halted.
+    pub fn check_intake(&self) -> SuiResult {
+        match &*self.intake_halt.load() {
+            Some(reason) => Err(SuiError::ValidatorOverloaded {
+                reason: format!("Intake of transactions is halted: {reason}"),
+                retry_after_ms: None,
+            }),
+            None => Ok(()),
+        }
+    }
+
     pub fn db(&self) -> Arc<AuthorityStore> {
         self.database.clone()
     }
@@ -1707,6 +2436,85 @@ impl AuthorityState {
         self.database.get_sui_system_state_object()
     }
 
+    /// The gas fees and reward distribution of the given epoch, known once the epoch has ended.
+    pub fn get_epoch_accounting_report(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<EpochAccountingReport>> {
+        self.database.get_epoch_accounting_report(epoch)
+    }
+
+    /// The certificates queued for execution in this epoch, by queue sequence number. A
+    /// certificate stays queued until it is executed, e.g. once its input objects are available.
+    pub fn get_pending_certificates(
+        &self,
+    ) -> SuiResult<Vec<(InternalSequenceNumber, TransactionDigest)>> {
+        self.database.get_pending_digests()
+    }
+
+    /// The number of certificates queued for execution, without reading the queue.
+    pub fn pending_certificate_count(&self) -> u64 {
+        self.database.pending_count()
+    }
+
+    /// The number of certificates taken off the queue since the node started, e.g. once
+    /// executed. Unlike the number of executed transactions, it does not move with the
+    /// certificates executed directly, without being queued.
+    pub fn dequeued_certificate_count(&self) -> u64 {
+        self.database.dequeued_count()
+    }
+
+    /// Execute a queued certificate now instead of waiting for the execution driver. If this node
+    /// does not have the certificate, it is queued again to be downloaded.
+    pub async fn reprocess_certificate(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<CertificateReprocessing> {
+        if self.is_tx_already_executed(digest)? {
+            return Ok(CertificateReprocessing::AlreadyExecuted);
+        }
+        let certificate = match self.node_sync_store.get_cert(self.epoch(), digest)? {
+            Some(certificate) => Some(certificate),
+            None => self.database.read_certificate(digest)?,
+        };
+        match certificate {
+            Some(certificate) => {
+                self.handle_certificate(&certificate).await?;
+                Ok(CertificateReprocessing::Executed)
+            }
+            None => {
+                self.add_pending_certificates(vec![(*digest, None)])?;
+                Ok(CertificateReprocessing::Requeued)
+            }
+        }
+    }
+
+    /// Export the object contention metrics, after dropping from its queues the transactions
+    /// executed without their execution being recorded, e.g. when they were executed while
+    /// being recorded as sequenced.
+    pub fn export_object_contention(&self) -> SuiResult {
+        for digest in self.object_contention.queued_transactions() {
+            if self.database.effects_exists(&digest)? {
+                self.object_contention.record_executed(&digest);
+            }
+        }
+        self.object_contention.export_metrics();
+        Ok(())
+    }
+
+    /// The metrics of the databases of the node, in which they are registered.
+    pub fn db_metrics(&self) -> &Arc<DBMetrics> {
+        self.database.db_metrics()
+    }
+
+    /// The rocksdb properties of the tables of the current epoch, by table.
+    pub fn epoch_table_properties(&self) -> SuiResult<Vec<(String, Vec<(&'static str, u64)>)>> {
+        self.database
+            .db_metrics()
+            .cf_properties(EPOCH_DB_NAME)
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))
+    }
+
     pub async fn get_object_read(&self, object_id: &ObjectID) -> Result<ObjectRead, SuiError> {
         match self.database.get_latest_parent_entry(*object_id)? {
             None => Ok(ObjectRead::NotExists(*object_id)),
@@ -1797,6 +2605,47 @@ impl AuthorityState {
         self.database.get_owner_objects(owner)
     }
 
+    /// Returns at most `limit` of the objects owned by `owner`, without reading the others.
+    pub fn get_owner_objects_bounded(
+        &self,
+        owner: Owner,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        self.database.get_owner_objects_bounded(owner, limit)
+    }
+
+    /// Return the objects owned by `object_id`, and the objects they own in turn, up to
+    /// `max_depth` levels down and `max_objects` objects, with the level at which each was found
+    /// (1 for the objects owned by `object_id`). Objects are listed level by level. Also returns
+    /// whether the result was truncated, i.e. some objects were left out by either bound.
+    pub fn get_objects_owned_by_object_recursive(
+        &self,
+        object_id: ObjectID,
+        max_depth: u32,
+        max_objects: usize,
+    ) -> SuiResult<(Vec<(ObjectInfo, u32)>, bool)> {
+        let mut objects = Vec::new();
+        let mut visited = HashSet::from([object_id]);
+        let mut pending = VecDeque::from([(object_id, 0)]);
+        while let Some((owner_id, depth)) = pending.pop_front() {
+            let children = self.get_owner_objects(Owner::ObjectOwner(owner_id.into()))?;
+            if children.is_empty() {
+                continue;
+            }
+            if depth == max_depth || objects.len() + children.len() > max_objects {
+                return Ok((objects, true));
+            }
+            for child in children {
+                // Ownership cannot form cycles, but the index is read without a snapshot.
+                if visited.insert(child.object_id) {
+                    pending.push_back((child.object_id, depth + 1));
+                    objects.push((child, depth + 1));
+                }
+            }
+        }
+        Ok((objects, false))
+    }
+
     pub fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.database)
     }
@@ -1895,6 +2744,323 @@ impl AuthorityState {
         })
     }
 
+    /// Returns the objects sent to `address` by other senders, starting at `cursor` (a
+    /// transaction sequence number and object id) in the order they were received.
+    pub fn get_received_objects(
+        &self,
+        address: SuiAddress,
+        cursor: Option<(TxSequenceNumber, ObjectID)>,
+        limit: Option<usize>,
+    ) -> Result<Vec<(TxSequenceNumber, ObjectRef, TransactionDigest)>, anyhow::Error> {
+        let cursor = cursor.unwrap_or((TxSequenceNumber::MIN, ObjectID::ZERO));
+        Ok(self
+            .get_indexes()?
+            .get_received_objects(address, cursor, limit)?)
+    }
+
+    /// Returns the coins of `coin_type` owned by `owner`, starting at `cursor` in the order of
+    /// their ids, with their balance.
+    pub fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: &TypeTag,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<Vec<(ObjectRef, u64)>, anyhow::Error> {
+        let indexes = self.get_coin_index()?;
+        Ok(indexes.get_coins(owner, coin_type, cursor.unwrap_or(ObjectID::ZERO), limit)?)
+    }
+
+    /// Returns the total supply of `coin_type`, as kept by its `TreasuryCap`: the SUI one is
+    /// held by the system state. Fails if the cap is not indexed or not readable, e.g. because it
+    /// is wrapped into another object.
+    pub async fn get_total_supply(&self, coin_type: &TypeTag) -> Result<u64, anyhow::Error> {
+        if *coin_type == GAS::type_tag() {
+            return Ok(self.get_sui_system_state_object().await?.treasury_cap.value);
+        }
+        let cap_id = self
+            .get_coin_index()?
+            .get_treasury_cap(coin_type)?
+            .ok_or_else(|| anyhow!("No TreasuryCap of {coin_type} is known"))?;
+        let cap = self
+            .get_object(&cap_id)
+            .await?
+            .ok_or_else(|| anyhow!("The TreasuryCap {cap_id} of {coin_type} is not live"))?;
+        let move_obj = cap
+            .data
+            .try_as_move()
+            .ok_or_else(|| anyhow!("Object {cap_id} is not a TreasuryCap"))?;
+        let cap = TreasuryCap::from_bcs_bytes(move_obj.contents())?;
+        Ok(cap.total_supply.value)
+    }
+
+    fn get_coin_index(&self) -> SuiResult<Arc<IndexStore>> {
+        let indexes = self.get_indexes()?;
+        fp_ensure!(
+            indexes.is_coin_index_built()?,
+            SuiError::UnsupportedFeatureError {
+                error: "the coin index of this server is not built yet".into(),
+            }
+        );
+        Ok(indexes)
+    }
+
+    /// Returns the transactions `address` took part in, starting at `cursor` (a transaction
+    /// sequence number and role) in the order they were executed, with the checkpoint that
+    /// includes each of them if it is known yet.
+    pub fn get_address_activity(
+        &self,
+        address: SuiAddress,
+        cursor: Option<(TxSequenceNumber, AddressActivityRole)>,
+        limit: Option<usize>,
+    ) -> Result<
+        Vec<(
+            TxSequenceNumber,
+            AddressActivityRole,
+            TransactionDigest,
+            Option<CheckpointSequenceNumber>,
+        )>,
+        anyhow::Error,
+    > {
+        let cursor = cursor.unwrap_or((TxSequenceNumber::MIN, AddressActivityRole::Sender));
+        let activity = self
+            .get_indexes()?
+            .get_address_activity(address, cursor, limit)?;
+        let checkpoints = self
+            .checkpoints
+            .lock()
+            .tables
+            .transactions_to_checkpoint
+            .multi_get(activity.iter().map(|(_, _, digests)| digests))?;
+        Ok(activity
+            .into_iter()
+            .zip(checkpoints)
+            .map(|((seq, role, digests), checkpoint)| (seq, role, digests.transaction, checkpoint))
+            .collect())
+    }
+
+    /// Build a proof that the given object version was written by a transaction included in a
+    /// certified checkpoint, for light clients that only track the committee.
+    pub async fn get_object_inclusion_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<(ObjectRef, ObjectInclusionProof), anyhow::Error> {
+        let (object_ref, tx_digest) = self
+            .get_parent_iterator(object_id, Some(version))
+            .await?
+            .find(|((_, _, digest), _)| digest.is_alive())
+            .ok_or_else(|| anyhow!("Object {object_id} has no live version {version}"))?;
+        let (_, effects) = self.get_transaction(tx_digest).await?;
+        let execution_digests = ExecutionDigests::new(tx_digest, effects.digest());
+
+        let checkpoints = self.checkpoints.lock();
+        let sequence_number = checkpoints
+            .tables
+            .transactions_to_checkpoint
+            .get(&execution_digests)?
+            .ok_or_else(|| anyhow!("Transaction {tx_digest:?} is not yet checkpointed"))?;
+        let checkpoint = match checkpoints.get_checkpoint(sequence_number)? {
+            Some(AuthenticatedCheckpoint::Certified(checkpoint)) => checkpoint,
+            _ => return Err(anyhow!("Checkpoint {sequence_number} is not yet certified")),
+        };
+        let contents = checkpoints
+            .tables
+            .checkpoint_contents
+            .get(&sequence_number)?
+            .ok_or_else(|| anyhow!("Missing contents of checkpoint {sequence_number}"))?;
+
+        Ok((
+            object_ref,
+            ObjectInclusionProof {
+                checkpoint,
+                contents,
+                effects,
+            },
+        ))
+    }
+
+    /// Read objects as of checkpoint `checkpoint`, or of the latest checkpoint stored by this
+    /// node if None. Each object is read at the version written by the last transaction of that
+    /// checkpoint or an earlier one changing it, ignoring the transactions of later checkpoints
+    /// and those not yet checkpointed, so that the reads are consistent with each other.
+    ///
+    /// The checkpoint must be certified, and all its transactions executed by this node.
+    pub async fn get_objects_at_checkpoint(
+        &self,
+        object_ids: &[ObjectID],
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> Result<(CheckpointSequenceNumber, Vec<PastObjectRead>), anyhow::Error> {
+        let (sequence_number, contents, transactions_to_checkpoint) = {
+            let checkpoints = self.checkpoints.lock();
+            let sequence_number = match checkpoint {
+                Some(checkpoint) => checkpoint,
+                None => {
+                    checkpoints
+                        .latest_stored_checkpoint()
+                        .ok_or_else(|| anyhow!("No checkpoint is stored yet"))?
+                        .summary()
+                        .sequence_number
+                }
+            };
+            if !matches!(
+                checkpoints.get_checkpoint(sequence_number)?,
+                Some(AuthenticatedCheckpoint::Certified(_))
+            ) {
+                return Err(anyhow!("Checkpoint {sequence_number} is not yet certified"));
+            }
+            let contents = checkpoints
+                .tables
+                .checkpoint_contents
+                .get(&sequence_number)?
+                .ok_or_else(|| anyhow!("Missing contents of checkpoint {sequence_number}"))?;
+            (
+                sequence_number,
+                contents,
+                checkpoints.tables.transactions_to_checkpoint.clone(),
+            )
+        };
+        for digests in contents.iter() {
+            if !self.database.effects_exists(&digests.transaction)? {
+                return Err(anyhow!("Checkpoint {sequence_number} is not yet executed"));
+            }
+        }
+
+        let mut reads = Vec::with_capacity(object_ids.len());
+        for object_id in object_ids {
+            reads.push(self.get_object_at_checkpoint(
+                *object_id,
+                sequence_number,
+                &transactions_to_checkpoint,
+            )?);
+        }
+        Ok((sequence_number, reads))
+    }
+
+    /// Walks the versions of the object from the latest one down, stopping at the first one
+    /// written at or before `checkpoint`. Only the versions written after the checkpoint are
+    /// skipped, and at most MAX_VERSIONS_AFTER_CHECKPOINT of them.
+    fn get_object_at_checkpoint(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        transactions_to_checkpoint: &DBMap<ExecutionDigests, CheckpointSequenceNumber>,
+    ) -> Result<PastObjectRead, anyhow::Error> {
+        let entries = self
+            .database
+            .get_parent_iterator_reversed(object_id)?
+            .enumerate();
+        for (skipped, (object_ref, tx_digest)) in entries {
+            if skipped > MAX_VERSIONS_AFTER_CHECKPOINT {
+                return Err(anyhow!(
+                    "Object {object_id} changed more than {MAX_VERSIONS_AFTER_CHECKPOINT} times \
+                     after checkpoint {checkpoint}"
+                ));
+            }
+            if !self.is_checkpointed_at(tx_digest, checkpoint, transactions_to_checkpoint)? {
+                continue;
+            }
+            if object_ref.2 == ObjectDigest::OBJECT_DIGEST_WRAPPED {
+                return Ok(PastObjectRead::ObjectWrapped(object_ref));
+            }
+            if !object_ref.2.is_alive() {
+                return Ok(PastObjectRead::ObjectDeleted(object_ref));
+            }
+            return Ok(
+                match self.database.get_object_by_key(&object_id, object_ref.1)? {
+                    // The version was pruned.
+                    None => PastObjectRead::VersionNotFound(object_id, object_ref.1),
+                    Some(object) => {
+                        let layout = object.get_layout(
+                            ObjectFormatOptions::default(),
+                            self.module_cache.as_ref(),
+                        )?;
+                        PastObjectRead::VersionFound(object_ref, object, layout)
+                    }
+                },
+            );
+        }
+        Ok(PastObjectRead::ObjectNotExists(object_id))
+    }
+
+    /// Whether transaction `tx_digest` is part of checkpoint `checkpoint` or an earlier one.
+    /// Genesis transactions precede all checkpoints.
+    fn is_checkpointed_at(
+        &self,
+        tx_digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+        transactions_to_checkpoint: &DBMap<ExecutionDigests, CheckpointSequenceNumber>,
+    ) -> Result<bool, anyhow::Error> {
+        if tx_digest == TransactionDigest::genesis() {
+            return Ok(true);
+        }
+        let effects = match self.database.get_effects(&tx_digest) {
+            Ok(effects) => effects,
+            Err(SuiError::TransactionNotFound { .. }) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let sequence_number =
+            transactions_to_checkpoint.get(&ExecutionDigests::new(tx_digest, effects.digest()))?;
+        Ok(sequence_number.map_or(false, |seq| seq <= checkpoint))
+    }
+
+    /// The objects changed by the transactions of a checkpoint, in the order of its contents,
+    /// or None if this node does not have the checkpoint or the effects of all its transactions
+    /// yet.
+    pub fn get_checkpoint_object_changes(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<Vec<ObjectChange>>> {
+        let contents = match self
+            .checkpoints
+            .lock()
+            .tables
+            .checkpoint_contents
+            .get(&seq)?
+        {
+            Some(contents) => contents,
+            None => return Ok(None),
+        };
+        let mut changes = vec![];
+        for digests in contents.iter() {
+            let effects = match self.database.get_effects(&digests.transaction) {
+                Ok(effects) => effects,
+                Err(SuiError::TransactionNotFound { .. }) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            for change in changed_objects(&effects) {
+                // The contents of wrapped and deleted objects are those of their last version.
+                let object = match change.after.or(change.before) {
+                    Some(version) => self
+                        .database
+                        .get_object_by_key(&change.object_id(), version)?,
+                    None => continue,
+                };
+                let owner = change
+                    .owner
+                    .or_else(|| object.as_ref().map(|object| object.owner));
+                let object_type = object.and_then(|object| object.data.type_().cloned());
+                changes.push(ObjectChange {
+                    checkpoint: seq,
+                    tx_digest: effects.transaction_digest,
+                    kind: change.kind,
+                    object_ref: change.object_ref,
+                    owner,
+                    object_type,
+                });
+            }
+        }
+        Ok(Some(changes))
+    }
+
+    /// The sequence number the next checkpoint stored by this node will have.
+    pub fn next_stored_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.checkpoints
+            .lock()
+            .latest_stored_checkpoint()
+            .map_or(0, |checkpoint| checkpoint.summary().sequence_number + 1)
+    }
+
     pub async fn get_timestamp_ms(
         &self,
         digest: &TransactionDigest,
@@ -1902,6 +3068,13 @@ impl AuthorityState {
         Ok(self.get_indexes()?.get_timestamp_ms(digest)?)
     }
 
+    pub async fn get_balance_changes(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<Vec<BalanceChange>>, anyhow::Error> {
+        Ok(self.get_indexes()?.get_balance_changes(digest)?)
+    }
+
     /// Returns a full handle to the event store, including inserts... so be careful!
     fn get_event_store(&self) -> Option<Arc<EventStoreType>> {
         self.event_handler
@@ -2064,9 +3237,33 @@ impl AuthorityState {
         mutable_input_objects: &[ObjectRef],
         signed_transaction: SignedTransaction,
     ) -> Result<(), SuiError> {
-        self.database
+        self.metrics.transaction_lock_acquisitions.inc();
+        let result = self
+            .database
             .lock_and_write_transaction(self.epoch(), mutable_input_objects, signed_transaction)
-            .await
+            .await;
+        if let Err(SuiError::ObjectLockConflict { obj_ref, .. }) = &result {
+            self.metrics.transaction_lock_conflicts.inc();
+            self.object_contention.record_lock_conflict(obj_ref.0);
+        }
+        result
+    }
+
+    /// Reset the object locks left over from previous epochs at the start of `epoch`, so that
+    /// the lock table does not grow across epochs.
+    pub async fn reset_transaction_locks_for_epoch(&self, epoch: EpochId) -> SuiResult {
+        let stats = self
+            .database
+            .reset_transaction_locks_for_epoch(epoch)
+            .await?;
+        self.metrics.lock_table_size.set(stats.live_locks as i64);
+        self.metrics
+            .lock_table_reset_locks
+            .inc_by(stats.reset_locks);
+        self.metrics
+            .lock_table_deleted_locks
+            .inc_by(stats.deleted_locks);
+        Ok(())
     }
 
     /// Update state and signals that a new transactions has been processed
@@ -2095,10 +3292,11 @@ impl AuthorityState {
             )
             .await
             .tap_ok(|_| {
-                debug!(?digest, ?effects_digest, ?self.name, "commit_certificate finished");
+                debug!(tx_digest = ?digest, ?effects_digest, authority = ?self.name, "commit_certificate finished");
             })?;
         // We only notify i.e. update low watermark once database changes are committed
         notifier_ticket.notify();
+        self.object_contention.record_executed(digest);
         Ok(())
     }
 
@@ -2284,6 +3482,17 @@ impl AuthorityState {
                     Some(*certificate.clone()),
                 )])?;
 
+                // Recorded before the shared objects are locked, as the certificate may be
+                // executed as soon as they are. Certificates already executed, e.g. through node
+                // sync, are not waiting.
+                let digest = *certificate.digest();
+                if !self.database.effects_exists(&digest)? {
+                    self.object_contention.record_sequenced(
+                        digest,
+                        certificate.shared_input_objects().map(|(id, _)| *id),
+                    );
+                }
+
                 self.database
                     .lock_shared_objects(*certificate, consensus_index)
                     .await?;
@@ -23,6 +23,10 @@ pub struct TransactionNotifier {
     /// In special cases (i.e. executing known-to-be-finalized transactions) we will bypass
     /// this.
     is_paused: AtomicBool,
+    /// The epoch the pause lasts until, told to the requests rejected meanwhile.
+    paused_until_epoch: AtomicU64,
+    /// Whether the pause is for shutting the node down rather than for an epoch change.
+    is_shutting_down: AtomicBool,
     inner: Mutex<LockedNotifier>,
 }
 
@@ -42,6 +46,8 @@ impl TransactionNotifier {
             has_stream: AtomicBool::new(false),
             is_closed: AtomicBool::new(false),
             is_paused: AtomicBool::new(false),
+            paused_until_epoch: AtomicU64::new(0),
+            is_shutting_down: AtomicBool::new(false),
 
             // Keep a set of the tickets that are still being processed
             // This is the size of the number of concurrent processes.
@@ -56,7 +62,15 @@ impl TransactionNotifier {
         self.low_watermark.load(Ordering::SeqCst)
     }
 
-    pub fn pause(&self) {
+    pub fn pause(&self, until_epoch: EpochId) {
+        self.paused_until_epoch.store(until_epoch, Ordering::SeqCst);
+        self.is_shutting_down.store(false, Ordering::SeqCst);
+        self.is_paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Pause for good, as the node is shutting down.
+    pub fn pause_for_shutdown(&self) {
+        self.is_shutting_down.store(true, Ordering::SeqCst);
         self.is_paused.store(true, Ordering::SeqCst);
     }
 
@@ -68,6 +82,16 @@ impl TransactionNotifier {
         self.is_paused.load(Ordering::SeqCst)
     }
 
+    /// The error new requests are rejected with while paused.
+    pub fn paused_error(&self) -> SuiError {
+        if self.is_shutting_down.load(Ordering::SeqCst) {
+            return SuiError::ValidatorShuttingDown;
+        }
+        SuiError::ValidatorHalted {
+            until_epoch: self.paused_until_epoch.load(Ordering::SeqCst),
+        }
+    }
+
     /// Check that we have drained all tickets (i.e. low watermark reached high watermark).
     /// Return the watermark if we have drained.
     pub fn ticket_drained(&self) -> Option<u64> {
@@ -91,7 +115,7 @@ impl TransactionNotifier {
             return Err(SuiError::ClosedNotifierError);
         }
         if !bypass_pause && self.is_paused() {
-            return Err(SuiError::ValidatorHaltedAtEpochEnd);
+            return Err(self.paused_error());
         }
 
         let mut inner = self.inner.lock();
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    authority_store_tables::{AuthorityEpochTables, AuthorityPerpetualTables},
+    authority_store_tables::{
+        AuthorityEpochTables, AuthorityPerpetualTables, EPOCH_DB_NAME, PERPETUAL_DB_NAME,
+    },
     *,
 };
 use crate::authority::authority_store_tables::ExecutionIndicesWithHash;
+use crate::authority::input_prefetcher::{InputPrefetcher, PrefetchGuard};
+use crate::authority::object_cache::{CacheLookup, ObjectCache};
 use arc_swap::ArcSwap;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
@@ -14,17 +18,22 @@ use std::collections::BTreeMap;
 use std::iter;
 use std::path::Path;
 use std::sync::{atomic::AtomicU64, Arc};
+use std::time::Instant;
 use std::{fmt::Debug, path::PathBuf};
 use sui_storage::{
+    db_metrics::{DBMetrics, DBOp},
+    lock_service::LockTableResetStats,
     mutex_table::{LockGuard, MutexTable},
     write_ahead_log::{DBWriteAheadLog, WriteAheadLog},
-    LockService,
+    IndexedCoin, LockService,
 };
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
 use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
+use sui_types::epoch_accounting::EpochAccountingReport;
 use sui_types::object::Owner;
 use sui_types::storage::{ChildObjectResolver, WriteKind};
 use sui_types::{base_types::SequenceNumber, storage::ParentSync};
+use tap::TapFallible;
 use tokio::sync::Notify;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, info, trace};
@@ -64,12 +73,25 @@ pub struct SuiDataStore<S> {
 
     // The next sequence number.
     next_pending_seq: AtomicU64,
+    // The number of pending certificates, and of those taken off the queue since the store was
+    // opened, e.g. once executed.
+    pending_count: AtomicU64,
+    dequeued_count: AtomicU64,
     // A notifier for new pending certificates
     pending_notifier: Arc<Notify>,
 
     pub(crate) perpetual_tables: AuthorityPerpetualTables<S>,
     pub(crate) epoch_tables: ArcSwap<AuthorityEpochTables<S>>,
 
+    /// Latest versions of recently read objects, if enabled.
+    object_cache: Option<ObjectCache>,
+
+    /// Input objects of queued certificates read ahead of their execution, if enabled.
+    input_prefetcher: Option<Arc<InputPrefetcher>>,
+
+    /// The metrics of the reads and writes of the store, in which its databases are registered.
+    db_metrics: Arc<DBMetrics>,
+
     // needed for re-opening epoch db.
     path: PathBuf,
     db_options: Option<Options>,
@@ -87,6 +109,8 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         };
 
         let epoch_tables = Arc::new(AuthorityEpochTables::open(epoch, path, db_options.clone()));
+        let db_metrics = DBMetrics::new_unregistered();
+        register_dbs(&db_metrics, &perpetual_tables, &epoch_tables);
 
         // For now, create one LockService for each SuiDataStore, and we use a specific
         // subdir of the data store directory
@@ -106,20 +130,51 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .map(|(seq, _)| seq + 1)
             .unwrap_or(0);
         let next_pending_seq = AtomicU64::new(pending_seq);
+        let pending_count = AtomicU64::new(epoch_tables.pending_execution.keys().count() as u64);
 
         Ok(Self {
             wal,
             lock_service,
             mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
             next_pending_seq,
+            pending_count,
+            dequeued_count: AtomicU64::new(0),
             pending_notifier: Arc::new(Notify::new()),
             perpetual_tables,
             epoch_tables: epoch_tables.into(),
+            object_cache: None,
+            input_prefetcher: None,
+            db_metrics,
             path: path.into(),
             db_options,
         })
     }
 
+    /// Reads the latest versions of objects through `cache`.
+    pub fn with_object_cache(mut self, cache: ObjectCache) -> Self {
+        self.object_cache = Some(cache);
+        self
+    }
+
+    /// Reads the input objects of certificates ahead of their execution through `prefetcher`.
+    pub fn with_input_prefetcher(mut self, prefetcher: InputPrefetcher) -> Self {
+        self.input_prefetcher = Some(Arc::new(prefetcher));
+        self
+    }
+
+    /// Records the reads and writes of the store into `db_metrics`, the metrics of its node,
+    /// and registers its databases there.
+    pub fn with_db_metrics(mut self, db_metrics: Arc<DBMetrics>) -> Self {
+        register_dbs(&db_metrics, &self.perpetual_tables, &self.epoch_tables());
+        self.db_metrics = db_metrics;
+        self
+    }
+
+    /// The metrics of the store, in which its databases are registered.
+    pub fn db_metrics(&self) -> &Arc<DBMetrics> {
+        &self.db_metrics
+    }
+
     pub(crate) fn reopen_epoch_db(&self, new_epoch: EpochId) {
         info!(?new_epoch, "re-opening AuthorityEpochTables for new epoch");
         let epoch_tables = Arc::new(AuthorityEpochTables::open(
@@ -127,7 +182,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             &self.path,
             self.db_options.clone(),
         ));
+        self.db_metrics
+            .register_db(EPOCH_DB_NAME, &epoch_tables.transactions.rocksdb);
+        let pending_count = epoch_tables.pending_execution.keys().count();
         self.epoch_tables.store(epoch_tables);
+        self.pending_count
+            .store(pending_count as u64, Ordering::Relaxed);
     }
 
     pub fn epoch_tables(&self) -> arc_swap::Guard<Arc<AuthorityEpochTables<S>>> {
@@ -166,9 +226,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         transaction_digest: &TransactionDigest,
     ) -> SuiResult<TransactionEffects> {
-        self.perpetual_tables
-            .effects
-            .get(transaction_digest)?
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "effects", DBOp::Read, || {
+                self.perpetual_tables.effects.get(transaction_digest)
+            })?
             .map(|data| data.effects)
             .ok_or(SuiError::TransactionNotFound {
                 digest: *transaction_digest,
@@ -235,7 +296,17 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                 .enumerate()
                 .map(|(num, digest)| ((num as u64) + first_index, digest)),
         )?;
-        batch.write()?;
+        let write_start = Instant::now();
+        batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(EPOCH_DB_NAME))?;
+        self.db_metrics.record_batch(
+            EPOCH_DB_NAME,
+            &[("pending_execution", DBOp::Write, digests.len())],
+            write_start.elapsed(),
+        );
+        self.pending_count
+            .fetch_add(digests.len() as u64, Ordering::Relaxed);
 
         // now notify there is a pending certificate
         self.pending_notifier.notify_one();
@@ -254,16 +325,44 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     pub fn remove_pending_digests(&self, seqs: Vec<InternalSequenceNumber>) -> SuiResult<()> {
         let batch = self.epoch_tables().pending_execution.batch();
         let batch = batch.delete_batch(&self.epoch_tables().pending_execution, seqs.iter())?;
-        batch.write()?;
+        let write_start = Instant::now();
+        batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(EPOCH_DB_NAME))?;
+        self.db_metrics.record_batch(
+            EPOCH_DB_NAME,
+            &[("pending_execution", DBOp::Delete, seqs.len())],
+            write_start.elapsed(),
+        );
+        let removed = seqs.len() as u64;
+        // Saturating, as sequence numbers which were already removed may be removed again.
+        let _ = self
+            .pending_count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                Some(count.saturating_sub(removed))
+            });
+        self.dequeued_count.fetch_add(removed, Ordering::Relaxed);
         Ok(())
     }
 
     // Empty the pending_execution table.
     pub fn remove_all_pending_certificates(&self) -> SuiResult {
         self.epoch_tables().pending_execution.clear()?;
+        self.pending_count.store(0, Ordering::Relaxed);
         Ok(())
     }
 
+    /// The number of certificates pending execution, without reading the table.
+    pub fn pending_count(&self) -> u64 {
+        self.pending_count.load(Ordering::Relaxed)
+    }
+
+    /// The number of certificates taken off the queue of pending certificates since the store
+    /// was opened, e.g. once executed.
+    pub fn dequeued_count(&self) -> u64 {
+        self.dequeued_count.load(Ordering::Relaxed)
+    }
+
     /// A function that acquires all locks associated with the objects (in order to avoid deadlocks).
     async fn acquire_locks(&self, input_objects: &[ObjectRef]) -> Vec<LockGuard> {
         self.mutex_table
@@ -273,16 +372,69 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
 
     // Methods to read the store
     pub fn get_owner_objects(&self, owner: Owner) -> Result<Vec<ObjectInfo>, SuiError> {
-        debug!(?owner, "get_owner_objects");
-        Ok(self
-            .perpetual_tables
+        self.get_owner_objects_bounded(owner, usize::MAX)
+    }
+
+    /// Reads at most `limit` of the objects owned by `owner`, in the order of their IDs.
+    pub fn get_owner_objects_bounded(
+        &self,
+        owner: Owner,
+        limit: usize,
+    ) -> Result<Vec<ObjectInfo>, SuiError> {
+        debug!(?owner, limit, "get_owner_objects");
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "owner_index", DBOp::Read, || {
+                Ok(self
+                    .perpetual_tables
+                    .owner_index
+                    .iter()
+                    // The object id 0 is the smallest possible
+                    .skip_to(&(owner, ObjectID::ZERO))?
+                    .take_while(|((object_owner, _), _)| (object_owner == &owner))
+                    .take(limit)
+                    .map(|(_, object_info)| object_info)
+                    .collect())
+            })
+    }
+
+    /// Iterates over the live coins, read from the owner index, e.g. to build the coin index.
+    pub fn iter_live_coins(&self) -> impl Iterator<Item = SuiResult<IndexedCoin>> + '_ {
+        self.perpetual_tables
             .owner_index
             .iter()
-            // The object id 0 is the smallest possible
-            .skip_to(&(owner, ObjectID::ZERO))?
-            .take_while(|((object_owner, _), _)| (object_owner == &owner))
-            .map(|(_, object_info)| object_info)
-            .collect())
+            .filter_map(|(_, info)| {
+                let coin = self
+                    .get_object_by_key(&info.object_id, info.version)
+                    .and_then(|object| match object {
+                        Some(object) => IndexedCoin::from_object(&object),
+                        None => Err(SuiError::ObjectNotFound {
+                            object_id: info.object_id,
+                        }),
+                    });
+                coin.transpose()
+            })
+    }
+
+    /// Iterates over the live `TreasuryCap`s owned by addresses or objects, with the coin type of
+    /// each, read from the owner index, e.g. to build the coin index.
+    pub fn iter_live_treasury_caps(
+        &self,
+    ) -> impl Iterator<Item = SuiResult<(TypeTag, ObjectID)>> + '_ {
+        self.perpetual_tables
+            .owner_index
+            .iter()
+            .filter_map(|(_, info)| {
+                let cap = self
+                    .get_object_by_key(&info.object_id, info.version)
+                    .and_then(|object| match object {
+                        Some(object) => Ok(TreasuryCap::coin_type_of(&object)),
+                        None => Err(SuiError::ObjectNotFound {
+                            object_id: info.object_id,
+                        }),
+                    });
+                cap.map(|coin_type| coin_type.map(|coin_type| (coin_type, info.object_id)))
+                    .transpose()
+            })
     }
 
     pub fn get_object_by_key(
@@ -290,15 +442,48 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         object_id: &ObjectID,
         version: VersionNumber,
     ) -> Result<Option<Object>, SuiError> {
+        if let Some(object) = self
+            .object_cache
+            .as_ref()
+            .and_then(|cache| cache.get_by_version(object_id, version))
+        {
+            return Ok(Some(object));
+        }
         Ok(self
-            .perpetual_tables
-            .objects
-            .get(&ObjectKey(*object_id, version))?)
+            .db_metrics
+            .metered(PERPETUAL_DB_NAME, "objects", DBOp::Read, || {
+                self.perpetual_tables
+                    .objects
+                    .get(&ObjectKey(*object_id, version))
+            })?)
     }
 
     /// Read an object and return it, or Err(ObjectNotFound) if the object was not found.
     pub fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
-        self.perpetual_tables.get_object(object_id)
+        let cache = match &self.object_cache {
+            Some(cache) => cache,
+            None => return self.read_object(object_id),
+        };
+        match cache.get_latest(object_id) {
+            CacheLookup::Found(object) => Ok(Some(object)),
+            CacheLookup::Deleted => Ok(None),
+            CacheLookup::Miss => {
+                let stamp = cache.read_stamp();
+                let object = self.read_object(object_id)?;
+                if let Some(object) = &object {
+                    cache.insert_read(object, stamp);
+                }
+                Ok(object)
+            }
+        }
+    }
+
+    /// Reads the latest version of an object from the store, bypassing the cache.
+    fn read_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "objects", DBOp::Read, || {
+                self.perpetual_tables.get_object(object_id)
+            })
     }
 
     /// Get many objects
@@ -316,11 +501,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         let mut errors = Vec::new();
         for kind in objects {
             let obj = match kind {
-                InputObjectKind::MovePackage(id) | InputObjectKind::SharedMoveObject { id, .. } => {
-                    self.get_object(id)?
-                }
+                InputObjectKind::MovePackage(id) => self.get_input_package(id)?,
+                InputObjectKind::SharedMoveObject { id, .. } => self.get_object(id)?,
                 InputObjectKind::ImmOrOwnedMoveObject(objref) => {
-                    self.get_object_by_key(&objref.0, objref.1)?
+                    self.get_input_object(&objref.0, objref.1)?
                 }
             };
             match obj {
@@ -347,16 +531,16 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         let mut errors = Vec::new();
         for kind in objects {
             let obj = match kind {
-                InputObjectKind::MovePackage(id) => self.get_object(id)?,
+                InputObjectKind::MovePackage(id) => self.get_input_package(id)?,
                 InputObjectKind::SharedMoveObject { id, .. } => match shared_locks.get(id) {
-                    Some(version) => self.get_object_by_key(id, *version)?,
+                    Some(version) => self.get_input_object(id, *version)?,
                     None => {
                         errors.push(SuiError::SharedObjectLockNotSetError);
                         continue;
                     }
                 },
                 InputObjectKind::ImmOrOwnedMoveObject(objref) => {
-                    self.get_object_by_key(&objref.0, objref.1)?
+                    self.get_input_object(&objref.0, objref.1)?
                 }
             };
             match obj {
@@ -371,6 +555,80 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         }
     }
 
+    /// Reads an input object of a certificate, from the objects prefetched for it if any.
+    fn get_input_object(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, SuiError> {
+        match self
+            .input_prefetcher
+            .as_ref()
+            .and_then(|prefetcher| prefetcher.get(object_id, version))
+        {
+            Some(object) => Ok(Some(object)),
+            None => self.get_object_by_key(object_id, version),
+        }
+    }
+
+    fn get_input_package(&self, package_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        match self
+            .input_prefetcher
+            .as_ref()
+            .and_then(|prefetcher| prefetcher.get_package(package_id))
+        {
+            Some(package) => Ok(Some(package)),
+            None => self.get_object(package_id),
+        }
+    }
+
+    pub fn prefetches_input_objects(&self) -> bool {
+        self.input_prefetcher.is_some()
+    }
+
+    /// Reads the input objects of `certificate` which are stored already, ahead of its
+    /// execution, and keeps them until the returned guard is dropped. The versions of its shared
+    /// objects are taken from `shared_objects`, the others are not prefetched. Returns None if
+    /// prefetching is not enabled.
+    pub fn prefetch_input_objects(
+        &self,
+        certificate: &CertifiedTransaction,
+        shared_objects: &[ObjectRef],
+    ) -> SuiResult<Option<PrefetchGuard>> {
+        let prefetcher = match &self.input_prefetcher {
+            Some(prefetcher) => prefetcher,
+            None => return Ok(None),
+        };
+        let shared_versions: HashMap<_, _> = shared_objects
+            .iter()
+            .map(|(id, version, _)| (*id, *version))
+            .collect();
+        let mut guard = prefetcher.start();
+        for kind in certificate.signed_data.data.input_objects()? {
+            let (id, version) = match kind {
+                InputObjectKind::MovePackage(id) => (id, None),
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => (id, Some(version)),
+                InputObjectKind::SharedMoveObject { id, .. } => match shared_versions.get(&id) {
+                    Some(version) => (id, Some(*version)),
+                    None => continue,
+                },
+            };
+            if guard.retain(&id, version) {
+                continue;
+            }
+            let start = Instant::now();
+            let object = match version {
+                Some(version) => self.get_object_by_key(&id, version)?,
+                None => self.get_object(&id)?,
+            };
+            // Objects written by parents which did not execute yet are read at execution.
+            if let Some(object) = object {
+                guard.insert(object, start.elapsed());
+            }
+        }
+        Ok(Some(guard))
+    }
+
     /// Get the transaction envelope that currently locks the given object,
     /// or returns Err(TransactionLockDoesNotExist) if the lock does not exist.
     pub async fn get_object_locking_transaction(
@@ -416,18 +674,20 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         digest: &TransactionDigest,
     ) -> Result<Option<CertifiedTransaction>, SuiError> {
-        self.perpetual_tables
-            .certificates
-            .get(digest)
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "certificates", DBOp::Read, || {
+                self.perpetual_tables.certificates.get(digest)
+            })
             .map_err(|e| e.into())
     }
 
     /// Read the transactionDigest that is the parent of an object reference
     /// (ie. the transaction that created an object at this version.)
     pub fn parent(&self, object_ref: &ObjectRef) -> Result<Option<TransactionDigest>, SuiError> {
-        self.perpetual_tables
-            .parent_sync
-            .get(object_ref)
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "parent_sync", DBOp::Read, || {
+                self.perpetual_tables.parent_sync.get(object_ref)
+            })
             .map_err(|e| e.into())
     }
 
@@ -467,6 +727,22 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             }))
     }
 
+    /// Returns all parents (object_ref and transaction digests) that match an object_id, from
+    /// its latest version down to its earliest one.
+    pub fn get_parent_iterator_reversed(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<impl Iterator<Item = (ObjectRef, TransactionDigest)> + '_, SuiError> {
+        Ok(self
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            // Make the max possible entry for this object ID.
+            .skip_prior_to(&(object_id, SequenceNumber::MAX, ObjectDigest::MAX))?
+            .reverse()
+            .take_while(move |((id, _, _), _)| id == &object_id))
+    }
+
     /// Read a lock for a specific (transaction, shared object) pair.
     pub fn get_assigned_object_versions<'a>(
         &self,
@@ -512,9 +788,16 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// TODO: We need this today because we don't have another way to sync an account.
     pub async fn insert_object_direct(&self, object_ref: ObjectRef, object: &Object) -> SuiResult {
         // Insert object
+        let pending_write = self
+            .object_cache
+            .as_ref()
+            .map(|cache| cache.begin_write([object_ref.0]));
         self.perpetual_tables
             .objects
             .insert(&object_ref.into(), object)?;
+        if let Some(pending_write) = pending_write {
+            pending_write.commit([object], []);
+        }
 
         // Update the index
         if object.get_single_owner().is_some() {
@@ -541,6 +824,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// In particular it does not check the old locks before inserting new ones, so the objects
     /// must be new.
     pub async fn bulk_object_insert(&self, objects: &[&Object]) -> SuiResult<()> {
+        let pending_write = self
+            .object_cache
+            .as_ref()
+            .map(|cache| cache.begin_write(objects.iter().map(|o| o.id())));
         let batch = self.perpetual_tables.objects.batch();
         let ref_and_objects: Vec<_> = objects
             .iter()
@@ -567,6 +854,9 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                     .map(|(oref, o)| (oref, o.previous_transaction)),
             )?
             .write()?;
+        if let Some(pending_write) = pending_write {
+            pending_write.commit(objects.iter().copied(), []);
+        }
 
         let refs: Vec<_> = ref_and_objects.iter().map(|(oref, _)| *oref).collect();
         self.lock_service
@@ -613,6 +903,15 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(())
     }
 
+    /// Reset the transaction locks set in epochs before `epoch`, and delete the locks of
+    /// superseded object versions.
+    pub async fn reset_transaction_locks_for_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<LockTableResetStats> {
+        self.lock_service.reset_for_epoch(epoch).await
+    }
+
     /// Updates the state resulting from the execution of a certificate.
     ///
     /// Internally it checks that all locks for active inputs are at the correct
@@ -636,6 +935,19 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             iter::once((transaction_digest, certificate)),
         )?;
 
+        // Keep the accounting of the epoch this transaction ends, if it is the advance epoch one.
+        if effects.effects.status.is_ok() {
+            if let Some(report) = EpochAccountingReport::from_execution(
+                &certificate.signed_data.data,
+                &inner_temporary_store,
+            ) {
+                write_batch = write_batch.insert_batch(
+                    &self.perpetual_tables.epoch_accounting_reports,
+                    iter::once((report.epoch, report)),
+                )?;
+            }
+        }
+
         self.sequence_tx(
             write_batch,
             inner_temporary_store,
@@ -742,6 +1054,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             &self.perpetual_tables.effects,
             [(transaction_digest, effects)].into_iter(),
         )?;
+        let batch = batch.insert_batch(
+            &self.perpetual_tables.effects_digests,
+            [(effects_digest, transaction_digest)].into_iter(),
+        )?;
 
         // Writing to executed_sequence must be done *after* writing to effects, so that we never
         // broadcast a sequenced transaction (via the batch system) for which no effects can be
@@ -769,7 +1085,19 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .into_iter(),
         )?;
 
-        batch.write()?;
+        let write_start = Instant::now();
+        batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(PERPETUAL_DB_NAME))?;
+        self.db_metrics.record_batch(
+            PERPETUAL_DB_NAME,
+            &[
+                ("effects", DBOp::Write, 1),
+                ("effects_digests", DBOp::Write, 1),
+                ("executed_sequence", DBOp::Write, 1),
+            ],
+            write_start.elapsed(),
+        );
 
         Ok(())
     }
@@ -802,7 +1130,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         // For wrapped objects, although their owners technically didn't change, we will lose track
         // of them and there is no guarantee on their owner in the future. Hence we treat them
         // the same as deleted.
-        let old_object_owners =
+        let old_object_owners: Vec<_> =
             deleted
                 .iter()
                 // We need to call get() on objects because some object that were just deleted may not
@@ -816,9 +1144,11 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                         }
                         _ => None,
                     },
-                ));
+                ))
+                .collect();
 
         // Delete the old owner index entries
+        let owner_index_deletes = old_object_owners.len();
         write_batch =
             write_batch.delete_batch(&self.perpetual_tables.owner_index, old_object_owners)?;
 
@@ -857,17 +1187,18 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .remove(&transaction_digest)?;
 
         // Update the indexes of the objects written
-        write_batch = write_batch.insert_batch(
-            &self.perpetual_tables.owner_index,
-            written
-                .iter()
-                .filter_map(|(_id, (object_ref, new_object, _kind))| {
-                    trace!(?object_ref, owner =? new_object.owner, "Updating owner_index");
-                    new_object
-                        .get_owner_and_id()
-                        .map(|owner_id| (owner_id, ObjectInfo::new(object_ref, new_object)))
-                }),
-        )?;
+        let new_object_owners: Vec<_> = written
+            .iter()
+            .filter_map(|(_id, (object_ref, new_object, _kind))| {
+                trace!(?object_ref, owner =? new_object.owner, "Updating owner_index");
+                new_object
+                    .get_owner_and_id()
+                    .map(|owner_id| (owner_id, ObjectInfo::new(object_ref, new_object)))
+            })
+            .collect();
+        let owner_index_writes = new_object_owners.len();
+        write_batch =
+            write_batch.insert_batch(&self.perpetual_tables.owner_index, new_object_owners)?;
 
         // Insert each output object into the stores
         write_batch = write_batch.insert_batch(
@@ -877,8 +1208,36 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                 .map(|(_, (obj_ref, new_object, _kind))| (ObjectKey::from(obj_ref), new_object)),
         )?;
 
+        // The objects are kept out of the cache while being written, so that their previous
+        // versions are not read from it once the database has the new ones.
+        let pending_write = self
+            .object_cache
+            .as_ref()
+            .map(|cache| cache.begin_write(written.keys().chain(deleted.keys()).copied()));
+
         // Atomic write of all data other than locks
-        write_batch.write()?;
+        let write_start = Instant::now();
+        write_batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(PERPETUAL_DB_NAME))?;
+        self.db_metrics.record_batch(
+            PERPETUAL_DB_NAME,
+            &[
+                ("owner_index", DBOp::Delete, owner_index_deletes),
+                ("owner_index", DBOp::Write, owner_index_writes),
+                ("parent_sync", DBOp::Write, written.len() + deleted.len()),
+                ("objects", DBOp::Write, written.len()),
+            ],
+            write_start.elapsed(),
+        );
+        if let Some(pending_write) = pending_write {
+            pending_write.commit(
+                written.values().map(|(_, object, _)| object),
+                deleted
+                    .iter()
+                    .map(|(object_id, (version, _))| (*object_id, *version)),
+            );
+        }
         trace!("Finished writing batch");
 
         // Need to have a critical section for now because we need to prevent execution of older
@@ -955,6 +1314,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             write_batch.delete_batch(&self.perpetual_tables.certificates, iter::once(tx_digest))?;
         write_batch =
             write_batch.delete_batch(&self.perpetual_tables.effects, iter::once(tx_digest))?;
+        write_batch = write_batch.delete_batch(
+            &self.perpetual_tables.effects_digests,
+            iter::once(effects.digest()),
+        )?;
 
         let all_new_refs = effects
             .mutated
@@ -1017,7 +1380,28 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             });
         write_batch = write_batch.insert_batch(&self.perpetual_tables.owner_index, old_objects)?;
 
+        // The reverted versions are newer than the versions restored, so they must be dropped
+        // from the cache rather than replaced, and reads of them must not be cached.
+        let reverted_ids: Vec<_> = effects
+            .mutated
+            .iter()
+            .chain(effects.created.iter())
+            .chain(effects.unwrapped.iter())
+            .map(|(r, _)| r)
+            .chain(effects.deleted.iter())
+            .chain(effects.wrapped.iter())
+            .map(|(id, _, _)| *id)
+            .collect();
+        let _pending_write = self
+            .object_cache
+            .as_ref()
+            .map(|cache| cache.begin_write(reverted_ids.iter().copied()));
+
         write_batch.write()?;
+
+        if let Some(cache) = &self.object_cache {
+            cache.invalidate(reverted_ids);
+        }
         Ok(())
     }
 
@@ -1036,7 +1420,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<(ObjectRef, TransactionDigest)>, SuiError> {
-        self.perpetual_tables.get_latest_parent_entry(object_id)
+        self.db_metrics
+            .metered(PERPETUAL_DB_NAME, "parent_sync", DBOp::Read, || {
+                self.perpetual_tables.get_latest_parent_entry(object_id)
+            })
     }
 
     /// Remove the shared objects locks.
@@ -1183,13 +1570,16 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         //
         // (Both checkpoints and the node follower system ensure that at least one
         // honest validator has vouched for the TransactionEffects that were used).
+        let mut assigned_versions = 0;
         if !self.effects_exists(&transaction_digest)? {
+            assigned_versions = sequenced_to_write.len();
             write_batch = write_batch.insert_batch(
                 &self.epoch_tables().assigned_object_versions,
                 sequenced_to_write,
             )?;
         }
 
+        let next_versions = schedule_to_write.len();
         write_batch = write_batch
             .insert_batch(&self.epoch_tables().next_object_versions, schedule_to_write)?;
         write_batch =
@@ -1198,7 +1588,21 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             &self.epoch_tables().consensus_message_processed,
             iter::once((transaction_digest, true)),
         )?;
-        write_batch.write().map_err(SuiError::from)
+        let write_start = Instant::now();
+        write_batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(EPOCH_DB_NAME))?;
+        self.db_metrics.record_batch(
+            EPOCH_DB_NAME,
+            &[
+                ("assigned_object_versions", DBOp::Write, assigned_versions),
+                ("next_object_versions", DBOp::Write, next_versions),
+                ("last_consensus_index", DBOp::Write, 1),
+                ("consensus_message_processed", DBOp::Write, 1),
+            ],
+            write_start.elapsed(),
+        );
+        Ok(())
     }
 
     pub fn transactions_in_seq_range(
@@ -1332,7 +1736,11 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         transaction_digest: &TransactionDigest,
     ) -> SuiResult<Option<TransactionEnvelope<S>>> {
-        let transaction = self.epoch_tables().transactions.get(transaction_digest)?;
+        let transaction =
+            self.db_metrics
+                .metered(EPOCH_DB_NAME, "transactions", DBOp::Read, || {
+                    self.epoch_tables().transactions.get(transaction_digest)
+                })?;
         Ok(transaction)
     }
 
@@ -1340,7 +1748,11 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         transaction_digest: &TransactionDigest,
     ) -> SuiResult<Option<CertifiedTransaction>> {
-        let transaction = self.perpetual_tables.certificates.get(transaction_digest)?;
+        let transaction =
+            self.db_metrics
+                .metered(PERPETUAL_DB_NAME, "certificates", DBOp::Read, || {
+                    self.perpetual_tables.certificates.get(transaction_digest)
+                })?;
         Ok(transaction)
     }
 
@@ -1360,6 +1772,23 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     {
         self.perpetual_tables.get_sui_system_state_object()
     }
+
+    pub fn get_epoch_accounting_report(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<EpochAccountingReport>> {
+        Ok(self.perpetual_tables.epoch_accounting_reports.get(&epoch)?)
+    }
+}
+
+/// Registers the databases of the tables of a store in `db_metrics`.
+fn register_dbs<S>(
+    db_metrics: &DBMetrics,
+    perpetual_tables: &AuthorityPerpetualTables<S>,
+    epoch_tables: &AuthorityEpochTables<S>,
+) {
+    db_metrics.register_db(PERPETUAL_DB_NAME, &perpetual_tables.objects.rocksdb);
+    db_metrics.register_db(EPOCH_DB_NAME, &epoch_tables.transactions.rocksdb);
 }
 
 impl SuiDataStore<AuthoritySignInfo> {
@@ -1387,6 +1816,17 @@ impl SuiDataStore<AuthoritySignInfo> {
             signed_effects: self.perpetual_tables.effects.get(transaction_digest)?,
         })
     }
+
+    pub fn get_effects_info(
+        &self,
+        effects_digest: &TransactionEffectsDigest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        let signed_effects = match self.perpetual_tables.effects_digests.get(effects_digest)? {
+            Some(transaction_digest) => self.perpetual_tables.effects.get(&transaction_digest)?,
+            None => None,
+        };
+        Ok(EffectsInfoResponse { signed_effects })
+    }
 }
 
 impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> BackingPackageStore
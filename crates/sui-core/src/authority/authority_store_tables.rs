@@ -9,14 +9,20 @@ use narwhal_executor::ExecutionIndices;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use sui_storage::compression::with_cf_compression;
 use sui_storage::default_db_options;
 use sui_types::base_types::{ExecutionDigests, SequenceNumber};
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
+use sui_types::epoch_accounting::EpochAccountingReport;
 use typed_store::rocks::DBMap;
 use typed_store::traits::TypedStoreDebug;
 
 use typed_store_derive::DBMapUtils;
 
+/// Names under which the databases of the store are registered for the storage metrics.
+pub(crate) const EPOCH_DB_NAME: &str = "epoch";
+pub(crate) const PERPETUAL_DB_NAME: &str = "perpetual";
+
 /// AuthorityEpochTables contains tables that contain data that is only valid within an epoch.
 #[derive(DBMapUtils)]
 pub struct AuthorityEpochTables<S> {
@@ -118,6 +124,10 @@ pub struct AuthorityPerpetualTables<S> {
     #[default_options_override_fn = "effects_table_default_config"]
     pub(crate) effects: DBMap<TransactionDigest, TransactionEffectsEnvelope<S>>,
 
+    /// An index from the digest of the effects in `effects` to the digest of their transaction,
+    /// so that effects listed in a checkpoint can be served before their transaction is known.
+    pub(crate) effects_digests: DBMap<TransactionEffectsDigest, TransactionDigest>,
+
     // Tables used for authority batch structure
     // TODO: executed_sequence and batches both conceptually belong in AuthorityEpochTables,
     // but we currently require that effects and executed_sequence are written atomically.
@@ -137,6 +147,10 @@ pub struct AuthorityPerpetualTables<S> {
 
     /// A sequence of batches indexing into the sequence of executed transactions.
     pub batches: DBMap<TxSequenceNumber, SignedBatch>,
+
+    /// The gas fees and reward distribution of every epoch this authority saw end, written when
+    /// the advance epoch transaction is executed.
+    pub(crate) epoch_accounting_reports: DBMap<EpochId, EpochAccountingReport>,
 }
 
 impl<S> AuthorityPerpetualTables<S>
@@ -250,11 +264,11 @@ fn objects_table_default_config() -> Options {
     default_db_options(None, None).1
 }
 fn transactions_table_default_config() -> Options {
-    default_db_options(None, None).1
+    with_cf_compression("transactions", default_db_options(None, None).1)
 }
 fn certificates_table_default_config() -> Options {
-    default_db_options(None, None).1
+    with_cf_compression("certificates", default_db_options(None, None).1)
 }
 fn effects_table_default_config() -> Options {
-    default_db_options(None, None).1
+    with_cf_compression("effects", default_db_options(None, None).1)
 }
@@ -0,0 +1,305 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Input objects of queued certificates, read from the database ahead of their execution.
+//!
+//! A certificate synced by a node waits for its parents to execute before executing itself. Its
+//! inputs which are already stored are read in the meantime, so that the reads overlap with the
+//! execution of earlier certificates instead of delaying its own. The objects are kept until the
+//! certificate is executed or given up, within a bound on their total size: the inputs of a
+//! certificate which do not fit are read when it executes, as without prefetching.
+//!
+//! An object is kept at a single version. Certificates sharing an input, e.g. a package or a
+//! shared object, share its prefetched copy, which is dropped once all of them are done.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use sui_config::node::InputPrefetchConfig;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::object::Object;
+
+pub struct InputPrefetchMetrics {
+    hits: IntCounter,
+    misses: IntCounter,
+    skipped_full: IntCounter,
+    skipped_other_version: IntCounter,
+    size_bytes: IntGauge,
+    saved_read_time_us: IntCounter,
+}
+
+impl InputPrefetchMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            hits: register_int_counter_with_registry!(
+                "input_prefetch_hits",
+                "Number of input object reads served by objects prefetched for their certificate",
+                registry,
+            )
+            .unwrap(),
+            misses: register_int_counter_with_registry!(
+                "input_prefetch_misses",
+                "Number of input object reads not found in the prefetched objects",
+                registry,
+            )
+            .unwrap(),
+            skipped_full: register_int_counter_with_registry!(
+                "input_prefetch_skipped_full",
+                "Number of input objects not prefetched because the prefetched objects were full",
+                registry,
+            )
+            .unwrap(),
+            skipped_other_version: register_int_counter_with_registry!(
+                "input_prefetch_skipped_other_version",
+                "Number of input objects not prefetched because another version of the object \
+                 was prefetched already",
+                registry,
+            )
+            .unwrap(),
+            size_bytes: register_int_gauge_with_registry!(
+                "input_prefetch_size_bytes",
+                "Approximate size of the prefetched input objects",
+                registry,
+            )
+            .unwrap(),
+            saved_read_time_us: register_int_counter_with_registry!(
+                "input_prefetch_saved_read_time_us",
+                "Time spent reading the prefetched objects served to executions, which the \
+                 executions did not wait for",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+struct Prefetched {
+    object: Object,
+    /// Number of certificates the object was prefetched for which are not done yet.
+    refs: usize,
+    read_time: Duration,
+}
+
+struct Inner {
+    objects: HashMap<ObjectID, Prefetched>,
+    size_bytes: usize,
+}
+
+pub struct InputPrefetcher {
+    inner: Mutex<Inner>,
+    max_size_bytes: usize,
+    metrics: InputPrefetchMetrics,
+}
+
+impl InputPrefetcher {
+    pub fn new(config: &InputPrefetchConfig, metrics: InputPrefetchMetrics) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                objects: HashMap::new(),
+                size_bytes: 0,
+            }),
+            max_size_bytes: config.max_size_bytes,
+            metrics,
+        }
+    }
+
+    /// Returns the prefetched object at `version`, if any.
+    pub fn get(&self, object_id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        self.lookup(object_id, |object| object.version() == version)
+    }
+
+    /// Returns the prefetched package, if any. Packages are immutable, so any prefetched
+    /// version is the latest.
+    pub fn get_package(&self, object_id: &ObjectID) -> Option<Object> {
+        self.lookup(object_id, |object| object.is_package())
+    }
+
+    fn lookup(&self, object_id: &ObjectID, matches: impl Fn(&Object) -> bool) -> Option<Object> {
+        let found = match self.inner.lock().objects.get(object_id) {
+            Some(prefetched) if matches(&prefetched.object) => {
+                Some((prefetched.object.clone(), prefetched.read_time))
+            }
+            _ => None,
+        };
+        match found {
+            Some((object, read_time)) => {
+                self.metrics.hits.inc();
+                self.metrics
+                    .saved_read_time_us
+                    .inc_by(read_time.as_micros() as u64);
+                Some(object)
+            }
+            None => {
+                self.metrics.misses.inc();
+                None
+            }
+        }
+    }
+
+    /// Starts prefetching inputs for a certificate. The objects are kept until the returned
+    /// guard is dropped.
+    pub fn start(self: &Arc<Self>) -> PrefetchGuard {
+        PrefetchGuard {
+            prefetcher: self.clone(),
+            objects: vec![],
+        }
+    }
+
+    fn insert(&self, object: Object, read_time: Duration) -> bool {
+        let size = object.object_size_for_gas_metering();
+        let mut inner = self.inner.lock();
+        if inner.objects.contains_key(&object.id()) {
+            self.metrics.skipped_other_version.inc();
+            return false;
+        }
+        if inner.size_bytes + size > self.max_size_bytes {
+            self.metrics.skipped_full.inc();
+            return false;
+        }
+        inner.objects.insert(
+            object.id(),
+            Prefetched {
+                object,
+                refs: 1,
+                read_time,
+            },
+        );
+        inner.size_bytes += size;
+        self.metrics.size_bytes.set(inner.size_bytes as i64);
+        true
+    }
+
+    fn retain(&self, object_id: &ObjectID, version: Option<SequenceNumber>) -> bool {
+        match self.inner.lock().objects.get_mut(object_id) {
+            Some(prefetched)
+                if version.map_or(true, |version| prefetched.object.version() == version) =>
+            {
+                prefetched.refs += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn release(&self, object_ids: &[ObjectID]) {
+        let mut inner = self.inner.lock();
+        for object_id in object_ids {
+            let done = match inner.objects.get_mut(object_id) {
+                Some(prefetched) => {
+                    prefetched.refs -= 1;
+                    prefetched.refs == 0
+                }
+                None => false,
+            };
+            if done {
+                if let Some(removed) = inner.objects.remove(object_id) {
+                    inner.size_bytes -= removed.object.object_size_for_gas_metering();
+                }
+            }
+        }
+        self.metrics.size_bytes.set(inner.size_bytes as i64);
+    }
+}
+
+/// The inputs prefetched for a certificate, kept until it is dropped.
+pub struct PrefetchGuard {
+    prefetcher: Arc<InputPrefetcher>,
+    objects: Vec<ObjectID>,
+}
+
+impl PrefetchGuard {
+    /// Whether the object is prefetched already, at `version` if given, in which case it is
+    /// kept for this certificate too.
+    pub fn retain(&mut self, object_id: &ObjectID, version: Option<SequenceNumber>) -> bool {
+        let retained = self.prefetcher.retain(object_id, version);
+        if retained {
+            self.objects.push(*object_id);
+        }
+        retained
+    }
+
+    /// Keeps `object`, read from the database in `read_time`, for this certificate, unless it
+    /// does not fit.
+    pub fn insert(&mut self, object: Object, read_time: Duration) {
+        let object_id = object.id();
+        if self.prefetcher.insert(object, read_time) {
+            self.objects.push(object_id);
+        }
+    }
+}
+
+impl Drop for PrefetchGuard {
+    fn drop(&mut self) {
+        self.prefetcher.release(&self.objects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::SuiAddress;
+
+    fn object(id: ObjectID, version: u64) -> Object {
+        Object::with_id_owner_version_for_testing(
+            id,
+            SequenceNumber::from(version),
+            SuiAddress::random_for_testing_only(),
+        )
+    }
+
+    #[test]
+    fn test_prefetched_objects_are_kept_until_released() {
+        let shared = object(ObjectID::random(), 3);
+        let owned = object(ObjectID::random(), 1);
+        let size = shared.object_size_for_gas_metering();
+        let prefetcher = Arc::new(InputPrefetcher::new(
+            &InputPrefetchConfig {
+                max_size_bytes: 2 * size,
+            },
+            InputPrefetchMetrics::new_for_tests(),
+        ));
+
+        let mut first = prefetcher.start();
+        assert!(!first.retain(&shared.id(), Some(shared.version())));
+        first.insert(shared.clone(), Duration::from_micros(10));
+        first.insert(owned.clone(), Duration::from_micros(10));
+
+        // A second certificate shares the object at the same version only.
+        let mut second = prefetcher.start();
+        assert!(!second.retain(&shared.id(), Some(SequenceNumber::from(4))));
+        assert!(second.retain(&shared.id(), Some(shared.version())));
+        // Another version of the object is prefetched already.
+        second.insert(object(shared.id(), 4), Duration::from_micros(10));
+        // The prefetched objects are full.
+        second.insert(object(ObjectID::random(), 1), Duration::from_micros(10));
+
+        assert_eq!(
+            prefetcher.get(&owned.id(), owned.version()),
+            Some(owned.clone())
+        );
+        assert_eq!(prefetcher.get(&owned.id(), SequenceNumber::from(2)), None);
+        assert_eq!(prefetcher.get_package(&owned.id()), None);
+        assert_eq!(prefetcher.metrics.hits.get(), 1);
+        assert_eq!(prefetcher.metrics.misses.get(), 2);
+        assert_eq!(prefetcher.metrics.skipped_other_version.get(), 1);
+        assert_eq!(prefetcher.metrics.skipped_full.get(), 1);
+        assert_eq!(prefetcher.metrics.saved_read_time_us.get(), 10);
+
+        drop(first);
+        assert_eq!(prefetcher.get(&owned.id(), owned.version()), None);
+        assert!(prefetcher.get(&shared.id(), shared.version()).is_some());
+        drop(second);
+        assert_eq!(prefetcher.get(&shared.id(), shared.version()), None);
+        assert_eq!(prefetcher.metrics.size_bytes.get(), 0);
+    }
+}
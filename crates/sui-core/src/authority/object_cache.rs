@@ -0,0 +1,498 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A size-bounded cache of the latest versions of recently read objects, consulted by the
+//! authority store before reading the database. Hot objects, e.g. shared objects used by many
+//! transactions, are then read from memory while signing and executing transactions.
+//!
+//! The cache is kept up to date when effects are committed: the objects written are dropped from
+//! the cache before being written to the database, so that their previous versions are not read
+//! from the cache once the database has the new ones, and kept out of it until the write is
+//! committed. The new versions are then cached, and deleted or wrapped objects are cached as
+//! deleted. Objects read from the database before they were last written or invalidated are not
+//! cached, even once the version written was evicted from the cache, or when the invalidation made
+//! versions go back.
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use std::collections::HashMap;
+use sui_config::node::ObjectCacheConfig;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::object::Object;
+
+/// Approximate size of a cached deleted object.
+const DELETED_ENTRY_SIZE: usize = std::mem::size_of::<(ObjectID, SequenceNumber)>();
+
+/// Number of objects whose last removal from the cache is remembered. Reads taken before the
+/// removal of an object which was forgotten since are not cached.
+const MAX_TRACKED_REMOVALS: usize = 64 * 1024;
+
+#[derive(Clone, Debug)]
+enum CachedObject {
+    Object(Object),
+    /// The object was deleted or wrapped at this version.
+    Deleted(SequenceNumber),
+}
+
+impl CachedObject {
+    fn version(&self) -> SequenceNumber {
+        match self {
+            CachedObject::Object(object) => object.version(),
+            CachedObject::Deleted(version) => *version,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            CachedObject::Object(object) => object.object_size_for_gas_metering(),
+            CachedObject::Deleted(_) => DELETED_ENTRY_SIZE,
+        }
+    }
+}
+
+/// The result of a lookup of the latest version of an object in the cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheLookup {
+    /// The latest version of the object.
+    Found(Object),
+    /// The object was deleted or wrapped.
+    Deleted,
+    /// The object is not cached, the database must be read.
+    Miss,
+}
+
+pub struct ObjectCacheMetrics {
+    hits: IntCounter,
+    misses: IntCounter,
+    size_bytes: IntGauge,
+}
+
+impl ObjectCacheMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            hits: register_int_counter_with_registry!(
+                "object_cache_hits",
+                "Number of object reads served by the object cache",
+                registry,
+            )
+            .unwrap(),
+            misses: register_int_counter_with_registry!(
+                "object_cache_misses",
+                "Number of object reads not found in the object cache",
+                registry,
+            )
+            .unwrap(),
+            size_bytes: register_int_gauge_with_registry!(
+                "object_cache_size_bytes",
+                "Approximate size of the objects in the object cache",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+struct Inner {
+    objects: LruCache<ObjectID, CachedObject>,
+    size_bytes: usize,
+    /// Number of writes in progress for each object being written.
+    writing: HashMap<ObjectID, usize>,
+    /// Incremented each time objects are removed from the cache to be written or invalidated.
+    generation: u64,
+    /// The generation in which each recently removed object was last removed.
+    removed_at: LruCache<ObjectID, u64>,
+    /// The latest generation in which an object no longer in `removed_at` was removed.
+    forgotten_removals: u64,
+}
+
+impl Inner {
+    /// Removes the objects from the cache, and records that reads of them stamped before now may
+    /// be stale.
+    fn remove(&mut self, object_ids: impl IntoIterator<Item = ObjectID>) {
+        self.generation += 1;
+        for object_id in object_ids {
+            if let Some(removed) = self.objects.pop(&object_id) {
+                self.size_bytes -= removed.size();
+            }
+            self.removed_at.put(object_id, self.generation);
+            while self.removed_at.len() > MAX_TRACKED_REMOVALS {
+                match self.removed_at.pop_lru() {
+                    Some((_, generation)) => {
+                        self.forgotten_removals = self.forgotten_removals.max(generation)
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// Whether an object read with `stamp` may be an older version than the one in the database.
+    fn is_stale(&self, object_id: &ObjectID, ReadStamp(generation): ReadStamp) -> bool {
+        let removed_at = match self.removed_at.peek(object_id) {
+            Some(removed_at) => *removed_at,
+            None => self.forgotten_removals,
+        };
+        removed_at > generation || self.writing.contains_key(object_id)
+    }
+}
+
+/// Taken before reading an object from the database, so that the object is not cached if it was
+/// written or invalidated since.
+#[derive(Clone, Copy, Debug)]
+pub struct ReadStamp(u64);
+
+/// Objects being written to the database, which are kept out of the cache until the write is
+/// committed or, when the guard is dropped, abandoned.
+#[must_use]
+pub struct PendingWrite<'a> {
+    cache: &'a ObjectCache,
+    object_ids: Vec<ObjectID>,
+}
+
+impl PendingWrite<'_> {
+    /// Caches the objects written and deleted, once the write is committed to the database.
+    pub fn commit<'b>(
+        self,
+        written: impl IntoIterator<Item = &'b Object>,
+        deleted: impl IntoIterator<Item = (ObjectID, SequenceNumber)>,
+    ) {
+        for object in written {
+            self.cache
+                .insert(object.id(), CachedObject::Object(object.clone()), None);
+        }
+        for (object_id, version) in deleted {
+            self.cache
+                .insert(object_id, CachedObject::Deleted(version), None);
+        }
+    }
+}
+
+impl Drop for PendingWrite<'_> {
+    fn drop(&mut self) {
+        let mut inner = self.cache.inner.lock();
+        for object_id in &self.object_ids {
+            match inner.writing.get_mut(object_id) {
+                Some(writes) if *writes > 1 => *writes -= 1,
+                _ => {
+                    inner.writing.remove(object_id);
+                }
+            }
+        }
+    }
+}
+
+pub struct ObjectCache {
+    inner: Mutex<Inner>,
+    max_size_bytes: usize,
+    metrics: ObjectCacheMetrics,
+}
+
+impl ObjectCache {
+    pub fn new(config: &ObjectCacheConfig, metrics: ObjectCacheMetrics) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                objects: LruCache::unbounded(),
+                size_bytes: 0,
+                writing: HashMap::new(),
+                generation: 0,
+                removed_at: LruCache::unbounded(),
+                forgotten_removals: 0,
+            }),
+            max_size_bytes: config.max_size_bytes,
+            metrics,
+        }
+    }
+
+    /// Returns the latest version of the object, if it is cached.
+    pub fn get_latest(&self, object_id: &ObjectID) -> CacheLookup {
+        let lookup = match self.inner.lock().objects.get(object_id) {
+            Some(CachedObject::Object(object)) => CacheLookup::Found(object.clone()),
+            Some(CachedObject::Deleted(_)) => CacheLookup::Deleted,
+            None => CacheLookup::Miss,
+        };
+        self.record_lookup(lookup != CacheLookup::Miss);
+        lookup
+    }
+
+    /// Returns the object at `version`, if it is the cached version of the object.
+    pub fn get_by_version(&self, object_id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        let object = match self.inner.lock().objects.get(object_id) {
+            Some(CachedObject::Object(object)) if object.version() == version => {
+                Some(object.clone())
+            }
+            _ => None,
+        };
+        self.record_lookup(object.is_some());
+        object
+    }
+
+    /// To be taken before reading an object from the database, to cache it with
+    /// [ObjectCache::insert_read].
+    pub fn read_stamp(&self) -> ReadStamp {
+        ReadStamp(self.inner.lock().generation)
+    }
+
+    /// Caches an object read from the database after `stamp` was taken, unless a more recent
+    /// version is cached, or the object is being written or was written or invalidated since.
+    pub fn insert_read(&self, object: &Object, stamp: ReadStamp) {
+        self.insert(
+            object.id(),
+            CachedObject::Object(object.clone()),
+            Some(stamp),
+        );
+    }
+
+    /// Removes the objects from the cache before they are written to the database, and keeps
+    /// them out of it until the write is committed.
+    pub fn begin_write(&self, object_ids: impl IntoIterator<Item = ObjectID>) -> PendingWrite<'_> {
+        let object_ids: Vec<_> = object_ids.into_iter().collect();
+        let mut inner = self.inner.lock();
+        for object_id in &object_ids {
+            *inner.writing.entry(*object_id).or_default() += 1;
+        }
+        inner.remove(object_ids.iter().copied());
+        self.metrics.size_bytes.set(inner.size_bytes as i64);
+        PendingWrite {
+            cache: self,
+            object_ids,
+        }
+    }
+
+    /// Removes the objects from the cache, e.g. after reverting the effects which wrote them.
+    /// Objects read from the database before the invalidation are not cached afterwards.
+    pub fn invalidate(&self, object_ids: impl IntoIterator<Item = ObjectID>) {
+        let mut inner = self.inner.lock();
+        inner.remove(object_ids);
+        self.metrics.size_bytes.set(inner.size_bytes as i64);
+    }
+
+    fn insert(&self, object_id: ObjectID, entry: CachedObject, stamp: Option<ReadStamp>) {
+        let size = entry.size();
+        if size > self.max_size_bytes {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        // The object may have changed in the database since it was read.
+        if matches!(stamp, Some(stamp) if inner.is_stale(&object_id, stamp)) {
+            return;
+        }
+        if let Some(cached) = inner.objects.peek(&object_id) {
+            if cached.version() >= entry.version() {
+                return;
+            }
+        }
+        if let Some(replaced) = inner.objects.put(object_id, entry) {
+            inner.size_bytes -= replaced.size();
+        }
+        inner.size_bytes += size;
+        while inner.size_bytes > self.max_size_bytes {
+            match inner.objects.pop_lru() {
+                Some((_, evicted)) => inner.size_bytes -= evicted.size(),
+                None => break,
+            }
+        }
+        self.metrics.size_bytes.set(inner.size_bytes as i64);
+    }
+
+    fn record_lookup(&self, hit: bool) {
+        if hit {
+            self.metrics.hits.inc();
+        } else {
+            self.metrics.misses.inc();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use sui_types::base_types::SuiAddress;
+
+    fn cache(max_size_bytes: usize) -> ObjectCache {
+        ObjectCache::new(
+            &ObjectCacheConfig { max_size_bytes },
+            ObjectCacheMetrics::new_for_tests(),
+        )
+    }
+
+    fn object(id: ObjectID, version: u64) -> Object {
+        Object::with_id_owner_version_for_testing(
+            id,
+            SequenceNumber::from(version),
+            SuiAddress::random_for_testing_only(),
+        )
+    }
+
+    #[test]
+    fn test_cache_keeps_latest_version() {
+        let cache = cache(1024 * 1024);
+        let id = ObjectID::random();
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+
+        cache.begin_write([id]).commit([&object(id, 2)], []);
+        // A read of an older version does not replace the committed one.
+        cache.insert_read(&object(id, 1), cache.read_stamp());
+        match cache.get_latest(&id) {
+            CacheLookup::Found(cached) => assert_eq!(cached.version(), SequenceNumber::from(2)),
+            lookup => panic!("Unexpected lookup {lookup:?}"),
+        }
+        assert!(cache.get_by_version(&id, SequenceNumber::from(2)).is_some());
+        assert!(cache.get_by_version(&id, SequenceNumber::from(1)).is_none());
+
+        cache
+            .begin_write([id])
+            .commit([], [(id, SequenceNumber::from(3))]);
+        assert_eq!(cache.get_latest(&id), CacheLookup::Deleted);
+        cache.insert_read(&object(id, 2), cache.read_stamp());
+        assert_eq!(cache.get_latest(&id), CacheLookup::Deleted);
+
+        cache.invalidate([id]);
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let first = object(ObjectID::random(), 1);
+        let size = first.object_size_for_gas_metering();
+        let cache = cache(2 * size);
+        let second = object(ObjectID::random(), 1);
+        let third = object(ObjectID::random(), 1);
+
+        cache.insert_read(&first, cache.read_stamp());
+        cache.insert_read(&second, cache.read_stamp());
+        // Reading the first object makes the second one the least recently used.
+        cache.get_latest(&first.id());
+        cache.insert_read(&third, cache.read_stamp());
+
+        assert_ne!(cache.get_latest(&first.id()), CacheLookup::Miss);
+        assert_eq!(cache.get_latest(&second.id()), CacheLookup::Miss);
+        assert_ne!(cache.get_latest(&third.id()), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_cache_skips_reads_racing_with_writes() {
+        let cache = cache(1024 * 1024);
+        let id = ObjectID::random();
+        cache.insert_read(&object(id, 1), cache.read_stamp());
+
+        // The previous version is no longer served once the write starts, and reads of the
+        // database are not cached until the write is committed.
+        let pending = cache.begin_write([id]);
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+        cache.insert_read(&object(id, 1), cache.read_stamp());
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+        pending.commit([&object(id, 2)], []);
+        match cache.get_latest(&id) {
+            CacheLookup::Found(cached) => assert_eq!(cached.version(), SequenceNumber::from(2)),
+            lookup => panic!("Unexpected lookup {lookup:?}"),
+        }
+
+        // Abandoned writes no longer keep reads out of the cache.
+        drop(cache.begin_write([id]));
+        cache.insert_read(&object(id, 2), cache.read_stamp());
+        assert_ne!(cache.get_latest(&id), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_cache_skips_reads_from_before_invalidation() {
+        let cache = cache(1024 * 1024);
+        let id = ObjectID::random();
+
+        // Version 2 is read from the database, then reverted before being cached.
+        let stamp = cache.read_stamp();
+        let reverted = object(id, 2);
+        cache.invalidate([id]);
+        cache.insert_read(&reverted, stamp);
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+
+        cache.insert_read(&object(id, 1), cache.read_stamp());
+        assert_ne!(cache.get_latest(&id), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_cache_skips_reads_from_before_evicted_write() {
+        let stale = object(ObjectID::random(), 1);
+        let id = stale.id();
+        // The cache holds a single object.
+        let cache = cache(stale.object_size_for_gas_metering());
+
+        // Version 1 is read from the database, then version 2 is written and evicted from the
+        // cache before the read is cached.
+        let stamp = cache.read_stamp();
+        let other = object(ObjectID::random(), 1);
+        let other_stamp = cache.read_stamp();
+        cache.begin_write([id]).commit([&object(id, 2)], []);
+        cache.insert_read(&object(ObjectID::random(), 1), cache.read_stamp());
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+        cache.insert_read(&stale, stamp);
+        assert_eq!(cache.get_latest(&id), CacheLookup::Miss);
+
+        // Objects which were not written since they were read are still cached.
+        cache.insert_read(&other, other_stamp);
+        assert_ne!(cache.get_latest(&other.id()), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_concurrent_reads_and_writes() {
+        const VERSIONS: u64 = 500;
+        let cache = Arc::new(cache(1024 * 1024));
+        let id = ObjectID::random();
+        // The latest version of the object in the database.
+        let database = Arc::new(Mutex::new(object(id, 1)));
+        let written = Arc::new(AtomicU64::new(1));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                let database = database.clone();
+                let written = written.clone();
+                thread::spawn(move || {
+                    while written.load(Ordering::SeqCst) < VERSIONS {
+                        // A version written to the database before the lookup must not be
+                        // shadowed by a previous version in the cache.
+                        let latest = written.load(Ordering::SeqCst);
+                        match cache.get_latest(&id) {
+                            CacheLookup::Found(cached) => {
+                                assert!(cached.version() >= SequenceNumber::from(latest))
+                            }
+                            CacheLookup::Deleted => panic!("The object was never deleted"),
+                            CacheLookup::Miss => {
+                                let stamp = cache.read_stamp();
+                                let object = database.lock().clone();
+                                cache.insert_read(&object, stamp);
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for version in 2..=VERSIONS {
+            let new_object = object(id, version);
+            let pending = cache.begin_write([id]);
+            *database.lock() = new_object.clone();
+            written.store(version, Ordering::SeqCst);
+            pending.commit([&new_object], []);
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        match cache.get_latest(&id) {
+            CacheLookup::Found(cached) => {
+                assert_eq!(cached.version(), SequenceNumber::from(VERSIONS))
+            }
+            lookup => panic!("Unexpected lookup {lookup:?}"),
+        }
+    }
+}
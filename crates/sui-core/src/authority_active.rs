@@ -31,7 +31,11 @@
 
 use arc_swap::ArcSwap;
 use std::{collections::HashMap, ops::Deref, sync::Arc, time::Duration};
-use sui_types::{base_types::AuthorityName, error::SuiResult};
+use sui_network::channel_pool::ChannelPoolConfig;
+use sui_types::{
+    base_types::AuthorityName,
+    error::{SuiError, SuiResult},
+};
 use tokio::{
     sync::{oneshot, Mutex, MutexGuard},
     task::JoinHandle,
@@ -62,6 +66,12 @@ use checkpoint_driver::{checkpoint_process, get_latest_checkpoint_from_all, sync
 
 pub mod execution_driver;
 
+pub mod peer_monitor;
+use peer_monitor::{peer_monitor_process, PeerMonitorMetrics};
+
+pub mod endpoint_discovery;
+use endpoint_discovery::{endpoint_discovery_process, AuthorityEndpoints};
+
 use self::{checkpoint_driver::CheckpointProcessControl, execution_driver::execution_process};
 
 // TODO: Make these into a proper config
@@ -92,7 +102,10 @@ impl AuthorityHealth {
     /// Sets the no contact instant to be larger than what
     /// is currently recorded.
     pub fn set_no_contact_for(&mut self, period: Duration) {
-        let future_instant = Instant::now() + period;
+        let future_instant = match Instant::now().checked_add(period) {
+            Some(future_instant) => future_instant,
+            None => return,
+        };
         if self.no_contact_before < future_instant {
             self.no_contact_before = future_instant;
         }
@@ -134,6 +147,10 @@ pub struct ActiveAuthority<A> {
     // This is only meaningful if A is of type NetworkAuthorityClient,
     // and stored here for reconfiguration purposes.
     pub network_metrics: Arc<NetworkAuthorityClientMetrics>,
+    // The settings of the channel pools of the clients recreated on reconfiguration.
+    pub channel_pool_config: ChannelPoolConfig,
+    // The last known network address of each authority, and what it resolved to.
+    pub endpoints: Arc<AuthorityEndpoints>,
 }
 
 impl<A> ActiveAuthority<A> {
@@ -142,6 +159,7 @@ impl<A> ActiveAuthority<A> {
         net: AuthorityAggregator<A>,
         gossip_metrics: GossipMetrics,
         network_metrics: Arc<NetworkAuthorityClientMetrics>,
+        channel_pool_config: ChannelPoolConfig,
     ) -> SuiResult<Self> {
         let committee = authority.clone_committee();
 
@@ -160,6 +178,8 @@ impl<A> ActiveAuthority<A> {
             net: ArcSwap::from(net),
             gossip_metrics,
             network_metrics,
+            channel_pool_config,
+            endpoints: Default::default(),
         })
     }
 
@@ -176,6 +196,7 @@ impl<A> ActiveAuthority<A> {
             net,
             GossipMetrics::new_for_tests(),
             Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+            ChannelPoolConfig::default(),
         )
     }
 
@@ -205,6 +226,12 @@ impl<A> ActiveAuthority<A> {
     /// the`no contact` value to DELAY_FOR_1_RETRY_MS * EXPONENTIAL_DELAY_BASIS ^ retries, up to
     /// a maximum delay of MAX_RETRY_DELAY_MS.
     pub async fn set_failure_backoff(&self, name: AuthorityName) {
+        self.set_failure_backoff_with_jitter(name, 0.0).await
+    }
+
+    /// Like set_failure_backoff, but the delay is increased by up to `jitter` times itself at
+    /// random, so that nodes which lost an authority at the same time do not all retry at once.
+    pub async fn set_failure_backoff_with_jitter(&self, name: AuthorityName, jitter: f64) {
         let mut lock = self.health.lock().await;
         let mut entry = lock.entry(name).or_default();
         entry.retries = u32::min(entry.retries + 1, MAX_RETRIES_RECORDED);
@@ -212,7 +239,22 @@ impl<A> ActiveAuthority<A> {
             DELAY_FOR_1_RETRY_MS * u64::pow(EXPONENTIAL_DELAY_BASIS, entry.retries),
             MAX_RETRY_DELAY_MS,
         );
-        entry.set_no_contact_for(Duration::from_millis(delay));
+        let delay = Duration::from_millis(delay).mul_f64(1.0 + jitter * rand::random::<f64>());
+        entry.set_no_contact_for(delay);
+    }
+
+    /// Like set_failure_backoff, but if the authority failed because it is overloaded and told
+    /// how long to back off for, `no contact` is set to that delay instead.
+    pub async fn set_failure_backoff_for_error(&self, name: AuthorityName, error: &SuiError) {
+        match error.retry_after() {
+            Some(retry_after) => {
+                let mut lock = self.health.lock().await;
+                let mut entry = lock.entry(name).or_default();
+                entry.retries = u32::min(entry.retries + 1, MAX_RETRIES_RECORDED);
+                entry.set_no_contact_for(retry_after);
+            }
+            None => self.set_failure_backoff(name).await,
+        }
     }
 
     /// Resets retries to zero and sets no contact to zero delay.
@@ -242,6 +284,8 @@ impl<A> Clone for ActiveAuthority<A> {
             health: self.health.clone(),
             gossip_metrics: self.gossip_metrics.clone(),
             network_metrics: self.network_metrics.clone(),
+            channel_pool_config: self.channel_pool_config,
+            endpoints: self.endpoints.clone(),
         }
     }
 }
@@ -284,12 +328,41 @@ where
         let checkpoint_summary = match checkpoint_summary {
             Some(c) => c,
             None => {
-                info!(name = ?self.state.name, "no checkpoints found");
+                info!(authority = ?self.state.name, "no checkpoints found");
                 return Ok(());
             }
         };
+        self.state
+            .observe_highest_known_checkpoint(checkpoint_summary.summary.sequence_number);
+
+        let state = self.state.clone();
+        sync_to_checkpoint(self, checkpoint_store.clone(), checkpoint_summary).await?;
+        // Checkpoints are stored once their transactions were executed.
+        let latest_checkpoint = checkpoint_store.lock().latest_stored_checkpoint();
+        if let Some(checkpoint) = latest_checkpoint {
+            state.observe_executed_checkpoint(checkpoint.summary().sequence_number);
+        }
+        Ok(())
+    }
 
-        sync_to_checkpoint(self, checkpoint_store, checkpoint_summary).await
+    /// Spawn the process that syncs a full node to the latest checkpoint every `interval`. Node
+    /// sync executes the transactions of the network without storing checkpoints, so this is
+    /// what stores the checkpoints of a full node after it started.
+    pub async fn spawn_checkpoint_sync_process(
+        self: Arc<Self>,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(err) = self.clone().sync_to_latest_checkpoint().await {
+                    warn!(
+                        authority = ?self.state.name,
+                        "Failed to sync to latest checkpoint: {err}"
+                    );
+                }
+            }
+        })
     }
 
     /// Spawn gossip process
@@ -379,6 +452,16 @@ where
         })
     }
 
+    /// Spawn the process that probes the other authorities and tracks their reachability
+    pub async fn spawn_peer_monitor_process(
+        self: Arc<Self>,
+        metrics: PeerMonitorMetrics,
+    ) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            peer_monitor_process(self, metrics).await;
+        })
+    }
+
     pub async fn cancel_node_sync_process_for_tests(&self) {
         let mut lock_guard = self.node_sync_process.lock().await;
         Self::cancel_node_sync_process_impl(&mut lock_guard).await;
@@ -407,4 +490,12 @@ where
             checkpoint_process(self, &checkpoint_process_control, metrics).await;
         })
     }
+
+    /// Spawn the process that re-resolves the network addresses of the committee and replaces
+    /// the clients of the authorities whose endpoint changed
+    pub async fn spawn_endpoint_discovery_process(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            endpoint_discovery_process(self).await;
+        })
+    }
 }
@@ -41,6 +41,14 @@ use tracing::{debug, error, info, warn};
 pub(crate) mod tests;
 
 use super::ActiveAuthority;
+use crate::safe_client::spawn_verification;
+
+/// The number of checkpoints downloaded concurrently, and then verified together, when syncing.
+const SYNC_CHECKPOINT_WINDOW: usize = 64;
+
+/// The number of checkpoints whose signatures are verified in a single batch. The batches of a
+/// sync window are verified in parallel.
+const VERIFY_CHECKPOINT_BATCH_SIZE: usize = 8;
 
 #[derive(Clone, Debug)]
 pub struct CheckpointProcessControl {
@@ -169,6 +177,11 @@ pub async fn checkpoint_process<A>(
         let result = checkpoint_process_step(active_authority.clone(), timing).await;
         let state_checkpoints = &active_authority.state.checkpoints;
         let next_cp_seq = state_checkpoints.lock().next_checkpoint();
+        if next_cp_seq > 0 {
+            active_authority
+                .state
+                .observe_executed_checkpoint(next_cp_seq - 1);
+        }
         match result {
             Ok(result) => {
                 match result {
@@ -296,6 +309,9 @@ where
             "Highest Checkpoint Certificate from the network: {}",
             checkpoint
         );
+        active_authority
+            .state
+            .observe_highest_known_checkpoint(checkpoint.summary.sequence_number);
         // Check if there are more historic checkpoints to catch up with
         let next_checkpoint = state_checkpoints.lock().next_checkpoint();
         // First sync until before the latest checkpoint. We will special
@@ -431,7 +447,15 @@ where
             transactions.iter(),
             active_authority.state.database.clone(),
             next_epoch_committee,
-        )
+        )?;
+
+    if let Err(err) = active_authority
+        .state
+        .record_checkpoint_gas_prices(seq, transactions.iter())
+    {
+        warn!(checkpoint = ?seq, "Failed to record checkpoint gas prices: {}", err);
+    }
+    Ok(())
 }
 
 /// Obtain the highest checkpoint certificate from all validators.
@@ -572,7 +596,7 @@ where
                     &net.committee,
                 );
                 if let Ok(cert) = certificate {
-                    debug!(cp_seq=?seq, "A checkpoint certificate is formed from the network");
+                    debug!(checkpoint = ?seq, "A checkpoint certificate is formed from the network");
                     highest_certificate_cert = Some(cert);
                 }
             }
@@ -656,6 +680,7 @@ where
                 committee,
                 checkpoint,
                 &contents,
+                false,
             )
             .await?;
             info!(
@@ -694,7 +719,7 @@ where
     // so download a full certificate for it.
     if let Some(AuthenticatedCheckpoint::Signed(signed)) = &latest_checkpoint {
         let seq = *signed.summary.sequence_number();
-        debug!(name = ?state.name, ?seq, "Partial Sync",);
+        debug!(authority = ?state.name, checkpoint = ?seq, "Partial Sync",);
         let (past, _) = get_one_checkpoint(net.clone(), seq, false, &available_authorities).await?;
 
         checkpoint_db
@@ -706,30 +731,91 @@ where
         .map(|chk| chk.summary().sequence_number + 1)
         .unwrap_or(0);
 
-    for seq in full_sync_start..latest_known_checkpoint.summary.sequence_number {
-        debug!(name = ?state.name, ?seq, "Full Sync",);
-        let (past, contents) =
-            get_one_checkpoint_with_contents(net.clone(), seq, &available_authorities).await?;
-
-        process_new_checkpoint_certificate(
-            active_authority.clone(),
-            &checkpoint_db,
-            &net.committee,
-            &past,
-            &contents,
+    // Checkpoints are downloaded and verified a window at a time, and then executed in order.
+    let full_sync_end = latest_known_checkpoint.summary.sequence_number;
+    let committee = Arc::new(net.committee.clone());
+    let mut window_start = full_sync_start;
+    while window_start < full_sync_end {
+        let window = window_start..full_sync_end.min(window_start + SYNC_CHECKPOINT_WINDOW as u64);
+        debug!(authority = ?state.name, checkpoints = ?window, "Full Sync",);
+        let checkpoints =
+            futures::future::try_join_all(window.clone().map(|seq| {
+                get_one_checkpoint_with_contents(net.clone(), seq, &available_authorities)
+            }))
+            .await?;
+        let checkpoints = Arc::new(checkpoints);
+        verify_checkpoints_in_parallel(
+            committee.clone(),
+            checkpoints.clone(),
+            VERIFY_CHECKPOINT_BATCH_SIZE,
         )
         .await?;
+
+        for (past, contents) in checkpoints.iter() {
+            process_new_checkpoint_certificate(
+                active_authority.clone(),
+                &checkpoint_db,
+                &net.committee,
+                past,
+                contents,
+                true,
+            )
+            .await?;
+        }
+        window_start = window.end;
     }
 
     Ok(())
 }
 
+/// Verifies certificates of the epoch of `committee` along with their contents, in batches of
+/// `batch_size` checkpoints whose signatures are verified together. Batches are verified in
+/// parallel with `spawn_verification`. The checkpoints of a batch failing verification are
+/// verified one by one, so that the error names the invalid checkpoint.
+pub async fn verify_checkpoints_in_parallel(
+    committee: Arc<Committee>,
+    checkpoints: Arc<Vec<(CertifiedCheckpointSummary, CheckpointContents)>>,
+    batch_size: usize,
+) -> SuiResult {
+    let batch_size = batch_size.max(1);
+    let batches = (0..checkpoints.len()).step_by(batch_size).map(|start| {
+        let committee = committee.clone();
+        let checkpoints = checkpoints.clone();
+        spawn_verification(move || {
+            let batch = &checkpoints[start..checkpoints.len().min(start + batch_size)];
+            CertifiedCheckpointSummary::verify_batch(
+                batch
+                    .iter()
+                    .map(|(checkpoint, contents)| (checkpoint, Some(contents))),
+                &committee,
+            )
+            .or_else(|_| {
+                batch.iter().try_for_each(|(checkpoint, contents)| {
+                    checkpoint
+                        .verify(&committee, Some(contents))
+                        .map_err(|err| SuiError::CheckpointingError {
+                            error: format!(
+                                "Invalid checkpoint {}: {err}",
+                                checkpoint.summary.sequence_number
+                            ),
+                        })
+                })
+            })
+        })
+    });
+    futures::future::try_join_all(batches).await?;
+    Ok(())
+}
+
+/// Executes the transactions of a checkpoint certificate and stores it. The certificate and its
+/// contents are verified first, unless `verified` says this was already done.
 async fn process_new_checkpoint_certificate<A>(
     active_authority: Arc<ActiveAuthority<A>>,
     checkpoint_db: &Arc<Mutex<CheckpointStore>>,
     committee: &Committee,
     checkpoint_cert: &CertifiedCheckpointSummary,
     contents: &CheckpointContents,
+    verified: bool,
 ) -> SuiResult
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
@@ -752,13 +838,38 @@ where
 
     if !errors.is_empty() {
         let error = "Failed to sync transactions in checkpoint".to_string();
-        error!(cp_seq=?checkpoint_cert.summary.sequence_number, "{}", error);
+        error!(checkpoint = ?checkpoint_cert.summary.sequence_number, "{}", error);
         return Err(SuiError::CheckpointingError { error });
     }
 
-    checkpoint_db
-        .lock()
-        .process_synced_checkpoint_certificate(checkpoint_cert, contents, committee)
+    // The certificate is signed by a quorum, but it must also commit to the objects written by
+    // its transactions as executed by this node, or this node has forked.
+    checkpoint_db.lock().verify_state_digest(
+        &checkpoint_cert.summary,
+        contents,
+        active_authority.state.database.clone(),
+    )?;
+
+    if verified {
+        checkpoint_db
+            .lock()
+            .process_verified_checkpoint_certificate(checkpoint_cert, contents, committee)?;
+    } else {
+        checkpoint_db.lock().process_synced_checkpoint_certificate(
+            checkpoint_cert,
+            contents,
+            committee,
+        )?;
+    }
+
+    let seq = checkpoint_cert.summary.sequence_number;
+    if let Err(err) = active_authority
+        .state
+        .record_checkpoint_gas_prices(seq, contents.iter())
+    {
+        warn!(checkpoint = ?seq, "Failed to record checkpoint gas prices: {}", err);
+    }
+    Ok(())
 }
 
 pub async fn get_one_checkpoint_with_contents<A>(
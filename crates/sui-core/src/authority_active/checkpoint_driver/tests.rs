@@ -87,6 +87,69 @@ async fn checkpoint_active_flow_happy_path() {
     }
 }
 
+#[sim_test]
+async fn checkpoint_sync_updates_sync_watermarks() {
+    use telemetry_subscribers::init_for_testing;
+    init_for_testing();
+
+    let setup = checkpoint_tests_setup(5, Duration::from_millis(200), true).await;
+
+    let TestSetup {
+        committee: _committee,
+        authorities,
+        mut transactions,
+        aggregator,
+    } = setup;
+
+    // The last authority does not run the checkpoint process, and catches up as a full node
+    // does instead.
+    let (follower, others) = authorities.split_last().unwrap();
+    for inner_state in others {
+        let active_state = Arc::new(
+            ActiveAuthority::new_with_ephemeral_storage_for_test(
+                inner_state.authority.clone(),
+                aggregator.clone(),
+            )
+            .unwrap(),
+        );
+        let _active_handle = active_state
+            .spawn_checkpoint_process(CheckpointMetrics::new_for_tests())
+            .await;
+    }
+
+    while let Some(t) = transactions.pop() {
+        let (_cert, effects) = aggregator.execute_transaction(&t).await.expect("All ok.");
+        assert!(matches!(
+            effects.effects.status,
+            ExecutionStatus::Success { .. }
+        ));
+        tokio::time::sleep(Duration::from_secs(27)).await;
+    }
+    tokio::time::sleep(Duration::from_secs(20 * 60)).await;
+
+    let state = follower.authority.clone();
+    assert_eq!(state.sync_watermarks().highest_executed_checkpoint, None);
+    let active_state = Arc::new(
+        ActiveAuthority::new_with_ephemeral_storage_for_test(state.clone(), aggregator.clone())
+            .unwrap(),
+    );
+    active_state.sync_to_latest_checkpoint().await.unwrap();
+
+    let latest_checkpoint = state
+        .checkpoints
+        .lock()
+        .latest_stored_checkpoint()
+        .unwrap()
+        .summary()
+        .sequence_number;
+    let watermarks = state.sync_watermarks();
+    assert_eq!(
+        watermarks.highest_executed_checkpoint,
+        Some(latest_checkpoint)
+    );
+    assert!(watermarks.highest_known_checkpoint >= Some(latest_checkpoint));
+}
+
 #[sim_test]
 async fn checkpoint_active_flow_crash_client_with_gossip() {
     use telemetry_subscribers::init_for_testing;
@@ -0,0 +1,290 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validators may move to a new host during an epoch, either by updating the DNS name they
+//! advertise on chain or, from the next epoch on, by changing the address itself. The endpoint
+//! discovery process periodically reads the network addresses of the committee from the system
+//! state, re-resolves the DNS names among them, and replaces the client of every authority whose
+//! endpoint changed, so that clients follow validators without having to be restarted.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use fastcrypto::traits::ToFromBytes;
+use multiaddr::{Multiaddr, Protocol};
+use parking_lot::Mutex;
+use sui_config::genesis::Genesis;
+use sui_network::channel_pool::ChannelPool;
+use sui_network::default_mysten_network_config;
+use sui_types::base_types::AuthorityName;
+use sui_types::crypto::AuthorityPublicKeyBytes;
+use sui_types::error::SuiResult;
+use sui_types::sui_system_state::SuiSystemState;
+use tracing::{debug, error, info, warn};
+
+use super::ActiveAuthority;
+use crate::authority_client::{parse_net_addresses, AuthorityAPI};
+use crate::epoch::reconfiguration::Reconfigurable;
+
+/// Interval between two re-resolutions of the committee endpoints.
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorityEndpoint {
+    /// The network address of the authority followed by its backup addresses, as found on chain.
+    pub addresses: Vec<Multiaddr>,
+    /// The socket addresses the DNS name of each of `addresses` resolved to, if it has one.
+    pub resolved: Vec<Option<BTreeSet<SocketAddr>>>,
+}
+
+/// The endpoints the current clients of the authority aggregator were created for.
+#[derive(Default)]
+pub struct AuthorityEndpoints {
+    endpoints: Mutex<HashMap<AuthorityName, AuthorityEndpoint>>,
+}
+
+impl AuthorityEndpoints {
+    pub fn get(&self, name: &AuthorityName) -> Option<AuthorityEndpoint> {
+        self.endpoints.lock().get(name).cloned()
+    }
+
+    pub fn insert(
+        &self,
+        name: AuthorityName,
+        addresses: Vec<Multiaddr>,
+        resolved: Vec<Option<BTreeSet<SocketAddr>>>,
+    ) {
+        self.endpoints.lock().insert(
+            name,
+            AuthorityEndpoint {
+                addresses,
+                resolved,
+            },
+        );
+    }
+
+    /// Like `insert`, for addresses whose DNS names were not resolved.
+    pub fn insert_unresolved(&self, name: AuthorityName, addresses: Vec<Multiaddr>) {
+        let resolved = vec![None; addresses.len()];
+        self.insert(name, addresses, resolved);
+    }
+
+    /// Whether the client of `name` was created for addresses other than `addresses`. The
+    /// addresses of an authority without a recorded endpoint are not known, so they may have
+    /// changed.
+    pub fn address_changed(&self, name: &AuthorityName, addresses: &[Multiaddr]) -> bool {
+        self.endpoints
+            .lock()
+            .get(name)
+            .map_or(true, |endpoint| endpoint.addresses != addresses)
+    }
+
+    /// Records the addresses of the genesis validators, for clients created from the genesis.
+    pub fn insert_from_genesis(&self, genesis: &Genesis) {
+        self.insert_from_system_state(&genesis.sui_system_object());
+    }
+
+    /// Records the addresses of the active validators, for clients created from the system
+    /// state.
+    pub fn insert_from_system_state(&self, system_state: &SuiSystemState) {
+        for validator in &system_state.validators.active_validators {
+            let metadata = &validator.metadata;
+            let name = AuthorityPublicKeyBytes::from_bytes(&metadata.pubkey_bytes);
+            let addresses =
+                parse_net_addresses(&metadata.net_address, &metadata.backup_net_addresses);
+            if let (Ok(name), Ok(addresses)) = (name, addresses) {
+                self.insert_unresolved(name, addresses);
+            }
+        }
+    }
+}
+
+/// Resolves the DNS name of `address`, if it has one. Returns None for addresses without a DNS
+/// name or a TCP port, and for names that fail to resolve.
+pub async fn resolve_dns(address: &Multiaddr) -> Option<BTreeSet<SocketAddr>> {
+    let mut host = None;
+    let mut port = None;
+    for protocol in address.iter() {
+        match protocol {
+            Protocol::Dns(name) | Protocol::Dns4(name) | Protocol::Dns6(name) => {
+                host = Some(name.to_string())
+            }
+            Protocol::Tcp(p) => port = Some(p),
+            _ => {}
+        }
+    }
+    let (host, port) = (host?, port?);
+    match tokio::net::lookup_host((host.as_str(), port)).await {
+        Ok(addrs) => Some(addrs.collect()),
+        Err(err) => {
+            warn!(?address, "Failed to resolve network address: {:?}", err);
+            None
+        }
+    }
+}
+
+pub async fn endpoint_discovery_process<A>(active_authority: Arc<ActiveAuthority<A>>)
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone + Reconfigurable,
+{
+    if !A::needs_network_recreation() {
+        return;
+    }
+    info!("Start endpoint discovery process.");
+    loop {
+        if let Err(err) = refresh_endpoints(&active_authority).await {
+            error!("Failed to refresh the committee endpoints: {:?}", err);
+        }
+        tokio::time::sleep(DISCOVERY_INTERVAL).await;
+    }
+}
+
+/// Re-resolves the endpoints of the current committee, and replaces the clients of the
+/// authorities whose endpoint changed. Returns the number of replaced clients.
+async fn refresh_endpoints<A>(active_authority: &ActiveAuthority<A>) -> SuiResult<usize>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone + Reconfigurable,
+{
+    let system_state = active_authority.state.get_sui_system_state_object().await?;
+    let net = active_authority.agg_aggregator();
+    // The system state may be ahead of the aggregator during an epoch change, which updates
+    // the clients itself.
+    if system_state.epoch != net.committee.epoch {
+        return Ok(0);
+    }
+
+    let net_config = default_mysten_network_config();
+    let mut replaced = BTreeMap::new();
+    let mut replaced_endpoints = vec![];
+    for validator in system_state.validators.active_validators {
+        let metadata = validator.metadata;
+        let name = match AuthorityPublicKeyBytes::from_bytes(&metadata.pubkey_bytes) {
+            Ok(name) => name,
+            Err(err) => {
+                error!("Error parsing validator public key: {:?}", err);
+                continue;
+            }
+        };
+        if !net.authority_clients.contains_key(&name) {
+            continue;
+        }
+        let addresses = match parse_net_addresses(
+            &metadata.net_address,
+            &metadata.backup_net_addresses,
+        ) {
+            Ok(addresses) => addresses,
+            Err(err) => {
+                error!(authority = ?name.concise(), "Error parsing validator network address: {:?}", err);
+                continue;
+            }
+        };
+        let mut resolved = Vec::with_capacity(addresses.len());
+        for address in &addresses {
+            resolved.push(resolve_dns(address).await);
+        }
+
+        let previous = active_authority.endpoints.get(&name);
+        let changed = match &previous {
+            // The client was created from the same committee information, so only keep track
+            // of where it points to.
+            None => false,
+            Some(previous) if previous.addresses != addresses => true,
+            // A failed resolution does not mean the validator moved.
+            Some(previous) => {
+                previous
+                    .resolved
+                    .iter()
+                    .zip(&resolved)
+                    .any(|resolutions| match resolutions {
+                        (Some(previous), Some(current)) => previous != current,
+                        _ => false,
+                    })
+            }
+        };
+        if !changed {
+            // Keep the previous resolution of the names that failed to resolve.
+            let resolved = match previous {
+                Some(previous) => resolved
+                    .into_iter()
+                    .zip(previous.resolved)
+                    .map(|(current, previous)| current.or(previous))
+                    .collect(),
+                None => resolved,
+            };
+            active_authority.endpoints.insert(name, addresses, resolved);
+            continue;
+        }
+
+        let channels = match ChannelPool::connect_lazy_with_failover(
+            &net_config,
+            &active_authority.channel_pool_config,
+            &addresses,
+        ) {
+            Ok(channels) => channels,
+            Err(err) => {
+                error!(authority = ?name.concise(), ?addresses, "Error connecting to validator: {:?}", err);
+                continue;
+            }
+        };
+        info!(authority = ?name.concise(), ?addresses, ?resolved, "Validator endpoint changed");
+        replaced.insert(
+            name,
+            A::recreate(channels, active_authority.network_metrics.clone()),
+        );
+        replaced_endpoints.push((name, addresses, resolved));
+    }
+
+    let count = replaced.len();
+    if count == 0 {
+        debug!("Committee endpoints unchanged");
+        return Ok(0);
+    }
+    // The aggregator may have been replaced while resolving, e.g. by a reconfiguration, in which
+    // case the clients are not replaced, and the endpoints are resolved again in the next round.
+    let previous = active_authority
+        .net
+        .compare_and_swap(&net, Arc::new(net.with_replaced_clients(replaced)));
+    if !Arc::ptr_eq(&*previous, &net) {
+        info!("Authority aggregator replaced while refreshing the committee endpoints");
+        return Ok(0);
+    }
+    for (name, addresses, resolved) in replaced_endpoints {
+        active_authority.endpoints.insert(name, addresses, resolved);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+
+    #[tokio::test]
+    async fn test_resolve_dns() {
+        let address: Multiaddr = "/dns/localhost/tcp/8080/http".parse().unwrap();
+        let resolved = resolve_dns(&address).await.unwrap();
+        assert!(resolved.contains(&"127.0.0.1:8080".parse().unwrap()));
+
+        // Addresses without a DNS name are not resolved.
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/8080/http".parse().unwrap();
+        assert!(resolve_dns(&address).await.is_none());
+    }
+
+    #[test]
+    fn test_address_changed() {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        let name: AuthorityName = key.public().into();
+        let first: Multiaddr = "/dns/validator-1/tcp/8080/http".parse().unwrap();
+        let second: Multiaddr = "/dns/validator-2/tcp/8080/http".parse().unwrap();
+
+        let endpoints = AuthorityEndpoints::default();
+        assert!(endpoints.address_changed(&name, &[second.clone()]));
+        endpoints.insert_unresolved(name, vec![first.clone()]);
+        assert!(!endpoints.address_changed(&name, &[first.clone()]));
+        assert!(endpoints.address_changed(&name, &[second.clone()]));
+        // So is a change of the backup addresses.
+        assert!(endpoints.address_changed(&name, &[first, second]));
+    }
+}
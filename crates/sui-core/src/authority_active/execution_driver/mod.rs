@@ -53,13 +53,19 @@ where
 {
     info!("Start pending certificates execution process.");
 
-    // Loop whenever there is a signal that a new transactions is ready to process.
+    // Loop whenever there is a signal that a new transactions is ready to process. The first
+    // iteration does not wait, so that a restarted process executes the transactions left
+    // pending by the previous one.
+    let mut wait_for_pending = false;
     loop {
         // NOTE: nothing terrible happens if we fire more often than there are
         //       transactions awaiting execution, or less often than once per transactions.
         //       However, we need to be sure that if there is an awaiting trasnactions we
         //       will eventually fire the notification and wake up here.
-        active_authority.state.database.wait_for_new_pending().await;
+        if wait_for_pending {
+            active_authority.state.database.wait_for_new_pending().await;
+        }
+        wait_for_pending = true;
 
         debug!("Pending certificate execution activated.");
 
@@ -18,16 +18,19 @@ use std::sync::Once;
 use sui_adapter::genesis;
 use sui_types::base_types::*;
 use sui_types::batch::{AuthorityBatch, SignedBatch, UpdateItem};
+use sui_types::bridge::{BridgeAttestationRequest, BridgeAttestationResponse};
 use sui_types::committee::Committee;
 use sui_types::crypto::{get_key_pair, AuthorityKeyPair};
 use sui_types::error::SuiError;
 use sui_types::messages::{
     AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
     CertifiedTransaction, CheckpointStreamRequest, CommitteeInfoRequest, CommitteeInfoResponse,
-    ObjectInfoRequest, ObjectInfoResponse, Transaction, TransactionInfoRequest,
-    TransactionInfoResponse,
+    EffectsInfoRequest, EffectsInfoResponse, ObjectInfoRequest, ObjectInfoResponse, Transaction,
+    TransactionInfoRequest, TransactionInfoResponse, TransactionsInfoRequest,
+    TransactionsInfoResponse,
 };
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 use sui_types::object::Object;
 
 static mut SHOULD_FAIL: bool = true;
@@ -132,6 +135,20 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         self.state.handle_transaction_info_request(request).await
     }
 
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        self.state.handle_transactions_info_request(request).await
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        self.state.handle_effects_info_request(request).await
+    }
+
     /// Handle Batch information requests for this authority.
     async fn handle_batch_stream(
         &self,
@@ -211,6 +228,20 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
     ) -> Result<CommitteeInfoResponse, SuiError> {
         self.state.handle_committee_info_request(&request)
     }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        self.state.handle_bridge_attestation_request(&request)
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        self.state.handle_network_info_request(&request)
+    }
 }
 
 #[cfg(test)]
@@ -224,7 +255,10 @@ pub async fn init_configurable_authorities(
     use fastcrypto::traits::KeyPair;
     use sui_types::crypto::AccountKeyPair;
 
-    use crate::{authority_client::NetworkAuthorityClientMetrics, safe_client::SafeClientMetrics};
+    use crate::{
+        authority_client::NetworkAuthorityClientMetrics,
+        safe_client::{SafeClientMetrics, VerifiedCertificateCache},
+    };
 
     let authority_count = 4;
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -265,6 +299,7 @@ pub async fn init_configurable_authorities(
         clients.push(SafeClient::new(
             client,
             committee_store,
+            Arc::new(VerifiedCertificateCache::new_for_tests()),
             authority_name,
             Arc::new(SafeClientMetrics::new_for_tests()),
         ));
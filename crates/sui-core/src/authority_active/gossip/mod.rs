@@ -1,7 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{authority::AuthorityState, authority_client::AuthorityAPI, safe_client::SafeClient};
+use crate::{
+    authority::AuthorityState,
+    authority_client::AuthorityAPI,
+    safe_client::{BatchStreamCursor, SafeClient},
+};
 use async_trait::async_trait;
 use futures::{
     future::BoxFuture,
@@ -20,9 +24,7 @@ use sui_types::{
     base_types::{AuthorityName, ExecutionDigests},
     batch::{TxSequenceNumber, UpdateItem},
     error::{SuiError, SuiResult},
-    messages::{
-        BatchInfoRequest, BatchInfoResponseItem, TransactionInfoRequest, TransactionInfoResponse,
-    },
+    messages::{BatchInfoResponseItem, TransactionInfoRequest, TransactionInfoResponse},
 };
 use tap::TapFallible;
 use tracing::{debug, error, info, trace};
@@ -250,7 +252,9 @@ async fn wait_for_one_gossip_task_to_finish<A>(
 {
     let (finished_name, result) = gossip_tasks.select_next_some().await;
     if let Err(err) = result {
-        active_authority.set_failure_backoff(finished_name).await;
+        active_authority
+            .set_failure_backoff_for_error(finished_name, &err)
+            .await;
         active_authority.state.metrics.gossip_task_error_count.inc();
         error!(peer = ?finished_name, "Peer returned error: {:?}", err);
     } else {
@@ -409,11 +413,14 @@ where
         let mut timeout = Box::pin(tokio::time::sleep(duration));
         let mut results = FuturesOrdered::new();
 
-        let req = BatchInfoRequest {
-            start: None,
-            length: REQUEST_FOLLOW_NUM_DIGESTS,
-        };
-        let mut streamx = Box::pin(self.client.handle_batch_stream(req).await?);
+        // Streams start from the latest batch, and resume from the last batch validated once
+        // reconnected, so that the transactions executed meanwhile are not missed.
+        let cursor = BatchStreamCursor::default();
+        let mut streamx = Box::pin(
+            self.client
+                .resume_batch_stream(&cursor, REQUEST_FOLLOW_NUM_DIGESTS)
+                .await?,
+        );
         let metrics = handler.get_metrics();
         let mut timer = metrics.follower_stream_duration.start_timer();
 
@@ -459,11 +466,11 @@ where
                             info!(peer = ?self.peer_name, "Gossip stream was closed. Restarting");
                             self.client.metrics_total_times_reconnect_follower_stream.inc();
                             tokio::time::sleep(Duration::from_secs(REFRESH_FOLLOWER_PERIOD_SECS / 12)).await;
-                            let req = BatchInfoRequest {
-                                start: None,
-                                length: REQUEST_FOLLOW_NUM_DIGESTS,
-                            };
-                            streamx = Box::pin(self.client.handle_batch_stream(req).await?);
+                            streamx = Box::pin(
+                                self.client
+                                    .resume_batch_stream(&cursor, REQUEST_FOLLOW_NUM_DIGESTS)
+                                    .await?,
+                            );
                         },
                     }
                 },
@@ -8,6 +8,7 @@ use crate::authority_active::gossip::configurable_batch_action_client::{
 use crate::authority_active::MAX_RETRY_DELAY_MS;
 use crate::authority_aggregator::AuthorityAggregator;
 use std::time::Duration;
+use sui_types::messages::BatchInfoRequest;
 use tokio::task::JoinHandle;
 
 #[tokio::test(flavor = "current_thread", start_paused = true)]
@@ -87,19 +88,13 @@ pub async fn test_gossip_after_revert() {
     // 2. For the second transaction, all validators revert it.
     for state in &states {
         if state.get_transaction(digests[0].transaction).await.is_ok() {
-            state
-                .database
-                .revert_state_update(&digests[0].transaction)
-                .unwrap();
+            state.revert_state_update(&digests[0].transaction).unwrap();
             break;
         }
     }
     for state in &states {
         if state.get_transaction(digests[1].transaction).await.is_ok() {
-            state
-                .database
-                .revert_state_update(&digests[1].transaction)
-                .unwrap();
+            state.revert_state_update(&digests[1].transaction).unwrap();
         }
     }
 
@@ -152,6 +147,29 @@ pub async fn test_gossip_after_revert() {
     assert_eq!(all_batch_item_counts.iter().filter(|c| *c == &2).count(), 1);
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+pub async fn test_resume_batch_stream() {
+    let action_sequence = vec![BatchAction::EmitUpdateItem(), BatchAction::EmitUpdateItem()];
+    let (net, _states, _digests) = init_configurable_authorities(action_sequence).await;
+    // The first authority executed the transactions.
+    let client = net.authority_clients.values().next().unwrap();
+
+    let cursor = BatchStreamCursor::default();
+    let stream = client.resume_batch_stream(&cursor, 10).await.unwrap();
+    let items: Vec<_> = stream.collect().await;
+    assert!(items.iter().all(|item| item.is_ok()));
+    assert_eq!(cursor.next_sequence_number(), Some(2));
+
+    // The test client streams from the initial batch whatever the request, which is not the
+    // batch the stream resumes from.
+    let mut stream = client.resume_batch_stream(&cursor, 10).await.unwrap();
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(SuiError::ByzantineAuthoritySuspicion { .. }))
+    ));
+    assert_eq!(cursor.next_sequence_number(), Some(2));
+}
+
 async fn start_gossip_process(
     states: Vec<Arc<AuthorityState>>,
     net: AuthorityAggregator<ConfigurableBatchActionClient>,
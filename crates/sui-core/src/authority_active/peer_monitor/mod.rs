@@ -0,0 +1,167 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The peer monitor periodically probes every other authority of the committee with a cheap
+//! request, so that dead authorities are noticed before a client request times out on them.
+//! A failed probe puts the authority in backoff, during which it is not probed again, and marks
+//! it unreachable in the aggregator, which then tries it last. The next probe after the backoff
+//! re-establishes the channel to the authority if it came back.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use prometheus::{
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, HistogramVec, IntCounterVec, IntGaugeVec, Registry,
+};
+use sui_types::base_types::AuthorityName;
+use sui_types::messages::CommitteeInfoRequest;
+use tokio::time::{timeout, Instant};
+use tracing::{debug, info};
+
+use super::ActiveAuthority;
+use crate::authority_client::AuthorityAPI;
+
+#[cfg(test)]
+mod tests;
+
+/// Interval between two probes of a reachable authority.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// Time an authority has to answer a probe before it is considered unreachable.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Fraction of the failure backoff added at random, to spread reconnection attempts.
+const BACKOFF_JITTER: f64 = 0.5;
+
+const PROBE_LATENCY_SEC_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5.];
+
+#[derive(Clone)]
+pub struct PeerMonitorMetrics {
+    pub peer_reachable: IntGaugeVec,
+    pub peer_probe_failures: IntCounterVec,
+    pub peer_probe_latency: HistogramVec,
+}
+
+impl PeerMonitorMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            peer_reachable: register_int_gauge_vec_with_registry!(
+                "peer_monitor_peer_reachable",
+                "Whether the last probe of an authority succeeded.",
+                &["peer"],
+                registry,
+            )
+            .unwrap(),
+            peer_probe_failures: register_int_counter_vec_with_registry!(
+                "peer_monitor_probe_failures",
+                "Total number of failed probes of an authority.",
+                &["peer"],
+                registry,
+            )
+            .unwrap(),
+            peer_probe_latency: register_histogram_vec_with_registry!(
+                "peer_monitor_probe_latency_sec",
+                "Latency of successful probes of an authority, in seconds.",
+                &["peer"],
+                PROBE_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        let registry = Registry::new();
+        Self::new(&registry)
+    }
+}
+
+pub async fn peer_monitor_process<A>(
+    active_authority: Arc<ActiveAuthority<A>>,
+    metrics: PeerMonitorMetrics,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    info!("Start peer monitor process.");
+    loop {
+        probe_peers(&active_authority, &metrics, PROBE_TIMEOUT).await;
+        tokio::time::sleep(PROBE_INTERVAL).await;
+    }
+}
+
+/// Probe the authorities of the current committee that are not in backoff, and record whether
+/// they are reachable.
+pub(crate) async fn probe_peers<A>(
+    active_authority: &ActiveAuthority<A>,
+    metrics: &PeerMonitorMetrics,
+    probe_timeout: Duration,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    let net = active_authority.agg_aggregator();
+    let mut peers = Vec::new();
+    for name in net.authority_clients.keys() {
+        if *name != active_authority.state.name && active_authority.can_contact(*name).await {
+            peers.push(*name);
+        }
+    }
+
+    let probes = peers.into_iter().map(|name| {
+        let client = net.clone_client(&name);
+        async move {
+            let start = Instant::now();
+            let result = timeout(
+                probe_timeout,
+                client.handle_committee_info_request(CommitteeInfoRequest { epoch: None }),
+            )
+            .await;
+            let reachable = matches!(result, Ok(Ok(_)));
+            if !reachable {
+                debug!(authority = ?name.concise(), ?result, "Peer probe failed");
+            }
+            (name, reachable, start.elapsed())
+        }
+    });
+
+    for (name, reachable, latency) in join_all(probes).await {
+        record_probe(active_authority, metrics, name, reachable, latency).await;
+    }
+}
+
+async fn record_probe<A>(
+    active_authority: &ActiveAuthority<A>,
+    metrics: &PeerMonitorMetrics,
+    name: AuthorityName,
+    reachable: bool,
+    latency: Duration,
+) {
+    let peer = format!("{:?}", name.concise());
+    let net = active_authority.agg_aggregator();
+    let was_reachable = net.peer_availability.is_reachable(&name);
+    net.peer_availability.set_reachable(name, reachable);
+    metrics
+        .peer_reachable
+        .with_label_values(&[&peer])
+        .set(reachable as i64);
+    if reachable {
+        metrics
+            .peer_probe_latency
+            .with_label_values(&[&peer])
+            .observe(latency.as_secs_f64());
+        active_authority.set_success_backoff(name).await;
+        if !was_reachable {
+            info!(authority = ?name.concise(), "Peer is reachable again");
+        }
+    } else {
+        metrics
+            .peer_probe_failures
+            .with_label_values(&[&peer])
+            .inc();
+        active_authority
+            .set_failure_backoff_with_jitter(name, BACKOFF_JITTER)
+            .await;
+        if was_reachable {
+            info!(authority = ?name.concise(), "Peer is unreachable");
+        }
+    }
+}
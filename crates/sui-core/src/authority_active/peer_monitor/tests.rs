@@ -0,0 +1,35 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::authority_aggregator::authority_aggregator_tests::init_local_authorities;
+
+#[tokio::test]
+async fn test_probe_peers() {
+    let (net, states, _) = init_local_authorities(4, vec![]).await;
+    let active_authority =
+        ActiveAuthority::new_with_ephemeral_storage_for_test(states[0].clone(), net).unwrap();
+    let metrics = PeerMonitorMetrics::new_for_tests();
+
+    // A peer previously seen as unreachable is reachable again after a successful probe.
+    let peer = states[1].name;
+    let availability = active_authority.agg_aggregator().peer_availability.clone();
+    availability.set_reachable(peer, false);
+
+    probe_peers(&active_authority, &metrics, Duration::from_secs(5)).await;
+
+    assert!(availability.is_reachable(&peer));
+    for state in &states[1..] {
+        let label = format!("{:?}", state.name.concise());
+        assert_eq!(metrics.peer_reachable.with_label_values(&[&label]).get(), 1);
+    }
+    // The authority does not probe itself.
+    let own_label = format!("{:?}", states[0].name.concise());
+    assert_eq!(
+        metrics
+            .peer_probe_latency
+            .with_label_values(&[&own_label])
+            .get_sample_count(),
+        0
+    );
+}
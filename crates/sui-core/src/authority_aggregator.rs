@@ -6,26 +6,40 @@ use crate::authority_client::{
     make_authority_clients, make_network_authority_client_sets_from_committee, AuthorityAPI,
     NetworkAuthorityClient, NetworkAuthorityClientMetrics,
 };
-use crate::safe_client::{SafeClient, SafeClientMetrics};
+use crate::object_downloader::{ObjectDownloader, MAX_CONCURRENT_DOWNLOADS_PER_AUTHORITY};
+use crate::safe_client::{
+    SafeClient, SafeClientMetrics, VerifiedCertificateCache, DEFAULT_VERIFIED_CERT_CACHE_SIZE,
+};
+use crate::trace_sampling::{self, QUORUM_MAP_AUTH_SPAN};
 use crate::validator_info::make_committee;
 use async_trait::async_trait;
 
-use futures::{future, future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
 use itertools::Itertools;
 use move_core_types::value::MoveStructLayout;
 use mysten_network::config::Config;
+use parking_lot::{Mutex, RwLock};
 use sui_config::genesis::Genesis;
 use sui_config::NetworkConfig;
+use sui_network::channel_pool::ChannelPoolConfig;
 use sui_network::{
     default_mysten_network_config, DEFAULT_CONNECT_TIMEOUT_SEC, DEFAULT_REQUEST_TIMEOUT_SEC,
 };
+use sui_types::bridge::{
+    BridgeAttestationRequest, BridgeAttestationResponse, BridgeMessageDigest,
+    CertifiedBridgeAttestation,
+};
 use sui_types::crypto::{AuthorityPublicKeyBytes, AuthoritySignature};
-use sui_types::object::{Object, ObjectFormatOptions, ObjectRead};
+use sui_types::event::Event;
+use sui_types::network_info::{
+    CertifiedNetworkInfo, NetworkInfoDigest, NetworkInfoRequest, NetworkInfoResponse,
+};
+use sui_types::object::{Object, ObjectRead};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::{
     base_types::*,
     committee::Committee,
-    error::{SuiError, SuiResult},
+    error::{ErrorCode, SuiError, SuiResult},
     messages::*,
     messages_checkpoint::{
         AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents, CheckpointRequest,
@@ -39,10 +53,10 @@ use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
     Registry,
 };
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::string::ToString;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sui_types::committee::{CommitteeWithNetAddresses, StakeUnit};
 use tokio::sync::mpsc::Receiver;
 use tokio::time::{sleep, timeout};
@@ -58,6 +72,10 @@ pub const DEFAULT_RETRIES: usize = 4;
 #[path = "unit_tests/authority_aggregator_tests.rs"]
 pub mod authority_aggregator_tests;
 
+#[cfg(test)]
+#[path = "unit_tests/quorum_scenario_tests.rs"]
+mod quorum_scenario_tests;
+
 pub type AsyncResult<'a, T, E> = BoxFuture<'a, Result<T, E>>;
 
 #[derive(Clone)]
@@ -81,6 +99,15 @@ pub struct TimeoutConfig {
     // it is set to a value greater than serial_authority_request_timeout then it becomes
     // completely serial.
     pub serial_authority_request_interval: Duration,
+
+    // If set, idempotent reads are hedged: the delay before a read is also sent to another
+    // authority follows the observed read latencies instead of serial_authority_request_interval,
+    // and the number of such duplicate requests is capped by a budget.
+    pub hedging: Option<HedgingConfig>,
+
+    // If set, the timeout of a request to an authority is derived from the latency observed on
+    // previous requests to it, instead of using the fixed durations above.
+    pub adaptive: Option<AdaptiveTimeoutConfig>,
 }
 
 impl Default for TimeoutConfig {
@@ -91,6 +118,177 @@ impl Default for TimeoutConfig {
             post_quorum_timeout: Duration::from_secs(30),
             serial_authority_request_timeout: Duration::from_secs(5),
             serial_authority_request_interval: Duration::from_millis(1000),
+            hedging: None,
+            adaptive: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HedgingConfig {
+    // Percentile of the recent read latencies after which a read is hedged.
+    pub latency_percentile: f64,
+    // Bounds of the hedging delay. The upper bound is used until enough reads were observed.
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    // Hedged requests earned by each read, e.g. 0.05 allows hedging one read in twenty.
+    pub budget_ratio: f64,
+    // Maximum number of hedged requests that can be saved up.
+    pub max_budget: f64,
+}
+
+impl Default for HedgingConfig {
+    fn default() -> Self {
+        Self {
+            latency_percentile: 0.99,
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(1000),
+            budget_ratio: 0.05,
+            max_budget: 10.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdaptiveTimeoutConfig {
+    // Weight of a new sample in the latency average and in its mean deviation.
+    pub average_weight: f64,
+    pub deviation_weight: f64,
+    // The timeout is the latency average plus this many mean deviations.
+    pub deviation_multiplier: f64,
+    // Bounds of the derived timeouts.
+    pub floor: Duration,
+    pub ceiling: Duration,
+}
+
+impl Default for AdaptiveTimeoutConfig {
+    fn default() -> Self {
+        // The weights and multiplier are the ones used to compute TCP retransmission timeouts.
+        Self {
+            average_weight: 0.125,
+            deviation_weight: 0.25,
+            deviation_multiplier: 4.0,
+            floor: Duration::from_secs(1),
+            ceiling: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LatencyEstimate {
+    average: f64,
+    deviation: f64,
+}
+
+/// Per authority estimates of the request latency, from which request timeouts are derived.
+#[derive(Debug)]
+pub struct AdaptiveTimeouts {
+    config: AdaptiveTimeoutConfig,
+    estimates: Mutex<HashMap<AuthorityName, LatencyEstimate>>,
+}
+
+impl AdaptiveTimeouts {
+    pub fn new(config: AdaptiveTimeoutConfig) -> Self {
+        Self {
+            config,
+            estimates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The timeout of a request to `name`, or `default` if no request to it completed yet.
+    pub fn request_timeout(&self, name: &AuthorityName, default: Duration) -> Duration {
+        match self.estimates.lock().get(name) {
+            Some(estimate) => Duration::from_secs_f64(
+                estimate.average + self.config.deviation_multiplier * estimate.deviation,
+            )
+            .clamp(self.config.floor, self.config.ceiling),
+            None => default,
+        }
+    }
+
+    pub fn record_latency(&self, name: AuthorityName, latency: Duration) {
+        let latency = latency.as_secs_f64();
+        let mut estimates = self.estimates.lock();
+        match estimates.get_mut(&name) {
+            Some(estimate) => {
+                estimate.deviation += self.config.deviation_weight
+                    * ((estimate.average - latency).abs() - estimate.deviation);
+                estimate.average += self.config.average_weight * (latency - estimate.average);
+            }
+            None => {
+                estimates.insert(
+                    name,
+                    LatencyEstimate {
+                        average: latency,
+                        deviation: latency / 2.0,
+                    },
+                );
+            }
+        }
+    }
+
+    /// A request that timed out took at least `timeout`, which is recorded so that the timeout
+    /// of an authority on a link slower than estimated grows.
+    pub fn record_timeout(&self, name: AuthorityName, timeout: Duration) {
+        self.record_latency(name, timeout);
+    }
+}
+
+/// Number of recent read latencies the hedging delay is computed from.
+const HEDGING_LATENCY_WINDOW: usize = 1000;
+/// Number of read latencies needed before the hedging delay follows them.
+const HEDGING_MIN_SAMPLES: usize = 20;
+
+/// Tracks the latency of successful reads and the budget of hedged requests.
+#[derive(Debug)]
+pub struct ReadHedger {
+    config: HedgingConfig,
+    latencies: Mutex<VecDeque<Duration>>,
+    budget: Mutex<f64>,
+}
+
+impl ReadHedger {
+    pub fn new(config: HedgingConfig) -> Self {
+        Self {
+            budget: Mutex::new(config.max_budget),
+            config,
+            latencies: Mutex::new(VecDeque::with_capacity(HEDGING_LATENCY_WINDOW)),
+        }
+    }
+
+    pub fn record_latency(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock();
+        if latencies.len() == HEDGING_LATENCY_WINDOW {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency);
+    }
+
+    /// How long to wait for a response before hedging a read.
+    pub fn hedging_delay(&self) -> Duration {
+        let mut latencies: Vec<_> = self.latencies.lock().iter().copied().collect();
+        if latencies.len() < HEDGING_MIN_SAMPLES {
+            return self.config.max_delay;
+        }
+        latencies.sort_unstable();
+        let index = ((latencies.len() - 1) as f64 * self.config.latency_percentile).round();
+        latencies[index as usize].clamp(self.config.min_delay, self.config.max_delay)
+    }
+
+    /// Earns the hedging budget of one read.
+    fn on_read(&self) {
+        let mut budget = self.budget.lock();
+        *budget = f64::min(*budget + self.config.budget_ratio, self.config.max_budget);
+    }
+
+    /// Takes one hedged request from the budget, if there is one left.
+    pub fn try_hedge(&self) -> bool {
+        let mut budget = self.budget.lock();
+        if *budget >= 1.0 {
+            *budget -= 1.0;
+            true
+        } else {
+            false
         }
     }
 }
@@ -103,6 +301,9 @@ pub struct AuthAggMetrics {
     pub num_good_stake: Histogram,
     pub num_bad_stake: Histogram,
     pub total_quorum_once_timeout: IntCounter,
+    pub total_hedged_requests: IntCounter,
+    pub total_object_downloads: IntCounter,
+    pub total_deduped_object_downloads: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -148,6 +349,24 @@ impl AuthAggMetrics {
                 registry,
             )
             .unwrap(),
+            total_hedged_requests: register_int_counter_with_registry!(
+                "total_hedged_requests",
+                "Total number of duplicate requests sent to hedge a slow read",
+                registry,
+            )
+            .unwrap(),
+            total_object_downloads: register_int_counter_with_registry!(
+                "total_object_downloads",
+                "Total number of objects downloaded from the authorities",
+                registry,
+            )
+            .unwrap(),
+            total_deduped_object_downloads: register_int_counter_with_registry!(
+                "total_deduped_object_downloads",
+                "Total number of object downloads served by a concurrent download of the same object",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -157,6 +376,95 @@ impl AuthAggMetrics {
     }
 }
 
+/// Reachability of the authorities, as last observed by the peer monitor of the active
+/// authority. Requests that need a single response try unreachable authorities last.
+#[derive(Debug, Default)]
+pub struct PeerAvailability {
+    unreachable: RwLock<HashSet<AuthorityName>>,
+}
+
+impl PeerAvailability {
+    pub fn set_reachable(&self, name: AuthorityName, reachable: bool) {
+        if reachable {
+            self.unreachable.write().remove(&name);
+        } else {
+            self.unreachable.write().insert(name);
+        }
+    }
+
+    pub fn is_reachable(&self, name: &AuthorityName) -> bool {
+        !self.unreachable.read().contains(name)
+    }
+
+    /// Move the unreachable authorities to the end, keeping the order otherwise.
+    pub fn reachable_first(&self, mut authorities: Vec<AuthorityName>) -> Vec<AuthorityName> {
+        let unreachable = self.unreachable.read();
+        if !unreachable.is_empty() {
+            authorities.sort_by_key(|name| unreachable.contains(name));
+        }
+        authorities
+    }
+}
+
+/// Preferred order in which to contact the authorities, e.g. the authorities of the same region
+/// first, to cut round trips for latency sensitive clients. The authorities are grouped in tiers,
+/// the most preferred first, and authorities of no tier come last. Within a tier, authorities are
+/// still shuffled by stake. Set statically when building the aggregator, or updated at any time
+/// by its users, and kept across re-config.
+#[derive(Debug, Default)]
+pub struct AuthorityPreferences {
+    tiers: RwLock<Vec<BTreeSet<AuthorityName>>>,
+}
+
+impl AuthorityPreferences {
+    pub fn new(tiers: Vec<BTreeSet<AuthorityName>>) -> Self {
+        Self {
+            tiers: RwLock::new(tiers),
+        }
+    }
+
+    pub fn set_tiers(&self, tiers: Vec<BTreeSet<AuthorityName>>) {
+        *self.tiers.write() = tiers;
+    }
+
+    pub fn tiers(&self) -> Vec<BTreeSet<AuthorityName>> {
+        self.tiers.read().clone()
+    }
+
+    /// Order `authorities` by tier, and by their order in `authorities` within a tier. If
+    /// `preferences` are given, they come first regardless of their tier.
+    pub fn order(
+        &self,
+        mut authorities: Vec<AuthorityName>,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+    ) -> Vec<AuthorityName> {
+        let tiers = self.tiers.read();
+        if !tiers.is_empty() {
+            authorities.sort_by_key(|name| {
+                let preferred = preferences.map_or(false, |preferences| preferences.contains(name));
+                let tier = tiers
+                    .iter()
+                    .position(|tier| tier.contains(name))
+                    .unwrap_or(tiers.len());
+                (!preferred, tier)
+            });
+        }
+        authorities
+    }
+}
+
+/// Everything needed to prove a transaction is final: its certificate, a quorum of signatures on
+/// its effects, the events it emitted, and the certified checkpoint which includes it.
+#[derive(Clone, Debug)]
+pub struct TransactionFinalityPackage {
+    pub certificate: CertifiedTransaction,
+    pub effects: CertifiedTransactionEffects,
+    pub events: Vec<Event>,
+    /// None if the transaction is not part of a certified checkpoint yet, or if no authority
+    /// could return it.
+    pub checkpoint: Option<(CertifiedCheckpointSummary, CheckpointContents)>,
+}
+
 #[derive(Clone)]
 pub struct AuthorityAggregator<A> {
     /// Our Sui committee.
@@ -173,6 +481,18 @@ pub struct AuthorityAggregator<A> {
     pub network_client_metrics: Arc<NetworkAuthorityClientMetrics>,
     /// Store here for clone during re-config.
     pub committee_store: Arc<CommitteeStore>,
+    /// Shared by the SafeClients, and kept across re-config.
+    pub verified_cert_cache: Arc<VerifiedCertificateCache>,
+    /// Updated by the peer monitor, and kept across re-config.
+    pub peer_availability: Arc<PeerAvailability>,
+    /// Set by the users of the aggregator, and kept across re-config.
+    pub authority_preferences: Arc<AuthorityPreferences>,
+    /// Set if read hedging is configured in the timeouts.
+    pub read_hedger: Option<Arc<ReadHedger>>,
+    /// Set if adaptive timeouts are configured in the timeouts.
+    pub adaptive_timeouts: Option<Arc<AdaptiveTimeouts>>,
+    /// Shared by concurrent syncs, and kept across re-config.
+    pub object_downloader: Arc<ObjectDownloader>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -204,6 +524,23 @@ impl<A> AuthorityAggregator<A> {
         network_client_metrics: Arc<NetworkAuthorityClientMetrics>,
         timeouts: TimeoutConfig,
     ) -> Self {
+        let verified_cert_cache = Arc::new(VerifiedCertificateCache::new(
+            DEFAULT_VERIFIED_CERT_CACHE_SIZE,
+            &safe_client_metrics,
+        ));
+        let read_hedger = timeouts
+            .hedging
+            .clone()
+            .map(|config| Arc::new(ReadHedger::new(config)));
+        let adaptive_timeouts = timeouts
+            .adaptive
+            .clone()
+            .map(|config| Arc::new(AdaptiveTimeouts::new(config)));
+        let object_downloader = Arc::new(ObjectDownloader::new(
+            MAX_CONCURRENT_DOWNLOADS_PER_AUTHORITY,
+            metrics.total_object_downloads.clone(),
+            metrics.total_deduped_object_downloads.clone(),
+        ));
         Self {
             committee,
             authority_clients: authority_clients
@@ -214,6 +551,7 @@ impl<A> AuthorityAggregator<A> {
                         SafeClient::new(
                             api,
                             committee_store.clone(),
+                            verified_cert_cache.clone(),
                             name,
                             safe_client_metrics.clone(),
                         ),
@@ -225,6 +563,12 @@ impl<A> AuthorityAggregator<A> {
             safe_client_metrics,
             network_client_metrics,
             committee_store,
+            verified_cert_cache,
+            peer_availability: Arc::new(PeerAvailability::default()),
+            authority_preferences: Arc::new(AuthorityPreferences::default()),
+            read_hedger,
+            adaptive_timeouts,
+            object_downloader,
         }
     }
 
@@ -242,6 +586,7 @@ impl<A> AuthorityAggregator<A> {
         let network_clients = make_network_authority_client_sets_from_committee(
             &committee,
             network_config,
+            &ChannelPoolConfig::default(),
             self.network_client_metrics.clone(),
         )
         .map_err(|err| SuiError::GenericAuthorityError {
@@ -256,6 +601,7 @@ impl<A> AuthorityAggregator<A> {
                     SafeClient::new(
                         api,
                         self.committee_store.clone(),
+                        self.verified_cert_cache.clone(),
                         name,
                         self.safe_client_metrics.clone(),
                     ),
@@ -265,13 +611,12 @@ impl<A> AuthorityAggregator<A> {
 
         // TODO: It's likely safer to do the following operations atomically, in case this function
         // gets called from different threads. It cannot happen today, but worth the caution.
-        let new_committee = committee.committee;
         fp_ensure!(
-            self.committee.epoch + 1 == new_committee.epoch,
+            self.committee.epoch + 1 == committee.committee.epoch,
             SuiError::AdvanceEpochError {
                 error: format!(
                     "Trying to advance from epoch {} to epoch {}",
-                    self.committee.epoch, new_committee.epoch
+                    self.committee.epoch, committee.committee.epoch
                 )
             }
         );
@@ -280,15 +625,23 @@ impl<A> AuthorityAggregator<A> {
         // This is because there may be multiple AuthorityAggregators
         // or its containers (e.g. Quorum Drivers)  share the same committee
         // store and all of them need to reconfigure.
-        let _ = self.committee_store.insert_new_committee(&new_committee);
+        let _ = self
+            .committee_store
+            .insert_new_committee_with_net_addresses(&committee);
         Ok(AuthorityAggregator {
-            committee: new_committee,
+            committee: committee.committee,
             authority_clients: safe_clients,
             metrics: self.metrics.clone(),
             timeouts: self.timeouts.clone(),
             safe_client_metrics: self.safe_client_metrics.clone(),
             network_client_metrics: self.network_client_metrics.clone(),
             committee_store: self.committee_store.clone(),
+            verified_cert_cache: self.verified_cert_cache.clone(),
+            peer_availability: self.peer_availability.clone(),
+            authority_preferences: self.authority_preferences.clone(),
+            read_hedger: self.read_hedger.clone(),
+            adaptive_timeouts: self.adaptive_timeouts.clone(),
+            object_downloader: self.object_downloader.clone(),
         })
     }
 
@@ -309,6 +662,42 @@ impl<A> AuthorityAggregator<A> {
         }
         clients
     }
+
+    /// Returns a copy of this aggregator, for the same committee, in which the clients of the
+    /// given authorities are replaced. Caches and peer availability are shared with `self`.
+    pub fn with_replaced_clients(&self, replaced: BTreeMap<AuthorityName, A>) -> Self
+    where
+        A: Clone,
+    {
+        let mut authority_clients = self.authority_clients.clone();
+        for (name, api) in replaced {
+            authority_clients.insert(
+                name,
+                SafeClient::new(
+                    api,
+                    self.committee_store.clone(),
+                    self.verified_cert_cache.clone(),
+                    name,
+                    self.safe_client_metrics.clone(),
+                ),
+            );
+        }
+        Self {
+            committee: self.committee.clone(),
+            authority_clients,
+            metrics: self.metrics.clone(),
+            timeouts: self.timeouts.clone(),
+            safe_client_metrics: self.safe_client_metrics.clone(),
+            network_client_metrics: self.network_client_metrics.clone(),
+            committee_store: self.committee_store.clone(),
+            verified_cert_cache: self.verified_cert_cache.clone(),
+            peer_availability: self.peer_availability.clone(),
+            authority_preferences: self.authority_preferences.clone(),
+            read_hedger: self.read_hedger.clone(),
+            adaptive_timeouts: self.adaptive_timeouts.clone(),
+            object_downloader: self.object_downloader.clone(),
+        }
+    }
 }
 
 pub enum ReduceOutput<S> {
@@ -396,7 +785,10 @@ where
                     processed_certificates.insert(cert_digest);
                     continue;
                 }
-                Err(SuiError::ObjectErrors { .. }) => {}
+                // The destination is missing input objects, from certificates it has not
+                // executed yet. The error may have been received over the network, so it is
+                // recognized by its code.
+                Err(err) if err.code() == ErrorCode::OBJECT_ERRORS => {}
                 Err(e) => return Err(e),
             }
 
@@ -716,6 +1108,46 @@ where
         .await
     }
 
+    /// The timeout of a request to `name`: derived from the latency of previous requests in the
+    /// adaptive mode, `default` otherwise.
+    pub fn authority_request_timeout(&self, name: &AuthorityName, default: Duration) -> Duration {
+        match &self.adaptive_timeouts {
+            Some(adaptive) => adaptive.request_timeout(name, default),
+            None => default,
+        }
+    }
+
+    /// Feeds the outcome of a request to `name` to the adaptive timeouts, if enabled.
+    fn record_request_outcome<V>(
+        &self,
+        name: AuthorityName,
+        request_timeout: Duration,
+        elapsed: Duration,
+        result: &Result<SuiResult<V>, tokio::time::error::Elapsed>,
+    ) {
+        if let Some(adaptive) = &self.adaptive_timeouts {
+            match result {
+                Ok(Ok(_)) => adaptive.record_latency(name, elapsed),
+                Err(_) => adaptive.record_timeout(name, request_timeout),
+                // Errors may be returned before doing any work, and say little about latency.
+                Ok(Err(_)) => {}
+            }
+        }
+    }
+
+    /// The authorities shuffled by stake, then ordered by the call `preferences` and the
+    /// authority preferences of the aggregator.
+    fn authorities_in_preferred_order(
+        &self,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+        restrict_to: Option<&BTreeSet<AuthorityName>>,
+    ) -> Vec<AuthorityName> {
+        self.authority_preferences.order(
+            self.committee.shuffle_by_stake(preferences, restrict_to),
+            preferences,
+        )
+    }
+
     pub(crate) async fn quorum_map_then_reduce_with_timeout_and_prefs<'a, S, V, FMap, FReduce>(
         &'a self,
         authority_prefences: Option<&BTreeSet<AuthorityName>>,
@@ -733,7 +1165,7 @@ where
             Result<V, SuiError>,
         ) -> AsyncResult<'a, ReduceOutput<S>, SuiError>,
     {
-        let authorities_shuffled = self.committee.shuffle_by_stake(authority_prefences, None);
+        let authorities_shuffled = self.authorities_in_preferred_order(authority_prefences, None);
 
         // First, execute in parallel for each authority FMap.
         let mut responses: futures::stream::FuturesUnordered<_> = authorities_shuffled
@@ -741,13 +1173,29 @@ where
             .map(|name| {
                 let client = &self.authority_clients[name];
                 let execute = map_each_authority.clone();
+                let span = if trace_sampling::should_sample(QUORUM_MAP_AUTH_SPAN) {
+                    tracing::trace_span!("quorum_map_auth", authority =? name.concise())
+                } else {
+                    tracing::Span::none()
+                };
                 async move {
-                    (
+                    let request = execute(*name, client).instrument(span);
+                    if self.adaptive_timeouts.is_none() {
+                        return (*name, request.await);
+                    }
+                    // In the adaptive mode, requests to each authority are also bounded by its
+                    // own timeout, so that dead authorities do not hold up the reducer.
+                    let request_timeout = self
+                        .authority_request_timeout(name, self.timeouts.authority_request_timeout);
+                    let request_start = tokio::time::Instant::now();
+                    let result = timeout(request_timeout, request).await;
+                    self.record_request_outcome(
                         *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name.concise()))
-                            .await,
-                    )
+                        request_timeout,
+                        request_start.elapsed(),
+                        &result,
+                    );
+                    (*name, result.unwrap_or(Err(SuiError::TimeoutError)))
                 }
             })
             .collect();
@@ -792,6 +1240,8 @@ where
         // and authority client parameter and returns a Result<V>.
         map_each_authority: FMap,
         timeout_each_authority: Duration,
+        // Set to hedge an idempotent read.
+        hedger: Option<&'a ReadHedger>,
         authority_errors: &mut HashMap<AuthorityName, SuiError>,
     ) -> Result<S, SuiError>
     where
@@ -800,8 +1250,13 @@ where
     {
         let start = tokio::time::Instant::now();
         let mut delay = Duration::from_secs(1);
+        if let Some(hedger) = hedger {
+            hedger.on_read();
+        }
         loop {
-            let authorities_shuffled = self.committee.shuffle_by_stake(preferences, restrict_to);
+            let authorities_shuffled = self
+                .peer_availability
+                .reachable_first(self.authorities_in_preferred_order(preferences, restrict_to));
             let mut authorities_shuffled = authorities_shuffled.iter();
 
             type RequestResult<S> = Result<Result<S, SuiError>, tokio::time::error::Elapsed>;
@@ -816,14 +1271,30 @@ where
             let start_req = |name: AuthorityName, client: SafeClient<A>| {
                 let map_each_authority = map_each_authority.clone();
                 Box::pin(async move {
-                    trace!(?name, now = ?tokio::time::Instant::now() - start, "new request");
+                    let request_start = tokio::time::Instant::now();
+                    trace!(?name, now = ?request_start - start, "new request");
                     let map = map_each_authority(name, client);
-                    Event::Request(name, timeout(timeout_each_authority, map).await)
+                    let request_timeout =
+                        self.authority_request_timeout(&name, timeout_each_authority);
+                    let res = timeout(request_timeout, map).await;
+                    self.record_request_outcome(
+                        name,
+                        request_timeout,
+                        request_start.elapsed(),
+                        &res,
+                    );
+                    if let (Some(hedger), Ok(Ok(_))) = (hedger, &res) {
+                        hedger.record_latency(request_start.elapsed());
+                    }
+                    Event::Request(name, res)
                 })
             };
 
             let schedule_next = || {
-                let delay = self.timeouts.serial_authority_request_interval;
+                let delay = match hedger {
+                    Some(hedger) => hedger.hedging_delay(),
+                    None => self.timeouts.serial_authority_request_interval,
+                };
                 Box::pin(async move {
                     sleep(delay).await;
                     Event::StartNext
@@ -850,6 +1321,9 @@ where
             //   new request.
             // - If serial_authority_request_interval elapses, we begin a new request even if the
             //   previous one is not finished, and schedule another future request.
+            //
+            // When hedging a read, the interval is instead derived from the latency of previous
+            // reads, and a new request is only begun early if the hedging budget allows it.
 
             let name = authorities_shuffled.next().ok_or_else(|| {
                 error!(
@@ -865,8 +1339,15 @@ where
             while let Some(res) = futures.next().await {
                 match res {
                     Event::StartNext => {
-                        trace!(now = ?tokio::time::Instant::now() - start, "eagerly beginning next request");
                         futures.push(schedule_next());
+                        if let Some(hedger) = hedger {
+                            if !hedger.try_hedge() {
+                                trace!("hedging budget exhausted");
+                                continue;
+                            }
+                            self.metrics.total_hedged_requests.inc();
+                        }
+                        trace!(now = ?tokio::time::Instant::now() - start, "eagerly beginning next request");
                     }
                     Event::Request(name, res) => {
                         match res {
@@ -927,6 +1408,60 @@ where
         // The behavior that authorities expect to perform, used for logging and error
         description: &'static str,
     ) -> Result<S, SuiError>
+    where
+        FMap: Fn(AuthorityName, SafeClient<A>) -> AsyncResult<'a, S, SuiError> + Send + Clone + 'a,
+        S: Send,
+    {
+        self.quorum_once_with_timeout_impl(
+            preferences,
+            restrict_to,
+            map_each_authority,
+            timeout_each_authority,
+            timeout_total,
+            None,
+            description,
+        )
+        .await
+    }
+
+    /// Like quorum_once_with_timeout, for idempotent reads, which are hedged if hedging is
+    /// configured in the timeouts.
+    pub(crate) async fn quorum_once_read_with_timeout<'a, S, FMap>(
+        &'a self,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+        restrict_to: Option<&BTreeSet<AuthorityName>>,
+        map_each_authority: FMap,
+        timeout_each_authority: Duration,
+        timeout_total: Option<Duration>,
+        description: &'static str,
+    ) -> Result<S, SuiError>
+    where
+        FMap: Fn(AuthorityName, SafeClient<A>) -> AsyncResult<'a, S, SuiError> + Send + Clone + 'a,
+        S: Send,
+    {
+        self.quorum_once_with_timeout_impl(
+            preferences,
+            restrict_to,
+            map_each_authority,
+            timeout_each_authority,
+            timeout_total,
+            self.read_hedger.as_deref(),
+            description,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn quorum_once_with_timeout_impl<'a, S, FMap>(
+        &'a self,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+        restrict_to: Option<&BTreeSet<AuthorityName>>,
+        map_each_authority: FMap,
+        timeout_each_authority: Duration,
+        timeout_total: Option<Duration>,
+        hedger: Option<&'a ReadHedger>,
+        description: &'static str,
+    ) -> Result<S, SuiError>
     where
         FMap: Fn(AuthorityName, SafeClient<A>) -> AsyncResult<'a, S, SuiError> + Send + Clone + 'a,
         S: Send,
@@ -938,6 +1473,7 @@ where
             restrict_to,
             map_each_authority,
             timeout_each_authority,
+            hedger,
             &mut authority_errors,
         );
 
@@ -1041,6 +1577,191 @@ where
         }
     }
 
+    /// The committee of `epoch`, e.g. to verify a certificate signed in that epoch. Committees
+    /// of past epochs are read from the committee store if known, and otherwise from a quorum
+    /// of the current committee.
+    pub async fn committee_for_epoch(&self, epoch: EpochId) -> SuiResult<Committee> {
+        if epoch == self.committee.epoch {
+            return Ok(self.committee.clone());
+        }
+        if let Some(committee) = self.committee_store.get_committee(&epoch)? {
+            return Ok(committee);
+        }
+        let committee = Committee::try_from(self.get_committee_info(Some(epoch)).await?)?;
+        fp_ensure!(
+            committee.epoch == epoch,
+            SuiError::MissingCommitteeAtEpoch(epoch)
+        );
+        Ok(committee)
+    }
+
+    /// Collect signatures from a quorum of validators on the bridge message for the
+    /// `event_seq`-th event of a transaction. Validators only sign events of transactions they
+    /// have executed, so the transaction certificate must have been executed by a quorum.
+    /// Only signatures from the epoch of the committee of the aggregator are counted.
+    pub async fn get_bridge_attestation(
+        &self,
+        tx_digest: TransactionDigest,
+        event_seq: u64,
+    ) -> SuiResult<CertifiedBridgeAttestation> {
+        #[derive(Default)]
+        struct BridgeAttestationState {
+            bad_weight: StakeUnit,
+            // Signatures grouped by the digest of the message they sign, since byzantine
+            // validators may sign a different message.
+            signatures:
+                HashMap<BridgeMessageDigest, (StakeUnit, Vec<(AuthorityName, AuthoritySignature)>)>,
+            errors: Vec<(AuthorityName, SuiError)>,
+            attestation: Option<CertifiedBridgeAttestation>,
+        }
+        let request = BridgeAttestationRequest {
+            tx_digest,
+            event_seq,
+        };
+        let committee = &self.committee;
+        let threshold = committee.quorum_threshold();
+        let validity = committee.validity_threshold();
+        let final_state = self
+            .quorum_map_then_reduce_with_timeout(
+                BridgeAttestationState::default(),
+                |_name, client| {
+                    let request = request.clone();
+                    Box::pin(async move { client.handle_bridge_attestation_request(request).await })
+                },
+                |mut state, name, weight, result| {
+                    Box::pin(async move {
+                        match result {
+                            // The certificate is formed for the epoch of our committee, so
+                            // signatures from another epoch must not count towards its quorum.
+                            Ok(BridgeAttestationResponse { attestation })
+                                if attestation.auth_signature.epoch != committee.epoch =>
+                            {
+                                state.bad_weight += weight;
+                                state.errors.push((
+                                    name,
+                                    SuiError::WrongEpoch {
+                                        expected_epoch: committee.epoch,
+                                        actual_epoch: attestation.auth_signature.epoch,
+                                    },
+                                ));
+                            }
+                            Ok(BridgeAttestationResponse { attestation }) => {
+                                let (stake, signatures) = state
+                                    .signatures
+                                    .entry(attestation.message.digest())
+                                    .or_default();
+                                *stake += weight;
+                                signatures.push((name, attestation.auth_signature.signature));
+                                if *stake >= threshold {
+                                    let signatures = std::mem::take(signatures);
+                                    state.attestation = Some(CertifiedBridgeAttestation::new(
+                                        attestation.message,
+                                        signatures,
+                                        committee,
+                                    )?);
+                                    return Ok(ReduceOutput::End(state));
+                                }
+                            }
+                            Err(err) => {
+                                state.bad_weight += weight;
+                                state.errors.push((name, err));
+                            }
+                        };
+
+                        // Return all errors if a quorum is not possible.
+                        if state.bad_weight > validity {
+                            return Err(SuiError::TooManyIncorrectAuthorities {
+                                errors: state.errors,
+                                action: "get_bridge_attestation",
+                            });
+                        }
+                        Ok(ReduceOutput::Continue(state))
+                    })
+                },
+                // A long timeout before we hear back from a quorum
+                self.timeouts.pre_quorum_timeout,
+            )
+            .await?;
+
+        final_state
+            .attestation
+            .ok_or(SuiError::TooManyIncorrectAuthorities {
+                errors: final_state.errors,
+                action: "get_bridge_attestation",
+            })
+    }
+
+    /// Collect signatures from a quorum of validators on the network info of the current
+    /// epoch. Validators configured with different RPC endpoints sign different info, in which
+    /// case no quorum may be reached.
+    pub async fn get_network_info(&self) -> SuiResult<CertifiedNetworkInfo> {
+        #[derive(Default)]
+        struct NetworkInfoState {
+            bad_weight: StakeUnit,
+            // Signatures grouped by the digest of the info they sign.
+            signatures:
+                HashMap<NetworkInfoDigest, (StakeUnit, Vec<(AuthorityName, AuthoritySignature)>)>,
+            errors: Vec<(AuthorityName, SuiError)>,
+            info: Option<CertifiedNetworkInfo>,
+        }
+        let committee = &self.committee;
+        let threshold = committee.quorum_threshold();
+        let validity = committee.validity_threshold();
+        let final_state = self
+            .quorum_map_then_reduce_with_timeout(
+                NetworkInfoState::default(),
+                |_name, client| {
+                    Box::pin(async move {
+                        client
+                            .handle_network_info_request(NetworkInfoRequest {})
+                            .await
+                    })
+                },
+                |mut state, name, weight, result| {
+                    Box::pin(async move {
+                        match result {
+                            Ok(NetworkInfoResponse { info }) => {
+                                let (stake, signatures) =
+                                    state.signatures.entry(info.info.digest()).or_default();
+                                *stake += weight;
+                                signatures.push((name, info.auth_signature.signature));
+                                if *stake >= threshold {
+                                    let signatures = std::mem::take(signatures);
+                                    state.info = Some(CertifiedNetworkInfo::new(
+                                        info.info, signatures, committee,
+                                    )?);
+                                    return Ok(ReduceOutput::End(state));
+                                }
+                            }
+                            Err(err) => {
+                                state.bad_weight += weight;
+                                state.errors.push((name, err));
+                            }
+                        };
+
+                        // Return all errors if a quorum is not possible.
+                        if state.bad_weight > validity {
+                            return Err(SuiError::TooManyIncorrectAuthorities {
+                                errors: state.errors,
+                                action: "get_network_info",
+                            });
+                        }
+                        Ok(ReduceOutput::Continue(state))
+                    })
+                },
+                // A long timeout before we hear back from a quorum
+                self.timeouts.pre_quorum_timeout,
+            )
+            .await?;
+
+        final_state
+            .info
+            .ok_or(SuiError::TooManyIncorrectAuthorities {
+                errors: final_state.errors,
+                action: "get_network_info",
+            })
+    }
+
     /// Query validators for latest SuiSystemState and try to form
     /// CommitteeWithNetworkAddress. Only return Some(CommitteeWithNetAddresses)
     /// when there is quorum. This function tolerates uninteresting
@@ -1057,7 +1778,7 @@ where
     ) -> SuiResult<CommitteeWithNetAddresses> {
         let (aggregate_object_info, _certificates) =
             // Skip committee check because this call usually happens when there's a potential new epoch
-            self.get_object_by_id(SUI_SYSTEM_STATE_OBJECT_ID, true).await?;
+            self.get_object_by_id(SUI_SYSTEM_STATE_OBJECT_ID, ObjectContentOptions::WithOwnerOnly, true).await?;
 
         let mut committee_and_sigs = aggregate_object_info
             .into_iter()
@@ -1129,9 +1850,11 @@ where
     /// pair to the content of the object as well as a list of authorities that responded this
     /// pair.
     /// The second part of the return value is a map from transaction digest to the cert.
+    /// Layouts, locks and certificates are only returned if requested in `content`.
     async fn get_object_by_id(
         &self,
         object_id: ObjectID,
+        content: ObjectContentOptions,
         skip_committee_check_during_reconfig: bool,
     ) -> Result<
         (
@@ -1162,11 +1885,8 @@ where
                 |_name, client| {
                     Box::pin(async move {
                         // Request and return an error if any
-                        // TODO: Expose layout format option.
-                        let request = ObjectInfoRequest::latest_object_info_request(
-                            object_id,
-                            Some(ObjectFormatOptions::default()),
-                        );
+                        let request =
+                            ObjectInfoRequest::latest_object_content_request(object_id, content);
                         client
                             .handle_object_info_request(
                                 request,
@@ -1409,8 +2129,9 @@ where
             // Authorities to update.
             let mut authorities: HashSet<AuthorityName> = self.committee.names().cloned().collect();
 
-            let (aggregate_object_info, certificates) =
-                self.get_object_by_id(*object_id, false).await?;
+            let (aggregate_object_info, certificates) = self
+                .get_object_by_id(*object_id, ObjectContentOptions::BcsOnly, false)
+                .await?;
 
             let mut aggregate_object_info: Vec<_> = aggregate_object_info.into_iter().collect();
 
@@ -1495,7 +2216,8 @@ where
     ///
     /// This function returns all objects, including those that are
     /// no more owned by the user (but were previously owned by the user), as well as a list of
-    /// deleted object references.
+    /// deleted object references. Objects which all authorities agree on are only downloaded,
+    /// and are returned without their layout and certificate.
     pub async fn sync_all_owned_objects(
         &self,
         address: SuiAddress,
@@ -1512,15 +2234,56 @@ where
         SuiError,
     > {
         // Contact a quorum of authorities, and return all objects they report we own.
-        let (object_map, _authority_list) = self
+        let (object_map, authority_list) = self
             .get_all_owned_objects(address, timeout_after_quorum)
             .await?;
 
-        let all_object_ids: HashSet<_> = object_map.keys().map(|object_ref| object_ref.0).collect();
+        // Objects reported at the same version by every authority which responded, if they
+        // hold a quorum of stake, are up-to-date on all of them: they only need to be
+        // downloaded, from any of their holders. The other objects are synced one by one.
+        let mut refs_by_id: BTreeMap<ObjectID, Vec<ObjectRef>> = BTreeMap::new();
+        for object_ref in object_map.keys() {
+            refs_by_id
+                .entry(object_ref.0)
+                .or_default()
+                .push(*object_ref);
+        }
+        let mut agreed_refs = BTreeSet::new();
+        let mut objects_to_sync = Vec::new();
+        for (object_id, refs) in refs_by_id {
+            let holders = &object_map[&refs[0]];
+            let agreed = refs.len() == 1
+                && holders.len() == authority_list.len()
+                && holders
+                    .iter()
+                    .map(|name| self.committee.weight(name))
+                    .sum::<StakeUnit>()
+                    >= self.committee.quorum_threshold();
+            if agreed {
+                self.object_downloader
+                    .record_holders(refs[0], holders.iter().cloned());
+                agreed_refs.insert(refs[0]);
+            } else {
+                objects_to_sync.push(object_id);
+            }
+        }
+
+        let mut downloaded = Vec::new();
+        let mut receiver = self.fetch_objects_from_authorities(agreed_refs);
+        while let Some(result) = receiver.recv().await {
+            match result {
+                Ok(object) => downloaded.push((object, None, None)),
+                Err(SuiError::ObjectFetchFailed { object_id, .. }) => {
+                    objects_to_sync.push(object_id)
+                }
+                Err(err) => return Err(err),
+            }
+        }
 
-        // Then sync all the owned objects
-        self.sync_all_given_objects(&all_object_ids.into_iter().collect::<Vec<_>>())
-            .await
+        // Then sync all the other owned objects
+        let (mut active, deleted) = self.sync_all_given_objects(&objects_to_sync).await?;
+        active.extend(downloaded);
+        Ok((active, deleted))
     }
 
     /// Submits the transaction to a quorum of validators to make a certificate.
@@ -1732,6 +2495,17 @@ where
         &self,
         certificate: CertifiedTransaction,
     ) -> Result<CertifiedTransactionEffects, SuiError> {
+        self.process_certificate_with_timing(certificate)
+            .await
+            .map(|(effects, _)| effects)
+    }
+
+    /// Like `process_certificate`, also returning when the first validator returned effects,
+    /// which tells how long broadcasting the certificate took apart from collecting a quorum.
+    pub async fn process_certificate_with_timing(
+        &self,
+        certificate: CertifiedTransaction,
+    ) -> Result<(CertifiedTransactionEffects, Instant), SuiError> {
         struct EffectsStakeInfo {
             stake: StakeUnit,
             effects: TransactionEffects,
@@ -1744,12 +2518,14 @@ where
             effects_map: HashMap<TransactionEffectsDigest, EffectsStakeInfo>,
             bad_stake: StakeUnit,
             errors: Vec<SuiError>,
+            first_effects_at: Option<Instant>,
         }
 
         let state = ProcessCertificateState {
             effects_map: HashMap::new(),
             bad_stake: 0,
             errors: vec![],
+            first_effects_at: None,
         };
 
         let tx_digest = *certificate.digest();
@@ -1803,6 +2579,7 @@ where
                                 signed_effects: Some(inner_effects),
                                 ..
                             }) => {
+                                state.first_effects_at.get_or_insert_with(Instant::now);
                                 // Note: here we aggregate votes by the hash of the effects structure
                                 let entry = state
                                     .effects_map
@@ -1855,6 +2632,7 @@ where
 
         // Check that one effects structure has more than 2f votes,
         // and return it.
+        let first_effects_at = state.first_effects_at.unwrap_or_else(Instant::now);
         for stake_info in state.effects_map.into_values() {
             let EffectsStakeInfo {
                 stake,
@@ -1867,7 +2645,8 @@ where
                     good_stake = stake,
                     "Found an effect with good stake over threshold"
                 );
-                return CertifiedTransactionEffects::new(effects, signatures, &self.committee);
+                return CertifiedTransactionEffects::new(effects, signatures, &self.committee)
+                    .map(|effects| (effects, first_effects_at));
             }
         }
 
@@ -1895,7 +2674,27 @@ where
     }
 
     pub async fn get_object_info_execute(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
-        let (object_map, cert_map) = self.get_object_by_id(object_id, false).await?;
+        self.get_object_info(object_id, true).await
+    }
+
+    /// Same as `get_object_info_execute`, except that a version known to authorities with less
+    /// than f+1 stake is not accepted, rather than accepted once its certificate was executed
+    /// again on all authorities.
+    pub async fn get_object_info_without_execute(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<ObjectRead> {
+        self.get_object_info(object_id, false).await
+    }
+
+    async fn get_object_info(
+        &self,
+        object_id: ObjectID,
+        execute_certificates: bool,
+    ) -> SuiResult<ObjectRead> {
+        let (object_map, cert_map) = self
+            .get_object_by_id(object_id, ObjectContentOptions::WithTypeLayout, false)
+            .await?;
         let mut object_ref_stack: Vec<_> = object_map.into_iter().collect();
 
         while let Some(((obj_ref, tx_digest), (obj_option, layout_option, authorities))) =
@@ -1910,7 +2709,7 @@ where
             if stake >= self.committee.validity_threshold() {
                 // If we have f+1 stake telling us of the latest version of the object, we just accept it.
                 is_ok = true;
-            } else if cert_map.contains_key(&tx_digest) {
+            } else if execute_certificates && cert_map.contains_key(&tx_digest) {
                 // If we have less stake telling us about the latest state of an object
                 // we re-run the certificate on all authorities to ensure it is correct.
                 if let Ok(effects) = self.process_certificate(cert_map[&tx_digest].clone()).await {
@@ -1943,80 +2742,36 @@ where
         Ok(ObjectRead::NotExists(object_id))
     }
 
-    /// Given a list of object refs, download the objects.
+    /// Given a list of object refs, download the objects. Results are streamed as each download
+    /// completes.
     pub fn fetch_objects_from_authorities(
         &self,
         object_refs: BTreeSet<ObjectRef>,
     ) -> Receiver<SuiResult<Object>> {
         let (sender, receiver) = tokio::sync::mpsc::channel(OBJECT_DOWNLOAD_CHANNEL_BOUND);
+        let authorities = Arc::new(
+            self.peer_availability
+                .reachable_first(self.authority_clients.keys().cloned().collect())
+                .into_iter()
+                .map(|name| (name, self.authority_clients[&name].clone()))
+                .collect::<Vec<_>>(),
+        );
         for object_ref in object_refs {
             let sender = sender.clone();
-            tokio::spawn(Self::fetch_one_object(
-                self.authority_clients.clone(),
-                object_ref,
-                self.timeouts.authority_request_timeout,
-                sender,
-            ));
+            let downloader = self.object_downloader.clone();
+            let authorities = authorities.clone();
+            let timeout = self.timeouts.authority_request_timeout;
+            tokio::spawn(async move {
+                let result = downloader.download(object_ref, authorities, timeout).await;
+                // The receiver may have been dropped, in which case nobody needs the object.
+                let _ = sender.send(result).await;
+            });
         }
         // Close unused channel
         drop(sender);
         receiver
     }
 
-    /// This function fetches one object at a time, and sends back the result over the channel
-    /// The object ids are also returned so the caller can determine which fetches failed
-    /// NOTE: This function assumes all authorities are honest
-    async fn fetch_one_object(
-        authority_clients: BTreeMap<AuthorityName, SafeClient<A>>,
-        object_ref: ObjectRef,
-        timeout: Duration,
-        sender: tokio::sync::mpsc::Sender<Result<Object, SuiError>>,
-    ) {
-        let object_id = object_ref.0;
-        // Prepare the request
-        // TODO: We should let users decide what layout they want in the result.
-        let request = ObjectInfoRequest::latest_object_info_request(
-            object_id,
-            Some(ObjectFormatOptions::default()),
-        );
-
-        // For now assume all authorities. Assume they're all honest
-        // This assumption is woeful, and should be fixed
-        // TODO: https://github.com/MystenLabs/sui/issues/320
-        let results = future::join_all(authority_clients.iter().map(|(_, ac)| {
-            tokio::time::timeout(
-                timeout,
-                ac.handle_object_info_request(request.clone(), false),
-            )
-        }))
-        .await;
-
-        let mut ret_val: Result<Object, SuiError> = Err(SuiError::ObjectFetchFailed {
-            object_id,
-            err: "No authority returned the correct object".to_string(),
-        });
-        // Find the first non-error value
-        // There are multiple reasons why we might not have an object
-        // We can timeout, or the authority returns an error or simply no object
-        // When we get an object back, it also might not match the digest we want
-        for resp in results.into_iter().flatten().flatten() {
-            match resp.object_and_lock {
-                // Either the object is a shared object, in which case we don't care about its content
-                // because we can never keep shared objects up-to-date.
-                // Or if it's not shared object, we check if the digest matches.
-                Some(o) if o.object.is_shared() || o.object.digest() == object_ref.2 => {
-                    ret_val = Ok(o.object);
-                    break;
-                }
-                _ => (),
-            }
-        }
-        sender
-            .send(ret_val)
-            .await
-            .expect("Cannot send object on channel after object fetch attempt");
-    }
-
     pub async fn handle_checkpoint_request(
         &self,
         request: &CheckpointRequest,
@@ -2077,7 +2832,7 @@ where
         digest: &TransactionDigest,
         timeout_total: Option<Duration>,
     ) -> SuiResult<TransactionInfoResponse> {
-        self.quorum_once_with_timeout(
+        self.quorum_once_read_with_timeout(
             None,
             None,
             |_authority, client| {
@@ -2114,7 +2869,7 @@ where
         authorities: Option<&BTreeSet<AuthorityName>>,
         timeout_total: Option<Duration>,
     ) -> SuiResult<(CertifiedTransaction, SignedTransactionEffects)> {
-        self.quorum_once_with_timeout(
+        self.quorum_once_read_with_timeout(
             None,
             authorities,
             |authority, client| {
@@ -2152,6 +2907,292 @@ where
         .await
     }
 
+    /// Fetch the certificates and effects of several transactions, in requests of up to
+    /// MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS digests. The transactions an authority does not have
+    /// are requested again from the other authorities, as are those left out of a response that
+    /// would have been too large, so that a transaction no authority returns only fails its own
+    /// result. The results are in the order of `digests`.
+    pub async fn handle_transactions_and_effects_info_request(
+        &self,
+        digests: &[TransactionDigest],
+        // authorities known to have the transactions we are requesting.
+        authorities: Option<&BTreeSet<AuthorityName>>,
+        timeout_total: Option<Duration>,
+    ) -> Vec<SuiResult<(CertifiedTransaction, SignedTransactionEffects)>> {
+        let mut results = Vec::with_capacity(digests.len());
+        for chunk in digests.chunks(MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS) {
+            let mut found = HashMap::new();
+            let mut errors = HashMap::new();
+            let mut pending = chunk.to_vec();
+            // Authorities which may have the pending transactions.
+            let mut candidates: BTreeSet<AuthorityName> = match authorities {
+                Some(authorities) => authorities.clone(),
+                None => self.committee.names().cloned().collect(),
+            };
+            while !pending.is_empty() && !candidates.is_empty() {
+                let request = &pending;
+                let result = self
+                    .quorum_once_read_with_timeout(
+                        None,
+                        Some(&candidates),
+                        |authority, client| {
+                            Box::pin(async move {
+                                let resp = client
+                                    .handle_transactions_info_request(request.clone().into())
+                                    .await?;
+                                Ok((authority, resp.responses))
+                            })
+                        },
+                        self.timeouts.serial_authority_request_timeout,
+                        timeout_total,
+                        "handle_transactions_and_effects_info_request",
+                    )
+                    .await;
+                let (authority, infos) = match result {
+                    Ok(result) => result,
+                    Err(err) => {
+                        for digest in pending.drain(..) {
+                            errors.insert(digest, err.clone());
+                        }
+                        break;
+                    }
+                };
+
+                // SafeClient checks that responses cover a non-empty prefix of the request.
+                let answered: Vec<_> = pending.drain(..infos.len()).collect();
+                let mut missing = false;
+                for (info, digest) in infos.into_iter().zip(answered) {
+                    match (info.certified_transaction, info.signed_effects) {
+                        (Some(cert), Some(effects)) => {
+                            found.insert(digest, (cert, effects));
+                        }
+                        _ => {
+                            let err = if authorities.is_some() {
+                                // The caller is passing in authorities that have claimed to have
+                                // the cert and effects, so if they now say they don't, they're
+                                // byzantine.
+                                SuiError::ByzantineAuthoritySuspicion {
+                                    authority,
+                                    reason: format!(
+                                        "Validator claimed to have the cert and effects for tx {:?} but did not return them when queried",
+                                        digest,
+                                    ),
+                                }
+                            } else {
+                                SuiError::TransactionNotFound { digest }
+                            };
+                            errors.insert(digest, err);
+                            missing = true;
+                            pending.push(digest);
+                        }
+                    }
+                }
+                if missing {
+                    candidates.remove(&authority);
+                }
+            }
+
+            results.extend(chunk.iter().map(|digest| {
+                match found.get(digest) {
+                    Some(result) => Ok(result.clone()),
+                    None => Err(errors
+                        .get(digest)
+                        .cloned()
+                        .unwrap_or(SuiError::TransactionNotFound { digest: *digest })),
+                }
+            }));
+        }
+        results
+    }
+
+    /// Fetch the effects with the given digest from any authority that has them. This is used
+    /// when a checkpoint lists effects whose transaction digest is not known yet.
+    pub async fn handle_effects_info_request(
+        &self,
+        effects_digest: &TransactionEffectsDigest,
+        // authorities known to have the effects we are requesting.
+        authorities: Option<&BTreeSet<AuthorityName>>,
+        timeout_total: Option<Duration>,
+    ) -> SuiResult<SignedTransactionEffects> {
+        self.quorum_once_read_with_timeout(
+            None,
+            authorities,
+            |authority, client| {
+                Box::pin(async move {
+                    let resp = client
+                        .handle_effects_info_request((*effects_digest).into())
+                        .await?;
+
+                    match resp.signed_effects {
+                        Some(effects) => Ok(effects),
+                        None => {
+                            if authorities.is_some() {
+                                Err(SuiError::ByzantineAuthoritySuspicion {
+                                    authority,
+                                    reason: format!(
+                                        "Validator claimed to have the effects {:?} but did not return them when queried",
+                                        effects_digest,
+                                    )
+                                })
+                            } else {
+                                Err(SuiError::GenericAuthorityError {
+                                    error: format!("Effects {:?} not found", effects_digest),
+                                })
+                            }
+                        }
+                    }
+                })
+            },
+            self.timeouts.serial_authority_request_timeout,
+            timeout_total,
+            "handle_effects_info_request",
+        )
+        .await
+    }
+
+    /// Fetch the certified checkpoint which includes the transaction `digests`, with its
+    /// contents, from any authority that has it.
+    pub async fn get_transaction_checkpoint(
+        &self,
+        digests: &ExecutionDigests,
+        timeout_total: Option<Duration>,
+    ) -> SuiResult<(CertifiedCheckpointSummary, CheckpointContents)> {
+        let request = CheckpointRequest::containing_transaction(*digests, true);
+        self.quorum_once_read_with_timeout(
+            None,
+            None,
+            |_, client| {
+                let r = request.clone();
+                Box::pin(async move {
+                    // SafeClient checked that the contents include the transaction.
+                    match client.handle_checkpoint(r).await? {
+                        CheckpointResponse::AuthenticatedCheckpoint {
+                            checkpoint: Some(AuthenticatedCheckpoint::Certified(checkpoint)),
+                            contents: Some(contents),
+                        } => Ok((checkpoint, contents)),
+                        _ => Err(SuiError::GenericAuthorityError {
+                            error: format!(
+                                "No certified checkpoint includes transaction {:?}",
+                                digests.transaction
+                            ),
+                        }),
+                    }
+                })
+            },
+            self.timeouts.serial_authority_request_timeout,
+            timeout_total,
+            "get_transaction_checkpoint",
+        )
+        .await
+    }
+
+    /// Assembles the finality package of the transaction `digest`: its certificate, its effects
+    /// certified by a quorum of the committee, its events, and the checkpoint which includes it,
+    /// if any. Fails if no effects gather a quorum of signatures.
+    pub async fn get_transaction_finality_package(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<TransactionFinalityPackage> {
+        struct EffectsStakeInfo {
+            stake: StakeUnit,
+            effects: TransactionEffects,
+            signatures: Vec<(AuthorityName, AuthoritySignature)>,
+        }
+        struct FinalityState {
+            certificate: Option<CertifiedTransaction>,
+            effects_map: HashMap<TransactionEffectsDigest, EffectsStakeInfo>,
+            certified_effects: Option<TransactionEffectsDigest>,
+            errors: Vec<(AuthorityName, SuiError)>,
+        }
+
+        let state = FinalityState {
+            certificate: None,
+            effects_map: HashMap::new(),
+            certified_effects: None,
+            errors: vec![],
+        };
+        let threshold = self.committee.quorum_threshold();
+        let mut state = self
+            .quorum_map_then_reduce_with_timeout(
+                state,
+                |_name, client| {
+                    Box::pin(async move {
+                        client
+                            .handle_transaction_info_request((*digest).into())
+                            .await
+                    })
+                },
+                |mut state, name, weight, result| {
+                    Box::pin(async move {
+                        match result {
+                            Ok(TransactionInfoResponse {
+                                certified_transaction: Some(certificate),
+                                signed_effects: Some(signed_effects),
+                                ..
+                            }) => {
+                                state.certificate.get_or_insert(certificate);
+                                let effects_digest = *signed_effects.digest();
+                                let entry = state.effects_map.entry(effects_digest).or_insert(
+                                    EffectsStakeInfo {
+                                        stake: 0,
+                                        effects: signed_effects.effects,
+                                        signatures: vec![],
+                                    },
+                                );
+                                entry.stake += weight;
+                                entry
+                                    .signatures
+                                    .push((name, signed_effects.auth_signature.signature));
+                                if entry.stake >= threshold {
+                                    state.certified_effects = Some(effects_digest);
+                                    return Ok(ReduceOutput::End(state));
+                                }
+                            }
+                            Ok(_) => state
+                                .errors
+                                .push((name, SuiError::TransactionNotFound { digest: *digest })),
+                            Err(err) => state.errors.push((name, err)),
+                        }
+                        Ok(ReduceOutput::Continue(state))
+                    })
+                },
+                self.timeouts.pre_quorum_timeout,
+            )
+            .await?;
+
+        let (certificate, effects) = match (
+            state.certificate,
+            state
+                .certified_effects
+                .and_then(|d| state.effects_map.remove(&d)),
+        ) {
+            (Some(certificate), Some(info)) => (
+                certificate,
+                CertifiedTransactionEffects::new(info.effects, info.signatures, &self.committee)?,
+            ),
+            _ => {
+                return Err(SuiError::TooManyIncorrectAuthorities {
+                    errors: state.errors,
+                    action: "get_transaction_finality_package",
+                })
+            }
+        };
+
+        let digests = ExecutionDigests::new(*digest, *effects.digest());
+        let checkpoint = self
+            .get_transaction_checkpoint(&digests, None)
+            .await
+            .tap_err(|err| debug!(?digest, "No checkpoint found for transaction: {}", err))
+            .ok();
+
+        Ok(TransactionFinalityPackage {
+            events: effects.effects.events.clone(),
+            certificate,
+            effects,
+            checkpoint,
+        })
+    }
+
     /// Given a certificate, execute the cert on remote validators (and preferentially on the
     /// signers of the cert who are guaranteed to be able to process it immediately) until we
     /// receive f+1 identical SignedTransactionEffects - at this point we know we have the
@@ -2303,6 +3344,7 @@ pub struct AuthorityAggregatorBuilder<'a> {
     genesis: Option<&'a Genesis>,
     committee_store: Option<Arc<CommitteeStore>>,
     registry: Option<Arc<Registry>>,
+    authority_preferences: Vec<BTreeSet<AuthorityName>>,
 }
 
 impl<'a> AuthorityAggregatorBuilder<'a> {
@@ -2312,6 +3354,7 @@ impl<'a> AuthorityAggregatorBuilder<'a> {
             genesis: None,
             committee_store: None,
             registry: None,
+            authority_preferences: vec![],
         }
     }
 
@@ -2321,6 +3364,7 @@ impl<'a> AuthorityAggregatorBuilder<'a> {
             genesis: Some(genesis),
             committee_store: None,
             registry: None,
+            authority_preferences: vec![],
         }
     }
 
@@ -2334,6 +3378,13 @@ impl<'a> AuthorityAggregatorBuilder<'a> {
         self
     }
 
+    /// Tiers of authorities to contact first, the most preferred first. See
+    /// `AuthorityPreferences`.
+    pub fn with_authority_preferences(mut self, tiers: Vec<BTreeSet<AuthorityName>>) -> Self {
+        self.authority_preferences = tiers;
+        self
+    }
+
     pub fn build(
         self,
     ) -> anyhow::Result<(
@@ -2364,16 +3415,17 @@ impl<'a> AuthorityAggregatorBuilder<'a> {
         } else {
             Arc::new(CommitteeStore::new_for_testing(&committee))
         };
-        Ok((
-            AuthorityAggregator::new(
-                committee,
-                committee_store,
-                auth_clients.clone(),
-                AuthAggMetrics::new(&registry),
-                Arc::new(SafeClientMetrics::new(&registry)),
-                network_metrics,
-            ),
-            auth_clients,
-        ))
+        let aggregator = AuthorityAggregator::new(
+            committee,
+            committee_store,
+            auth_clients.clone(),
+            AuthAggMetrics::new(&registry),
+            Arc::new(SafeClientMetrics::new(&registry)),
+            network_metrics,
+        );
+        aggregator
+            .authority_preferences
+            .set_tiers(self.authority_preferences);
+        Ok((aggregator, auth_clients))
     }
 }
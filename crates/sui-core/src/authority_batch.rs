@@ -24,7 +24,7 @@ use futures::StreamExt;
 use typed_store::Map;
 
 use tokio::sync::broadcast::{error::RecvError, Receiver, Sender};
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 #[cfg(test)]
 #[path = "unit_tests/batch_tests.rs"]
@@ -57,6 +57,9 @@ pub type BroadcastReceiver = Receiver<UpdateItem>;
 
 pub type BroadcastPair = (BroadcastSender, BroadcastReceiver);
 
+/// How often `drain` checks whether executing certificates were committed and batched.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 impl crate::authority::AuthorityState {
     pub fn last_batch(&self) -> Result<Option<SignedBatch>, SuiError> {
         let last_batch = self
@@ -70,6 +73,50 @@ impl crate::authority::AuthorityState {
         Ok(last_batch)
     }
 
+    /// Shut the intake of work down in stages, so that as little as possible is left to redo
+    /// on restart:
+    /// 1. Stop signing new transactions, and give the certificates pending execution up to
+    ///    `pending_timeout` to be executed. Those left are executed on restart.
+    /// 2. Stop accepting certificates, then wait until the certificates being executed are
+    ///    committed and included in a batch.
+    ///
+    /// Requests are rejected with retryable errors, `ValidatorShuttingDown` for certificates, so
+    /// clients retry on other validators rather than wait for an epoch change. Used to shut the
+    /// node down gracefully, so requires the batch service and the execution driver to be
+    /// running.
+    pub async fn drain(&self, pending_timeout: Duration) -> SuiResult {
+        self.set_intake_halt(Some("the node is shutting down".to_string()));
+        let flushed = tokio::time::timeout(pending_timeout, async {
+            while self.database.pending_count() > 0 {
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            }
+        })
+        .await;
+        if flushed.is_err() {
+            warn!(
+                pending_count = self.database.pending_count(),
+                "Certificates pending execution left for the next start"
+            );
+        }
+
+        self.batch_notifier.pause_for_shutdown();
+        let last_ticket = loop {
+            match self.batch_notifier.ticket_drained() {
+                Some(ticket) => break ticket,
+                None => tokio::time::sleep(DRAIN_POLL_INTERVAL).await,
+            }
+        };
+        debug!(?last_ticket, "Executing certificates committed");
+        while self
+            .last_batch()?
+            .map_or(0, |batch| batch.data().next_sequence_number)
+            < last_ticket
+        {
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+
     /// Initializes the database to handle batches, and recovers from a potential
     /// crash by creating a last batch to include any trailing trasnactions not
     /// in a batch.
@@ -9,17 +9,27 @@ use fastcrypto::traits::ToFromBytes;
 use futures::{stream::BoxStream, TryStreamExt};
 use multiaddr::Multiaddr;
 use mysten_network::config::Config;
-use prometheus::{register_histogram_with_registry, Histogram};
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, Histogram, IntCounterVec, IntGaugeVec,
+};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_config::genesis::Genesis;
 use sui_config::ValidatorInfo;
-use sui_network::{api::ValidatorClient, tonic};
+use sui_network::bandwidth::{BandwidthMetrics, BandwidthService};
+use sui_network::channel_pool::{ChannelPoolConfig, InflightService, PooledChannel};
+use sui_network::failover::{FailoverMetrics, FailoverService};
+use sui_network::message_size::MessageSizeLimitService;
+use sui_network::version::{PeerRole, PeerVersionMonitor, VersionService};
+use sui_network::{api::ValidatorClient, channel_pool::ChannelPool, tonic};
 use sui_types::base_types::AuthorityName;
+use sui_types::bridge::{BridgeAttestationRequest, BridgeAttestationResponse};
 use sui_types::committee::CommitteeWithNetAddresses;
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::{error::SuiError, messages::*};
 
@@ -61,6 +71,18 @@ pub trait AuthorityAPI {
         request: TransactionInfoRequest,
     ) -> Result<TransactionInfoResponse, SuiError>;
 
+    /// Handle information requests for several transactions at once.
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError>;
+
+    /// Handle requests for the effects with a given digest.
+    async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError>;
+
     async fn handle_batch_stream(
         &self,
         request: BatchInfoRequest,
@@ -80,6 +102,18 @@ pub trait AuthorityAPI {
         &self,
         request: CommitteeInfoRequest,
     ) -> Result<CommitteeInfoResponse, SuiError>;
+
+    /// Ask the authority to sign a bridge message for an event it has executed.
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError>;
+
+    /// Ask the authority to sign the network info of its current epoch.
+    async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError>;
 }
 
 pub type BatchInfoResponseItemStream = BoxStream<'static, Result<BatchInfoResponseItem, SuiError>>;
@@ -88,7 +122,7 @@ pub type CheckpointStreamResponseItemStream =
 
 #[derive(Clone)]
 pub struct NetworkAuthorityClient {
-    client: ValidatorClient<Channel>,
+    channels: Arc<ChannelPool>,
     metrics: Arc<NetworkAuthorityClientMetrics>,
 }
 
@@ -113,25 +147,62 @@ impl NetworkAuthorityClient {
     }
 
     pub fn new(channel: Channel, metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
+        Self::new_with_pool(channel.into(), metrics)
+    }
+
+    /// Creates a client which spreads its requests over the channels of `channels`.
+    pub fn new_with_pool(
+        channels: ChannelPool,
+        metrics: Arc<NetworkAuthorityClientMetrics>,
+    ) -> Self {
         Self {
-            client: ValidatorClient::new(channel),
+            channels: Arc::new(channels),
             metrics,
         }
     }
 
-    fn client(&self) -> ValidatorClient<Channel> {
-        self.client.clone()
+    /// Returns a client on the next channel of the pool, which counts its requests as in flight
+    /// on that channel until their responses are received in full.
+    fn client(&self) -> ValidatorClient<ValidatorChannel> {
+        let PooledChannel {
+            address,
+            index,
+            channel,
+            health,
+        } = self.channels.next_channel();
+        let index = index.to_string();
+        let labels = [address, index.as_str()];
+        self.metrics
+            .channel_requests
+            .with_label_values(&labels)
+            .inc();
+        let inflight = self
+            .metrics
+            .channel_inflight_requests
+            .with_label_values(&labels);
+        let channel =
+            FailoverService::client(channel, address, health, self.metrics.failover.clone());
+        let channel = InflightService::new(channel, inflight);
+        let channel = BandwidthService::client(channel, address, self.metrics.bandwidth.clone());
+        let channel = VersionService::client(channel, address, self.metrics.peer_versions.clone());
+        let channel = MessageSizeLimitService::client(channel, self.channels.message_size_limits());
+        ValidatorClient::new(channel)
     }
 }
 
+/// The channel of a client, wrapped in the services run on each of its requests.
+type ValidatorChannel = MessageSizeLimitService<
+    VersionService<BandwidthService<InflightService<FailoverService<Channel>>>>,
+>;
+
 #[async_trait]
 impl Reconfigurable for NetworkAuthorityClient {
     fn needs_network_recreation() -> bool {
         true
     }
 
-    fn recreate(channel: Channel, metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
-        NetworkAuthorityClient::new(channel, metrics)
+    fn recreate(channels: ChannelPool, metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
+        NetworkAuthorityClient::new_with_pool(channels, metrics)
     }
 }
 
@@ -147,7 +218,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_transaction_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .transaction(transaction)
             .await
             .map(tonic::Response::into_inner)
@@ -164,7 +236,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_certificate_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .handle_certificate(certificate)
             .await
             .map(tonic::Response::into_inner)
@@ -180,7 +253,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_account_info_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .account_info(request)
             .await
             .map(tonic::Response::into_inner)
@@ -196,7 +270,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_object_info_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .object_info(request)
             .await
             .map(tonic::Response::into_inner)
@@ -213,20 +288,55 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_transaction_info_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .transaction_info(request)
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
     }
 
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_transactions_info_request_latency
+            .start_timer();
+
+        let mut client = self.client();
+        client
+            .transactions_info(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_effects_info_request_latency
+            .start_timer();
+
+        let mut client = self.client();
+        client
+            .effects_info(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
     /// Handle Batch information requests for this authority.
     async fn handle_batch_stream(
         &self,
         request: BatchInfoRequest,
     ) -> Result<BatchInfoResponseItemStream, SuiError> {
-        let stream = self
-            .client()
+        let mut client = self.client();
+        let stream = client
             .batch_info(request)
             .await
             .map(tonic::Response::into_inner)?
@@ -242,7 +352,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
     ) -> Result<CheckpointResponse, SuiError> {
         let _timer = self.metrics.handle_checkpoint_request_latency.start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .checkpoint(request)
             .await
             .map(tonic::Response::into_inner)
@@ -254,8 +365,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
         &self,
         request: CheckpointStreamRequest,
     ) -> Result<CheckpointStreamResponseItemStream, SuiError> {
-        let stream = self
-            .client()
+        let mut client = self.client();
+        let stream = client
             .checkpoint_info(request)
             .await
             .map(tonic::Response::into_inner)?
@@ -273,26 +384,64 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_committee_info_request_latency
             .start_timer();
 
-        self.client()
+        let mut client = self.client();
+        client
             .committee_info(request)
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
     }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_bridge_attestation_request_latency
+            .start_timer();
+
+        let mut client = self.client();
+        client
+            .bridge_attestation(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_network_info_request_latency
+            .start_timer();
+
+        let mut client = self.client();
+        client
+            .network_info(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::into)
+    }
 }
 
 pub fn make_network_authority_client_sets_from_system_state(
     sui_system_state: &SuiSystemState,
     network_config: &Config,
+    pool_config: &ChannelPoolConfig,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
     for validator in &sui_system_state.validators.active_validators {
-        let address = Multiaddr::try_from(validator.metadata.net_address.clone())?;
-        let channel = network_config
-            .connect_lazy(&address)
-            .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let addresses = parse_net_addresses(
+            &validator.metadata.net_address,
+            &validator.metadata.backup_net_addresses,
+        )?;
+        let channels =
+            ChannelPool::connect_lazy_with_failover(network_config, pool_config, &addresses)?;
+        let client = NetworkAuthorityClient::new_with_pool(channels, network_metrics.clone());
         let name: &[u8] = &validator.metadata.name;
         let public_key_bytes = AuthorityPublicKeyBytes::from_bytes(name)?;
         authority_clients.insert(public_key_bytes, client);
@@ -303,6 +452,7 @@ pub fn make_network_authority_client_sets_from_system_state(
 pub fn make_network_authority_client_sets_from_committee(
     committee: &CommitteeWithNetAddresses,
     network_config: &Config,
+    pool_config: &ChannelPoolConfig,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
@@ -310,11 +460,14 @@ pub fn make_network_authority_client_sets_from_committee(
         let address = committee.net_addresses.get(name).ok_or_else(|| {
             SuiError::from("Missing network address in CommitteeWithNetAddresses")
         })?;
-        let address = Multiaddr::try_from(address.clone())?;
-        let channel = network_config
-            .connect_lazy(&address)
-            .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let backups = committee
+            .backup_net_addresses
+            .get(name)
+            .map_or(&[][..], Vec::as_slice);
+        let addresses = parse_net_addresses(address, backups)?;
+        let channels =
+            ChannelPool::connect_lazy_with_failover(network_config, pool_config, &addresses)?;
+        let client = NetworkAuthorityClient::new_with_pool(channels, network_metrics.clone());
         authority_clients.insert(*name, client);
     }
     Ok(authority_clients)
@@ -323,17 +476,27 @@ pub fn make_network_authority_client_sets_from_committee(
 pub fn make_network_authority_client_sets_from_genesis(
     genesis: &Genesis,
     network_config: &Config,
+    pool_config: &ChannelPoolConfig,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
-    let mut authority_clients = BTreeMap::new();
-    for validator in genesis.validator_set() {
-        let channel = network_config
-            .connect_lazy(validator.network_address())
-            .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
-        authority_clients.insert(validator.protocol_key(), client);
-    }
-    Ok(authority_clients)
+    make_network_authority_client_sets_from_committee(
+        &genesis.committee_with_net_addresses()?,
+        network_config,
+        pool_config,
+        network_metrics,
+    )
+}
+
+/// The network address of a validator followed by its backup addresses, in the order clients
+/// dial them.
+pub fn parse_net_addresses(
+    net_address: &[u8],
+    backup_net_addresses: &[Vec<u8>],
+) -> anyhow::Result<Vec<Multiaddr>> {
+    std::iter::once(net_address)
+        .chain(backup_net_addresses.iter().map(Vec::as_slice))
+        .map(|bytes| Ok(Multiaddr::try_from(bytes.to_vec())?))
+        .collect()
 }
 
 pub fn make_authority_clients(
@@ -347,10 +510,13 @@ pub fn make_authority_clients(
     network_config.connect_timeout = Some(connect_timeout);
     network_config.request_timeout = Some(request_timeout);
     for authority in validator_set {
-        let channel = network_config
-            .connect_lazy(authority.network_address())
-            .unwrap();
-        let client = NetworkAuthorityClient::new(channel, net_metrics.clone());
+        let channels = ChannelPool::connect_lazy(
+            &network_config,
+            &ChannelPoolConfig::default(),
+            authority.network_address(),
+        )
+        .unwrap();
+        let client = NetworkAuthorityClient::new_with_pool(channels, net_metrics.clone());
         authority_clients.insert(authority.protocol_key(), client);
     }
     authority_clients
@@ -381,7 +547,7 @@ impl Reconfigurable for LocalAuthorityClient {
         false
     }
 
-    fn recreate(_channel: Channel, _metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
+    fn recreate(_channels: ChannelPool, _metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
         unreachable!(); // this function should not get called because the above function returns false
     }
 }
@@ -443,6 +609,22 @@ impl AuthorityAPI for LocalAuthorityClient {
         state.handle_transaction_info_request(request).await
     }
 
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        let state = self.state.clone();
+        state.handle_transactions_info_request(request).await
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        let state = self.state.clone();
+        state.handle_effects_info_request(request).await
+    }
+
     /// Handle Batch information requests for this authority.
     async fn handle_batch_stream(
         &self,
@@ -479,6 +661,24 @@ impl AuthorityAPI for LocalAuthorityClient {
 
         state.handle_committee_info_request(&request)
     }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        let state = self.state.clone();
+
+        state.handle_bridge_attestation_request(&request)
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        let state = self.state.clone();
+
+        state.handle_network_info_request(&request)
+    }
 }
 
 impl LocalAuthorityClient {
@@ -550,8 +750,22 @@ pub struct NetworkAuthorityClientMetrics {
     pub handle_account_info_request_latency: Histogram,
     pub handle_object_info_request_latency: Histogram,
     pub handle_transaction_info_request_latency: Histogram,
+    pub handle_transactions_info_request_latency: Histogram,
+    pub handle_effects_info_request_latency: Histogram,
     pub handle_checkpoint_request_latency: Histogram,
     pub handle_committee_info_request_latency: Histogram,
+    pub handle_bridge_attestation_request_latency: Histogram,
+    pub handle_network_info_request_latency: Histogram,
+    /// Requests sent on each channel of the channel pools, by address and channel index.
+    pub channel_requests: IntCounterVec,
+    /// Requests in flight on each channel of the channel pools, by address and channel index.
+    pub channel_inflight_requests: IntGaugeVec,
+    /// Versions of the authorities, learnt from their responses.
+    pub peer_versions: Arc<PeerVersionMonitor>,
+    /// Bytes sent to and received from each authority, by method.
+    pub bandwidth: Arc<BandwidthMetrics>,
+    /// Health of each address of the authorities.
+    pub failover: Arc<FailoverMetrics>,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -596,6 +810,20 @@ impl NetworkAuthorityClientMetrics {
                 registry
             )
             .unwrap(),
+            handle_transactions_info_request_latency: register_histogram_with_registry!(
+                "handle_transactions_info_request_latency",
+                "Latency of handle transactions info request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            handle_effects_info_request_latency: register_histogram_with_registry!(
+                "handle_effects_info_request_latency",
+                "Latency of handle effects info request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
             handle_checkpoint_request_latency: register_histogram_with_registry!(
                 "handle_checkpoint_request_latency",
                 "Latency of handle checkpoint request",
@@ -610,6 +838,41 @@ impl NetworkAuthorityClientMetrics {
                 registry
             )
             .unwrap(),
+            handle_bridge_attestation_request_latency: register_histogram_with_registry!(
+                "handle_bridge_attestation_request_latency",
+                "Latency of handle bridge attestation request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            handle_network_info_request_latency: register_histogram_with_registry!(
+                "handle_network_info_request_latency",
+                "Latency of handle network info request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            channel_requests: register_int_counter_vec_with_registry!(
+                "network_client_channel_requests",
+                "Number of requests sent on each channel to an authority",
+                &["address", "channel"],
+                registry
+            )
+            .unwrap(),
+            channel_inflight_requests: register_int_gauge_vec_with_registry!(
+                "network_client_channel_inflight_requests",
+                "Number of requests in flight on each channel to an authority",
+                &["address", "channel"],
+                registry
+            )
+            .unwrap(),
+            peer_versions: Arc::new(PeerVersionMonitor::new(
+                PeerRole::Server,
+                env!("CARGO_PKG_VERSION"),
+                registry,
+            )),
+            bandwidth: Arc::new(BandwidthMetrics::new(registry)),
+            failover: Arc::new(FailoverMetrics::new(registry)),
         }
     }
 
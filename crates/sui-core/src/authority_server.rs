@@ -3,25 +3,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admission_control::{AdmissionController, AdmissionPermit, RequestKind},
     authority::{AuthorityState, ReconfigConsensusMessage},
     consensus_adapter::{
         CheckpointConsensusAdapter, CheckpointSender, ConsensusAdapter, ConsensusAdapterMetrics,
         ConsensusListener, ConsensusListenerMessage,
     },
-    metrics::start_timer,
+    consensus_monitor::ConsensusMonitor,
+    deny_list::DenyStage,
+    metrics::{start_timer, StageTimer},
+    slow_calls::CallServer,
 };
 use anyhow::anyhow;
 use anyhow::Result;
 use async_trait::async_trait;
 use fastcrypto::traits::KeyPair;
-use futures::{stream::BoxStream, TryStreamExt};
+use futures::{stream::BoxStream, Future, TryStreamExt};
 use multiaddr::Multiaddr;
-use prometheus::{register_histogram_with_registry, Histogram, Registry};
-use std::{io, sync::Arc, time::Duration};
+use prometheus::{
+    register_histogram_vec_with_registry, register_histogram_with_registry, Histogram,
+    HistogramVec, Registry,
+};
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use sui_config::NodeConfig;
 use sui_network::{
     api::{Validator, ValidatorServer},
+    message_size::{MessageSizeLimitService, MessageSizeLimits},
     tonic,
+    version::{PeerRole, PeerVersionMonitor, VersionService},
 };
 
 use sui_types::{error::*, messages::*};
@@ -32,8 +45,10 @@ use tokio::{
     task::JoinHandle,
 };
 
+use sui_types::bridge::{BridgeAttestationRequest, BridgeAttestationResponse};
 use sui_types::messages_checkpoint::CheckpointRequest;
 use sui_types::messages_checkpoint::CheckpointResponse;
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 
 use crate::consensus_handler::ConsensusHandler;
 use tracing::{debug, error, info, Instrument};
@@ -45,10 +60,6 @@ mod server_tests;
 const MIN_BATCH_SIZE: u64 = 1000;
 const MAX_DELAY_MILLIS: u64 = 5_000; // 5 sec
 
-// Assuming 200 consensus tps * 5 sec consensus latency = 1000 inflight consensus txns.
-// Leaving a bit more headroom to cap the max inflight consensus txns to 1000*2 = 2000.
-const MAX_PENDING_CONSENSUS_TRANSACTIONS: u64 = 2000;
-
 pub struct AuthorityServerHandle {
     tx_cancellation: tokio::sync::oneshot::Sender<()>,
     local_addr: Multiaddr,
@@ -83,8 +94,13 @@ pub struct AuthorityServer {
     address: Multiaddr,
     pub state: Arc<AuthorityState>,
     consensus_adapter: ConsensusAdapter,
+    consensus_monitor: Arc<ConsensusMonitor>,
     min_batch_size: u64,
     max_delay: Duration,
+    /// Versions of the clients, learnt from their requests.
+    pub peer_versions: Arc<PeerVersionMonitor>,
+    /// Max sizes of the messages received and sent by the server.
+    pub message_size_limits: MessageSizeLimits,
 }
 
 impl AuthorityServer {
@@ -95,11 +111,13 @@ impl AuthorityServer {
         tx_consensus_listener: Sender<ConsensusListenerMessage>,
     ) -> Self {
         let metrics = ConsensusAdapterMetrics::new_test();
+        let consensus_monitor = Arc::new(ConsensusMonitor::new_for_test());
         let consensus_adapter = ConsensusAdapter::new(
             consensus_address,
             state.clone_committee(),
             tx_consensus_listener,
             Duration::from_secs(20),
+            consensus_monitor.clone(),
             metrics,
         );
 
@@ -107,8 +125,15 @@ impl AuthorityServer {
             address,
             state,
             consensus_adapter,
+            consensus_monitor,
             min_batch_size: MIN_BATCH_SIZE,
             max_delay: Duration::from_millis(MAX_DELAY_MILLIS),
+            peer_versions: Arc::new(PeerVersionMonitor::new(
+                PeerRole::Client,
+                env!("CARGO_PKG_VERSION"),
+                &Registry::new(),
+            )),
+            message_size_limits: MessageSizeLimits::default(),
         }
     }
 
@@ -145,12 +170,20 @@ impl AuthorityServer {
 
         let mut server = mysten_network::config::Config::new()
             .server_builder()
-            .add_service(ValidatorServer::new(ValidatorService {
-                state: self.state,
-                consensus_adapter: Arc::new(self.consensus_adapter),
-                _checkpoint_consensus_handle: None,
-                metrics: Arc::new(ValidatorServiceMetrics::new_for_tests()),
-            }))
+            .add_service(MessageSizeLimitService::server(
+                VersionService::server(
+                    ValidatorServer::new(ValidatorService {
+                        state: self.state,
+                        consensus_adapter: Arc::new(self.consensus_adapter),
+                        consensus_monitor: self.consensus_monitor,
+                        _checkpoint_consensus_handle: None,
+                        metrics: Arc::new(ValidatorServiceMetrics::new_for_tests()),
+                        admission_control: None,
+                    }),
+                    self.peer_versions,
+                ),
+                self.message_size_limits,
+            ))
             .bind(&address)
             .await
             .unwrap();
@@ -173,6 +206,7 @@ pub struct ValidatorServiceMetrics {
     pub handle_transaction_non_consensus_latency: Histogram,
     pub handle_certificate_consensus_latency: Histogram,
     pub handle_certificate_non_consensus_latency: Histogram,
+    pub shared_obj_tx_stage_latency: HistogramVec,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -231,6 +265,15 @@ impl ValidatorServiceMetrics {
                 registry,
             )
             .unwrap(),
+            shared_obj_tx_stage_latency: register_histogram_vec_with_registry!(
+                "validator_service_shared_obj_tx_stage_latency",
+                "Latency of each stage of handling a shared object transaction certificate: \
+                 pre_consensus (verification and checks), consensus (until sequenced) and execution",
+                &["stage"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -243,8 +286,10 @@ impl ValidatorServiceMetrics {
 pub struct ValidatorService {
     state: Arc<AuthorityState>,
     consensus_adapter: Arc<ConsensusAdapter>,
+    consensus_monitor: Arc<ConsensusMonitor>,
     _checkpoint_consensus_handle: Option<JoinHandle<()>>,
     metrics: Arc<ValidatorServiceMetrics>,
+    admission_control: Option<Arc<AdmissionController>>,
 }
 
 impl ValidatorService {
@@ -267,8 +312,12 @@ impl ValidatorService {
         let consensus_committee = config.genesis()?.narwhal_committee().load();
         let consensus_worker_cache = config.genesis()?.narwhal_worker_cache();
         let consensus_storage_base_path = consensus_config.db_path().to_path_buf();
-        let consensus_execution_state =
-            ConsensusHandler::new(state.clone(), tx_consensus_listener.clone());
+        let consensus_monitor = Arc::new(ConsensusMonitor::new(&prometheus_registry));
+        let consensus_execution_state = ConsensusHandler::new(
+            state.clone(),
+            tx_consensus_listener.clone(),
+            consensus_monitor.clone(),
+        );
         let consensus_execution_state = Arc::new(consensus_execution_state);
         let consensus_parameters = consensus_config.narwhal_config().to_owned();
         let network_keypair = config.network_key_pair.copy();
@@ -303,6 +352,7 @@ impl ValidatorService {
             state.clone_committee(),
             tx_consensus_listener.clone(),
             timeout,
+            consensus_monitor.clone(),
             ca_metrics.clone(),
         );
 
@@ -327,14 +377,72 @@ impl ValidatorService {
             .spawn(),
         );
 
+        let admission_control = config
+            .admission_control
+            .as_ref()
+            .map(|config| Arc::new(AdmissionController::new(config, &prometheus_registry)));
+
         Ok(Self {
             state,
             consensus_adapter: Arc::new(consensus_adapter),
+            consensus_monitor,
             _checkpoint_consensus_handle: checkpoint_consensus_handle,
             metrics: Arc::new(ValidatorServiceMetrics::new(&prometheus_registry)),
+            admission_control,
         })
     }
 
+    /// The health of consensus as seen from this validator.
+    pub fn consensus_monitor(&self) -> Arc<ConsensusMonitor> {
+        self.consensus_monitor.clone()
+    }
+
+    /// Wait for admission of `request`, if admission control is enabled.
+    async fn admit<T>(
+        &self,
+        request: &tonic::Request<T>,
+        kind: RequestKind,
+    ) -> Result<Option<AdmissionPermit>, tonic::Status> {
+        let admission_control = match &self.admission_control {
+            Some(admission_control) => admission_control,
+            None => return Ok(None),
+        };
+        let class = admission_control.classify(request.remote_addr());
+        admission_control
+            .admit(class, kind)
+            .await
+            .map(Some)
+            .ok_or_else(|| {
+                SuiError::ValidatorOverloaded {
+                    reason: format!("{kind:?} from {class:?} peer rejected"),
+                    retry_after_ms: Some(admission_control.retry_after().as_millis() as u64),
+                }
+                .into()
+            })
+    }
+
+    /// Await the response to a call of the gRPC method `method`, named as on the wire, recording
+    /// the call if it exceeds the gRPC slow call threshold. `params` describes the request, and
+    /// is only evaluated for slow calls: it captures what identifies the request rather than a
+    /// copy of it.
+    async fn record_slow_call<T>(
+        &self,
+        method: &str,
+        params: impl FnOnce() -> String,
+        response: impl Future<Output = Result<T, tonic::Status>>,
+    ) -> Result<T, tonic::Status> {
+        let started_at = Instant::now();
+        let response = response.await;
+        self.state.slow_calls.record(
+            CallServer::Grpc,
+            method,
+            params,
+            started_at.elapsed(),
+            response.is_ok(),
+        );
+        response
+    }
+
     async fn handle_transaction(
         state: Arc<AuthorityState>,
         request: tonic::Request<Transaction>,
@@ -350,9 +458,7 @@ impl ValidatorService {
         });
         let tx_verif_metrics_guard = start_timer(metrics.tx_verification_latency.clone());
 
-        transaction
-            .verify()
-            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        transaction.verify().map_err(tonic::Status::from)?;
         drop(tx_verif_metrics_guard);
         // TODO This is really really bad, we should have different types for signature-verified transactions
         transaction.is_verified = true;
@@ -370,7 +476,7 @@ impl ValidatorService {
             .handle_transaction(transaction)
             .instrument(span)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
         Ok(tonic::Response::new(info))
     }
@@ -389,13 +495,14 @@ impl ValidatorService {
         } else {
             metrics.handle_certificate_non_consensus_latency.clone()
         });
+        let mut stage_timer = StageTimer::new(metrics.shared_obj_tx_stage_latency.clone());
 
         // 1) Verify certificate
         let cert_verif_metrics_guard = start_timer(metrics.cert_verification_latency.clone());
 
         certificate
             .verify(&state.committee.load())
-            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
         drop(cert_verif_metrics_guard);
         // TODO This is really really bad, we should have different types for signature verified transactions
         certificate.is_verified = true;
@@ -405,7 +512,7 @@ impl ValidatorService {
         if let Some(response) = state
             .get_tx_info_already_executed(tx_digest)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .map_err(tonic::Status::from)?
         {
             return Ok(tonic::Response::new(response));
         }
@@ -413,33 +520,40 @@ impl ValidatorService {
         // 3) If the validator is already halted, we stop here, to avoid
         // sending the transaction to consensus.
         if state.is_halted() && !certificate.signed_data.data.kind.is_system_tx() {
-            return Err(tonic::Status::internal(
-                SuiError::ValidatorHaltedAtEpochEnd.to_string(),
-            ));
+            return Err(state.halted_error().into());
         }
 
-        // 4) If it's a shared object transaction and requires consensus, we need to do so.
+        // 4) Refuse to sequence or execute certificates calling denied packages on behalf of
+        // clients. Certificates received through consensus or state sync are still executed, so
+        // that the state of this validator does not diverge from the others.
+        state
+            .deny_list
+            .check(&certificate.signed_data.data, DenyStage::Execution)
+            .map_err(tonic::Status::from)?;
+
+        // 5) If it's a shared object transaction and requires consensus, we need to do so.
         // This will wait until either timeout or we have heard back from consensus.
         if is_consensus_tx
             && !state
                 .transaction_shared_locks_exist(&certificate)
                 .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?
+                .map_err(tonic::Status::from)?
         {
-            // Note that num_inflight_transactions() only include user submitted transactions, and only user txns can be dropped here.
+            // Note that the backpressure only counts user submitted transactions, and only user txns can be dropped here.
             // This backpressure should not affect system transactions, e.g. for checkpointing.
-            if consensus_adapter.num_inflight_transactions() > MAX_PENDING_CONSENSUS_TRANSACTIONS {
-                return Err(tonic::Status::resource_exhausted("Reached {MAX_PENDING_CONSENSUS_TRANSACTIONS} concurrent consensus transactions",
-                ));
-            }
+            consensus_adapter
+                .check_backpressure()
+                .map_err(tonic::Status::from)?;
+            stage_timer.finish_stage("pre_consensus");
             let _metrics_guard = start_timer(metrics.consensus_latency.clone());
             consensus_adapter
                 .submit(&state.name, &certificate)
                 .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?;
+                .map_err(tonic::Status::from)?;
+            stage_timer.finish_stage("consensus");
         }
 
-        // 5) Execute the certificate.
+        // 6) Execute the certificate.
         // Often we cannot execute a cert due to dependenties haven't been executed, and we will
         // observe ObjectErrors. In such case, we can wait and retry. It should eventually
         // succeed.
@@ -459,14 +573,14 @@ impl ValidatorService {
             {
                 err @ Err(SuiError::ObjectErrors { .. }) => {
                     if retry_cnt >= 30 {
-                        return Err(tonic::Status::internal(err.unwrap_err().to_string()));
+                        return Err(err.unwrap_err().into());
                     }
                     if !is_consensus_tx {
                         error!(
                             ?tx_digest,
                             "Owned object transaction cert execution should not have object error"
                         );
-                        return Err(tonic::Status::internal(err.unwrap_err().to_string()));
+                        return Err(err.unwrap_err().into());
                     }
                     debug!(
                         ?tx_digest,
@@ -482,9 +596,12 @@ impl ValidatorService {
                     let _ = state
                         .add_pending_certificates(vec![(tx_digest, Some(certificate))])
                         .tap_err(|e| error!(?tx_digest, "add_pending_certificates failed: {}", e));
-                    return Err(tonic::Status::internal(e.to_string()));
+                    return Err(e.into());
                 }
                 Ok(response) => {
+                    if is_consensus_tx {
+                        stage_timer.finish_stage("execution");
+                    }
                     return Ok(tonic::Response::new(response));
                 }
             }
@@ -498,31 +615,45 @@ impl Validator for ValidatorService {
         &self,
         request: tonic::Request<Transaction>,
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
-        let state = self.state.clone();
-
-        // Spawns a task which handles the transaction. The task will unconditionally continue
-        // processing in the event that the client connection is dropped.
-        let metrics = self.metrics.clone();
-        tokio::spawn(async move { Self::handle_transaction(state, request, metrics).await })
+        let tx_digest = *request.get_ref().digest();
+        self.record_slow_call("Transaction", || format!("{tx_digest:?}"), async {
+            let permit = self.admit(&request, RequestKind::Transaction).await?;
+            let state = self.state.clone();
+
+            // Spawns a task which handles the transaction. The task will unconditionally continue
+            // processing in the event that the client connection is dropped.
+            let metrics = self.metrics.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                Self::handle_transaction(state, request, metrics).await
+            })
             .await
             .unwrap()
+        })
+        .await
     }
 
     async fn handle_certificate(
         &self,
         request: tonic::Request<CertifiedTransaction>,
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
-        let state = self.state.clone();
-        let consensus_adapter = self.consensus_adapter.clone();
-
-        // Spawns a task which handles the certificate. The task will unconditionally continue
-        // processing in the event that the client connection is dropped.
-        let metrics = self.metrics.clone();
-        tokio::spawn(async move {
-            Self::handle_certificate(state, consensus_adapter, request, metrics).await
+        let tx_digest = *request.get_ref().digest();
+        self.record_slow_call("CertifiedTransaction", || format!("{tx_digest:?}"), async {
+            let permit = self.admit(&request, RequestKind::Certificate).await?;
+            let state = self.state.clone();
+            let consensus_adapter = self.consensus_adapter.clone();
+
+            // Spawns a task which handles the certificate. The task will unconditionally continue
+            // processing in the event that the client connection is dropped.
+            let metrics = self.metrics.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                Self::handle_certificate(state, consensus_adapter, request, metrics).await
+            })
+            .await
+            .unwrap()
         })
         .await
-        .unwrap()
     }
 
     async fn account_info(
@@ -530,14 +661,18 @@ impl Validator for ValidatorService {
         request: tonic::Request<AccountInfoRequest>,
     ) -> Result<tonic::Response<AccountInfoResponse>, tonic::Status> {
         let request = request.into_inner();
+        let account = request.account;
 
-        let response = self
-            .state
-            .handle_account_info_request(request)
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        self.record_slow_call("AccountInfo", || format!("{account}"), async {
+            let response = self
+                .state
+                .handle_account_info_request(request)
+                .await
+                .map_err(tonic::Status::from)?;
 
-        Ok(tonic::Response::new(response))
+            Ok(tonic::Response::new(response))
+        })
+        .await
     }
 
     async fn object_info(
@@ -545,14 +680,22 @@ impl Validator for ValidatorService {
         request: tonic::Request<ObjectInfoRequest>,
     ) -> Result<tonic::Response<ObjectInfoResponse>, tonic::Status> {
         let request = request.into_inner();
-
-        let response = self
-            .state
-            .handle_object_info_request(request)
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-
-        Ok(tonic::Response::new(response))
+        let (object_id, request_kind) = (request.object_id, request.request_kind.clone());
+
+        self.record_slow_call(
+            "ObjectInfo",
+            || format!("{object_id} {request_kind:?}"),
+            async {
+                let response = self
+                    .state
+                    .handle_object_info_request(request)
+                    .await
+                    .map_err(tonic::Status::from)?;
+
+                Ok(tonic::Response::new(response))
+            },
+        )
+        .await
     }
 
     async fn transaction_info(
@@ -560,14 +703,56 @@ impl Validator for ValidatorService {
         request: tonic::Request<TransactionInfoRequest>,
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
         let request = request.into_inner();
+        let tx_digest = request.transaction_digest;
 
-        let response = self
-            .state
-            .handle_transaction_info_request(request)
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        self.record_slow_call("TransactionInfo", || format!("{tx_digest:?}"), async {
+            let response = self
+                .state
+                .handle_transaction_info_request(request)
+                .await
+                .map_err(tonic::Status::from)?;
+
+            Ok(tonic::Response::new(response))
+        })
+        .await
+    }
+
+    async fn transactions_info(
+        &self,
+        request: tonic::Request<TransactionsInfoRequest>,
+    ) -> Result<tonic::Response<TransactionsInfoResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let count = request.transaction_digests.len();
+
+        self.record_slow_call("TransactionsInfo", || format!("{count} digests"), async {
+            let response = self
+                .state
+                .handle_transactions_info_request(request)
+                .await
+                .map_err(tonic::Status::from)?;
 
-        Ok(tonic::Response::new(response))
+            Ok(tonic::Response::new(response))
+        })
+        .await
+    }
+
+    async fn effects_info(
+        &self,
+        request: tonic::Request<EffectsInfoRequest>,
+    ) -> Result<tonic::Response<EffectsInfoResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let effects_digest = request.effects_digest;
+
+        self.record_slow_call("EffectsInfo", || format!("{effects_digest:?}"), async {
+            let response = self
+                .state
+                .handle_effects_info_request(request)
+                .await
+                .map_err(tonic::Status::from)?;
+
+            Ok(tonic::Response::new(response))
+        })
+        .await
     }
 
     type FollowTxStreamStream = BoxStream<'static, Result<BatchInfoResponseItem, tonic::Status>>;
@@ -582,9 +767,9 @@ impl Validator for ValidatorService {
             .state
             .handle_batch_streaming(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
-        let response = xstream.map_err(|e| tonic::Status::internal(e.to_string()));
+        let response = xstream.map_err(tonic::Status::from);
 
         Ok(tonic::Response::new(Box::pin(response)))
     }
@@ -595,12 +780,15 @@ impl Validator for ValidatorService {
     ) -> Result<tonic::Response<CheckpointResponse>, tonic::Status> {
         let request = request.into_inner();
 
-        let response = self
-            .state
-            .handle_checkpoint_request(&request)
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        self.record_slow_call("Checkpoint", || format!("{request:?}"), async {
+            let response = self
+                .state
+                .handle_checkpoint_request(&request)
+                .map_err(tonic::Status::from)?;
 
-        return Ok(tonic::Response::new(response));
+            Ok(tonic::Response::new(response))
+        })
+        .await
     }
 
     type FollowCheckpointStreamStream =
@@ -614,9 +802,9 @@ impl Validator for ValidatorService {
             .state
             .handle_checkpoint_streaming(request)
             .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            .map_err(tonic::Status::from)?;
 
-        let response = xstream.map_err(|e| tonic::Status::internal(e.to_string()));
+        let response = xstream.map_err(tonic::Status::from);
 
         Ok(tonic::Response::new(Box::pin(response)))
     }
@@ -627,11 +815,48 @@ impl Validator for ValidatorService {
     ) -> Result<tonic::Response<CommitteeInfoResponse>, tonic::Status> {
         let request = request.into_inner();
 
-        let response = self
-            .state
-            .handle_committee_info_request(&request)
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        self.record_slow_call("CommitteeInfo", || format!("{request:?}"), async {
+            let response = self
+                .state
+                .handle_committee_info_request(&request)
+                .map_err(tonic::Status::from)?;
+
+            Ok(tonic::Response::new(response))
+        })
+        .await
+    }
+
+    async fn bridge_attestation(
+        &self,
+        request: tonic::Request<BridgeAttestationRequest>,
+    ) -> Result<tonic::Response<BridgeAttestationResponse>, tonic::Status> {
+        let request = request.into_inner();
 
-        return Ok(tonic::Response::new(response));
+        self.record_slow_call("BridgeAttestation", || format!("{request:?}"), async {
+            let response = self
+                .state
+                .handle_bridge_attestation_request(&request)
+                .map_err(tonic::Status::from)?;
+
+            Ok(tonic::Response::new(response))
+        })
+        .await
+    }
+
+    async fn network_info(
+        &self,
+        request: tonic::Request<NetworkInfoRequest>,
+    ) -> Result<tonic::Response<NetworkInfoResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        self.record_slow_call("NetworkInfo", || format!("{request:?}"), async {
+            let response = self
+                .state
+                .handle_network_info_request(&request)
+                .map_err(tonic::Status::from)?;
+
+            Ok(tonic::Response::new(response))
+        })
+        .await
     }
 }
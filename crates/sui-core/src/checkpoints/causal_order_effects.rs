@@ -8,7 +8,7 @@ use std::{
 };
 
 use sui_types::{
-    base_types::{ExecutionDigests, TransactionDigest},
+    base_types::{ExecutionDigests, ObjectDigest, ObjectRef, TransactionDigest},
     error::{SuiError, SuiResult},
     gas::GasCostSummary,
     messages::TransactionEffects,
@@ -184,6 +184,45 @@ pub trait EffectsStore {
 
         Ok((execution_digests, gas_summary))
     }
+
+    /// Return the reference of the live object version that `object_ref` supersedes, if any.
+    /// Stores that do not keep the object history never report a previous version.
+    fn get_previous_live_object_ref(
+        &self,
+        _object_ref: &ObjectRef,
+    ) -> SuiResult<Option<ObjectRef>> {
+        Ok(None)
+    }
+
+    /// Return the changes the given transactions make to the live object set, as the object
+    /// references that become live and the references that stop being live.
+    fn get_live_object_changes<'a>(
+        &self,
+        transactions: impl Iterator<Item = &'a ExecutionDigests> + Clone,
+    ) -> SuiResult<(Vec<ObjectRef>, Vec<ObjectRef>)> {
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        for effects in self.get_effects(transactions)? {
+            let effects = effects.ok_or_else(|| {
+                SuiError::from(
+                    "Cannot compute the state of a checkpoint with unexecuted transactions.",
+                )
+            })?;
+            added.extend(effects.all_mutated().map(|(obj_ref, _, _)| *obj_ref));
+            // Every written, deleted or wrapped object supersedes its previous version.
+            for obj_ref in effects
+                .all_mutated()
+                .map(|(obj_ref, _, _)| obj_ref)
+                .chain(effects.deleted.iter())
+                .chain(effects.wrapped.iter())
+            {
+                if let Some(previous) = self.get_previous_live_object_ref(obj_ref)? {
+                    removed.push(previous);
+                }
+            }
+        }
+        Ok((added, removed))
+    }
 }
 
 impl EffectsStore for Arc<AuthorityStore> {
@@ -199,6 +238,21 @@ impl EffectsStore for Arc<AuthorityStore> {
             .map(|item| item.map(|x| x.effects))
             .collect())
     }
+
+    fn get_previous_live_object_ref(&self, object_ref: &ObjectRef) -> SuiResult<Option<ObjectRef>> {
+        let (object_id, version, _) = *object_ref;
+        let previous = self
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            // The smallest possible entry for this version, so that we land on the one before.
+            .skip_prior_to(&(object_id, version, ObjectDigest::MIN))?
+            .next()
+            .map(|(previous, _)| previous);
+        Ok(previous.filter(|(id, previous_version, digest)| {
+            *id == object_id && *previous_version < version && digest.is_alive()
+        }))
+    }
 }
 
 /// A transaction effects store that returns an identity causal order. For testing.
@@ -225,6 +279,17 @@ impl EffectsStore for TestEffectsStore {
             .collect())
     }
 
+    /// Transactions without effects in the store change no objects.
+    fn get_live_object_changes<'a>(
+        &self,
+        transactions: impl Iterator<Item = &'a ExecutionDigests> + Clone,
+    ) -> SuiResult<(Vec<ObjectRef>, Vec<ObjectRef>)> {
+        let executed: Vec<_> = transactions
+            .filter(|digests| self.0.contains_key(&digests.transaction))
+            .collect();
+        self.0.get_live_object_changes(executed.into_iter())
+    }
+
     fn get_causal_order_and_gas_summary_from_effects<'a>(
         &self,
         transactions: impl Iterator<Item = &'a ExecutionDigests> + Clone,
@@ -14,10 +14,12 @@ use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::{path::Path, sync::Arc};
+use sui_storage::compression::with_cf_compression;
+use sui_storage::db_metrics::DBMetrics;
 use sui_storage::default_db_options;
 use sui_types::messages_checkpoint::{CheckpointProposal, CheckpointProposalContents};
 use sui_types::{
-    base_types::{AuthorityName, ExecutionDigests},
+    base_types::{AuthorityName, ExecutionDigests, ObjectRef},
     batch::TxSequenceNumber,
     committee::{Committee, EpochId},
     error::{SuiError, SuiResult},
@@ -27,6 +29,7 @@ use sui_types::{
         CheckpointFragment, CheckpointResponse, CheckpointSequenceNumber, CheckpointSummary,
         SignedCheckpointSummary,
     },
+    state_accumulator::StateAccumulator,
 };
 use tap::TapFallible;
 use tokio::sync::broadcast;
@@ -117,6 +120,7 @@ pub struct CheckpointStoreTables {
     /// The checkpoint content should be causally ordered and is consistent among
     /// all validators.
     /// TODO: CheckpointContents may grow very big and becomes problematic to store as db value.
+    #[default_options_override_fn = "checkpoint_contents_table_default_config"]
     pub checkpoint_contents: DBMap<CheckpointSequenceNumber, CheckpointContents>,
 
     /// The set of transaction/effects this authority has processed but have not yet been
@@ -146,6 +150,10 @@ pub struct CheckpointStoreTables {
     /// A single entry table to store locals.
     #[default_options_override_fn = "locals_table_default_config"]
     pub locals: DBMap<DBLabel, CheckpointLocals>,
+
+    /// A single entry table holding the accumulator over the genesis objects, from which
+    /// the state digest of the first checkpoint is derived.
+    pub genesis_state: DBMap<DBLabel, StateAccumulator>,
 }
 
 // These functions are used to initialize the DB tables
@@ -156,8 +164,12 @@ fn extra_transactions_table_default_config() -> Options {
     default_db_options(None, None).1
 }
 
+fn checkpoint_contents_table_default_config() -> Options {
+    with_cf_compression("checkpoint_contents", default_db_options(None, None).1)
+}
+
 fn checkpoints_table_default_config() -> Options {
-    default_db_options(None, None).1
+    with_cf_compression("checkpoints", default_db_options(None, None).1)
 }
 fn local_fragments_table_default_config() -> Options {
     default_db_options(None, None).1
@@ -266,6 +278,35 @@ impl CheckpointStore {
         })
     }
 
+    /// Get the accumulator over the live object set before the given checkpoint. This is the
+    /// state committed to by the previous checkpoint, or the genesis state for checkpoint 0.
+    fn get_prev_state_accumulator(
+        &self,
+        checkpoint_sequence: CheckpointSequenceNumber,
+    ) -> SuiResult<StateAccumulator> {
+        if checkpoint_sequence == 0 {
+            // Stores not initialized from a genesis, e.g. in tests, start from no objects.
+            return Ok(self.tables.genesis_state.get(&LOCALS)?.unwrap_or_default());
+        }
+        let prev_checkpoint = self
+            .get_checkpoint(checkpoint_sequence - 1)?
+            .ok_or_else(|| {
+                SuiError::from("Cannot compute the state of a checkpoint without its predecessor.")
+            })?;
+        StateAccumulator::from_digest(&prev_checkpoint.summary().state_digest)
+    }
+
+    /// Record the genesis object set, which the state digest of the first checkpoint builds on.
+    pub fn set_genesis_state<'a>(
+        &mut self,
+        objects: impl IntoIterator<Item = &'a ObjectRef>,
+    ) -> SuiResult {
+        self.tables
+            .genesis_state
+            .insert(&LOCALS, &StateAccumulator::from_objects(objects))?;
+        Ok(())
+    }
+
     /// Subscribe to new checkpoints.
     pub fn subscribe_to_checkpoints(&self) -> broadcast::Receiver<CertifiedCheckpointSummary> {
         self.notify_new_checkpoint_tx.subscribe()
@@ -368,6 +409,11 @@ impl CheckpointStore {
         })
     }
 
+    /// Registers the database of the checkpoints in `db_metrics`, the metrics of its node.
+    pub fn register_db_metrics(&self, db_metrics: &DBMetrics) {
+        db_metrics.register_db("checkpoints", &self.tables.checkpoints.rocksdb);
+    }
+
     // Define handlers for request
 
     pub fn handle_proposal(&mut self, detail: bool) -> Result<CheckpointResponse, SuiError> {
@@ -433,6 +479,22 @@ impl CheckpointStore {
         })
     }
 
+    /// Returns the stored checkpoint which includes `digests`, if the transaction was
+    /// checkpointed and the checkpoint is stored.
+    pub fn handle_transaction_checkpoint(
+        &mut self,
+        digests: &ExecutionDigests,
+        detail: bool,
+    ) -> SuiResult<CheckpointResponse> {
+        match self.tables.transactions_to_checkpoint.get(digests)? {
+            Some(seq) => self.handle_authenticated_checkpoint(&Some(seq), detail),
+            None => Ok(CheckpointResponse::AuthenticatedCheckpoint {
+                checkpoint: None,
+                contents: None,
+            }),
+        }
+    }
+
     pub fn sign_new_checkpoint<'a>(
         &mut self,
         epoch: EpochId,
@@ -459,6 +521,11 @@ impl CheckpointStore {
             causally_ordered_transactions.into_iter(),
         );
 
+        // Derive the live object set committed to by this checkpoint from the previous one.
+        let mut state = self.get_prev_state_accumulator(sequence_number)?;
+        let (added, removed) = effects_store.get_live_object_changes(ordered_contents.iter())?;
+        state.apply_changes(&added, &removed);
+
         let summary = CheckpointSummary::new(
             epoch,
             sequence_number,
@@ -466,6 +533,7 @@ impl CheckpointStore {
             previous_digest,
             gas_cost_summary,
             next_epoch_committee,
+            state.digest(),
         );
 
         let checkpoint = AuthenticatedCheckpoint::Signed(
@@ -474,6 +542,31 @@ impl CheckpointStore {
         self.handle_internal_set_checkpoint(&checkpoint, &ordered_contents)
     }
 
+    /// Check that the state digest of a checkpoint learned from other validators commits to the
+    /// live object set derived from the previous checkpoint and the effects of its transactions,
+    /// as executed locally. The transactions of the checkpoint must have been executed.
+    pub fn verify_state_digest(
+        &self,
+        summary: &CheckpointSummary,
+        contents: &CheckpointContents,
+        effects_store: impl EffectsStore,
+    ) -> SuiResult {
+        let mut state = self.get_prev_state_accumulator(summary.sequence_number)?;
+        let (added, removed) = effects_store.get_live_object_changes(contents.iter())?;
+        state.apply_changes(&added, &removed);
+        fp_ensure!(
+            state.digest() == summary.state_digest,
+            SuiError::CheckpointingError {
+                error: format!(
+                    "The state digest of checkpoint {} does not match the objects written by its \
+                     transactions",
+                    summary.sequence_number
+                ),
+            }
+        );
+        Ok(())
+    }
+
     /// Call this function internally to update the latest checkpoint.
     /// Internally it is called with an unsigned checkpoint, and results
     /// in the checkpoint being signed, stored and the contents
@@ -861,10 +954,21 @@ impl CheckpointStore {
         contents: &CheckpointContents,
         committee: &Committee,
     ) -> SuiResult {
-        let seq = checkpoint.summary.sequence_number();
-        debug_assert!(self.tables.checkpoints.get(seq)?.is_none());
         // Check and process contents
         checkpoint.verify(committee, Some(contents))?;
+        self.process_verified_checkpoint_certificate(checkpoint, contents, committee)
+    }
+
+    /// Same as process_synced_checkpoint_certificate, for a checkpoint certificate and contents
+    /// the caller already verified, e.g. along with other checkpoints in a batch.
+    pub fn process_verified_checkpoint_certificate(
+        &mut self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+        committee: &Committee,
+    ) -> SuiResult {
+        let seq = checkpoint.summary.sequence_number();
+        debug_assert!(self.tables.checkpoints.get(seq)?.is_none());
 
         self.handle_internal_set_checkpoint(
             &AuthenticatedCheckpoint::Certified(checkpoint.clone()),
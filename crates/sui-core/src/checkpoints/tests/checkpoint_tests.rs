@@ -15,17 +15,23 @@ use crate::{
 };
 use rand::prelude::StdRng;
 use rand::SeedableRng;
-use std::{collections::HashSet, env, fs, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env, fs,
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 use sui_types::{
-    base_types::{AuthorityName, ObjectID},
+    base_types::{AuthorityName, ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
     batch::UpdateItem,
     crypto::{
         get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair, AuthoritySignature, KeypairTraits,
         SuiAuthoritySignature,
     },
-    messages::{CertifiedTransaction, ExecutionStatus},
+    messages::{CertifiedTransaction, ExecutionStatus, TransactionEffects},
     messages_checkpoint::CheckpointRequest,
-    object::Object,
+    object::{Object, Owner},
     utils::{make_committee_key, make_committee_key_num},
     waypoint::GlobalCheckpoint,
 };
@@ -2148,3 +2154,43 @@ async fn test_no_more_fragments() {
         .attempt_to_construct_checkpoint()
         .is_ok());
 }
+
+#[test]
+fn checkpoint_state_digest_is_verified() {
+    let (_committee, _keys, mut stores) = random_ckpoint_store();
+    let (_, cps) = &mut stores[0];
+
+    let mut effects = TransactionEffects::default();
+    let created = (
+        ObjectID::random(),
+        SequenceNumber::from(1),
+        ObjectDigest::random(),
+    );
+    effects
+        .created
+        .push((created, Owner::AddressOwner(SuiAddress::default())));
+    let digests = ExecutionDigests::new(effects.transaction_digest, effects.digest());
+    let effects_store = || {
+        TestEffectsStore(BTreeMap::from([(
+            effects.transaction_digest,
+            effects.clone(),
+        )]))
+    };
+
+    cps.handle_internal_batch(0, &[(0, digests)]).unwrap();
+    cps.sign_new_checkpoint(0, 0, [digests].iter(), effects_store(), None)
+        .unwrap();
+    let summary = match cps.latest_stored_checkpoint().unwrap() {
+        AuthenticatedCheckpoint::Signed(signed) => signed.summary,
+        _ => unreachable!(),
+    };
+    let contents =
+        CheckpointContents::new_with_causally_ordered_transactions([digests].into_iter());
+
+    cps.verify_state_digest(&summary, &contents, effects_store())
+        .unwrap();
+    // A node whose execution of the transactions did not write the same objects has forked.
+    assert!(cps
+        .verify_state_digest(&summary, &contents, TestEffectsStore::default())
+        .is_err());
+}
@@ -3,6 +3,7 @@
 
 use crate::checkpoints::CheckpointStore;
 use crate::checkpoints::ConsensusSender;
+use crate::consensus_monitor::ConsensusMonitor;
 use bytes::Bytes;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
@@ -216,6 +217,8 @@ pub struct ConsensusAdapter {
     timeout: Duration,
     /// Number of submitted transactions still inflight at this node.
     num_inflight_transactions: AtomicU64,
+    /// Tracks the load of consensus, for metrics and backpressure.
+    monitor: Arc<ConsensusMonitor>,
     /// A structure to register metrics
     opt_metrics: OptArcConsensusAdapterMetrics,
 }
@@ -227,6 +230,7 @@ impl ConsensusAdapter {
         committee: Committee,
         tx_consensus_listener: Sender<ConsensusListenerMessage>,
         timeout: Duration,
+        monitor: Arc<ConsensusMonitor>,
         opt_metrics: OptArcConsensusAdapterMetrics,
     ) -> Self {
         let consensus_client = TransactionsClient::new(
@@ -240,6 +244,7 @@ impl ConsensusAdapter {
             tx_consensus_listener,
             timeout,
             num_inflight_transactions,
+            monitor,
             opt_metrics,
         }
     }
@@ -248,6 +253,13 @@ impl ConsensusAdapter {
         self.num_inflight_transactions.load(Ordering::Relaxed)
     }
 
+    /// Returns `SuiError::ValidatorOverloaded` if consensus is too loaded to submit more user
+    /// certificates.
+    pub fn check_backpressure(&self) -> SuiResult {
+        self.monitor
+            .check_backpressure(self.num_inflight_transactions())
+    }
+
     /// Check if this authority should submit the transaction to consensus.
     fn should_submit(_certificate: &CertifiedTransaction) -> bool {
         // TODO [issue #1647]: Right now every authority submits the transaction to consensus.
@@ -304,6 +316,8 @@ impl ConsensusAdapter {
             let inflight = self
                 .num_inflight_transactions
                 .fetch_add(1, Ordering::SeqCst);
+            self.monitor.observe_queue_depth(inflight + 1);
+            self.monitor.observe_submitted(*tx_digest);
             self.opt_metrics.as_ref().map(|metrics| {
                 metrics.sequencing_certificate_attempt.inc();
                 metrics.sequencing_certificate_inflight.set(inflight as i64);
@@ -317,6 +331,9 @@ impl ConsensusAdapter {
                 self.opt_metrics.as_ref().map(|metrics| {
                     metrics.sequencing_certificate_success.inc();
                 });
+                if should_submit {
+                    self.monitor.observe_commit_lag(now.elapsed());
+                }
 
                 Ok(())
             }
@@ -333,6 +350,11 @@ impl ConsensusAdapter {
                 self.opt_metrics.as_ref().map(|metrics| {
                     metrics.sequencing_certificate_timeouts.inc();
                 });
+                // The certificate was not committed in time, so the commit lag is at least the
+                // timeout.
+                if should_submit {
+                    self.monitor.observe_commit_lag(now.elapsed());
+                }
 
                 // We drop the waiter which will signal to the conensus listener task to clean up
                 // the channels.
@@ -344,6 +366,8 @@ impl ConsensusAdapter {
             let inflight = self
                 .num_inflight_transactions
                 .fetch_sub(1, Ordering::SeqCst);
+            self.monitor.observe_queue_depth(inflight - 1);
+            self.monitor.observe_settled(tx_digest);
             let elapsed_secs = now.elapsed().as_secs_f64();
             // Store the latest latency
             self.opt_metrics.as_ref().map(|metrics| {
@@ -4,6 +4,7 @@
 use crate::authority::authority_store_tables::ExecutionIndicesWithHash;
 use crate::authority::AuthorityState;
 use crate::consensus_adapter::ConsensusListenerMessage;
+use crate::consensus_monitor::ConsensusMonitor;
 use async_trait::async_trait;
 use narwhal_executor::{ExecutionIndices, ExecutionState};
 use std::collections::hash_map::DefaultHasher;
@@ -16,15 +17,21 @@ use tracing::{debug, instrument, warn};
 pub struct ConsensusHandler {
     state: Arc<AuthorityState>,
     sender: mpsc::Sender<ConsensusListenerMessage>,
+    monitor: Arc<ConsensusMonitor>,
     hash: Mutex<u64>,
 }
 
 impl ConsensusHandler {
-    pub fn new(state: Arc<AuthorityState>, sender: mpsc::Sender<ConsensusListenerMessage>) -> Self {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        sender: mpsc::Sender<ConsensusListenerMessage>,
+        monitor: Arc<ConsensusMonitor>,
+    ) -> Self {
         let hash = Mutex::new(0);
         Self {
             state,
             sender,
+            monitor,
             hash,
         }
     }
@@ -62,6 +69,8 @@ impl ExecutionState for ConsensusHandler {
         serialized_transaction: Vec<u8>,
     ) {
         let consensus_index = self.update_hash(consensus_index, &serialized_transaction);
+        self.monitor
+            .observe_round(consensus_output.certificate.round());
         let transaction =
             match bincode::deserialize::<ConsensusTransaction>(&serialized_transaction) {
                 Ok(transaction) => transaction,
@@ -0,0 +1,285 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*
+The consensus monitor bridges the health of Narwhal, as seen from this validator, to the node:
+it exports the depth of the queue of certificates submitted to consensus, the latency of
+consensus rounds and the lag between submitting a certificate and its commit to the node's
+Prometheus registry, and derives from them whether consensus is overloaded. Shared object
+certificates are rejected while it is, with an error asking clients to retry later, instead of
+queuing ever more certificates consensus cannot commit in time.
+*/
+use narwhal_types::Round;
+use parking_lot::Mutex;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounterVec, IntGauge, Registry,
+};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sui_types::base_types::TransactionDigest;
+use sui_types::error::{SuiError, SuiResult};
+
+#[cfg(test)]
+#[path = "unit_tests/consensus_monitor_tests.rs"]
+mod consensus_monitor_tests;
+
+// Assuming 200 consensus tps * 5 sec consensus latency = 1000 inflight consensus txns.
+// Leaving a bit more headroom to cap the max inflight consensus txns to 1000*2 = 2000.
+pub const MAX_PENDING_CONSENSUS_TRANSACTIONS: u64 = 2000;
+
+/// Submitted certificates are rejected while the estimated commit lag exceeds this, as clients
+/// would likely time out waiting for them.
+pub const MAX_CONSENSUS_COMMIT_LAG: Duration = Duration::from_secs(30);
+
+// How long clients are asked to wait when consensus is overloaded, unless the commit lag is
+// longer: about the time for consensus to commit the inflight transactions at the rate above.
+const CONSENSUS_BACKPRESSURE_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+const ROUND_LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.05, 0.1, 0.25, 0.5, 0.75, 1., 1.5, 2., 3., 5., 7.5, 10., 20., 30., 60.,
+];
+
+const COMMIT_LAG_SEC_BUCKETS: &[f64] = &[
+    0.1, 0.25, 0.5, 1., 2.5, 5., 7.5, 10., 15., 20., 30., 60., 90., 120., 300.,
+];
+
+struct LastRound {
+    round: Round,
+    committed_at: Instant,
+}
+
+struct CommitLag {
+    // Moving average of the commit lag of the certificates submitted by this node.
+    estimate: Duration,
+    observed_at: Instant,
+}
+
+struct PendingCertificate {
+    submitted_at: Instant,
+    // The same certificate may be submitted concurrently, e.g. when clients retry.
+    submissions: usize,
+}
+
+impl CommitLag {
+    fn is_stale(&self) -> bool {
+        self.observed_at.elapsed() >= self.estimate
+    }
+}
+
+pub struct ConsensusMonitor {
+    max_pending_transactions: u64,
+    max_commit_lag: Duration,
+    last_round: Mutex<Option<LastRound>>,
+    commit_lag: Mutex<Option<CommitLag>>,
+    pending_certificates: Mutex<HashMap<TransactionDigest, PendingCertificate>>,
+
+    submission_queue_depth: IntGauge,
+    committed_round: IntGauge,
+    round_latency: Histogram,
+    commit_lag_latency: Histogram,
+    commit_lag_estimate: IntGauge,
+    backpressure_rejections: IntCounterVec,
+}
+
+impl ConsensusMonitor {
+    pub fn new(registry: &Registry) -> Self {
+        Self::new_with_limits(
+            MAX_PENDING_CONSENSUS_TRANSACTIONS,
+            MAX_CONSENSUS_COMMIT_LAG,
+            registry,
+        )
+    }
+
+    pub fn new_with_limits(
+        max_pending_transactions: u64,
+        max_commit_lag: Duration,
+        registry: &Registry,
+    ) -> Self {
+        Self {
+            max_pending_transactions,
+            max_commit_lag,
+            last_round: Mutex::new(None),
+            commit_lag: Mutex::new(None),
+            pending_certificates: Mutex::new(HashMap::new()),
+            submission_queue_depth: register_int_gauge_with_registry!(
+                "consensus_submission_queue_depth",
+                "Number of certificates submitted to consensus by this node and not committed yet",
+                registry,
+            )
+            .unwrap(),
+            committed_round: register_int_gauge_with_registry!(
+                "consensus_committed_round",
+                "Round of the latest consensus certificate committed to this node",
+                registry,
+            )
+            .unwrap(),
+            round_latency: register_histogram_with_registry!(
+                "consensus_round_latency",
+                "Time between the commits of consecutive consensus rounds, per round",
+                ROUND_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            commit_lag_latency: register_histogram_with_registry!(
+                "consensus_commit_lag",
+                "Time between submitting a certificate to consensus and its commit",
+                COMMIT_LAG_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            commit_lag_estimate: register_int_gauge_with_registry!(
+                "consensus_commit_lag_estimate_ms",
+                "Moving average of the commit lag of submitted certificates, used for backpressure",
+                registry,
+            )
+            .unwrap(),
+            backpressure_rejections: register_int_counter_vec_with_registry!(
+                "consensus_backpressure_rejections",
+                "Number of shared object certificates rejected as consensus is overloaded, by cause",
+                &["cause"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_test() -> Self {
+        Self::new(&Registry::new())
+    }
+
+    pub fn observe_queue_depth(&self, depth: u64) {
+        self.submission_queue_depth.set(depth as i64);
+    }
+
+    /// Number of certificates submitted to consensus by this node and not committed yet.
+    pub fn queue_depth(&self) -> u64 {
+        self.submission_queue_depth.get() as u64
+    }
+
+    /// Records that the certificate `digest` was submitted to consensus.
+    pub fn observe_submitted(&self, digest: TransactionDigest) {
+        self.pending_certificates
+            .lock()
+            .entry(digest)
+            .or_insert_with(|| PendingCertificate {
+                submitted_at: Instant::now(),
+                submissions: 0,
+            })
+            .submissions += 1;
+    }
+
+    /// Records that a submission of the certificate `digest` was committed, failed or timed out.
+    pub fn observe_settled(&self, digest: &TransactionDigest) {
+        let mut pending_certificates = self.pending_certificates.lock();
+        if let Some(pending) = pending_certificates.get_mut(digest) {
+            pending.submissions -= 1;
+            if pending.submissions == 0 {
+                pending_certificates.remove(digest);
+            }
+        }
+    }
+
+    /// The certificates submitted to consensus by this node and not committed yet, with the time
+    /// since they were first submitted, oldest first.
+    pub fn pending_certificates(&self) -> Vec<(TransactionDigest, Duration)> {
+        let mut pending: Vec<_> = self
+            .pending_certificates
+            .lock()
+            .iter()
+            .map(|(digest, pending)| (*digest, pending.submitted_at.elapsed()))
+            .collect();
+        pending.sort_by(|(_, a), (_, b)| b.cmp(a));
+        pending
+    }
+
+    /// The round of the latest consensus certificate committed to this node, if any.
+    pub fn last_committed_round(&self) -> Option<Round> {
+        self.last_round.lock().as_ref().map(|last| last.round)
+    }
+
+    /// Records the commit of a consensus certificate of `round`. Rounds are committed in order,
+    /// possibly several at once, so the time since the previous round is shared among the
+    /// rounds committed since.
+    pub fn observe_round(&self, round: Round) {
+        let mut last_round = self.last_round.lock();
+        let now = Instant::now();
+        match &*last_round {
+            Some(last) if round <= last.round => return,
+            Some(last) => {
+                let elapsed = now.duration_since(last.committed_at).as_secs_f64();
+                self.round_latency
+                    .observe(elapsed / (round - last.round) as f64);
+            }
+            None => (),
+        }
+        *last_round = Some(LastRound {
+            round,
+            committed_at: now,
+        });
+        self.committed_round.set(round as i64);
+    }
+
+    /// Records that a certificate submitted `lag` ago was committed, or that it was not committed
+    /// after waiting for `lag`.
+    pub fn observe_commit_lag(&self, lag: Duration) {
+        self.commit_lag_latency.observe(lag.as_secs_f64());
+        let mut commit_lag = self.commit_lag.lock();
+        let estimate = match &*commit_lag {
+            Some(previous) if !previous.is_stale() => (previous.estimate * 7 + lag) / 8,
+            _ => lag,
+        };
+        *commit_lag = Some(CommitLag {
+            estimate,
+            observed_at: Instant::now(),
+        });
+        self.commit_lag_estimate.set(estimate.as_millis() as i64);
+    }
+
+    /// The estimated time for consensus to commit a certificate submitted now. Once no
+    /// certificate was committed for as long as the last estimate, e.g. as they were all
+    /// rejected, the estimate is stale and submitting certificates again measures it anew.
+    pub fn commit_lag_estimate(&self) -> Duration {
+        match &*self.commit_lag.lock() {
+            Some(commit_lag) if !commit_lag.is_stale() => commit_lag.estimate,
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Whether a new certificate can be submitted to consensus while `queue_depth` certificates
+    /// are inflight. Returns `SuiError::ValidatorOverloaded` if consensus is overloaded.
+    pub fn check_backpressure(&self, queue_depth: u64) -> SuiResult {
+        let commit_lag = self.commit_lag_estimate();
+        let (cause, reason) = if queue_depth > self.max_pending_transactions {
+            (
+                "queue_depth",
+                format!(
+                    "Reached {} concurrent consensus transactions",
+                    self.max_pending_transactions
+                ),
+            )
+        } else if commit_lag > self.max_commit_lag {
+            (
+                "commit_lag",
+                format!(
+                    "Consensus commits transactions after {}ms, the maximum is {}ms",
+                    commit_lag.as_millis(),
+                    self.max_commit_lag.as_millis()
+                ),
+            )
+        } else {
+            return Ok(());
+        };
+        self.backpressure_rejections
+            .with_label_values(&[cause])
+            .inc();
+        Err(SuiError::ValidatorOverloaded {
+            reason,
+            retry_after_ms: Some(
+                commit_lag
+                    .max(CONSENSUS_BACKPRESSURE_RETRY_AFTER)
+                    .as_millis() as u64,
+            ),
+        })
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*
+The deny list lets node operators stop transactions that call specific packages or Move functions,
+e.g. to contain an exploit until a fix is deployed. Validators refuse to sign such transactions,
+and to execute certificates for them that they have not executed yet. Fullnodes refuse to submit
+them. Certificates synced from the network are always executed, so that a node never falls
+behind the network because of its deny list. Only the functions a transaction calls directly are
+checked, not the functions they call in turn.
+*/
+use parking_lot::RwLock;
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_with_registry, IntCounterVec,
+    IntGauge, Registry,
+};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use sui_types::base_types::ObjectID;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{SingleTransactionKind, TransactionData};
+
+/// Where a denied transaction was rejected, as the `stage` label of the metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenyStage {
+    /// A validator was asked to sign the transaction.
+    Signing,
+    /// A client asked a validator to execute a certificate for the transaction.
+    Execution,
+    /// A fullnode was asked to submit the transaction.
+    Submission,
+}
+
+impl DenyStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signing => "signing",
+            Self::Execution => "execution",
+            Self::Submission => "submission",
+        }
+    }
+}
+
+/// A package, or a function of a package, that transactions may not call. Written as
+/// `<package>` or `<package>::<module>::<function>`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DenyListEntry {
+    Package(ObjectID),
+    Function {
+        package: ObjectID,
+        module: String,
+        function: String,
+    },
+}
+
+impl FromStr for DenyListEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.trim().split("::").collect();
+        match parts[..] {
+            [package] => Ok(Self::Package(ObjectID::from_hex_literal(package)?)),
+            [package, module, function] if !module.is_empty() && !function.is_empty() => {
+                Ok(Self::Function {
+                    package: ObjectID::from_hex_literal(package)?,
+                    module: module.to_string(),
+                    function: function.to_string(),
+                })
+            }
+            _ => Err(anyhow::anyhow!(
+                "expected <package> or <package>::<module>::<function>, got {s}"
+            )),
+        }
+    }
+}
+
+impl Display for DenyListEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Package(package) => write!(f, "{package}"),
+            Self::Function {
+                package,
+                module,
+                function,
+            } => write!(f, "{package}::{module}::{function}"),
+        }
+    }
+}
+
+pub struct TransactionDenyList {
+    entries: RwLock<BTreeSet<DenyListEntry>>,
+    metrics: DenyListMetrics,
+}
+
+impl TransactionDenyList {
+    /// An empty deny list, allowing all transactions.
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            entries: RwLock::new(BTreeSet::new()),
+            metrics: DenyListMetrics::new(registry),
+        }
+    }
+
+    /// Deny `entry`. Returns false if it was already denied.
+    pub fn deny(&self, entry: DenyListEntry) -> bool {
+        let mut entries = self.entries.write();
+        let inserted = entries.insert(entry);
+        self.metrics.entries.set(entries.len() as i64);
+        inserted
+    }
+
+    /// Allow `entry` again. Returns false if it was not denied.
+    pub fn allow(&self, entry: &DenyListEntry) -> bool {
+        let mut entries = self.entries.write();
+        let removed = entries.remove(entry);
+        self.metrics.entries.set(entries.len() as i64);
+        removed
+    }
+
+    pub fn entries(&self) -> Vec<DenyListEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    /// Fails with `SuiError::DeniedPackage` or `SuiError::DeniedFunction` if `data` calls a
+    /// denied package or function.
+    pub fn check(&self, data: &TransactionData, stage: DenyStage) -> SuiResult {
+        let entries = self.entries.read();
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for kind in data.kind.single_transactions() {
+            let call = match kind {
+                SingleTransactionKind::Call(call) => call,
+                _ => continue,
+            };
+            let package = call.package.0;
+            let error = if entries.contains(&DenyListEntry::Package(package)) {
+                SuiError::DeniedPackage { package }
+            } else {
+                let function = DenyListEntry::Function {
+                    package,
+                    module: call.module.to_string(),
+                    function: call.function.to_string(),
+                };
+                if !entries.contains(&function) {
+                    continue;
+                }
+                SuiError::DeniedFunction {
+                    function: function.to_string(),
+                }
+            };
+            self.metrics
+                .denied_transactions
+                .with_label_values(&[stage.as_str()])
+                .inc();
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+struct DenyListMetrics {
+    entries: IntGauge,
+    denied_transactions: IntCounterVec,
+}
+
+impl DenyListMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            entries: register_int_gauge_with_registry!(
+                "deny_list_entries",
+                "Number of packages and functions in the transaction deny list",
+                registry,
+            )
+            .unwrap(),
+            denied_transactions: register_int_counter_vec_with_registry!(
+                "deny_list_denied_transactions",
+                "Number of transactions rejected for calling a denied package or function",
+                &["stage"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber, SuiAddress};
+
+    fn call(package: ObjectID, module: &str, function: &str) -> TransactionData {
+        TransactionData::new_move_call(
+            SuiAddress::random_for_testing_only(),
+            (package, SequenceNumber::from(1), ObjectDigest::random()),
+            module.parse().unwrap(),
+            function.parse().unwrap(),
+            vec![],
+            (
+                ObjectID::random(),
+                SequenceNumber::new(),
+                ObjectDigest::random(),
+            ),
+            vec![],
+            1000,
+        )
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let package = ObjectID::random();
+        let entry: DenyListEntry = package.to_string().parse().unwrap();
+        assert_eq!(entry, DenyListEntry::Package(package));
+
+        let entry: DenyListEntry = format!("{package}::coin::join").parse().unwrap();
+        assert_eq!(entry.to_string(), format!("{package}::coin::join"));
+        assert_eq!(entry.to_string().parse::<DenyListEntry>().unwrap(), entry);
+
+        assert!(format!("{package}::coin").parse::<DenyListEntry>().is_err());
+        assert!(format!("{package}::::join")
+            .parse::<DenyListEntry>()
+            .is_err());
+        assert!("coin::join".parse::<DenyListEntry>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let deny_list = TransactionDenyList::new(&Registry::new());
+        let package = ObjectID::random();
+        let other = ObjectID::random();
+        assert!(deny_list
+            .check(&call(package, "coin", "join"), DenyStage::Signing)
+            .is_ok());
+
+        let function: DenyListEntry = format!("{package}::coin::join").parse().unwrap();
+        assert!(deny_list.deny(function.clone()));
+        assert!(!deny_list.deny(function.clone()));
+        assert!(matches!(
+            deny_list.check(&call(package, "coin", "join"), DenyStage::Signing),
+            Err(SuiError::DeniedFunction { .. })
+        ));
+        assert!(deny_list
+            .check(&call(package, "coin", "split"), DenyStage::Signing)
+            .is_ok());
+
+        assert!(deny_list.deny(DenyListEntry::Package(other)));
+        assert_eq!(
+            deny_list.check(&call(other, "coin", "split"), DenyStage::Execution),
+            Err(SuiError::DeniedPackage { package: other })
+        );
+        assert_eq!(
+            deny_list
+                .metrics
+                .denied_transactions
+                .with_label_values(&["signing"])
+                .get(),
+            1
+        );
+        assert_eq!(deny_list.metrics.entries.get(), 2);
+
+        assert!(deny_list.allow(&function));
+        assert!(!deny_list.allow(&function));
+        assert!(deny_list
+            .check(&call(package, "coin", "join"), DenyStage::Signing)
+            .is_ok());
+        assert_eq!(deny_list.entries(), vec![DenyListEntry::Package(other)]);
+    }
+}
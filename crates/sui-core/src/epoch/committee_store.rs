@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rocksdb::Options;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use sui_storage::default_db_options;
-use sui_types::base_types::ObjectID;
-use sui_types::committee::{Committee, EpochId};
+use sui_types::base_types::{AuthorityName, ObjectID};
+use sui_types::committee::{Committee, CommitteeWithNetAddresses, EpochId};
 use sui_types::error::{SuiError, SuiResult};
 use typed_store::rocks::DBMap;
 use typed_store::traits::TypedStoreDebug;
@@ -23,6 +24,13 @@ pub struct CommitteeStore {
     /// the committee for the next epoch.
     #[default_options_override_fn = "committee_table_default_config"]
     pub(crate) committee_map: DBMap<EpochId, Committee>,
+    /// Map from each epoch ID to the network addresses of the validators of its committee, for
+    /// the epochs whose addresses are known to this node.
+    #[default_options_override_fn = "committee_table_default_config"]
+    net_addresses: DBMap<EpochId, BTreeMap<AuthorityName, Vec<u8>>>,
+    /// Map from each epoch ID to the backup network addresses of the validators of its committee.
+    #[default_options_override_fn = "committee_table_default_config"]
+    backup_net_addresses: DBMap<EpochId, BTreeMap<AuthorityName, Vec<Vec<u8>>>>,
 }
 
 // These functions are used to initialize the DB tables
@@ -64,10 +72,64 @@ impl CommitteeStore {
         Ok(())
     }
 
+    /// Like `insert_new_committee`, also recording the network addresses of the validators.
+    pub fn insert_new_committee_with_net_addresses(
+        &self,
+        new_committee: &CommitteeWithNetAddresses,
+    ) -> SuiResult {
+        self.insert_new_committee(&new_committee.committee)?;
+        self.insert_net_addresses(new_committee)
+    }
+
+    /// Record the network addresses, backups included, of the validators of a known committee.
+    pub fn insert_net_addresses(&self, committee: &CommitteeWithNetAddresses) -> SuiResult {
+        let epoch = committee.committee.epoch;
+        fp_ensure!(
+            self.committee_map.contains_key(&epoch)?,
+            SuiError::MissingCommitteeAtEpoch(epoch)
+        );
+        self.net_addresses
+            .insert(&epoch, &committee.net_addresses)?;
+        self.backup_net_addresses
+            .insert(&epoch, &committee.backup_net_addresses)?;
+        Ok(())
+    }
+
     pub fn get_committee(&self, epoch_id: &EpochId) -> SuiResult<Option<Committee>> {
         Ok(self.committee_map.get(epoch_id)?)
     }
 
+    /// The committee of `epoch_id` with the network addresses of its validators. The addresses
+    /// are empty if they were not recorded, e.g. for epochs this node caught up on.
+    pub fn get_committee_with_net_addresses(
+        &self,
+        epoch_id: &EpochId,
+    ) -> SuiResult<Option<CommitteeWithNetAddresses>> {
+        let committee = match self.committee_map.get(epoch_id)? {
+            Some(committee) => committee,
+            None => return Ok(None),
+        };
+        Ok(Some(CommitteeWithNetAddresses {
+            committee,
+            net_addresses: self.net_addresses.get(epoch_id)?.unwrap_or_default(),
+            backup_net_addresses: self.backup_net_addresses.get(epoch_id)?.unwrap_or_default(),
+        }))
+    }
+
+    /// All committees known to this node, from genesis to the latest epoch.
+    pub fn get_all_committees_with_net_addresses(&self) -> Vec<CommitteeWithNetAddresses> {
+        let mut net_addresses = self.net_addresses.iter().collect::<BTreeMap<_, _>>();
+        let mut backup_net_addresses = self.backup_net_addresses.iter().collect::<BTreeMap<_, _>>();
+        self.committee_map
+            .iter()
+            .map(|(epoch, committee)| CommitteeWithNetAddresses {
+                committee,
+                net_addresses: net_addresses.remove(&epoch).unwrap_or_default(),
+                backup_net_addresses: backup_net_addresses.remove(&epoch).unwrap_or_default(),
+            })
+            .collect()
+    }
+
     pub fn get_latest_committee(&self) -> Committee {
         self.committee_map
             .iter()
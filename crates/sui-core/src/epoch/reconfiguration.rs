@@ -3,15 +3,15 @@
 
 use crate::authority_active::ActiveAuthority;
 use crate::authority_aggregator::AuthorityAggregator;
-use crate::authority_client::{AuthorityAPI, NetworkAuthorityClientMetrics};
+use crate::authority_client::{parse_net_addresses, AuthorityAPI, NetworkAuthorityClientMetrics};
 use async_trait::async_trait;
 use fastcrypto::traits::ToFromBytes;
 use itertools::MultiUnzip;
-use multiaddr::Multiaddr;
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
-use sui_network::{default_mysten_network_config, tonic};
+use sui_network::channel_pool::ChannelPool;
+use sui_network::default_mysten_network_config;
 use sui_types::base_types::AuthorityName;
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::error::SuiResult;
@@ -24,10 +24,7 @@ use typed_store::Map;
 pub trait Reconfigurable {
     fn needs_network_recreation() -> bool;
 
-    fn recreate(
-        channel: tonic::transport::Channel,
-        metrics: Arc<NetworkAuthorityClientMetrics>,
-    ) -> Self;
+    fn recreate(channels: ChannelPool, metrics: Arc<NetworkAuthorityClientMetrics>) -> Self;
 }
 
 const WAIT_BETWEEN_QUORUM_QUERY_RETRY: Duration = Duration::from_millis(300);
@@ -78,9 +75,7 @@ where
                 checkpoints.filter_already_checkpointed_transactions(pending_tx.iter())?;
             for tx_digest in extra_tx {
                 warn!(?epoch, tx_digest=?tx_digest.transaction, "Reverting local transaction effects");
-                self.state
-                    .database
-                    .revert_state_update(&tx_digest.transaction)?;
+                self.state.revert_state_update(&tx_digest.transaction)?;
             }
 
             // Delete any extra certificates now unprocessed.
@@ -115,13 +110,24 @@ where
 
         // Create new AuthorityEpochTables for epoch-specific data.
         self.state.database.reopen_epoch_db(next_epoch);
+        // The lock table is scanned in the background, as it only holds stale locks back.
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            if let Err(err) = state.reset_transaction_locks_for_epoch(next_epoch).await {
+                error!(
+                    ?next_epoch,
+                    "Failed to reset the transaction locks: {:?}", err
+                );
+            }
+        });
 
-        let new_committee = sui_system_state.get_next_epoch_committee();
+        let new_committee = sui_system_state.get_next_epoch_committee_with_net_addresses();
         debug!(
             ?epoch,
-            "New committee for the next epoch: {}", new_committee
+            "New committee for the next epoch: {}", new_committee.committee
         );
         self.state.update_committee(new_committee.clone())?;
+        let new_committee = new_committee.committee;
 
         // Reconnect the network if we have an type of AuthorityClient that has a network.
         let new_clients = if A::needs_network_recreation() {
@@ -218,22 +224,10 @@ where
                 }
                 Ok(result) => result,
             };
-            // TODO: We only recreate network connection if this is a new validator.
-            // This is because creating a new network connection on the same address doesn't
-            // work. We may want to look into this and see why it doesn't work.
-            if let Some(existing_client) = cur_clients.get(&public_key_bytes) {
-                // TODO: Since we rely purely on the public key to decide whether to recreate
-                // the network, it means that validators won't be able to modify their network
-                // information without also using a new public key.
-                new_clients.insert(public_key_bytes, existing_client.authority_client().clone());
-                debug!(
-                    "Adding unchanged client to the new network: {}",
-                    public_key_bytes
-                );
-                continue;
-            }
-
-            let address = match Multiaddr::try_from(validator.net_address) {
+            let addresses = match parse_net_addresses(
+                &validator.net_address,
+                &validator.backup_net_addresses,
+            ) {
                 Err(err) => {
                     error!("Error parsing validator network address. Skip this validator in the committee: {:?}", err);
                     continue;
@@ -241,18 +235,46 @@ where
                 Ok(result) => result,
             };
 
-            let channel = match net_config.connect_lazy(&address) {
+            // We only recreate the network connection of a validator that is new, or whose
+            // network address changed on chain.
+            if let Some(existing_client) = cur_clients.get(&public_key_bytes) {
+                if !self
+                    .endpoints
+                    .address_changed(&public_key_bytes, &addresses)
+                {
+                    new_clients
+                        .insert(public_key_bytes, existing_client.authority_client().clone());
+                    debug!(
+                        "Adding unchanged client to the new network: {}",
+                        public_key_bytes
+                    );
+                    continue;
+                }
+                info!(
+                    authority = ?public_key_bytes.concise(),
+                    ?addresses,
+                    "Validator network address changed"
+                );
+            }
+
+            let channels = match ChannelPool::connect_lazy_with_failover(
+                &net_config,
+                &self.channel_pool_config,
+                &addresses,
+            ) {
                 Err(err) => {
-                    error!("Error connecting to client {} with address {:?}. Skip this validator in the committee: {:?}", public_key_bytes, address, err);
+                    error!("Error connecting to client {} with addresses {:?}. Skip this validator in the committee: {:?}", public_key_bytes, addresses, err);
                     continue;
                 }
                 Ok(result) => result,
             };
-            let client: A = A::recreate(channel, self.network_metrics.clone());
+            let client: A = A::recreate(channels, self.network_metrics.clone());
             debug!(
                 "New network client created for {} at {:?}",
-                public_key_bytes, address
+                public_key_bytes, addresses
             );
+            self.endpoints
+                .insert_unresolved(public_key_bytes, addresses);
             new_clients.insert(public_key_bytes, client);
         }
         Ok(new_clients)
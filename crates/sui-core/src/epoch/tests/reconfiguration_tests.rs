@@ -111,7 +111,9 @@ async fn test_start_epoch_change() {
             .handle_transaction(transaction.clone())
             .await
             .unwrap_err(),
-        SuiError::ValidatorHaltedAtEpochEnd
+        SuiError::ValidatorHalted {
+            until_epoch: state.epoch() + 1
+        }
     );
 
     // Test that when validator is halted, we cannot send any certificate.
@@ -128,7 +130,9 @@ async fn test_start_epoch_change() {
     let certificate = cert.unwrap();
     assert_eq!(
         state.handle_certificate(&certificate).await.unwrap_err(),
-        SuiError::ValidatorHaltedAtEpochEnd
+        SuiError::ValidatorHalted {
+            until_epoch: state.epoch() + 1
+        }
     );
 
     // Test that for certificates that have finished execution and is about to write effects,
@@ -0,0 +1,197 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages::TransactionInfoResponse;
+
+/// Default number of responses remembered by an `ExecutedCertificateCache`.
+pub const DEFAULT_EXECUTED_CERT_CACHE_SIZE: usize = 10_000;
+
+/// Remembers the responses to the certificates executed most recently, by transaction digest.
+/// Clients retry `handle_certificate` until they hear back from a quorum, so validators often
+/// receive certificates they have just executed, whose response would otherwise be read back
+/// from several tables of the store.
+///
+/// The responses hold transactions signed in the current epoch, so the cache must be cleared
+/// when the epoch changes.
+pub struct ExecutedCertificateCache {
+    capacity: AtomicUsize,
+    inner: Mutex<ExecutedCertificateCacheInner>,
+    metrics: ExecutedCertificateCacheMetrics,
+}
+
+#[derive(Default)]
+struct ExecutedCertificateCacheInner {
+    responses: HashMap<TransactionDigest, TransactionInfoResponse>,
+    // Keys in insertion order, oldest first.
+    order: VecDeque<TransactionDigest>,
+}
+
+impl ExecutedCertificateCacheInner {
+    fn evict(&mut self, capacity: usize) {
+        while self.responses.len() > capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.responses.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl ExecutedCertificateCache {
+    pub fn new(capacity: usize, registry: &Registry) -> Self {
+        Self {
+            capacity: AtomicUsize::new(capacity),
+            inner: Mutex::new(ExecutedCertificateCacheInner::default()),
+            metrics: ExecutedCertificateCacheMetrics::new(registry),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Changes the number of responses remembered, evicting the oldest ones if there are more.
+    /// A capacity of 0 disables the cache.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut inner = self.inner.lock();
+        inner.evict(capacity);
+        self.metrics.size.set(inner.responses.len() as i64);
+    }
+
+    pub fn get(&self, digest: &TransactionDigest) -> Option<TransactionInfoResponse> {
+        if self.capacity() == 0 {
+            return None;
+        }
+        let response = self.inner.lock().responses.get(digest).cloned();
+        match response {
+            Some(_) => self.metrics.hits.inc(),
+            None => self.metrics.misses.inc(),
+        }
+        response
+    }
+
+    /// Remembers `response`, the response to the executed certificate of `digest`.
+    pub fn insert(&self, digest: TransactionDigest, response: TransactionInfoResponse) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        if inner.responses.insert(digest, response).is_none() {
+            inner.order.push_back(digest);
+        }
+        inner.evict(capacity);
+        self.metrics.size.set(inner.responses.len() as i64);
+    }
+
+    /// Forgets the response to the certificate of `digest`, e.g. because its effects were
+    /// reverted.
+    pub fn remove(&self, digest: &TransactionDigest) {
+        let mut inner = self.inner.lock();
+        if inner.responses.remove(digest).is_some() {
+            inner.order.retain(|d| d != digest);
+            self.metrics.size.set(inner.responses.len() as i64);
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.responses.clear();
+        inner.order.clear();
+        self.metrics.size.set(0);
+    }
+}
+
+struct ExecutedCertificateCacheMetrics {
+    hits: IntCounter,
+    misses: IntCounter,
+    size: IntGauge,
+}
+
+impl ExecutedCertificateCacheMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            hits: register_int_counter_with_registry!(
+                "executed_cert_cache_hits",
+                "Total certificates answered from the cache of recently executed certificates",
+                registry,
+            )
+            .unwrap(),
+            misses: register_int_counter_with_registry!(
+                "executed_cert_cache_misses",
+                "Total certificates not found in the cache of recently executed certificates",
+                registry,
+            )
+            .unwrap(),
+            size: register_int_gauge_with_registry!(
+                "executed_cert_cache_size",
+                "Number of responses in the cache of recently executed certificates",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> TransactionInfoResponse {
+        TransactionInfoResponse {
+            signed_transaction: None,
+            certified_transaction: None,
+            signed_effects: None,
+        }
+    }
+
+    #[test]
+    fn test_eviction() {
+        let cache = ExecutedCertificateCache::new(2, &Registry::new());
+        let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+        assert!(cache.get(&digests[0]).is_none());
+
+        for digest in &digests {
+            cache.insert(*digest, response());
+        }
+        assert!(
+            cache.get(&digests[0]).is_none(),
+            "oldest response is evicted"
+        );
+        assert!(cache.get(&digests[1]).is_some());
+        assert!(cache.get(&digests[2]).is_some());
+        assert_eq!(cache.metrics.hits.get(), 2);
+        assert_eq!(cache.metrics.misses.get(), 2);
+        assert_eq!(cache.metrics.size.get(), 2);
+
+        cache.set_capacity(1);
+        assert!(cache.get(&digests[1]).is_none());
+        assert!(cache.get(&digests[2]).is_some());
+
+        cache.set_capacity(0);
+        assert!(cache.get(&digests[2]).is_none());
+        assert_eq!(cache.metrics.size.get(), 0);
+        cache.insert(digests[0], response());
+        assert_eq!(cache.metrics.size.get(), 0);
+
+        cache.set_capacity(2);
+        cache.insert(digests[0], response());
+        cache.insert(digests[1], response());
+        cache.remove(&digests[0]);
+        assert!(cache.get(&digests[0]).is_none());
+        assert_eq!(cache.metrics.size.get(), 1);
+        cache.clear();
+        assert!(cache.get(&digests[0]).is_none());
+    }
+}
@@ -0,0 +1,364 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Audit of the determinism of execution, an early warning of non-determinism bugs, e.g. between
+//! the versions of the node run by the network.
+//!
+//! A node configured with an audit reports the certificates it syncs with their certified
+//! effects whose execution, to commit them, did not reproduce these effects. It also executes
+//! again a sample of them, once committed, in a Move VM created for each audit, so that the
+//! loader cache of the node plays no part in it, and nothing it produces is committed.
+//! Divergences are logged, counted in the metrics, and their context is written to a file.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use serde::Serialize;
+use sui_adapter::adapter;
+use sui_config::node::ExecutionAuditConfig;
+use sui_network::version::PROTOCOL_VERSION;
+use sui_types::base_types::{encode_bytes_hex, EpochId};
+use sui_types::error::SuiResult;
+use sui_types::execution_limits::ExecutionLimits;
+use sui_types::gas::SuiGasStatus;
+use sui_types::messages::{CertifiedTransaction, InputObjects, TransactionEffects};
+use sui_types::object::Object;
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{error, warn};
+
+use crate::authority::{AuthorityState, AuthorityStore};
+use crate::{execution_engine, transaction_input_checker};
+
+/// The most audits executing at once. Certificates sampled beyond it are not audited.
+const MAX_CONCURRENT_AUDITS: usize = 2;
+
+/// How the effects compared with the certified ones were produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Execution {
+    /// By the node, to commit the certificate.
+    Synced,
+    /// By the audit, once the certificate was committed.
+    Audit,
+}
+
+impl Execution {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Execution::Synced => "synced",
+            Execution::Audit => "audit",
+        }
+    }
+}
+
+pub struct ExecutionAuditMetrics {
+    pub(crate) compared_effects: IntCounterVec,
+}
+
+impl ExecutionAuditMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            compared_effects: register_int_counter_vec_with_registry!(
+                "execution_audit_transactions",
+                "Number of synced certificates compared with their certified effects, by outcome",
+                &["execution", "outcome"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+/// The inputs of a certificate sampled for an audit, read before it was committed.
+pub struct AuditInput {
+    certificate: CertifiedTransaction,
+    gas_status: SuiGasStatus<'static>,
+    input_objects: InputObjects,
+    _permit: OwnedSemaphorePermit,
+}
+
+/// The context of a divergence, as written to the dump directory.
+#[derive(Serialize)]
+struct Divergence<'a> {
+    node_version: &'static str,
+    protocol_version: u64,
+    epoch: EpochId,
+    execution: Execution,
+    certificate: &'a CertifiedTransaction,
+    certified_effects: &'a TransactionEffects,
+    executed_effects: Option<&'a TransactionEffects>,
+    execution_error: Option<&'a str>,
+    /// The objects the audit executed the certificate against. Not known for synced executions.
+    input_objects: &'a [Object],
+}
+
+pub struct ExecutionAuditor {
+    sample_interval: u64,
+    dump_dir: Option<PathBuf>,
+    synced: AtomicU64,
+    native_functions: NativeFunctionTable,
+    audits: Arc<Semaphore>,
+    pub(crate) metrics: ExecutionAuditMetrics,
+}
+
+impl ExecutionAuditor {
+    pub fn new(config: &ExecutionAuditConfig, metrics: ExecutionAuditMetrics) -> Result<Self> {
+        if let Some(dump_dir) = &config.dump_dir {
+            std::fs::create_dir_all(dump_dir)?;
+        }
+        Ok(Self {
+            sample_interval: config.sample_interval,
+            dump_dir: config.dump_dir.clone(),
+            synced: AtomicU64::new(0),
+            native_functions: sui_framework::natives::all_natives(
+                MOVE_STDLIB_ADDRESS,
+                SUI_FRAMEWORK_ADDRESS,
+            ),
+            audits: Arc::new(Semaphore::new(MAX_CONCURRENT_AUDITS)),
+            metrics,
+        })
+    }
+
+    /// Whether the next synced certificate is audited.
+    pub fn sample(&self) -> bool {
+        self.sample_interval != 0
+            && self.synced.fetch_add(1, Ordering::Relaxed) % self.sample_interval == 0
+    }
+
+    /// Reads the inputs of `certificate` if it is sampled for an audit, which must happen before
+    /// it is committed.
+    pub async fn prepare(
+        &self,
+        state: &AuthorityState,
+        certificate: &CertifiedTransaction,
+    ) -> Option<AuditInput> {
+        if !self.sample() {
+            return None;
+        }
+        let permit = match self.audits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.record(Execution::Audit, "skipped");
+                return None;
+            }
+        };
+        let inputs =
+            transaction_input_checker::check_certificate_input(&state.database, certificate).await;
+        match inputs {
+            Ok((gas_status, input_objects)) => Some(AuditInput {
+                certificate: certificate.clone(),
+                gas_status,
+                input_objects,
+                _permit: permit,
+            }),
+            Err(err) => {
+                let tx_digest = certificate.digest();
+                warn!(
+                    ?tx_digest,
+                    "Failed to read the inputs of certificate to audit: {err}"
+                );
+                self.record(Execution::Audit, "failed");
+                None
+            }
+        }
+    }
+
+    /// Executes again the certificate of `input` on a blocking thread, and reports a divergence
+    /// if the effects produced are not `certified_effects`. Objects read by the execution other
+    /// than its inputs, e.g. dynamic fields, are read as they are when it runs.
+    pub fn spawn_audit(
+        self: Arc<Self>,
+        state: &AuthorityState,
+        input: AuditInput,
+        certified_effects: TransactionEffects,
+    ) {
+        let database = state.database.clone();
+        let epoch = state.epoch();
+        tokio::task::spawn_blocking(move || {
+            let AuditInput {
+                certificate,
+                gas_status,
+                input_objects,
+                _permit,
+            } = input;
+            let objects: Vec<_> = input_objects.objects().cloned().collect();
+            match self.execute(database, epoch, &certificate, gas_status, input_objects) {
+                Ok((effects, execution_error)) => self.compare(
+                    Execution::Audit,
+                    epoch,
+                    &certificate,
+                    &certified_effects,
+                    Some(&effects),
+                    execution_error.as_deref(),
+                    &objects,
+                ),
+                Err(err) => {
+                    let tx_digest = certificate.digest();
+                    warn!(
+                        ?tx_digest,
+                        "Failed to audit the execution of certificate: {err}"
+                    );
+                    self.record(Execution::Audit, "failed");
+                }
+            }
+        });
+    }
+
+    /// Reports whether the node reproduced `certified_effects` when it executed the synced
+    /// `certificate` to commit it.
+    pub fn report_synced(
+        &self,
+        epoch: EpochId,
+        certificate: &CertifiedTransaction,
+        certified_effects: &TransactionEffects,
+        executed_effects: Option<&TransactionEffects>,
+    ) {
+        self.compare(
+            Execution::Synced,
+            epoch,
+            certificate,
+            certified_effects,
+            executed_effects,
+            None,
+            &[],
+        );
+    }
+
+    fn execute(
+        &self,
+        database: Arc<AuthorityStore>,
+        epoch: EpochId,
+        certificate: &CertifiedTransaction,
+        gas_status: SuiGasStatus<'static>,
+        input_objects: InputObjects,
+    ) -> SuiResult<(TransactionEffects, Option<String>)> {
+        let transaction_digest = *certificate.digest();
+        let move_vm = Arc::new(adapter::new_move_vm(self.native_functions.clone())?);
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store = TemporaryStore::new(database, input_objects, transaction_digest);
+        let (_inner_temp_store, effects, execution_error, _) =
+            execution_engine::execute_transaction_to_effects(
+                shared_object_refs,
+                temporary_store,
+                certificate.signed_data.data.clone(),
+                transaction_digest,
+                transaction_dependencies,
+                &move_vm,
+                &self.native_functions,
+                gas_status,
+                // The limits of the epoch the certificate was certified in.
+                &ExecutionLimits::for_epoch(certificate.epoch()),
+                epoch,
+                false,
+            );
+        Ok((effects, execution_error.map(|err| err.to_string())))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn compare(
+        &self,
+        execution: Execution,
+        epoch: EpochId,
+        certificate: &CertifiedTransaction,
+        certified_effects: &TransactionEffects,
+        executed_effects: Option<&TransactionEffects>,
+        execution_error: Option<&str>,
+        input_objects: &[Object],
+    ) {
+        let certified_effects_digest = certified_effects.digest();
+        let executed_effects_digest = executed_effects.map(|effects| effects.digest());
+        if executed_effects_digest == Some(certified_effects_digest) {
+            self.record(execution, "matched");
+            return;
+        }
+        let divergence = Divergence {
+            node_version: env!("CARGO_PKG_VERSION"),
+            protocol_version: PROTOCOL_VERSION,
+            epoch,
+            execution,
+            certificate,
+            certified_effects,
+            executed_effects,
+            execution_error,
+            input_objects,
+        };
+        let dump = self
+            .dump_dir
+            .as_ref()
+            .and_then(|dump_dir| dump(dump_dir, &divergence));
+        error!(
+            tx_digest = ?certificate.digest(),
+            execution = execution.as_str(),
+            ?certified_effects_digest,
+            ?executed_effects_digest,
+            ?certified_effects,
+            ?executed_effects,
+            ?execution_error,
+            ?dump,
+            "Executed effects do not reproduce the certified effects!"
+        );
+        self.record(execution, "diverged");
+    }
+
+    fn record(&self, execution: Execution, outcome: &str) {
+        self.metrics
+            .compared_effects
+            .with_label_values(&[execution.as_str(), outcome])
+            .inc();
+    }
+}
+
+/// Writes the context of a divergence to `dump_dir`, returning the file written.
+fn dump(dump_dir: &Path, divergence: &Divergence) -> Option<PathBuf> {
+    let path = dump_dir.join(format!(
+        "{}.{}.json",
+        encode_bytes_hex(divergence.certificate.digest()),
+        divergence.execution.as_str(),
+    ));
+    let written = serde_json::to_vec_pretty(divergence)
+        .map_err(anyhow::Error::from)
+        .and_then(|bytes| Ok(std::fs::write(&path, bytes)?));
+    match written {
+        Ok(()) => Some(path),
+        Err(err) => {
+            warn!(?path, "Failed to write the context of a divergence: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_one_in_interval() {
+        let config = ExecutionAuditConfig {
+            sample_interval: 3,
+            dump_dir: None,
+        };
+        let auditor =
+            ExecutionAuditor::new(&config, ExecutionAuditMetrics::new_for_tests()).unwrap();
+        let sampled: Vec<_> = (0..7).map(|_| auditor.sample()).collect();
+        assert_eq!(sampled, vec![true, false, false, true, false, false, true]);
+
+        let config = ExecutionAuditConfig {
+            sample_interval: 0,
+            dump_dir: None,
+        };
+        let auditor =
+            ExecutionAuditor::new(&config, ExecutionAuditMetrics::new_for_tests()).unwrap();
+        assert!(!auditor.sample());
+    }
+}
@@ -18,6 +18,7 @@ use sui_adapter::adapter;
 use sui_types::coin::Coin;
 use sui_types::committee::EpochId;
 use sui_types::error::{ExecutionError, ExecutionErrorKind};
+use sui_types::execution_limits::ExecutionLimits;
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 #[cfg(test)]
@@ -41,6 +42,9 @@ use sui_types::{
 };
 use tracing::{debug, instrument, trace};
 
+/// Executes a transaction, returning its effects. If `measure_commands`, e.g. for dry runs, also
+/// returns the computation gas used by each command executed, and nothing otherwise, so that
+/// validators do not pay for measuring commands.
 #[instrument(name = "tx_execute_to_effects", level = "debug", skip_all)]
 pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
     shared_object_refs: Vec<ObjectRef>,
@@ -51,16 +55,19 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync + Chil
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
     gas_status: SuiGasStatus,
+    limits: &ExecutionLimits,
     epoch: EpochId,
+    measure_commands: bool,
 ) -> (
     InnerTemporaryStore,
     TransactionEffects,
     Option<ExecutionError>,
+    Vec<u64>,
 ) {
     let mut tx_ctx = TxContext::new(&transaction_data.signer(), &transaction_digest, epoch);
 
     let gas_object_ref = *transaction_data.gas_payment_object_ref();
-    let (gas_cost_summary, execution_result) = execute_transaction(
+    let (gas_cost_summary, execution_result, command_computation_costs) = execute_transaction(
         &mut temporary_store,
         transaction_data,
         gas_object_ref.0,
@@ -68,6 +75,8 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync + Chil
         move_vm,
         native_functions,
         gas_status,
+        limits,
+        measure_commands,
     );
 
     let (status, execution_error) = match execution_result {
@@ -96,7 +105,7 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync + Chil
         status,
         gas_object_ref,
     );
-    (inner, effects, execution_error)
+    (inner, effects, execution_error, command_computation_costs)
 }
 
 fn charge_gas_for_object_read<S>(
@@ -123,14 +132,31 @@ fn execute_transaction<S: BackingPackageStore + ParentSync + ChildObjectResolver
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
     mut gas_status: SuiGasStatus,
-) -> (GasCostSummary, Result<(), ExecutionError>) {
+    limits: &ExecutionLimits,
+    measure_commands: bool,
+) -> (GasCostSummary, Result<(), ExecutionError>, Vec<u64>) {
+    // The merged gas coins pay for gas whatever the outcome of the transaction, so they are
+    // merged again whenever the temporary store is reset.
+    let merged_gas_ids: Vec<_> = transaction_data
+        .merged_gas_payment()
+        .iter()
+        .map(|(object_id, _, _)| *object_id)
+        .collect();
+    smash_gas(temporary_store, &gas_object_id, &merged_gas_ids);
+
     // We must charge object read gas inside here during transaction execution, because if this fails
     // we must still ensure an effect is committed and all objects versions incremented.
     let mut result = charge_gas_for_object_read(temporary_store, &mut gas_status);
+    // The computation gas used by each command executed if `measure_commands`, to attribute the
+    // cost of a batch to its commands. Object reads and storage changes are charged for the whole
+    // transaction.
+    let mut command_computation_costs = vec![];
     if result.is_ok() {
         // TODO: Since we require all mutable objects to not show up more than
         // once across single tx, we should be able to run them in parallel.
         for single_tx in transaction_data.kind.into_single_transactions() {
+            let computation_cost_before =
+                measure_commands.then(|| gas_status.summary(true).computation_cost);
             result = match single_tx {
                 SingleTransactionKind::TransferObject(TransferObject {
                     recipient,
@@ -243,13 +269,21 @@ fn execute_transaction<S: BackingPackageStore + ParentSync + ChildObjectResolver
                     )
                 }
             };
+            if let Some(computation_cost_before) = computation_cost_before {
+                command_computation_costs
+                    .push(gas_status.summary(true).computation_cost - computation_cost_before);
+            }
             if result.is_err() {
                 break;
             }
         }
+        if result.is_ok() {
+            result = temporary_store.check_execution_limits(limits);
+        }
         if result.is_err() {
             // Roll back the temporary store if execution failed.
             temporary_store.reset();
+            smash_gas(temporary_store, &gas_object_id, &merged_gas_ids);
         }
     }
 
@@ -280,6 +314,7 @@ fn execute_transaction<S: BackingPackageStore + ParentSync + ChildObjectResolver
             // and re-ensure all mutable objects' versions are incremented.
             if result.is_ok() {
                 temporary_store.reset();
+                smash_gas(temporary_store, &gas_object_id, &merged_gas_ids);
                 temporary_store.ensure_active_inputs_mutated(&gas_object_id);
                 result = Err(err);
             }
@@ -300,7 +335,43 @@ fn execute_transaction<S: BackingPackageStore + ParentSync + ChildObjectResolver
     }
 
     let cost_summary = gas_status.summary(result.is_ok());
-    (cost_summary, result)
+    (cost_summary, result, command_computation_costs)
+}
+
+/// Merge the balances of the coins `merged_gas_ids` into the gas object, and delete them.
+fn smash_gas<S>(
+    temporary_store: &mut TemporaryStore<S>,
+    gas_object_id: &ObjectID,
+    merged_gas_ids: &[ObjectID],
+) {
+    if merged_gas_ids.is_empty() {
+        return;
+    }
+    let mut gas_object = temporary_store
+        .read_object(gas_object_id)
+        .expect("We constructed the object map so it should always have the gas object id")
+        .clone();
+    // The gas objects were checked to be SUI coins in transaction handle phase.
+    let gas_coin = GasCoin::try_from(&gas_object).unwrap();
+    let mut balance = gas_coin.value();
+    for object_id in merged_gas_ids {
+        let merged_object = temporary_store
+            .read_object(object_id)
+            .expect("We constructed the object map so it should always have the gas objects");
+        balance += GasCoin::try_from(merged_object).unwrap().value();
+        let version = merged_object.version();
+        temporary_store.delete_object(object_id, version, DeleteKind::Normal);
+    }
+    // We do not update the version number yet because gas charge will update it latter.
+    gas_object
+        .data
+        .try_as_move_mut()
+        .expect("Gas object must be Move object")
+        .update_contents_without_version_change(
+            bcs::to_bytes(&GasCoin::new(*gas_coin.id(), balance))
+                .expect("Serializing gas coin can never fail"),
+        );
+    temporary_store.write_object(gas_object, WriteKind::Mutate);
 }
 
 fn transfer_object<S>(
@@ -0,0 +1,393 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execution of transactions submitted by untrusted users, e.g. dry runs over JSON-RPC, isolated
+//! from the execution of certificates.
+//!
+//! Executions read the objects of the store through a `TemporaryStore`, which keeps every write
+//! in memory, so they can never change the canonical state. Each runs in a Move VM of its own,
+//! dropped once it completes, so the packages it loads or publishes never enter the loader cache
+//! of the node or of other executions. They run on the blocking thread pool, within ceilings
+//! tighter than the protocol limits: the gas budget meters the instructions executed, so every
+//! execution completes or runs out of gas, the size of the input and written objects bounds
+//! memory, and the size of the published packages bounds the unmetered work of verifying them.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use sui_adapter::adapter;
+use sui_config::node::ExecutionSandboxConfig;
+use sui_json_rpc_types::{
+    SuiCommandGasCost, SuiDryRunTraceResponse, SuiExecutionTrace, SuiTransactionEffects,
+};
+use sui_types::execution_limits::ExecutionLimits;
+use sui_types::messages::{SingleTransactionKind, Transaction};
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use tokio::sync::Semaphore;
+
+use crate::authority::AuthorityState;
+use crate::{execution_engine, transaction_input_checker};
+
+pub struct ExecutionSandboxMetrics {
+    executions: IntCounterVec,
+}
+
+impl ExecutionSandboxMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            executions: register_int_counter_vec_with_registry!(
+                "execution_sandbox_executions",
+                "Number of sandboxed executions, by outcome",
+                &["outcome"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+
+    fn record(&self, outcome: &str) {
+        self.executions.with_label_values(&[outcome]).inc();
+    }
+}
+
+pub struct ExecutionSandbox {
+    config: ExecutionSandboxConfig,
+    native_functions: NativeFunctionTable,
+    executions: Arc<Semaphore>,
+    metrics: ExecutionSandboxMetrics,
+}
+
+impl ExecutionSandbox {
+    pub fn new(config: ExecutionSandboxConfig, metrics: ExecutionSandboxMetrics) -> Self {
+        Self {
+            executions: Arc::new(Semaphore::new(config.max_concurrent_executions)),
+            config,
+            native_functions: sui_framework::natives::all_natives(
+                MOVE_STDLIB_ADDRESS,
+                SUI_FRAMEWORK_ADDRESS,
+            ),
+            metrics,
+        }
+    }
+
+    /// The limits of executions in the sandbox: the protocol `limits`, lowered to the ceilings
+    /// of the sandbox.
+    pub fn execution_limits(&self, limits: ExecutionLimits) -> ExecutionLimits {
+        ExecutionLimits {
+            max_created_objects: limits
+                .max_created_objects
+                .min(self.config.max_created_objects),
+            max_object_size: limits
+                .max_object_size
+                .min(self.config.max_object_size_bytes),
+            max_event_count: limits.max_event_count.min(self.config.max_events),
+            ..limits
+        }
+    }
+
+    /// Checks the parts of `transaction` the sandbox bounds before loading its inputs.
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<()> {
+        let data = &transaction.signed_data.data;
+        if data.gas_budget > self.config.max_gas_budget {
+            bail!(
+                "Gas budget {} is above the maximum of {} for dry runs",
+                data.gas_budget,
+                self.config.max_gas_budget
+            );
+        }
+        let package_size: usize = data
+            .kind
+            .single_transactions()
+            .map(|kind| match kind {
+                SingleTransactionKind::Publish(publish) => {
+                    publish.modules.iter().map(Vec::len).sum()
+                }
+                _ => 0,
+            })
+            .sum();
+        if package_size > self.config.max_package_size_bytes {
+            bail!(
+                "Package of {package_size} bytes is above the maximum of {} for dry runs",
+                self.config.max_package_size_bytes
+            );
+        }
+        Ok(())
+    }
+
+    /// Executes `transaction` against the objects of `state` without committing anything, and
+    /// returns its effects.
+    pub async fn dry_run(
+        &self,
+        state: &AuthorityState,
+        transaction: &Transaction,
+    ) -> Result<SuiTransactionEffects> {
+        Ok(self.run(state, transaction, false).await?.effects)
+    }
+
+    /// Like `dry_run`, also tracing where the Move calls of `transaction` stopped if execution
+    /// failed.
+    pub async fn dry_run_with_trace(
+        &self,
+        state: &AuthorityState,
+        transaction: &Transaction,
+    ) -> Result<SuiDryRunTraceResponse> {
+        self.run(state, transaction, true).await
+    }
+
+    async fn run(
+        &self,
+        state: &AuthorityState,
+        transaction: &Transaction,
+        trace: bool,
+    ) -> Result<SuiDryRunTraceResponse> {
+        let result = self.execute(state, transaction, trace).await;
+        self.metrics.record(match &result {
+            Ok(_) => "executed",
+            Err(SandboxError::Rejected(_)) => "rejected",
+            Err(SandboxError::Failed(_)) => "failed",
+        });
+        result.map_err(|err| match err {
+            SandboxError::Rejected(err) | SandboxError::Failed(err) => err,
+        })
+    }
+
+    async fn execute(
+        &self,
+        state: &AuthorityState,
+        transaction: &Transaction,
+        trace: bool,
+    ) -> Result<SuiDryRunTraceResponse, SandboxError> {
+        let permit = self.executions.clone().try_acquire_owned().map_err(|_| {
+            SandboxError::Rejected(anyhow!("Too many dry runs in progress, retry later"))
+        })?;
+        transaction
+            .verify()
+            .map_err(|e| SandboxError::Rejected(e.into()))?;
+        self.check_transaction(transaction)
+            .map_err(SandboxError::Rejected)?;
+
+        let (gas_status, input_objects) =
+            transaction_input_checker::check_transaction_input(&state.database, transaction)
+                .await
+                .map_err(|e| SandboxError::Rejected(e.into()))?;
+        let input_size = input_objects.size_for_gas_metering();
+        if input_size > self.config.max_input_size_bytes {
+            return Err(SandboxError::Rejected(anyhow!(
+                "Input objects of {input_size} bytes are above the maximum of {} for dry runs",
+                self.config.max_input_size_bytes
+            )));
+        }
+
+        let transaction_digest = *transaction.digest();
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store =
+            TemporaryStore::new(state.database.clone(), input_objects, transaction_digest);
+        let transaction_data = transaction.signed_data.data.clone();
+        let native_functions = self.native_functions.clone();
+        let epoch = state.epoch();
+        let limits = self.execution_limits(ExecutionLimits::for_epoch(epoch));
+
+        // The permit is released when the execution ends, even if the caller stopped waiting
+        // for it, so that abandoned executions still count against the concurrency ceiling.
+        let execution = tokio::task::spawn_blocking(move || -> Result<_> {
+            let _permit = permit;
+            let move_vm = Arc::new(adapter::new_move_vm(native_functions.clone())?);
+            let (_inner_temp_store, effects, execution_error, command_computation_costs) =
+                execution_engine::execute_transaction_to_effects(
+                    shared_object_refs,
+                    temporary_store,
+                    transaction_data,
+                    transaction_digest,
+                    transaction_dependencies,
+                    &move_vm,
+                    &native_functions,
+                    gas_status,
+                    &limits,
+                    epoch,
+                    true,
+                );
+            Ok((effects, execution_error, command_computation_costs))
+        });
+        let (effects, execution_error, command_computation_costs) = execution
+            .await
+            .map_err(|err| SandboxError::Failed(err.into()))?
+            .map_err(SandboxError::Failed)?;
+        let trace = execution_error
+            .filter(|_| trace)
+            .map(|error| {
+                SuiExecutionTrace::new(
+                    &error,
+                    &transaction.signed_data.data,
+                    state.module_cache.as_ref(),
+                )
+            })
+            .transpose()
+            .map_err(SandboxError::Failed)?;
+        let mut effects = SuiTransactionEffects::try_from(effects, state.module_cache.as_ref())
+            .map_err(SandboxError::Failed)?;
+        effects.command_gas_costs = SuiCommandGasCost::for_commands(
+            &transaction.signed_data.data,
+            &command_computation_costs,
+        );
+        Ok(SuiDryRunTraceResponse { effects, trace })
+    }
+}
+
+enum SandboxError {
+    /// The transaction is above a ceiling of the sandbox, or invalid.
+    Rejected(anyhow::Error),
+    Failed(anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority::authority_tests::{
+        init_state_with_ids, init_state_with_ids_and_object_basics, init_transfer_transaction,
+    };
+    use crate::test_utils::to_sender_signed_transaction;
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::ObjectID;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::messages::{CallArg, TransactionData};
+
+    fn sandbox(config: ExecutionSandboxConfig) -> ExecutionSandbox {
+        ExecutionSandbox::new(config, ExecutionSandboxMetrics::new_for_tests())
+    }
+
+    #[test]
+    fn test_limits_are_lowered_to_ceilings() {
+        let config = ExecutionSandboxConfig {
+            max_created_objects: 10,
+            max_events: 100_000,
+            ..Default::default()
+        };
+        let sandbox = sandbox(config);
+        let protocol = ExecutionLimits::for_protocol_version(1);
+        let limits = sandbox.execution_limits(protocol);
+
+        assert_eq!(limits.max_created_objects, 10);
+        assert_eq!(limits.max_event_count, protocol.max_event_count);
+        assert_eq!(limits.max_object_size, 64 * 1024);
+        assert_eq!(
+            limits.max_dynamic_field_changes,
+            protocol.max_dynamic_field_changes
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_change_the_store() {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+        let object_id = ObjectID::random();
+        let gas_object_id = ObjectID::random();
+        let state = init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+        let object = state.database.get_object(&object_id).unwrap().unwrap();
+        let gas_object = state.database.get_object(&gas_object_id).unwrap().unwrap();
+        let transaction = init_transfer_transaction(
+            sender,
+            &sender_key,
+            recipient,
+            object.compute_object_reference(),
+            gas_object.compute_object_reference(),
+        );
+
+        let sandbox = sandbox(ExecutionSandboxConfig::default());
+        let effects = sandbox.dry_run(&state, &transaction).await.unwrap();
+        assert!(effects.status.is_ok());
+        assert_eq!(effects.mutated.len(), 2);
+
+        // Neither the object nor the gas changed in the store.
+        assert_eq!(state.database.get_object(&object_id).unwrap(), Some(object));
+        assert_eq!(
+            state.database.get_object(&gas_object_id).unwrap(),
+            Some(gas_object)
+        );
+        assert!(!state.is_tx_already_executed(transaction.digest()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_stops_when_out_of_gas() {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let gas_object_id = ObjectID::random();
+        let (state, pkg_ref) =
+            init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+        let gas_object = state.database.get_object(&gas_object_id).unwrap().unwrap();
+        let config = ExecutionSandboxConfig::default();
+        let data = TransactionData::new_move_call(
+            sender,
+            pkg_ref,
+            Identifier::new("object_basics").unwrap(),
+            Identifier::new("iterate").unwrap(),
+            vec![],
+            gas_object.compute_object_reference(),
+            vec![CallArg::Pure(bcs::to_bytes(&u64::MAX).unwrap())],
+            config.max_gas_budget,
+        );
+        let transaction = to_sender_signed_transaction(data, &sender_key);
+
+        // The gas budget meters the loop, which never ends on its own.
+        let effects = sandbox(config).dry_run(&state, &transaction).await.unwrap();
+        assert!(!effects.status.is_ok());
+        assert_eq!(
+            state.database.get_object(&gas_object_id).unwrap(),
+            Some(gas_object)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_rejects_transactions_above_ceilings() {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let object_id = ObjectID::random();
+        let gas_object_id = ObjectID::random();
+        let state = init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+        let object_ref = state
+            .database
+            .get_object(&object_id)
+            .unwrap()
+            .unwrap()
+            .compute_object_reference();
+        let gas_ref = state
+            .database
+            .get_object(&gas_object_id)
+            .unwrap()
+            .unwrap()
+            .compute_object_reference();
+        let transfer = init_transfer_transaction(sender, &sender_key, sender, object_ref, gas_ref);
+
+        // The transfer has a budget of 10000.
+        let low_budget = sandbox(ExecutionSandboxConfig {
+            max_gas_budget: 9_999,
+            ..Default::default()
+        });
+        let err = low_budget.dry_run(&state, &transfer).await.unwrap_err();
+        assert!(err.to_string().contains("Gas budget"), "{err}");
+
+        let no_executions = sandbox(ExecutionSandboxConfig {
+            max_concurrent_executions: 0,
+            ..Default::default()
+        });
+        let err = no_executions.dry_run(&state, &transfer).await.unwrap_err();
+        assert!(err.to_string().contains("Too many dry runs"), "{err}");
+
+        // Publishing is rejected on the size of the modules, before verifying them.
+        let publish = to_sender_signed_transaction(
+            TransactionData::new_module(sender, gas_ref, vec![vec![0; 1025]], 10_000),
+            &sender_key,
+        );
+        let small_packages = sandbox(ExecutionSandboxConfig {
+            max_package_size_bytes: 1024,
+            ..Default::default()
+        });
+        let err = small_packages.dry_run(&state, &publish).await.unwrap_err();
+        assert!(err.to_string().contains("Package of 1025 bytes"), "{err}");
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Number of recent checkpoints for which gas price statistics are retained.
+pub const GAS_PRICE_HISTORY_CHECKPOINTS: usize = 128;
+
+/// Distribution of the gas prices paid by the transactions of a single checkpoint, together
+/// with the execution queue depth observed when the checkpoint was processed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckpointGasPriceStats {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub tx_count: u64,
+    pub min: u64,
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub max: u64,
+    /// Number of certificates waiting for execution on this node.
+    pub pending_certificates: u64,
+}
+
+impl CheckpointGasPriceStats {
+    pub fn new(
+        checkpoint: CheckpointSequenceNumber,
+        mut gas_prices: Vec<u64>,
+        pending_certificates: u64,
+    ) -> Self {
+        gas_prices.sort_unstable();
+        // Nearest-rank percentile over the sorted prices.
+        let percentile = |p: usize| {
+            if gas_prices.is_empty() {
+                return 0;
+            }
+            let rank = (p * gas_prices.len() + 99) / 100;
+            gas_prices[rank.saturating_sub(1)]
+        };
+        Self {
+            checkpoint,
+            tx_count: gas_prices.len() as u64,
+            min: gas_prices.first().copied().unwrap_or_default(),
+            p25: percentile(25),
+            p50: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+            max: gas_prices.last().copied().unwrap_or_default(),
+            pending_certificates,
+        }
+    }
+}
+
+/// Keeps the gas price statistics of the most recent checkpoints processed by this node,
+/// so that clients can pick a competitive gas price under load.
+pub struct GasPriceTracker {
+    capacity: usize,
+    recent: Mutex<VecDeque<CheckpointGasPriceStats>>,
+}
+
+impl Default for GasPriceTracker {
+    fn default() -> Self {
+        Self::new(GAS_PRICE_HISTORY_CHECKPOINTS)
+    }
+}
+
+impl GasPriceTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recent: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Record the statistics of a checkpoint. Checkpoints that are not newer than the latest
+    /// recorded one are ignored, which makes this safe to call from both the signing and the
+    /// syncing path.
+    pub fn record(&self, stats: CheckpointGasPriceStats) {
+        let mut recent = self.recent.lock();
+        if matches!(recent.back(), Some(last) if last.checkpoint >= stats.checkpoint) {
+            return;
+        }
+        if recent.len() == self.capacity {
+            recent.pop_front();
+        }
+        recent.push_back(stats);
+    }
+
+    /// Return the statistics of up to `count` most recent checkpoints, newest first.
+    pub fn recent(&self, count: usize) -> Vec<CheckpointGasPriceStats> {
+        self.recent
+            .lock()
+            .iter()
+            .rev()
+            .take(count)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_gas_price_stats() {
+        let stats = CheckpointGasPriceStats::new(3, (1..=100).rev().collect(), 7);
+        assert_eq!(stats.tx_count, 100);
+        assert_eq!(stats.min, 1);
+        assert_eq!(stats.p25, 25);
+        assert_eq!(stats.p50, 50);
+        assert_eq!(stats.p90, 90);
+        assert_eq!(stats.max, 100);
+        assert_eq!(stats.pending_certificates, 7);
+
+        let empty = CheckpointGasPriceStats::new(4, vec![], 0);
+        assert_eq!(empty.tx_count, 0);
+        assert_eq!(empty.p50, 0);
+    }
+
+    #[test]
+    fn test_gas_price_tracker_window() {
+        let tracker = GasPriceTracker::new(2);
+        for seq in 0..3 {
+            tracker.record(CheckpointGasPriceStats::new(seq, vec![seq + 1], 0));
+        }
+        // Stale checkpoints are ignored.
+        tracker.record(CheckpointGasPriceStats::new(1, vec![100], 0));
+
+        let recent = tracker.recent(10);
+        assert_eq!(
+            recent.iter().map(|s| s.checkpoint).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(tracker.recent(1)[0].max, 3);
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::gas_algebra::InternalGas;
+use move_core_types::language_storage::ModuleId;
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry, IntCounter,
+    IntGaugeVec, Registry,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use sui_types::gas::GasObserver;
+
+/// Number of functions exported in the metrics.
+pub const DEFAULT_TOP_FUNCTIONS: usize = 20;
+
+/// Gas spent in a Move function, excluding the functions it calls, in internal gas units.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FunctionGasUsage {
+    /// `address::module::function`
+    pub function: String,
+    pub calls: u64,
+    pub gas: u64,
+}
+
+#[derive(Default)]
+struct ProfileState {
+    /// Functions being executed, innermost last.
+    stack: Vec<String>,
+    /// Set after a call until the next charge, which is for the native function called if it
+    /// is native. Native functions do not return through a `Ret` instruction.
+    call_pending: bool,
+    /// calls and gas by function
+    functions: HashMap<String, (u64, u64)>,
+}
+
+/// Gas profile of a single transaction, attributing the gas charged by the VM to the Move
+/// function executing when it is charged.
+#[derive(Default)]
+pub struct TransactionGasProfile {
+    state: Mutex<ProfileState>,
+}
+
+impl TransactionGasProfile {
+    fn push(state: &mut ProfileState, module_id: &ModuleId, function: &str) {
+        let name = format!("{}::{}", module_id.short_str_lossless(), function);
+        state.functions.entry(name.clone()).or_default().0 += 1;
+        state.stack.push(name);
+    }
+}
+
+impl GasObserver for TransactionGasProfile {
+    fn on_entry(&self, module_id: &ModuleId, function: &str) {
+        let mut state = self.state.lock();
+        // A previous entry function may have aborted without returning.
+        state.stack.clear();
+        state.call_pending = false;
+        Self::push(&mut state, module_id, function);
+    }
+
+    fn on_call(&self, module_id: &ModuleId, function: &str) {
+        let mut state = self.state.lock();
+        Self::push(&mut state, module_id, function);
+        state.call_pending = true;
+    }
+
+    fn on_return(&self) {
+        self.state.lock().stack.pop();
+    }
+
+    fn on_charge(&self, amount: InternalGas, native: bool) {
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        if let Some(current) = state.stack.last() {
+            if let Some((_, gas)) = state.functions.get_mut(current) {
+                *gas += u64::from(amount);
+            }
+        }
+        if std::mem::take(&mut state.call_pending) && native {
+            state.stack.pop();
+        }
+    }
+}
+
+/// GasProfiler profiles the gas usage of one in every `sample_interval` executed transactions,
+/// and aggregates the profiles by Move function, so that package authors can find the functions
+/// that are the most expensive to call. It is disabled until a sample interval is set, e.g.
+/// through the admin API.
+pub struct GasProfiler {
+    sample_interval: AtomicU64,
+    executed: AtomicU64,
+    functions: Mutex<HashMap<String, (u64, u64)>>,
+    metrics: GasProfilerMetrics,
+}
+
+impl GasProfiler {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            sample_interval: AtomicU64::new(0),
+            executed: AtomicU64::new(0),
+            functions: Mutex::new(HashMap::new()),
+            metrics: GasProfilerMetrics::new(registry),
+        }
+    }
+
+    /// Profile one in every `interval` transactions. 0 disables profiling.
+    pub fn set_sample_interval(&self, interval: u64) {
+        self.sample_interval.store(interval, Ordering::Relaxed);
+    }
+
+    pub fn sample_interval(&self) -> u64 {
+        self.sample_interval.load(Ordering::Relaxed)
+    }
+
+    /// Return a profile to observe the execution of the next transaction with, if it is sampled.
+    pub fn sample(&self) -> Option<Arc<TransactionGasProfile>> {
+        let interval = self.sample_interval();
+        if interval == 0 || self.executed.fetch_add(1, Ordering::Relaxed) % interval != 0 {
+            return None;
+        }
+        Some(Arc::new(TransactionGasProfile::default()))
+    }
+
+    /// Aggregate the profile of a sampled transaction, and refresh the metrics.
+    pub fn record(&self, profile: &TransactionGasProfile) {
+        let mut functions = self.functions.lock();
+        for (function, (calls, gas)) in &profile.state.lock().functions {
+            let usage = functions.entry(function.clone()).or_default();
+            usage.0 += calls;
+            usage.1 += gas;
+        }
+        self.metrics.sampled_transactions.inc();
+        // Only export the hottest functions, to bound the cardinality of the metrics.
+        self.metrics.function_gas.reset();
+        self.metrics.function_calls.reset();
+        for usage in Self::top(&functions, DEFAULT_TOP_FUNCTIONS) {
+            self.metrics
+                .function_gas
+                .with_label_values(&[&usage.function])
+                .set(usage.gas as i64);
+            self.metrics
+                .function_calls
+                .with_label_values(&[&usage.function])
+                .set(usage.calls as i64);
+        }
+    }
+
+    /// Return the `count` functions that used the most gas in sampled transactions.
+    pub fn top_functions(&self, count: usize) -> Vec<FunctionGasUsage> {
+        Self::top(&self.functions.lock(), count)
+    }
+
+    /// Discard the profiles aggregated so far.
+    pub fn reset(&self) {
+        self.functions.lock().clear();
+        self.metrics.function_gas.reset();
+        self.metrics.function_calls.reset();
+    }
+
+    fn top(functions: &HashMap<String, (u64, u64)>, count: usize) -> Vec<FunctionGasUsage> {
+        let mut usages: Vec<_> = functions
+            .iter()
+            .map(|(function, (calls, gas))| FunctionGasUsage {
+                function: function.clone(),
+                calls: *calls,
+                gas: *gas,
+            })
+            .collect();
+        usages.sort_by(|a, b| b.gas.cmp(&a.gas).then_with(|| a.function.cmp(&b.function)));
+        usages.truncate(count);
+        usages
+    }
+}
+
+struct GasProfilerMetrics {
+    sampled_transactions: IntCounter,
+    function_gas: IntGaugeVec,
+    function_calls: IntGaugeVec,
+}
+
+impl GasProfilerMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            sampled_transactions: register_int_counter_with_registry!(
+                "gas_profiler_sampled_transactions",
+                "Number of transactions whose gas usage was profiled",
+                registry,
+            )
+            .unwrap(),
+            function_gas: register_int_gauge_vec_with_registry!(
+                "gas_profiler_function_gas",
+                "Gas used by the hottest Move functions in profiled transactions, excluding callees",
+                &["function"],
+                registry,
+            )
+            .unwrap(),
+            function_calls: register_int_gauge_vec_with_registry!(
+                "gas_profiler_function_calls",
+                "Number of calls to the hottest Move functions in profiled transactions",
+                &["function"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+
+    fn module(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    #[test]
+    fn test_gas_is_attributed_to_the_executing_function() {
+        let profiler = GasProfiler::new(&Registry::new());
+        assert!(profiler.sample().is_none());
+        profiler.set_sample_interval(2);
+        let profile = profiler.sample().unwrap();
+        assert!(profiler.sample().is_none());
+
+        let (game, math) = (module("game"), module("math"));
+        profile.on_entry(&game, "play");
+        profile.on_charge(InternalGas::new(10), false);
+        profile.on_call(&math, "sqrt");
+        profile.on_charge(InternalGas::new(5), false);
+        profile.on_call(&math, "native_pow");
+        profile.on_charge(InternalGas::new(100), true);
+        profile.on_charge(InternalGas::new(1), false);
+        profile.on_return();
+        profile.on_charge(InternalGas::new(2), false);
+        profile.on_return();
+        profiler.record(&profile);
+
+        let name = |m: &ModuleId, f: &str| format!("{}::{}", m.short_str_lossless(), f);
+        assert_eq!(
+            profiler.top_functions(2),
+            vec![
+                FunctionGasUsage {
+                    function: name(&math, "native_pow"),
+                    calls: 1,
+                    gas: 100,
+                },
+                FunctionGasUsage {
+                    function: name(&game, "play"),
+                    calls: 1,
+                    gas: 12,
+                },
+            ]
+        );
+        assert_eq!(profiler.top_functions(10)[2].gas, 6);
+
+        profiler.reset();
+        assert!(profiler.top_functions(10).is_empty());
+    }
+}
@@ -1355,6 +1355,7 @@ where
             effects: SuiTransactionEffects::try_from(effects, &self.module_cache)?,
             timestamp_ms: None,
             parsed_data,
+            balance_changes: None,
         });
     }
 
@@ -1713,6 +1714,7 @@ where
             effects: SuiTransactionEffects::try_from(effect, &self.module_cache)?,
             timestamp_ms: None,
             parsed_data: None,
+            balance_changes: None,
         })
     }
 }
@@ -18,9 +18,10 @@ use sui_types::{
         get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes,
         AuthoritySignature, KeypairTraits, Signature,
     },
+    execution_limits::ExecutionLimit,
     messages::{
         CallArg, EntryArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus, ObjectArg,
-        ObjectInfoRequestKind, SingleTransactionKind, TransactionKind,
+        ObjectContentOptions, ObjectInfoRequestKind, SingleTransactionKind, TransactionKind,
     },
     object::{Data, Owner},
     storage::DeleteKind,
@@ -77,11 +78,13 @@ fn get_registry() -> Result<Registry> {
     tracer.trace_type::<ExecutionFailureStatus>(&samples)?;
     tracer.trace_type::<AbortLocation>(&samples)?;
     tracer.trace_type::<EntryArgumentErrorKind>(&samples)?;
+    tracer.trace_type::<ExecutionLimit>(&samples)?;
     tracer.trace_type::<CallArg>(&samples)?;
     tracer.trace_type::<ObjectArg>(&samples)?;
     tracer.trace_type::<Data>(&samples)?;
     tracer.trace_type::<TypeTag>(&samples)?;
     tracer.trace_type::<TypedStoreError>(&samples)?;
+    tracer.trace_type::<ObjectContentOptions>(&samples)?;
     tracer.trace_type::<ObjectInfoRequestKind>(&samples)?;
     tracer.trace_type::<SingleTransactionKind>(&samples)?;
     tracer.trace_type::<TransactionKind>(&samples)?;
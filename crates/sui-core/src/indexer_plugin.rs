@@ -0,0 +1,695 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plugins run custom indexers inside a node, on the transactions it commits and the checkpoints
+//! it completes, without changing how the node syncs them.
+//!
+//! The plugins are registered before the node starts. They are handed every transaction the node
+//! commits from then on, in the order it executed them, then every checkpoint once all its
+//! transactions were committed. Checkpoints are final, so a plugin can treat what it derived from
+//! the transactions of a checkpoint as settled once it is handed the checkpoint. Transactions
+//! committed before the node started are not handed to the plugins, which resume from the
+//! checkpoint they saw last by other means if they need to.
+
+use std::collections::HashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+use sui_types::balance::read_changed_objects;
+use sui_types::base_types::TransactionDigest;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::event::Event;
+use sui_types::messages::{CertifiedTransaction, TransactionEffects};
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+};
+use sui_types::object::Object;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use typed_store::Map;
+
+use crate::authority::AuthorityState;
+
+/// The most transactions handed to the plugins in a single round.
+const MAX_TRANSACTIONS_PER_ROUND: usize = 1000;
+
+/// A transaction committed by the node, with everything it changed.
+#[derive(Clone, Debug)]
+pub struct CommittedTransaction {
+    /// The position of the transaction in the execution order of the node.
+    pub sequence: TxSequenceNumber,
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+    /// The contents of the objects changed by the transaction, before and after it. The contents
+    /// before are None for created and unwrapped objects, after for deleted and wrapped ones, and
+    /// either is None if the node pruned that version.
+    pub object_changes: Vec<(Option<Object>, Option<Object>)>,
+}
+
+impl CommittedTransaction {
+    pub fn digest(&self) -> &TransactionDigest {
+        self.certificate.digest()
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.effects.events
+    }
+}
+
+/// A custom indexer run by the node. Errors returned by the hooks, and their panics, are logged
+/// and counted, and the plugin is handed the next transactions regardless.
+pub trait IndexerPlugin: Send + Sync + 'static {
+    /// Names the plugin in logs and metrics.
+    fn name(&self) -> &str;
+
+    /// Called once before the other hooks, with the first transaction and checkpoint the plugin
+    /// will be handed.
+    fn on_start(
+        &self,
+        _first_transaction: TxSequenceNumber,
+        _first_checkpoint: CheckpointSequenceNumber,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called for every transaction committed by the node, in the order it executed them.
+    fn on_transaction(&self, transaction: &CommittedTransaction) -> anyhow::Result<()>;
+
+    /// Called for every checkpoint, in order, once all its transactions were committed and handed
+    /// to `on_transaction`, unless they were committed before the plugin started.
+    fn on_checkpoint(
+        &self,
+        _summary: &CheckpointSummary,
+        _contents: &CheckpointContents,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called once the plugin will not be handed anything more, e.g. when the node stops.
+    fn on_stop(&self) {}
+}
+
+/// How the hooks of a plugin are called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluginDelivery {
+    /// By the task following the commits of the node, on a thread allowed to block, so that a
+    /// slow plugin delays the plugins registered after it.
+    Synchronous,
+    /// By a thread of the plugin, fed through a channel of `capacity` events. Following the
+    /// commits of the node waits while the channel is full, so no event is dropped.
+    Channel { capacity: usize },
+}
+
+/// The plugins a node runs, registered before it starts.
+#[derive(Default)]
+pub struct IndexerPlugins {
+    plugins: Vec<(Arc<dyn IndexerPlugin>, PluginDelivery)>,
+}
+
+impl IndexerPlugins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, plugin: Arc<dyn IndexerPlugin>, delivery: PluginDelivery) {
+        self.plugins.push((plugin, delivery));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Starts the plugins, which are handed the given transaction and checkpoint first.
+    pub async fn start(
+        self,
+        first_transaction: TxSequenceNumber,
+        first_checkpoint: CheckpointSequenceNumber,
+        metrics: Arc<IndexerPluginMetrics>,
+    ) -> RunningPlugins {
+        let mut synchronous = vec![];
+        let mut channels = vec![];
+        for (plugin, delivery) in self.plugins {
+            info!(plugin = plugin.name(), ?delivery, "Starting indexer plugin");
+            match delivery {
+                PluginDelivery::Synchronous => synchronous.push(plugin),
+                PluginDelivery::Channel { capacity } => {
+                    let (sender, mut receiver) = mpsc::channel(capacity.max(1));
+                    let metrics = metrics.clone();
+                    tokio::task::spawn_blocking(move || {
+                        while let Some(event) = receiver.blocking_recv() {
+                            deliver(&*plugin, &event, &metrics);
+                        }
+                        stop(&*plugin, &metrics);
+                    });
+                    channels.push(sender);
+                }
+            }
+        }
+        let running = RunningPlugins {
+            synchronous: Arc::new(synchronous),
+            channels,
+            metrics,
+        };
+        running
+            .dispatch(PluginEvent::Start {
+                first_transaction,
+                first_checkpoint,
+            })
+            .await;
+        running
+    }
+}
+
+#[derive(Clone)]
+enum PluginEvent {
+    Start {
+        first_transaction: TxSequenceNumber,
+        first_checkpoint: CheckpointSequenceNumber,
+    },
+    Transaction(Arc<CommittedTransaction>),
+    Checkpoint(Arc<(CheckpointSummary, CheckpointContents)>),
+}
+
+fn deliver(plugin: &dyn IndexerPlugin, event: &PluginEvent, metrics: &IndexerPluginMetrics) {
+    let name = plugin.name();
+    let result = call_hook(|| match event {
+        PluginEvent::Start {
+            first_transaction,
+            first_checkpoint,
+        } => plugin.on_start(*first_transaction, *first_checkpoint),
+        PluginEvent::Transaction(transaction) => {
+            metrics.transactions.with_label_values(&[name]).inc();
+            plugin.on_transaction(transaction)
+        }
+        PluginEvent::Checkpoint(checkpoint) => {
+            let (summary, contents) = &**checkpoint;
+            metrics
+                .last_checkpoint
+                .with_label_values(&[name])
+                .set(summary.sequence_number as i64);
+            plugin.on_checkpoint(summary, contents)
+        }
+    });
+    if let Err(err) = result {
+        metrics.errors.with_label_values(&[name]).inc();
+        warn!(plugin = name, "Indexer plugin failed: {err}");
+    }
+}
+
+fn stop(plugin: &dyn IndexerPlugin, metrics: &IndexerPluginMetrics) {
+    let name = plugin.name();
+    let result = call_hook(|| {
+        plugin.on_stop();
+        Ok(())
+    });
+    if let Err(err) = result {
+        metrics.errors.with_label_values(&[name]).inc();
+        warn!(plugin = name, "Indexer plugin failed to stop: {err}");
+    }
+}
+
+/// Calls a hook, turning its panic into an error so that a panicking plugin does not stop the
+/// thread handing events to it.
+fn call_hook(hook: impl FnOnce() -> anyhow::Result<()>) -> anyhow::Result<()> {
+    catch_unwind(AssertUnwindSafe(hook)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(anyhow::anyhow!("Panicked: {message}"))
+    })
+}
+
+/// The started plugins. Dropping them stops the plugins.
+pub struct RunningPlugins {
+    synchronous: Arc<Vec<Arc<dyn IndexerPlugin>>>,
+    channels: Vec<mpsc::Sender<PluginEvent>>,
+    metrics: Arc<IndexerPluginMetrics>,
+}
+
+impl RunningPlugins {
+    pub async fn on_transaction(&self, transaction: CommittedTransaction) {
+        self.dispatch(PluginEvent::Transaction(Arc::new(transaction)))
+            .await
+    }
+
+    pub async fn on_checkpoint(&self, summary: CheckpointSummary, contents: CheckpointContents) {
+        self.dispatch(PluginEvent::Checkpoint(Arc::new((summary, contents))))
+            .await
+    }
+
+    async fn dispatch(&self, event: PluginEvent) {
+        if !self.synchronous.is_empty() {
+            let plugins = self.synchronous.clone();
+            let metrics = self.metrics.clone();
+            let event = event.clone();
+            // The hooks may block, so they are not called on a worker of the runtime.
+            tokio::task::spawn_blocking(move || {
+                for plugin in plugins.iter() {
+                    deliver(&**plugin, &event, &metrics);
+                }
+            })
+            .await
+            .ok();
+        }
+        for sender in &self.channels {
+            // Fails only if the runtime is shutting down.
+            sender.send(event.clone()).await.ok();
+        }
+    }
+}
+
+impl Drop for RunningPlugins {
+    fn drop(&mut self) {
+        // Plugins fed through channels stop once their channel is closed.
+        let plugins = self.synchronous.clone();
+        let metrics = self.metrics.clone();
+        let stop_all = move || {
+            for plugin in plugins.iter() {
+                stop(&**plugin, &metrics);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn_blocking(stop_all);
+            }
+            Err(_) => stop_all(),
+        }
+    }
+}
+
+pub struct IndexerPluginMetrics {
+    transactions: IntCounterVec,
+    errors: IntCounterVec,
+    last_checkpoint: IntGaugeVec,
+}
+
+impl IndexerPluginMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            transactions: register_int_counter_vec_with_registry!(
+                "indexer_plugin_transactions",
+                "Number of committed transactions handed to each indexer plugin",
+                &["plugin"],
+                registry,
+            )
+            .unwrap(),
+            errors: register_int_counter_vec_with_registry!(
+                "indexer_plugin_errors",
+                "Number of errors returned by the hooks of each indexer plugin",
+                &["plugin"],
+                registry,
+            )
+            .unwrap(),
+            last_checkpoint: register_int_gauge_vec_with_registry!(
+                "indexer_plugin_last_checkpoint",
+                "The last checkpoint handed to each indexer plugin",
+                &["plugin"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+/// Hands the transactions committed by a node and its completed checkpoints to its plugins.
+pub struct IndexerPluginRunner {
+    state: Arc<AuthorityState>,
+    plugins: RunningPlugins,
+    next_transaction: TxSequenceNumber,
+    next_checkpoint: CheckpointSequenceNumber,
+}
+
+impl IndexerPluginRunner {
+    /// Starts the plugins from the next transaction committed by the node, and the next
+    /// checkpoint it stores.
+    pub async fn new(
+        plugins: IndexerPlugins,
+        state: Arc<AuthorityState>,
+        registry: &Registry,
+    ) -> Self {
+        let next_transaction = state.batch_notifier.low_watermark();
+        let next_checkpoint = state.next_stored_checkpoint();
+        let metrics = Arc::new(IndexerPluginMetrics::new(registry));
+        Self {
+            plugins: plugins
+                .start(next_transaction, next_checkpoint, metrics)
+                .await,
+            state,
+            next_transaction,
+            next_checkpoint,
+        }
+    }
+
+    /// Hands the transactions committed since the last round to the plugins, then the
+    /// checkpoints completed by them. Returns the number of transactions handed.
+    pub async fn dispatch_available(&mut self) -> SuiResult<usize> {
+        let executed_sequence = &self.state.database.perpetual_tables.executed_sequence;
+        // Transactions below the low watermark of the notifier are executed without gaps.
+        let last_safe = self.state.batch_notifier.low_watermark();
+        let executed: Vec<_> = executed_sequence
+            .iter()
+            .skip_to(&self.next_transaction)?
+            .take_while(|(seq, _)| *seq < last_safe)
+            .take(MAX_TRANSACTIONS_PER_ROUND)
+            .collect();
+        for (seq, digests) in &executed {
+            let transaction = self.committed_transaction(*seq, &digests.transaction)?;
+            self.plugins.on_transaction(transaction).await;
+            self.next_transaction = seq + 1;
+        }
+        // Checkpoints are looked for once all the transactions below the watermark were handed.
+        if executed.len() == MAX_TRANSACTIONS_PER_ROUND {
+            return Ok(executed.len());
+        }
+
+        while let Some((summary, contents)) = self.completed_checkpoint(last_safe)? {
+            self.plugins.on_checkpoint(summary, contents).await;
+            self.next_checkpoint += 1;
+        }
+        Ok(executed.len())
+    }
+
+    fn committed_transaction(
+        &self,
+        sequence: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> SuiResult<CommittedTransaction> {
+        let database = &self.state.database;
+        let certificate =
+            database
+                .get_certified_transaction(digest)?
+                .ok_or(SuiError::CertificateNotfound {
+                    certificate_digest: *digest,
+                })?;
+        let effects = database.get_effects(digest)?;
+        let object_changes = read_changed_objects(&effects, |id, version| {
+            database.get_object_by_key(id, version)
+        })?;
+        Ok(CommittedTransaction {
+            sequence,
+            certificate,
+            effects,
+            object_changes,
+        })
+    }
+
+    /// Returns the next checkpoint if all its transactions were executed and handed to the
+    /// plugins, which were handed all the transactions executed below `last_safe`.
+    fn completed_checkpoint(
+        &self,
+        last_safe: TxSequenceNumber,
+    ) -> SuiResult<Option<(CheckpointSummary, CheckpointContents)>> {
+        let (checkpoint, contents) = {
+            let checkpoints = self.state.checkpoints.lock();
+            (
+                checkpoints.tables.checkpoints.get(&self.next_checkpoint)?,
+                checkpoints
+                    .tables
+                    .checkpoint_contents
+                    .get(&self.next_checkpoint)?,
+            )
+        };
+        let (checkpoint, contents) = match (checkpoint, contents) {
+            (Some(checkpoint), Some(contents)) => (checkpoint, contents),
+            _ => return Ok(None),
+        };
+        for digests in contents.iter() {
+            if !self.state.database.effects_exists(&digests.transaction)? {
+                return Ok(None);
+            }
+        }
+        // Effects are written along with the sequence number of the transaction, and the plugins
+        // were handed every transaction executed below `last_safe`, so the executed transactions
+        // not handed yet are the few executed at or above it since.
+        let undelivered: HashSet<_> = self
+            .state
+            .database
+            .perpetual_tables
+            .executed_sequence
+            .iter()
+            .skip_to(&last_safe)?
+            .map(|(_, digests)| digests.transaction)
+            .collect();
+        if contents
+            .iter()
+            .any(|digests| undelivered.contains(&digests.transaction))
+        {
+            return Ok(None);
+        }
+        Ok(Some((checkpoint.summary().clone(), contents)))
+    }
+
+    /// Hands transactions and checkpoints to the plugins as they become available, forever.
+    pub async fn run(mut self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            match self.dispatch_available().await {
+                Ok(handed) if handed == MAX_TRANSACTIONS_PER_ROUND => continue,
+                Ok(_) => (),
+                Err(err) => warn!(
+                    next_transaction = self.next_transaction,
+                    "Failed to hand committed transactions to indexer plugins: {err}"
+                ),
+            }
+            interval.tick().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority::authority_tests::{
+        init_state_with_ids, init_transfer_transaction, send_and_confirm_transaction,
+    };
+    use crate::test_utils::create_fake_transaction;
+    use parking_lot::Mutex;
+    use sui_types::base_types::{ExecutionDigests, ObjectID};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, SignedCheckpointSummary};
+
+    struct RecordingPlugin {
+        name: &'static str,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl RecordingPlugin {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                calls: Mutex::new(vec![]),
+            })
+        }
+
+        fn record(&self, call: String) {
+            self.calls.lock().push(call);
+        }
+    }
+
+    impl IndexerPlugin for RecordingPlugin {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn on_start(
+            &self,
+            first_transaction: TxSequenceNumber,
+            first_checkpoint: CheckpointSequenceNumber,
+        ) -> anyhow::Result<()> {
+            self.record(format!("start {first_transaction} {first_checkpoint}"));
+            Ok(())
+        }
+
+        fn on_transaction(&self, transaction: &CommittedTransaction) -> anyhow::Result<()> {
+            self.record(format!("transaction {}", transaction.sequence));
+            anyhow::ensure!(transaction.sequence != 4, "cannot index it");
+            assert_ne!(transaction.sequence, 5, "cannot index it either");
+            Ok(())
+        }
+
+        fn on_checkpoint(
+            &self,
+            summary: &CheckpointSummary,
+            _contents: &CheckpointContents,
+        ) -> anyhow::Result<()> {
+            self.record(format!("checkpoint {}", summary.sequence_number));
+            Ok(())
+        }
+
+        fn on_stop(&self) {
+            self.record("stop".to_string());
+        }
+    }
+
+    async fn wait_for_calls(plugin: &RecordingPlugin, count: usize) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while plugin.calls.lock().len() < count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    fn transaction(sequence: TxSequenceNumber) -> CommittedTransaction {
+        let certificate = CertifiedTransaction::new(0, create_fake_transaction());
+        CommittedTransaction {
+            sequence,
+            effects: TransactionEffects {
+                transaction_digest: *certificate.digest(),
+                ..Default::default()
+            },
+            certificate,
+            object_changes: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugins_are_handed_events_in_order() {
+        let synchronous = RecordingPlugin::new("synchronous");
+        let channel = RecordingPlugin::new("channel");
+        let mut plugins = IndexerPlugins::new();
+        plugins.register(synchronous.clone(), PluginDelivery::Synchronous);
+        plugins.register(channel.clone(), PluginDelivery::Channel { capacity: 1 });
+
+        let metrics = Arc::new(IndexerPluginMetrics::new_for_tests());
+        let running = plugins.start(3, 1, metrics.clone()).await;
+        running.on_transaction(transaction(3)).await;
+        running.on_transaction(transaction(4)).await;
+        running.on_transaction(transaction(5)).await;
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let summary = CheckpointSummary::new(
+            0,
+            1,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            [0; 32],
+        );
+        running.on_checkpoint(summary, contents).await;
+        drop(running);
+
+        let expected = [
+            "start 3 1",
+            "transaction 3",
+            "transaction 4",
+            "transaction 5",
+            "checkpoint 1",
+            "stop",
+        ];
+        // The plugins stop once they handled all the events.
+        wait_for_calls(&synchronous, expected.len()).await;
+        assert_eq!(*synchronous.calls.lock(), expected);
+        wait_for_calls(&channel, expected.len()).await;
+        assert_eq!(*channel.calls.lock(), expected);
+        // Neither the failure nor the panic of a hook stops the plugin.
+        assert_eq!(metrics.errors.with_label_values(&["synchronous"]).get(), 2);
+        assert_eq!(metrics.errors.with_label_values(&["channel"]).get(), 2);
+        assert_eq!(
+            metrics
+                .last_checkpoint
+                .with_label_values(&["channel"])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runner_hands_checkpoints_once_their_transactions_were_handed() {
+        let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+        let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+        let object_id = ObjectID::random();
+        let gas_id = ObjectID::random();
+        let state = Arc::new(init_state_with_ids([(sender, object_id), (sender, gas_id)]).await);
+
+        let synchronous = RecordingPlugin::new("synchronous");
+        let channel = RecordingPlugin::new("channel");
+        let mut plugins = IndexerPlugins::new();
+        plugins.register(synchronous.clone(), PluginDelivery::Synchronous);
+        plugins.register(channel.clone(), PluginDelivery::Channel { capacity: 1 });
+        let first = state.batch_notifier.low_watermark();
+        let mut runner = IndexerPluginRunner::new(plugins, state.clone(), &Registry::new()).await;
+        assert_eq!(runner.dispatch_available().await.unwrap(), 0);
+
+        let object = state.get_object(&object_id).await.unwrap().unwrap();
+        let gas = state.get_object(&gas_id).await.unwrap().unwrap();
+        let transaction = init_transfer_transaction(
+            sender,
+            &sender_key,
+            recipient,
+            object.compute_object_reference(),
+            gas.compute_object_reference(),
+        );
+        let effects = send_and_confirm_transaction(&state, transaction)
+            .await
+            .unwrap()
+            .signed_effects
+            .unwrap()
+            .effects;
+
+        // The checkpoint of a transaction is handed after it, in the same round, and the
+        // checkpoint of a transaction not executed yet is not handed.
+        let executed = ExecutionDigests::new(effects.transaction_digest, effects.digest());
+        store_checkpoint(&state, 0, [executed]);
+        store_checkpoint(&state, 1, [ExecutionDigests::random()]);
+        assert_eq!(runner.dispatch_available().await.unwrap(), 1);
+        assert_eq!(runner.next_transaction, first + 1);
+        assert_eq!(runner.next_checkpoint, 1);
+        assert_eq!(runner.dispatch_available().await.unwrap(), 0);
+        assert_eq!(runner.next_checkpoint, 1);
+
+        drop(runner);
+        let expected = [
+            format!("start {first} 0"),
+            format!("transaction {first}"),
+            "checkpoint 0".to_string(),
+            "stop".to_string(),
+        ];
+        wait_for_calls(&synchronous, expected.len()).await;
+        assert_eq!(*synchronous.calls.lock(), expected);
+        wait_for_calls(&channel, expected.len()).await;
+        assert_eq!(*channel.calls.lock(), expected);
+    }
+
+    fn store_checkpoint<const N: usize>(
+        state: &AuthorityState,
+        seq: CheckpointSequenceNumber,
+        transactions: [ExecutionDigests; N],
+    ) {
+        let committee = state.clone_committee();
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(transactions.into_iter());
+        let signed = SignedCheckpointSummary::new(
+            committee.epoch,
+            seq,
+            state.name,
+            &*state.secret,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            [0; 32],
+        );
+        let certified = CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap();
+        state
+            .checkpoints
+            .lock()
+            .process_synced_checkpoint_certificate(&certified, &contents, &committee)
+            .unwrap();
+    }
+}
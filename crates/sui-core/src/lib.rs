@@ -1,6 +1,8 @@
 // Copyright (c) 2021, Facebook, Inc. and its affiliates
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+pub mod admission_control;
+pub mod analytics_export;
 pub mod authority;
 pub mod authority_active;
 pub mod authority_aggregator;
@@ -9,14 +11,28 @@ pub mod authority_client;
 pub mod authority_server;
 pub mod checkpoints;
 pub mod consensus_adapter;
+pub mod consensus_monitor;
+pub mod deny_list;
 pub mod epoch;
 pub mod event_handler;
+pub mod executed_certificate_cache;
+pub mod execution_audit;
 pub mod execution_engine;
+pub mod execution_sandbox;
+pub mod gas_price_tracker;
+pub mod gas_profiler;
 pub mod gateway_state;
+pub mod indexer_plugin;
 pub mod metrics;
+pub mod object_change_feed;
+pub mod object_contention;
+pub mod object_downloader;
 pub mod quorum_driver;
+pub mod read_fallback;
 pub mod safe_client;
+pub mod slow_calls;
 pub mod streamer;
+pub mod trace_sampling;
 pub mod transaction_input_checker;
 pub mod transaction_orchestrator;
 pub mod transaction_streamer;
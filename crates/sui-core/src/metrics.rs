@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{Histogram, IntCounter};
+use prometheus::{Histogram, HistogramVec, IntCounter};
 use tokio::time::Instant;
 
 /// Increment an IntGauge metric, and decrement it when the scope ends.
@@ -29,6 +29,31 @@ pub struct TaskUtilizationGuard<'a> {
     start: Instant,
 }
 
+/// Attributes the latency of a transaction to the consecutive stages it goes through, by
+/// observing the time elapsed since the previous stage ended in `histogram`, labelled by stage.
+pub struct StageTimer {
+    histogram: HistogramVec,
+    stage_start: Instant,
+}
+
+impl StageTimer {
+    pub fn new(histogram: HistogramVec) -> Self {
+        Self {
+            histogram,
+            stage_start: Instant::now(),
+        }
+    }
+
+    /// Record the end of `stage`. The next stage starts now.
+    pub fn finish_stage(&mut self, stage: &str) {
+        let now = Instant::now();
+        self.histogram
+            .with_label_values(&[stage])
+            .observe((now - self.stage_start).as_secs_f64());
+        self.stage_start = now;
+    }
+}
+
 pub trait TaskUtilizationExt {
     /// Measures amount of time spent until guard is dropped and increments the counter by duration in mcs
     /// Primary usage for this counter is to measure 'utilization' of the single task
@@ -432,7 +432,10 @@ where
 
                 let res = match res {
                     Err(error) | Ok(Err(error)) => {
-                        if matches!(error, SuiError::ValidatorHaltedAtEpochEnd) {
+                        if matches!(
+                            error,
+                            SuiError::ValidatorHalted { .. } | SuiError::ValidatorShuttingDown
+                        ) {
                             // This is not a real error.
                             debug!(?tx_digest, "process_digest failed: {}", error);
                         } else {
@@ -713,6 +716,19 @@ where
             })
             .tap_err(|e| error!(?digest, "error: {}", e))?;
 
+        // Read the inputs of the cert while its parents execute. The prefetched objects are
+        // dropped along with the task once the cert is executed or given up.
+        let database = self.state().database.clone();
+        let _prefetch = database.prefetches_input_objects().then(|| {
+            let cert = cert.clone();
+            let shared_objects = effects.effects.shared_objects.clone();
+            tokio::task::spawn_blocking(move || {
+                database
+                    .prefetch_input_objects(&cert, &shared_objects)
+                    .tap_err(|e| debug!(tx_digest = ?cert.digest(), "prefetch failed: {}", e))
+            })
+        });
+
         self.process_parents(permit, epoch_id, &digests.transaction, &effects)
             .await?;
 
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::ready;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use sui_types::error::SuiResult;
+use sui_types::filter::{Filter, ObjectChangeFilter};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object_change::ObjectChange;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::authority::AuthorityState;
+
+/// How long a subscription waits for the notification of a new checkpoint before looking for
+/// it in the store, as checkpoints made by this node are not notified.
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Streams the object changes matching `filter` of the checkpoints from `start`, inclusive, or
+/// from the next checkpoint if None, as this node executes them. Changes are streamed in the
+/// order of the checkpoints, so a subscriber resumes from the checkpoint of the last change it
+/// processed, skipping the changes of that checkpoint it already saw. The stream ends after an
+/// error.
+pub fn subscribe(
+    state: Arc<AuthorityState>,
+    filter: ObjectChangeFilter,
+    start: Option<CheckpointSequenceNumber>,
+) -> impl Stream<Item = SuiResult<ObjectChange>> + Send {
+    let notifications = state.checkpoints.lock().subscribe_to_checkpoints();
+    let next = start.unwrap_or_else(|| state.next_stored_checkpoint());
+    stream::unfold(
+        (state, Some(next), notifications),
+        |(state, next, mut notifications)| async move {
+            let seq = next?;
+            loop {
+                match state.get_checkpoint_object_changes(seq) {
+                    Ok(Some(changes)) => {
+                        return Some((Ok(changes), (state, Some(seq + 1), notifications)))
+                    }
+                    Ok(None) => (),
+                    Err(e) => return Some((Err(e), (state, None, notifications))),
+                }
+                // Lagging behind notifications is fine, as checkpoints are read from the store.
+                if let Ok(Err(RecvError::Closed)) =
+                    tokio::time::timeout(CHECKPOINT_POLL_INTERVAL, notifications.recv()).await
+                {
+                    tokio::time::sleep(CHECKPOINT_POLL_INTERVAL).await;
+                }
+            }
+        },
+    )
+    .flat_map(|changes| {
+        stream::iter(match changes {
+            Ok(changes) => changes.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        })
+    })
+    .filter(move |change| {
+        ready(
+            change
+                .as_ref()
+                .map_or(true, |change| filter.matches(change)),
+        )
+    })
+}
@@ -0,0 +1,348 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use prometheus::{register_int_gauge_vec_with_registry, IntGaugeVec, Registry};
+use std::collections::HashMap;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+
+/// Number of objects whose counts are kept for each kind of contention.
+pub const DEFAULT_TRACKED_OBJECTS: usize = 1000;
+
+/// Number of objects exported in the metrics for each kind of contention.
+pub const DEFAULT_TOP_OBJECTS: usize = 20;
+
+/// Approximate counts of the most frequent objects in a stream of events, in bounded memory.
+///
+/// Once `capacity` objects are tracked, an event for a new object replaces the least counted
+/// object, whose count the new one inherits (the Space-Saving algorithm). Objects seen more
+/// often than the least counted one are never replaced, and counts are over-estimated by at most
+/// the count of the object replaced.
+struct TopObjects {
+    capacity: usize,
+    counts: HashMap<ObjectID, u64>,
+}
+
+impl TopObjects {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counts: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, object_id: ObjectID) {
+        if let Some(count) = self.counts.get_mut(&object_id) {
+            *count += 1;
+            return;
+        }
+        let mut count = 1;
+        if self.counts.len() >= self.capacity {
+            let min = self
+                .counts
+                .iter()
+                .min_by_key(|(_, count)| **count)
+                .map(|(id, count)| (*id, *count));
+            if let Some((min_id, min_count)) = min {
+                self.counts.remove(&min_id);
+                count += min_count;
+            }
+        }
+        self.counts.insert(object_id, count);
+    }
+
+    fn top(&self, count: usize) -> Vec<(ObjectID, u64)> {
+        top(&self.counts, count)
+    }
+
+    fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+fn top(counts: &HashMap<ObjectID, u64>, count: usize) -> Vec<(ObjectID, u64)> {
+    let order =
+        |a: &(ObjectID, u64), b: &(ObjectID, u64)| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0));
+    let mut top: Vec<_> = counts.iter().map(|(id, count)| (*id, *count)).collect();
+    // Only sort the objects returned.
+    if count < top.len() {
+        top.select_nth_unstable_by(count, order);
+        top.truncate(count);
+    }
+    top.sort_by(order);
+    top
+}
+
+struct ContentionState {
+    lock_conflicts: TopObjects,
+    shared_object_transactions: TopObjects,
+    /// Shared objects of the transactions sequenced by consensus and not executed yet.
+    queued_transactions: HashMap<TransactionDigest, Vec<ObjectID>>,
+    /// Number of queued transactions by shared object.
+    queue_depths: HashMap<ObjectID, u64>,
+}
+
+/// Objects contended by the most transactions, by kind of contention.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentionReport {
+    /// Transactions which failed to lock an owned object already locked by another transaction.
+    pub lock_conflicts: Vec<(ObjectID, u64)>,
+    /// Transactions sequenced by consensus which use a shared object.
+    pub shared_object_transactions: Vec<(ObjectID, u64)>,
+    /// Transactions sequenced by consensus and waiting to be executed, by shared object.
+    pub queue_depths: Vec<(ObjectID, u64)>,
+}
+
+/// ObjectContentionTracker finds the objects which limit the throughput of the transactions
+/// using them: owned objects which several transactions try to lock at once, and shared objects
+/// used by many transactions, which are executed one at a time. Only the most contended objects
+/// are tracked and exported in the metrics, to bound memory and the cardinality of the metrics.
+/// Recording events only updates the counts: the metrics are exported periodically by
+/// `export_metrics`, off the execution path.
+pub struct ObjectContentionTracker {
+    state: Mutex<ContentionState>,
+    metrics: ObjectContentionMetrics,
+}
+
+impl ObjectContentionTracker {
+    pub fn new(tracked_objects: usize, registry: &Registry) -> Self {
+        Self {
+            state: Mutex::new(ContentionState {
+                lock_conflicts: TopObjects::new(tracked_objects),
+                shared_object_transactions: TopObjects::new(tracked_objects),
+                queued_transactions: HashMap::new(),
+                queue_depths: HashMap::new(),
+            }),
+            metrics: ObjectContentionMetrics::new(registry),
+        }
+    }
+
+    /// Record that a transaction failed to lock `object_id`, locked by another transaction.
+    pub fn record_lock_conflict(&self, object_id: ObjectID) {
+        self.state.lock().lock_conflicts.add(object_id);
+    }
+
+    /// Record that the transaction `digest`, using the shared objects `object_ids`, was
+    /// sequenced by consensus and waits to be executed.
+    pub fn record_sequenced(
+        &self,
+        digest: TransactionDigest,
+        object_ids: impl IntoIterator<Item = ObjectID>,
+    ) {
+        let object_ids: Vec<_> = object_ids.into_iter().collect();
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        if object_ids.is_empty() || state.queued_transactions.contains_key(&digest) {
+            return;
+        }
+        for object_id in &object_ids {
+            state.shared_object_transactions.add(*object_id);
+            *state.queue_depths.entry(*object_id).or_default() += 1;
+        }
+        state.queued_transactions.insert(digest, object_ids);
+    }
+
+    /// Record that the transaction `digest` was executed. Transactions which were not recorded
+    /// as sequenced, e.g. owned object transactions, are ignored.
+    pub fn record_executed(&self, digest: &TransactionDigest) {
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        let object_ids = match state.queued_transactions.remove(digest) {
+            Some(object_ids) => object_ids,
+            None => return,
+        };
+        for object_id in object_ids {
+            if let Some(depth) = state.queue_depths.get_mut(&object_id) {
+                *depth -= 1;
+                if *depth == 0 {
+                    state.queue_depths.remove(&object_id);
+                }
+            }
+        }
+    }
+
+    /// The transactions recorded as sequenced and not executed yet.
+    pub fn queued_transactions(&self) -> Vec<TransactionDigest> {
+        self.state
+            .lock()
+            .queued_transactions
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Return the `count` most contended objects of each kind.
+    pub fn report(&self, count: usize) -> ContentionReport {
+        let state = self.state.lock();
+        ContentionReport {
+            lock_conflicts: state.lock_conflicts.top(count),
+            shared_object_transactions: state.shared_object_transactions.top(count),
+            queue_depths: top(&state.queue_depths, count),
+        }
+    }
+
+    /// Discard the counts of lock conflicts and shared object transactions. Queued transactions
+    /// are kept, as they are yet to be executed.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        state.lock_conflicts.clear();
+        state.shared_object_transactions.clear();
+    }
+
+    /// Export the most contended objects of each kind to the metrics.
+    pub fn export_metrics(&self) {
+        let report = self.report(DEFAULT_TOP_OBJECTS);
+        self.metrics.lock_conflicts.export(report.lock_conflicts);
+        self.metrics
+            .shared_object_transactions
+            .export(report.shared_object_transactions);
+        self.metrics.queue_depths.export(report.queue_depths);
+    }
+}
+
+/// Gauges of the counts of the objects last exported.
+struct TopObjectGauges {
+    gauges: IntGaugeVec,
+    exported: Mutex<Vec<ObjectID>>,
+}
+
+impl TopObjectGauges {
+    fn new(name: &str, help: &str, registry: &Registry) -> Self {
+        Self {
+            gauges: register_int_gauge_vec_with_registry!(name, help, &["object_id"], registry)
+                .unwrap(),
+            exported: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set the gauges of the objects of `top`, and remove those of the objects exported before
+    /// and not in `top` anymore. Gauges are updated in place, so scrapes never see them empty.
+    fn export(&self, top: Vec<(ObjectID, u64)>) {
+        let mut exported = self.exported.lock();
+        for (object_id, count) in &top {
+            self.gauges
+                .with_label_values(&[&object_id.to_string()])
+                .set(*count as i64);
+        }
+        for object_id in exported.iter() {
+            if !top.iter().any(|(id, _)| id == object_id) {
+                // The gauge exists, as it was exported before.
+                let _ = self.gauges.remove_label_values(&[&object_id.to_string()]);
+            }
+        }
+        *exported = top.into_iter().map(|(object_id, _)| object_id).collect();
+    }
+}
+
+struct ObjectContentionMetrics {
+    lock_conflicts: TopObjectGauges,
+    shared_object_transactions: TopObjectGauges,
+    queue_depths: TopObjectGauges,
+}
+
+impl ObjectContentionMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            lock_conflicts: TopObjectGauges::new(
+                "object_lock_conflicts",
+                "Number of transactions which failed to lock the most contended owned objects",
+                registry,
+            ),
+            shared_object_transactions: TopObjectGauges::new(
+                "shared_object_transactions",
+                "Number of transactions sequenced by consensus using the hottest shared objects",
+                registry,
+            ),
+            queue_depths: TopObjectGauges::new(
+                "shared_object_queue_depth",
+                "Number of sequenced transactions waiting for execution on the most contended shared objects",
+                registry,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::core::Collector;
+
+    #[test]
+    fn test_top_objects_keeps_frequent_objects() {
+        let frequent = ObjectID::random();
+        let mut top = TopObjects::new(3);
+        for _ in 0..10 {
+            top.add(frequent);
+            // Each other object is seen once, and replaces another rare one.
+            top.add(ObjectID::random());
+        }
+        assert_eq!(top.counts.len(), 3);
+        let first = top.top(1);
+        assert_eq!(first[0].0, frequent);
+        assert!(first[0].1 >= 10);
+    }
+
+    #[test]
+    fn test_queue_depths() {
+        let tracker = ObjectContentionTracker::new(DEFAULT_TRACKED_OBJECTS, &Registry::new());
+        let (hot, cold) = (ObjectID::random(), ObjectID::random());
+        let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+
+        tracker.record_sequenced(digests[0], [hot, cold]);
+        tracker.record_sequenced(digests[1], [hot]);
+        tracker.record_sequenced(digests[2], [hot]);
+        // Transactions sequenced again, e.g. after a restart, are only counted once.
+        tracker.record_sequenced(digests[2], [hot]);
+        tracker.record_lock_conflict(cold);
+
+        let report = tracker.report(10);
+        assert_eq!(report.queue_depths, vec![(hot, 3), (cold, 1)]);
+        assert_eq!(report.shared_object_transactions[0], (hot, 3));
+        assert_eq!(report.lock_conflicts, vec![(cold, 1)]);
+        assert_eq!(tracker.queued_transactions().len(), 3);
+
+        tracker.record_executed(&digests[0]);
+        tracker.record_executed(&digests[1]);
+        // Transactions not sequenced are ignored.
+        tracker.record_executed(&TransactionDigest::random());
+        let report = tracker.report(10);
+        assert_eq!(report.queue_depths, vec![(hot, 1)]);
+        assert_eq!(report.shared_object_transactions[0], (hot, 3));
+
+        tracker.reset();
+        let report = tracker.report(10);
+        assert!(report.lock_conflicts.is_empty());
+        assert!(report.shared_object_transactions.is_empty());
+        assert_eq!(report.queue_depths, vec![(hot, 1)]);
+    }
+
+    #[test]
+    fn test_export_metrics() {
+        let tracker = ObjectContentionTracker::new(DEFAULT_TRACKED_OBJECTS, &Registry::new());
+        let (hot, cold) = (ObjectID::random(), ObjectID::random());
+        let (first, second) = (TransactionDigest::random(), TransactionDigest::random());
+        let depth = |object_id: ObjectID| {
+            tracker
+                .metrics
+                .queue_depths
+                .gauges
+                .with_label_values(&[&object_id.to_string()])
+                .get()
+        };
+
+        // Recording only updates the metrics once exported.
+        tracker.record_sequenced(first, [hot, cold]);
+        tracker.record_sequenced(second, [hot]);
+        assert_eq!(depth(hot), 0);
+        tracker.export_metrics();
+        assert_eq!((depth(hot), depth(cold)), (2, 1));
+
+        // The gauges of objects no longer contended are removed.
+        tracker.record_executed(&first);
+        tracker.export_metrics();
+        assert_eq!(*tracker.metrics.queue_depths.exported.lock(), vec![hot]);
+        let gauges = tracker.metrics.queue_depths.gauges.collect();
+        assert_eq!(gauges[0].get_metric().len(), 1);
+        assert_eq!(depth(hot), 1);
+    }
+}
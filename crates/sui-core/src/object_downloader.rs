@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority_client::AuthorityAPI;
+use crate::safe_client::SafeClient;
+use parking_lot::Mutex;
+use prometheus::IntCounter;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_types::base_types::{AuthorityName, ObjectRef};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{ObjectContentOptions, ObjectInfoRequest, ObjectInfoResponse};
+use sui_types::object::Object;
+use tokio::sync::{broadcast, Semaphore};
+use tracing::{debug, trace};
+
+/// Default number of object requests sent concurrently to each authority.
+pub const MAX_CONCURRENT_DOWNLOADS_PER_AUTHORITY: usize = 16;
+
+/// Number of object references whose holders are remembered. Hints are dropped once the
+/// object is downloaded, so this only bounds the hints recorded for objects never downloaded.
+const MAX_HOLDER_HINTS: usize = 100_000;
+
+/// Downloads objects from the authorities, given their references.
+///
+/// Concurrent downloads of the same reference, e.g. by syncs of several accounts, share a
+/// single download. Each object is requested from one authority at a time, starting with the
+/// authorities known to hold its version, and the number of concurrent requests to each
+/// authority is limited, so a large sync does not hit every authority for every object.
+///
+/// Object references do not depend on the epoch, so the downloader is kept across re-config.
+pub struct ObjectDownloader {
+    max_concurrent_per_authority: usize,
+    permits: Mutex<HashMap<AuthorityName, Arc<Semaphore>>>,
+    pending: Mutex<HashMap<ObjectRef, broadcast::Sender<SuiResult<Object>>>>,
+    holders: Mutex<HashMap<ObjectRef, BTreeSet<AuthorityName>>>,
+    total_downloads: IntCounter,
+    total_deduped_downloads: IntCounter,
+}
+
+impl ObjectDownloader {
+    pub fn new(
+        max_concurrent_per_authority: usize,
+        total_downloads: IntCounter,
+        total_deduped_downloads: IntCounter,
+    ) -> Self {
+        Self {
+            max_concurrent_per_authority,
+            permits: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
+            holders: Mutex::new(HashMap::new()),
+            total_downloads,
+            total_deduped_downloads,
+        }
+    }
+
+    /// Records that `authorities` reported holding `object_ref`, so that they are asked for it
+    /// first.
+    pub fn record_holders(
+        &self,
+        object_ref: ObjectRef,
+        authorities: impl IntoIterator<Item = AuthorityName>,
+    ) {
+        let mut holders = self.holders.lock();
+        if holders.len() >= MAX_HOLDER_HINTS && !holders.contains_key(&object_ref) {
+            holders.clear();
+        }
+        holders.entry(object_ref).or_default().extend(authorities);
+    }
+
+    /// Downloads the object of `object_ref` from one of `authorities`, tried in order after the
+    /// known holders of the object. The object must match the digest of the reference, unless
+    /// it is shared: shared objects can never be kept up-to-date, so any version is returned.
+    pub async fn download<A>(
+        self: &Arc<Self>,
+        object_ref: ObjectRef,
+        authorities: Arc<Vec<(AuthorityName, SafeClient<A>)>>,
+        timeout: Duration,
+    ) -> SuiResult<Object>
+    where
+        A: AuthorityAPI + Send + Sync + 'static + Clone,
+    {
+        let mut receiver = {
+            let mut pending = self.pending.lock();
+            match pending.get(&object_ref) {
+                Some(sender) => {
+                    self.total_deduped_downloads.inc();
+                    sender.subscribe()
+                }
+                None => {
+                    let (sender, receiver) = broadcast::channel(1);
+                    pending.insert(object_ref, sender);
+                    self.total_downloads.inc();
+                    // The download runs in its own task, so that it completes for the other
+                    // callers even if this one is dropped.
+                    let downloader = self.clone();
+                    tokio::spawn(async move {
+                        let result = downloader
+                            .download_impl(object_ref, &authorities, timeout)
+                            .await;
+                        if let Some(sender) = downloader.pending.lock().remove(&object_ref) {
+                            let _ = sender.send(result);
+                        }
+                    });
+                    receiver
+                }
+            }
+        };
+        receiver
+            .recv()
+            .await
+            .unwrap_or_else(|_| Err(fetch_failed(object_ref, "Download task failed")))
+    }
+
+    async fn download_impl<A>(
+        &self,
+        object_ref: ObjectRef,
+        authorities: &[(AuthorityName, SafeClient<A>)],
+        timeout: Duration,
+    ) -> SuiResult<Object>
+    where
+        A: AuthorityAPI + Send + Sync + 'static + Clone,
+    {
+        let holders = self.holders.lock().remove(&object_ref).unwrap_or_default();
+        let (preferred, others): (Vec<_>, Vec<_>) = authorities
+            .iter()
+            .partition(|(name, _)| holders.contains(name));
+
+        let request = ObjectInfoRequest::latest_object_content_request(
+            object_ref.0,
+            ObjectContentOptions::WithOwnerOnly,
+        );
+        for (name, client) in preferred.into_iter().chain(others) {
+            let permits = self.permits(name);
+            let _permit = permits.acquire().await;
+            let response = tokio::time::timeout(
+                timeout,
+                client.handle_object_info_request(request.clone(), false),
+            )
+            .await;
+            match response {
+                Ok(Ok(ObjectInfoResponse {
+                    object_and_lock: Some(o),
+                    ..
+                })) if o.object.is_shared() || o.object.digest() == object_ref.2 => {
+                    return Ok(o.object);
+                }
+                Ok(Ok(_)) => {
+                    trace!(
+                        ?name,
+                        ?object_ref,
+                        "Authority does not have the object version"
+                    )
+                }
+                Ok(Err(err)) => debug!(?name, ?object_ref, ?err, "Failed to fetch object"),
+                Err(_) => debug!(?name, ?object_ref, "Timed out fetching object"),
+            }
+        }
+        Err(fetch_failed(
+            object_ref,
+            "No authority returned the correct object",
+        ))
+    }
+
+    fn permits(&self, name: &AuthorityName) -> Arc<Semaphore> {
+        self.permits
+            .lock()
+            .entry(*name)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max_concurrent_per_authority)))
+            .clone()
+    }
+}
+
+fn fetch_failed(object_ref: ObjectRef, err: &str) -> SuiError {
+    SuiError::ObjectFetchFailed {
+        object_id: object_ref.0,
+        err: err.to_string(),
+    }
+}
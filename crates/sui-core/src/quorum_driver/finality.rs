@@ -0,0 +1,323 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use prometheus::{HistogramVec, IntCounter};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant, SystemTime};
+use sui_types::base_types::{ExecutionDigests, TransactionDigest};
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Transactions waiting for checkpoint inclusion are dropped after this, so that transactions
+/// never checkpointed, e.g. after a reconfiguration, do not accumulate.
+const MAX_CHECKPOINT_WAIT: Duration = Duration::from_secs(600);
+
+/// Maximum number of transactions waiting for checkpoint inclusion at once. Transactions reaching
+/// a quorum of effects beyond it are counted as untracked.
+const MAX_PENDING_CHECKPOINT_INCLUSION: usize = 10_000;
+
+/// Number of waiting transactions looked up at once by `check_checkpoints`.
+const CHECKPOINT_LOOKUP_BATCH_SIZE: usize = 1_000;
+
+/// A stage of the finality of a transaction driven by the quorum driver, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FinalityStage {
+    /// Collecting the signatures of a quorum of validators into a certificate.
+    SigningQuorum,
+    /// Sending the certificate until the first validator returns effects.
+    CertBroadcast,
+    /// From the first effects to a quorum of effects.
+    EffectsQuorum,
+    /// From the quorum of effects to the inclusion of the transaction in a checkpoint.
+    CheckpointInclusion,
+}
+
+impl FinalityStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FinalityStage::SigningQuorum => "signing_quorum",
+            FinalityStage::CertBroadcast => "cert_broadcast",
+            FinalityStage::EffectsQuorum => "effects_quorum",
+            FinalityStage::CheckpointInclusion => "checkpoint_inclusion",
+        }
+    }
+}
+
+impl Display for FinalityStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The time spent in each stage of the finality of a transaction, for the stages reached so far.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalityTrace {
+    pub tx_digest: TransactionDigest,
+    /// When the first stage finished.
+    pub recorded_at: SystemTime,
+    pub stages: Vec<(FinalityStage, Duration)>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+#[derive(Default)]
+struct Traces {
+    capacity: usize,
+    traces: HashMap<TransactionDigest, FinalityTrace>,
+    // Traced transactions, oldest first.
+    order: VecDeque<TransactionDigest>,
+}
+
+/// FinalityTracker records how long each stage of finality takes into a histogram by stage,
+/// and, if enabled, keeps per transaction traces of the most recent transactions, to find
+/// where the latency of finality goes.
+pub struct FinalityTracker {
+    stage_latency: HistogramVec,
+    untracked_checkpoint_inclusion: IntCounter,
+    traces: Mutex<Traces>,
+    // Effects quorum time of the transactions waiting to be checkpointed.
+    pending_checkpoint: Mutex<HashMap<ExecutionDigests, Instant>>,
+}
+
+impl FinalityTracker {
+    pub fn new(stage_latency: HistogramVec, untracked_checkpoint_inclusion: IntCounter) -> Self {
+        Self {
+            stage_latency,
+            untracked_checkpoint_inclusion,
+            traces: Mutex::new(Traces::default()),
+            pending_checkpoint: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of transactions whose traces are kept. 0, the default, disables traces.
+    pub fn trace_capacity(&self) -> usize {
+        self.traces.lock().capacity
+    }
+
+    pub fn set_trace_capacity(&self, capacity: usize) {
+        let mut traces = self.traces.lock();
+        traces.capacity = capacity;
+        while traces.order.len() > capacity {
+            if let Some(oldest) = traces.order.pop_front() {
+                traces.traces.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn record(&self, tx_digest: TransactionDigest, stage: FinalityStage, latency: Duration) {
+        self.stage_latency
+            .with_label_values(&[stage.as_str()])
+            .observe(latency.as_secs_f64());
+        self.update_trace(tx_digest, |trace| trace.stages.push((stage, latency)));
+    }
+
+    fn update_trace(&self, tx_digest: TransactionDigest, update: impl FnOnce(&mut FinalityTrace)) {
+        let mut traces = self.traces.lock();
+        if traces.capacity == 0 {
+            return;
+        }
+        if !traces.traces.contains_key(&tx_digest) {
+            if traces.order.len() >= traces.capacity {
+                if let Some(oldest) = traces.order.pop_front() {
+                    traces.traces.remove(&oldest);
+                }
+            }
+            traces.order.push_back(tx_digest);
+        }
+        let trace = traces
+            .traces
+            .entry(tx_digest)
+            .or_insert_with(|| FinalityTrace {
+                tx_digest,
+                recorded_at: SystemTime::now(),
+                stages: vec![],
+                checkpoint: None,
+            });
+        update(trace);
+    }
+
+    pub fn get_trace(&self, tx_digest: &TransactionDigest) -> Option<FinalityTrace> {
+        self.traces.lock().traces.get(tx_digest).cloned()
+    }
+
+    /// The kept traces, oldest first.
+    pub fn traces(&self) -> Vec<FinalityTrace> {
+        let traces = self.traces.lock();
+        traces
+            .order
+            .iter()
+            .filter_map(|digest| traces.traces.get(digest).cloned())
+            .collect()
+    }
+
+    /// Starts waiting for the inclusion in a checkpoint of a transaction which just reached a
+    /// quorum of effects.
+    pub fn await_checkpoint(&self, digests: ExecutionDigests) {
+        let mut pending = self.pending_checkpoint.lock();
+        if pending.len() < MAX_PENDING_CHECKPOINT_INCLUSION {
+            pending.insert(digests, Instant::now());
+        } else {
+            self.untracked_checkpoint_inclusion.inc();
+        }
+    }
+
+    pub fn num_pending_checkpoint(&self) -> usize {
+        self.pending_checkpoint.lock().len()
+    }
+
+    /// Records the checkpoint inclusion of the waiting transactions for which `get_checkpoints`
+    /// returns a checkpoint. `get_checkpoints` is called with batches of waiting transactions and
+    /// returns their checkpoints in the same order.
+    pub fn check_checkpoints<F>(&self, get_checkpoints: F) -> SuiResult
+    where
+        F: Fn(&[ExecutionDigests]) -> SuiResult<Vec<Option<CheckpointSequenceNumber>>>,
+    {
+        let pending: Vec<_> = self
+            .pending_checkpoint
+            .lock()
+            .iter()
+            .map(|(digests, effects_at)| (*digests, *effects_at))
+            .collect();
+        for batch in pending.chunks(CHECKPOINT_LOOKUP_BATCH_SIZE) {
+            let digests: Vec<_> = batch.iter().map(|(digests, _)| *digests).collect();
+            let checkpoints = get_checkpoints(&digests)?;
+            let mut included = vec![];
+            {
+                let mut pending = self.pending_checkpoint.lock();
+                for ((digests, effects_at), checkpoint) in batch.iter().zip(checkpoints) {
+                    if checkpoint.is_none() && effects_at.elapsed() < MAX_CHECKPOINT_WAIT {
+                        continue;
+                    }
+                    pending.remove(digests);
+                    if let Some(checkpoint) = checkpoint {
+                        included.push((digests.transaction, effects_at.elapsed(), checkpoint));
+                    }
+                }
+            }
+            for (tx_digest, latency, checkpoint) in included {
+                self.record(tx_digest, FinalityStage::CheckpointInclusion, latency);
+                self.update_trace(tx_digest, |trace| trace.checkpoint = Some(checkpoint));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{
+        register_histogram_vec_with_registry, register_int_counter_with_registry, Registry,
+    };
+
+    fn tracker() -> FinalityTracker {
+        let registry = Registry::new();
+        FinalityTracker::new(
+            register_histogram_vec_with_registry!(
+                "finality_stage_latency_sec",
+                "help",
+                &["stage"],
+                &registry,
+            )
+            .unwrap(),
+            register_int_counter_with_registry!(
+                "finality_untracked_checkpoint_inclusion",
+                "help",
+                &registry,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_traces() {
+        let tracker = tracker();
+        let first = TransactionDigest::random();
+        let second = TransactionDigest::random();
+        // Traces are disabled by default.
+        tracker.record(
+            first,
+            FinalityStage::SigningQuorum,
+            Duration::from_millis(3),
+        );
+        assert_eq!(tracker.get_trace(&first), None);
+
+        tracker.set_trace_capacity(1);
+        tracker.record(
+            first,
+            FinalityStage::SigningQuorum,
+            Duration::from_millis(3),
+        );
+        tracker.record(
+            first,
+            FinalityStage::CertBroadcast,
+            Duration::from_millis(4),
+        );
+        assert_eq!(
+            tracker.get_trace(&first).unwrap().stages,
+            vec![
+                (FinalityStage::SigningQuorum, Duration::from_millis(3)),
+                (FinalityStage::CertBroadcast, Duration::from_millis(4)),
+            ]
+        );
+        // The oldest trace is dropped.
+        tracker.record(
+            second,
+            FinalityStage::SigningQuorum,
+            Duration::from_millis(5),
+        );
+        assert_eq!(tracker.get_trace(&first), None);
+        assert_eq!(tracker.traces().len(), 1);
+    }
+
+    #[test]
+    fn test_checkpoint_inclusion() {
+        let tracker = tracker();
+        tracker.set_trace_capacity(10);
+        let checkpointed = ExecutionDigests::random();
+        let pending = ExecutionDigests::random();
+        tracker.await_checkpoint(checkpointed);
+        tracker.await_checkpoint(pending);
+        tracker
+            .check_checkpoints(|digests| {
+                Ok(digests
+                    .iter()
+                    .map(|digests| (digests == &checkpointed).then_some(7))
+                    .collect())
+            })
+            .unwrap();
+        assert_eq!(tracker.num_pending_checkpoint(), 1);
+        let trace = tracker.get_trace(&checkpointed.transaction).unwrap();
+        assert_eq!(trace.checkpoint, Some(7));
+        assert_eq!(trace.stages[0].0, FinalityStage::CheckpointInclusion);
+    }
+
+    #[test]
+    fn test_untracked_checkpoint_inclusion() {
+        let tracker = tracker();
+        for _ in 0..MAX_PENDING_CHECKPOINT_INCLUSION + 2 {
+            tracker.await_checkpoint(ExecutionDigests::random());
+        }
+        assert_eq!(
+            tracker.num_pending_checkpoint(),
+            MAX_PENDING_CHECKPOINT_INCLUSION
+        );
+        assert_eq!(tracker.untracked_checkpoint_inclusion.get(), 2);
+
+        // The waiting transactions are looked up in batches.
+        let batches = std::sync::atomic::AtomicUsize::new(0);
+        tracker
+            .check_checkpoints(|digests| {
+                assert!(digests.len() <= CHECKPOINT_LOOKUP_BATCH_SIZE);
+                batches.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(vec![Some(1); digests.len()])
+            })
+            .unwrap();
+        assert_eq!(
+            batches.into_inner(),
+            MAX_PENDING_CHECKPOINT_INCLUSION / CHECKPOINT_LOOKUP_BATCH_SIZE
+        );
+        assert_eq!(tracker.num_pending_checkpoint(), 0);
+    }
+}
@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, Histogram, HistogramVec,
+    IntCounter, IntGauge, Registry,
 };
 
 #[derive(Clone, Debug)]
@@ -19,8 +20,17 @@ pub struct QuorumDriverMetrics {
     pub(crate) latency_sec_immediate_return: Histogram,
     pub(crate) latency_sec_wait_for_tx_cert: Histogram,
     pub(crate) latency_sec_wait_for_effects_cert: Histogram,
+    pub(crate) shared_obj_tx_stage_latency_sec: HistogramVec,
+    pub(crate) finality_stage_latency_sec: HistogramVec,
+    pub(crate) finality_untracked_checkpoint_inclusion: IntCounter,
 
     pub(crate) current_requests_in_flight: IntGauge,
+
+    pub(crate) total_retried_certificates: IntCounter,
+
+    pub(crate) current_buffered_requests: IntGauge,
+    pub(crate) total_buffered_requests: IntCounter,
+    pub(crate) total_buffered_requests_expired: IntCounter,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -87,12 +97,64 @@ impl QuorumDriverMetrics {
                 registry,
             )
             .unwrap(),
+            shared_obj_tx_stage_latency_sec: register_histogram_vec_with_registry!(
+                "quorum_driver_shared_obj_tx_stage_latency_sec",
+                "Latency of each stage of executing a shared object transaction, in sec: \
+                 tx_cert (collecting the certificate) and effects_cert (consensus, execution \
+                 and collecting the effects certificate)",
+                &["stage"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            finality_stage_latency_sec: register_histogram_vec_with_registry!(
+                "quorum_driver_finality_stage_latency_sec",
+                "Latency of each stage of the finality of a transaction, in sec: signing_quorum, \
+                 cert_broadcast, effects_quorum and checkpoint_inclusion",
+                &["stage"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            finality_untracked_checkpoint_inclusion: register_int_counter_with_registry!(
+                "quorum_driver_finality_untracked_checkpoint_inclusion",
+                "Number of transactions whose checkpoint inclusion latency is not recorded because \
+                 too many transactions were already waiting for checkpoint inclusion",
+                registry,
+            )
+            .unwrap(),
             current_requests_in_flight: register_int_gauge_with_registry!(
                 "current_requests_in_flight",
                 "Current number of requests being processed in QuorumDriver",
                 registry,
             )
             .unwrap(),
+            total_retried_certificates: register_int_counter_with_registry!(
+                "quorum_driver_total_retried_certificates",
+                "Total number of certificates processed again after a retryable error",
+                registry,
+            )
+            .unwrap(),
+            current_buffered_requests: register_int_gauge_with_registry!(
+                "quorum_driver_current_buffered_requests",
+                "Current number of requests waiting for the committee of the next epoch, after \
+                 validators halted to reconfigure",
+                registry,
+            )
+            .unwrap(),
+            total_buffered_requests: register_int_counter_with_registry!(
+                "quorum_driver_total_buffered_requests",
+                "Total number of requests buffered until the committee of the next epoch",
+                registry,
+            )
+            .unwrap(),
+            total_buffered_requests_expired: register_int_counter_with_registry!(
+                "quorum_driver_total_buffered_requests_expired",
+                "Total number of buffered requests failed because the committee of the next \
+                 epoch was not installed in time",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -1,14 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod finality;
 mod metrics;
+pub use finality::*;
 pub use metrics::*;
 
 use arc_swap::ArcSwap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_types::base_types::ExecutionDigests;
 use sui_types::committee::{Committee, EpochId};
 
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::{watch, Semaphore};
 use tokio::task::JoinHandle;
 use tracing::Instrument;
 use tracing::{debug, warn};
@@ -22,6 +27,20 @@ use sui_types::messages::{
 };
 
 const TASK_QUEUE_SIZE: usize = 5000;
+/// Number of times a certificate is processed again when it fails with a retryable error, e.g.
+/// while validators change epoch.
+const MAX_CERTIFICATE_RETRIES: u32 = 3;
+const CERTIFICATE_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// How long a request rejected by validators halted to reconfigure waits at most for the
+/// committee of the next epoch, before failing with the rejection.
+const RECONFIGURATION_BUFFER_TIMEOUT: Duration = Duration::from_secs(30);
+/// Number of requests waiting for the committee of the next epoch at once, beyond which the
+/// requests rejected by halted validators fail right away.
+const MAX_BUFFERED_REQUESTS: usize = TASK_QUEUE_SIZE;
+/// Number of times a request is sent again after being rejected by halted validators, since
+/// validators may still be halted for a while once the next committee is installed.
+const MAX_RECONFIGURATION_RETRIES: u32 = 5;
+const RECONFIGURATION_RETRY_DELAY: Duration = Duration::from_millis(200);
 
 pub enum QuorumTask {
     ProcessTransaction(Transaction),
@@ -37,6 +56,7 @@ pub struct QuorumDriverHandler<A> {
     effects_subscriber:
         tokio::sync::broadcast::Receiver<(CertifiedTransaction, CertifiedTransactionEffects)>,
     quorum_driver_metrics: Arc<QuorumDriverMetrics>,
+    finality_tracker: Arc<FinalityTracker>,
 }
 
 /// The core data structure of the QuorumDriver.
@@ -46,10 +66,15 @@ pub struct QuorumDriverHandler<A> {
 /// committee, or to subscribe effects generated from the QuorumDriver.
 pub struct QuorumDriver<A> {
     validators: ArcSwap<AuthorityAggregator<A>>,
+    /// The epoch of `validators`, watched by the requests waiting for the next committee.
+    epoch_sender: watch::Sender<EpochId>,
+    /// Bounds the requests waiting for the next committee to `MAX_BUFFERED_REQUESTS`.
+    buffer_permits: Semaphore,
     task_sender: Sender<QuorumTask>,
     effects_subscribe_sender:
         tokio::sync::broadcast::Sender<(CertifiedTransaction, CertifiedTransactionEffects)>,
     metrics: Arc<QuorumDriverMetrics>,
+    finality_tracker: Arc<FinalityTracker>,
 }
 
 impl<A> QuorumDriver<A> {
@@ -61,12 +86,17 @@ impl<A> QuorumDriver<A> {
             CertifiedTransactionEffects,
         )>,
         metrics: Arc<QuorumDriverMetrics>,
+        finality_tracker: Arc<FinalityTracker>,
     ) -> Self {
+        let (epoch_sender, _) = watch::channel(validators.committee.epoch);
         Self {
             validators: ArcSwap::from(validators),
+            epoch_sender,
+            buffer_permits: Semaphore::new(MAX_BUFFERED_REQUESTS),
             task_sender,
             effects_subscribe_sender,
             metrics,
+            finality_tracker,
         }
     }
 
@@ -81,6 +111,70 @@ impl<A> QuorumDriver<A> {
     pub fn current_epoch(&self) -> EpochId {
         self.validators.load().committee.epoch
     }
+
+    pub fn finality_tracker(&self) -> &Arc<FinalityTracker> {
+        &self.finality_tracker
+    }
+
+    /// How long to back off for after `err`: the longest back-off asked by at least f+1 of the
+    /// validators, so that a single faulty validator cannot hold requests back.
+    fn retry_after(&self, err: &SuiError) -> Option<Duration> {
+        let mut hints = err.retry_after_hints();
+        hints.sort_unstable_by(|a, b| b.cmp(a));
+        let faulty = (self.validators.load().committee.num_members().max(1) - 1) / 3;
+        hints.get(faulty).copied()
+    }
+
+    /// Waits until the committee a request was rejected for, because validators halted to
+    /// reconfigure, is installed by `update_validators`. Returns whether it was, in which case
+    /// the request can be sent again to the new committee after backing off for its
+    /// `retries`-th time. Otherwise, e.g. if `err` is not due to the reconfiguration, the
+    /// request was retried too often or the committee is not installed in time, the request
+    /// fails with `err`.
+    async fn wait_for_reconfiguration(&self, err: &SuiError, retries: u32) -> bool {
+        let until_epoch = match err.halted_until_epoch() {
+            Some(until_epoch) => until_epoch,
+            None => return false,
+        };
+        if retries >= MAX_RECONFIGURATION_RETRIES {
+            return false;
+        }
+        let backoff = RECONFIGURATION_RETRY_DELAY * (retries + 1);
+        let mut epochs = self.epoch_sender.subscribe();
+        if *epochs.borrow_and_update() >= until_epoch {
+            // Installed since the request was sent, but validators may not have resumed yet.
+            tokio::time::sleep(backoff).await;
+            return true;
+        }
+        let _permit = match self.buffer_permits.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => return false,
+        };
+        self.metrics.total_buffered_requests.inc();
+        self.metrics.current_buffered_requests.inc();
+        let _metrics_guard = scopeguard::guard(self.metrics.clone(), |metrics| {
+            metrics.current_buffered_requests.dec();
+        });
+        debug!(
+            ?until_epoch,
+            "Buffering request until the next committee: {err}"
+        );
+        let installed = tokio::time::timeout(RECONFIGURATION_BUFFER_TIMEOUT, async {
+            while *epochs.borrow_and_update() < until_epoch {
+                if epochs.changed().await.is_err() {
+                    return false;
+                }
+            }
+            true
+        })
+        .await;
+        if !matches!(installed, Ok(true)) {
+            self.metrics.total_buffered_requests_expired.inc();
+            return false;
+        }
+        tokio::time::sleep(backoff).await;
+        true
+    }
 }
 
 impl<A> QuorumDriver<A>
@@ -170,15 +264,25 @@ where
         &self,
         transaction: Transaction,
     ) -> SuiResult<QuorumDriverResponse> {
-        let certificate = self
-            .process_transaction(transaction)
-            .instrument(tracing::debug_span!("process_tx"))
-            .await?;
-        let response = self
-            .process_certificate(certificate)
-            .instrument(tracing::debug_span!("process_cert"))
-            .await?;
-        Ok(QuorumDriverResponse::EffectsCert(Box::new(response)))
+        let mut retries = 0;
+        loop {
+            let certificate = self
+                .process_transaction(transaction.clone())
+                .instrument(tracing::debug_span!("process_tx"))
+                .await?;
+            let result = self
+                .process_certificate(certificate)
+                .instrument(tracing::debug_span!("process_cert"))
+                .await;
+            match result {
+                // The certificate is of the previous epoch, so the transaction is certified
+                // again by the new committee.
+                Err(err) if self.wait_for_reconfiguration(&err, retries).await => {
+                    retries += 1;
+                }
+                result => return Ok(QuorumDriverResponse::EffectsCert(Box::new(result?))),
+            }
+        }
     }
 
     pub async fn process_transaction(
@@ -186,23 +290,85 @@ where
         transaction: Transaction,
     ) -> SuiResult<CertifiedTransaction> {
         let tx_digest = *transaction.digest();
-        self.validators
-            .load()
-            .process_transaction(transaction)
-            .instrument(tracing::debug_span!("process_tx", ?tx_digest))
-            .await
+        let _timer = transaction.contains_shared_object().then(|| {
+            self.metrics
+                .shared_obj_tx_stage_latency_sec
+                .with_label_values(&["tx_cert"])
+                .start_timer()
+        });
+        let mut retries = 0;
+        let start = Instant::now();
+        let certificate = loop {
+            let result = self
+                .validators
+                .load()
+                .process_transaction(transaction.clone())
+                .instrument(tracing::debug_span!("process_tx", ?tx_digest))
+                .await;
+            match result {
+                Err(err) if self.wait_for_reconfiguration(&err, retries).await => {
+                    retries += 1;
+                    debug!(?tx_digest, "Sending transaction to the new committee");
+                }
+                result => break result?,
+            }
+        };
+        self.finality_tracker
+            .record(tx_digest, FinalityStage::SigningQuorum, start.elapsed());
+        Ok(certificate)
     }
 
     pub async fn process_certificate(
         &self,
         certificate: CertifiedTransaction,
     ) -> SuiResult<(CertifiedTransaction, CertifiedTransactionEffects)> {
-        let effects = self
-            .validators
-            .load()
-            .process_certificate(certificate.clone())
-            .instrument(tracing::debug_span!("process_cert", tx_digest = ?certificate.digest()))
-            .await?;
+        let _timer = certificate.contains_shared_object().then(|| {
+            self.metrics
+                .shared_obj_tx_stage_latency_sec
+                .with_label_values(&["effects_cert"])
+                .start_timer()
+        });
+        let tx_digest = *certificate.digest();
+        let mut retries = 0;
+        let start = Instant::now();
+        let (effects, first_effects_at) = loop {
+            let result = self
+                .validators
+                .load()
+                .process_certificate_with_timing(certificate.clone())
+                .instrument(tracing::debug_span!("process_cert", ?tx_digest))
+                .await;
+            match result {
+                Err(err) if err.is_retryable() && retries < MAX_CERTIFICATE_RETRIES => {
+                    retries += 1;
+                    debug!(
+                        ?tx_digest,
+                        ?retries,
+                        "Retrying certificate after error: {err}"
+                    );
+                    self.metrics.total_retried_certificates.inc();
+                    // Overloaded validators tell how long to back off for.
+                    let delay = self
+                        .retry_after(&err)
+                        .unwrap_or(CERTIFICATE_RETRY_DELAY * retries);
+                    tokio::time::sleep(delay).await;
+                }
+                result => break result?,
+            }
+        };
+        // Retries count towards broadcasting the certificate.
+        self.finality_tracker.record(
+            tx_digest,
+            FinalityStage::CertBroadcast,
+            first_effects_at.duration_since(start),
+        );
+        self.finality_tracker.record(
+            tx_digest,
+            FinalityStage::EffectsQuorum,
+            first_effects_at.elapsed(),
+        );
+        self.finality_tracker
+            .await_checkpoint(ExecutionDigests::new(tx_digest, *effects.digest()));
         let response = (certificate, effects);
         // An error to send the result to subscribers should not block returning the result.
         if let Err(err) = self.effects_subscribe_sender.send(response.clone()) {
@@ -216,7 +382,10 @@ where
         &self,
         new_validators: Arc<AuthorityAggregator<A>>,
     ) -> SuiResult {
+        let epoch = new_validators.committee.epoch;
         self.validators.store(new_validators);
+        // Wakes up the requests buffered until this committee.
+        self.epoch_sender.send_replace(epoch);
         Ok(())
     }
 }
@@ -228,12 +397,17 @@ where
     pub fn new(validators: Arc<AuthorityAggregator<A>>, metrics: QuorumDriverMetrics) -> Self {
         let (task_tx, task_rx) = mpsc::channel::<QuorumTask>(TASK_QUEUE_SIZE);
         let (subscriber_tx, subscriber_rx) = tokio::sync::broadcast::channel::<_>(100);
+        let finality_tracker = Arc::new(FinalityTracker::new(
+            metrics.finality_stage_latency_sec.clone(),
+            metrics.finality_untracked_checkpoint_inclusion.clone(),
+        ));
         let metrics = Arc::new(metrics);
         let quorum_driver = Arc::new(QuorumDriver::new(
             validators,
             task_tx,
             subscriber_tx,
             metrics.clone(),
+            finality_tracker.clone(),
         ));
         let handle = {
             let quorum_driver_copy = quorum_driver.clone();
@@ -246,6 +420,7 @@ where
             _processor_handle: handle,
             effects_subscriber: subscriber_rx,
             quorum_driver_metrics: metrics,
+            finality_tracker,
         }
     }
 
@@ -256,12 +431,16 @@ where
     pub fn clone_new(&self) -> Self {
         let (task_sender, task_rx) = mpsc::channel::<QuorumTask>(TASK_QUEUE_SIZE);
         let (effects_subscribe_sender, subscriber_rx) = tokio::sync::broadcast::channel::<_>(100);
-        let validators = ArcSwap::new(self.quorum_driver.authority_aggregator().load_full());
+        let validators = self.quorum_driver.authority_aggregator().load_full();
+        let (epoch_sender, _) = watch::channel(validators.committee.epoch);
         let quorum_driver = Arc::new(QuorumDriver {
-            validators,
+            validators: ArcSwap::new(validators),
+            epoch_sender,
+            buffer_permits: Semaphore::new(MAX_BUFFERED_REQUESTS),
             task_sender,
             effects_subscribe_sender,
             metrics: self.quorum_driver_metrics.clone(),
+            finality_tracker: self.finality_tracker.clone(),
         });
         let handle = {
             let quorum_driver_copy = quorum_driver.clone();
@@ -274,6 +453,7 @@ where
             _processor_handle: handle,
             effects_subscriber: subscriber_rx,
             quorum_driver_metrics: self.quorum_driver_metrics.clone(),
+            finality_tracker: self.finality_tracker.clone(),
         }
     }
 
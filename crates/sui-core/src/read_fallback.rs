@@ -0,0 +1,332 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority_client::AuthorityAPI;
+use crate::quorum_driver::QuorumDriver;
+use parking_lot::Mutex;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_config::node::ReadFallbackConfig;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{CertifiedTransaction, TransactionEffects};
+use sui_types::object::ObjectRead;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+/// ReadFallback serves the transactions and objects a full node has not synced yet by fetching
+/// them from the validators, so that clients reading right after finality do not get "not found"
+/// errors. Transactions are returned with effects certified by a quorum of the committee, and
+/// objects once validators with f+1 stake agree on their latest version.
+///
+/// Fetched data is cached, as clients typically retry a read until it succeeds. Reads the
+/// validators could not serve are not fetched again for a while, and the fetches running at
+/// once are bounded, so that reads of data that does not exist cannot be turned into a flood of
+/// requests to the committee.
+pub struct ReadFallback<A> {
+    quorum_driver: Arc<QuorumDriver<A>>,
+    object_ttl: Duration,
+    not_found_ttl: Duration,
+    transactions: Mutex<FifoCache<TransactionDigest, (CertifiedTransaction, TransactionEffects)>>,
+    objects: Mutex<FifoCache<ObjectID, (Instant, ObjectRead)>>,
+    // When the validators last failed to serve a transaction or object.
+    missing_transactions: Mutex<FifoCache<TransactionDigest, Instant>>,
+    missing_objects: Mutex<FifoCache<ObjectID, Instant>>,
+    fetches: Semaphore,
+    reads: IntCounterVec,
+}
+
+impl<A> ReadFallback<A> {
+    pub fn new(
+        quorum_driver: Arc<QuorumDriver<A>>,
+        config: &ReadFallbackConfig,
+        registry: &Registry,
+    ) -> Self {
+        Self {
+            quorum_driver,
+            object_ttl: Duration::from_millis(config.object_ttl_ms),
+            not_found_ttl: Duration::from_millis(config.not_found_ttl_ms),
+            transactions: Mutex::new(FifoCache::new(config.cache_size)),
+            objects: Mutex::new(FifoCache::new(config.cache_size)),
+            missing_transactions: Mutex::new(FifoCache::new(config.cache_size)),
+            missing_objects: Mutex::new(FifoCache::new(config.cache_size)),
+            fetches: Semaphore::new(config.max_concurrent_fetches),
+            reads: register_int_counter_vec_with_registry!(
+                "read_fallback_reads",
+                "Number of reads served from the validators, by kind of data and by whether they \
+                 hit the cache, were fetched, failed, were known to fail or were throttled",
+                &["kind", "result"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn cached_object(&self, object_id: &ObjectID) -> Option<ObjectRead> {
+        // Expired objects are overwritten when fetched again.
+        match self.objects.lock().get(object_id) {
+            Some((fetched_at, object)) if fetched_at.elapsed() < self.object_ttl => {
+                Some(object.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn recently_missing<K: Clone + Eq + Hash>(
+        &self,
+        missing: &Mutex<FifoCache<K, Instant>>,
+        key: &K,
+    ) -> bool {
+        matches!(
+            missing.lock().get(key),
+            Some(failed_at) if failed_at.elapsed() < self.not_found_ttl
+        )
+    }
+
+    fn observe(&self, kind: &str, result: &str) {
+        self.reads.with_label_values(&[kind, result]).inc();
+    }
+}
+
+impl<A> ReadFallback<A>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    /// Fetches the certificate and effects of a transaction from the validators.
+    pub async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiResult<(CertifiedTransaction, TransactionEffects)> {
+        if let Some(transaction) = self.transactions.lock().get(&digest) {
+            self.observe("transaction", "cache_hit");
+            return Ok(transaction.clone());
+        }
+        let not_found = SuiError::TransactionNotFound { digest };
+        if self.recently_missing(&self.missing_transactions, &digest) {
+            self.observe("transaction", "known_failed");
+            return Err(not_found);
+        }
+        let _permit = match self.fetches.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.observe("transaction", "throttled");
+                return Err(not_found);
+            }
+        };
+        let package = self
+            .quorum_driver
+            .authority_aggregator()
+            .load_full()
+            .get_transaction_finality_package(&digest)
+            .await
+            .map_err(|e| {
+                self.observe("transaction", "failed");
+                debug!(?digest, "Could not fetch transaction from validators: {e}");
+                self.missing_transactions
+                    .lock()
+                    .insert(digest, Instant::now());
+                e
+            })?;
+        self.observe("transaction", "fetched");
+        let transaction = (package.certificate, package.effects.effects);
+        self.transactions.lock().insert(digest, transaction.clone());
+        Ok(transaction)
+    }
+
+    /// Fetches the latest version of an object from the validators. Objects the validators do
+    /// not know are not cached.
+    pub async fn get_object_read(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
+        if let Some(object) = self.cached_object(&object_id) {
+            self.observe("object", "cache_hit");
+            return Ok(object);
+        }
+        let not_found = SuiError::ObjectNotFound { object_id };
+        if self.recently_missing(&self.missing_objects, &object_id) {
+            self.observe("object", "known_failed");
+            return Err(not_found);
+        }
+        let _permit = match self.fetches.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.observe("object", "throttled");
+                return Err(not_found);
+            }
+        };
+        // Certificates are not executed again to confirm a version fewer validators know, as
+        // reads must not make the validators execute anything.
+        let object = self
+            .quorum_driver
+            .authority_aggregator()
+            .load_full()
+            .get_object_info_without_execute(object_id)
+            .await;
+        let object = match object {
+            Ok(ObjectRead::NotExists(_)) => Err(not_found),
+            Ok(object) => Ok(object),
+            Err(e) => {
+                debug!(?object_id, "Could not fetch object from validators: {e}");
+                Err(e)
+            }
+        };
+        match &object {
+            Ok(object) => {
+                self.observe("object", "fetched");
+                self.objects
+                    .lock()
+                    .insert(object_id, (Instant::now(), object.clone()));
+            }
+            Err(_) => {
+                self.observe("object", "failed");
+                self.missing_objects
+                    .lock()
+                    .insert(object_id, Instant::now());
+            }
+        }
+        object
+    }
+}
+
+/// A map keeping the `capacity` keys inserted most recently. Updating the value of a key does
+/// not make it more recent.
+struct FifoCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    // Keys in insertion order, oldest first.
+    order: VecDeque<K>,
+}
+
+impl<K: Clone + Eq + Hash, V> FifoCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.entries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority_aggregator::authority_aggregator_tests::init_local_authorities;
+    use crate::authority_client::LocalAuthorityClient;
+    use crate::quorum_driver::{QuorumDriverHandler, QuorumDriverMetrics};
+    use sui_types::base_types::SuiAddress;
+    use sui_types::object::Object;
+
+    async fn init_read_fallback(
+        config: ReadFallbackConfig,
+        objects: Vec<Object>,
+    ) -> ReadFallback<LocalAuthorityClient> {
+        let (aggregator, _, _) = init_local_authorities(4, objects).await;
+        let quorum_driver =
+            QuorumDriverHandler::new(Arc::new(aggregator), QuorumDriverMetrics::new_for_tests())
+                .clone_quorum_driver();
+        ReadFallback::new(quorum_driver, &config, &Registry::new())
+    }
+
+    fn reads<A>(fallback: &ReadFallback<A>, kind: &str, result: &str) -> u64 {
+        fallback.reads.with_label_values(&[kind, result]).get()
+    }
+
+    #[tokio::test]
+    async fn test_failed_reads_are_not_fetched_again() {
+        let object = Object::with_id_owner_for_testing(
+            ObjectID::random(),
+            SuiAddress::random_for_testing_only(),
+        );
+        let config = ReadFallbackConfig {
+            not_found_ttl_ms: 60_000,
+            ..Default::default()
+        };
+        let fallback = init_read_fallback(config, vec![object.clone()]).await;
+
+        for _ in 0..2 {
+            let read = fallback.get_object_read(object.id()).await.unwrap();
+            assert!(matches!(read, ObjectRead::Exists(..)));
+        }
+        assert_eq!(reads(&fallback, "object", "fetched"), 1);
+        assert_eq!(reads(&fallback, "object", "cache_hit"), 1);
+
+        let missing = ObjectID::random();
+        for _ in 0..3 {
+            let read = fallback.get_object_read(missing).await;
+            assert!(matches!(read, Err(SuiError::ObjectNotFound { .. })));
+        }
+        assert_eq!(reads(&fallback, "object", "failed"), 1);
+        assert_eq!(reads(&fallback, "object", "known_failed"), 2);
+
+        let digest = TransactionDigest::random();
+        for _ in 0..3 {
+            assert!(fallback.get_transaction(digest).await.is_err());
+        }
+        assert_eq!(reads(&fallback, "transaction", "failed"), 1);
+        assert_eq!(reads(&fallback, "transaction", "known_failed"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetches_beyond_the_limit_are_throttled() {
+        let config = ReadFallbackConfig {
+            max_concurrent_fetches: 1,
+            ..Default::default()
+        };
+        let fallback = init_read_fallback(config, vec![]).await;
+        let missing = ObjectID::random();
+
+        let permit = fallback.fetches.try_acquire().unwrap();
+        assert!(fallback.get_object_read(missing).await.is_err());
+        assert!(fallback
+            .get_transaction(TransactionDigest::random())
+            .await
+            .is_err());
+        assert_eq!(reads(&fallback, "object", "throttled"), 1);
+        assert_eq!(reads(&fallback, "transaction", "throttled"), 1);
+        assert_eq!(reads(&fallback, "object", "failed"), 0);
+
+        drop(permit);
+        assert!(fallback.get_object_read(missing).await.is_err());
+        assert_eq!(reads(&fallback, "object", "failed"), 1);
+    }
+
+    #[test]
+    fn test_fifo_cache() {
+        let mut cache = FifoCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        // Updating a key keeps its place.
+        cache.insert(1, "c");
+        cache.insert(3, "d");
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(&"b"));
+        assert_eq!(cache.get(&3), Some(&"d"));
+        assert_eq!(cache.order.len(), 2);
+
+        let mut disabled = FifoCache::new(0);
+        disabled.insert(1, "a");
+        assert_eq!(disabled.get(&1), None);
+    }
+}
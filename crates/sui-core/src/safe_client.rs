@@ -8,30 +8,39 @@ use crate::authority_client::{
 use crate::epoch::committee_store::CommitteeStore;
 use crate::histogram::{Histogram, HistogramVec};
 use futures::StreamExt;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use prometheus::core::{GenericCounter, GenericGauge};
 use prometheus::{
-    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
-    IntGaugeVec,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntCounter,
+    IntCounterVec, IntGauge, IntGaugeVec,
 };
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
 use sui_types::batch::{AuthorityBatch, SignedBatch, TxSequenceNumber, UpdateItem};
+use sui_types::bridge::{BridgeAttestationRequest, BridgeAttestationResponse};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::{
-    AuthenticatedCheckpoint, CheckpointRequest, CheckpointRequestType, CheckpointResponse,
-    CheckpointSequenceNumber,
+    AuthenticatedCheckpoint, CheckpointContents, CheckpointRequest, CheckpointRequestType,
+    CheckpointResponse, CheckpointSequenceNumber, CheckpointSummary,
 };
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 use sui_types::{base_types::*, committee::*, fp_ensure};
 use sui_types::{
     error::{SuiError, SuiResult},
     messages::*,
 };
 use tap::TapFallible;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
 use tracing::{debug, error};
 
 macro_rules! check_error {
     ($address:expr, $cond:expr, $msg:expr) => {
         $cond.tap_err(|err| {
-            if matches!(err, SuiError::ValidatorHaltedAtEpochEnd) {
+            if err.indicates_epoch_change() {
                 debug!(?err, authority=?$address, "Not a real client error");
             } else {
                 error!(?err, authority=?$address, $msg);
@@ -40,6 +49,73 @@ macro_rules! check_error {
     }
 }
 
+/// Number of responses that can be verified at once on the blocking thread pool. Further
+/// verifications wait for a slot, which pushes back on the requests that produced them.
+static VERIFICATION_SLOTS: Lazy<Semaphore> = Lazy::new(|| {
+    let parallelism = std::thread::available_parallelism().map_or(1, |n| n.get());
+    Semaphore::new(2 * parallelism)
+});
+
+/// Runs a signature or digest verification on the blocking thread pool, so that verifying large
+/// responses does not stall the tokio workers serving other requests.
+pub async fn spawn_verification<T, F>(verify: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let _permit = VERIFICATION_SLOTS
+        .acquire()
+        .await
+        .expect("The verification semaphore is never closed");
+    tokio::task::spawn_blocking(verify)
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+}
+
+/// Responses whose checks include verifying signatures.
+trait SignedResponse {
+    fn has_signatures(&self) -> bool;
+}
+
+impl SignedResponse for TransactionInfoResponse {
+    fn has_signatures(&self) -> bool {
+        self.signed_transaction.is_some()
+            || self.certified_transaction.is_some()
+            || self.signed_effects.is_some()
+    }
+}
+
+impl SignedResponse for ObjectInfoResponse {
+    fn has_signatures(&self) -> bool {
+        self.parent_certificate.is_some()
+            || matches!(
+                &self.object_and_lock,
+                Some(ObjectResponse { lock: Some(_), .. })
+            )
+    }
+}
+
+impl SignedResponse for TransactionsInfoResponse {
+    fn has_signatures(&self) -> bool {
+        self.responses.iter().any(SignedResponse::has_signatures)
+    }
+}
+
+impl SignedResponse for EffectsInfoResponse {
+    fn has_signatures(&self) -> bool {
+        self.signed_effects.is_some()
+    }
+}
+
+impl SignedResponse for CheckpointResponse {
+    fn has_signatures(&self) -> bool {
+        match self {
+            CheckpointResponse::AuthenticatedCheckpoint { checkpoint, .. } => checkpoint.is_some(),
+            CheckpointResponse::CheckpointProposal { proposal, .. } => proposal.is_some(),
+        }
+    }
+}
+
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
 #[derive(Clone)]
 pub struct SafeClientMetrics {
@@ -47,6 +123,10 @@ pub struct SafeClientMetrics {
     pub(crate) total_responses_by_address_method: IntCounterVec,
     pub(crate) follower_streaming_from_seq_number_by_address: IntGaugeVec,
     pub(crate) follower_streaming_reconnect_times_by_address: IntCounterVec,
+    pub(crate) invalid_checkpoint_responses_by_address: IntCounterVec,
+    verified_cert_cache_hits: IntCounter,
+    verified_cert_cache_misses: IntCounter,
+    verified_cert_cache_size: IntGauge,
     latency: HistogramVec,
 }
 
@@ -81,6 +161,31 @@ impl SafeClientMetrics {
                 registry,
             )
             .unwrap(),
+            invalid_checkpoint_responses_by_address: register_int_counter_vec_with_registry!(
+                "safe_client_invalid_checkpoint_responses_by_address",
+                "Total checkpoint responses from validators that failed validation, group by address",
+                &["address"],
+                registry,
+            )
+            .unwrap(),
+            verified_cert_cache_hits: register_int_counter_with_registry!(
+                "safe_client_verified_cert_cache_hits",
+                "Total certificates whose signature verification was skipped because they were verified before",
+                registry,
+            )
+            .unwrap(),
+            verified_cert_cache_misses: register_int_counter_with_registry!(
+                "safe_client_verified_cert_cache_misses",
+                "Total certificates whose signatures were verified",
+                registry,
+            )
+            .unwrap(),
+            verified_cert_cache_size: register_int_gauge_with_registry!(
+                "safe_client_verified_cert_cache_size",
+                "Number of certificates in the verified certificate cache",
+                registry,
+            )
+            .unwrap(),
             latency: HistogramVec::new_in_registry(
                 "safe_client_latency",
                 "RPC latency observed by safe client aggregator, group by address and method",
@@ -96,12 +201,114 @@ impl SafeClientMetrics {
     }
 }
 
+/// Default number of certificates remembered by a `VerifiedCertificateCache`.
+pub const DEFAULT_VERIFIED_CERT_CACHE_SIZE: usize = 100_000;
+
+/// Remembers the certificates whose signatures have been verified, so that a certificate
+/// returned by several validators, or first fetched during sync and then processed, is only
+/// verified once. It is shared by the SafeClients of an AuthorityAggregator.
+///
+/// Certificates are keyed by transaction digest and epoch. The serialized signature is kept
+/// too, since a byzantine validator could return a known certificate with invalid signatures.
+pub struct VerifiedCertificateCache {
+    capacity: usize,
+    inner: Mutex<VerifiedCertificateCacheInner>,
+    hits: IntCounter,
+    misses: IntCounter,
+    size: IntGauge,
+}
+
+#[derive(Default)]
+struct VerifiedCertificateCacheInner {
+    signatures: HashMap<(TransactionDigest, EpochId), Vec<u8>>,
+    // Keys in insertion order, oldest first.
+    order: VecDeque<(TransactionDigest, EpochId)>,
+}
+
+impl VerifiedCertificateCache {
+    pub fn new(capacity: usize, safe_client_metrics: &SafeClientMetrics) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(VerifiedCertificateCacheInner::default()),
+            hits: safe_client_metrics.verified_cert_cache_hits.clone(),
+            misses: safe_client_metrics.verified_cert_cache_misses.clone(),
+            size: safe_client_metrics.verified_cert_cache_size.clone(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(
+            DEFAULT_VERIFIED_CERT_CACHE_SIZE,
+            &SafeClientMetrics::new_for_tests(),
+        )
+    }
+
+    /// Verify `certificate` against `committee`, unless the same certificate was verified before.
+    pub fn verify(&self, certificate: &CertifiedTransaction, committee: &Committee) -> SuiResult {
+        let key = (*certificate.digest(), certificate.auth_sign_info.epoch);
+        let signature =
+            bcs::to_bytes(&certificate.auth_sign_info).expect("Serialization should not fail");
+        if self.inner.lock().signatures.get(&key) == Some(&signature) {
+            self.hits.inc();
+            return Ok(());
+        }
+        self.misses.inc();
+        certificate.verify(committee)?;
+
+        let mut inner = self.inner.lock();
+        if inner.signatures.insert(key, signature).is_none() {
+            inner.order.push_back(key);
+        }
+        while inner.signatures.len() > self.capacity {
+            match inner.order.pop_front() {
+                Some(oldest) => {
+                    inner.signatures.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.size.set(inner.signatures.len() as i64);
+        Ok(())
+    }
+}
+
+/// How far the batch stream of an authority was validated: the transactions before the
+/// `next_sequence_number` of the last batch validated are covered by batches signed by the
+/// authority and chained to each other. A stream interrupted by a disconnection resumes from the
+/// cursor with `SafeClient::resume_batch_stream`, and is rejected unless it carries on the same
+/// history. Clones share the same position.
+#[derive(Clone, Debug, Default)]
+pub struct BatchStreamCursor {
+    last_batch: Arc<Mutex<Option<AuthorityBatch>>>,
+}
+
+impl BatchStreamCursor {
+    /// The last batch validated, if any.
+    pub fn last_batch(&self) -> Option<AuthorityBatch> {
+        self.last_batch.lock().clone()
+    }
+
+    /// The sequence number of the first transaction not covered by a validated batch, from
+    /// which a stream resumes, or None if no batch was validated yet.
+    pub fn next_sequence_number(&self) -> Option<TxSequenceNumber> {
+        self.last_batch
+            .lock()
+            .as_ref()
+            .map(|batch| batch.next_sequence_number)
+    }
+
+    fn advance(&self, batch: &AuthorityBatch) {
+        *self.last_batch.lock() = Some(batch.clone());
+    }
+}
+
 /// See `SafeClientMetrics::new` for description of each metrics.
 /// The metrics are per validator client.
 #[derive(Clone)]
 pub struct SafeClient<C> {
     authority_client: C,
     committee_store: Arc<CommitteeStore>,
+    verified_cert_cache: Arc<VerifiedCertificateCache>,
     address: AuthorityPublicKeyBytes,
     metrics_total_requests_handle_transaction_and_effects_info_request:
         GenericCounter<prometheus::core::AtomicU64>,
@@ -119,16 +326,21 @@ pub struct SafeClient<C> {
     pub(crate) metrics_seq_number_to_handle_batch_stream: GenericGauge<prometheus::core::AtomicI64>,
     pub(crate) metrics_total_times_reconnect_follower_stream:
         GenericCounter<prometheus::core::AtomicU64>,
+    metrics_total_invalid_checkpoint_responses: GenericCounter<prometheus::core::AtomicU64>,
     metrics_handle_transaction_latency: Histogram,
     metrics_handle_certificate_latency: Histogram,
     metrics_handle_obj_info_latency: Histogram,
     metrics_handle_tx_info_latency: Histogram,
+    // Until when the authority asked not to send it transactions and certificates, as it is
+    // overloaded.
+    overloaded_until: Arc<Mutex<Option<Instant>>>,
 }
 
 impl<C> SafeClient<C> {
     pub fn new(
         authority_client: C,
         committee_store: Arc<CommitteeStore>,
+        verified_cert_cache: Arc<VerifiedCertificateCache>,
         address: AuthorityPublicKeyBytes,
         safe_client_metrics: Arc<SafeClientMetrics>,
     ) -> Self {
@@ -169,6 +381,9 @@ impl<C> SafeClient<C> {
         let metrics_total_times_reconnect_follower_stream = safe_client_metrics
             .follower_streaming_reconnect_times_by_address
             .with_label_values(&[&validator_address]);
+        let metrics_total_invalid_checkpoint_responses = safe_client_metrics
+            .invalid_checkpoint_responses_by_address
+            .with_label_values(&[&validator_address]);
 
         let metrics_handle_transaction_latency = safe_client_metrics
             .latency
@@ -186,6 +401,7 @@ impl<C> SafeClient<C> {
         Self {
             authority_client,
             committee_store,
+            verified_cert_cache,
             address,
             metrics_total_requests_handle_transaction_and_effects_info_request,
             metrics_total_ok_responses_handle_transaction_and_effects_info_request,
@@ -197,10 +413,12 @@ impl<C> SafeClient<C> {
             metrics_total_ok_responses_handle_batch_stream,
             metrics_seq_number_to_handle_batch_stream,
             metrics_total_times_reconnect_follower_stream,
+            metrics_total_invalid_checkpoint_responses,
             metrics_handle_transaction_latency,
             metrics_handle_certificate_latency,
             metrics_handle_obj_info_latency,
             metrics_handle_tx_info_latency,
+            overloaded_until: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -213,6 +431,35 @@ impl<C> SafeClient<C> {
         &mut self.authority_client
     }
 
+    /// How long the authority asked not to send it transactions and certificates, if it is
+    /// overloaded.
+    pub fn overloaded_for(&self) -> Option<Duration> {
+        let mut overloaded_until = self.overloaded_until.lock();
+        let remaining = (*overloaded_until)?.checked_duration_since(Instant::now());
+        if remaining.is_none() {
+            *overloaded_until = None;
+        }
+        remaining
+    }
+
+    // Fail fast rather than add to the load of an authority which asked clients to back off.
+    fn check_not_overloaded(&self) -> SuiResult {
+        match self.overloaded_for() {
+            Some(remaining) => Err(SuiError::ValidatorOverloaded {
+                reason: "Backing off at the request of the validator".to_string(),
+                retry_after_ms: Some(remaining.as_millis() as u64),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn record_overload<T>(&self, result: SuiResult<T>) -> SuiResult<T> {
+        if let Some(retry_after) = result.as_ref().err().and_then(SuiError::retry_after) {
+            *self.overloaded_until.lock() = Instant::now().checked_add(retry_after);
+        }
+        result
+    }
+
     fn get_committee(&self, epoch_id: &EpochId) -> SuiResult<Committee> {
         self.committee_store
             .get_committee(epoch_id)?
@@ -254,7 +501,8 @@ impl<C> SafeClient<C> {
                 committee = Some(self.get_committee(&certificate.auth_sign_info.epoch)?);
             }
             // Check signatures and quorum
-            certificate.verify(committee.as_ref().unwrap())?;
+            self.verified_cert_cache
+                .verify(certificate, committee.as_ref().unwrap())?;
             // Check it's the right transaction
             fp_ensure!(
                 certificate.digest() == digest,
@@ -314,7 +562,10 @@ impl<C> SafeClient<C> {
         // If we get a certificate make sure it is a valid certificate
         if !skip_committee_check_during_reconfig {
             if let Some(certificate) = &response.parent_certificate {
-                certificate.verify(&self.get_committee(&certificate.auth_sign_info.epoch)?)?;
+                self.verified_cert_cache.verify(
+                    certificate,
+                    &self.get_committee(&certificate.auth_sign_info.epoch)?,
+                )?;
             }
         }
 
@@ -342,10 +593,7 @@ impl<C> SafeClient<C> {
         if let Some(object_and_lock) = &response.object_and_lock {
             // We should only be returning the object and lock data if requesting the latest object info.
             fp_ensure!(
-                matches!(
-                    request.request_kind,
-                    ObjectInfoRequestKind::LatestObjectInfo(_)
-                ),
+                request.latest_content().is_some(),
                 SuiError::ByzantineAuthoritySuspicion {
                     authority: self.address,
                     reason:
@@ -407,9 +655,19 @@ impl<C> SafeClient<C> {
             Vec<(TxSequenceNumber, ExecutionDigests)>,
             AuthorityBatch,
         )>,
+        resumed_from: Option<&AuthorityBatch>,
     ) -> SuiResult {
         // check the signature of the batch
         signed_batch.verify(&self.get_committee(&signed_batch.auth_sig().epoch)?)?;
+        // Check it has the right signer, so that the batches of another authority are not
+        // passed off as the history of this one.
+        fp_ensure!(
+            signed_batch.auth_sig().authority == self.address,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: "Unexpected validator address in the batch signature".to_string()
+            }
+        );
 
         // ensure transactions enclosed match requested range
 
@@ -446,6 +704,17 @@ impl<C> SafeClient<C> {
                     reason: "Inconsistent batch".to_string()
                 }
             );
+        } else if let Some(resumed_from) = resumed_from {
+            // A resumed stream starts again from the last batch validated, so that the batches
+            // which follow extend the history validated before the disconnection.
+            fp_ensure!(
+                signed_batch.data() == resumed_from,
+                SuiError::ByzantineAuthoritySuspicion {
+                    authority: self.address,
+                    reason: "Resumed stream does not start with the last validated batch"
+                        .to_string()
+                }
+            );
         }
 
         Ok(())
@@ -460,23 +729,38 @@ impl<C> SafeClient<C>
 where
     C: AuthorityAPI + Send + Sync + Clone + 'static,
 {
+    /// Runs `check` on `response`, with `spawn_verification` if it carries signatures, and
+    /// returns the response if it passes.
+    async fn check_off_runtime<T, F>(&self, response: T, check: F) -> SuiResult<T>
+    where
+        T: SignedResponse + Send + 'static,
+        F: FnOnce(&Self, &T) -> SuiResult + Send + 'static,
+    {
+        if !response.has_signatures() {
+            return check(self, &response).map(|()| response);
+        }
+        let client = self.clone();
+        spawn_verification(move || check(&client, &response).map(|()| response)).await
+    }
+
     /// Initiate a new transfer to a Sui or Primary account.
     pub async fn handle_transaction(
         &self,
         transaction: Transaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        self.check_not_overloaded()?;
         let digest = *transaction.digest();
         let _timer = self.metrics_handle_transaction_latency.start_timer();
-        let transaction_info = self
-            .authority_client
-            .handle_transaction(transaction)
-            .await?;
+        let transaction_info =
+            self.record_overload(self.authority_client.handle_transaction(transaction).await)?;
         check_error!(
             self.address,
-            self.check_transaction_response(&digest, None, &transaction_info),
+            self.check_off_runtime(transaction_info, move |client, info| {
+                client.check_transaction_response(&digest, None, info)
+            })
+            .await,
             "Client error in handle_transaction"
-        )?;
-        Ok(transaction_info)
+        )
     }
 
     fn verify_certificate_response(
@@ -500,19 +784,20 @@ where
         &self,
         certificate: CertifiedTransaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
+        self.check_not_overloaded()?;
         let digest = *certificate.digest();
         let _timer = self.metrics_handle_certificate_latency.start_timer();
-        let transaction_info = self
-            .authority_client
-            .handle_certificate(certificate)
-            .await?;
+        let transaction_info =
+            self.record_overload(self.authority_client.handle_certificate(certificate).await)?;
 
         check_error!(
             self.address,
-            self.verify_certificate_response(&digest, &transaction_info),
+            self.check_off_runtime(transaction_info, move |client, info| {
+                client.verify_certificate_response(&digest, info)
+            })
+            .await,
             "Client error in handle_certificate"
-        )?;
-        Ok(transaction_info)
+        )
     }
 
     pub async fn handle_account_info_request(
@@ -538,12 +823,16 @@ where
             .authority_client
             .handle_object_info_request(request.clone())
             .await?;
-        if let Err(err) =
-            self.check_object_response(&request, &response, skip_committee_check_during_reconfig)
-        {
-            error!(?err, authority=?self.address, "Client error in handle_object_info_request");
-            return Err(err);
-        }
+        let response = self
+            .check_off_runtime(response, move |client, response| {
+                client.check_object_response(
+                    &request,
+                    response,
+                    skip_committee_check_during_reconfig,
+                )
+            })
+            .await
+            .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_object_info_request"))?;
         self.metrics_total_ok_responses_handle_object_info_request
             .inc();
         Ok(response)
@@ -565,15 +854,59 @@ where
             .handle_transaction_info_request(request)
             .await?;
 
-        if let Err(err) = self.check_transaction_response(&digest, None, &transaction_info) {
-            error!(?err, authority=?self.address, "Client error in handle_transaction_info_request");
-            return Err(err);
-        }
+        let transaction_info = self
+            .check_off_runtime(transaction_info, move |client, info| {
+                client.check_transaction_response(&digest, None, info)
+            })
+            .await
+            .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_transaction_info_request"))?;
         self.metrics_total_ok_responses_handle_transaction_info_request
             .inc();
         Ok(transaction_info)
     }
 
+    /// Handle information requests for several transactions at once. Each response is checked
+    /// as done by handle_transaction_info_request for its digest.
+    pub async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> SuiResult<TransactionsInfoResponse> {
+        let digests = request.transaction_digests.clone();
+        let response = self
+            .authority_client
+            .handle_transactions_info_request(request)
+            .await?;
+        self.check_off_runtime(response, move |client, response| {
+            client.check_transactions_response(&digests, response)
+        })
+        .await
+        .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_transactions_info_request"))
+    }
+
+    fn check_transactions_response(
+        &self,
+        digests: &[TransactionDigest],
+        response: &TransactionsInfoResponse,
+    ) -> SuiResult {
+        // Responses may leave out the last transactions, but must include at least one.
+        let num_responses = response.responses.len();
+        fp_ensure!(
+            num_responses <= digests.len() && (num_responses > 0 || digests.is_empty()),
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: format!(
+                    "Requested {} transactions but received {} responses",
+                    digests.len(),
+                    response.responses.len()
+                )
+            }
+        );
+        for (digest, info) in digests.iter().zip(&response.responses) {
+            self.check_transaction_response(digest, None, info)?;
+        }
+        Ok(())
+    }
+
     /// Handle Transaction + Effects information requests for this account.
     pub async fn handle_transaction_and_effects_info_request(
         &self,
@@ -586,19 +919,66 @@ where
             .handle_transaction_info_request(digests.transaction.into())
             .await?;
 
-        if let Err(err) = self.check_transaction_response(
-            &digests.transaction,
-            Some(&digests.effects),
-            &transaction_info,
-        ) {
-            error!(?err, authority=?self.address, "Client error in handle_transaction_and_effects_info_request");
-            return Err(err);
-        }
+        let digests = *digests;
+        let transaction_info = self
+            .check_off_runtime(transaction_info, move |client, info| {
+                client.check_transaction_response(
+                    &digests.transaction,
+                    Some(&digests.effects),
+                    info,
+                )
+            })
+            .await
+            .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_transaction_and_effects_info_request"))?;
         self.metrics_total_ok_responses_handle_transaction_and_effects_info_request
             .inc();
         Ok(transaction_info)
     }
 
+    /// Handle requests for the effects with a given digest.
+    pub async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> SuiResult<EffectsInfoResponse> {
+        let effects_digest = request.effects_digest;
+        let response = self
+            .authority_client
+            .handle_effects_info_request(request)
+            .await?;
+        self.check_off_runtime(response, move |client, response| {
+            client.check_effects_response(&effects_digest, response)
+        })
+        .await
+        .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_effects_info_request"))
+    }
+
+    fn check_effects_response(
+        &self,
+        effects_digest: &TransactionEffectsDigest,
+        response: &EffectsInfoResponse,
+    ) -> SuiResult {
+        if let Some(signed_effects) = &response.signed_effects {
+            fp_ensure!(
+                signed_effects.digest() == effects_digest,
+                SuiError::ByzantineAuthoritySuspicion {
+                    authority: self.address,
+                    reason: "Effects digest does not match with requested digest".to_string()
+                }
+            );
+            fp_ensure!(
+                signed_effects.auth_signature.authority == self.address,
+                SuiError::ByzantineAuthoritySuspicion {
+                    authority: self.address,
+                    reason: "Unexpected validator address in the signed effects signature"
+                        .to_string()
+                }
+            );
+            let committee = self.get_committee(&signed_effects.auth_signature.epoch)?;
+            signed_effects.verify(&committee)?;
+        }
+        Ok(())
+    }
+
     pub async fn handle_committee_info_request(
         &self,
         request: CommitteeInfoRequest,
@@ -612,6 +992,74 @@ where
         Ok(committee_info)
     }
 
+    pub async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> SuiResult<BridgeAttestationResponse> {
+        let response = self
+            .authority_client
+            .handle_bridge_attestation_request(request.clone())
+            .await?;
+        self.verify_bridge_attestation_response(&request, &response)
+            .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_bridge_attestation_request"))?;
+        Ok(response)
+    }
+
+    fn verify_bridge_attestation_response(
+        &self,
+        request: &BridgeAttestationRequest,
+        response: &BridgeAttestationResponse,
+    ) -> SuiResult {
+        let attestation = &response.attestation;
+        fp_ensure!(
+            attestation.message.tx_digest == request.tx_digest
+                && attestation.message.event_seq == request.event_seq,
+            SuiError::from("Bridge attestation does not match the requested event")
+        );
+        fp_ensure!(
+            attestation.authority() == &self.address,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: "Bridge attestation signed by another authority".to_string(),
+            }
+        );
+        let committee = self.get_committee(&attestation.auth_signature.epoch)?;
+        attestation.verify(&committee)
+    }
+
+    pub async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> SuiResult<NetworkInfoResponse> {
+        let response = self
+            .authority_client
+            .handle_network_info_request(request)
+            .await?;
+        self.verify_network_info_response(&response)
+            .tap_err(|err| error!(?err, authority=?self.address, "Client error in handle_network_info_request"))?;
+        Ok(response)
+    }
+
+    fn verify_network_info_response(&self, response: &NetworkInfoResponse) -> SuiResult {
+        let info = &response.info;
+        fp_ensure!(
+            info.authority() == &self.address,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: "Network info signed by another authority".to_string(),
+            }
+        );
+        let committee = self.get_committee(&info.auth_signature.epoch)?;
+        fp_ensure!(
+            info.info.committee == committee.voting_rights,
+            SuiError::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: "Network info with another committee".to_string(),
+            }
+        );
+        info.verify(&committee)
+    }
+
     fn verify_committee_info_response(
         &self,
         requested_epoch: Option<EpochId>,
@@ -634,6 +1082,13 @@ where
         Ok(())
     }
 
+    fn checkpoint_suspicion(&self, reason: impl Into<String>) -> SuiError {
+        SuiError::ByzantineAuthoritySuspicion {
+            authority: self.address,
+            reason: reason.into(),
+        }
+    }
+
     fn verify_checkpoint_sequence(
         &self,
         expected_seq: Option<CheckpointSequenceNumber>,
@@ -644,7 +1099,9 @@ where
         if let (Some(e), Some(o)) = (expected_seq, observed_seq) {
             fp_ensure!(
                 e == o,
-                SuiError::from("Expected checkpoint number doesn't match with returned")
+                self.checkpoint_suspicion(format!(
+                    "Expected checkpoint number {e} doesn't match with returned {o}"
+                ))
             );
         }
         Ok(())
@@ -661,13 +1118,47 @@ where
             // it's an error.
             // If content is not requested, or checkpoint is None, yet we are still getting content,
             // it's an error.
-            (true, Some(_), None) | (false, _, Some(_)) | (_, None, Some(_)) => Err(
-                SuiError::from("Checkpoint contents inconsistent with request"),
-            ),
+            (true, Some(_), None) | (false, _, Some(_)) | (_, None, Some(_)) => {
+                Err(self.checkpoint_suspicion("Checkpoint contents inconsistent with request"))
+            }
             _ => Ok(()),
         }
     }
 
+    /// Checks that a checkpoint summary links to its predecessor the way honest authorities
+    /// build it: only the first checkpoint has no predecessor.
+    fn verify_checkpoint_summary(&self, summary: &CheckpointSummary) -> SuiResult {
+        fp_ensure!(
+            summary.sequence_number > 0 || summary.previous_digest.is_none(),
+            self.checkpoint_suspicion("First checkpoint cannot have a previous digest")
+        );
+        Ok(())
+    }
+
+    /// Verifies the signatures on `checkpoint`, and that `contents`, if any, match its digest.
+    /// A checkpoint with a single signature must be signed by the authority we are talking to.
+    fn verify_authenticated_checkpoint(
+        &self,
+        checkpoint: &AuthenticatedCheckpoint,
+        contents: Option<&CheckpointContents>,
+    ) -> SuiResult {
+        if let AuthenticatedCheckpoint::Signed(signed) = checkpoint {
+            fp_ensure!(
+                signed.auth_signature.authority == self.address,
+                self.checkpoint_suspicion("Checkpoint signed by another authority")
+            );
+        }
+        self.verify_checkpoint_summary(checkpoint.summary())?;
+        let committee = self.get_committee(&checkpoint.epoch())?;
+        checkpoint.verify(&committee, contents).map_err(|err| {
+            self.checkpoint_suspicion(format!(
+                "Invalid checkpoint {} of epoch {}: {err}",
+                checkpoint.sequence_number(),
+                checkpoint.epoch()
+            ))
+        })
+    }
+
     fn verify_checkpoint_response(
         &self,
         request: &CheckpointRequest,
@@ -686,14 +1177,11 @@ where
                     self.verify_contents_exist(request.detail, checkpoint, contents)?;
                     // Verify signature.
                     match checkpoint {
-                        Some(c) => {
-                            let epoch_id = c.summary().epoch;
-                            c.verify(&self.get_committee(&epoch_id)?, contents.as_ref())
-                        }
+                        Some(c) => self.verify_authenticated_checkpoint(c, contents.as_ref()),
                         None => Ok(()),
                     }
                 } else {
-                    Err(SuiError::from(
+                    Err(self.checkpoint_suspicion(
                         "Invalid AuthorityCheckpointInfo type in the response",
                     ))
                 }
@@ -705,36 +1193,81 @@ where
                     proposal_contents,
                 } = &response
                 {
+                    self.verify_contents_exist(request.detail, proposal, proposal_contents)?;
                     // Verify signature.
                     if let Some(signed_proposal) = proposal {
-                        let mut committee =
+                        let seq = signed_proposal.summary.sequence_number;
+                        fp_ensure!(
+                            signed_proposal.auth_signature.authority == self.address,
+                            self.checkpoint_suspicion(
+                                "Checkpoint proposal signed by another authority"
+                            )
+                        );
+                        let committee =
                             self.get_committee(&signed_proposal.auth_signature.epoch)?;
-                        signed_proposal.verify(&committee, proposal_contents.as_ref())?;
-                        if signed_proposal.summary.sequence_number > 0 {
+                        signed_proposal
+                            .verify(&committee, proposal_contents.as_ref())
+                            .map_err(|err| {
+                                self.checkpoint_suspicion(format!(
+                                    "Invalid checkpoint proposal {seq}: {err}"
+                                ))
+                            })?;
+                        if seq > 0 {
                             let cert = prev_cert.as_ref().ok_or_else(|| {
-                                SuiError::from("No checkpoint cert provided along with proposal")
+                                self.checkpoint_suspicion(
+                                    "No checkpoint cert provided along with proposal",
+                                )
                             })?;
-                            if cert.auth_signature.epoch != signed_proposal.auth_signature.epoch {
-                                // It's possible that the previous checkpoint cert is from the
-                                // previous epoch, and in that case we verify them using different
-                                // committee.
-                                fp_ensure!(
-                                    cert.auth_signature.epoch + 1
+                            // It's possible that the previous checkpoint cert is from the
+                            // previous epoch, and in that case it is verified using a different
+                            // committee.
+                            fp_ensure!(
+                                cert.auth_signature.epoch == signed_proposal.auth_signature.epoch
+                                    || cert.auth_signature.epoch + 1
                                         == signed_proposal.auth_signature.epoch,
-                                    SuiError::from("Unexpected epoch for checkpoint cert")
-                                );
-                                committee = self.get_committee(&cert.auth_signature.epoch)?;
-                            }
-                            cert.verify(&committee, None)?;
+                                self.checkpoint_suspicion("Unexpected epoch for checkpoint cert")
+                            );
+                            self.verify_authenticated_checkpoint(
+                                &AuthenticatedCheckpoint::Certified(cert.clone()),
+                                None,
+                            )?;
                             fp_ensure!(
-                                signed_proposal.summary.sequence_number - 1 == cert.summary.sequence_number,
-                                SuiError::from("Checkpoint proposal sequence number inconsistent with previous cert")
+                                seq - 1 == cert.summary.sequence_number,
+                                self.checkpoint_suspicion(
+                                    "Checkpoint proposal sequence number inconsistent with previous cert"
+                                )
                             );
                         }
                     }
-                    self.verify_contents_exist(request.detail, proposal, proposal_contents)
+                    Ok(())
+                } else {
+                    Err(self.checkpoint_suspicion(
+                        "Invalid AuthorityCheckpointInfo type in the response",
+                    ))
+                }
+            }
+            CheckpointRequestType::TransactionCheckpoint(digests) => {
+                if let CheckpointResponse::AuthenticatedCheckpoint {
+                    checkpoint,
+                    contents,
+                } = &response
+                {
+                    self.verify_contents_exist(request.detail, checkpoint, contents)?;
+                    if let Some(contents) = contents {
+                        fp_ensure!(
+                            contents.iter().any(|d| d == digests),
+                            self.checkpoint_suspicion(format!(
+                                "Checkpoint contents do not include transaction {:?}",
+                                digests.transaction
+                            ))
+                        );
+                    }
+                    match checkpoint {
+                        Some(c) => self.verify_authenticated_checkpoint(c, contents.as_ref()),
+                        None => Ok(()),
+                    }
                 } else {
-                    Err(SuiError::from(
+                    Err(self.checkpoint_suspicion(
                         "Invalid AuthorityCheckpointInfo type in the response",
                     ))
                 }
@@ -750,11 +1283,14 @@ where
             .authority_client
             .handle_checkpoint(request.clone())
             .await?;
-        self.verify_checkpoint_response(&request, &resp)
-            .tap_err(|err| {
-                error!(?err, authority=?self.address, "Client error in handle_checkpoint");
-            })?;
-        Ok(resp)
+        self.check_off_runtime(resp, move |client, resp| {
+            client.verify_checkpoint_response(&request, resp)
+        })
+        .await
+        .tap_err(|err| {
+            self.metrics_total_invalid_checkpoint_responses.inc();
+            error!(?err, authority=?self.address, "Client error in handle_checkpoint");
+        })
     }
 
     pub async fn handle_checkpoint_stream(
@@ -768,14 +1304,37 @@ where
 
         let client = self.clone();
 
-        let stream = Box::pin(checkpoint_info_items.scan((), move |_, item| {
-            let process_item = |item: SuiResult<CheckpointStreamResponseItem>| -> SuiResult<CheckpointStreamResponseItem> {
+        // The stream state is the sequence number of the last checkpoint received.
+        let stream = Box::pin(checkpoint_info_items.scan(None, move |last_seq, item| {
+            let mut process_item = |item: SuiResult<CheckpointStreamResponseItem>| -> SuiResult<CheckpointStreamResponseItem> {
                 let item = item?;
-                let CheckpointStreamResponseItem { ref checkpoint, .. } = item;
-                checkpoint.verify(&client.get_committee(&checkpoint.epoch())?, None)?;
+                let seq = item.checkpoint.sequence_number();
+                fp_ensure!(
+                    item.first_available_sequence <= seq,
+                    client.checkpoint_suspicion(format!(
+                        "Streamed checkpoint {seq} is before the first available checkpoint {}",
+                        item.first_available_sequence
+                    ))
+                );
+                if let Some(last_seq) = *last_seq {
+                    fp_ensure!(
+                        seq > last_seq,
+                        client.checkpoint_suspicion(format!(
+                            "Streamed checkpoint {seq} is not after the previous one {last_seq}"
+                        ))
+                    );
+                }
+                client.verify_authenticated_checkpoint(&item.checkpoint, None)?;
+                *last_seq = Some(seq);
                 Ok(item)
             };
-            futures::future::ready(Some(process_item(item)))
+            let result = process_item(item).tap_err(|err| {
+                if matches!(err, SuiError::ByzantineAuthoritySuspicion { .. }) {
+                    client.metrics_total_invalid_checkpoint_responses.inc();
+                    error!(?err, authority=?client.address, "Client error in handle_checkpoint_stream");
+                }
+            });
+            futures::future::ready(Some(result))
         }));
         Ok(Box::pin(stream))
     }
@@ -784,6 +1343,30 @@ where
     pub async fn handle_batch_stream(
         &self,
         request: BatchInfoRequest,
+    ) -> Result<BatchInfoResponseItemStream, SuiError> {
+        self.handle_batch_stream_from(request, BatchStreamCursor::default())
+            .await
+    }
+
+    /// Stream the batches and transactions of this authority from `cursor`, i.e. from its latest
+    /// batch if no batch was validated through the cursor yet, or from the last batch validated
+    /// after a disconnection. The cursor advances as the batches streamed are validated.
+    pub async fn resume_batch_stream(
+        &self,
+        cursor: &BatchStreamCursor,
+        length: u64,
+    ) -> Result<BatchInfoResponseItemStream, SuiError> {
+        let request = BatchInfoRequest {
+            start: cursor.next_sequence_number(),
+            length,
+        };
+        self.handle_batch_stream_from(request, cursor.clone()).await
+    }
+
+    async fn handle_batch_stream_from(
+        &self,
+        request: BatchInfoRequest,
+        cursor: BatchStreamCursor,
     ) -> Result<BatchInfoResponseItemStream, SuiError> {
         self.metrics_total_requests_handle_batch_stream.inc();
         let batch_info_items = self
@@ -794,9 +1377,10 @@ where
         let client = self.clone();
         let address = self.address;
         let count: u64 = 0;
+        let resumed_from = cursor.last_batch();
         let stream = Box::pin(batch_info_items.scan(
-            (None, count),
-            move |(txs_and_last_batch, count), batch_info_item| {
+            (None, resumed_from, count),
+            move |(txs_and_last_batch, resumed_from, count), batch_info_item| {
                 let req_clone = request.clone();
                 let client = client.clone();
 
@@ -812,10 +1396,12 @@ where
                             req_clone,
                             signed_batch,
                             txs_and_last_batch,
+                            resumed_from.as_ref(),
                         ) {
                             error!(?err, authority=?address, "Client error in handle_batch_stream");
                             Some(Err(err))
                         } else {
+                            cursor.advance(signed_batch.data());
                             // Insert a fresh vector for the new batch of transactions
                             let _ = txs_and_last_batch
                                 .insert((Vec::new(), signed_batch.data().clone()));
@@ -834,6 +1420,8 @@ where
                                 error!(?err, authority=?address, "Client error in handle_batch_stream");
                                 Some(Err(err))
                             }
+                            // Transactions are checked once the batch covering them arrives,
+                            // which may skip sequence numbers never executed.
                             Some(txs) => {
                                 txs.0.push((*seq, *digest));
 
@@ -851,3 +1439,53 @@ where
         Ok(Box::pin(stream))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::messages::make_random_certified_transaction;
+    use test_utils::test_committee;
+
+    #[tokio::test]
+    async fn test_spawn_verification() {
+        let committee = Arc::new(test_committee());
+        let cert = make_random_certified_transaction();
+        let mut forged = cert.clone();
+        forged.auth_sign_info.signature =
+            make_random_certified_transaction().auth_sign_info.signature;
+
+        let verify = |cert: CertifiedTransaction| {
+            let committee = committee.clone();
+            spawn_verification(move || cert.verify(&committee))
+        };
+        verify(cert).await.unwrap();
+        assert!(verify(forged).await.is_err());
+    }
+
+    #[test]
+    fn test_verified_cert_cache() {
+        let metrics = SafeClientMetrics::new_for_tests();
+        let cache = VerifiedCertificateCache::new(1, &metrics);
+        let committee = test_committee();
+        let cert1 = make_random_certified_transaction();
+        let cert2 = make_random_certified_transaction();
+
+        cache.verify(&cert1, &committee).unwrap();
+        cache.verify(&cert1, &committee).unwrap();
+        assert_eq!(metrics.verified_cert_cache_misses.get(), 1);
+        assert_eq!(metrics.verified_cert_cache_hits.get(), 1);
+
+        // A known certificate with other signatures is verified again.
+        let mut forged = cert1.clone();
+        forged.auth_sign_info.signature = cert2.auth_sign_info.signature.clone();
+        assert!(cache.verify(&forged, &committee).is_err());
+        assert_eq!(metrics.verified_cert_cache_misses.get(), 2);
+
+        // The oldest certificate is evicted when the cache is full.
+        cache.verify(&cert2, &committee).unwrap();
+        cache.verify(&cert1, &committee).unwrap();
+        assert_eq!(metrics.verified_cert_cache_misses.get(), 4);
+        assert_eq!(metrics.verified_cert_cache_hits.get(), 1);
+        assert_eq!(metrics.verified_cert_cache_size.get(), 1);
+    }
+}
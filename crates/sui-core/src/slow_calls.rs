@@ -0,0 +1,392 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::warn;
+
+/// Latency above which calls are recorded as slow, unless configured otherwise.
+pub const DEFAULT_SLOW_CALL_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Number of slow calls kept for the admin API, unless configured otherwise.
+pub const DEFAULT_SLOW_CALL_LOG_SIZE: usize = 100;
+
+/// Max length of the parameters recorded with a slow call.
+const MAX_PARAMS_LEN: usize = 512;
+
+/// JSON strings longer than this, e.g. transaction bytes, signatures and public keys, are
+/// replaced by their length. Addresses and object IDs are shorter.
+const MAX_PARAM_STRING_LEN: usize = 66;
+
+/// Max number of calls of a method waiting for their result in `PendingCalls`. Calls whose
+/// result never comes, e.g. because the client disconnected, are dropped past this number.
+const MAX_PENDING_CALLS_PER_METHOD: usize = 1024;
+
+/// Max number of methods with calls waiting for their result in `PendingCalls`, as method names
+/// come from clients.
+const MAX_PENDING_METHODS: usize = 1024;
+
+/// Number of independently locked shards of `PendingCalls`, by method.
+const PENDING_CALLS_SHARDS: usize = 16;
+
+/// Parameters longer than this are only kept by their length in `PendingCalls`, so that calls
+/// do not copy large parameters in case they turn out slow.
+const MAX_PENDING_PARAMS_LEN: usize = 16 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallServer {
+    JsonRpc,
+    Grpc,
+}
+
+impl CallServer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallServer::JsonRpc => "json-rpc",
+            CallServer::Grpc => "grpc",
+        }
+    }
+}
+
+impl fmt::Display for CallServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CallServer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json-rpc" => Ok(CallServer::JsonRpc),
+            "grpc" => Ok(CallServer::Grpc),
+            _ => Err(format!("unknown server {s}, expected json-rpc or grpc")),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SlowCall {
+    pub server: CallServer,
+    pub method: String,
+    /// The parameters of the call, without long values and truncated.
+    pub params: String,
+    pub latency: Duration,
+    pub success: bool,
+    pub finished_at: SystemTime,
+}
+
+/// SlowCallLog records the calls to the JSON-RPC and gRPC servers which take longer than a
+/// threshold, so that operators can find the requests loading a node. The most recent slow
+/// calls are kept for the admin API, and all of them are counted in the metrics and logged.
+pub struct SlowCallLog {
+    json_rpc_threshold_ms: AtomicU64,
+    grpc_threshold_ms: AtomicU64,
+    capacity: AtomicUsize,
+    calls: Mutex<VecDeque<SlowCall>>,
+    slow_calls: IntCounterVec,
+}
+
+impl SlowCallLog {
+    pub fn new(registry: &Registry) -> Self {
+        let threshold_ms = DEFAULT_SLOW_CALL_THRESHOLD.as_millis() as u64;
+        Self {
+            json_rpc_threshold_ms: AtomicU64::new(threshold_ms),
+            grpc_threshold_ms: AtomicU64::new(threshold_ms),
+            capacity: AtomicUsize::new(DEFAULT_SLOW_CALL_LOG_SIZE),
+            calls: Mutex::new(VecDeque::new()),
+            slow_calls: register_int_counter_vec_with_registry!(
+                "slow_calls",
+                "Number of calls slower than the threshold of their server, by server and method",
+                &["server", "method"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn threshold_ms(&self, server: CallServer) -> &AtomicU64 {
+        match server {
+            CallServer::JsonRpc => &self.json_rpc_threshold_ms,
+            CallServer::Grpc => &self.grpc_threshold_ms,
+        }
+    }
+
+    pub fn threshold(&self, server: CallServer) -> Duration {
+        Duration::from_millis(self.threshold_ms(server).load(Ordering::Relaxed))
+    }
+
+    pub fn set_threshold(&self, server: CallServer, threshold: Duration) {
+        self.threshold_ms(server)
+            .store(threshold.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Changes the number of slow calls kept, dropping the oldest ones if there are more.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut calls = self.calls.lock();
+        while calls.len() > capacity {
+            calls.pop_front();
+        }
+    }
+
+    /// Record the call if it is slow. `params` is only evaluated for slow calls.
+    pub fn record(
+        &self,
+        server: CallServer,
+        method: &str,
+        params: impl FnOnce() -> String,
+        latency: Duration,
+        success: bool,
+    ) {
+        if latency < self.threshold(server) {
+            return;
+        }
+        let params = truncate(params());
+        self.slow_calls
+            .with_label_values(&[server.as_str(), method])
+            .inc();
+        warn!(
+            %server,
+            method,
+            params = %params,
+            latency_ms = latency.as_millis() as u64,
+            success,
+            "Slow call"
+        );
+        let capacity = self.capacity();
+        let mut calls = self.calls.lock();
+        calls.push_back(SlowCall {
+            server,
+            method: method.to_string(),
+            params,
+            latency,
+            success,
+            finished_at: SystemTime::now(),
+        });
+        while calls.len() > capacity {
+            calls.pop_front();
+        }
+    }
+
+    /// The slow calls kept, oldest first.
+    pub fn calls(&self) -> Vec<SlowCall> {
+        self.calls.lock().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.calls.lock().clear();
+    }
+}
+
+/// The JSON parameters of a call with long strings, e.g. signatures and transaction bytes,
+/// replaced by their length. Parameters which are not valid JSON are kept as is, and truncated
+/// when recorded.
+pub fn sanitize_json_params(params: &str) -> String {
+    match serde_json::from_str::<Value>(params) {
+        Ok(mut value) => {
+            redact_long_strings(&mut value);
+            value.to_string()
+        }
+        Err(_) => params.to_string(),
+    }
+}
+
+fn redact_long_strings(value: &mut Value) {
+    match value {
+        Value::String(s) if s.len() > MAX_PARAM_STRING_LEN => {
+            *s = format!("<{} chars>", s.len());
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_long_strings),
+        Value::Object(values) => values.values_mut().for_each(redact_long_strings),
+        _ => {}
+    }
+}
+
+fn truncate(mut params: String) -> String {
+    if params.len() > MAX_PARAMS_LEN {
+        let mut end = MAX_PARAMS_LEN;
+        while !params.is_char_boundary(end) {
+            end -= 1;
+        }
+        params.truncate(end);
+        params.push_str("...");
+    }
+    params
+}
+
+/// Parameters of calls waiting for their result, for servers which report the parameters of a
+/// call and its result separately, as the JSON-RPC middleware does. A result is matched to the
+/// earliest call of the same method made since its request started, so the parameters of
+/// concurrent calls of a method may be swapped. Calls are kept in shards by method, so that
+/// calls of different methods rarely wait for each other.
+pub struct PendingCalls {
+    shards: Vec<Mutex<HashMap<String, VecDeque<(Instant, String)>>>>,
+}
+
+impl Default for PendingCalls {
+    fn default() -> Self {
+        Self {
+            shards: (0..PENDING_CALLS_SHARDS)
+                .map(|_| Mutex::default())
+                .collect(),
+        }
+    }
+}
+
+impl PendingCalls {
+    fn shard(&self, method: &str) -> &Mutex<HashMap<String, VecDeque<(Instant, String)>>> {
+        let mut hasher = DefaultHasher::new();
+        method.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    pub fn on_call(&self, method: &str, params: &str) {
+        let params = if params.len() > MAX_PENDING_PARAMS_LEN {
+            format!("<{} bytes>", params.len())
+        } else {
+            params.to_string()
+        };
+        let mut calls = self.shard(method).lock();
+        if calls.len() >= MAX_PENDING_METHODS / PENDING_CALLS_SHARDS && !calls.contains_key(method)
+        {
+            return;
+        }
+        let pending = calls.entry(method.to_string()).or_default();
+        if pending.len() >= MAX_PENDING_CALLS_PER_METHOD {
+            pending.pop_front();
+        }
+        pending.push_back((Instant::now(), params));
+    }
+
+    /// Take the parameters of the call of `method` made by the request started at
+    /// `request_started_at`.
+    pub fn on_result(&self, method: &str, request_started_at: Instant) -> Option<String> {
+        let mut calls = self.shard(method).lock();
+        let pending = calls.get_mut(method)?;
+        let index = pending
+            .iter()
+            .position(|(called_at, _)| *called_at >= request_started_at);
+        // Calls made before the request started belong to requests which never got a result.
+        let stale = index.unwrap_or(pending.len());
+        pending.drain(..stale);
+        let params = pending.pop_front().map(|(_, params)| params);
+        if pending.is_empty() {
+            calls.remove(method);
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_call_log() {
+        let log = SlowCallLog::new(&Registry::new());
+        log.set_threshold(CallServer::JsonRpc, Duration::from_millis(100));
+        log.set_capacity(2);
+
+        log.record(
+            CallServer::JsonRpc,
+            "sui_getObject",
+            || panic!("parameters of fast calls are not evaluated"),
+            Duration::from_millis(99),
+            true,
+        );
+        assert!(log.calls().is_empty());
+
+        for (method, latency_ms) in [("a", 100), ("b", 200), ("c", 300)] {
+            log.record(
+                CallServer::JsonRpc,
+                method,
+                || "[]".to_string(),
+                Duration::from_millis(latency_ms),
+                true,
+            );
+        }
+        // The default threshold applies to the other server.
+        log.record(
+            CallServer::Grpc,
+            "object_info",
+            String::new,
+            Duration::from_millis(300),
+            false,
+        );
+        let methods: Vec<_> = log.calls().into_iter().map(|call| call.method).collect();
+        assert_eq!(methods, vec!["b", "c"]);
+        assert_eq!(
+            log.slow_calls.with_label_values(&["json-rpc", "a"]).get(),
+            1
+        );
+
+        log.set_capacity(1);
+        assert_eq!(log.calls()[0].method, "c");
+        log.clear();
+        assert!(log.calls().is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_json_params() {
+        let signature = "A".repeat(100);
+        let params = format!(r#"["0x0000000000000000000000000000000000000005","{signature}",10]"#);
+        assert_eq!(
+            sanitize_json_params(&params),
+            r#"["0x0000000000000000000000000000000000000005","<100 chars>",10]"#
+        );
+        assert_eq!(sanitize_json_params("not json"), "not json");
+
+        let long = truncate("é".repeat(MAX_PARAMS_LEN));
+        assert!(long.len() <= MAX_PARAMS_LEN + 3);
+        assert!(long.ends_with("..."));
+    }
+
+    #[test]
+    fn test_pending_calls() {
+        let pending = PendingCalls::default();
+        let started_at = Instant::now();
+        pending.on_call("sui_getObject", "[1]");
+        pending.on_call("sui_getObject", "[2]");
+        pending.on_call("sui_getEvents", "[3]");
+        assert_eq!(
+            pending.on_result("sui_getEvents", started_at).as_deref(),
+            Some("[3]")
+        );
+        assert_eq!(
+            pending.on_result("sui_getObject", started_at).as_deref(),
+            Some("[1]")
+        );
+
+        // A call left without result by an earlier request is discarded.
+        std::thread::sleep(Duration::from_millis(1));
+        let started_at = Instant::now();
+        pending.on_call("sui_getObject", "[4]");
+        assert_eq!(
+            pending.on_result("sui_getObject", started_at).as_deref(),
+            Some("[4]")
+        );
+        assert!(pending.shards.iter().all(|shard| shard.lock().is_empty()));
+        assert_eq!(pending.on_result("sui_getObject", started_at), None);
+
+        // Large parameters are only kept by their length.
+        let large = "1".repeat(MAX_PENDING_PARAMS_LEN + 1);
+        pending.on_call("sui_executeTransaction", &large);
+        assert_eq!(
+            pending.on_result("sui_executeTransaction", started_at),
+            Some(format!("<{} bytes>", large.len()))
+        );
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sampling of high-volume trace spans. Some spans, such as `quorum_map_auth` which is entered
+//! once per validator for every request of the aggregator, drown out other logs when tracing
+//! is enabled. Such spans are only created for one in every N occurrences, N being the sample
+//! interval of their name, set through the node config or the admin API.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub const QUORUM_MAP_AUTH_SPAN: &str = "quorum_map_auth";
+
+struct Sampler {
+    interval: u64,
+    count: AtomicU64,
+}
+
+static SAMPLERS: Lazy<RwLock<HashMap<String, Arc<Sampler>>>> = Lazy::new(Default::default);
+
+/// Create one in every `interval` spans named `span`. 0 and 1 disable sampling.
+pub fn set_sample_interval(span: &str, interval: u64) {
+    let mut samplers = SAMPLERS.write();
+    if interval <= 1 {
+        samplers.remove(span);
+    } else {
+        samplers.insert(
+            span.to_string(),
+            Arc::new(Sampler {
+                interval,
+                count: AtomicU64::new(0),
+            }),
+        );
+    }
+}
+
+/// Sample intervals of the sampled spans, by span name.
+pub fn sample_intervals() -> BTreeMap<String, u64> {
+    SAMPLERS
+        .read()
+        .iter()
+        .map(|(span, sampler)| (span.clone(), sampler.interval))
+        .collect()
+}
+
+/// Whether the next span named `span` should be created.
+pub fn should_sample(span: &str) -> bool {
+    match SAMPLERS.read().get(span) {
+        Some(sampler) => sampler.count.fetch_add(1, Ordering::Relaxed) % sampler.interval == 0,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling() {
+        let span = "test_sampling_span";
+        assert!((0..3).all(|_| should_sample(span)));
+
+        set_sample_interval(span, 3);
+        let sampled = (0..9).filter(|_| should_sample(span)).count();
+        assert_eq!(sampled, 3);
+        assert_eq!(sample_intervals().get(span), Some(&3));
+
+        set_sample_interval(span, 0);
+        assert!(should_sample(span));
+        assert!(sample_intervals().get(span).is_none());
+    }
+}
@@ -5,7 +5,6 @@ use crate::authority::SuiDataStore;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Debug;
-use sui_types::base_types::ObjectRef;
 use sui_types::messages::TransactionKind;
 use sui_types::{
     base_types::{SequenceNumber, SuiAddress},
@@ -17,6 +16,7 @@ use sui_types::{
         TransactionData, TransactionEnvelope,
     },
     object::{Object, Owner},
+    transaction_checks,
 };
 use tracing::instrument;
 
@@ -27,14 +27,7 @@ async fn get_gas_status<S, T>(
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
-    let mut gas_status = check_gas(
-        store,
-        transaction.gas_payment_object_ref(),
-        transaction.signed_data.data.gas_budget,
-        transaction.signed_data.data.gas_price,
-        &transaction.signed_data.data.kind,
-    )
-    .await?;
+    let mut gas_status = check_gas(store, &transaction.signed_data.data).await?;
 
     if transaction.contains_shared_object() {
         // It's important that we do this here to make sure there is enough
@@ -53,7 +46,7 @@ pub async fn check_transaction_input<S, T>(
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
-    transaction.signed_data.data.kind.validity_check()?;
+    transaction_checks::check_transaction_data(&transaction.signed_data.data)?;
     let gas_status = get_gas_status(store, transaction).await?;
     let input_objects = transaction.signed_data.data.input_objects()?;
     let objects = store.get_input_objects(&input_objects)?;
@@ -84,30 +77,35 @@ where
     Ok((gas_status, input_objects))
 }
 
-/// Checking gas budget by fetching the gas object only from the store,
-/// and check whether the balance and budget satisfies the miminum requirement.
-/// Returns the gas object (to be able to reuse it latter) and a gas status
+/// Checking gas budget by fetching the gas objects only from the store,
+/// and check whether their total balance and budget satisfies the miminum requirement.
+/// Returns a gas status
 /// that will be used in the entire lifecycle of the transaction execution.
 #[instrument(level = "trace", skip_all)]
 async fn check_gas<S>(
     store: &SuiDataStore<S>,
-    gas_payment: &ObjectRef,
-    gas_budget: u64,
-    computation_gas_price: u64,
-    tx_kind: &TransactionKind,
+    data: &TransactionData,
 ) -> SuiResult<SuiGasStatus<'static>>
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
+    let gas_budget = data.gas_budget;
+    let computation_gas_price = data.gas_price;
+    let tx_kind = &data.kind;
     if tx_kind.is_system_tx() {
         Ok(SuiGasStatus::new_unmetered())
     } else {
-        let gas_object = store.get_object_by_key(&gas_payment.0, gas_payment.1)?;
-        let gas_object = gas_object.ok_or(SuiError::ObjectErrors {
-            errors: vec![SuiError::ObjectNotFound {
-                object_id: gas_payment.0,
-            }],
-        })?;
+        let mut gas_objects = Vec::new();
+        for (object_id, version, _) in data.gas_coins() {
+            let gas_object = store.get_object_by_key(object_id, *version)?;
+            let gas_object = gas_object.ok_or(SuiError::ObjectErrors {
+                errors: vec![SuiError::ObjectNotFound {
+                    object_id: *object_id,
+                }],
+            })?;
+            transaction_checks::check_gas_object(data, &gas_object)?;
+            gas_objects.push(gas_object);
+        }
 
         //TODO: cache this storage_gas_price in memory
         let storage_gas_price = store
@@ -126,7 +124,7 @@ where
         // TODO: We should revisit how we compute gas price and compare to gas budget.
         let gas_price = std::cmp::max(computation_gas_price, storage_gas_price);
 
-        gas::check_gas_balance(&gas_object, gas_budget, gas_price, extra_amount)?;
+        gas::check_gas_balance(&gas_objects, gas_budget, gas_price, extra_amount)?;
         let gas_status =
             gas::start_gas_metering(gas_budget, computation_gas_price, storage_gas_price)?;
         Ok(gas_status)
@@ -7,6 +7,7 @@ submit transactions to validators for finality, and proactively executes
 finalized transactions locally, with the help of Node Sync.
 */
 use prometheus::core::{AtomicI64, AtomicU64, GenericCounter, GenericGauge};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio_stream::StreamExt;
@@ -14,12 +15,16 @@ use tokio_stream::StreamExt;
 use crate::authority::AuthorityState;
 use crate::authority_aggregator::AuthorityAggregator;
 use crate::authority_client::AuthorityAPI;
+use crate::deny_list::DenyStage;
 use crate::node_sync::{NodeSyncHandle, SyncStatus};
-use crate::quorum_driver::{QuorumDriver, QuorumDriverHandler, QuorumDriverMetrics};
+use crate::quorum_driver::{
+    FinalityTracker, QuorumDriver, QuorumDriverHandler, QuorumDriverMetrics,
+};
 use prometheus::{
     register_int_counter_vec_with_registry, register_int_counter_with_registry,
     register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Registry,
 };
+use sui_storage::submission_store::SubmissionStore;
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
     CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionRequest,
@@ -35,14 +40,24 @@ use tracing::{debug, error, instrument, warn, Instrument};
 
 // How long to wait for local execution (including parents) before a timeout
 // is returned to client.
-const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(5);
+pub const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+// The longest wait for local execution a client can request.
+pub const MAX_LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+// How often transactions finalized by the quorum driver are checked for checkpoint inclusion.
+const CHECKPOINT_INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct TransactiondOrchestrator<A> {
     quorum_driver_handler: QuorumDriverHandler<A>,
     quorum_driver: Arc<QuorumDriver<A>>,
     node_sync_handle: NodeSyncHandle,
     validator_state: Arc<AuthorityState>,
+    submission_store: Option<Arc<SubmissionStore>>,
+    read_only: AtomicBool,
+    write_through: AtomicBool,
     _local_executor_handle: JoinHandle<()>,
+    _checkpoint_inclusion_handle: JoinHandle<()>,
     metrics: Arc<TransactionOrchestratorMetrics>,
 }
 
@@ -54,6 +69,7 @@ where
         validators: Arc<AuthorityAggregator<A>>,
         validator_state: Arc<AuthorityState>,
         node_sync_handle: NodeSyncHandle,
+        submission_store: Option<Arc<SubmissionStore>>,
         prometheus_registry: &Registry,
     ) -> Self {
         let quorum_driver_handler =
@@ -75,21 +91,122 @@ where
                 .await;
             })
         };
+        let _checkpoint_inclusion_handle =
+            tokio::task::spawn(Self::loop_check_checkpoint_inclusion(
+                validator_state.clone(),
+                quorum_driver.finality_tracker().clone(),
+            ));
         Self {
             quorum_driver_handler,
             quorum_driver,
             validator_state,
             node_sync_handle,
+            submission_store,
+            read_only: AtomicBool::new(false),
+            write_through: AtomicBool::new(false),
             _local_executor_handle,
+            _checkpoint_inclusion_handle,
             metrics,
         }
     }
 
-    #[instrument(name = "tx_orchestrator_execute_transaction", level = "debug", skip_all, fields(request_type = ?request.request_type), err)]
+    /// In read-only mode, transactions are rejected with `SuiError::NodeReadOnly` and the node
+    /// keeps executing the transactions it syncs.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+        self.metrics.read_only.set(read_only as i64);
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// In write-through mode, every transaction is executed locally before its response is
+    /// returned, as if it was submitted with `WaitForLocalExecution`.
+    pub fn set_write_through(&self, write_through: bool) {
+        self.write_through.store(write_through, Ordering::Relaxed);
+    }
+
+    pub fn is_write_through(&self) -> bool {
+        self.write_through.load(Ordering::Relaxed)
+    }
+
+    /// Rejects transactions in read-only mode, or while the node halts their intake.
+    fn check_not_read_only(&self) -> SuiResult {
+        if self.is_read_only() {
+            self.metrics.rejected_read_only.inc();
+            return Err(SuiError::NodeReadOnly);
+        }
+        self.validator_state.check_intake()
+    }
+
+    /// Execute a transaction at most once per `request_id`.
+    /// The request id is bound to the transaction digest before the transaction is submitted,
+    /// and the response is persisted once the submission completes. Retries with the same
+    /// request id return the persisted response. If the node restarted before the first
+    /// submission completed, the transaction is submitted again, which is safe since
+    /// executing a transaction is idempotent on its digest.
+    #[instrument(
+        name = "tx_orchestrator_execute_transaction_with_request_id",
+        level = "debug",
+        skip(self, request),
+        err
+    )]
+    pub async fn execute_transaction_with_request_id(
+        &self,
+        request_id: String,
+        request: ExecuteTransactionRequest,
+        local_execution_timeout: Duration,
+    ) -> SuiResult<ExecuteTransactionResponse> {
+        // Checked before binding the request id, which could otherwise be left without a response.
+        self.check_not_read_only()?;
+        let submission_store =
+            self.submission_store
+                .as_ref()
+                .ok_or_else(|| SuiError::UnsupportedFeatureError {
+                    error: "Submission store is not enabled in this node".to_string(),
+                })?;
+        let tx_digest = *request.transaction.digest();
+        let _lock = submission_store.acquire_lock(&request_id).await;
+        let record = submission_store.begin(&request_id, &tx_digest)?;
+        if let Some(response) = record.response {
+            debug!(
+                ?tx_digest,
+                "Returning stored response for duplicate submission"
+            );
+            self.metrics.duplicate_submissions.inc();
+            return Ok(response);
+        }
+        let response = self
+            .execute_transaction_with_timeout(request, local_execution_timeout)
+            .await?;
+        submission_store.complete(&request_id, &tx_digest, &response)?;
+        Ok(response)
+    }
+
     pub async fn execute_transaction(
         &self,
         request: ExecuteTransactionRequest,
     ) -> SuiResult<ExecuteTransactionResponse> {
+        self.execute_transaction_with_timeout(request, LOCAL_EXECUTION_TIMEOUT)
+            .await
+    }
+
+    /// Execute a transaction, waiting at most `local_execution_timeout`, capped to
+    /// `MAX_LOCAL_EXECUTION_TIMEOUT`, for its local execution if requested.
+    #[instrument(name = "tx_orchestrator_execute_transaction", level = "debug", skip_all, fields(request_type = ?request.request_type), err)]
+    pub async fn execute_transaction_with_timeout(
+        &self,
+        mut request: ExecuteTransactionRequest,
+        local_execution_timeout: Duration,
+    ) -> SuiResult<ExecuteTransactionResponse> {
+        self.check_not_read_only()?;
+        self.validator_state
+            .deny_list
+            .check(&request.transaction.signed_data.data, DenyStage::Submission)?;
+        if self.is_write_through() {
+            request.request_type = ExecuteTransactionRequestType::WaitForLocalExecution;
+        }
         let (_in_flight_metrics_guard, good_response_metrics) =
             self.update_metrics(&request.request_type);
         // TODO check if tx is already executed on this node.
@@ -141,6 +258,7 @@ where
                     &self.node_sync_handle,
                     &tx_cert,
                     &effects_cert,
+                    local_execution_timeout.min(MAX_LOCAL_EXECUTION_TIMEOUT),
                     &self.metrics,
                 )
                 .await
@@ -166,6 +284,7 @@ where
         node_sync_handle: &NodeSyncHandle,
         tx_cert: &CertifiedTransaction,
         effects_cert: &CertifiedTransactionEffects,
+        local_execution_timeout: Duration,
         metrics: &TransactionOrchestratorMetrics,
     ) -> SuiResult {
         // TODO: attempt a finalized tx at most once per request.
@@ -186,7 +305,7 @@ where
                 in_flight.dec();
             });
         match timeout(
-            LOCAL_EXECUTION_TIMEOUT,
+            local_execution_timeout,
             Self::execute_impl(
                 validator_state,
                 node_sync_handle,
@@ -201,7 +320,7 @@ where
                 debug!(
                     ?tx_digest,
                     "Executing tx locally by orchestrator timed out within {:?}.",
-                    LOCAL_EXECUTION_TIMEOUT
+                    local_execution_timeout
                 );
                 metrics.local_execution_timeout.inc();
                 Err(SuiError::TimeoutError)
@@ -237,6 +356,7 @@ where
                         &node_sync_handle,
                         &tx_cert,
                         &effects_cert,
+                        LOCAL_EXECUTION_TIMEOUT,
                         &metrics,
                     )
                     .await;
@@ -252,6 +372,31 @@ where
         }
     }
 
+    async fn loop_check_checkpoint_inclusion(
+        validator_state: Arc<AuthorityState>,
+        finality_tracker: Arc<FinalityTracker>,
+    ) {
+        let mut interval = tokio::time::interval(CHECKPOINT_INCLUSION_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if finality_tracker.num_pending_checkpoint() == 0 {
+                continue;
+            }
+            let checkpoints = validator_state.checkpoints();
+            // The checkpoints lock is taken once per batch of waiting transactions.
+            let result = finality_tracker.check_checkpoints(|digests| {
+                Ok(checkpoints
+                    .lock()
+                    .tables
+                    .transactions_to_checkpoint
+                    .multi_get(digests)?)
+            });
+            if let Err(err) = result {
+                warn!("Failed to check checkpoint inclusion of transactions: {err}");
+            }
+        }
+    }
+
     pub fn quorum_driver(&self) -> &Arc<QuorumDriver<A>> {
         &self.quorum_driver
     }
@@ -399,6 +544,11 @@ pub struct TransactionOrchestratorMetrics {
     tx_directly_executed: GenericCounter<AtomicU64>,
     tx_executed_via_node_sync: GenericCounter<AtomicU64>,
     tx_not_executed: GenericCounter<AtomicU64>,
+
+    duplicate_submissions: GenericCounter<AtomicU64>,
+
+    read_only: GenericGauge<AtomicI64>,
+    rejected_read_only: GenericCounter<AtomicU64>,
 }
 
 impl TransactionOrchestratorMetrics {
@@ -505,6 +655,24 @@ impl TransactionOrchestratorMetrics {
                 registry,
             )
             .unwrap(),
+            duplicate_submissions: register_int_counter_with_registry!(
+                "tx_orchestrator_duplicate_submissions",
+                "Total number of submissions Transaction Orchestrator answered from the submission store",
+                registry,
+            )
+            .unwrap(),
+            read_only: register_int_gauge_with_registry!(
+                "tx_orchestrator_read_only",
+                "Whether Transaction Orchestrator rejects transactions because the node is in read-only mode",
+                registry,
+            )
+            .unwrap(),
+            rejected_read_only: register_int_counter_with_registry!(
+                "tx_orchestrator_rejected_read_only",
+                "Total number of txns Transaction Orchestrator rejected in read-only mode",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
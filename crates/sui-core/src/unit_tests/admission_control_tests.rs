@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+
+fn test_config() -> AdmissionControlConfig {
+    AdmissionControlConfig {
+        max_concurrent_certificates: 5,
+        max_concurrent_transactions: 5,
+        known_peers: vec!["10.0.0.1".parse().unwrap()],
+        known_peer_weight: 4,
+        unknown_peer_weight: 1,
+        queue_timeout_ms: 50,
+    }
+}
+
+#[test]
+fn test_classify_peers() {
+    let controller = AdmissionController::new(&test_config(), &Registry::new());
+    assert_eq!(
+        controller.classify(Some("10.0.0.1:4000".parse().unwrap())),
+        PeerClass::Known
+    );
+    assert_eq!(
+        controller.classify(Some("10.0.0.2:4000".parse().unwrap())),
+        PeerClass::Unknown
+    );
+    assert_eq!(controller.classify(None), PeerClass::Unknown);
+}
+
+#[tokio::test]
+async fn test_capacity_is_shared_by_weight() {
+    let controller = AdmissionController::new(&test_config(), &Registry::new());
+
+    // Unknown peers get 1/5 of the transaction capacity.
+    let _unknown = controller
+        .admit(PeerClass::Unknown, RequestKind::Transaction)
+        .await
+        .unwrap();
+    assert!(controller
+        .admit(PeerClass::Unknown, RequestKind::Transaction)
+        .await
+        .is_none());
+
+    // Known peers still have their own 4/5.
+    let mut known = vec![];
+    for _ in 0..4 {
+        known.push(
+            controller
+                .admit(PeerClass::Known, RequestKind::Transaction)
+                .await
+                .unwrap(),
+        );
+    }
+    assert!(controller
+        .admit(PeerClass::Known, RequestKind::Transaction)
+        .await
+        .is_none());
+
+    // Releasing a permit frees a slot.
+    known.pop();
+    assert!(controller
+        .admit(PeerClass::Known, RequestKind::Transaction)
+        .await
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_certificates_take_priority() {
+    let controller = Arc::new(AdmissionController::new(&test_config(), &Registry::new()));
+
+    let permit = controller
+        .admit(PeerClass::Unknown, RequestKind::Certificate)
+        .await
+        .unwrap();
+
+    // A second certificate from the same class has to wait for a slot.
+    let waiting = {
+        let controller = controller.clone();
+        tokio::spawn(async move {
+            controller
+                .admit(PeerClass::Unknown, RequestKind::Certificate)
+                .await
+                .is_some()
+        })
+    };
+    tokio::task::yield_now().await;
+
+    // While it waits, transactions are rejected even though there is transaction capacity.
+    assert!(controller
+        .admit(PeerClass::Known, RequestKind::Transaction)
+        .await
+        .is_none());
+
+    drop(permit);
+    assert!(waiting.await.unwrap());
+    assert!(controller
+        .admit(PeerClass::Known, RequestKind::Transaction)
+        .await
+        .is_some());
+}
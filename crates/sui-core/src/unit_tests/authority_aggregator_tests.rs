@@ -5,17 +5,26 @@ use bcs::to_bytes;
 use move_core_types::{account_address::AccountAddress, ident_str};
 use move_package::BuildConfig;
 use multiaddr::Multiaddr;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use sui_config::genesis::Genesis;
 use sui_config::ValidatorInfo;
 use sui_network::{DEFAULT_CONNECT_TIMEOUT_SEC, DEFAULT_REQUEST_TIMEOUT_SEC};
+use sui_types::bridge::{
+    BridgeAttestationRequest, BridgeAttestationResponse, BridgeMessage, SignedBridgeAttestation,
+    BRIDGE_MESSAGE_VERSION,
+};
 use sui_types::crypto::{
     generate_proof_of_possession, get_authority_key_pair, get_key_pair, AccountKeyPair,
     AuthorityKeyPair, AuthorityPublicKeyBytes, NetworkKeyPair, SuiKeyPair,
 };
-use sui_types::crypto::{KeypairTraits, Signature};
+use sui_types::crypto::{AuthoritySignInfoTrait, KeypairTraits, Signature};
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{
+    CheckpointProposal, CheckpointProposalContents, CheckpointSummary, SignedCheckpointSummary,
+};
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 use test_utils::sui_system_state::{test_sui_system_state, test_validator};
 
 use sui_macros::sim_test;
@@ -146,6 +155,8 @@ pub async fn init_local_authorities_with_genesis(
         post_quorum_timeout: Duration::from_secs(5),
         serial_authority_request_timeout: Duration::from_secs(1),
         serial_authority_request_interval: Duration::from_secs(1),
+        hedging: None,
+        adaptive: None,
     };
     let committee_store = Arc::new(CommitteeStore::new_for_testing(&committee));
     (
@@ -797,6 +808,71 @@ async fn test_sync_all_owned_objects() {
             .filter(|(o, _, _)| o.owner == addr1)
             .count()
     );
+
+    // Test 3: All authorities are now in sync, so the objects are only downloaded, once each.
+    let downloads = authorities.metrics.total_object_downloads.get();
+    let (owned_object, deleted) = authorities
+        .sync_all_owned_objects(addr1, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(2, owned_object.len());
+    assert!(deleted.is_empty());
+    assert!(owned_object
+        .iter()
+        .all(|(o, layout, cert)| o.owner == addr1 && layout.is_none() && cert.is_none()));
+    assert_eq!(
+        downloads + 2,
+        authorities.metrics.total_object_downloads.get()
+    );
+}
+
+#[sim_test]
+async fn test_fetch_objects_from_authorities_dedup() {
+    let (addr1, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_object1 = Object::with_owner_for_testing(addr1);
+    let gas_object2 = Object::with_owner_for_testing(addr1);
+    let (authorities, _, _) =
+        init_local_authorities(4, vec![gas_object1.clone(), gas_object2.clone()]).await;
+    let authority_clients: Vec<_> = authorities.authority_clients.values().collect();
+    let gas_ref_1 = get_latest_ref(authority_clients[0], gas_object1.id()).await;
+    let gas_ref_2 = get_latest_ref(authority_clients[0], gas_object2.id()).await;
+    let mut stale_ref = gas_ref_2;
+    stale_ref.2 = ObjectDigest::MIN;
+
+    let mut receiver =
+        authorities.fetch_objects_from_authorities(BTreeSet::from([gas_ref_1, stale_ref]));
+    let mut results = Vec::new();
+    while let Some(result) = receiver.recv().await {
+        results.push(result);
+    }
+    assert_eq!(2, results.len());
+    assert!(results
+        .iter()
+        .any(|r| matches!(r, Ok(o) if o.compute_object_reference() == gas_ref_1)));
+    assert!(results.iter().any(|r| matches!(
+        r,
+        Err(SuiError::ObjectFetchFailed { object_id, .. }) if *object_id == gas_object2.id()
+    )));
+    assert_eq!(2, authorities.metrics.total_object_downloads.get());
+
+    // Concurrent downloads of the same object are served by a single download.
+    let clients = Arc::new(
+        authorities
+            .authority_clients
+            .iter()
+            .map(|(name, client)| (*name, client.clone()))
+            .collect::<Vec<_>>(),
+    );
+    let downloader = &authorities.object_downloader;
+    let timeout = Duration::from_secs(10);
+    let (object1, object2) = futures::join!(
+        downloader.download(gas_ref_2, clients.clone(), timeout),
+        downloader.download(gas_ref_2, clients.clone(), timeout),
+    );
+    assert_eq!(gas_ref_2, object1.unwrap().compute_object_reference());
+    assert_eq!(gas_ref_2, object2.unwrap().compute_object_reference());
+    assert_eq!(3, authorities.metrics.total_object_downloads.get());
+    assert_eq!(1, authorities.metrics.total_deduped_object_downloads.get());
 }
 
 #[sim_test]
@@ -838,6 +914,142 @@ async fn test_execute_cert_to_true_effects() {
     assert!(count >= 2);
 }
 
+#[sim_test]
+async fn test_get_effects_by_digest() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_object1 = Object::with_owner_for_testing(addr1);
+    let (authorities, _, pkg_ref) = init_local_authorities(4, vec![gas_object1.clone()]).await;
+    let authority_clients: Vec<_> = authorities.authority_clients.values().collect();
+
+    let gas_ref_1 = get_latest_ref(authority_clients[0], gas_object1.id()).await;
+    let create1 = crate_object_move_transaction(addr1, &key1, addr1, 100, pkg_ref, gas_ref_1);
+    do_transaction(authority_clients[0], &create1).await;
+    do_transaction(authority_clients[1], &create1).await;
+    do_transaction(authority_clients[2], &create1).await;
+    let cert1 = extract_cert(&authority_clients, &authorities.committee, create1.digest()).await;
+
+    // Only one authority has executed the certificate.
+    let effects_digest = do_cert(authority_clients[0], &cert1).await.digest();
+    assert!(authority_clients[1]
+        .handle_effects_info_request(effects_digest.into())
+        .await
+        .unwrap()
+        .signed_effects
+        .is_none());
+
+    let signed_effects = authorities
+        .handle_effects_info_request(&effects_digest, None, None)
+        .await
+        .unwrap();
+    assert_eq!(signed_effects.digest(), &effects_digest);
+    assert_eq!(signed_effects.effects.transaction_digest, *cert1.digest());
+}
+
+#[sim_test]
+async fn test_get_transactions_info() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_object1 = Object::with_owner_for_testing(addr1);
+    let gas_object2 = Object::with_owner_for_testing(addr1);
+    let (authorities, _, pkg_ref) =
+        init_local_authorities(4, vec![gas_object1.clone(), gas_object2.clone()]).await;
+    let authority_clients: Vec<_> = authorities.authority_clients.values().collect();
+
+    // Each certificate is executed by a single authority, a different one for each.
+    let mut certs = Vec::new();
+    for (i, gas_object) in [&gas_object1, &gas_object2].into_iter().enumerate() {
+        let gas_ref = get_latest_ref(authority_clients[0], gas_object.id()).await;
+        let create = crate_object_move_transaction(addr1, &key1, addr1, 100, pkg_ref, gas_ref);
+        do_transaction(authority_clients[0], &create).await;
+        do_transaction(authority_clients[1], &create).await;
+        do_transaction(authority_clients[2], &create).await;
+        let cert = extract_cert(&authority_clients, &authorities.committee, create.digest()).await;
+        do_cert(authority_clients[i], &cert).await;
+        certs.push(cert);
+    }
+    // Ask for the second transaction first, to check the order of the responses.
+    let digests = vec![*certs[1].digest(), *certs[0].digest()];
+
+    // An authority which has not executed the certificates returns a response for each of them.
+    let response = authority_clients[3]
+        .handle_transactions_info_request(digests.clone().into())
+        .await
+        .unwrap();
+    assert_eq!(response.responses.len(), 2);
+    assert!(response
+        .responses
+        .iter()
+        .all(|r| r.signed_effects.is_none()));
+
+    // The transactions are fetched from the authorities that have them, and one that no
+    // authority has only fails its own result.
+    let unknown = TransactionDigest::random();
+    let results = authorities
+        .handle_transactions_and_effects_info_request(
+            &[digests[0], unknown, digests[1]],
+            None,
+            None,
+        )
+        .await;
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        results[1],
+        Err(SuiError::TransactionNotFound { digest }) if digest == unknown
+    ));
+    for (result, digest) in [&results[0], &results[2]].into_iter().zip(&digests) {
+        let (cert, effects) = result.as_ref().unwrap();
+        assert_eq!(cert.digest(), digest);
+        assert_eq!(&effects.effects.transaction_digest, digest);
+    }
+
+    // Requests for too many transactions are rejected.
+    let err = authority_clients[0]
+        .handle_transactions_info_request(
+            vec![*certs[0].digest(); MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS + 1].into(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SuiError::TooManyTransactionDigests { .. }));
+}
+
+#[sim_test]
+async fn test_get_transaction_finality_package() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_object1 = Object::with_owner_for_testing(addr1);
+    let (authorities, _, pkg_ref) = init_local_authorities(4, vec![gas_object1.clone()]).await;
+    let authority_clients: Vec<_> = authorities.authority_clients.values().collect();
+
+    let gas_ref_1 = get_latest_ref(authority_clients[0], gas_object1.id()).await;
+    let create1 = crate_object_move_transaction(addr1, &key1, addr1, 100, pkg_ref, gas_ref_1);
+    do_transaction(authority_clients[0], &create1).await;
+    do_transaction(authority_clients[1], &create1).await;
+    do_transaction(authority_clients[2], &create1).await;
+    let cert1 = extract_cert(&authority_clients, &authorities.committee, create1.digest()).await;
+
+    // The effects of a single authority are not final.
+    let effects_digest = do_cert(authority_clients[0], &cert1).await.digest();
+    assert!(authorities
+        .get_transaction_finality_package(cert1.digest())
+        .await
+        .is_err());
+
+    do_cert(authority_clients[1], &cert1).await;
+    do_cert(authority_clients[2], &cert1).await;
+    let package = authorities
+        .get_transaction_finality_package(cert1.digest())
+        .await
+        .unwrap();
+    assert_eq!(package.certificate.digest(), cert1.digest());
+    assert_eq!(package.effects.digest(), &effects_digest);
+    package
+        .effects
+        .auth_signature
+        .verify(&package.effects.effects, &authorities.committee)
+        .unwrap();
+    assert_eq!(package.events, package.effects.effects.events);
+    // No checkpoint was certified.
+    assert!(package.checkpoint.is_none());
+}
+
 #[sim_test]
 async fn test_process_transaction_fault_success() {
     // This test exercises the 4 different possible fauling case when one authority is faulty.
@@ -906,6 +1118,9 @@ struct MockAuthorityApi {
     count: Arc<Mutex<u32>>,
     handle_committee_info_request_result: Option<SuiResult<CommitteeInfoResponse>>,
     handle_object_info_request_result: Option<SuiResult<ObjectInfoResponse>>,
+    handle_certificate_result: Option<SuiResult<TransactionInfoResponse>>,
+    handle_bridge_attestation_request_result: Option<SuiResult<BridgeAttestationResponse>>,
+    handle_checkpoint_result: Option<SuiResult<CheckpointResponse>>,
 }
 
 impl MockAuthorityApi {
@@ -915,6 +1130,9 @@ impl MockAuthorityApi {
             count,
             handle_committee_info_request_result: None,
             handle_object_info_request_result: None,
+            handle_certificate_result: None,
+            handle_bridge_attestation_request_result: None,
+            handle_checkpoint_result: None,
         }
     }
     pub fn set_handle_committee_info_request_result(
@@ -927,6 +1145,21 @@ impl MockAuthorityApi {
     pub fn set_handle_object_info_request(&mut self, result: SuiResult<ObjectInfoResponse>) {
         self.handle_object_info_request_result = Some(result);
     }
+
+    pub fn set_handle_certificate_result(&mut self, result: SuiResult<TransactionInfoResponse>) {
+        self.handle_certificate_result = Some(result);
+    }
+
+    pub fn set_handle_bridge_attestation_request_result(
+        &mut self,
+        result: SuiResult<BridgeAttestationResponse>,
+    ) {
+        self.handle_bridge_attestation_request_result = Some(result);
+    }
+
+    pub fn set_handle_checkpoint_result(&mut self, result: SuiResult<CheckpointResponse>) {
+        self.handle_checkpoint_result = Some(result);
+    }
 }
 
 #[async_trait]
@@ -944,7 +1177,8 @@ impl AuthorityAPI for MockAuthorityApi {
         &self,
         _certificate: CertifiedTransaction,
     ) -> Result<TransactionInfoResponse, SuiError> {
-        unreachable!()
+        *self.count.lock().unwrap() += 1;
+        self.handle_certificate_result.clone().unwrap()
     }
 
     /// Handle Account information requests for this account.
@@ -998,7 +1232,7 @@ impl AuthorityAPI for MockAuthorityApi {
         &self,
         _request: CheckpointRequest,
     ) -> Result<CheckpointResponse, SuiError> {
-        unreachable!();
+        self.handle_checkpoint_result.clone().unwrap()
     }
 
     async fn handle_checkpoint_stream(
@@ -1014,6 +1248,76 @@ impl AuthorityAPI for MockAuthorityApi {
     ) -> Result<CommitteeInfoResponse, SuiError> {
         self.handle_committee_info_request_result.clone().unwrap()
     }
+
+    async fn handle_transactions_info_request(
+        &self,
+        _request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        unimplemented!();
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        _request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        unimplemented!();
+    }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        _request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        self.handle_bridge_attestation_request_result
+            .clone()
+            .unwrap()
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        _request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        unimplemented!();
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_safe_client_respects_retry_after() {
+    let count = Arc::new(Mutex::new(0));
+    let (authorities, _authorities_vec, mut clients) = get_authorities(count.clone(), 4);
+    let name = *clients.keys().next().unwrap();
+    clients
+        .get_mut(&name)
+        .unwrap()
+        .set_handle_certificate_result(Err(SuiError::ValidatorOverloaded {
+            reason: "too many certificates".to_string(),
+            retry_after_ms: Some(1000),
+        }));
+    let agg = get_agg(authorities, clients);
+    let client = &agg.authority_clients[&name];
+    let certificate = make_random_certified_transaction();
+
+    let err = client
+        .handle_certificate(certificate.clone())
+        .await
+        .unwrap_err();
+    assert_eq!(err.retry_after(), Some(Duration::from_millis(1000)));
+    assert_eq!(*count.lock().unwrap(), 1);
+
+    // While the validator is overloaded, requests fail without reaching it.
+    tokio::time::advance(Duration::from_millis(400)).await;
+    let err = client
+        .handle_certificate(certificate.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SuiError::ValidatorOverloaded { .. }));
+    assert!(err.retry_after().unwrap() <= Duration::from_millis(600));
+    assert_eq!(*count.lock().unwrap(), 1);
+
+    // Once the delay is over, requests are sent again.
+    tokio::time::advance(Duration::from_millis(600)).await;
+    assert_eq!(client.overloaded_for(), None);
+    client.handle_certificate(certificate).await.unwrap_err();
+    assert_eq!(*count.lock().unwrap(), 2);
 }
 
 #[tokio::test(start_paused = true)]
@@ -1069,6 +1373,250 @@ async fn test_quorum_once_with_timeout() {
     );
 }
 
+#[tokio::test]
+async fn test_quorum_once_tries_unreachable_authorities_last() {
+    let (agg, _, _) = init_local_authorities(4, vec![]).await;
+    let names: Vec<_> = agg.committee.names().cloned().collect();
+    // Only the last authority is reachable, so it is the first one tried.
+    for name in &names[..3] {
+        agg.peer_availability.set_reachable(*name, false);
+    }
+    assert_eq!(
+        agg.peer_availability.reachable_first(names.clone()),
+        vec![names[3], names[0], names[1], names[2]]
+    );
+
+    for _ in 0..10 {
+        let first_tried = agg
+            .quorum_once_with_timeout(
+                None,
+                None,
+                |name, _client| Box::pin(async move { Ok(name) }),
+                Duration::from_secs(5),
+                None,
+                "test",
+            )
+            .await
+            .unwrap();
+        assert_eq!(first_tried, names[3]);
+    }
+}
+
+#[tokio::test]
+async fn test_quorum_once_tries_preferred_authorities_first() {
+    let (agg, _, _) = init_local_authorities(4, vec![]).await;
+    let names: Vec<_> = agg.committee.names().cloned().collect();
+    agg.authority_preferences.set_tiers(vec![
+        BTreeSet::from([names[2]]),
+        BTreeSet::from([names[0], names[3]]),
+    ]);
+    let ordered = agg.authority_preferences.order(names.clone(), None);
+    assert_eq!(ordered, vec![names[2], names[0], names[3], names[1]]);
+    // The preferences of a call come first, regardless of their tier.
+    let ordered = agg
+        .authority_preferences
+        .order(names.clone(), Some(&BTreeSet::from([names[1]])));
+    assert_eq!(ordered, vec![names[1], names[2], names[0], names[3]]);
+
+    for _ in 0..10 {
+        let first_tried = agg
+            .quorum_once_with_timeout(
+                None,
+                None,
+                |name, _client| Box::pin(async move { Ok(name) }),
+                Duration::from_secs(5),
+                None,
+                "test",
+            )
+            .await
+            .unwrap();
+        assert_eq!(first_tried, names[2]);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_quorum_once_read_hedging() {
+    let count = Arc::new(Mutex::new(0));
+    let (authorities, _authorities_vec, clients) = get_authorities(count.clone(), 10);
+    let committee = Committee::new(0, authorities).unwrap();
+    let committee_store = Arc::new(CommitteeStore::new_for_testing(&committee));
+    // The budget allows a single hedged request.
+    let agg = AuthorityAggregator::new_with_timeouts(
+        committee,
+        committee_store,
+        clients,
+        AuthAggMetrics::new_for_tests(),
+        Arc::new(SafeClientMetrics::new_for_tests()),
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+        TimeoutConfig {
+            hedging: Some(HedgingConfig {
+                max_delay: Duration::from_millis(200),
+                budget_ratio: 0.0,
+                max_budget: 1.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let start = Instant::now();
+    agg.quorum_once_read_with_timeout(
+        None,
+        None,
+        |_name, client| {
+            let digest = TransactionDigest::new([0u8; 32]);
+            let log = log.clone();
+            Box::pin(async move {
+                log.lock().unwrap().push(Instant::now() - start);
+                client.handle_transaction_info_request(digest.into()).await
+            })
+        },
+        Duration::from_secs(5),
+        None,
+        "test",
+    )
+    .await
+    .unwrap();
+
+    // Each request takes 1000ms. The read is hedged once after the maximum hedging delay, since
+    // no latency was observed yet, and then waits for the first response.
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![Duration::from_millis(0), Duration::from_millis(200)]
+    );
+    assert_eq!(agg.metrics.total_hedged_requests.get(), 1);
+    assert!(!agg.read_hedger.as_ref().unwrap().try_hedge());
+}
+
+#[test]
+fn test_read_hedger_delay_and_budget() {
+    let hedger = ReadHedger::new(HedgingConfig {
+        latency_percentile: 0.9,
+        min_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(500),
+        budget_ratio: 0.5,
+        max_budget: 1.0,
+    });
+    // Not enough reads were observed yet.
+    assert_eq!(hedger.hedging_delay(), Duration::from_millis(500));
+
+    for latency in 1..=100 {
+        hedger.record_latency(Duration::from_millis(latency));
+    }
+    assert_eq!(hedger.hedging_delay(), Duration::from_millis(90));
+    // Older latencies leave the window.
+    for _ in 0..HEDGING_LATENCY_WINDOW {
+        hedger.record_latency(Duration::from_millis(1));
+    }
+    assert_eq!(hedger.hedging_delay(), Duration::from_millis(10));
+
+    // Two reads earn one hedged request.
+    assert!(hedger.try_hedge());
+    assert!(!hedger.try_hedge());
+    hedger.on_read();
+    assert!(!hedger.try_hedge());
+    hedger.on_read();
+    assert!(hedger.try_hedge());
+}
+
+#[test]
+fn test_adaptive_timeouts() {
+    let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+    let name: AuthorityName = key.public().into();
+    let adaptive = AdaptiveTimeouts::new(AdaptiveTimeoutConfig {
+        floor: Duration::from_millis(10),
+        ceiling: Duration::from_secs(1),
+        ..Default::default()
+    });
+    let timeout_millis = || {
+        adaptive
+            .request_timeout(&name, Duration::from_secs(5))
+            .as_millis()
+    };
+
+    // No latency observed yet.
+    assert_eq!(timeout_millis(), 5000);
+
+    // The first sample sets the deviation to half the latency.
+    adaptive.record_latency(name, Duration::from_millis(100));
+    assert_eq!(timeout_millis(), 300);
+    // The deviation shrinks as latency stays stable.
+    adaptive.record_latency(name, Duration::from_millis(100));
+    assert!(timeout_millis() < 300);
+
+    // Fast links are bounded by the floor, and slow ones by the ceiling.
+    for _ in 0..100 {
+        adaptive.record_latency(name, Duration::from_millis(1));
+    }
+    assert_eq!(timeout_millis(), 10);
+    for _ in 0..100 {
+        adaptive.record_timeout(name, Duration::from_secs(2));
+    }
+    assert_eq!(timeout_millis(), 1000);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_quorum_once_adaptive_timeouts() {
+    let count = Arc::new(Mutex::new(0));
+    let (authorities, _authorities_vec, clients) = get_authorities(count.clone(), 30);
+    let committee = Committee::new(0, authorities).unwrap();
+    let committee_store = Arc::new(CommitteeStore::new_for_testing(&committee));
+    let agg = AuthorityAggregator::new_with_timeouts(
+        committee,
+        committee_store,
+        clients,
+        AuthAggMetrics::new_for_tests(),
+        Arc::new(SafeClientMetrics::new_for_tests()),
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+        TimeoutConfig {
+            // Only start a new request when the previous one failed.
+            serial_authority_request_interval: Duration::from_secs(10),
+            adaptive: Some(AdaptiveTimeoutConfig {
+                floor: Duration::from_millis(100),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    // All authorities used to answer within 10ms.
+    let adaptive = agg.adaptive_timeouts.clone().unwrap();
+    for name in agg.committee.names() {
+        for _ in 0..10 {
+            adaptive.record_latency(*name, Duration::from_millis(10));
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let start = Instant::now();
+    agg.quorum_once_with_timeout(
+        None,
+        None,
+        |_name, client| {
+            let digest = TransactionDigest::new([0u8; 32]);
+            let log = log.clone();
+            Box::pin(async move {
+                log.lock().unwrap().push(Instant::now() - start);
+                client.handle_transaction_info_request(digest.into()).await
+            })
+        },
+        Duration::from_secs(5),
+        None,
+        "test",
+    )
+    .await
+    .unwrap();
+
+    // The first 14 requests hang, and each times out after the 100ms floor instead of the 5s
+    // fixed timeout, before the next authority is tried.
+    assert_eq!(
+        *log.lock().unwrap(),
+        (0..15)
+            .map(|i| Duration::from_millis(i * 100))
+            .collect::<Vec<_>>()
+    );
+}
+
 #[allow(clippy::type_complexity)]
 fn get_authorities(
     count: Arc<Mutex<u32>>,
@@ -1442,3 +1990,220 @@ pub fn get_authority_pub_key_bytes_and_address() -> (AuthorityPublicKeyBytes, Ve
         Multiaddr::empty().to_vec(),
     )
 }
+
+#[tokio::test]
+async fn test_get_bridge_attestation() {
+    let count = Arc::new(Mutex::new(0));
+    let keys: Vec<AuthorityKeyPair> = (0..4).map(|_| get_authority_key_pair().1).collect();
+    let authorities: BTreeMap<AuthorityName, StakeUnit> =
+        keys.iter().map(|key| (key.public().into(), 1)).collect();
+    let message = BridgeMessage {
+        version: BRIDGE_MESSAGE_VERSION,
+        tx_digest: TransactionDigest::random(),
+        event_seq: 0,
+        package_id: ObjectID::random(),
+        sender: SuiAddress::default(),
+        type_: SuiSystemState::type_(),
+        contents: vec![1, 2, 3],
+    };
+    // Each validator signs the message in the given epoch.
+    let make_clients = |epochs: [EpochId; 4]| {
+        keys.iter()
+            .zip(epochs)
+            .map(|(key, epoch)| {
+                let name: AuthorityName = key.public().into();
+                let attestation = SignedBridgeAttestation::new(epoch, message.clone(), name, key);
+                let mut client = MockAuthorityApi::new(Duration::from_millis(0), count.clone());
+                client.set_handle_bridge_attestation_request_result(Ok(
+                    BridgeAttestationResponse { attestation },
+                ));
+                (name, client)
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    let agg = get_agg(authorities.clone(), make_clients([0; 4]));
+    let attestation = agg
+        .get_bridge_attestation(message.tx_digest, message.event_seq)
+        .await
+        .unwrap();
+    assert_eq!(attestation.message, message);
+    assert_eq!(attestation.auth_signature.epoch, 0);
+    attestation.verify(&agg.committee).unwrap();
+
+    // Signatures from another epoch are valid for its committee, but must not be mixed with
+    // those of the epoch of the aggregator into a certificate.
+    let agg = get_agg(authorities.clone(), make_clients([0, 0, 1, 1]));
+    agg.committee_store
+        .insert_new_committee(&Committee::new(1, authorities).unwrap())
+        .unwrap();
+    let err = agg
+        .get_bridge_attestation(message.tx_digest, message.event_seq)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, SuiError::TooManyIncorrectAuthorities { ref errors, .. }
+            if errors.iter().all(|(_, err)| matches!(err, SuiError::WrongEpoch { .. }))),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_safe_client_checkpoint_validation() {
+    let keys: Vec<AuthorityKeyPair> = (0..4).map(|_| get_authority_key_pair().1).collect();
+    let authorities: BTreeMap<AuthorityName, StakeUnit> =
+        keys.iter().map(|key| (key.public().into(), 1)).collect();
+    let committee = Committee::new(0, authorities).unwrap();
+    let committee_store = Arc::new(CommitteeStore::new_for_testing(&committee));
+    let metrics = Arc::new(SafeClientMetrics::new_for_tests());
+    let name: AuthorityName = keys[0].public().into();
+
+    let contents = CheckpointContents::new(std::iter::empty());
+    let sign = |key: &AuthorityKeyPair, summary: CheckpointSummary| {
+        SignedCheckpointSummary::new_from_summary(summary, key.public().into(), key)
+    };
+    let summary = |seq, previous_digest| {
+        CheckpointSummary::new(
+            0,
+            seq,
+            &contents,
+            previous_digest,
+            GasCostSummary::default(),
+            None,
+            [0; 32],
+        )
+    };
+    // Send the response to a request through a SafeClient talking to the first authority.
+    let check = |request: CheckpointRequest, response: CheckpointResponse| {
+        let mut client = MockAuthorityApi::new(Duration::from_millis(0), Arc::new(Mutex::new(0)));
+        client.set_handle_checkpoint_result(Ok(response));
+        let client = SafeClient::new(
+            client,
+            committee_store.clone(),
+            Arc::new(VerifiedCertificateCache::new_for_tests()),
+            name,
+            metrics.clone(),
+        );
+        async move { client.handle_checkpoint(request).await }
+    };
+    let authenticated = |checkpoint: SignedCheckpointSummary, contents| {
+        CheckpointResponse::AuthenticatedCheckpoint {
+            checkpoint: Some(AuthenticatedCheckpoint::Signed(checkpoint)),
+            contents,
+        }
+    };
+    let assert_suspicion = |result: SuiResult<CheckpointResponse>, expected: &str| match result {
+        Err(SuiError::ByzantineAuthoritySuspicion { authority, reason }) => {
+            assert_eq!(authority, name);
+            assert!(reason.contains(expected), "{reason}");
+        }
+        other => panic!("Expected a byzantine authority suspicion, got {other:?}"),
+    };
+
+    let checkpoint = sign(&keys[0], summary(0, None));
+    check(
+        CheckpointRequest::authenticated(Some(0), true),
+        authenticated(checkpoint.clone(), Some(contents.clone())),
+    )
+    .await
+    .unwrap();
+
+    assert_suspicion(
+        check(
+            CheckpointRequest::authenticated(Some(1), false),
+            authenticated(checkpoint.clone(), None),
+        )
+        .await,
+        "Expected checkpoint number 1 doesn't match with returned 0",
+    );
+    assert_suspicion(
+        check(
+            CheckpointRequest::authenticated(Some(0), true),
+            authenticated(checkpoint.clone(), None),
+        )
+        .await,
+        "Checkpoint contents inconsistent with request",
+    );
+    assert_suspicion(
+        check(
+            CheckpointRequest::authenticated(Some(0), false),
+            authenticated(sign(&keys[1], summary(0, None)), None),
+        )
+        .await,
+        "Checkpoint signed by another authority",
+    );
+    assert_suspicion(
+        check(
+            CheckpointRequest::authenticated(Some(0), false),
+            authenticated(sign(&keys[0], summary(0, Some([1; 32]))), None),
+        )
+        .await,
+        "First checkpoint cannot have a previous digest",
+    );
+
+    // A signature of the authority over another summary is not valid.
+    let mut forged = checkpoint.clone();
+    forged.summary.gas_cost_summary.computation_cost = 1;
+    assert_suspicion(
+        check(
+            CheckpointRequest::authenticated(Some(0), false),
+            authenticated(forged, None),
+        )
+        .await,
+        "Invalid checkpoint 0 of epoch 0",
+    );
+
+    // A proposal must come with the certificate of the previous checkpoint.
+    let cert = CertifiedCheckpointSummary::aggregate(
+        keys.iter().map(|key| sign(key, summary(0, None))).collect(),
+        &committee,
+    )
+    .unwrap();
+    let proposal = |key: &AuthorityKeyPair, seq, prev_cert| {
+        let contents = CheckpointProposalContents::new(std::iter::empty());
+        let proposal = CheckpointProposal::new(0, seq, key.public().into(), key, contents);
+        CheckpointResponse::CheckpointProposal {
+            proposal: Some(proposal.signed_summary),
+            prev_cert,
+            proposal_contents: None,
+        }
+    };
+    check(
+        CheckpointRequest::proposal(false),
+        proposal(&keys[0], 1, Some(cert.clone())),
+    )
+    .await
+    .unwrap();
+    assert_suspicion(
+        check(
+            CheckpointRequest::proposal(false),
+            proposal(&keys[0], 1, None),
+        )
+        .await,
+        "No checkpoint cert provided along with proposal",
+    );
+    assert_suspicion(
+        check(
+            CheckpointRequest::proposal(false),
+            proposal(&keys[0], 2, Some(cert.clone())),
+        )
+        .await,
+        "Checkpoint proposal sequence number inconsistent with previous cert",
+    );
+    assert_suspicion(
+        check(
+            CheckpointRequest::proposal(false),
+            proposal(&keys[1], 1, Some(cert)),
+        )
+        .await,
+        "Checkpoint proposal signed by another authority",
+    );
+
+    assert_eq!(
+        metrics
+            .invalid_checkpoint_responses_by_address
+            .with_label_values(&[&name.to_string()])
+            .get(),
+        8
+    );
+}
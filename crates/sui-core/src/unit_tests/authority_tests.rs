@@ -488,6 +488,99 @@ async fn test_handle_transfer_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_handle_object_info_request_content() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    // Lock the object.
+    let transfer_transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+    authority_state
+        .handle_transaction(transfer_transaction)
+        .await
+        .unwrap();
+
+    let get = |content| {
+        let authority_state = &authority_state;
+        async move {
+            authority_state
+                .handle_object_info_request(ObjectInfoRequest::latest_object_content_request(
+                    object_id, content,
+                ))
+                .await
+                .unwrap()
+                .object_and_lock
+                .unwrap()
+        }
+    };
+
+    let bcs_only = get(ObjectContentOptions::BcsOnly).await;
+    assert_eq!(bcs_only.object, object);
+    assert!(bcs_only.lock.is_some());
+    assert!(bcs_only.layout.is_none());
+
+    let display = get(ObjectContentOptions::WithDisplayLayout).await;
+    let typed = get(ObjectContentOptions::WithTypeLayout).await;
+    assert!(display.lock.is_some() && typed.lock.is_some());
+    assert_eq!(
+        display.layout.unwrap(),
+        object
+            .get_layout(
+                ObjectFormatOptions::with_fields_only(),
+                authority_state.module_cache.as_ref()
+            )
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        typed.layout.unwrap(),
+        object
+            .get_layout(
+                ObjectFormatOptions::with_types(),
+                authority_state.module_cache.as_ref()
+            )
+            .unwrap()
+            .unwrap()
+    );
+
+    let owner_only = get(ObjectContentOptions::WithOwnerOnly).await;
+    assert_eq!(owner_only.object.owner, Owner::AddressOwner(sender));
+    assert!(owner_only.lock.is_none());
+    assert!(owner_only.layout.is_none());
+
+    // Requests without content options return the lock, and the layout in the given format.
+    let legacy = authority_state
+        .handle_object_info_request(ObjectInfoRequest::latest_object_info_request(
+            object_id,
+            Some(ObjectFormatOptions::with_types()),
+        ))
+        .await
+        .unwrap()
+        .object_and_lock
+        .unwrap();
+    assert!(legacy.lock.is_some());
+    assert!(legacy.layout.is_some());
+}
+
 #[tokio::test]
 async fn test_transfer_package() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1287,6 +1380,85 @@ async fn test_handle_confirmation_transaction_idempotent() {
     compare_transaction_info_responses(&info, &info3);
 }
 
+#[tokio::test]
+async fn test_executed_certificate_cache() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let tx_digest = *certificate.digest();
+
+    let info = authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap();
+    let cached = authority_state
+        .executed_certificates
+        .get(&tx_digest)
+        .unwrap();
+    compare_transaction_info_responses(&info, &cached);
+
+    // Retries are answered from the store when the cache is disabled.
+    authority_state.executed_certificates.set_capacity(0);
+    let info2 = authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap();
+    compare_transaction_info_responses(&info, &info2);
+    authority_state
+        .executed_certificates
+        .set_capacity(DEFAULT_EXECUTED_CERT_CACHE_SIZE);
+
+    // Reverted transactions are executed again rather than answered from the cache.
+    authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap();
+    assert!(authority_state
+        .executed_certificates
+        .get(&tx_digest)
+        .is_some());
+    authority_state.revert_state_update(&tx_digest).unwrap();
+    assert!(authority_state
+        .executed_certificates
+        .get(&tx_digest)
+        .is_none());
+    let info3 = authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap();
+    assert!(info3.signed_effects.unwrap().effects.status.is_ok());
+    assert_eq!(
+        authority_state
+            .get_object(&object_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .owner,
+        Owner::AddressOwner(recipient)
+    );
+}
+
 #[tokio::test]
 async fn test_move_call_mutable_object_not_mutated() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1525,6 +1697,181 @@ async fn test_get_latest_parent_entry_genesis() {
         .is_none());
 }
 
+#[tokio::test]
+async fn test_handle_network_info_request() {
+    let authority_state = init_state().await;
+    let response = authority_state
+        .handle_network_info_request(&NetworkInfoRequest {})
+        .unwrap();
+    let info = response.info.info;
+    assert_eq!(info.epoch, authority_state.epoch());
+    assert_eq!(info.protocol_version, epoch_protocol_version(info.epoch));
+}
+
+#[tokio::test]
+async fn test_intake_halt() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+
+    authority_state.set_intake_halt(Some("execution stalled".to_string()));
+    let err = authority_state
+        .handle_transaction(transaction.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SuiError::ValidatorOverloaded { .. }));
+
+    // Transactions are accepted again once the intake resumes.
+    authority_state.set_intake_halt(None);
+    authority_state
+        .handle_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pending_certificate_counts() {
+    let authority_state = init_state().await;
+    let digests = [TransactionDigest::random(), TransactionDigest::random()];
+    authority_state
+        .add_pending_certificates(digests.iter().map(|digest| (*digest, None)).collect())
+        .unwrap();
+    assert_eq!(authority_state.pending_certificate_count(), 2);
+    assert_eq!(authority_state.dequeued_certificate_count(), 0);
+
+    let pending = authority_state.get_pending_certificates().unwrap();
+    authority_state
+        .database
+        .remove_pending_digests(vec![pending[0].0])
+        .unwrap();
+    assert_eq!(authority_state.pending_certificate_count(), 1);
+    assert_eq!(authority_state.dequeued_certificate_count(), 1);
+
+    authority_state
+        .database
+        .remove_all_pending_certificates()
+        .unwrap();
+    assert_eq!(authority_state.pending_certificate_count(), 0);
+}
+
+#[tokio::test]
+async fn test_get_objects_at_checkpoint() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, pkg_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let call = |function, object_id| {
+        call_move(
+            &authority_state,
+            &gas_object_id,
+            &sender,
+            &sender_key,
+            &pkg_ref,
+            "object_basics",
+            function,
+            vec![],
+            vec![TestCallArg::Object(object_id)],
+        )
+    };
+
+    let effects1 = create_move_object(
+        &pkg_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    let object1 = effects1.created[0].0;
+    let effects2 = create_move_object(
+        &pkg_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    let object2 = effects2.created[0].0;
+    store_certified_checkpoint(&authority_state, 0, &[&effects1, &effects2]);
+
+    let effects3 = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &pkg_ref,
+        "object_basics",
+        "set_value",
+        vec![],
+        vec![
+            TestCallArg::Object(object1.0),
+            TestCallArg::Pure(bcs::to_bytes(&(42_u64)).unwrap()),
+        ],
+    )
+    .await
+    .unwrap();
+    let object1_mutated = effects3
+        .mutated
+        .iter()
+        .find(|(object_ref, _)| object_ref.0 == object1.0)
+        .unwrap()
+        .0;
+    let effects4 = call("wrap", object2.0).await.unwrap();
+    assert_eq!(effects4.wrapped.len(), 1);
+    store_certified_checkpoint(&authority_state, 1, &[&effects3, &effects4]);
+
+    // Not checkpointed yet, so not visible at any checkpoint.
+    let effects5 = call("delete", object1.0).await.unwrap();
+    assert_eq!(effects5.deleted.len(), 1);
+
+    let unknown = ObjectID::random();
+    let object_ids = [object1.0, object2.0, unknown];
+    let (checkpoint, reads) = authority_state
+        .get_objects_at_checkpoint(&object_ids, Some(0))
+        .await
+        .unwrap();
+    assert_eq!(checkpoint, 0);
+    assert!(matches!(&reads[0], PastObjectRead::VersionFound(r, _, _) if *r == object1));
+    assert!(matches!(&reads[1], PastObjectRead::VersionFound(r, _, _) if *r == object2));
+    assert!(matches!(&reads[2], PastObjectRead::ObjectNotExists(id) if *id == unknown));
+
+    // The latest stored checkpoint by default.
+    let (checkpoint, reads) = authority_state
+        .get_objects_at_checkpoint(&object_ids, None)
+        .await
+        .unwrap();
+    assert_eq!(checkpoint, 1);
+    assert!(matches!(&reads[0], PastObjectRead::VersionFound(r, _, _) if *r == object1_mutated));
+    assert!(matches!(&reads[1], PastObjectRead::ObjectWrapped(r) if r.0 == object2.0));
+    assert!(matches!(&reads[2], PastObjectRead::ObjectNotExists(id) if *id == unknown));
+
+    assert!(authority_state
+        .get_objects_at_checkpoint(&object_ids, Some(2))
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_get_latest_parent_entry() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1792,6 +2139,62 @@ async fn test_change_epoch_transaction() {
     assert_eq!(sui_system_object.epoch, 1);
 }
 
+#[tokio::test]
+async fn test_get_objects_owned_by_object_recursive() {
+    let sender = dbg_addr(1);
+    let owned_by = |object_id: ObjectID, owner: ObjectID| {
+        let mut object = Object::with_id_owner_for_testing(object_id, sender);
+        object.owner = Owner::ObjectOwner(owner.into());
+        object
+    };
+    // The parent owns two children, and the first child owns a grandchild.
+    let (parent, child1, child2, grandchild) = (
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+    );
+    let authority_state = init_state_with_objects(vec![
+        Object::with_id_owner_for_testing(parent, sender),
+        owned_by(child1, parent),
+        owned_by(child2, parent),
+        owned_by(grandchild, child1),
+    ])
+    .await;
+    let resolve = |max_depth, max_objects| {
+        let (objects, truncated) = authority_state
+            .get_objects_owned_by_object_recursive(parent, max_depth, max_objects)
+            .unwrap();
+        let objects: Vec<_> = objects
+            .into_iter()
+            .map(|(info, depth)| (info.object_id, depth))
+            .collect();
+        (objects, truncated)
+    };
+
+    let (objects, truncated) = resolve(MAX_OBJECT_OWNERSHIP_DEPTH, 10);
+    assert!(!truncated);
+    assert_eq!(objects.len(), 3);
+    assert!(objects.contains(&(child1, 1)) && objects.contains(&(child2, 1)));
+    assert_eq!(objects[2], (grandchild, 2));
+
+    // The grandchild is beyond the maximum depth.
+    let (objects, truncated) = resolve(1, 10);
+    assert!(truncated);
+    assert_eq!(objects.len(), 2);
+
+    // The grandchild does not fit in the result.
+    let (objects, truncated) = resolve(MAX_OBJECT_OWNERSHIP_DEPTH, 2);
+    assert!(truncated);
+    assert_eq!(objects.len(), 2);
+
+    // Objects owning nothing resolve to nothing.
+    let (objects, truncated) = authority_state
+        .get_objects_owned_by_object_recursive(grandchild, MAX_OBJECT_OWNERSHIP_DEPTH, 10)
+        .unwrap();
+    assert!(objects.is_empty() && !truncated);
+}
+
 #[tokio::test]
 async fn test_transfer_sui_no_amount() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1842,6 +2245,68 @@ async fn test_transfer_sui_no_amount() {
     );
 }
 
+#[tokio::test]
+async fn test_transfer_sui_with_merged_gas() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    // Neither coin can pay for the gas budget on its own.
+    let gas_object_id = ObjectID::random();
+    let gas_object = Object::with_id_owner_gas_for_testing(gas_object_id, sender, MAX_GAS * 3 / 5);
+    let merged_object_id = ObjectID::random();
+    let merged_object =
+        Object::with_id_owner_gas_for_testing(merged_object_id, sender, MAX_GAS * 3 / 5);
+    let authority_state =
+        init_state_with_objects(vec![gas_object.clone(), merged_object.clone()]).await;
+
+    let tx_data = TransactionData::new_transfer_sui(
+        recipient,
+        sender,
+        Some(500),
+        gas_object.compute_object_reference(),
+        MAX_GAS,
+    );
+    let transaction = to_sender_signed_transaction(tx_data.clone(), &sender_key);
+    assert!(matches!(
+        authority_state.handle_transaction(transaction).await,
+        Err(SuiError::InsufficientGas { .. })
+    ));
+
+    let tx_data = tx_data.with_merged_gas_payment(vec![merged_object.compute_object_reference()]);
+    let transaction = to_sender_signed_transaction(tx_data, &sender_key);
+    authority_state
+        .handle_transaction(transaction.clone())
+        .await
+        .unwrap();
+    let certificate = init_certified_transaction(transaction, &authority_state);
+    let response = authority_state
+        .handle_certificate(&certificate)
+        .await
+        .unwrap();
+    let effects = response.signed_effects.unwrap().effects;
+    // The merged coin is deleted, and its balance pays for gas and the transfer.
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.deleted.len(), 1);
+    assert_eq!(effects.deleted[0].0, merged_object_id);
+    assert_eq!(effects.gas_object.0 .1, SequenceNumber::new().increment());
+    assert!(authority_state
+        .get_object(&merged_object_id)
+        .await
+        .unwrap()
+        .is_none());
+    let new_balance = sui_types::gas::get_gas_balance(
+        &authority_state
+            .get_object(&gas_object_id)
+            .await
+            .unwrap()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        new_balance as i64 + effects.gas_cost_summary().net_gas_usage() + 500,
+        (MAX_GAS * 3 / 5 * 2) as i64
+    );
+}
+
 #[tokio::test]
 async fn test_transfer_sui_with_amount() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -2200,6 +2665,40 @@ pub async fn call_move_with_shared(
     Ok(response.signed_effects.unwrap().effects)
 }
 
+#[cfg(test)]
+fn store_certified_checkpoint(
+    authority: &AuthorityState,
+    seq: CheckpointSequenceNumber,
+    effects: &[&TransactionEffects],
+) {
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{CheckpointContents, SignedCheckpointSummary};
+
+    let committee = authority.clone_committee();
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        effects
+            .iter()
+            .map(|effects| ExecutionDigests::new(effects.transaction_digest, effects.digest())),
+    );
+    let signed = SignedCheckpointSummary::new(
+        committee.epoch,
+        seq,
+        authority.name,
+        &*authority.secret,
+        &contents,
+        None,
+        GasCostSummary::default(),
+        None,
+        [0; 32],
+    );
+    let certified = CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap();
+    authority
+        .checkpoints
+        .lock()
+        .process_synced_checkpoint_certificate(&certified, &contents, &committee)
+        .unwrap();
+}
+
 pub async fn create_move_object(
     package_ref: &ObjectRef,
     authority: &AuthorityState,
@@ -2485,3 +2984,116 @@ async fn test_consensus_message_processed() {
             .get_next_object_version(&shared_object_id),
     );
 }
+
+#[tokio::test]
+async fn test_execution_audit_of_synced_certificates() {
+    use crate::execution_audit::{ExecutionAuditMetrics, ExecutionAuditor};
+    use sui_config::node::ExecutionAuditConfig;
+    use sui_types::base_types::encode_bytes_hex;
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+    let committee = Committee::new(0, BTreeMap::from([(key.public().into(), 1)])).unwrap();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let gas_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let objects = vec![object.clone(), gas_object.clone()];
+
+    let validator = init_state_with_objects_and_committee(
+        objects.clone(),
+        Some((committee.clone(), key.copy())),
+    )
+    .await;
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        dbg_addr(2),
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &validator,
+    );
+    let effects = validator
+        .handle_certificate(&certificate)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap();
+    let mut forged_effects = effects.clone();
+    forged_effects.effects.gas_used.computation_cost += 1;
+
+    let dump_dir = tempfile::tempdir().unwrap();
+    let config = ExecutionAuditConfig {
+        sample_interval: 1,
+        dump_dir: Some(dump_dir.path().to_path_buf()),
+    };
+    let dump = |execution| {
+        dump_dir.path().join(format!(
+            "{}.{execution}.json",
+            encode_bytes_hex(certificate.digest())
+        ))
+    };
+    // The audit executes the certificate again once it was committed, on a thread of its own.
+    let audited = |auditor: &ExecutionAuditor, outcome| {
+        let counter = auditor
+            .metrics
+            .compared_effects
+            .with_label_values(&["audit", outcome]);
+        async move {
+            tokio::time::timeout(Duration::from_secs(10), async {
+                while counter.get() == 0 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap();
+        }
+    };
+
+    // A node reproducing the certified effects.
+    let node = init_state_with_objects_and_committee(
+        objects.clone(),
+        Some((committee.clone(), key.copy())),
+    )
+    .await;
+    let auditor =
+        Arc::new(ExecutionAuditor::new(&config, ExecutionAuditMetrics::new_for_tests()).unwrap());
+    node.execution_auditor.store(Some(auditor.clone()));
+    node.handle_certificate_with_effects(&certificate, &effects)
+        .await
+        .unwrap();
+    audited(&auditor, "matched").await;
+    let metrics = &auditor.metrics.compared_effects;
+    assert_eq!(metrics.with_label_values(&["synced", "matched"]).get(), 1);
+    assert_eq!(metrics.with_label_values(&["synced", "diverged"]).get(), 0);
+    assert_eq!(metrics.with_label_values(&["audit", "matched"]).get(), 1);
+    assert_eq!(metrics.with_label_values(&["audit", "diverged"]).get(), 0);
+    assert!(!dump("synced").exists());
+    assert!(!dump("audit").exists());
+
+    // A node handed effects it cannot reproduce.
+    let node = init_state_with_objects_and_committee(objects, Some((committee, key))).await;
+    let auditor =
+        Arc::new(ExecutionAuditor::new(&config, ExecutionAuditMetrics::new_for_tests()).unwrap());
+    node.execution_auditor.store(Some(auditor.clone()));
+    node.handle_certificate_with_effects(&certificate, &forged_effects)
+        .await
+        .unwrap();
+    audited(&auditor, "diverged").await;
+    let metrics = &auditor.metrics.compared_effects;
+    assert_eq!(metrics.with_label_values(&["synced", "matched"]).get(), 0);
+    assert_eq!(metrics.with_label_values(&["synced", "diverged"]).get(), 1);
+    assert_eq!(metrics.with_label_values(&["audit", "matched"]).get(), 0);
+    assert_eq!(metrics.with_label_values(&["audit", "diverged"]).get(), 1);
+    for execution in ["synced", "audit"] {
+        let dumped: serde_json::Value =
+            serde_json::from_slice(&fs::read(dump(execution)).unwrap()).unwrap();
+        assert_eq!(dumped["execution"], execution);
+        assert_eq!(
+            dumped["certified_effects"]["gas_used"]["computation_cost"],
+            forged_effects.effects.gas_used.computation_cost
+        );
+        assert_eq!(
+            dumped["executed_effects"]["gas_used"]["computation_cost"],
+            effects.effects.gas_used.computation_cost
+        );
+    }
+}
@@ -4,6 +4,7 @@
 use fastcrypto::traits::KeyPair;
 use rand::{prelude::StdRng, SeedableRng};
 use sui_storage::node_sync_store::NodeSyncStore;
+use sui_types::bridge::{BridgeAttestationRequest, BridgeAttestationResponse};
 use sui_types::committee::Committee;
 use sui_types::crypto::get_key_pair;
 use sui_types::crypto::get_key_pair_from_rng;
@@ -12,18 +13,20 @@ use sui_types::crypto::AuthorityKeyPair;
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::CheckpointRequest;
 use sui_types::messages_checkpoint::CheckpointResponse;
+use sui_types::network_info::{NetworkInfoRequest, NetworkInfoResponse};
 
 use super::*;
 use crate::authority::authority_tests::*;
 use crate::authority::*;
-use crate::safe_client::SafeClient;
+use crate::safe_client::{BatchStreamCursor, SafeClient};
 
 use crate::authority_client::{
     AuthorityAPI, BatchInfoResponseItemStream, CheckpointStreamResponseItemStream,
+    LocalAuthorityClient,
 };
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
-use crate::safe_client::SafeClientMetrics;
+use crate::safe_client::{SafeClientMetrics, VerifiedCertificateCache};
 use async_trait::async_trait;
 use futures::lock::Mutex;
 use futures::stream;
@@ -34,9 +37,11 @@ use std::sync::Arc;
 use sui_types::messages::{
     AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
     CertifiedTransaction, CheckpointStreamRequest, CommitteeInfoRequest, CommitteeInfoResponse,
-    ObjectInfoRequest, ObjectInfoResponse, Transaction, TransactionInfoRequest,
-    TransactionInfoResponse,
+    EffectsInfoRequest, EffectsInfoResponse, ObjectInfoRequest, ObjectInfoResponse, Transaction,
+    TransactionInfoRequest, TransactionInfoResponse, TransactionsInfoRequest,
+    TransactionsInfoResponse,
 };
+use tokio::time::timeout;
 
 pub(crate) fn init_state_parameters_from_rng<R>(
     rng: &mut R,
@@ -247,6 +252,109 @@ async fn test_batch_manager_happy_path() {
     assert_eq!(authority_state.metrics.num_batch_service_tasks.get(), 0);
 }
 
+#[tokio::test]
+async fn test_drain() {
+    // Create a random directory to store the DB
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+
+    // Create an authority
+    let store = Arc::new(AuthorityStore::open(&path, None).unwrap());
+
+    // Make a test key pair
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
+    let authority_state = Arc::new(init_state(committee, authority_key, store.clone()).await);
+
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service_once(1000, Duration::from_millis(500))
+            .await
+    });
+
+    // A certificate is pending execution and another is being executed when the drain starts.
+    store
+        .add_pending_digests(vec![TransactionDigest::random()])
+        .unwrap();
+    let t0 = authority_state.batch_notifier.ticket(false).expect("ok");
+    let drain_state = authority_state.clone();
+    let mut drain =
+        tokio::task::spawn(async move { drain_state.drain(Duration::from_secs(10)).await });
+
+    assert!(timeout(Duration::from_millis(200), &mut drain)
+        .await
+        .is_err());
+    // New transactions are rejected, but certificates are still executed until the pending
+    // certificates are.
+    assert!(authority_state.check_intake().is_err());
+    assert!(!authority_state.is_halted());
+
+    let pending = store.get_pending_digests().unwrap();
+    store
+        .remove_pending_digests(pending.into_iter().map(|(seq, _)| seq).collect())
+        .unwrap();
+    assert!(timeout(Duration::from_millis(200), &mut drain)
+        .await
+        .is_err());
+    // New certificates are rejected, without claiming an epoch change.
+    assert!(matches!(
+        authority_state.batch_notifier.ticket(false),
+        Err(SuiError::ValidatorShuttingDown)
+    ));
+
+    store.side_sequence(t0.seq(), &ExecutionDigests::random());
+    t0.notify();
+
+    timeout(Duration::from_secs(5), drain)
+        .await
+        .expect("drain completes once the certificate is batched")
+        .unwrap()
+        .unwrap();
+    let last_batch = authority_state.last_batch().unwrap().unwrap();
+    assert_eq!(last_batch.data().next_sequence_number, 1);
+}
+
+#[tokio::test]
+async fn test_drain_leaves_pending_certificates_after_timeout() {
+    // Create a random directory to store the DB
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+
+    // Create an authority
+    let store = Arc::new(AuthorityStore::open(&path, None).unwrap());
+
+    // Make a test key pair
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
+    let authority_state = Arc::new(init_state(committee, authority_key, store.clone()).await);
+
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service_once(1000, Duration::from_millis(500))
+            .await
+    });
+
+    // Nothing executes the pending certificate, which is kept for the next start.
+    store
+        .add_pending_digests(vec![TransactionDigest::random()])
+        .unwrap();
+    timeout(
+        Duration::from_secs(5),
+        authority_state.drain(Duration::from_millis(200)),
+    )
+    .await
+    .expect("drain completes once the pending timeout expires")
+    .unwrap();
+    assert!(authority_state.is_halted());
+    assert_eq!(store.pending_count(), 1);
+}
+
 #[tokio::test]
 async fn test_batch_manager_out_of_order() {
     // Create a random directory to store the DB
@@ -610,6 +718,26 @@ impl AuthorityAPI for TrustworthyAuthorityClient {
         })
     }
 
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        let mut responses = Vec::new();
+        for digest in request.transaction_digests {
+            responses.push(self.handle_transaction_info_request(digest.into()).await?);
+        }
+        Ok(TransactionsInfoResponse { responses })
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        _request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        Ok(EffectsInfoResponse {
+            signed_effects: None,
+        })
+    }
+
     async fn handle_checkpoint(
         &self,
         _request: CheckpointRequest,
@@ -671,6 +799,23 @@ impl AuthorityAPI for TrustworthyAuthorityClient {
     ) -> Result<CommitteeInfoResponse, SuiError> {
         unimplemented!();
     }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        self.0
+            .lock()
+            .await
+            .handle_bridge_attestation_request(&request)
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        _request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        unimplemented!();
+    }
 }
 
 impl TrustworthyAuthorityClient {
@@ -739,6 +884,26 @@ impl AuthorityAPI for ByzantineAuthorityClient {
         })
     }
 
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        let mut responses = Vec::new();
+        for digest in request.transaction_digests {
+            responses.push(self.handle_transaction_info_request(digest.into()).await?);
+        }
+        Ok(TransactionsInfoResponse { responses })
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        _request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        Ok(EffectsInfoResponse {
+            signed_effects: None,
+        })
+    }
+
     async fn handle_checkpoint(
         &self,
         _request: CheckpointRequest,
@@ -807,6 +972,23 @@ impl AuthorityAPI for ByzantineAuthorityClient {
     ) -> Result<CommitteeInfoResponse, SuiError> {
         unimplemented!();
     }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        self.0
+            .lock()
+            .await
+            .handle_bridge_attestation_request(&request)
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        _request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        unimplemented!();
+    }
 }
 
 impl ByzantineAuthorityClient {
@@ -839,6 +1021,7 @@ async fn test_safe_batch_stream() {
     let safe_client = SafeClient::new(
         auth_client,
         committee_store,
+        Arc::new(VerifiedCertificateCache::new_for_tests()),
         public_key_bytes,
         Arc::new(SafeClientMetrics::new_for_tests()),
     );
@@ -888,6 +1071,7 @@ async fn test_safe_batch_stream() {
     let safe_client_from_byzantine = SafeClient::new(
         auth_client_from_byzantine,
         committee_store,
+        Arc::new(VerifiedCertificateCache::new_for_tests()),
         public_key_bytes_b,
         Arc::new(SafeClientMetrics::new_for_tests()),
     );
@@ -932,3 +1116,102 @@ async fn test_safe_batch_stream() {
     }
     assert!(error_found);
 }
+
+/// Reads `stream` up to the first batch ending at `next_sequence_number` or later, checking that
+/// every item is valid, and returns the sequence numbers of the transactions read.
+async fn read_batch_stream_until(
+    stream: &mut BatchInfoResponseItemStream,
+    next_sequence_number: TxSequenceNumber,
+) -> Vec<TxSequenceNumber> {
+    let mut transactions = Vec::new();
+    loop {
+        let item = timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("the batch is streamed")
+            .expect("the stream is open")
+            .expect("the item is valid");
+        match item.0 {
+            UpdateItem::Transaction((seq, _)) => transactions.push(seq),
+            UpdateItem::Batch(batch)
+                if batch.data().next_sequence_number >= next_sequence_number =>
+            {
+                return transactions;
+            }
+            UpdateItem::Batch(_) => (),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_resume_safe_batch_stream() {
+    // Create a random directory to store the DB
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+
+    let store = Arc::new(AuthorityStore::open(&path, None).unwrap());
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
+    let authority_state = Arc::new(init_state(committee, authority_key, store.clone()).await);
+
+    let inner_state = authority_state.clone();
+    let _join = tokio::task::spawn(async move {
+        inner_state
+            .run_batch_service_once(3, Duration::from_millis(200))
+            .await
+    });
+
+    let safe_client = SafeClient::new(
+        LocalAuthorityClient::new_from_authority(authority_state.clone()),
+        authority_state.committee_store().clone(),
+        Arc::new(VerifiedCertificateCache::new_for_tests()),
+        authority_state.name,
+        Arc::new(SafeClientMetrics::new_for_tests()),
+    );
+    // Executes the transactions with the next sequence numbers, or skips them if `executed` is
+    // false, as when their certificate fails to be committed.
+    let execute = |count: u64, executed: bool| {
+        for _ in 0..count {
+            let ticket = authority_state.batch_notifier.ticket(false).expect("ok");
+            if executed {
+                store
+                    .perpetual_tables
+                    .executed_sequence
+                    .insert(&ticket.seq(), &ExecutionDigests::random())
+                    .expect("Failed to write.");
+            }
+            ticket.notify();
+        }
+    };
+
+    let cursor = BatchStreamCursor::default();
+    let mut stream = safe_client.resume_batch_stream(&cursor, 10).await.unwrap();
+    // Transaction 3 is never executed, which leaves a gap in the sequence.
+    execute(3, true);
+    execute(1, false);
+    execute(2, true);
+    let transactions = read_batch_stream_until(&mut stream, 6).await;
+    assert_eq!(transactions, vec![0, 1, 2, 4, 5]);
+    assert_eq!(cursor.next_sequence_number(), Some(6));
+
+    // The transactions executed while disconnected are streamed once resumed.
+    drop(stream);
+    execute(3, true);
+    while authority_state
+        .last_batch()
+        .unwrap()
+        .map_or(0, |batch| batch.data().next_sequence_number)
+        < 9
+    {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let mut stream = safe_client.resume_batch_stream(&cursor, 10).await.unwrap();
+    let transactions = read_batch_stream_until(&mut stream, 9).await;
+    assert_eq!(transactions, vec![6, 7, 8]);
+    assert_eq!(cursor.next_sequence_number(), Some(9));
+
+    drop(stream);
+    authority_state.batch_notifier.close();
+    _join.await.expect("No errors in task").expect("ok");
+}
@@ -246,3 +246,64 @@ async fn test_batch_insufficient_gas_balance() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_batch_transaction_command_gas_costs() -> anyhow::Result<()> {
+    // A dry run attributes the computation gas of a batch to its commands.
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, package) =
+        init_state_with_ids_and_object_basics(vec![(sender, object_id), (sender, gas_object_id)])
+            .await;
+    let transactions = vec![
+        SingleTransactionKind::TransferObject(TransferObject {
+            recipient,
+            object_ref: authority_state
+                .get_object(&object_id)
+                .await?
+                .unwrap()
+                .compute_object_reference(),
+        }),
+        SingleTransactionKind::Call(MoveCall {
+            package,
+            module: ident_str!("object_basics").to_owned(),
+            function: ident_str!("create").to_owned(),
+            type_arguments: vec![],
+            arguments: vec![
+                CallArg::Pure(16u64.to_le_bytes().to_vec()),
+                CallArg::Pure(bcs::to_bytes(&AccountAddress::from(sender)).unwrap()),
+            ],
+        }),
+    ];
+    let data = TransactionData::new(
+        TransactionKind::Batch(transactions),
+        sender,
+        authority_state
+            .get_object(&gas_object_id)
+            .await?
+            .unwrap()
+            .compute_object_reference(),
+        1000000,
+    );
+
+    let tx = to_sender_signed_transaction(data, &sender_key);
+    let effects = authority_state
+        .dry_run_transaction(&tx, *tx.digest())
+        .await?;
+    let costs = &effects.command_gas_costs;
+    assert_eq!(costs.len(), 2);
+    assert_eq!(costs[0].command, "TransferObject");
+    assert_eq!(
+        costs[1].command,
+        format!("{}::object_basics::create", package.0)
+    );
+    assert!(costs[1].computation_cost > 0);
+    assert!(
+        costs.iter().map(|cost| cost.computation_cost).sum::<u64>()
+            <= effects.gas_used.computation_cost
+    );
+
+    Ok(())
+}
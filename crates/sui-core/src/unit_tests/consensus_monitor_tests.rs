@@ -0,0 +1,98 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_queue_depth_backpressure() {
+    let monitor = ConsensusMonitor::new_with_limits(10, Duration::from_secs(30), &Registry::new());
+    monitor.check_backpressure(10).unwrap();
+    match monitor.check_backpressure(11) {
+        Err(SuiError::ValidatorOverloaded { retry_after_ms, .. }) => {
+            assert_eq!(
+                retry_after_ms,
+                Some(CONSENSUS_BACKPRESSURE_RETRY_AFTER.as_millis() as u64)
+            );
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn test_commit_lag_backpressure() {
+    let monitor = ConsensusMonitor::new_with_limits(10, Duration::from_secs(30), &Registry::new());
+    monitor.observe_commit_lag(Duration::from_secs(40));
+    assert_eq!(monitor.commit_lag_estimate(), Duration::from_secs(40));
+    match monitor.check_backpressure(0) {
+        Err(SuiError::ValidatorOverloaded { retry_after_ms, .. }) => {
+            // Clients are asked to wait until the lag is stale.
+            assert_eq!(retry_after_ms, Some(40_000));
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+
+    // The estimate is a moving average.
+    monitor.observe_commit_lag(Duration::from_secs(0));
+    assert_eq!(monitor.commit_lag_estimate(), Duration::from_secs(35));
+    for _ in 0..10 {
+        monitor.observe_commit_lag(Duration::from_secs(1));
+    }
+    monitor.check_backpressure(0).unwrap();
+}
+
+#[test]
+fn test_stale_commit_lag() {
+    let monitor =
+        ConsensusMonitor::new_with_limits(10, Duration::from_millis(10), &Registry::new());
+    monitor.observe_commit_lag(Duration::from_millis(50));
+    assert!(monitor.check_backpressure(0).is_err());
+
+    // Without commits for as long as the lag, submissions are accepted again.
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(monitor.commit_lag_estimate(), Duration::ZERO);
+    monitor.check_backpressure(0).unwrap();
+    // And the lag is measured anew.
+    monitor.observe_commit_lag(Duration::from_millis(5));
+    assert_eq!(monitor.commit_lag_estimate(), Duration::from_millis(5));
+}
+
+#[test]
+fn test_observe_round() {
+    let monitor = ConsensusMonitor::new_for_test();
+    assert_eq!(monitor.last_committed_round(), None);
+    monitor.observe_round(3);
+    monitor.observe_round(5);
+    // Earlier rounds are ignored.
+    monitor.observe_round(4);
+    assert_eq!(monitor.committed_round.get(), 5);
+    assert_eq!(monitor.last_committed_round(), Some(5));
+    assert_eq!(monitor.round_latency.get_sample_count(), 1);
+}
+
+#[test]
+fn test_pending_certificates() {
+    let monitor = ConsensusMonitor::new_for_test();
+    let first = TransactionDigest::random();
+    let second = TransactionDigest::random();
+    monitor.observe_submitted(first);
+    std::thread::sleep(Duration::from_millis(10));
+    monitor.observe_submitted(second);
+    // A certificate submitted again is listed once, since its first submission.
+    monitor.observe_submitted(first);
+    let pending = monitor.pending_certificates();
+    assert_eq!(
+        pending
+            .iter()
+            .map(|(digest, _)| *digest)
+            .collect::<Vec<_>>(),
+        vec![first, second]
+    );
+    assert!(pending[0].1 >= pending[1].1 + Duration::from_millis(10));
+
+    // It stays pending until all its submissions settle.
+    monitor.observe_settled(&first);
+    monitor.observe_settled(&second);
+    assert_eq!(monitor.pending_certificates()[0].0, first);
+    monitor.observe_settled(&first);
+    assert!(monitor.pending_certificates().is_empty());
+}
@@ -62,4 +62,12 @@ module examples::object_basics {
         object::delete(id);
         transfer::transfer(o, tx_context::sender(ctx))
     }
+
+    /// Loops `iterations` times, e.g. to run out of gas.
+    public entry fun iterate(iterations: u64) {
+        let i = 0;
+        while (i < iterations) {
+            i = i + 1;
+        }
+    }
 }
@@ -0,0 +1,472 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scenarios in which authorities crash and recover, are partitioned from the client, or answer
+//! late, checking that `process_transaction` and `process_certificate` never certify conflicting
+//! transactions, and make progress once a quorum of authorities is reachable again. Under msim
+//! the scenarios run in simulated time, and are deterministic.
+
+use super::authority_aggregator_tests::{
+    get_latest_ref, init_local_authorities, transfer_coin_transaction,
+};
+use super::*;
+use crate::authority_client::{
+    BatchInfoResponseItemStream, CheckpointStreamResponseItemStream, LocalAuthorityClient,
+};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use sui_macros::sim_test;
+use sui_network::tonic::Code;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+/// How long partitions last, and how late skewed authorities answer: shorter than the time the
+/// client waits for a quorum, longer than the time it waits for the rest once it has one.
+const PARTITION_DURATION: Duration = Duration::from_secs(2);
+const CLOCK_SKEW: Duration = Duration::from_secs(3);
+
+/// The faults injected in the requests to one authority.
+struct Faults {
+    /// A crashed authority refuses connections. It recovers with the state it had.
+    crashed: AtomicBool,
+    /// Requests to a partitioned authority are held until the partition heals, like TCP
+    /// retransmissions, and are then delivered.
+    partitioned: watch::Sender<bool>,
+    partitioned_receiver: watch::Receiver<bool>,
+    /// Delay of the responses of the authority. Requests are still processed as soon as they
+    /// arrive: only the client sees the authority late.
+    response_delay: parking_lot::Mutex<Duration>,
+}
+
+impl Faults {
+    fn new() -> Self {
+        let (partitioned, partitioned_receiver) = watch::channel(false);
+        Self {
+            crashed: AtomicBool::new(false),
+            partitioned,
+            partitioned_receiver,
+            response_delay: parking_lot::Mutex::new(Duration::ZERO),
+        }
+    }
+
+    fn crash(&self) {
+        self.crashed.store(true, Ordering::SeqCst);
+    }
+
+    fn recover(&self) {
+        self.crashed.store(false, Ordering::SeqCst);
+    }
+
+    fn partition(&self) {
+        self.partitioned.send_replace(true);
+    }
+
+    fn heal(&self) {
+        self.partitioned.send_replace(false);
+    }
+
+    fn set_response_delay(&self, delay: Duration) {
+        *self.response_delay.lock() = delay;
+    }
+}
+
+#[derive(Clone)]
+struct FaultyAuthorityClient {
+    inner: LocalAuthorityClient,
+    faults: Arc<Faults>,
+}
+
+impl FaultyAuthorityClient {
+    async fn call<T>(&self, request: impl Future<Output = SuiResult<T>>) -> SuiResult<T> {
+        let mut partitioned = self.faults.partitioned_receiver.clone();
+        while *partitioned.borrow() {
+            partitioned.changed().await.unwrap();
+        }
+        if self.faults.crashed.load(Ordering::SeqCst) {
+            return Err(SuiError::RpcError(
+                "connection refused".to_string(),
+                Code::Unavailable.description(),
+            ));
+        }
+        let response = request.await;
+        let delay = *self.faults.response_delay.lock();
+        tokio::time::sleep(delay).await;
+        response
+    }
+}
+
+#[async_trait]
+impl AuthorityAPI for FaultyAuthorityClient {
+    async fn handle_transaction(
+        &self,
+        transaction: Transaction,
+    ) -> Result<TransactionInfoResponse, SuiError> {
+        self.call(self.inner.handle_transaction(transaction)).await
+    }
+
+    async fn handle_certificate(
+        &self,
+        certificate: CertifiedTransaction,
+    ) -> Result<TransactionInfoResponse, SuiError> {
+        self.call(self.inner.handle_certificate(certificate)).await
+    }
+
+    async fn handle_account_info_request(
+        &self,
+        request: AccountInfoRequest,
+    ) -> Result<AccountInfoResponse, SuiError> {
+        self.call(self.inner.handle_account_info_request(request))
+            .await
+    }
+
+    async fn handle_object_info_request(
+        &self,
+        request: ObjectInfoRequest,
+    ) -> Result<ObjectInfoResponse, SuiError> {
+        self.call(self.inner.handle_object_info_request(request))
+            .await
+    }
+
+    async fn handle_transaction_info_request(
+        &self,
+        request: TransactionInfoRequest,
+    ) -> Result<TransactionInfoResponse, SuiError> {
+        self.call(self.inner.handle_transaction_info_request(request))
+            .await
+    }
+
+    async fn handle_transactions_info_request(
+        &self,
+        request: TransactionsInfoRequest,
+    ) -> Result<TransactionsInfoResponse, SuiError> {
+        self.call(self.inner.handle_transactions_info_request(request))
+            .await
+    }
+
+    async fn handle_effects_info_request(
+        &self,
+        request: EffectsInfoRequest,
+    ) -> Result<EffectsInfoResponse, SuiError> {
+        self.call(self.inner.handle_effects_info_request(request))
+            .await
+    }
+
+    async fn handle_batch_stream(
+        &self,
+        request: BatchInfoRequest,
+    ) -> Result<BatchInfoResponseItemStream, SuiError> {
+        self.call(self.inner.handle_batch_stream(request)).await
+    }
+
+    async fn handle_checkpoint(
+        &self,
+        request: CheckpointRequest,
+    ) -> Result<CheckpointResponse, SuiError> {
+        self.call(self.inner.handle_checkpoint(request)).await
+    }
+
+    async fn handle_checkpoint_stream(
+        &self,
+        request: CheckpointStreamRequest,
+    ) -> Result<CheckpointStreamResponseItemStream, SuiError> {
+        self.call(self.inner.handle_checkpoint_stream(request))
+            .await
+    }
+
+    async fn handle_committee_info_request(
+        &self,
+        request: CommitteeInfoRequest,
+    ) -> Result<CommitteeInfoResponse, SuiError> {
+        self.call(self.inner.handle_committee_info_request(request))
+            .await
+    }
+
+    async fn handle_bridge_attestation_request(
+        &self,
+        request: BridgeAttestationRequest,
+    ) -> Result<BridgeAttestationResponse, SuiError> {
+        self.call(self.inner.handle_bridge_attestation_request(request))
+            .await
+    }
+
+    async fn handle_network_info_request(
+        &self,
+        request: NetworkInfoRequest,
+    ) -> Result<NetworkInfoResponse, SuiError> {
+        self.call(self.inner.handle_network_info_request(request))
+            .await
+    }
+}
+
+struct Scenario {
+    aggregator: AuthorityAggregator<FaultyAuthorityClient>,
+    /// The faults of each authority, in the order of their names.
+    faults: Vec<Arc<Faults>>,
+    sender: SuiAddress,
+    key: AccountKeyPair,
+    coin: ObjectID,
+    /// A coin for scenarios needing a second transfer after `coin` left the account.
+    spare_coin: ObjectID,
+    gas: ObjectID,
+}
+
+impl Scenario {
+    /// Four authorities of equal stake, tolerating one fault, and an account owning three coins.
+    async fn new() -> Self {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let coin = Object::with_owner_for_testing(sender);
+        let spare_coin = Object::with_owner_for_testing(sender);
+        let gas = Object::with_owner_for_testing(sender);
+        let genesis_objects = vec![coin.clone(), spare_coin.clone(), gas.clone()];
+        let (local, _, _) = init_local_authorities(4, genesis_objects).await;
+
+        let mut faults = Vec::new();
+        let clients = local
+            .clone_inner_clients()
+            .into_iter()
+            .map(|(name, inner)| {
+                let client_faults = Arc::new(Faults::new());
+                faults.push(client_faults.clone());
+                (
+                    name,
+                    FaultyAuthorityClient {
+                        inner,
+                        faults: client_faults,
+                    },
+                )
+            })
+            .collect();
+        let aggregator = AuthorityAggregator::new_with_timeouts(
+            local.committee.clone(),
+            local.committee_store.clone(),
+            clients,
+            AuthAggMetrics::new_for_tests(),
+            Arc::new(SafeClientMetrics::new_for_tests()),
+            Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+            TimeoutConfig {
+                authority_request_timeout: Duration::from_secs(30),
+                pre_quorum_timeout: Duration::from_secs(30),
+                post_quorum_timeout: Duration::from_secs(1),
+                ..local.timeouts.clone()
+            },
+        );
+        Self {
+            aggregator,
+            faults,
+            sender,
+            key,
+            coin: coin.id(),
+            spare_coin: spare_coin.id(),
+            gas: gas.id(),
+        }
+    }
+
+    fn client(&self, index: usize) -> &SafeClient<FaultyAuthorityClient> {
+        self.aggregator
+            .authority_clients
+            .values()
+            .nth(index)
+            .unwrap()
+    }
+
+    /// The highest reference to `object_id` held by the authorities which are not crashed.
+    async fn latest_ref(&self, object_id: ObjectID) -> ObjectRef {
+        let mut latest = None;
+        for (index, faults) in self.faults.iter().enumerate() {
+            if !faults.crashed.load(Ordering::SeqCst) {
+                let object_ref = get_latest_ref(self.client(index), object_id).await;
+                latest = latest.max(Some(object_ref));
+            }
+        }
+        latest.unwrap()
+    }
+
+    /// A transaction transferring the coin of the account to `recipient`.
+    async fn transfer_coin(&self, recipient: SuiAddress) -> Transaction {
+        self.transfer_object(self.coin, recipient).await
+    }
+
+    /// A transaction transferring `object_id`, owned by the account, to `recipient`.
+    async fn transfer_object(&self, object_id: ObjectID, recipient: SuiAddress) -> Transaction {
+        transfer_coin_transaction(
+            self.sender,
+            &self.key,
+            recipient,
+            self.latest_ref(object_id).await,
+            self.latest_ref(self.gas).await,
+        )
+    }
+
+    /// Whether the authority at `index` executed the transaction of `digest`.
+    async fn executed(&self, index: usize, digest: TransactionDigest) -> bool {
+        self.client(index)
+            .authority_client()
+            .inner
+            .handle_transaction_info_request(digest.into())
+            .await
+            .unwrap()
+            .signed_effects
+            .is_some()
+    }
+}
+
+#[sim_test]
+async fn test_crash_recovery_of_f_validators() {
+    let scenario = Scenario::new().await;
+    let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+
+    // With f authorities crashed, the others form a quorum. The coin is transferred to its
+    // owner, so that it can be transferred again.
+    scenario.faults[0].crash();
+    let coin_ref = scenario.latest_ref(scenario.coin).await;
+    let gas_ref = scenario.latest_ref(scenario.gas).await;
+    let transfer1 = scenario.transfer_coin(scenario.sender).await;
+    let cert1 = scenario
+        .aggregator
+        .process_transaction(transfer1.clone())
+        .await
+        .unwrap();
+    scenario
+        .aggregator
+        .process_certificate(cert1.clone())
+        .await
+        .unwrap();
+    assert!(!scenario.executed(0, *transfer1.digest()).await);
+
+    // Another authority crashes once the first recovered. The recovered authority has not
+    // signed the first transfer, but the others have locked the coin for it, so no conflicting
+    // transfer can be certified.
+    scenario.faults[0].recover();
+    scenario.faults[3].crash();
+    let conflicting =
+        transfer_coin_transaction(scenario.sender, &scenario.key, recipient, coin_ref, gas_ref);
+    assert!(scenario
+        .aggregator
+        .process_transaction(conflicting)
+        .await
+        .is_err());
+
+    // Driving the certificate again brings the recovered authority up to date, after which the
+    // coin can be transferred again without the crashed authority.
+    scenario
+        .aggregator
+        .process_certificate(cert1.clone())
+        .await
+        .unwrap();
+    assert!(scenario.executed(0, *transfer1.digest()).await);
+    let transfer2 = scenario.transfer_coin(recipient).await;
+    let cert2 = scenario
+        .aggregator
+        .process_transaction(transfer2.clone())
+        .await
+        .unwrap();
+    let effects2 = scenario
+        .aggregator
+        .process_certificate(cert2.clone())
+        .await
+        .unwrap();
+    assert_eq!(effects2.effects.transaction_digest, *transfer2.digest());
+
+    // Once recovered, the last authority catches up as well.
+    scenario.faults[3].recover();
+    assert!(!scenario.executed(3, *transfer2.digest()).await);
+    scenario
+        .aggregator
+        .process_certificate(cert2)
+        .await
+        .unwrap();
+    assert!(scenario.executed(3, *transfer2.digest()).await);
+}
+
+/// Partitions the authorities at `indexes` from the client for `PARTITION_DURATION`.
+fn partition(scenario: &Arc<Scenario>, indexes: &[usize]) -> tokio::task::JoinHandle<()> {
+    for index in indexes {
+        scenario.faults[*index].partition();
+    }
+    let scenario = scenario.clone();
+    let indexes = indexes.to_vec();
+    tokio::spawn(async move {
+        tokio::time::sleep(PARTITION_DURATION).await;
+        for index in indexes {
+            scenario.faults[index].heal();
+        }
+    })
+}
+
+#[sim_test]
+async fn test_partition_heals_mid_certificate() {
+    let scenario = Arc::new(Scenario::new().await);
+    let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+    let transfer = scenario.transfer_coin(recipient).await;
+
+    // Half of the authorities are partitioned while the transaction is signed: no quorum can
+    // be formed until the partition heals.
+    let start = Instant::now();
+    let healer = partition(&scenario, &[2, 3]);
+    let cert = scenario
+        .aggregator
+        .process_transaction(transfer.clone())
+        .await
+        .unwrap();
+    assert!(start.elapsed() >= PARTITION_DURATION);
+    healer.await.unwrap();
+
+    // The partition is back between the certificate and its execution. The reachable
+    // authorities execute it, but effects need a quorum, so they wait for the heal again.
+    let start = Instant::now();
+    let healer = partition(&scenario, &[1, 2]);
+    let effects = scenario
+        .aggregator
+        .process_certificate(cert.clone())
+        .await
+        .unwrap();
+    assert!(start.elapsed() >= PARTITION_DURATION);
+    assert_eq!(effects.effects.transaction_digest, *transfer.digest());
+    healer.await.unwrap();
+
+    // All authorities agree on the effects, whichever side of the partition they were on.
+    for index in 0..4 {
+        assert!(scenario.executed(index, *transfer.digest()).await);
+    }
+}
+
+#[sim_test]
+async fn test_clock_skew() {
+    let scenario = Scenario::new().await;
+    let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+
+    // One authority runs far behind the client, answering after the client stopped waiting for
+    // responses past the quorum.
+    scenario.faults[0].set_response_delay(CLOCK_SKEW);
+    let transfer = scenario.transfer_coin(recipient).await;
+    let start = Instant::now();
+    let cert = scenario
+        .aggregator
+        .process_transaction(transfer.clone())
+        .await
+        .unwrap();
+    let effects = scenario.aggregator.process_certificate(cert).await.unwrap();
+    assert!(start.elapsed() < CLOCK_SKEW);
+    assert_eq!(effects.effects.transaction_digest, *transfer.digest());
+
+    // The late authority still processed the requests, and executed the certificate.
+    tokio::time::sleep(CLOCK_SKEW).await;
+    assert!(scenario.executed(0, *transfer.digest()).await);
+
+    // With more than f authorities late, the client waits for them instead of failing. The coin
+    // was transferred away, so the spare one is transferred.
+    scenario.faults[1].set_response_delay(CLOCK_SKEW);
+    let transfer = scenario
+        .transfer_object(scenario.spare_coin, recipient)
+        .await;
+    let start = Instant::now();
+    let cert = scenario
+        .aggregator
+        .process_transaction(transfer.clone())
+        .await
+        .unwrap();
+    assert!(start.elapsed() >= CLOCK_SKEW);
+    scenario.aggregator.process_certificate(cert).await.unwrap();
+}
@@ -8,7 +8,7 @@ use crate::{
         AuthorityAPI, LocalAuthorityClient, LocalAuthorityClientFaultConfig,
         NetworkAuthorityClient, NetworkAuthorityClientMetrics,
     },
-    safe_client::SafeClientMetrics,
+    safe_client::{SafeClientMetrics, VerifiedCertificateCache},
 };
 use futures::StreamExt;
 use std::sync::Arc;
@@ -56,6 +56,282 @@ async fn test_simple_request() {
     client.handle_object_info_request(req).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_version_handshake() {
+    use sui_network::version::{PeerVersion, PROTOCOL_VERSION};
+
+    let sender = dbg_addr(1);
+    let object_id = dbg_object_id(1);
+    let authority_state = init_state_with_object_id(sender, object_id).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new_for_test(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_versions = server.peer_versions.clone();
+    let server_handle = server.spawn_for_test().await.unwrap();
+
+    let metrics = Arc::new(NetworkAuthorityClientMetrics::new_for_tests());
+    let client = NetworkAuthorityClient::connect(server_handle.address(), metrics.clone())
+        .await
+        .unwrap();
+    let req = ObjectInfoRequest::latest_object_info_request(object_id, None);
+    client
+        .handle_object_info_request(req.clone())
+        .await
+        .unwrap();
+    client.handle_object_info_request(req).await.unwrap();
+
+    // Both sides learnt the version of the other, and count it once.
+    let protocol_version = PROTOCOL_VERSION.to_string();
+    let application_version = env!("CARGO_PKG_VERSION");
+    assert_eq!(
+        metrics
+            .peer_versions
+            .peers(&protocol_version, application_version),
+        1
+    );
+    assert_eq!(
+        server_versions.peers(&protocol_version, application_version),
+        1
+    );
+    assert_eq!(metrics.peer_versions.incompatible_messages(), 0);
+    assert_eq!(server_versions.incompatible_messages(), 0);
+
+    // A peer requiring a newer protocol is reported on every message.
+    let newer = PeerVersion {
+        protocol_version: Some(PROTOCOL_VERSION + 2),
+        min_supported_protocol_version: Some(PROTOCOL_VERSION + 1),
+        application_version: None,
+    };
+    assert!(!newer.is_compatible());
+    server_versions.observe("peer", newer.clone());
+    server_versions.observe("peer", newer);
+    assert_eq!(server_versions.incompatible_messages(), 2);
+    assert_eq!(
+        server_versions.peers(&(PROTOCOL_VERSION + 2).to_string(), "unknown"),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_message_size_limits() {
+    use sui_network::channel_pool::{ChannelPool, ChannelPoolConfig};
+    use sui_network::message_size::MessageSizeLimits;
+
+    async fn spawn_server(message_size_limits: MessageSizeLimits) -> AuthorityServerHandle {
+        let authority_state = init_state_with_object_id(dbg_addr(1), dbg_object_id(1)).await;
+        let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+        let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+        let mut server = AuthorityServer::new_for_test(
+            "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+            Arc::new(authority_state),
+            consensus_address,
+            tx_consensus_listener,
+        );
+        server.message_size_limits = message_size_limits;
+        server.spawn_for_test().await.unwrap()
+    }
+
+    fn client(server: &AuthorityServerHandle, limits: MessageSizeLimits) -> NetworkAuthorityClient {
+        let pool_config = ChannelPoolConfig {
+            message_size_limits: limits,
+            ..Default::default()
+        };
+        let config = mysten_network::config::Config::new();
+        let channels = ChannelPool::connect_lazy(&config, &pool_config, server.address()).unwrap();
+        assert_eq!(channels.message_size_limits(), limits);
+        NetworkAuthorityClient::new_with_pool(
+            channels,
+            Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+        )
+    }
+
+    let req = ObjectInfoRequest::latest_object_info_request(dbg_object_id(1), None);
+    let server = spawn_server(MessageSizeLimits::default()).await;
+    client(&server, MessageSizeLimits::default())
+        .handle_object_info_request(req.clone())
+        .await
+        .unwrap();
+
+    // Clients bound the requests they send and the responses they receive.
+    let limits = MessageSizeLimits::new(Some(8), None);
+    let result = client(&server, limits)
+        .handle_object_info_request(req.clone())
+        .await;
+    assert!(result.is_err());
+    let limits = MessageSizeLimits::new(None, Some(16));
+    let result = client(&server, limits)
+        .handle_object_info_request(req.clone())
+        .await;
+    assert!(result.is_err());
+
+    // Servers bound the requests they receive.
+    let server = spawn_server(MessageSizeLimits::new(None, Some(8))).await;
+    let result = client(&server, MessageSizeLimits::default())
+        .handle_object_info_request(req)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_channel_inflight_requests() {
+    let sender = dbg_addr(1);
+    let object_id = dbg_object_id(1);
+    let authority_state = init_state_with_object_id(sender, object_id).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new_for_test(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn_for_test().await.unwrap();
+
+    let metrics = Arc::new(NetworkAuthorityClientMetrics::new_for_tests());
+    let client = NetworkAuthorityClient::connect(server_handle.address(), metrics.clone())
+        .await
+        .unwrap();
+    // A client created from a single channel does not know its address.
+    let labels = ["", "0"];
+    let inflight = metrics.channel_inflight_requests.with_label_values(&labels);
+
+    let req = ObjectInfoRequest::latest_object_info_request(object_id, None);
+    client.handle_object_info_request(req).await.unwrap();
+    assert_eq!(inflight.get(), 0);
+
+    // A streamed request is in flight until its stream is dropped.
+    let req = BatchInfoRequest {
+        start: None,
+        length: 10,
+    };
+    let stream = client.handle_batch_stream(req).await.unwrap();
+    assert_eq!(inflight.get(), 1);
+    drop(stream);
+    assert_eq!(inflight.get(), 0);
+
+    let requests = metrics.channel_requests.with_label_values(&labels);
+    assert_eq!(requests.get(), 2);
+}
+
+#[tokio::test]
+async fn test_bandwidth_metrics() {
+    let sender = dbg_addr(1);
+    let object_id = dbg_object_id(1);
+    let authority_state = init_state_with_object_id(sender, object_id).await;
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new_for_test(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn_for_test().await.unwrap();
+
+    let metrics = Arc::new(NetworkAuthorityClientMetrics::new_for_tests());
+    let client = NetworkAuthorityClient::connect(server_handle.address(), metrics.clone())
+        .await
+        .unwrap();
+    let req = ObjectInfoRequest::latest_object_info_request(
+        object_id,
+        Some(ObjectFormatOptions::default()),
+    );
+    client.handle_object_info_request(req).await.unwrap();
+
+    // Clients connected to a single channel do not know the address of the server.
+    let sent = metrics.bandwidth.bytes_sent("", "ObjectInfo");
+    let received = metrics.bandwidth.bytes_received("", "ObjectInfo");
+    assert!(sent > 0);
+    // The response holds the object and its layout, and is larger than the request.
+    assert!(received > sent);
+    assert_eq!(metrics.bandwidth.bytes_sent("", "AccountInfo"), 0);
+}
+
+#[tokio::test]
+async fn test_failover_after_reconfiguration() {
+    use crate::authority_client::make_network_authority_client_sets_from_committee;
+    use sui_network::channel_pool::ChannelPoolConfig;
+    use sui_types::base_types::AuthorityName;
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+    use sui_types::sui_system_state::ValidatorMetadata;
+
+    let sender = dbg_addr(1);
+    let object_id = dbg_object_id(1);
+    let authority_state = init_state_with_object_id(sender, object_id).await;
+    let mut system_state = authority_state.get_sui_system_state_object().await.unwrap();
+
+    let consensus_address = "/ip4/127.0.0.1/tcp/0/http".parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = tokio::sync::mpsc::channel(1);
+
+    let server = AuthorityServer::new_for_test(
+        "/ip4/127.0.0.1/tcp/0/http".parse().unwrap(),
+        Arc::new(authority_state),
+        consensus_address,
+        tx_consensus_listener,
+    );
+    let server_handle = server.spawn_for_test().await.unwrap();
+
+    // Nothing listens on the port of a dropped listener.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    let unreachable: Multiaddr = format!("/ip4/127.0.0.1/tcp/{port}/http").parse().unwrap();
+
+    // The validator joins the committee of the next epoch with an unreachable network address,
+    // and the address of the server as its backup.
+    let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+    let name: AuthorityName = key.public().into();
+    system_state
+        .validators
+        .next_epoch_validators
+        .push(ValidatorMetadata {
+            sui_address: sender.into(),
+            pubkey_bytes: name.as_ref().to_vec(),
+            network_pubkey_bytes: vec![],
+            proof_of_possession_bytes: vec![],
+            name: b"validator".to_vec(),
+            net_address: unreachable.to_vec(),
+            backup_net_addresses: vec![server_handle.address().to_vec()],
+            next_epoch_stake: 1,
+            next_epoch_delegation: 0,
+            next_epoch_gas_price: 1,
+        });
+    let committee = system_state.get_next_epoch_committee_with_net_addresses();
+
+    let clients = make_network_authority_client_sets_from_committee(
+        &committee,
+        &mysten_network::config::Config::new(),
+        &ChannelPoolConfig::default(),
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+    )
+    .unwrap();
+    let client = &clients[&name];
+
+    // The first request fails to reach the network address, the next ones fail over to the
+    // backup address.
+    let req = ObjectInfoRequest::latest_object_info_request(object_id, None);
+    assert!(client
+        .handle_object_info_request(req.clone())
+        .await
+        .is_err());
+    client
+        .handle_object_info_request(req.clone())
+        .await
+        .unwrap();
+    client.handle_object_info_request(req).await.unwrap();
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn test_subscription() {
     let sender = dbg_addr(1);
@@ -290,6 +566,7 @@ async fn test_subscription_safe_client() {
             fault_config: LocalAuthorityClientFaultConfig::default(),
         },
         state.committee_store().clone(),
+        Arc::new(VerifiedCertificateCache::new_for_tests()),
         state.name,
         Arc::new(SafeClientMetrics::new_for_tests()),
     );
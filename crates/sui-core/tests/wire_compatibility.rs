@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// This file pins the encoding of the messages exchanged with validators. Messages are sent over
+// gRPC with the bincode codec of sui-network, and signed or digested in their BCS encoding, so a
+// change in either encoding of a message breaks compatibility with deployed validators.
+//
+// If a test here fails, the PR changes the wire format: it should be marked as a breaking change,
+// and the golden bytes below updated only once the change is meant to be rolled out. The bytes
+// of signed messages depend on their keys, so their golden encodings pin the framing of the
+// signatures, while the signature bytes themselves are taken from the messages.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use sui_types::base_types::{
+    ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::crypto::{
+    get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthoritySignInfo, KeypairTraits,
+};
+use sui_types::messages::{
+    AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, CheckpointStreamRequest,
+    CommitteeInfoRequest, CommitteeInfoResponse, EffectsInfoRequest, EffectsInfoResponse,
+    ObjectContentOptions, ObjectInfoRequest, SenderSignedData, SignedTransaction,
+    TransactionInfoRequest, TransactionInfoResponse, TransactionsInfoRequest,
+};
+use sui_types::messages_checkpoint::CheckpointRequest;
+use sui_types::sui_serde::{Base64, Encoding};
+use test_utils::messages::{
+    make_random_certified_transaction, make_transfer_sui_transaction,
+    make_tx_certs_and_signed_effects, random_object_ref,
+};
+
+fn object_id() -> ObjectID {
+    ObjectID::new([2; ObjectID::LENGTH])
+}
+
+fn address() -> SuiAddress {
+    SuiAddress::from(ObjectID::new([3; ObjectID::LENGTH]))
+}
+
+/// Little endian encoding of a u64, as done by both BCS and bincode.
+fn u64_hex(value: u64) -> String {
+    hex::encode(value.to_le_bytes())
+}
+
+/// ULEB128 encoding of a length, as prefixed by BCS to sequences.
+fn uleb128_hex(mut len: usize) -> String {
+    let mut bytes = vec![];
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bytes.push(byte);
+            return hex::encode(bytes);
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// The BCS and bincode encodings of a sequence of bytes, with their length prefix.
+fn bytes_hex(bytes: &[u8]) -> (String, String) {
+    let bytes_hex = hex::encode(bytes);
+    (
+        format!("{}{bytes_hex}", uleb128_hex(bytes.len())),
+        format!("{}{bytes_hex}", u64_hex(bytes.len() as u64)),
+    )
+}
+
+/// The BCS and bincode encodings of the data and sender signature of a transaction.
+fn signed_data_hex(signed_data: &SenderSignedData) -> (String, String) {
+    let data_bcs = hex::encode(bcs::to_bytes(&signed_data.data).unwrap());
+    let data_bincode = hex::encode(bincode::serialize(&signed_data.data).unwrap());
+    let (signature_bcs, signature_bincode) = bytes_hex(signed_data.tx_signature.as_ref());
+    (
+        format!("{data_bcs}{signature_bcs}"),
+        format!("{data_bincode}{signature_bincode}"),
+    )
+}
+
+/// The BCS and bincode encodings of the signature of a single authority.
+fn auth_sign_info_hex(info: &AuthoritySignInfo) -> (String, String) {
+    let epoch = u64_hex(info.epoch);
+    let (authority_bcs, authority_bincode) = bytes_hex(info.authority.as_ref());
+    let (signature_bcs, signature_bincode) = bytes_hex(info.signature.as_ref());
+    (
+        format!("{epoch}{authority_bcs}{signature_bcs}"),
+        format!("{epoch}{authority_bincode}{signature_bincode}"),
+    )
+}
+
+/// A bitmap of signers in the roaring format, for committees small enough to fit in a single
+/// array container.
+fn signers_map_bytes(signers: &[u32]) -> Vec<u8> {
+    // Cookie of bitmaps without run containers, then the number of containers.
+    let mut bytes = hex::decode("3a30000001000000").unwrap();
+    // Key and cardinality minus one of the container, then the offset of its values.
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&(signers.len() as u16 - 1).to_le_bytes());
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    for signer in signers {
+        bytes.extend_from_slice(&(*signer as u16).to_le_bytes());
+    }
+    bytes
+}
+
+/// Asserts the BCS and bincode encodings of `message` are the given hex strings, and that they
+/// decode back to the same bytes.
+fn assert_encoding<T>(name: &str, message: &T, bcs_hex: &str, bincode_hex: &str)
+where
+    T: Serialize + DeserializeOwned + Debug,
+{
+    let bcs_bytes = bcs::to_bytes(message).unwrap();
+    assert_eq!(
+        hex::encode(&bcs_bytes),
+        bcs_hex,
+        "The BCS encoding of {name} changed, which breaks compatibility with deployed validators"
+    );
+    let decoded: T = bcs::from_bytes(&bcs_bytes).unwrap();
+    assert_eq!(bcs::to_bytes(&decoded).unwrap(), bcs_bytes);
+
+    let bincode_bytes = bincode::serialize(message).unwrap();
+    assert_eq!(
+        hex::encode(&bincode_bytes),
+        bincode_hex,
+        "The wire encoding of {name} changed, which breaks compatibility with deployed validators"
+    );
+    let decoded: T = bincode::deserialize(&bincode_bytes).unwrap();
+    assert_eq!(bincode::serialize(&decoded).unwrap(), bincode_bytes);
+}
+
+#[test]
+fn request_encodings_are_stable() {
+    let digest = "01".repeat(32);
+    let id = "02".repeat(20);
+    let owner = "03".repeat(20);
+
+    assert_encoding(
+        "TransactionInfoRequest",
+        &TransactionInfoRequest::from(TransactionDigest::new([1; 32])),
+        &format!("20{digest}"),
+        &format!("{}{digest}", u64_hex(32)),
+    );
+    assert_encoding(
+        "TransactionsInfoRequest",
+        &TransactionsInfoRequest::from(vec![TransactionDigest::new([1; 32])]),
+        &format!("0120{digest}"),
+        &format!("{}{}{digest}", u64_hex(1), u64_hex(32)),
+    );
+    assert_encoding(
+        "EffectsInfoRequest",
+        &EffectsInfoRequest::from(TransactionEffectsDigest([1; 32])),
+        &format!("20{digest}"),
+        &format!("{}{digest}", u64_hex(32)),
+    );
+    assert_encoding(
+        "ObjectInfoRequest::LatestObjectInfo",
+        &ObjectInfoRequest::latest_object_info_request(object_id(), None),
+        &format!("{id}0000"),
+        &format!("{id}0000000000"),
+    );
+    assert_encoding(
+        "ObjectInfoRequest::PastObjectInfo",
+        &ObjectInfoRequest::past_object_info_request(object_id(), SequenceNumber::from_u64(5)),
+        &format!("{id}01{}", u64_hex(5)),
+        &format!("{id}01000000{}", u64_hex(5)),
+    );
+    assert_encoding(
+        "ObjectInfoRequest::LatestObjectContent",
+        &ObjectInfoRequest::latest_object_content_request(
+            object_id(),
+            ObjectContentOptions::WithOwnerOnly,
+        ),
+        &format!("{id}0303"),
+        &format!("{id}0300000003000000"),
+    );
+    assert_encoding(
+        "AccountInfoRequest",
+        &AccountInfoRequest { account: address() },
+        &owner,
+        &owner,
+    );
+    assert_encoding(
+        "BatchInfoRequest",
+        &BatchInfoRequest {
+            start: Some(7),
+            length: 100,
+        },
+        &format!("01{}{}", u64_hex(7), u64_hex(100)),
+        &format!("01{}{}", u64_hex(7), u64_hex(100)),
+    );
+    assert_encoding(
+        "CheckpointStreamRequest",
+        &CheckpointStreamRequest::new(),
+        &u64_hex(0),
+        &u64_hex(0),
+    );
+    assert_encoding(
+        "CommitteeInfoRequest",
+        &CommitteeInfoRequest { epoch: Some(3) },
+        &format!("01{}", u64_hex(3)),
+        &format!("01{}", u64_hex(3)),
+    );
+    assert_encoding(
+        "CheckpointRequest::AuthenticatedCheckpoint",
+        &CheckpointRequest::authenticated(Some(9), true),
+        &format!("0001{}01", u64_hex(9)),
+        &format!("0000000001{}01", u64_hex(9)),
+    );
+    assert_encoding(
+        "CheckpointRequest::CheckpointProposal",
+        &CheckpointRequest::proposal(false),
+        "0100",
+        "0100000000",
+    );
+}
+
+#[test]
+fn unsigned_response_encodings_are_stable() {
+    let id = "02".repeat(20);
+    let owner = "03".repeat(20);
+    let object_digest = "05".repeat(32);
+
+    assert_encoding(
+        "TransactionInfoResponse",
+        &TransactionInfoResponse {
+            signed_transaction: None,
+            certified_transaction: None,
+            signed_effects: None,
+        },
+        "000000",
+        "000000",
+    );
+    assert_encoding(
+        "EffectsInfoResponse",
+        &EffectsInfoResponse {
+            signed_effects: None,
+        },
+        "00",
+        "00",
+    );
+    assert_encoding(
+        "AccountInfoResponse",
+        &AccountInfoResponse {
+            object_ids: vec![(
+                object_id(),
+                SequenceNumber::from_u64(1),
+                ObjectDigest::new([5; 32]),
+            )],
+            owner: address(),
+        },
+        &format!("01{id}{}20{object_digest}{owner}", u64_hex(1)),
+        &format!(
+            "{}{id}{}{}{object_digest}{owner}",
+            u64_hex(1),
+            u64_hex(1),
+            u64_hex(32)
+        ),
+    );
+    assert_encoding(
+        "CommitteeInfoResponse",
+        &CommitteeInfoResponse {
+            epoch: 1,
+            committee_info: None,
+        },
+        &format!("{}00", u64_hex(1)),
+        &format!("{}00", u64_hex(1)),
+    );
+}
+
+#[test]
+fn signed_response_encodings_are_stable() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let transaction =
+        make_transfer_sui_transaction(random_object_ref(), address(), Some(1), sender, &sender_key);
+    let (mut certificates, mut effects) =
+        make_tx_certs_and_signed_effects(vec![transaction.clone()]);
+    let (certificate, effects) = (certificates.remove(0), effects.remove(0));
+    let (_, authority_key): (_, AuthorityKeyPair) = get_key_pair();
+    let signed_transaction = SignedTransaction::new(
+        0,
+        transaction,
+        authority_key.public().into(),
+        &authority_key,
+    );
+
+    let (signed_data_bcs, signed_data_bincode) = signed_data_hex(&signed_transaction.signed_data);
+    let (vote_bcs, vote_bincode) = auth_sign_info_hex(&signed_transaction.auth_sign_info);
+
+    // Quorum signatures carry the aggregated signature as a base64 string, and their signers as
+    // a roaring bitmap.
+    let quorum = &certificate.auth_sign_info;
+    let epoch = u64_hex(quorum.epoch);
+    let aggregated = Base64::encode(quorum.signature.as_ref());
+    let (aggregated_bcs, aggregated_bincode) = bytes_hex(aggregated.as_bytes());
+    let signers: Vec<u32> = quorum.signers_map.iter().collect();
+    let (signers_bcs, signers_bincode) = bytes_hex(&signers_map_bytes(&signers));
+
+    let effects_bcs = hex::encode(bcs::to_bytes(&effects.effects).unwrap());
+    let effects_bincode = hex::encode(bincode::serialize(&effects.effects).unwrap());
+    let (effects_sig_bcs, effects_sig_bincode) = auth_sign_info_hex(&effects.auth_signature);
+
+    assert_encoding(
+        "TransactionInfoResponse",
+        &TransactionInfoResponse {
+            signed_transaction: Some(signed_transaction),
+            certified_transaction: Some(certificate),
+            signed_effects: Some(effects.clone()),
+        },
+        &format!(
+            "01{signed_data_bcs}{vote_bcs}\
+             01{signed_data_bcs}{epoch}{aggregated_bcs}{signers_bcs}\
+             01{effects_bcs}{effects_sig_bcs}"
+        ),
+        &format!(
+            "01{signed_data_bincode}{vote_bincode}\
+             01{signed_data_bincode}{epoch}{aggregated_bincode}{signers_bincode}\
+             01{effects_bincode}{effects_sig_bincode}"
+        ),
+    );
+    assert_encoding(
+        "EffectsInfoResponse",
+        &EffectsInfoResponse {
+            signed_effects: Some(effects),
+        },
+        &format!("01{effects_bcs}{effects_sig_bcs}"),
+        &format!("01{effects_bincode}{effects_sig_bincode}"),
+    );
+}
+
+#[test]
+fn signed_message_encodings_are_canonical() {
+    // Signatures are checked against the re-encoded message, so decoding and encoding a signed
+    // message must give back the exact bytes that were received.
+    let certificate = make_random_certified_transaction();
+
+    let bcs_bytes = bcs::to_bytes(&certificate).unwrap();
+    let decoded: sui_types::messages::CertifiedTransaction = bcs::from_bytes(&bcs_bytes).unwrap();
+    assert_eq!(bcs::to_bytes(&decoded).unwrap(), bcs_bytes);
+
+    let bincode_bytes = bincode::serialize(&certificate).unwrap();
+    let decoded: sui_types::messages::CertifiedTransaction =
+        bincode::deserialize(&bincode_bytes).unwrap();
+    assert_eq!(bincode::serialize(&decoded).unwrap(), bincode_bytes);
+    assert_eq!(decoded.digest(), certificate.digest());
+}
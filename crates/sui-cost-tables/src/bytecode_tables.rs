@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::ops::Mul;
+use std::sync::Arc;
 
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_binary_format::file_format_common::Opcodes::{self};
@@ -41,6 +42,19 @@ pub const MIN_EXISTS_DATA_SIZE: AbstractMemorySize = AbstractMemorySize::new(100
 
 static ZERO_COST_SCHEDULE: Lazy<CostTable> = Lazy::new(zero_cost_schedule);
 
+/// Observes the gas charged by a `GasStatus`, e.g. to profile which Move functions a
+/// transaction spends its gas in. Observing has no effect on the gas charged.
+pub trait GasObserver: Send + Sync {
+    /// Execution of the entry function `function` of `module_id` starts.
+    fn on_entry(&self, module_id: &ModuleId, function: &str);
+    /// The current function calls `function` of `module_id`. Called after the call is charged.
+    fn on_call(&self, module_id: &ModuleId, function: &str);
+    /// The current function returns. Called after the return is charged.
+    fn on_return(&self);
+    /// `amount` is charged, either for the execution of a native function or of an instruction.
+    fn on_charge(&self, amount: InternalGas, native: bool);
+}
+
 /// The Move VM implementation of state for gas metering.
 ///
 /// Initialize with a `CostTable` and the gas provided to the transaction.
@@ -51,6 +65,7 @@ pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGas,
     charge: bool,
+    observer: Option<Arc<dyn GasObserver>>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -63,6 +78,7 @@ impl<'a> GasStatus<'a> {
             gas_left: gas_left.to_unit(),
             cost_table,
             charge: true,
+            observer: None,
         }
     }
 
@@ -75,6 +91,7 @@ impl<'a> GasStatus<'a> {
             gas_left: InternalGas::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
+            observer: None,
         }
     }
 
@@ -107,11 +124,12 @@ impl<'a> GasStatus<'a> {
     }
 
     fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
-        self.deduct_gas(
+        self.deduct_observed_gas(
             self.cost_table
                 .instruction_cost(opcode as u8)
                 .total()
                 .into(),
+            false,
         )
     }
 
@@ -124,17 +142,40 @@ impl<'a> GasStatus<'a> {
         // Make sure that the size is always non-zero
         let size = std::cmp::max(1.into(), size);
         debug_assert!(size > 0.into());
-        self.deduct_gas(
+        self.deduct_observed_gas(
             InternalGasPerAbstractMemoryUnit::new(
                 self.cost_table.instruction_cost(opcode as u8).total(),
             )
             .mul(size),
+            false,
         )
     }
 
+    /// Charge gas spent executing Move code, and report it to the observer if any.
+    fn deduct_observed_gas(&mut self, amount: InternalGas, native: bool) -> PartialVMResult<()> {
+        if let Some(observer) = &self.observer {
+            observer.on_charge(amount, native);
+        }
+        self.deduct_gas(amount)
+    }
+
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled
     }
+
+    /// Report the gas charged from now on to `observer`.
+    pub fn set_observer(&mut self, observer: Arc<dyn GasObserver>) {
+        self.observer = Some(observer)
+    }
+
+    /// Notify the observer, if any, that the entry function `function` of `module_id` is about
+    /// to be executed. The VM does not charge for calling entry functions, so it has to be told
+    /// by the caller of the VM.
+    pub fn observe_entry(&self, module_id: &ModuleId, function: &str) {
+        if let Some(observer) = &self.observer {
+            observer.on_entry(module_id, function);
+        }
+    }
 }
 
 fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
@@ -196,29 +237,44 @@ fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
 impl<'b> GasMeter for GasStatus<'b> {
     /// Charge an instruction and fail if not enough gas units are left.
     fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
-        self.charge_instr(get_simple_instruction_opcode(instr))
+        let is_return = matches!(instr, SimpleInstruction::Ret);
+        self.charge_instr(get_simple_instruction_opcode(instr))?;
+        if is_return {
+            if let Some(observer) = &self.observer {
+                observer.on_return();
+            }
+        }
+        Ok(())
     }
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
-        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())
+        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())?;
+        if let Some(observer) = &self.observer {
+            observer.on_call(module_id, func_name);
+        }
+        Ok(())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
         self.charge_instr_with_size(
             Opcodes::CALL_GENERIC,
             ((ty_args.len() + args.len() + 1) as u64).into(),
-        )
+        )?;
+        if let Some(observer) = &self.observer {
+            observer.on_call(module_id, func_name);
+        }
+        Ok(())
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
@@ -410,7 +466,7 @@ impl<'b> GasMeter for GasStatus<'b> {
     }
 
     fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
-        self.deduct_gas(amount)
+        self.deduct_observed_gas(amount, true)
     }
 }
 
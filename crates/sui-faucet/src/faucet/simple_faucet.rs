@@ -55,7 +55,7 @@ impl SimpleFaucet {
         // Sync to have the latest status
         if wallet.client.is_gateway() {
             SuiClientCommands::SyncClientState {
-                address: Some(active_address),
+                address: Some(active_address.into()),
             }
             .execute(&mut wallet)
             .await
@@ -393,6 +393,7 @@ impl SimpleFaucet {
             effects,
             timestamp_ms: None,
             parsed_data: None,
+            balance_changes: None,
         })
     }
 
@@ -563,7 +564,7 @@ mod tests {
 
         // Now we transfer one gas out
         let res = SuiClientCommands::TransferSui {
-            to: SuiAddress::random_for_testing_only(),
+            to: SuiAddress::random_for_testing_only().into(),
             sui_coin_object_id: *bad_gas.id(),
             amount: None,
             gas_budget: 50000,
@@ -683,7 +684,7 @@ mod tests {
     async fn get_current_gases(address: SuiAddress, context: &mut WalletContext) -> Vec<GasCoin> {
         // Get the latest list of gas
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await
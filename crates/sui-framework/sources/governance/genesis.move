@@ -34,6 +34,7 @@ module sui::genesis {
         validator_sui_addresses: vector<address>,
         validator_names: vector<vector<u8>>,
         validator_net_addresses: vector<vector<u8>>,
+        validator_backup_net_addresses: vector<vector<vector<u8>>>,
         validator_stakes: vector<u64>,
         validator_gas_prices: vector<u64>,
         ctx: &mut TxContext,
@@ -47,6 +48,7 @@ module sui::genesis {
                 && vector::length(&validator_stakes) == count
                 && vector::length(&validator_names) == count
                 && vector::length(&validator_net_addresses) == count
+                && vector::length(&validator_backup_net_addresses) == count
                 && vector::length(&validator_gas_prices) == count,
             1
         );
@@ -58,6 +60,7 @@ module sui::genesis {
             let proof_of_possession = *vector::borrow(&validator_proof_of_possessions, i);
             let name = *vector::borrow(&validator_names, i);
             let net_address = *vector::borrow(&validator_net_addresses, i);
+            let backup_net_addresses = *vector::borrow(&validator_backup_net_addresses, i);
             let stake = *vector::borrow(&validator_stakes, i);
             let gas_price = *vector::borrow(&validator_gas_prices, i);
             vector::push_back(&mut validators, validator::new(
@@ -67,6 +70,7 @@ module sui::genesis {
                 proof_of_possession,
                 name,
                 net_address,
+                backup_net_addresses,
                 balance::increase_supply(&mut sui_supply, stake),
                 option::none(),
                 gas_price,
@@ -114,6 +114,7 @@ module sui::sui_system {
         proof_of_possession: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        backup_net_addresses: vector<vector<u8>>,
         stake: Coin<SUI>,
         gas_price: u64,
         ctx: &mut TxContext,
@@ -134,6 +135,7 @@ module sui::sui_system {
             proof_of_possession,
             name,
             net_address,
+            backup_net_addresses,
             coin::into_balance(stake),
             option::none(),
             gas_price,
@@ -43,6 +43,9 @@ module sui::validator {
         name: vector<u8>,
         /// The network address of the validator (could also contain extra info such as port, DNS and etc.).
         net_address: vector<u8>,
+        /// Other network addresses of the validator, e.g. an IPv6 or a backup address, which clients
+        /// dial in order when the network address is unreachable.
+        backup_net_addresses: vector<vector<u8>>,
         /// Total amount of validator stake that would be active in the next epoch.
         next_epoch_stake: u64,
         /// Total amount of delegated stake that would be active in the next epoch.
@@ -69,6 +72,9 @@ module sui::validator {
 
     const PROOF_OF_POSSESSION_DOMAIN: vector<u8> = vector[107, 111, 115, 107];
 
+    /// The maximum number of backup network addresses of a validator.
+    const MAX_BACKUP_NET_ADDRESSES: u64 = 4;
+
     fun verify_proof_of_possession(
         proof_of_possession: vector<u8>,
         sui_address: address,
@@ -86,6 +92,16 @@ module sui::validator {
         );
     }
 
+    fun check_backup_net_addresses(backup_net_addresses: &vector<vector<u8>>) {
+        let count = vector::length(backup_net_addresses);
+        assert!(count <= MAX_BACKUP_NET_ADDRESSES, 0);
+        let i = 0;
+        while (i < count) {
+            assert!(vector::length(vector::borrow(backup_net_addresses, i)) <= 128, 0);
+            i = i + 1;
+        };
+    }
+
     public(friend) fun new(
         sui_address: address,
         pubkey_bytes: vector<u8>,
@@ -93,6 +109,7 @@ module sui::validator {
         proof_of_possession: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        backup_net_addresses: vector<vector<u8>>,
         stake: Balance<SUI>,
         coin_locked_until_epoch: Option<EpochTimeLock>,
         gas_price: u64,
@@ -103,6 +120,7 @@ module sui::validator {
             vector::length(&net_address) <= 128 && vector::length(&name) <= 128 && vector::length(&pubkey_bytes) <= 128,
             0
         );
+        check_backup_net_addresses(&backup_net_addresses);
         verify_proof_of_possession(
             proof_of_possession,
             sui_address,
@@ -120,6 +138,7 @@ module sui::validator {
                 proof_of_possession,
                 name,
                 net_address,
+                backup_net_addresses,
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
@@ -275,6 +294,7 @@ module sui::validator {
         proof_of_possession: vector<u8>,
         name: vector<u8>,
         net_address: vector<u8>,
+        backup_net_addresses: vector<vector<u8>>,
         stake: Balance<SUI>,
         coin_locked_until_epoch: Option<EpochTimeLock>,
         gas_price: u64,
@@ -285,6 +305,7 @@ module sui::validator {
             vector::length(&net_address) <= 128 && vector::length(&name) <= 128 && vector::length(&pubkey_bytes) <= 128,
             0
         );
+        check_backup_net_addresses(&backup_net_addresses);
         // Check that the name is human-readable.
         ascii::string(copy name);
         let stake_amount = balance::value(&stake);
@@ -297,6 +318,7 @@ module sui::validator {
                 proof_of_possession,
                 name,
                 net_address,
+                backup_net_addresses,
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
@@ -113,7 +113,16 @@ pub fn build_move_package_to_bytes(
     path: &Path,
     build_config: BuildConfig,
 ) -> Result<Vec<Vec<u8>>, SuiError> {
-    build_move_package(path, build_config).map(|mods| {
+    let pkg = build_move_package_with_deps(path, build_config)?;
+    compiled_package_to_bytes(&pkg)
+}
+
+/// Given a package compiled with its dependencies, return the compiled modules of the package
+/// itself as Vec<Vec<u8>>. This is useful for when publishing a package whose compiled form is
+/// needed for more than its modules, e.g. for its source maps.
+pub fn compiled_package_to_bytes(pkg: &CompiledPackage) -> Result<Vec<Vec<u8>>, SuiError> {
+    verify_framework_version(pkg)?;
+    filter_package_modules(pkg).map(|mods| {
         mods.iter()
             .map(|m| {
                 let mut bytes = Vec::new();
@@ -25,6 +25,7 @@ module sui::governance_test_utils {
             x"FF",
             b"ValidatorName",
             x"FFFF",
+            vector[],
             balance::create_for_testing<SUI>(init_stake_amount),
             option::none(),
             1,
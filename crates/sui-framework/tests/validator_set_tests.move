@@ -173,6 +173,7 @@ module sui::validator_set_tests {
             vector[hint],
             vector[hint],
             vector[hint],
+            vector[],
             init_stake,
             option::none(),
             1,
@@ -191,6 +192,7 @@ module sui::validator_set_tests {
             vector[hint],
             vector[hint],
             vector[hint],
+            vector[],
             init_stake,
             option::none(),
             gas_price,
@@ -29,6 +29,7 @@ module sui::validator_tests {
                 vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
                 b"Validator1",
                 x"FFFF",
+                vector[],
                 init_stake,
                 option::none(),
                 1,
@@ -65,6 +66,7 @@ module sui::validator_tests {
             vector[150, 32, 70, 34, 231, 29, 255, 62, 248, 219, 245, 72, 85, 77, 190, 195, 251, 255, 166, 250, 229, 133, 29, 117, 17, 182, 0, 164, 162, 59, 36, 250, 78, 129, 8, 46, 106, 112, 197, 152, 219, 114, 241, 121, 242, 189, 75, 204],
             b"Validator1",
             x"FFFF",
+            vector[],
             init_stake,
             option::none(),
             1,
@@ -109,4 +111,28 @@ module sui::validator_tests {
         validator::destroy(validator, test_scenario::ctx(scenario));
         test_scenario::end(scenario_val);
     }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_too_many_backup_net_addresses() {
+        let sender = @0x8feebb589ffa14667ff721b7cfb186cfad6530fc;
+        let scenario_val = test_scenario::begin(sender);
+        let ctx = test_scenario::ctx(&mut scenario_val);
+        let init_stake = coin::into_balance(coin::mint_for_testing(10, ctx));
+        let validator = validator::new_for_testing(
+            sender,
+            x"FF",
+            x"FF",
+            x"FF",
+            b"Validator1",
+            x"FFFF",
+            vector[x"FF01", x"FF02", x"FF03", x"FF04", x"FF05"],
+            init_stake,
+            option::none(),
+            1,
+            ctx
+        );
+        validator::destroy(validator, ctx);
+        test_scenario::end(scenario_val);
+    }
 }
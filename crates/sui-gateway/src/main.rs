@@ -62,7 +62,7 @@ async fn main() -> anyhow::Result<()> {
 
     let address = SocketAddr::new(IpAddr::V4(options.host), options.port);
     let mut server =
-        JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), false, &prometheus_registry)?;
+        JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), false, &prometheus_registry, None)?;
     server.register_module(RpcGatewayImpl::new(client.clone()))?;
     server.register_module(GatewayReadApiImpl::new(client.clone()))?;
     server.register_module(TransactionBuilderImpl::new(client.clone()))?;
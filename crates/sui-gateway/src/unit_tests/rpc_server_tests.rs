@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
 use std::{path::Path, str::FromStr};
 
 use move_package::BuildConfig;
@@ -16,9 +17,12 @@ use sui_json_rpc::api::{
 };
 use sui_json_rpc_types::{GetObjectDataResponse, SuiTransactionResponse, TransactionBytes};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::gas_pool::{GasPool, GasPoolConfig, LeaseOutcome};
 use sui_sdk::SuiClient;
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::TransactionDigest;
+use sui_types::crypto::{EncodeDecodeBase64, SuiKeyPair};
+use sui_types::error::SuiError;
 use sui_types::gas_coin::GAS;
 use sui_types::messages::ExecuteTransactionRequestType;
 use sui_types::query::{Ordering, TransactionQuery};
@@ -38,7 +42,9 @@ async fn test_get_objects() -> Result<(), anyhow::Error> {
     let address = cluster.accounts.first().unwrap();
 
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
     assert_eq!(5, objects.len());
     Ok(())
 }
@@ -53,7 +59,9 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client().unwrap();
     let address = cluster.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
 
     let transaction_bytes: TransactionBytes = http_client
         .transfer_object(
@@ -90,7 +98,9 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client().unwrap();
     let address = cluster.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
     let gas = objects.first().unwrap();
 
     let compiled_modules = build_move_package_to_bytes(
@@ -127,7 +137,9 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client().unwrap();
     let address = cluster.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
     let gas = objects.first().unwrap();
     let coin = &objects[1];
 
@@ -178,10 +190,12 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client().unwrap();
     let address = cluster.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
 
     for oref in objects {
-        let result: GetObjectDataResponse = http_client.get_object(oref.object_id).await?;
+        let result: GetObjectDataResponse = http_client.get_object(oref.object_id, None).await?;
         assert!(
             matches!(result, GetObjectDataResponse::Exists(object) if oref.object_id == object.id() && &object.owner.get_owner_address()? == address)
         );
@@ -201,7 +215,9 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
 
     http_client.sync_account_state(*address).await?;
 
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None)
+        .await?;
     let gas_id = objects.last().unwrap().object_id;
 
     // Make some transactions
@@ -233,7 +249,7 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
 
     // test get_transaction
     for tx_digest in tx {
-        let response: SuiTransactionResponse = http_client.get_transaction(tx_digest).await?;
+        let response: SuiTransactionResponse = http_client.get_transaction(tx_digest, None).await?;
         assert!(tx_responses.iter().any(
             |effects| effects.effects.transaction_digest == response.effects.transaction_digest
         ))
@@ -385,3 +401,137 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_execute_with_refresh() -> Result<(), anyhow::Error> {
+    let port = get_available_port();
+    let cluster = TestClusterBuilder::new()
+        .set_fullnode_rpc_port(port)
+        .build()
+        .await?;
+
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    let client = SuiClient::new_rpc_client(&format!("http://{}", addr), None).await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let address = *cluster.accounts.first().unwrap();
+
+    let objects = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let object = objects.first().unwrap();
+    let gas_id = objects.last().unwrap().object_id;
+
+    // Another transaction consumes the pinned version of the object.
+    let data = client
+        .transaction_builder()
+        .transfer_object(address, object.object_id, Some(gas_id), 1000, address)
+        .await?;
+    let consumer = client
+        .quorum_driver()
+        .execute_transaction(
+            to_sender_signed_transaction(data, keystore.get_key(&address)?),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let pinned = client
+        .transaction_builder()
+        .clone()
+        .with_pinned_objects([object.to_object_ref()]);
+    let error = pinned
+        .transfer_object(address, object.object_id, Some(gas_id), 1000, address)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<SuiError>(),
+        Some(SuiError::ObjectVersionConflict { .. })
+    ));
+
+    let execution = client
+        .execute_with_refresh(
+            vec![object.to_object_ref()],
+            2,
+            |builder| async move {
+                builder
+                    .transfer_object(address, object.object_id, Some(gas_id), 1000, address)
+                    .await
+            },
+            |data| {
+                Ok(to_sender_signed_transaction(
+                    data,
+                    keystore.get_key(&address)?,
+                ))
+            },
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    assert_eq!(1, execution.conflicts.len());
+    let conflict = &execution.conflicts[0];
+    assert_eq!(object.object_id, conflict.object_id);
+    assert_eq!(object.version, conflict.stale_version);
+    assert_eq!(Some(consumer.tx_digest), conflict.transaction);
+    assert_eq!(Some(address), conflict.sender);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gas_pool() -> Result<(), anyhow::Error> {
+    let port = get_available_port();
+    let cluster = TestClusterBuilder::new()
+        .set_fullnode_rpc_port(port)
+        .build()
+        .await?;
+
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    let client = SuiClient::new_rpc_client(&format!("http://{}", addr), None).await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let address = cluster.accounts[0];
+    let key = SuiKeyPair::decode_base64(&keystore.get_key(&address)?.encode_base64()).unwrap();
+
+    let config = GasPoolConfig {
+        target_balance: 100_000,
+        target_coins: 3,
+        rebalance_gas_budget: 10_000,
+        ..GasPoolConfig::new(address)
+    };
+    let pool = GasPool::new(client, Arc::new(key), config).await?;
+    assert_eq!(5, pool.stats().available_coins);
+
+    // The genesis coins are split into coins of the target balance, and the rest.
+    assert!(pool.maintain().await?.is_some());
+    let lease = pool.lease(1)?;
+    assert_eq!(100_000, lease.balance);
+
+    let data = pool
+        .transaction_builder(&lease)
+        .transfer_sui(
+            address,
+            lease.coin.0,
+            10_000,
+            cluster.accounts[1],
+            Some(1000),
+        )
+        .await?;
+    let (object_id, version, _) = lease.coin;
+    pool.execute(lease, data).await?;
+
+    // The coin is leased again at the version the transaction left it at.
+    let lease = pool.lease(1)?;
+    assert_eq!(object_id, lease.coin.0);
+    assert!(lease.coin.1 > version);
+    assert!(lease.balance < 99_000);
+
+    // A coin whose transaction may have been sent is not leased again at the same version.
+    let coin = lease.coin;
+    pool.release(lease, LeaseOutcome::Unknown).await;
+    assert_eq!(1, pool.stats().unverified_coins);
+    let lease = pool.lease(1)?;
+    assert_ne!(coin, lease.coin);
+    pool.release(lease, LeaseOutcome::NotSubmitted).await;
+    assert_eq!(1, pool.stats().unverified_coins);
+    assert_eq!(0, pool.stats().leased_coins);
+    Ok(())
+}
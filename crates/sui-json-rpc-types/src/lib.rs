@@ -4,14 +4,20 @@
 /// This file contain response types used by the GatewayAPI, most of the types mirrors it's internal type counterparts.
 /// These mirrored types allow us to optimise the JSON serde without impacting the internal types, which are optimise for storage.
 ///
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
 
 use colored::Colorize;
 use itertools::Itertools;
-use move_binary_format::file_format::{Ability, AbilitySet, StructTypeParameter, Visibility};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::errors::Location;
+use move_binary_format::file_format::{
+    Ability, AbilitySet, CompiledModule, StructTypeParameter, Visibility,
+};
 use move_binary_format::normalized::{
     Field as NormalizedField, Function as SuiNormalizedFunction, Module as NormalizedModule,
     Struct as NormalizedStruct, Type as NormalizedType,
@@ -20,6 +26,7 @@ use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveStructLayout, MoveValue};
+use multiaddr::Multiaddr;
 use schemars::JsonSchema;
 use serde::ser::Error;
 use serde::Deserialize;
@@ -29,30 +36,38 @@ use serde_with::serde_as;
 use tracing::warn;
 
 use sui_json::SuiJsonValue;
+use sui_types::balance::BalanceChange;
 use sui_types::base_types::{
-    ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
-    TransactionEffectsDigest,
+    AuthorityName, ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress,
+    TransactionDigest, TransactionEffectsDigest, TX_CONTEXT_MODULE_NAME, TX_CONTEXT_STRUCT_NAME,
 };
-use sui_types::committee::EpochId;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::bridge::CertifiedBridgeAttestation;
+use sui_types::committee::{CommitteeWithNetAddresses, EpochId, StakeUnit};
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
-use sui_types::error::SuiError;
+use sui_types::error::{ErrorCode, ExecutionError, SuiError};
 use sui_types::event::{Event, TransferType};
 use sui_types::event::{EventEnvelope, EventType};
-use sui_types::filter::{EventFilter, TransactionFilter};
+use sui_types::filter::{EventFilter, ObjectChangeFilter, TransactionFilter};
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
-    CallArg, CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionResponse,
-    ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, Pay, SingleTransactionKind,
-    TransactionData, TransactionEffects, TransactionKind,
+    CallArg, CertifiedTransaction, CertifiedTransactionEffects, CommitteeInfoResponse,
+    ExecuteTransactionResponse, ExecutionStatus, InputObjectKind, MoveCall, MoveModulePublish,
+    ObjectArg, Pay, SingleTransactionKind, TransactionData, TransactionEffects,
+    TransactionExpiration, TransactionKind,
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::{disassemble_modules, MovePackage};
+use sui_types::network_info::NetworkInfoBundle;
 use sui_types::object::{
     Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
 };
+use sui_types::object_change::{ObjectChange, ObjectChangeKind};
+use sui_types::query::AddressActivityRole;
+use sui_types::state_accumulator::ObjectInclusionProof;
 use sui_types::sui_serde::{Base64, Encoding};
-use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
+use sui_types::{parse_sui_struct_tag, parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS};
 
 #[cfg(test)]
 #[path = "unit_tests/rpc_types_tests.rs"]
@@ -60,6 +75,9 @@ mod rpc_types_tests;
 
 pub type SuiMoveTypeParameterIndex = u16;
 pub type TransactionsPage = Page<TransactionDigest, TransactionDigest>;
+pub type ReceivedObjectsPage = Page<SuiReceivedObject, ReceivedObjectsCursor>;
+pub type AddressActivityPage = Page<SuiAddressActivity, AddressActivityCursor>;
+pub type CoinPage = Page<SuiCoin, ObjectID>;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum SuiMoveAbility {
@@ -276,6 +294,37 @@ impl From<NormalizedType> for SuiMoveNormalizedType {
     }
 }
 
+/// Formats types the way Move source does, naming type parameters by index, e.g.
+/// `&mut 0x2::coin::Coin<T0>`.
+impl Display for SuiMoveNormalizedType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SuiMoveNormalizedType::Bool => write!(f, "bool"),
+            SuiMoveNormalizedType::U8 => write!(f, "u8"),
+            SuiMoveNormalizedType::U64 => write!(f, "u64"),
+            SuiMoveNormalizedType::U128 => write!(f, "u128"),
+            SuiMoveNormalizedType::Address => write!(f, "address"),
+            SuiMoveNormalizedType::Signer => write!(f, "signer"),
+            SuiMoveNormalizedType::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                write!(f, "{address}::{module}::{name}")?;
+                if !type_arguments.is_empty() {
+                    write!(f, "<{}>", type_arguments.iter().join(", "))?;
+                }
+                Ok(())
+            }
+            SuiMoveNormalizedType::Vector(t) => write!(f, "vector<{t}>"),
+            SuiMoveNormalizedType::TypeParameter(i) => write!(f, "T{i}"),
+            SuiMoveNormalizedType::Reference(t) => write!(f, "&{t}"),
+            SuiMoveNormalizedType::MutableReference(t) => write!(f, "&mut {t}"),
+        }
+    }
+}
+
 impl From<AbilitySet> for SuiMoveAbilitySet {
     fn from(set: AbilitySet) -> SuiMoveAbilitySet {
         Self {
@@ -292,6 +341,121 @@ impl From<AbilitySet> for SuiMoveAbilitySet {
     }
 }
 
+/// The interface of a package: the entry functions clients can call and the structs they
+/// handle, by module.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename = "PackageAbi", rename_all = "camelCase")]
+pub struct SuiPackageAbi {
+    pub package_id: ObjectID,
+    pub modules: BTreeMap<String, SuiModuleAbi>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename = "ModuleAbi", rename_all = "camelCase")]
+pub struct SuiModuleAbi {
+    pub entry_functions: BTreeMap<String, SuiEntryFunctionAbi>,
+    pub structs: BTreeMap<String, SuiMoveNormalizedStruct>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename = "EntryFunctionAbi", rename_all = "camelCase")]
+pub struct SuiEntryFunctionAbi {
+    pub type_parameters: Vec<SuiMoveAbilitySet>,
+    /// The parameters callers pass arguments for, without the `TxContext` the runtime passes.
+    pub parameters: Vec<SuiMoveNormalizedType>,
+    /// Whether the function takes a `TxContext` as its last parameter.
+    pub takes_tx_context: bool,
+}
+
+impl SuiPackageAbi {
+    pub fn new(package_id: ObjectID, modules: BTreeMap<String, SuiMoveNormalizedModule>) -> Self {
+        Self {
+            package_id,
+            modules: modules
+                .into_iter()
+                .map(|(name, module)| (name, module.into()))
+                .collect(),
+        }
+    }
+
+    /// Checks that a call to `module::function` with the given numbers of type arguments and
+    /// arguments matches the interface, so clients can reject a call before building it.
+    pub fn check_move_call(
+        &self,
+        module: &str,
+        function: &str,
+        type_arguments: usize,
+        arguments: usize,
+    ) -> Result<&SuiEntryFunctionAbi, anyhow::Error> {
+        let entry_function = self
+            .modules
+            .get(module)
+            .ok_or_else(|| anyhow::anyhow!("Package {} has no module {module}", self.package_id))?
+            .entry_functions
+            .get(function)
+            .ok_or_else(|| anyhow::anyhow!("Module {module} has no entry function {function}"))?;
+        if entry_function.type_parameters.len() != type_arguments {
+            return Err(anyhow::anyhow!(
+                "{module}::{function}: Expected {} type args, found {type_arguments}",
+                entry_function.type_parameters.len()
+            ));
+        }
+        if entry_function.parameters.len() != arguments {
+            return Err(anyhow::anyhow!(
+                "{module}::{function}: Expected {} args, found {arguments}",
+                entry_function.parameters.len()
+            ));
+        }
+        Ok(entry_function)
+    }
+}
+
+impl From<SuiMoveNormalizedModule> for SuiModuleAbi {
+    fn from(module: SuiMoveNormalizedModule) -> Self {
+        Self {
+            entry_functions: module
+                .exposed_functions
+                .into_iter()
+                .filter(|(_, function)| function.is_entry)
+                .map(|(name, function)| (name, function.into()))
+                .collect(),
+            structs: module.structs,
+        }
+    }
+}
+
+impl From<SuiMoveNormalizedFunction> for SuiEntryFunctionAbi {
+    fn from(function: SuiMoveNormalizedFunction) -> Self {
+        let mut parameters = function.parameters;
+        let takes_tx_context =
+            matches!(parameters.last(), Some(parameter) if is_tx_context(parameter));
+        if takes_tx_context {
+            parameters.pop();
+        }
+        Self {
+            type_parameters: function.type_parameters,
+            parameters,
+            takes_tx_context,
+        }
+    }
+}
+
+fn is_tx_context(type_: &SuiMoveNormalizedType) -> bool {
+    match type_ {
+        SuiMoveNormalizedType::Reference(inner)
+        | SuiMoveNormalizedType::MutableReference(inner) => {
+            matches!(
+                inner.as_ref(),
+                SuiMoveNormalizedType::Struct { address, module, name, .. }
+                    if *address == SUI_FRAMEWORK_ADDRESS.to_hex_literal()
+                        && module == TX_CONTEXT_MODULE_NAME.as_str()
+                        && name == TX_CONTEXT_STRUCT_NAME.as_str()
+            )
+        }
+        _ => false,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum ObjectValueKind {
     ByImmutableReference,
@@ -311,6 +475,8 @@ pub struct SuiTransactionResponse {
     pub effects: SuiTransactionEffects,
     pub timestamp_ms: Option<u64>,
     pub parsed_data: Option<SuiParsedTransactionResponse>,
+    /// None if the node does not index transactions, or has not processed this one yet.
+    pub balance_changes: Option<Vec<SuiBalanceChange>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
@@ -369,9 +535,30 @@ pub enum SuiExecuteTransactionResponse {
         // If the transaction is confirmed to be executed locally
         // before this response.
         confirmed_local_execution: bool,
+        /// Passed to reads of full nodes, makes them wait until the node has executed the
+        /// transaction, so that they observe its effects
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session_token: Option<SessionToken>,
     },
 }
 
+/// Token of the latest write of a client. Reads given the token only answer once the node has
+/// executed the write, so that clients read their writes wherever they are routed to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(transparent)]
+pub struct SessionToken(TransactionDigest);
+
+impl SessionToken {
+    pub fn new(tx_digest: TransactionDigest) -> Self {
+        Self(tx_digest)
+    }
+
+    /// The transaction reads wait for.
+    pub fn tx_digest(&self) -> &TransactionDigest {
+        &self.0
+    }
+}
+
 impl SuiExecuteTransactionResponse {
     pub fn from_execute_transaction_response(
         resp: ExecuteTransactionResponse,
@@ -396,6 +583,7 @@ impl SuiExecuteTransactionResponse {
                     certificate,
                     effects,
                     confirmed_local_execution: is_executed_locally,
+                    session_token: Some(SessionToken::new(tx_digest)),
                 }
             }
         })
@@ -1005,6 +1193,8 @@ pub enum SuiPastObjectRead<T: SuiData> {
     ObjectNotExists(ObjectID),
     /// The object is found to be deleted with this version
     ObjectDeleted(SuiObjectRef),
+    /// The object is found to be wrapped into another object with this version
+    ObjectWrapped(SuiObjectRef),
     /// The object exists but not found with this version
     VersionNotFound(ObjectID, SequenceNumber),
     /// The asked object version is higher than the latest
@@ -1022,6 +1212,9 @@ impl<T: SuiData> SuiPastObjectRead<T> {
             Self::ObjectDeleted(oref) => Err(SuiError::ObjectDeleted {
                 object_ref: oref.to_object_ref(),
             }),
+            Self::ObjectWrapped(oref) => Err(SuiError::ObjectWrapped {
+                object_ref: oref.to_object_ref(),
+            }),
             Self::ObjectNotExists(id) => Err(SuiError::ObjectNotFound { object_id: *id }),
             Self::VersionFound(o) => Ok(o),
             Self::VersionNotFound(id, seq_num) => Err(SuiError::ObjectVersionNotFound {
@@ -1046,6 +1239,9 @@ impl<T: SuiData> SuiPastObjectRead<T> {
             Self::ObjectDeleted(oref) => Err(SuiError::ObjectDeleted {
                 object_ref: oref.to_object_ref(),
             }),
+            Self::ObjectWrapped(oref) => Err(SuiError::ObjectWrapped {
+                object_ref: oref.to_object_ref(),
+            }),
             Self::ObjectNotExists(id) => Err(SuiError::ObjectNotFound { object_id: id }),
             Self::VersionFound(o) => Ok(o),
             Self::VersionNotFound(object_id, version) => {
@@ -1076,6 +1272,9 @@ impl<T: SuiData> TryFrom<PastObjectRead> for SuiPastObjectRead<T> {
             PastObjectRead::ObjectDeleted(oref) => {
                 Ok(SuiPastObjectRead::ObjectDeleted(oref.into()))
             }
+            PastObjectRead::ObjectWrapped(oref) => {
+                Ok(SuiPastObjectRead::ObjectWrapped(oref.into()))
+            }
             PastObjectRead::VersionNotFound(id, seq_num) => {
                 Ok(SuiPastObjectRead::VersionNotFound(id, seq_num))
             }
@@ -1384,7 +1583,13 @@ pub struct SuiTransactionData {
     pub transactions: Vec<SuiTransactionKind>,
     pub sender: SuiAddress,
     pub gas_payment: SuiObjectRef,
+    /// Coins merged into the gas payment before execution
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_gas_payment: Vec<SuiObjectRef>,
     pub gas_budget: u64,
+    /// The last epoch in which the transaction can be signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_epoch: Option<EpochId>,
 }
 
 impl Display for SuiTransactionData {
@@ -1420,7 +1625,16 @@ impl TryFrom<TransactionData> for SuiTransactionData {
             transactions,
             sender: data.signer(),
             gas_payment: data.gas().into(),
+            merged_gas_payment: data
+                .merged_gas_payment()
+                .iter()
+                .map(|object_ref| (*object_ref).into())
+                .collect(),
             gas_budget: data.gas_budget,
+            expiration_epoch: match data.expiration() {
+                TransactionExpiration::None => None,
+                TransactionExpiration::Epoch(epoch) => Some(*epoch),
+            },
         })
     }
 }
@@ -1523,33 +1737,7 @@ impl TryFrom<SingleTransactionKind> for SuiTransactionKind {
             }),
             SingleTransactionKind::Pay(p) => Self::Pay(p.into()),
             SingleTransactionKind::Publish(p) => Self::Publish(p.try_into()?),
-            SingleTransactionKind::Call(c) => Self::Call(SuiMoveCall {
-                package: c.package.into(),
-                module: c.module.to_string(),
-                function: c.function.to_string(),
-                type_arguments: c.type_arguments.iter().map(|ty| ty.to_string()).collect(),
-                arguments: c
-                    .arguments
-                    .into_iter()
-                    .map(|arg| match arg {
-                        CallArg::Pure(p) => SuiJsonValue::from_bcs_bytes(&p),
-                        CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
-                        | CallArg::Object(ObjectArg::SharedObject { id, .. }) => {
-                            SuiJsonValue::new(Value::String(id.to_hex_literal()))
-                        }
-                        CallArg::ObjVec(vec) => SuiJsonValue::new(Value::Array(
-                            vec.iter()
-                                .map(|obj_arg| match obj_arg {
-                                    ObjectArg::ImmOrOwnedObject((id, _, _))
-                                    | ObjectArg::SharedObject { id, .. } => {
-                                        Value::String(id.to_hex_literal())
-                                    }
-                                })
-                                .collect(),
-                        )),
-                    })
-                    .collect::<Result<Vec<_>, _>>()?,
-            }),
+            SingleTransactionKind::Call(c) => Self::Call(c.try_into()?),
             SingleTransactionKind::ChangeEpoch(e) => Self::ChangeEpoch(SuiChangeEpoch {
                 epoch: e.epoch,
                 storage_charge: e.storage_charge,
@@ -1571,6 +1759,153 @@ pub struct SuiMoveCall {
     pub arguments: Vec<SuiJsonValue>,
 }
 
+impl TryFrom<MoveCall> for SuiMoveCall {
+    type Error = anyhow::Error;
+
+    fn try_from(c: MoveCall) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package: c.package.into(),
+            module: c.module.to_string(),
+            function: c.function.to_string(),
+            type_arguments: c.type_arguments.iter().map(|ty| ty.to_string()).collect(),
+            arguments: c
+                .arguments
+                .into_iter()
+                .map(|arg| match arg {
+                    CallArg::Pure(p) => SuiJsonValue::from_bcs_bytes(&p),
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
+                    | CallArg::Object(ObjectArg::SharedObject { id, .. }) => {
+                        SuiJsonValue::new(Value::String(id.to_hex_literal()))
+                    }
+                    CallArg::ObjVec(vec) => SuiJsonValue::new(Value::Array(
+                        vec.iter()
+                            .map(|obj_arg| match obj_arg {
+                                ObjectArg::ImmOrOwnedObject((id, _, _))
+                                | ObjectArg::SharedObject { id, .. } => {
+                                    Value::String(id.to_hex_literal())
+                                }
+                            })
+                            .collect(),
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+/// Where the Move calls of a dry run stopped when they failed, to debug an abort beyond its
+/// module and code.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ExecutionTrace", rename_all = "camelCase")]
+pub struct SuiExecutionTrace {
+    /// The error execution stopped with
+    pub error: String,
+    /// The Move call stack where execution stopped, innermost frame first. The VM only reports
+    /// the frame that failed for aborts and runtime errors.
+    pub stack: Vec<SuiMoveFrame>,
+    /// The Move calls of the transaction, with their arguments
+    pub calls: Vec<SuiMoveCall>,
+}
+
+/// A frame of the Move call stack, stopped at an instruction of a function.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "MoveFrame", rename_all = "camelCase")]
+pub struct SuiMoveFrame {
+    pub package: ObjectID,
+    pub module: String,
+    /// Name of the function, if its module could be loaded
+    pub function: Option<String>,
+    /// Index of the function among the function definitions of its module, as in source maps
+    pub function_index: u16,
+    /// Offset of the instruction in the bytecode of the function
+    pub instruction: u16,
+}
+
+impl SuiExecutionTrace {
+    /// Traces the execution of `data` that failed with `error`, loading the modules on the stack
+    /// from `resolver` to name their functions.
+    pub fn new(
+        error: &ExecutionError,
+        data: &TransactionData,
+        resolver: &impl GetModule,
+    ) -> Result<Self, anyhow::Error> {
+        let frames = match error.vm_error() {
+            Some(vm_error) => match (vm_error.exec_state(), vm_error.location()) {
+                (Some(state), _) => state
+                    .stack_trace()
+                    .iter()
+                    .rev()
+                    .filter_map(|(module, function, instruction)| {
+                        Some((module.clone()?, *function, *instruction))
+                    })
+                    .collect(),
+                (None, Location::Module(module)) => vm_error
+                    .offsets()
+                    .iter()
+                    .take(1)
+                    .map(|(function, instruction)| (module.clone(), *function, *instruction))
+                    .collect(),
+                (None, _) => vec![],
+            },
+            None => vec![],
+        };
+        let stack =
+            frames
+                .into_iter()
+                .map(|(module, function, instruction)| SuiMoveFrame {
+                    package: ObjectID::from(*module.address()),
+                    module: module.name().to_string(),
+                    function: resolver.get_module_by_id(&module).ok().flatten().and_then(
+                        |compiled| {
+                            let compiled: &CompiledModule = std::borrow::Borrow::borrow(&compiled);
+                            let definition = compiled.function_defs().get(function.0 as usize)?;
+                            let handle = compiled.function_handle_at(definition.function);
+                            Some(compiled.identifier_at(handle.name).to_string())
+                        },
+                    ),
+                    function_index: function.0,
+                    instruction,
+                })
+                .collect();
+        let calls = data
+            .kind
+            .single_transactions()
+            .filter_map(|kind| match kind {
+                SingleTransactionKind::Call(call) => Some(call.clone().try_into()),
+                _ => None,
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            error: error.kind().to_string(),
+            stack,
+            calls,
+        })
+    }
+}
+
+impl Display for SuiMoveFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.function {
+            Some(function) => write!(f, "{}::{}::{function}", self.package, self.module)?,
+            None => write!(
+                f,
+                "{}::{}::<function #{}>",
+                self.package, self.module, self.function_index
+            )?,
+        }
+        write!(f, " (instruction {})", self.instruction)
+    }
+}
+
+/// The effects of a dry run, with a trace of where its Move calls stopped if they failed.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "DryRunTraceResponse", rename_all = "camelCase")]
+pub struct SuiDryRunTraceResponse {
+    pub effects: SuiTransactionEffects,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<SuiExecutionTrace>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SuiChangeEpoch {
     pub epoch: EpochId,
@@ -1697,6 +2032,44 @@ pub struct SuiTransactionEffects {
     /// The set of transaction digests this transaction depends on.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<TransactionDigest>,
+    /// The computation gas used by each command of the transaction, up to the one that failed if
+    /// execution failed. Only reported by dry runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command_gas_costs: Vec<SuiCommandGasCost>,
+}
+
+/// The computation gas used by a command of a transaction. Reading the input objects and storing
+/// the changes are charged for the whole transaction, not its commands.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CommandGasCost", rename_all = "camelCase")]
+pub struct SuiCommandGasCost {
+    /// The command, as the Move function it calls or the kind of the command
+    pub command: String,
+    pub computation_cost: u64,
+}
+
+impl SuiCommandGasCost {
+    /// Attributes `computation_costs`, as measured by execution for each command executed, to
+    /// the commands of `data`.
+    pub fn for_commands(data: &TransactionData, computation_costs: &[u64]) -> Vec<Self> {
+        data.kind
+            .single_transactions()
+            .zip(computation_costs)
+            .map(|(kind, computation_cost)| Self {
+                command: match kind {
+                    SingleTransactionKind::Call(call) => {
+                        format!("{}::{}::{}", call.package.0, call.module, call.function)
+                    }
+                    SingleTransactionKind::TransferObject(_) => "TransferObject".to_string(),
+                    SingleTransactionKind::TransferSui(_) => "TransferSui".to_string(),
+                    SingleTransactionKind::Pay(_) => "Pay".to_string(),
+                    SingleTransactionKind::Publish(_) => "Publish".to_string(),
+                    SingleTransactionKind::ChangeEpoch(_) => "ChangeEpoch".to_string(),
+                },
+                computation_cost: *computation_cost,
+            })
+            .collect()
+    }
 }
 
 impl SuiTransactionEffects {
@@ -1729,6 +2102,7 @@ impl SuiTransactionEffects {
                 .map(|event| SuiEvent::try_from(event, resolver))
                 .collect::<Result<_, _>>()?,
             dependencies: effect.dependencies,
+            command_gas_costs: vec![],
         })
     }
 }
@@ -1779,6 +2153,12 @@ impl Display for SuiTransactionEffects {
                 )?;
             }
         }
+        if !self.command_gas_costs.is_empty() {
+            writeln!(writer, "Computation Gas By Command:")?;
+            for cost in &self.command_gas_costs {
+                writeln!(writer, "  - {} : {}", cost.command, cost.computation_cost)?;
+            }
+        }
         write!(f, "{}", writer)
     }
 }
@@ -1844,6 +2224,25 @@ impl From<GasCostSummary> for SuiGasCostSummary {
     }
 }
 
+/// Gas price distribution of the transactions included in one checkpoint.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CheckpointGasPrices", rename_all = "camelCase")]
+pub struct SuiCheckpointGasPrices {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub tx_count: u64,
+    pub min: u64,
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub max: u64,
+    /// Number of certificates waiting for execution when the checkpoint was processed.
+    pub pending_certificates: u64,
+}
+
+/// Gas prices paid in the most recent checkpoints, newest first.
+pub type GasPriceMarket = Vec<SuiCheckpointGasPrices>;
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "ObjectRef")]
 pub struct OwnedObjectRef {
@@ -2209,6 +2608,26 @@ impl SuiObjectInfo {
     }
 }
 
+/// An object owned by another object, found when resolving the objects held by an object.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename = "NestedObjectInfo", rename_all = "camelCase")]
+pub struct SuiNestedObjectInfo {
+    pub object: SuiObjectInfo,
+    /// Number of objects on the ownership chain from the queried object: 1 for the objects it
+    /// owns directly, 2 for the objects these own, and so on
+    pub depth: u32,
+}
+
+/// The objects held, directly or through other objects, by an object.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename = "NestedObjects", rename_all = "camelCase")]
+pub struct SuiNestedObjects {
+    /// The objects found, listed by increasing depth
+    pub objects: Vec<SuiNestedObjectInfo>,
+    /// Whether objects were left out because of the depth or size limits
+    pub truncated: bool,
+}
+
 impl From<ObjectInfo> for SuiObjectInfo {
     fn from(info: ObjectInfo) -> Self {
         Self {
@@ -2348,6 +2767,42 @@ impl TryInto<EventFilter> for SuiEventFilter {
     }
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+#[serde(rename = "ObjectChangeFilter")]
+pub enum SuiObjectChangeFilter {
+    ObjectIds(Vec<ObjectID>),
+    /// Objects owned by the address, directly or through the object of that ID
+    Owner(SuiAddress),
+    /// Move StructTag string value of the object type e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+    StructType(String),
+    /// Changes matching all the filters, or every change if empty
+    All(Vec<SuiObjectChangeFilter>),
+    Any(Vec<SuiObjectChangeFilter>),
+}
+
+impl TryInto<ObjectChangeFilter> for SuiObjectChangeFilter {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<ObjectChangeFilter, anyhow::Error> {
+        use SuiObjectChangeFilter::*;
+        let try_into_all = |filters: Vec<SuiObjectChangeFilter>| {
+            filters
+                .into_iter()
+                .map(SuiObjectChangeFilter::try_into)
+                .collect::<Result<_, _>>()
+        };
+        Ok(match self {
+            ObjectIds(ids) => {
+                ObjectChangeFilter::ObjectIds(ids.into_iter().collect::<BTreeSet<_>>())
+            }
+            Owner(address) => ObjectChangeFilter::Owner(address),
+            StructType(type_) => ObjectChangeFilter::StructType(parse_sui_struct_tag(&type_)?),
+            All(filters) => ObjectChangeFilter::MatchAll(try_into_all(filters)?),
+            Any(filters) => ObjectChangeFilter::MatchAny(try_into_all(filters)?),
+        })
+    }
+}
+
 #[serde_as]
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -2378,9 +2833,390 @@ impl TransactionBytes {
     }
 }
 
+/// A proof that an object version was written by a transaction included in a certified
+/// checkpoint.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+#[serde(rename = "ObjectInclusionProof", rename_all = "camelCase")]
+pub struct SuiObjectInclusionProof {
+    pub reference: SuiObjectRef,
+    pub checkpoint: CheckpointSequenceNumber,
+    /// BCS serialized proof, as base-64 encoded string
+    pub proof_bytes: Base64,
+}
+
+impl SuiObjectInclusionProof {
+    pub fn new(object_ref: ObjectRef, proof: &ObjectInclusionProof) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            reference: object_ref.into(),
+            checkpoint: proof.checkpoint.summary.sequence_number,
+            proof_bytes: Base64::from_bytes(&bcs::to_bytes(proof)?),
+        })
+    }
+
+    /// Decode the proof, to be checked with `ObjectInclusionProof::verify`.
+    pub fn to_proof(&self) -> Result<ObjectInclusionProof, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.proof_bytes.to_vec()?)?)
+    }
+}
+
+/// Objects read as of a checkpoint, consistent with each other.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(rename = "ObjectsAtCheckpoint", rename_all = "camelCase")]
+pub struct SuiObjectsAtCheckpoint {
+    /// The checkpoint the objects are read as of
+    pub checkpoint: CheckpointSequenceNumber,
+    /// The objects, in the order they were requested in
+    pub objects: Vec<GetPastObjectDataResponse>,
+}
+
+/// An object changed by a transaction, with its contents before and after the transaction.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "ObjectChange", rename_all = "camelCase")]
+pub struct SuiObjectChange {
+    pub object_id: ObjectID,
+    pub kind: ObjectChangeKind,
+    /// None if the object did not exist as such before the transaction, or if the node pruned
+    /// that version.
+    pub before: Option<SuiParsedObject>,
+    /// None if the object does not exist as such after the transaction.
+    pub after: Option<SuiParsedObject>,
+}
+
+/// An object changed by a checkpointed transaction, as streamed to subscribers.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "ObjectChangeEnvelope", rename_all = "camelCase")]
+pub struct SuiObjectChangeEnvelope {
+    /// The checkpoint including the transaction, to resume the subscription from
+    pub checkpoint: CheckpointSequenceNumber,
+    pub tx_digest: TransactionDigest,
+    pub kind: ObjectChangeKind,
+    pub reference: SuiObjectRef,
+    /// The owner after the change, or before it for wrapped and deleted objects
+    pub owner: Option<Owner>,
+    pub object_type: Option<String>,
+}
+
+impl From<ObjectChange> for SuiObjectChangeEnvelope {
+    fn from(change: ObjectChange) -> Self {
+        Self {
+            checkpoint: change.checkpoint,
+            tx_digest: change.tx_digest,
+            kind: change.kind,
+            reference: change.object_ref.into(),
+            owner: change.owner,
+            object_type: change.object_type.map(|type_| type_.to_string()),
+        }
+    }
+}
+
+/// The net change of the balance of an owner in one coin type.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "BalanceChange", rename_all = "camelCase")]
+pub struct SuiBalanceChange {
+    pub owner: Owner,
+    pub coin_type: String,
+    pub amount: i128,
+}
+
+impl From<BalanceChange> for SuiBalanceChange {
+    fn from(change: BalanceChange) -> Self {
+        Self {
+            owner: change.owner,
+            coin_type: change.coin_type.to_string(),
+            amount: change.amount,
+        }
+    }
+}
+
+/// The changes made by a transaction, as objects before and after it, balance changes and gas
+/// costs, for explorers and wallets to display instead of the raw effects.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "TransactionEffectsDiff", rename_all = "camelCase")]
+pub struct SuiTransactionEffectsDiff {
+    pub tx_digest: TransactionDigest,
+    pub status: SuiExecutionStatus,
+    pub object_changes: Vec<SuiObjectChange>,
+    pub balance_changes: Vec<SuiBalanceChange>,
+    pub gas_used: SuiGasCostSummary,
+}
+
+/// An object version read from the store, with its layout if it is a Move object.
+pub type ObjectWithLayout = (Object, Option<MoveStructLayout>);
+
+impl SuiTransactionEffectsDiff {
+    /// Builds the diff of `effects` from the versions of the objects it changed, as listed by
+    /// `sui_types::object_change::changed_objects`, read from the store.
+    pub fn try_new(
+        effects: &TransactionEffects,
+        objects: Vec<(
+            ObjectID,
+            ObjectChangeKind,
+            Option<ObjectWithLayout>,
+            Option<ObjectWithLayout>,
+        )>,
+    ) -> Result<Self, anyhow::Error> {
+        let balance_changes =
+            BalanceChange::from_objects(objects.iter().map(|(_, _, before, after)| {
+                (
+                    before.as_ref().map(|(object, _)| object),
+                    after.as_ref().map(|(object, _)| object),
+                )
+            }))
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        let mut object_changes = Vec::with_capacity(objects.len());
+        for (object_id, kind, before, after) in objects {
+            let parse = |object: Option<ObjectWithLayout>| {
+                object
+                    .map(|(object, layout)| SuiParsedObject::try_from(object, layout))
+                    .transpose()
+            };
+            object_changes.push(SuiObjectChange {
+                object_id,
+                kind,
+                before: parse(before)?,
+                after: parse(after)?,
+            });
+        }
+
+        Ok(Self {
+            tx_digest: effects.transaction_digest,
+            status: effects.status.clone().into(),
+            object_changes,
+            balance_changes: balance_changes.into_iter().map(Into::into).collect(),
+            gas_used: effects.gas_used.clone().into(),
+        })
+    }
+}
+
+/// A quorum of validator signatures over an event emitted by a finalized transaction.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+#[serde(rename = "BridgeAttestation", rename_all = "camelCase")]
+pub struct SuiBridgeAttestation {
+    pub tx_digest: TransactionDigest,
+    pub event_seq: u64,
+    /// the epoch of the committee that signed the attestation
+    pub epoch: EpochId,
+    /// BCS serialized certified attestation, as base-64 encoded string
+    pub attestation_bytes: Base64,
+}
+
+impl SuiBridgeAttestation {
+    pub fn new(attestation: &CertifiedBridgeAttestation) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            tx_digest: attestation.message.tx_digest,
+            event_seq: attestation.message.event_seq,
+            epoch: attestation.auth_signature.epoch,
+            attestation_bytes: Base64::from_bytes(&bcs::to_bytes(attestation)?),
+        })
+    }
+
+    pub fn to_attestation(&self) -> Result<CertifiedBridgeAttestation, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.attestation_bytes.to_vec()?)?)
+    }
+}
+
+/// The time spent in each stage of the finality of a transaction submitted through this node.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename = "FinalityTrace", rename_all = "camelCase")]
+pub struct SuiFinalityTrace {
+    pub tx_digest: TransactionDigest,
+    /// when the first stage finished, in milliseconds since the Unix epoch
+    pub recorded_at_ms: u64,
+    /// the stages reached so far, in order
+    pub stages: Vec<SuiFinalityStage>,
+    /// the checkpoint the transaction was included in, once known
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename = "FinalityStage", rename_all = "camelCase")]
+pub struct SuiFinalityStage {
+    /// one of signing_quorum, cert_broadcast, effects_quorum and checkpoint_inclusion
+    pub stage: String,
+    pub latency_ms: u64,
+}
+
+/// The network info of an epoch certified by a quorum of its validators, with the end-of-epoch
+/// checkpoints which let clients check it from the committee of an earlier epoch. This is also
+/// the format of the files clients keep the last bundle they verified in.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+#[serde(rename = "NetworkInfoBundle", rename_all = "camelCase")]
+pub struct SuiNetworkInfoBundle {
+    /// the epoch of the network info
+    pub epoch: EpochId,
+    pub protocol_version: u64,
+    /// the JSON-RPC endpoints the validators vouch for
+    pub rpc_endpoints: Vec<String>,
+    /// BCS serialized bundle, as base-64 encoded string. The fields above are only
+    /// trustworthy once it is verified.
+    pub bundle_bytes: Base64,
+}
+
+impl SuiNetworkInfoBundle {
+    pub fn new(bundle: &NetworkInfoBundle) -> Result<Self, anyhow::Error> {
+        let info = &bundle.info.info;
+        Ok(Self {
+            epoch: info.epoch,
+            protocol_version: info.protocol_version,
+            rpc_endpoints: info.rpc_endpoints.clone(),
+            bundle_bytes: Base64::from_bytes(&bcs::to_bytes(bundle)?),
+        })
+    }
+
+    /// Decode the bundle, to be checked with `NetworkInfoBundle::verify`.
+    pub fn to_bundle(&self) -> Result<NetworkInfoBundle, anyhow::Error> {
+        Ok(bcs::from_bytes(&self.bundle_bytes.to_vec()?)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let bundle = fs::read(path)?;
+        serde_json::from_slice(&bundle)
+            .map_err(|e| anyhow::anyhow!("Invalid network info bundle {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// The committee of an epoch: the stake of each of its validators and, if known, their network
+/// addresses.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "Committee", rename_all = "camelCase")]
+pub struct SuiCommittee {
+    pub epoch: EpochId,
+    pub total_stake: StakeUnit,
+    pub validators: Vec<SuiCommitteeMember>,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "CommitteeMember", rename_all = "camelCase")]
+pub struct SuiCommitteeMember {
+    pub name: AuthorityName,
+    pub stake: StakeUnit,
+    /// The multiaddr the validator registered for the epoch, None if not known
+    pub net_address: Option<String>,
+}
+
+impl From<CommitteeWithNetAddresses> for SuiCommittee {
+    fn from(committee: CommitteeWithNetAddresses) -> Self {
+        let CommitteeWithNetAddresses {
+            committee,
+            net_addresses,
+            ..
+        } = committee;
+        Self {
+            epoch: committee.epoch,
+            total_stake: committee.total_votes,
+            validators: committee
+                .voting_rights
+                .into_iter()
+                .map(|(name, stake)| SuiCommitteeMember {
+                    name,
+                    stake,
+                    net_address: net_addresses
+                        .get(&name)
+                        .and_then(|bytes| Multiaddr::try_from(bytes.clone()).ok())
+                        .map(|address| address.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The committee in the shape of the deprecated `sui_getCommitteeInfo`, without the network
+/// addresses.
+impl From<SuiCommittee> for CommitteeInfoResponse {
+    fn from(committee: SuiCommittee) -> Self {
+        Self {
+            epoch: committee.epoch,
+            committee_info: Some(
+                committee
+                    .validators
+                    .into_iter()
+                    .map(|validator| (validator.name, validator.stake))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Data of the JSON-RPC errors caused by a `SuiError`: its stable code and classification,
+/// so that clients can tell errors apart without parsing their messages.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiErrorData {
+    pub code: ErrorCode,
+    pub retryable: bool,
+    pub client_fault: bool,
+}
+
+impl From<&SuiError> for SuiErrorData {
+    fn from(error: &SuiError) -> Self {
+        Self {
+            code: error.code(),
+            retryable: error.is_retryable(),
+            client_fault: error.is_client_fault(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Page<T, C> {
     pub data: Vec<T>,
     pub next_cursor: Option<C>,
 }
+
+/// An object transferred to an address by a transaction sent from another address.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "ReceivedObject", rename_all = "camelCase")]
+pub struct SuiReceivedObject {
+    pub reference: SuiObjectRef,
+    pub tx_digest: TransactionDigest,
+    pub tx_sequence_number: TxSequenceNumber,
+}
+
+/// Position in an address's received objects index.
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedObjectsCursor {
+    pub tx_sequence_number: TxSequenceNumber,
+    pub object_id: ObjectID,
+}
+
+/// A transaction an address took part in, and the role it had in it.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "AddressActivity", rename_all = "camelCase")]
+pub struct SuiAddressActivity {
+    pub tx_digest: TransactionDigest,
+    pub tx_sequence_number: TxSequenceNumber,
+    pub role: AddressActivityRole,
+    /// None if the transaction is not part of a checkpoint yet.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// Position in an address's activity index.
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressActivityCursor {
+    pub tx_sequence_number: TxSequenceNumber,
+    pub role: AddressActivityRole,
+}
+
+/// A coin owned by an address, as of the latest transaction indexed by the node.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "Coin", rename_all = "camelCase")]
+pub struct SuiCoin {
+    pub coin_type: String,
+    pub reference: SuiObjectRef,
+    pub balance: u64,
+}
+
+/// The total supply of a coin type.
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename = "Supply", rename_all = "camelCase")]
+pub struct SuiSupply {
+    pub value: u64,
+}
@@ -159,3 +159,193 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_transaction_effects_diff() {
+    use sui_types::base_types::{ObjectDigest, TransactionDigest};
+    use sui_types::messages::TransactionEffects;
+    use sui_types::object::{Object, Owner};
+    use sui_types::object_change::{changed_objects, ObjectChangeKind};
+
+    use crate::SuiTransactionEffectsDiff;
+
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let gas_id = ObjectID::random();
+    let created_id = ObjectID::random();
+    let deleted_id = ObjectID::random();
+    let version = SequenceNumber::from_u64(5);
+
+    let effects = TransactionEffects {
+        transaction_digest: TransactionDigest::random(),
+        created: vec![(
+            (created_id, version, ObjectDigest::random()),
+            Owner::AddressOwner(recipient),
+        )],
+        mutated: vec![(
+            (gas_id, version, ObjectDigest::random()),
+            Owner::AddressOwner(sender),
+        )],
+        deleted: vec![(deleted_id, version, ObjectDigest::OBJECT_DIGEST_DELETED)],
+        ..Default::default()
+    };
+    let changes: Vec<_> = changed_objects(&effects)
+        .iter()
+        .map(|change| (change.object_id(), change.kind, change.before, change.after))
+        .collect();
+    let before = SequenceNumber::from_u64(4);
+    assert_eq!(
+        changes,
+        vec![
+            (created_id, ObjectChangeKind::Created, None, Some(version)),
+            (
+                gas_id,
+                ObjectChangeKind::Mutated,
+                Some(before),
+                Some(version)
+            ),
+            (deleted_id, ObjectChangeKind::Deleted, Some(before), None),
+        ]
+    );
+
+    let coin = |id, owner, value| {
+        (
+            Object::with_id_owner_gas_for_testing(id, owner, value),
+            Some(GasCoin::layout()),
+        )
+    };
+    let diff = SuiTransactionEffectsDiff::try_new(
+        &effects,
+        vec![
+            (
+                created_id,
+                ObjectChangeKind::Created,
+                None,
+                Some(coin(created_id, recipient, 20)),
+            ),
+            (
+                gas_id,
+                ObjectChangeKind::Mutated,
+                Some(coin(gas_id, sender, 100)),
+                Some(coin(gas_id, sender, 90)),
+            ),
+            (
+                deleted_id,
+                ObjectChangeKind::Deleted,
+                Some(coin(deleted_id, sender, 15)),
+                None,
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(diff.object_changes.len(), 3);
+    assert!(diff.object_changes[0].before.is_none());
+    assert!(diff.object_changes[2].after.is_none());
+    let balances: Vec<_> = diff
+        .balance_changes
+        .iter()
+        .map(|change| (change.owner, change.amount))
+        .collect();
+    assert_eq!(
+        balances.len(),
+        2,
+        "unexpected balance changes {:?}",
+        diff.balance_changes
+    );
+    assert!(balances.contains(&(Owner::AddressOwner(sender), -25)));
+    assert!(balances.contains(&(Owner::AddressOwner(recipient), 20)));
+}
+
+#[test]
+fn test_package_abi() {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        SuiMoveAbilitySet, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+        SuiMoveNormalizedType, SuiMoveVisibility, SuiPackageAbi,
+    };
+
+    let function = |is_entry, parameters| SuiMoveNormalizedFunction {
+        visibility: SuiMoveVisibility::Public,
+        is_entry,
+        type_parameters: vec![SuiMoveAbilitySet { abilities: vec![] }],
+        parameters,
+        return_: vec![],
+    };
+    let tx_context =
+        SuiMoveNormalizedType::MutableReference(Box::new(SuiMoveNormalizedType::Struct {
+            address: SUI_FRAMEWORK_ADDRESS.to_hex_literal(),
+            module: "tx_context".to_string(),
+            name: "TxContext".to_string(),
+            type_arguments: vec![],
+        }));
+    let module = SuiMoveNormalizedModule {
+        file_format_version: 5,
+        address: SUI_FRAMEWORK_ADDRESS.to_hex_literal(),
+        name: "m".to_string(),
+        friends: vec![],
+        structs: BTreeMap::new(),
+        exposed_functions: BTreeMap::from([
+            (
+                "mint".to_string(),
+                function(true, vec![SuiMoveNormalizedType::U64, tx_context.clone()]),
+            ),
+            (
+                "value".to_string(),
+                function(false, vec![SuiMoveNormalizedType::U64]),
+            ),
+        ]),
+    };
+    let package_id = ObjectID::random();
+    let abi = SuiPackageAbi::new(package_id, BTreeMap::from([("m".to_string(), module)]));
+
+    let entry_functions = &abi.modules["m"].entry_functions;
+    assert_eq!(entry_functions.len(), 1, "only entry functions are exposed");
+    assert!(entry_functions["mint"].takes_tx_context);
+    assert_eq!(entry_functions["mint"].parameters.len(), 1);
+
+    assert!(abi.check_move_call("m", "mint", 1, 1).is_ok());
+    assert!(abi.check_move_call("m", "mint", 0, 1).is_err());
+    assert!(abi.check_move_call("m", "mint", 1, 2).is_err());
+    assert!(abi.check_move_call("m", "value", 1, 1).is_err());
+    assert!(abi.check_move_call("n", "mint", 1, 1).is_err());
+}
+
+#[test]
+fn test_committee() {
+    use std::collections::BTreeMap;
+
+    use multiaddr::Multiaddr;
+    use sui_types::base_types::AuthorityName;
+    use sui_types::committee::{Committee, CommitteeWithNetAddresses};
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+
+    use crate::SuiCommittee;
+
+    let names: Vec<AuthorityName> = (0..2)
+        .map(|_| {
+            let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+            key.public().into()
+        })
+        .collect();
+    let address: Multiaddr = "/dns/localhost/tcp/8080/http".parse().unwrap();
+    let committee = CommitteeWithNetAddresses {
+        committee: Committee::new(3, BTreeMap::from([(names[0], 1), (names[1], 2)])).unwrap(),
+        net_addresses: BTreeMap::from([(names[0], address.to_vec())]),
+        backup_net_addresses: BTreeMap::new(),
+    };
+
+    let committee = SuiCommittee::from(committee);
+    assert_eq!(committee.epoch, 3);
+    assert_eq!(committee.total_stake, 3);
+    for member in &committee.validators {
+        if member.name == names[0] {
+            assert_eq!(member.stake, 1);
+            assert_eq!(member.net_address, Some(address.to_string()));
+        } else {
+            assert_eq!(member.stake, 2);
+            assert_eq!(member.net_address, None);
+        }
+    }
+}
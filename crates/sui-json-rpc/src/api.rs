@@ -8,19 +8,25 @@ use jsonrpsee_proc_macros::rpc;
 
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
-    MoveFunctionArgType, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
-    SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
-    SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, TransactionBytes, TransactionsPage,
+    AddressActivityCursor, AddressActivityPage, CoinPage, GasPriceMarket, GetObjectDataResponse,
+    GetPastObjectDataResponse, GetRawObjectDataResponse, MoveFunctionArgType,
+    RPCTransactionRequestParams, ReceivedObjectsCursor, ReceivedObjectsPage, SessionToken,
+    SuiBridgeAttestation, SuiCommittee, SuiDryRunTraceResponse, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiFinalityTrace, SuiGasCostSummary, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiNestedObjects, SuiNetworkInfoBundle,
+    SuiObjectChangeEnvelope, SuiObjectChangeFilter, SuiObjectInclusionProof, SuiObjectInfo,
+    SuiObjectsAtCheckpoint, SuiPackageAbi, SuiSupply, SuiTransactionEffects,
+    SuiTransactionEffectsDiff, SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag,
+    TransactionBytes, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::committee::EpochId;
 use sui_types::crypto::SignatureScheme;
-use sui_types::messages::CommitteeInfoResponse;
+use sui_types::epoch_accounting::EpochAccountingReport;
 use sui_types::messages::ExecuteTransactionRequestType;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::query::{Ordering, TransactionQuery};
 use sui_types::sui_serde::Base64;
@@ -70,6 +76,8 @@ pub trait RpcReadApi {
         &self,
         /// the owner's Sui address
         address: SuiAddress,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>>;
 
     /// Return the list of objects owned by an object.
@@ -78,6 +86,8 @@ pub trait RpcReadApi {
         &self,
         /// the ID of the owner object
         object_id: ObjectID,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>>;
 
     /// Return the total number of transactions known to the server.
@@ -100,6 +110,8 @@ pub trait RpcReadApi {
         &self,
         /// the digest of the queried transaction
         digest: TransactionDigest,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<SuiTransactionResponse>;
 
     /// Return the object information for a specified object
@@ -108,6 +120,8 @@ pub trait RpcReadApi {
         &self,
         /// the ID of the queried object
         object_id: ObjectID,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<GetObjectDataResponse>;
 }
 
@@ -123,6 +137,17 @@ pub trait RpcFullNodeReadApi {
         pub_key: Base64,
     ) -> RpcResult<SuiTransactionEffects>;
 
+    /// Dry run a transaction like `dryRunTransaction`, also returning, if execution failed, the
+    /// Move call stack where it stopped and the arguments of the Move calls of the transaction
+    #[method(name = "dryRunTransactionWithTrace")]
+    async fn dry_run_transaction_with_trace(
+        &self,
+        tx_bytes: Base64,
+        sig_scheme: SignatureScheme,
+        signature: Base64,
+        pub_key: Base64,
+    ) -> RpcResult<SuiDryRunTraceResponse>;
+
     /// Return the argument types of a Move function,
     /// based on normalized Type.
     #[method(name = "getMoveFunctionArgTypes")]
@@ -166,6 +191,20 @@ pub trait RpcFullNodeReadApi {
         function_name: String,
     ) -> RpcResult<SuiMoveNormalizedFunction>;
 
+    /// Return the JSON Schema of the arguments of a Move entry function, as accepted by
+    /// `sui_moveCall`
+    #[method(name = "getMoveFunctionArgSchema")]
+    async fn get_move_function_arg_schema(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> RpcResult<serde_json::Value>;
+
+    /// Return the interface of the given package: its entry functions and structs, by module
+    #[method(name = "getPackageAbi")]
+    async fn get_package_abi(&self, package: ObjectID) -> RpcResult<SuiPackageAbi>;
+
     /// Return list of transactions for a specified query criteria.
     #[method(name = "getTransactions")]
     async fn get_transactions(
@@ -178,6 +217,8 @@ pub trait RpcFullNodeReadApi {
         limit: Option<usize>,
         /// Transaction query ordering
         order: Ordering,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<TransactionsPage>;
 
     /// Note there is no software-level guarantee/SLA that objects with past versions
@@ -191,15 +232,127 @@ pub trait RpcFullNodeReadApi {
         object_id: ObjectID,
         /// the version of the queried object. If None, default to the latest known version
         version: SequenceNumber,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<GetPastObjectDataResponse>;
 
-    /// Return the committee information for the asked epoch
-    #[method(name = "getCommitteeInfo")]
-    async fn get_committee_info(
+    /// Return the objects sent to an address by transactions from other senders,
+    /// in the order they were received.
+    #[method(name = "getReceivedObjects")]
+    async fn get_received_objects(
+        &self,
+        /// the recipient's Sui address
+        address: SuiAddress,
+        /// Optional paging cursor, the next cursor of a previous page
+        cursor: Option<ReceivedObjectsCursor>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<ReceivedObjectsPage>;
+
+    /// Return the transactions an address took part in, as sender, recipient or gas payer,
+    /// in the order they were executed.
+    #[method(name = "getAddressActivity")]
+    async fn get_address_activity(
+        &self,
+        /// the Sui address
+        address: SuiAddress,
+        /// Optional paging cursor, the next cursor of a previous page
+        cursor: Option<AddressActivityCursor>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<AddressActivityPage>;
+
+    /// Return the coins of the given type owned by an address, ordered by object ID.
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// Optional type of the coins, e.g. `0x2::sui::SUI` (the default)
+        coin_type: Option<String>,
+        /// Optional paging cursor, the next cursor of a previous page
+        cursor: Option<ObjectID>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<CoinPage>;
+
+    /// Return the total supply of the given coin type, e.g. `0x2::sui::SUI`, as kept by its
+    /// `TreasuryCap`.
+    #[method(name = "getTotalSupply")]
+    async fn get_total_supply(
+        &self,
+        /// the type of the coins
+        coin_type: String,
+    ) -> RpcResult<SuiSupply>;
+
+    /// Return the objects owned by an object, and the objects these own in turn, following
+    /// ownership chains down to the given depth.
+    #[method(name = "getObjectsOwnedByObjectRecursive")]
+    async fn get_objects_owned_by_object_recursive(
+        &self,
+        /// the ID of the owner object
+        object_id: ObjectID,
+        /// Optional maximum length of the ownership chains followed, 16 at most and by default
+        max_depth: Option<u32>,
+    ) -> RpcResult<SuiNestedObjects>;
+
+    /// Return a proof that the given object version was written by a transaction included in a
+    /// certified checkpoint, which can be checked against the committee of that epoch.
+    #[method(name = "getObjectInclusionProof")]
+    async fn get_object_inclusion_proof(
+        &self,
+        /// the ID of the object
+        object_id: ObjectID,
+        /// the version of the object
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectInclusionProof>;
+
+    /// Return the given objects as of a checkpoint, each at the version written by the last
+    /// transaction of that checkpoint or an earlier one changing it. Unlike separate reads of
+    /// the latest objects, the objects returned are consistent with each other.
+    #[method(name = "getObjectsAtCheckpoint")]
+    async fn get_objects_at_checkpoint(
+        &self,
+        /// the IDs of the objects
+        object_ids: Vec<ObjectID>,
+        /// Optional sequence number of the checkpoint, the latest checkpoint synced by the node by
+        /// default
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> RpcResult<SuiObjectsAtCheckpoint>;
+
+    /// Return the changes made by a transaction: the objects it created, mutated or deleted
+    /// with their contents before and after it, the balance changes per owner, and its gas
+    /// costs. Object versions pruned by the node are omitted.
+    #[method(name = "getTransactionEffectsDiff")]
+    async fn get_transaction_effects_diff(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiTransactionEffectsDiff>;
+
+    /// Return the committee of the asked epoch as recorded by this node: the stake of each
+    /// validator and, when known, the network address it registered for the epoch
+    #[method(name = "getCommittee")]
+    async fn get_committee(
         &self,
         /// The epoch of interest. If None, default to the latest epoch
         epoch: Option<EpochId>,
-    ) -> RpcResult<CommitteeInfoResponse>;
+    ) -> RpcResult<SuiCommittee>;
+
+    /// Return the gas fees collected during an ended epoch, the storage fund flows, and the
+    /// rewards each validator and its delegators received at the epoch change
+    #[method(name = "getEpochAccountingReport")]
+    async fn get_epoch_accounting_report(
+        &self,
+        /// The ended epoch of interest
+        epoch: EpochId,
+    ) -> RpcResult<EpochAccountingReport>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
@@ -363,6 +516,8 @@ pub trait RpcBcsApi {
         &self,
         /// the id of the object
         object_id: ObjectID,
+        /// token of the latest write of the client, to wait for before reading
+        session_token: Option<SessionToken>,
     ) -> RpcResult<GetRawObjectDataResponse>;
 }
 
@@ -378,6 +533,20 @@ pub trait TransactionStreamingApi {
     );
 }
 
+#[open_rpc(namespace = "sui", tag = "Object Change Subscription")]
+#[rpc(server, client, namespace = "sui")]
+pub trait ObjectChangeStreamingApi {
+    /// Subscribe to a stream of the changes made to objects by checkpointed transactions
+    #[subscription(name = "subscribeObjectChanges", item = SuiObjectChangeEnvelope)]
+    fn subscribe_object_changes(
+        &self,
+        /// the filter criteria of the object change stream.
+        filter: SuiObjectChangeFilter,
+        /// the checkpoint to start from, inclusive, or the next checkpoint if none. To resume, pass the checkpoint of the last change received.
+        cursor: Option<CheckpointSequenceNumber>,
+    );
+}
+
 #[open_rpc(namespace = "sui", tag = "Event Subscription")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventStreamingApi {
@@ -488,6 +657,55 @@ pub trait EventReadApi {
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 }
 
+#[open_rpc(namespace = "sui", tag = "Bridge API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait BridgeApi {
+    /// Collect signatures from a quorum of validators over a Move event emitted by a finalized
+    /// transaction, to be relayed to an external chain.
+    #[method(name = "getBridgeAttestation")]
+    async fn get_bridge_attestation(
+        &self,
+        /// the digest of the transaction that emitted the event
+        tx_digest: TransactionDigest,
+        /// the index of the event in the events emitted by the transaction
+        event_seq: u64,
+    ) -> RpcResult<SuiBridgeAttestation>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Network Info API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait NetworkInfoApi {
+    /// Return the digest of the genesis, the committee, the protocol version and the JSON-RPC
+    /// endpoints of the network in the current epoch, signed by a quorum of its validators, for
+    /// clients to check that this node serves the network they trust.
+    #[method(name = "getNetworkInfoBundle")]
+    async fn get_network_info_bundle(
+        &self,
+        /// the epoch of the committee the client trusts, defaults to 0, the epoch of the genesis.
+        /// The bundle includes the last checkpoint of every epoch since.
+        from_epoch: Option<EpochId>,
+    ) -> RpcResult<SuiNetworkInfoBundle>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Debug API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait DebugApi {
+    /// Return the time spent in each stage of the finality of a transaction submitted through
+    /// this node, if its trace is kept. Traces are only kept for the most recent transactions,
+    /// and only once enabled on the node.
+    #[method(name = "getFinalityTrace")]
+    async fn get_finality_trace(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiFinalityTrace>>;
+
+    /// Return the kept finality traces of the transactions submitted through this node, oldest
+    /// first.
+    #[method(name = "getFinalityTraces")]
+    async fn get_finality_traces(&self) -> RpcResult<Vec<SuiFinalityTrace>>;
+}
+
 #[open_rpc(namespace = "sui", tag = "APIs to execute transactions.")]
 #[rpc(server, client, namespace = "sui")]
 pub trait TransactionExecutionApi {
@@ -505,6 +723,9 @@ pub trait TransactionExecutionApi {
     ///     makes sure this node is aware of this transaction when client fires subsequent queries.
     ///     However if the node fails to execute the transaction locally in a timely manner,
     ///     a bool type in the response is set to false to indicated the case.
+    /// Responses with effects carry a session token: reads given the token wait until the node
+    /// has executed the transaction, so that they observe its effects even if they are routed
+    /// to another node.
     #[method(name = "executeTransaction")]
     async fn execute_transaction(
         &self,
@@ -518,6 +739,35 @@ pub trait TransactionExecutionApi {
         pub_key: Base64,
         /// The request type
         request_type: ExecuteTransactionRequestType,
+        /// how long to wait for local execution with WaitForLocalExecution, in milliseconds,
+        /// defaults to 5 seconds and is capped to 60 seconds
+        local_execution_timeout_ms: Option<u64>,
+    ) -> RpcResult<SuiExecuteTransactionResponse>;
+
+    /// Execute the transaction at most once per client-supplied request id.
+    /// The first call binds the request id to the transaction and stores the response;
+    /// retries with the same request id return the stored response without executing the
+    /// transaction again, including after the node restarts. Reusing a request id for a
+    /// different transaction is an error. Request ids are at most 128 bytes long, and are
+    /// kept for 24 hours after their first use.
+    #[method(name = "executeTransactionWithRequestId")]
+    async fn execute_transaction_with_request_id(
+        &self,
+        /// client-supplied id that identifies this submission across retries
+        request_id: String,
+        /// transaction data bytes, as base-64 encoded string
+        tx_bytes: Base64,
+        /// Flag of the signature scheme that is used.
+        sig_scheme: SignatureScheme,
+        /// transaction signature, as base-64 encoded string
+        signature: Base64,
+        /// signer's public key, as base-64 encoded string
+        pub_key: Base64,
+        /// The request type
+        request_type: ExecuteTransactionRequestType,
+        /// how long to wait for local execution with WaitForLocalExecution, in milliseconds,
+        /// defaults to 5 seconds and is capped to 60 seconds
+        local_execution_timeout_ms: Option<u64>,
     ) -> RpcResult<SuiExecuteTransactionResponse>;
 }
 
@@ -538,4 +788,13 @@ pub trait EstimatorApi {
         mutated_object_sizes_after: Option<usize>,
         storage_rebate: Option<u64>,
     ) -> RpcResult<SuiGasCostSummary>;
+
+    /// Return the gas price percentiles and execution queue depth of the most recently
+    /// processed checkpoints, newest first, as a congestion signal for choosing a gas price.
+    #[method(name = "getGasPriceMarket")]
+    async fn get_gas_price_market(
+        &self,
+        /// number of recent checkpoints to return, capped to GAS_PRICE_HISTORY_CHECKPOINTS
+        count: Option<usize>,
+    ) -> RpcResult<GasPriceMarket>;
 }
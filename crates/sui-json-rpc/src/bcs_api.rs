@@ -4,6 +4,7 @@
 use std::sync::Arc;
 
 use crate::api::RpcBcsApiServer;
+use crate::read_api::wait_for_session;
 use crate::SuiRpcModule;
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -11,7 +12,7 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayClient;
-use sui_json_rpc_types::GetRawObjectDataResponse;
+use sui_json_rpc_types::{GetRawObjectDataResponse, SessionToken};
 use sui_open_rpc::Module;
 use sui_types::base_types::ObjectID;
 
@@ -56,7 +57,15 @@ impl ClientStateAdaptor {
 
 #[async_trait]
 impl RpcBcsApiServer for BcsApiImpl {
-    async fn get_raw_object(&self, object_id: ObjectID) -> RpcResult<GetRawObjectDataResponse> {
+    async fn get_raw_object(
+        &self,
+        object_id: ObjectID,
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<GetRawObjectDataResponse> {
+        // The gateway executes transactions itself, its reads always observe them.
+        if let ClientStateAdaptor::FullNode(state) = &self.client {
+            wait_for_session(state, session_token).await?;
+        }
         Ok(self.client.get_raw_object(object_id).await?)
     }
 }
@@ -0,0 +1,60 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_json_rpc_types::SuiBridgeAttestation;
+use sui_open_rpc::Module;
+use sui_types::base_types::TransactionDigest;
+
+use crate::api::BridgeApiServer;
+use crate::SuiRpcModule;
+
+pub struct BridgeApiImpl {
+    transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+}
+
+impl BridgeApiImpl {
+    pub fn new(
+        transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+    ) -> Self {
+        Self {
+            transaction_orchestrator,
+        }
+    }
+}
+
+#[async_trait]
+impl BridgeApiServer for BridgeApiImpl {
+    async fn get_bridge_attestation(
+        &self,
+        tx_digest: TransactionDigest,
+        event_seq: u64,
+    ) -> RpcResult<SuiBridgeAttestation> {
+        let attestation = self
+            .transaction_orchestrator
+            .quorum_driver()
+            .authority_aggregator()
+            .load_full()
+            .get_bridge_attestation(tx_digest, event_seq)
+            .await
+            .map_err(|e| anyhow!(e))?;
+        Ok(SuiBridgeAttestation::new(&attestation)?)
+    }
+}
+
+impl SuiRpcModule for BridgeApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::BridgeApiOpenRpc::module_doc()
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::quorum_driver::FinalityTrace;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_json_rpc_types::{SuiFinalityStage, SuiFinalityTrace};
+use sui_open_rpc::Module;
+use sui_types::base_types::TransactionDigest;
+
+use crate::api::DebugApiServer;
+use crate::SuiRpcModule;
+
+pub struct DebugApiImpl {
+    transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+}
+
+impl DebugApiImpl {
+    pub fn new(
+        transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+    ) -> Self {
+        Self {
+            transaction_orchestrator,
+        }
+    }
+}
+
+fn to_sui_finality_trace(trace: FinalityTrace) -> SuiFinalityTrace {
+    SuiFinalityTrace {
+        tx_digest: trace.tx_digest,
+        recorded_at_ms: trace
+            .recorded_at
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default(),
+        stages: trace
+            .stages
+            .into_iter()
+            .map(|(stage, latency)| SuiFinalityStage {
+                stage: stage.to_string(),
+                latency_ms: latency.as_millis() as u64,
+            })
+            .collect(),
+        checkpoint: trace.checkpoint,
+    }
+}
+
+#[async_trait]
+impl DebugApiServer for DebugApiImpl {
+    async fn get_finality_trace(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiFinalityTrace>> {
+        Ok(self
+            .transaction_orchestrator
+            .quorum_driver()
+            .finality_tracker()
+            .get_trace(&digest)
+            .map(to_sui_finality_trace))
+    }
+
+    async fn get_finality_traces(&self) -> RpcResult<Vec<SuiFinalityTrace>> {
+        Ok(self
+            .transaction_orchestrator
+            .quorum_driver()
+            .finality_tracker()
+            .traces()
+            .into_iter()
+            .map(to_sui_finality_trace)
+            .collect())
+    }
+}
+
+impl SuiRpcModule for DebugApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::DebugApiOpenRpc::module_doc()
+    }
+}
@@ -8,8 +8,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
+use sui_core::gas_price_tracker::GAS_PRICE_HISTORY_CHECKPOINTS;
 use sui_cost::estimator::estimate_transaction_computation_cost;
-use sui_json_rpc_types::SuiGasCostSummary;
+use sui_json_rpc_types::{GasPriceMarket, SuiCheckpointGasPrices, SuiGasCostSummary};
 use sui_open_rpc::Module;
 use sui_types::crypto::SignableBytes;
 use sui_types::messages::TransactionData;
@@ -50,6 +51,27 @@ impl EstimatorApiServer for EstimatorApi {
             .await?,
         ))
     }
+
+    async fn get_gas_price_market(&self, count: Option<usize>) -> RpcResult<GasPriceMarket> {
+        let count = count.unwrap_or(GAS_PRICE_HISTORY_CHECKPOINTS);
+        Ok(self
+            .state
+            .gas_price_tracker
+            .recent(count)
+            .into_iter()
+            .map(|stats| SuiCheckpointGasPrices {
+                checkpoint: stats.checkpoint,
+                tx_count: stats.tx_count,
+                min: stats.min,
+                p25: stats.p25,
+                p50: stats.p50,
+                p75: stats.p75,
+                p90: stats.p90,
+                max: stats.max,
+                pending_certificates: stats.pending_certificates,
+            })
+            .collect())
+    }
 }
 
 impl SuiRpcModule for EstimatorApi {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::api::EventReadApiServer;
 use crate::api::EventStreamingApiServer;
+use crate::response_limits::ResponseLimits;
 use crate::streaming_api::spawn_subscription;
 use crate::SuiRpcModule;
 use async_trait::async_trait;
@@ -83,6 +84,7 @@ impl SuiRpcModule for EventStreamingApiImpl {
 pub struct EventReadApiImpl {
     state: Arc<AuthorityState>,
     event_handler: Arc<EventHandler>,
+    limits: ResponseLimits,
 }
 
 impl EventReadApiImpl {
@@ -90,8 +92,15 @@ impl EventReadApiImpl {
         Self {
             state,
             event_handler,
+            limits: ResponseLimits::default(),
         }
     }
+
+    /// Bounds the number of events queried, and the size of the responses, by `limits`.
+    pub fn with_response_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 }
 
 #[allow(unused)]
@@ -102,7 +111,9 @@ impl EventReadApiServer for EventReadApiImpl {
         digest: TransactionDigest,
         count: usize,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self.state.get_events_by_transaction(digest, count).await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -114,6 +125,7 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let module_id = ModuleId::new(
             AccountAddress::from(package),
             Identifier::from_str(&module)?,
@@ -123,6 +135,7 @@ impl EventReadApiServer for EventReadApiImpl {
             .state
             .get_events_by_transaction_module(&module_id, start_time, end_time, count)
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -133,6 +146,7 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self
             .state
             .get_events_by_move_event_struct_name(
@@ -142,6 +156,7 @@ impl EventReadApiServer for EventReadApiImpl {
                 count,
             )
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -152,10 +167,12 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self
             .state
             .get_events_by_sender(&sender, start_time, end_time, count)
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -166,10 +183,12 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self
             .state
             .get_events_by_recipient(&recipient, start_time, end_time, count)
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -180,10 +199,12 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self
             .state
             .get_events_by_object(&object, start_time, end_time, count)
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 
@@ -193,10 +214,12 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let count = self.limits.clamp_count(count);
         let events = self
             .state
             .get_events_by_timerange(start_time, end_time, count)
             .await?;
+        self.limits.check_size(&events)?;
         Ok(events)
     }
 }
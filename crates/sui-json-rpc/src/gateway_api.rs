@@ -13,8 +13,8 @@ use signature::Signature;
 use sui_core::gateway_state::GatewayClient;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, RPCTransactionRequestParams, SuiObjectInfo, SuiTransactionResponse,
-    SuiTypeTag, TransactionBytes,
+    GetObjectDataResponse, RPCTransactionRequestParams, SessionToken, SuiObjectInfo,
+    SuiTransactionResponse, SuiTypeTag, TransactionBytes,
 };
 use sui_open_rpc::Module;
 use sui_types::batch::TxSequenceNumber;
@@ -124,6 +124,8 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
+        // The gateway executes transactions itself, its reads always observe them.
+        _session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
         debug!("get_objects_own_by_address : {}", address);
         Ok(self.client.get_objects_owned_by_address(address).await?)
@@ -132,18 +134,24 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
+        _session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
         debug!("get_objects_own_by_object : {}", object_id);
         Ok(self.client.get_objects_owned_by_object(object_id).await?)
     }
 
-    async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        _session_token: Option<SessionToken>,
+    ) -> RpcResult<GetObjectDataResponse> {
         Ok(self.client.get_object(object_id).await?)
     }
 
     async fn get_transaction(
         &self,
         digest: TransactionDigest,
+        _session_token: Option<SessionToken>,
     ) -> RpcResult<SuiTransactionResponse> {
         Ok(self.client.get_transaction(digest).await?)
     }
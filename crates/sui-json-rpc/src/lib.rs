@@ -3,9 +3,11 @@
 
 use std::env;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Instant;
 
 pub use jsonrpsee::http_server;
+use jsonrpsee::types::error::{CallError, ErrorObject, CALL_EXECUTION_FAILED_CODE};
 use jsonrpsee::types::Params;
 pub use jsonrpsee::ws_server;
 use jsonrpsee_core::middleware::{Headers, HttpMiddleware, MethodKind, WsMiddleware};
@@ -17,20 +19,31 @@ use prometheus::{
 };
 use tracing::info;
 
+use sui_core::slow_calls::{sanitize_json_params, CallServer, PendingCalls, SlowCallLog};
+use sui_json_rpc_types::SuiErrorData;
 use sui_open_rpc::{Module, Project};
+use sui_types::error::SuiError;
+
+use crate::response_limits::ResponseLimits;
+use crate::versioning::{deprecated_methods, register_deprecated_method, DeprecatedMethod};
 
 use crate::http_server::{HttpServerBuilder, HttpServerHandle};
 use crate::ws_server::{WsServerBuilder, WsServerHandle};
 
 pub mod api;
 pub mod bcs_api;
+pub mod bridge_api;
+pub mod debug_api;
 pub mod estimator_api;
 pub mod event_api;
 pub mod gateway_api;
+pub mod network_info_api;
 pub mod read_api;
+pub mod response_limits;
 pub mod streaming_api;
 pub mod transaction_builder_api;
 pub mod transaction_execution_api;
+pub mod versioning;
 
 pub enum ServerBuilder<M = ()> {
     HttpBuilder(HttpServerBuilder<M>),
@@ -74,6 +87,7 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     server_builder: ServerBuilder<ApiMetrics>,
     rpc_doc: Project,
+    deprecated_methods: Vec<DeprecatedMethod>,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -90,10 +104,12 @@ pub fn sui_rpc_doc(version: &str) -> Project {
 }
 
 impl JsonRpcServerBuilder {
+    /// Calls slower than the JSON-RPC threshold of `slow_calls`, if given, are recorded there.
     pub fn new(
         version: &str,
         use_websocket: bool,
         prometheus_registry: &prometheus::Registry,
+        slow_calls: Option<Arc<SlowCallLog>>,
     ) -> anyhow::Result<Self> {
         let acl = match env::var("ACCESS_CONTROL_ALLOW_ORIGIN") {
             Ok(value) => {
@@ -113,7 +129,9 @@ impl JsonRpcServerBuilder {
             ServerBuilder::WsBuilder(
                 WsServerBuilder::default()
                     .set_access_control(acl)
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
+                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {
+                        slow_calls: slow_calls.map(SlowCalls::new),
+                    })),
             )
         } else {
             ServerBuilder::HttpBuilder(
@@ -121,6 +139,7 @@ impl JsonRpcServerBuilder {
                     .set_access_control(acl)
                     .set_middleware(ApiMetrics::JsonRpcMetrics(JsonRpcMetrics::new(
                         prometheus_registry,
+                        slow_calls,
                     ))),
             )
         };
@@ -131,6 +150,7 @@ impl JsonRpcServerBuilder {
             module,
             server_builder,
             rpc_doc: sui_rpc_doc(version),
+            deprecated_methods: deprecated_methods(),
         })
     }
 
@@ -138,12 +158,12 @@ impl JsonRpcServerBuilder {
         let server_builder = if use_websocket {
             ServerBuilder::WsBuilder(
                 WsServerBuilder::default()
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
+                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics::default())),
             )
         } else {
             ServerBuilder::HttpBuilder(
                 HttpServerBuilder::default()
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
+                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics::default())),
             )
         };
 
@@ -153,18 +173,50 @@ impl JsonRpcServerBuilder {
             module,
             server_builder,
             rpc_doc: sui_rpc_doc("0.0.0"),
+            deprecated_methods: deprecated_methods(),
         })
     }
 
+    /// Replaces the responses larger than the limit of `limits` by an error.
+    pub fn with_response_limits(mut self, limits: &ResponseLimits) -> Self {
+        let max = limits.max_response_body_size();
+        self.server_builder = match self.server_builder {
+            ServerBuilder::HttpBuilder(builder) => {
+                ServerBuilder::HttpBuilder(builder.max_response_body_size(max))
+            }
+            ServerBuilder::WsBuilder(builder) => {
+                ServerBuilder::WsBuilder(builder.max_response_body_size(max))
+            }
+        };
+        self
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), anyhow::Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
     }
 
+    /// Serves `method` on top of its replacement, in addition to the methods of
+    /// `versioning::deprecated_methods`.
+    pub fn register_deprecated_method(&mut self, method: DeprecatedMethod) {
+        self.deprecated_methods.push(method);
+    }
+
     pub async fn start(
         mut self,
         listen_address: SocketAddr,
     ) -> Result<ServerHandle, anyhow::Error> {
+        // Registered last, so that the replacements of all modules are known.
+        for method in std::mem::take(&mut self.deprecated_methods) {
+            let (name, replacement, removed_in) =
+                (method.name, method.replacement, method.removed_in);
+            if register_deprecated_method(&mut self.module, method)? {
+                info!(
+                    method = name,
+                    replacement, removed_in, "Serving deprecated method"
+                );
+            }
+        }
         self.module
             .register_method("rpc.discover", move |_, _| Ok(self.rpc_doc.clone()))?;
         let methods_names = self.module.method_names().collect::<Vec<_>>();
@@ -198,6 +250,40 @@ pub struct JsonRpcMetrics {
     req_latency_by_route: HistogramVec,
     /// Failed requests by route
     errors_by_route: IntCounterVec,
+    slow_calls: Option<SlowCalls>,
+}
+
+/// Records the calls exceeding the JSON-RPC latency threshold, with their parameters.
+#[derive(Clone)]
+struct SlowCalls {
+    log: Arc<SlowCallLog>,
+    pending: Arc<PendingCalls>,
+}
+
+impl SlowCalls {
+    fn new(log: Arc<SlowCallLog>) -> Self {
+        Self {
+            log,
+            pending: Arc::new(PendingCalls::default()),
+        }
+    }
+
+    fn on_call(&self, method_name: &str, params: Params) {
+        self.pending
+            .on_call(method_name, params.as_str().unwrap_or_default());
+    }
+
+    fn on_result(&self, name: &str, success: bool, started_at: Instant) {
+        let latency = started_at.elapsed();
+        let params = self.pending.on_result(name, started_at);
+        self.log.record(
+            CallServer::JsonRpc,
+            name,
+            || sanitize_json_params(params.as_deref().unwrap_or_default()),
+            latency,
+            success,
+        );
+    }
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -205,7 +291,7 @@ const LATENCY_SEC_BUCKETS: &[f64] = &[
 ];
 
 impl JsonRpcMetrics {
-    pub fn new(registry: &prometheus::Registry) -> Self {
+    pub fn new(registry: &prometheus::Registry, slow_calls: Option<Arc<SlowCallLog>>) -> Self {
         Self {
             requests_by_route: register_int_counter_vec_with_registry!(
                 "rpc_requests_by_route",
@@ -229,13 +315,16 @@ impl JsonRpcMetrics {
                 registry,
             )
             .unwrap(),
+            slow_calls: slow_calls.map(SlowCalls::new),
         }
     }
 }
 
 // TODO: add metrics middleware for ws server
-#[derive(Clone)]
-pub struct WebsocketMetrics {}
+#[derive(Clone, Default)]
+pub struct WebsocketMetrics {
+    slow_calls: Option<SlowCalls>,
+}
 
 impl HttpMiddleware for ApiMetrics {
     type Instant = Instant;
@@ -244,13 +333,22 @@ impl HttpMiddleware for ApiMetrics {
         Instant::now()
     }
 
-    fn on_call(&self, _method_name: &str, _params: Params, _kind: MethodKind) {}
+    fn on_call(&self, method_name: &str, params: Params, _kind: MethodKind) {
+        if let ApiMetrics::JsonRpcMetrics(JsonRpcMetrics {
+            slow_calls: Some(slow_calls),
+            ..
+        }) = self
+        {
+            slow_calls.on_call(method_name, params);
+        }
+    }
 
     fn on_result(&self, name: &str, success: bool, started_at: Instant) {
         if let ApiMetrics::JsonRpcMetrics(JsonRpcMetrics {
             requests_by_route,
             req_latency_by_route,
             errors_by_route,
+            slow_calls,
         }) = self
         {
             requests_by_route.with_label_values(&[name]).inc();
@@ -261,6 +359,9 @@ impl HttpMiddleware for ApiMetrics {
             if !success {
                 errors_by_route.with_label_values(&[name]).inc();
             }
+            if let Some(slow_calls) = slow_calls {
+                slow_calls.on_result(name, success, started_at);
+            }
         }
     }
 
@@ -276,9 +377,23 @@ impl WsMiddleware for ApiMetrics {
         Instant::now()
     }
 
-    fn on_call(&self, _method_name: &str, _params: Params, _kind: MethodKind) {}
+    fn on_call(&self, method_name: &str, params: Params, _kind: MethodKind) {
+        if let ApiMetrics::WebsocketMetrics(WebsocketMetrics {
+            slow_calls: Some(slow_calls),
+        }) = self
+        {
+            slow_calls.on_call(method_name, params);
+        }
+    }
 
-    fn on_result(&self, _method_name: &str, _success: bool, _started_at: Self::Instant) {}
+    fn on_result(&self, method_name: &str, success: bool, started_at: Self::Instant) {
+        if let ApiMetrics::WebsocketMetrics(WebsocketMetrics {
+            slow_calls: Some(slow_calls),
+        }) = self
+        {
+            slow_calls.on_result(method_name, success, started_at);
+        }
+    }
 
     fn on_response(&self, _result: &str, _started_at: Self::Instant) {}
 
@@ -292,3 +407,17 @@ where
     fn rpc(self) -> RpcModule<Self>;
     fn rpc_doc_module() -> Module;
 }
+
+/// Converts the error of an RPC method to a JSON-RPC error. Errors caused by a `SuiError` carry
+/// its code and classification as `SuiErrorData`.
+pub fn to_rpc_error(error: anyhow::Error) -> jsonrpsee_core::Error {
+    match error.downcast_ref::<SuiError>() {
+        Some(sui_error) => CallError::Custom(ErrorObject::owned(
+            CALL_EXECUTION_FAILED_CODE,
+            error.to_string(),
+            Some(SuiErrorData::from(sui_error)),
+        ))
+        .into(),
+        None => error.into(),
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_json_rpc_types::SuiNetworkInfoBundle;
+use sui_open_rpc::Module;
+use sui_types::committee::EpochId;
+use sui_types::network_info::{CertifiedNetworkInfo, NetworkInfoBundle};
+use tokio::sync::Mutex;
+
+use crate::api::NetworkInfoApiServer;
+use crate::SuiRpcModule;
+
+pub struct NetworkInfoApiImpl {
+    state: Arc<AuthorityState>,
+    transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+    /// The network info certified by the committee of the latest epoch it was requested in.
+    certified_info: Mutex<Option<CertifiedNetworkInfo>>,
+}
+
+impl NetworkInfoApiImpl {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        transaction_orchestrator: Arc<TransactiondOrchestrator<NetworkAuthorityClient>>,
+    ) -> Self {
+        Self {
+            state,
+            transaction_orchestrator,
+            certified_info: Mutex::new(None),
+        }
+    }
+
+    /// Returns the network info certified by the committee of the current epoch. Validators
+    /// are only asked to sign it once per epoch: concurrent requests wait for the first one.
+    async fn get_certified_info(&self) -> anyhow::Result<CertifiedNetworkInfo> {
+        let aggregator = self
+            .transaction_orchestrator
+            .quorum_driver()
+            .authority_aggregator()
+            .load_full();
+        let mut certified_info = self.certified_info.lock().await;
+        if let Some(info) = &*certified_info {
+            if info.info.epoch == aggregator.committee.epoch {
+                return Ok(info.clone());
+            }
+        }
+        let info = aggregator.get_network_info().await?;
+        *certified_info = Some(info.clone());
+        Ok(info)
+    }
+}
+
+#[async_trait]
+impl NetworkInfoApiServer for NetworkInfoApiImpl {
+    async fn get_network_info_bundle(
+        &self,
+        from_epoch: Option<EpochId>,
+    ) -> RpcResult<SuiNetworkInfoBundle> {
+        let info = self.get_certified_info().await?;
+        // The checkpoints must lead to the epoch of the info, which may be ahead of this node.
+        let epoch_changes = self
+            .state
+            .get_epoch_change_checkpoints(from_epoch.unwrap_or(0), info.info.epoch)?;
+        Ok(SuiNetworkInfoBundle::new(&NetworkInfoBundle {
+            info,
+            epoch_changes,
+        })?)
+    }
+}
+
+impl SuiRpcModule for NetworkInfoApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::NetworkInfoApiOpenRpc::module_doc()
+    }
+}
@@ -3,6 +3,7 @@
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -12,11 +13,19 @@ use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
 use signature::Signature;
 
-use sui_core::authority::AuthorityState;
+use sui_core::authority::{AuthorityState, MAX_OBJECT_OWNERSHIP_DEPTH};
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::execution_sandbox::ExecutionSandbox;
+use sui_core::read_fallback::ReadFallback;
+use sui_json::entry_function_args_schema;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind, Page,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    AddressActivityCursor, AddressActivityPage, CoinPage, GetObjectDataResponse,
+    GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind, ObjectWithLayout,
+    ReceivedObjectsCursor, ReceivedObjectsPage, SessionToken, SuiAddressActivity, SuiCoin,
+    SuiCommittee, SuiDryRunTraceResponse, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiNestedObjectInfo, SuiNestedObjects, SuiObjectInclusionProof,
+    SuiObjectInfo, SuiObjectsAtCheckpoint, SuiPackageAbi, SuiReceivedObject, SuiSupply,
+    SuiTransactionEffects, SuiTransactionEffectsDiff, SuiTransactionResponse, TransactionsPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
@@ -24,75 +33,197 @@ use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{SignableBytes, SignatureScheme};
-use sui_types::messages::{
-    CommitteeInfoRequest, CommitteeInfoResponse, Transaction, TransactionData,
-};
+use sui_types::epoch_accounting::EpochAccountingReport;
+use sui_types::gas_coin::GAS;
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::normalize_modules;
-use sui_types::object::{Data, ObjectRead, Owner};
+use sui_types::object::{Data, ObjectRead, Owner, PastObjectRead};
+use sui_types::object_change::changed_objects;
+use sui_types::parse_sui_type_tag;
 use sui_types::query::{Ordering, TransactionQuery};
 use sui_types::sui_serde::Base64;
 
+use crate::api::RpcFullNodeReadApiServer;
 use crate::api::RpcReadApiServer;
-use crate::api::{RpcFullNodeReadApiServer, MAX_RESULT_SIZE};
-use crate::SuiRpcModule;
+use crate::response_limits::ResponseLimits;
+use crate::{to_rpc_error, SuiRpcModule};
+
+/// How long reads wait for the node to execute the transaction of their session token.
+const SESSION_TOKEN_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long reads wait for the node to learn of the certificate of their session token.
+const SESSION_TOKEN_UNKNOWN_TIMEOUT: Duration = Duration::from_secs(1);
+const SESSION_TOKEN_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 // An implementation of the read portion of the Gateway JSON-RPC interface intended for use in
 // Fullnodes.
 pub struct ReadApi {
     pub state: Arc<AuthorityState>,
+    /// Serves the transactions and objects this node has not synced yet, if enabled.
+    fallback: Option<Arc<ReadFallback<NetworkAuthorityClient>>>,
+    limits: ResponseLimits,
 }
 
 pub struct FullNodeApi {
     pub state: Arc<AuthorityState>,
+    /// Sandbox the dry runs execute in, if any.
+    sandbox: Option<Arc<ExecutionSandbox>>,
+    limits: ResponseLimits,
 }
 
 impl FullNodeApi {
     pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+        Self {
+            state,
+            sandbox: None,
+            limits: ResponseLimits::default(),
+        }
+    }
+
+    /// Bounds the pages returned by `limits`.
+    pub fn with_response_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Executes dry runs in `sandbox`.
+    pub fn with_sandbox(mut self, sandbox: Arc<ExecutionSandbox>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Reads the given version of an object with its layout, if the node still has it.
+    async fn get_object_version(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<Option<ObjectWithLayout>, anyhow::Error> {
+        let version = match version {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+        Ok(
+            match self
+                .state
+                .get_past_object_read(&object_id, version)
+                .await
+                .map_err(|e| anyhow!("{e}"))?
+            {
+                PastObjectRead::VersionFound(_, object, layout) => Some((object, layout)),
+                _ => None,
+            },
+        )
     }
 }
 
 impl ReadApi {
-    pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+    pub fn new(
+        state: Arc<AuthorityState>,
+        fallback: Option<Arc<ReadFallback<NetworkAuthorityClient>>>,
+    ) -> Self {
+        Self {
+            state,
+            fallback,
+            limits: ResponseLimits::default(),
+        }
+    }
+
+    /// Bounds the ranges of transactions and the lists of owned objects returned by `limits`.
+    pub fn with_response_limits(mut self, limits: ResponseLimits) -> Self {
+        self.limits = limits;
+        self
     }
 }
 
+/// Waits until this node has executed the transaction of `session_token`, e.g. synced from the
+/// node the client submitted it to, so that the read that follows observes it. Tokens of
+/// transactions the node does not even have the certificate of after a short while, e.g. forged
+/// ones, fail fast rather than holding the read for the whole timeout.
+pub(crate) async fn wait_for_session(
+    state: &AuthorityState,
+    session_token: Option<SessionToken>,
+) -> RpcResult<()> {
+    let tx_digest = match session_token {
+        Some(session_token) => *session_token.tx_digest(),
+        None => return Ok(()),
+    };
+    let start = Instant::now();
+    while !state.is_tx_already_executed(&tx_digest)? {
+        let elapsed = start.elapsed();
+        if elapsed >= SESSION_TOKEN_UNKNOWN_TIMEOUT
+            && state.read_certificate(&tx_digest).await?.is_none()
+        {
+            return Err(anyhow!(
+                "Transaction {tx_digest} of the session is unknown to the node, retry later"
+            )
+            .into());
+        }
+        if elapsed >= SESSION_TOKEN_TIMEOUT {
+            return Err(anyhow!(
+                "Timed out waiting for the node to execute transaction {tx_digest} of the session, retry later"
+            )
+            .into());
+        }
+        tokio::time::sleep(SESSION_TOKEN_POLL_INTERVAL).await;
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl RpcReadApiServer for ReadApi {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
+        session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
+        wait_for_session(&self.state, session_token).await?;
+        let owner = Owner::AddressOwner(address);
+        let objects: Vec<_> = self
             .state
-            .get_owner_objects(Owner::AddressOwner(address))
+            .get_owner_objects_bounded(owner, self.limits.unpaged_read_limit())
             .map_err(|e| anyhow!("{e}"))?
             .into_iter()
             .map(SuiObjectInfo::from)
-            .collect())
+            .collect();
+        self.limits.check_unpaged(&objects)?;
+        Ok(objects)
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
+        session_token: Option<SessionToken>,
     ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
+        wait_for_session(&self.state, session_token).await?;
+        let owner = Owner::ObjectOwner(object_id.into());
+        let objects: Vec<_> = self
             .state
-            .get_owner_objects(Owner::ObjectOwner(object_id.into()))
+            .get_owner_objects_bounded(owner, self.limits.unpaged_read_limit())
             .map_err(|e| anyhow!("{e}"))?
             .into_iter()
             .map(SuiObjectInfo::from)
-            .collect())
+            .collect();
+        self.limits.check_unpaged(&objects)?;
+        Ok(objects)
     }
 
-    async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
-        Ok(self
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<GetObjectDataResponse> {
+        wait_for_session(&self.state, session_token).await?;
+        let mut object = self
             .state
             .get_object_read(&object_id)
             .await
-            .map_err(|e| anyhow!("{e}"))?
-            .try_into()?)
+            .map_err(|e| anyhow!("{e}"))?;
+        if let (ObjectRead::NotExists(_), Some(fallback)) = (&object, &self.fallback) {
+            if let Ok(fetched) = fallback.get_object_read(object_id).await {
+                object = fetched;
+            }
+        }
+        Ok(object.try_into()?)
     }
 
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
@@ -104,6 +235,7 @@ impl RpcReadApiServer for ReadApi {
         start: TxSequenceNumber,
         end: TxSequenceNumber,
     ) -> RpcResult<Vec<TransactionDigest>> {
+        self.limits.check_range(start, end)?;
         Ok(self
             .state
             .get_transactions_in_range(start, end)?
@@ -115,13 +247,27 @@ impl RpcReadApiServer for ReadApi {
     async fn get_transaction(
         &self,
         digest: TransactionDigest,
+        session_token: Option<SessionToken>,
     ) -> RpcResult<SuiTransactionResponse> {
-        let (cert, effects) = self.state.get_transaction(digest).await?;
+        wait_for_session(&self.state, session_token).await?;
+        let (cert, effects) = match (self.state.get_transaction(digest).await, &self.fallback) {
+            (Ok(transaction), _) => transaction,
+            (Err(e), Some(fallback)) => fallback
+                .get_transaction(digest)
+                .await
+                .map_err(|_| to_rpc_error(e))?,
+            (Err(e), None) => return Err(to_rpc_error(e)),
+        };
         Ok(SuiTransactionResponse {
             certificate: cert.try_into()?,
             effects: SuiTransactionEffects::try_from(effects, self.state.module_cache.as_ref())?,
             timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
             parsed_data: None,
+            balance_changes: self
+                .state
+                .get_balance_changes(&digest)
+                .await?
+                .map(|changes| changes.into_iter().map(Into::into).collect()),
         })
     }
 }
@@ -153,7 +299,34 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         let txn = Transaction::new(data, signature);
         let txn_digest = *txn.digest();
 
-        Ok(self.state.dry_run_transaction(&txn, txn_digest).await?)
+        match &self.sandbox {
+            Some(sandbox) => Ok(sandbox.dry_run(&self.state, &txn).await?),
+            None => Ok(self.state.dry_run_transaction(&txn, txn_digest).await?),
+        }
+    }
+
+    async fn dry_run_transaction_with_trace(
+        &self,
+        tx_bytes: Base64,
+        sig_scheme: SignatureScheme,
+        signature: Base64,
+        pub_key: Base64,
+    ) -> RpcResult<SuiDryRunTraceResponse> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+        let flag = vec![sig_scheme.flag()];
+        let signature =
+            Signature::from_bytes(&[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat())
+                .map_err(|e| anyhow!(e))?;
+        let txn = Transaction::new(data, signature);
+        let txn_digest = *txn.digest();
+
+        match &self.sandbox {
+            Some(sandbox) => Ok(sandbox.dry_run_with_trace(&self.state, &txn).await?),
+            None => Ok(self
+                .state
+                .dry_run_transaction_with_trace(&txn, txn_digest)
+                .await?),
+        }
     }
 
     async fn get_normalized_move_modules_by_package(
@@ -212,6 +385,26 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         }?)
     }
 
+    async fn get_move_function_arg_schema(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        function_name: String,
+    ) -> RpcResult<serde_json::Value> {
+        let module = get_move_module(self, package, module_name).await?;
+        let identifier = Identifier::new(function_name.as_str()).map_err(|e| anyhow!("{e}"))?;
+        let function = module
+            .exposed_functions
+            .get(&identifier)
+            .ok_or_else(|| anyhow!("No function was found with function name {}", function_name))?;
+        Ok(entry_function_args_schema(function)?)
+    }
+
+    async fn get_package_abi(&self, package: ObjectID) -> RpcResult<SuiPackageAbi> {
+        let modules = self.get_normalized_move_modules_by_package(package).await?;
+        Ok(SuiPackageAbi::new(package, modules))
+    }
+
     async fn get_move_function_arg_types(
         &self,
         package: ObjectID,
@@ -269,12 +462,10 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         order: Ordering,
+        session_token: Option<SessionToken>,
     ) -> RpcResult<TransactionsPage> {
-        let limit = limit.unwrap_or(MAX_RESULT_SIZE);
-
-        if limit == 0 {
-            Err(anyhow!("Page result limit must be larger then 0."))?;
-        }
+        wait_for_session(&self.state, session_token).await?;
+        let limit = self.limits.page_limit(limit)?;
         let reverse = order == Ordering::Descending;
 
         // Retrieve 1 extra item for next cursor
@@ -285,14 +476,16 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         // extract next cursor
         let next_cursor = data.get(limit).cloned();
         data.truncate(limit);
-        Ok(Page { data, next_cursor })
+        Ok(self.limits.fit_page(data, next_cursor, |digest| *digest)?)
     }
 
     async fn try_get_past_object(
         &self,
         object_id: ObjectID,
         version: SequenceNumber,
+        session_token: Option<SessionToken>,
     ) -> RpcResult<GetPastObjectDataResponse> {
+        wait_for_session(&self.state, session_token).await?;
         Ok(self
             .state
             .get_past_object_read(&object_id, version)
@@ -301,11 +494,217 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
             .try_into()?)
     }
 
-    async fn get_committee_info(&self, epoch: Option<EpochId>) -> RpcResult<CommitteeInfoResponse> {
+    async fn get_received_objects(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ReceivedObjectsCursor>,
+        limit: Option<usize>,
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<ReceivedObjectsPage> {
+        wait_for_session(&self.state, session_token).await?;
+        let limit = self.limits.page_limit(limit)?;
+        let cursor = cursor.map(|c| (c.tx_sequence_number, c.object_id));
+
+        // Retrieve 1 extra item for next cursor
+        let mut data: Vec<_> = self
+            .state
+            .get_received_objects(address, cursor, Some(limit + 1))?
+            .into_iter()
+            .map(|(seq, obj_ref, digest)| SuiReceivedObject {
+                reference: obj_ref.into(),
+                tx_digest: digest,
+                tx_sequence_number: seq,
+            })
+            .collect();
+
+        // extract next cursor
+        let cursor_of = |o: &SuiReceivedObject| ReceivedObjectsCursor {
+            tx_sequence_number: o.tx_sequence_number,
+            object_id: o.reference.object_id,
+        };
+        let next_cursor = data.get(limit).map(cursor_of);
+        data.truncate(limit);
+        Ok(self.limits.fit_page(data, next_cursor, cursor_of)?)
+    }
+
+    async fn get_address_activity(
+        &self,
+        address: SuiAddress,
+        cursor: Option<AddressActivityCursor>,
+        limit: Option<usize>,
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<AddressActivityPage> {
+        wait_for_session(&self.state, session_token).await?;
+        let limit = self.limits.page_limit(limit)?;
+        let cursor = cursor.map(|c| (c.tx_sequence_number, c.role));
+
+        // Retrieve 1 extra item for next cursor
+        let mut data: Vec<_> = self
+            .state
+            .get_address_activity(address, cursor, Some(limit + 1))?
+            .into_iter()
+            .map(|(seq, role, digest, checkpoint)| SuiAddressActivity {
+                tx_digest: digest,
+                tx_sequence_number: seq,
+                role,
+                checkpoint,
+            })
+            .collect();
+
+        // extract next cursor
+        let cursor_of = |a: &SuiAddressActivity| AddressActivityCursor {
+            tx_sequence_number: a.tx_sequence_number,
+            role: a.role,
+        };
+        let next_cursor = data.get(limit).map(cursor_of);
+        data.truncate(limit);
+        Ok(self.limits.fit_page(data, next_cursor, cursor_of)?)
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+        session_token: Option<SessionToken>,
+    ) -> RpcResult<CoinPage> {
+        wait_for_session(&self.state, session_token).await?;
+        let limit = self.limits.page_limit(limit)?;
+        let coin_type = match coin_type {
+            Some(coin_type) => parse_sui_type_tag(&coin_type)?,
+            None => GAS::type_tag(),
+        };
+
+        // Retrieve 1 extra item for next cursor
+        let coin_type_name = coin_type.to_string();
+        let mut data: Vec<_> = self
+            .state
+            .get_coins(owner, &coin_type, cursor, Some(limit + 1))?
+            .into_iter()
+            .map(|(obj_ref, balance)| SuiCoin {
+                coin_type: coin_type_name.clone(),
+                reference: obj_ref.into(),
+                balance,
+            })
+            .collect();
+
+        // extract next cursor
+        let cursor_of = |c: &SuiCoin| c.reference.object_id;
+        let next_cursor = data.get(limit).map(cursor_of);
+        data.truncate(limit);
+        Ok(self.limits.fit_page(data, next_cursor, cursor_of)?)
+    }
+
+    async fn get_total_supply(&self, coin_type: String) -> RpcResult<SuiSupply> {
+        let coin_type = parse_sui_type_tag(&coin_type)?;
+        Ok(SuiSupply {
+            value: self.state.get_total_supply(&coin_type).await?,
+        })
+    }
+
+    async fn get_objects_owned_by_object_recursive(
+        &self,
+        object_id: ObjectID,
+        max_depth: Option<u32>,
+    ) -> RpcResult<SuiNestedObjects> {
+        let max_depth = max_depth.unwrap_or(MAX_OBJECT_OWNERSHIP_DEPTH);
+        if max_depth == 0 || max_depth > MAX_OBJECT_OWNERSHIP_DEPTH {
+            Err(anyhow!(
+                "Maximum depth must be between 1 and {MAX_OBJECT_OWNERSHIP_DEPTH}."
+            ))?;
+        }
+        let (objects, truncated) = self.state.get_objects_owned_by_object_recursive(
+            object_id,
+            max_depth,
+            self.limits.max_page_size,
+        )?;
+        Ok(SuiNestedObjects {
+            objects: objects
+                .into_iter()
+                .map(|(info, depth)| SuiNestedObjectInfo {
+                    object: info.into(),
+                    depth,
+                })
+                .collect(),
+            truncated,
+        })
+    }
+
+    async fn get_object_inclusion_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> RpcResult<SuiObjectInclusionProof> {
+        let (object_ref, proof) = self
+            .state
+            .get_object_inclusion_proof(object_id, version)
+            .await?;
+        Ok(SuiObjectInclusionProof::new(object_ref, &proof)?)
+    }
+
+    async fn get_objects_at_checkpoint(
+        &self,
+        object_ids: Vec<ObjectID>,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> RpcResult<SuiObjectsAtCheckpoint> {
+        if object_ids.len() > self.limits.max_page_size {
+            return Err(anyhow!(
+                "Cannot read more than {} objects at once",
+                self.limits.max_page_size
+            )
+            .into());
+        }
+        let (checkpoint, reads) = self
+            .state
+            .get_objects_at_checkpoint(&object_ids, checkpoint)
+            .await?;
+        Ok(SuiObjectsAtCheckpoint {
+            checkpoint,
+            objects: reads
+                .into_iter()
+                .map(GetPastObjectDataResponse::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    async fn get_transaction_effects_diff(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiTransactionEffectsDiff> {
+        let (_, effects) = self.state.get_transaction(digest).await?;
+        let mut objects = vec![];
+        for change in changed_objects(&effects) {
+            let object_id = change.object_id();
+            objects.push((
+                object_id,
+                change.kind,
+                self.get_object_version(object_id, change.before).await?,
+                self.get_object_version(object_id, change.after).await?,
+            ));
+        }
+        Ok(SuiTransactionEffectsDiff::try_new(&effects, objects)?)
+    }
+
+    async fn get_committee(&self, epoch: Option<EpochId>) -> RpcResult<SuiCommittee> {
+        let epoch = epoch.unwrap_or_else(|| self.state.epoch());
+        Ok(self
+            .state
+            .committee_store()
+            .get_committee_with_net_addresses(&epoch)
+            .map_err(|e| anyhow!("{e}"))?
+            .ok_or_else(|| anyhow!("Committee of epoch {epoch} is not known"))?
+            .into())
+    }
+
+    async fn get_epoch_accounting_report(
+        &self,
+        epoch: EpochId,
+    ) -> RpcResult<EpochAccountingReport> {
         Ok(self
             .state
-            .handle_committee_info_request(&CommitteeInfoRequest { epoch })
-            .map_err(|e| anyhow!("{e}"))?)
+            .get_epoch_accounting_report(epoch)?
+            .ok_or_else(|| anyhow!("No accounting report for epoch {epoch}, it has not ended"))?)
     }
 }
 
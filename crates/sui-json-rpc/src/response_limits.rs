@@ -0,0 +1,225 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bounds on the responses of the read methods, which protect the node from building and
+//! serializing responses of unbounded size, e.g. pages of thousands of large items.
+//!
+//! Paginated methods clamp the number of items of their pages, and cut pages whose serialized
+//! size is above the limit, pointing their next cursor at the first item left out. Responses of
+//! other methods cannot be cut without losing data: they are read up to one item more than a page
+//! holds, and replaced by an error if above either limit.
+
+use std::io;
+
+use anyhow::bail;
+use serde::Serialize;
+use sui_json_rpc_types::Page;
+
+use crate::api::MAX_RESULT_SIZE;
+
+/// Bytes of a response left for the JSON-RPC envelope and the next cursor of a page.
+const RESPONSE_OVERHEAD_BYTES: usize = 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ResponseLimits {
+    /// Largest serialized response.
+    pub max_response_size_bytes: usize,
+    /// Most items in a page.
+    pub max_page_size: usize,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        Self {
+            max_response_size_bytes: 16 * 1024 * 1024,
+            max_page_size: MAX_RESULT_SIZE,
+        }
+    }
+}
+
+impl ResponseLimits {
+    /// The number of items of a page requested with `limit`, clamped to the page size limit.
+    pub fn page_limit(&self, limit: Option<usize>) -> Result<usize, anyhow::Error> {
+        let limit = limit.unwrap_or(self.max_page_size);
+        if limit == 0 {
+            bail!("Page result limit must be larger then 0.");
+        }
+        Ok(limit.min(self.max_page_size))
+    }
+
+    /// Fails if a range query returns more items than a page holds.
+    pub fn check_range(&self, start: u64, end: u64) -> Result<(), anyhow::Error> {
+        let len = end.saturating_sub(start);
+        if len > self.max_page_size as u64 {
+            bail!(
+                "Range of {len} items is above the maximum of {}, request smaller ranges",
+                self.max_page_size
+            );
+        }
+        Ok(())
+    }
+
+    /// Clamps the number of items requested from a method without paging, e.g. the `count` of the
+    /// event queries, to the page size limit.
+    pub fn clamp_count(&self, count: usize) -> usize {
+        count.min(self.max_page_size)
+    }
+
+    /// The number of items to read for a list that cannot be paged: one more than a page holds,
+    /// which tells whether the list is above the limit without reading all of it.
+    pub fn unpaged_read_limit(&self) -> usize {
+        self.max_page_size.saturating_add(1)
+    }
+
+    /// Fails if a list that cannot be paged, read up to `unpaged_read_limit` items, holds more
+    /// items than a page or does not fit in a response.
+    pub fn check_unpaged<T: Serialize>(&self, data: &[T]) -> Result<(), anyhow::Error> {
+        if data.len() > self.max_page_size {
+            bail!(
+                "Result has more than the maximum of {} items",
+                self.max_page_size
+            );
+        }
+        self.check_size(&data)
+    }
+
+    /// Fails if the serialization of `value` does not fit in a response.
+    pub fn check_size<T: Serialize>(&self, value: &T) -> Result<(), anyhow::Error> {
+        let budget = self
+            .max_response_size_bytes
+            .saturating_sub(RESPONSE_OVERHEAD_BYTES);
+        if serialized_size(value) > budget {
+            bail!(
+                "Result is above the maximum response size of {} bytes",
+                self.max_response_size_bytes
+            );
+        }
+        Ok(())
+    }
+
+    /// Cuts `data` to the items whose serialization fits in a response. If items are left out,
+    /// the next cursor, given by `cursor_of`, points at the first of them.
+    pub fn fit_page<T: Serialize, C>(
+        &self,
+        mut data: Vec<T>,
+        next_cursor: Option<C>,
+        cursor_of: impl Fn(&T) -> C,
+    ) -> Result<Page<T, C>, anyhow::Error> {
+        let budget = self
+            .max_response_size_bytes
+            .saturating_sub(RESPONSE_OVERHEAD_BYTES);
+        let mut size = 0;
+        let fitting = data.iter().position(|item| {
+            // One more byte for the separator between items.
+            size += serialized_size(item) + 1;
+            size > budget
+        });
+        Ok(match fitting {
+            None => Page { data, next_cursor },
+            Some(0) => bail!(
+                "Item is above the maximum response size of {} bytes",
+                self.max_response_size_bytes
+            ),
+            Some(len) => {
+                let next_cursor = Some(cursor_of(&data[len]));
+                data.truncate(len);
+                Page { data, next_cursor }
+            }
+        })
+    }
+
+    /// The size limit of the responses of the server.
+    pub fn max_response_body_size(&self) -> u32 {
+        self.max_response_size_bytes.try_into().unwrap_or(u32::MAX)
+    }
+}
+
+fn serialized_size<T: Serialize>(value: &T) -> usize {
+    let mut counter = ByteCounter(0);
+    // Serializing to a counter only fails if the value cannot be serialized, which the server
+    // would fail on anyway.
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_limits(max_response_size_bytes: usize, max_page_size: usize) -> ResponseLimits {
+        ResponseLimits {
+            max_response_size_bytes,
+            max_page_size,
+        }
+    }
+
+    #[test]
+    fn test_page_limit() {
+        let limits = response_limits(16 * 1024, 10);
+        assert_eq!(limits.page_limit(None).unwrap(), 10);
+        assert_eq!(limits.page_limit(Some(3)).unwrap(), 3);
+        assert_eq!(limits.page_limit(Some(100)).unwrap(), 10);
+        assert!(limits.page_limit(Some(0)).is_err());
+
+        assert!(limits.check_range(5, 15).is_ok());
+        assert!(limits.check_range(5, 16).is_err());
+        assert!(limits.check_range(15, 5).is_ok());
+    }
+
+    #[test]
+    fn test_fit_page() {
+        // 100 strings of 100 bytes, each taking 103 bytes with its quotes and separator.
+        let data: Vec<String> = (0..100).map(|i| format!("{i:0>100}")).collect();
+        let limits = response_limits(RESPONSE_OVERHEAD_BYTES + 1030, 100);
+
+        let page = limits
+            .fit_page(data.clone(), Some(100), |item| item.parse().unwrap())
+            .unwrap();
+        assert_eq!(page.data, data[..10]);
+        assert_eq!(page.next_cursor, Some(10));
+
+        // A page that fits keeps its own next cursor.
+        let page = limits
+            .fit_page(data[..5].to_vec(), Some(5), |item| item.parse().unwrap())
+            .unwrap();
+        assert_eq!(page.data.len(), 5);
+        assert_eq!(page.next_cursor, Some(5));
+
+        // A page that cannot fit its first item fails rather than returning an empty page with
+        // a cursor pointing at that same item.
+        let limits = response_limits(RESPONSE_OVERHEAD_BYTES + 50, 100);
+        assert!(limits
+            .fit_page(data, None, |item| item.parse::<usize>().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_unpaged() {
+        let limits = response_limits(RESPONSE_OVERHEAD_BYTES + 1030, 10);
+        assert_eq!(limits.clamp_count(5), 5);
+        assert_eq!(limits.clamp_count(50), 10);
+        assert_eq!(limits.unpaged_read_limit(), 11);
+
+        let data: Vec<String> = (0..11).map(|i| format!("{i:0>10}")).collect();
+        assert!(limits.check_unpaged(&data[..10]).is_ok());
+        assert!(limits.check_unpaged(&data).is_err());
+
+        let data: Vec<String> = (0..10).map(|i| format!("{i:0>90}")).collect();
+        assert!(limits.check_unpaged(&data).is_ok());
+        let data: Vec<String> = (0..10).map(|i| format!("{i:0>200}")).collect();
+        assert!(limits.check_unpaged(&data).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::api::TransactionStreamingApiServer;
+use crate::api::{ObjectChangeStreamingApiServer, TransactionStreamingApiServer};
 use crate::SuiRpcModule;
 use async_trait::async_trait;
 use futures::{StreamExt, TryStream};
@@ -12,13 +12,17 @@ use serde::Serialize;
 use std::fmt::Display;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
+use sui_core::object_change_feed;
 use sui_core::transaction_streamer::TransactionStreamer;
 use sui_json_rpc_types::SuiCertifiedTransaction;
+use sui_json_rpc_types::SuiObjectChangeEnvelope;
+use sui_json_rpc_types::SuiObjectChangeFilter;
 use sui_json_rpc_types::SuiTransactionEffects;
 use sui_json_rpc_types::SuiTransactionFilter;
 use sui_json_rpc_types::SuiTransactionResponse;
 use sui_open_rpc::Module;
 use sui_types::filter::TransactionFilter;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::warn;
 
 pub struct TransactionStreamingApiImpl {
@@ -56,11 +60,17 @@ impl TransactionStreamingApiServer for TransactionStreamingApiImpl {
                 )?;
                 let digest = sui_tx_cert.transaction_digest;
                 let ts = state_clone.get_timestamp_ms(&digest).await.unwrap_or(None);
+                let balance_changes = state_clone
+                    .get_balance_changes(&digest)
+                    .await
+                    .unwrap_or(None)
+                    .map(|changes| changes.into_iter().map(Into::into).collect());
                 Ok::<SuiTransactionResponse, anyhow::Error>(SuiTransactionResponse {
                     certificate: sui_tx_cert,
                     effects: sui_tx_effects,
                     timestamp_ms: ts,
                     parsed_data: None,
+                    balance_changes,
                 })
             }
         });
@@ -80,6 +90,51 @@ impl SuiRpcModule for TransactionStreamingApiImpl {
     }
 }
 
+pub struct ObjectChangeStreamingApiImpl {
+    state: Arc<AuthorityState>,
+}
+
+impl ObjectChangeStreamingApiImpl {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl ObjectChangeStreamingApiServer for ObjectChangeStreamingApiImpl {
+    fn subscribe_object_changes(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: SuiObjectChangeFilter,
+        cursor: Option<CheckpointSequenceNumber>,
+    ) -> SubscriptionResult {
+        let filter = match filter.try_into() {
+            Ok(filter) => filter,
+            Err(e) => {
+                let e = jsonrpsee_core::Error::from(e);
+                warn!(error = ?e, "Rejecting subscription request.");
+                return Ok(sink.reject(e)?);
+            }
+        };
+
+        let stream = object_change_feed::subscribe(self.state.clone(), filter, cursor)
+            .map(|change| change.map(SuiObjectChangeEnvelope::from));
+        spawn_subscription(sink, Box::pin(stream));
+
+        Ok(())
+    }
+}
+
+impl SuiRpcModule for ObjectChangeStreamingApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::ObjectChangeStreamingApiOpenRpc::module_doc()
+    }
+}
+
 pub fn spawn_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
 where
     S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
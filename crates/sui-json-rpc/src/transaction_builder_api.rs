@@ -27,7 +27,7 @@ impl FullNodeTransactionBuilderApi {
     pub fn new(state: Arc<AuthorityState>) -> Self {
         let reader = Arc::new(AuthorityStateDataReader::new(state));
         Self {
-            builder: TransactionBuilder(reader),
+            builder: TransactionBuilder::new(reader),
         }
     }
 }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::api::TransactionExecutionApiServer;
-use crate::SuiRpcModule;
+use crate::{to_rpc_error, SuiRpcModule};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
@@ -10,9 +10,10 @@ use jsonrpsee_core::server::rpc_module::RpcModule;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use signature::Signature;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_core::authority::{AuthorityStore, ResolverWrapper};
 use sui_core::authority_client::NetworkAuthorityClient;
-use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_core::transaction_orchestrator::{TransactiondOrchestrator, LOCAL_EXECUTION_TIMEOUT};
 use sui_json_rpc_types::SuiExecuteTransactionResponse;
 use sui_open_rpc::Module;
 use sui_types::crypto::SignatureScheme;
@@ -41,6 +42,25 @@ impl FullNodeTransactionExecutionApi {
     }
 }
 
+fn to_transaction(
+    tx_bytes: Base64,
+    sig_scheme: SignatureScheme,
+    signature: Base64,
+    pub_key: Base64,
+) -> anyhow::Result<Transaction> {
+    let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+    let flag = vec![sig_scheme.flag()];
+    let signature = crypto::Signature::from_bytes(
+        &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
+    )
+    .map_err(|e| anyhow!(e))?;
+    Ok(Transaction::new(data, signature))
+}
+
+fn local_execution_timeout(timeout_ms: Option<u64>) -> Duration {
+    timeout_ms.map_or(LOCAL_EXECUTION_TIMEOUT, Duration::from_millis)
+}
+
 #[async_trait]
 impl TransactionExecutionApiServer for FullNodeTransactionExecutionApi {
     async fn execute_transaction(
@@ -50,24 +70,55 @@ impl TransactionExecutionApiServer for FullNodeTransactionExecutionApi {
         signature: Base64,
         pub_key: Base64,
         request_type: ExecuteTransactionRequestType,
+        local_execution_timeout_ms: Option<u64>,
     ) -> RpcResult<SuiExecuteTransactionResponse> {
-        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
-        let flag = vec![sig_scheme.flag()];
-        let signature = crypto::Signature::from_bytes(
-            &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
+        let txn = to_transaction(tx_bytes, sig_scheme, signature, pub_key)?;
+        let txn_digest = *txn.digest();
+
+        let response = self
+            .transaction_orchestrator
+            .execute_transaction_with_timeout(
+                ExecuteTransactionRequest {
+                    transaction: txn,
+                    request_type,
+                },
+                local_execution_timeout(local_execution_timeout_ms),
+            )
+            .await
+            .map_err(|e| to_rpc_error(e.into()))?;
+        SuiExecuteTransactionResponse::from_execute_transaction_response(
+            response,
+            txn_digest,
+            self.module_cache.as_ref(),
         )
-        .map_err(|e| anyhow!(e))?;
-        let txn = Transaction::new(data, signature);
+        .map_err(jsonrpsee_core::Error::from)
+    }
+
+    async fn execute_transaction_with_request_id(
+        &self,
+        request_id: String,
+        tx_bytes: Base64,
+        sig_scheme: SignatureScheme,
+        signature: Base64,
+        pub_key: Base64,
+        request_type: ExecuteTransactionRequestType,
+        local_execution_timeout_ms: Option<u64>,
+    ) -> RpcResult<SuiExecuteTransactionResponse> {
+        let txn = to_transaction(tx_bytes, sig_scheme, signature, pub_key)?;
         let txn_digest = *txn.digest();
 
         let response = self
             .transaction_orchestrator
-            .execute_transaction(ExecuteTransactionRequest {
-                transaction: txn,
-                request_type,
-            })
+            .execute_transaction_with_request_id(
+                request_id,
+                ExecuteTransactionRequest {
+                    transaction: txn,
+                    request_type,
+                },
+                local_execution_timeout(local_execution_timeout_ms),
+            )
             .await
-            .map_err(|e| anyhow!(e))?;
+            .map_err(|e| to_rpc_error(e.into()))?;
         SuiExecuteTransactionResponse::from_execute_transaction_response(
             response,
             txn_digest,
@@ -0,0 +1,203 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Methods of the previous version of the API, kept during a deprecation window so clients do
+//! not break the moment a node upgrades. A deprecated method is served by its replacement: its
+//! name is aliased to the replacement when their shapes are the same, and otherwise its params
+//! and result go through an adapter that converts them to and from the shapes of the
+//! replacement.
+//!
+//! When renaming a method or changing its shape, add the old method to [deprecated_methods],
+//! and remove it once the window closes.
+
+use std::sync::Arc;
+
+use jsonrpsee::types::Params;
+use jsonrpsee_core::server::rpc_module::{Methods, RpcModule};
+use jsonrpsee_core::Error;
+use serde_json::Value;
+use sui_json_rpc_types::SuiCommittee;
+use sui_types::messages::CommitteeInfoResponse;
+use tracing::debug;
+
+/// Converts the params and result of a deprecated method to and from those of its replacement.
+pub trait RpcAdapter: Send + Sync + 'static {
+    /// Converts the positional params of the deprecated method into those of the replacement.
+    fn adapt_params(&self, params: Vec<Value>) -> Result<Vec<Value>, Error> {
+        Ok(params)
+    }
+
+    /// Converts the result of the replacement into the result of the deprecated method.
+    fn adapt_result(&self, result: Value) -> Result<Value, Error> {
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+pub struct DeprecatedMethod {
+    /// Full name of the deprecated method, e.g. `sui_getObjectInfo`.
+    pub name: &'static str,
+    /// Full name of the method serving it.
+    pub replacement: &'static str,
+    /// Version of the API in which the deprecated method is removed.
+    pub removed_in: &'static str,
+    /// None if the deprecated method has the same shape as its replacement.
+    pub adapter: Option<Arc<dyn RpcAdapter>>,
+}
+
+impl DeprecatedMethod {
+    pub fn alias(name: &'static str, replacement: &'static str, removed_in: &'static str) -> Self {
+        Self {
+            name,
+            replacement,
+            removed_in,
+            adapter: None,
+        }
+    }
+
+    pub fn adapted(
+        name: &'static str,
+        replacement: &'static str,
+        removed_in: &'static str,
+        adapter: impl RpcAdapter,
+    ) -> Self {
+        Self {
+            name,
+            replacement,
+            removed_in,
+            adapter: Some(Arc::new(adapter)),
+        }
+    }
+}
+
+/// The methods of the previous version of the API still served.
+pub fn deprecated_methods() -> Vec<DeprecatedMethod> {
+    vec![DeprecatedMethod::adapted(
+        "sui_getCommitteeInfo",
+        "sui_getCommittee",
+        "0.14.0",
+        CommitteeInfoAdapter,
+    )]
+}
+
+/// `sui_getCommitteeInfo` returned the stake of each validator, which `sui_getCommittee` returns
+/// along with their network addresses. Unlike `sui_getCommitteeInfo`, which returned no
+/// validators for unknown epochs, `sui_getCommittee` fails for them.
+struct CommitteeInfoAdapter;
+
+impl RpcAdapter for CommitteeInfoAdapter {
+    fn adapt_result(&self, result: Value) -> Result<Value, Error> {
+        let committee: SuiCommittee = serde_json::from_value(result)?;
+        Ok(serde_json::to_value(CommitteeInfoResponse::from(
+            committee,
+        ))?)
+    }
+}
+
+/// Registers `method` in `module`, on top of its replacement. Returns false, without
+/// registering it, if the replacement is not part of `module`, which happens when it belongs to
+/// a module the server does not serve.
+pub fn register_deprecated_method(
+    module: &mut RpcModule<()>,
+    method: DeprecatedMethod,
+) -> Result<bool, Error> {
+    if module.method(method.replacement).is_none() {
+        return Ok(false);
+    }
+    let adapter = match method.adapter {
+        None => {
+            module.register_alias(method.name, method.replacement)?;
+            return Ok(true);
+        }
+        Some(adapter) => adapter,
+    };
+
+    // Calls go through the methods registered so far, which include the replacement.
+    let methods: Methods = module.clone().into();
+    let replacement = method.replacement;
+    let name = method.name;
+    module.register_async_method(name, move |params: Params<'static>, _| {
+        let methods = methods.clone();
+        let adapter = adapter.clone();
+        async move {
+            debug!(method = name, replacement, "Serving deprecated method");
+            let params = match params.parse::<Option<Vec<Value>>>()? {
+                Some(params) => params,
+                None => vec![],
+            };
+            let params = adapter.adapt_params(params)?;
+            let result: Value = methods.call(replacement, params).await?;
+            adapter.adapt_result(result)
+        }
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sui_json_rpc_types::SuiCommitteeMember;
+    use sui_types::base_types::AuthorityName;
+
+    fn module() -> RpcModule<()> {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("sui_getCommittee", |params, _| {
+                let epoch: Option<u64> = params.one()?;
+                Ok(SuiCommittee {
+                    epoch: epoch.unwrap_or(1),
+                    total_stake: 1,
+                    validators: vec![SuiCommitteeMember {
+                        name: AuthorityName::ZERO,
+                        stake: 1,
+                        net_address: Some("/dns/localhost/tcp/8080/http".to_string()),
+                    }],
+                })
+            })
+            .unwrap();
+        module
+    }
+
+    #[tokio::test]
+    async fn test_adapted_method() {
+        let mut module = module();
+        for method in deprecated_methods() {
+            assert!(register_deprecated_method(&mut module, method).unwrap());
+        }
+
+        let response: CommitteeInfoResponse = module
+            .call("sui_getCommitteeInfo", vec![json!(3)])
+            .await
+            .unwrap();
+        assert_eq!(response.epoch, 3);
+        assert_eq!(
+            response.committee_info,
+            Some(vec![(AuthorityName::ZERO, 1)])
+        );
+
+        // The replacement is still served in its own shape.
+        let committee: SuiCommittee = module
+            .call("sui_getCommittee", vec![json!(3)])
+            .await
+            .unwrap();
+        assert_eq!(committee.validators.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_aliased_method() {
+        let mut module = module();
+        let method = DeprecatedMethod::alias("sui_getValidators", "sui_getCommittee", "0.14.0");
+        assert!(register_deprecated_method(&mut module, method).unwrap());
+        let committee: SuiCommittee = module
+            .call("sui_getValidators", vec![json!(2)])
+            .await
+            .unwrap();
+        assert_eq!(committee.epoch, 2);
+
+        // Methods replaced by methods of modules the server does not serve are not registered.
+        let method = DeprecatedMethod::alias("sui_getFoo", "sui_getBar", "0.14.0");
+        assert!(!register_deprecated_method(&mut module, method).unwrap());
+        assert!(module.method("sui_getFoo").is_none());
+    }
+}
@@ -24,9 +24,12 @@ use sui_verifier::entry_points_verifier::{
 
 const HEX_PREFIX: &str = "0x";
 
+mod schema;
 #[cfg(test)]
 mod tests;
 
+pub use schema::entry_function_args_schema;
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub enum SuiJsonCallArg {
     // Needs to become an Object Ref or Object ID, depending on object type
@@ -67,7 +70,12 @@ impl SuiJsonValue {
     }
 
     pub fn to_bcs_bytes(&self, ty: &MoveTypeLayout) -> Result<Vec<u8>, anyhow::Error> {
-        let move_value = Self::to_move_value(&self.0, ty)?;
+        self.to_bcs_bytes_at("", ty)
+    }
+
+    /// Serializes the value as type `ty`, reporting the value as found at `path` in errors.
+    fn to_bcs_bytes_at(&self, path: &str, ty: &MoveTypeLayout) -> Result<Vec<u8>, anyhow::Error> {
+        let move_value = Self::to_move_value(&self.0, ty, path)?;
         MoveValue::simple_serialize(&move_value)
             .ok_or_else(|| anyhow!("Unable to serialize {:?}. Expected {}", move_value, ty))
     }
@@ -84,64 +92,65 @@ impl SuiJsonValue {
         inner_vec: &[MoveTypeLayout],
         val: &JsonValue,
         ty: &MoveTypeLayout,
-        s: &String,
-    ) -> Result<MoveValue, anyhow::Error> {
+        path: &str,
+    ) -> Result<MoveValue, SuiJsonCoercionError> {
         // delegate MoveValue construction to the case when JsonValue::String and
         // MoveTypeLayout::Vector are handled to get an address (with 0x string
         // prefix) or a vector of u8s (no prefix)
         debug_assert!(matches!(val, JsonValue::String(_)));
 
-        if inner_vec.len() != 1 {
-            bail!(
-                "Cannot convert string arg {s} to {ty} which is expected \
-                 to be a struct with one field"
-            );
-        }
-
-        match &inner_vec[0] {
-            MoveTypeLayout::Vector(inner) => match **inner {
-                MoveTypeLayout::U8 | MoveTypeLayout::Address => {
-                    Ok(MoveValue::Struct(MoveStruct::Runtime(vec![
-                        Self::to_move_value(val, &inner_vec[0].clone())?,
-                    ])))
-                }
-                _ => bail!(
-                    "Cannot convert string arg {s} to {ty} \
-                             which is expected to be a struct \
-                             with one field of address or u8 vector type"
-                ),
-            },
-            _ => bail!(
-                "Cannot convert string arg {s} to {ty} which is expected \
-                 to be a struct with one field of a vector type"
-            ),
+        // Only structs with one field of address or u8 vector type (IDs and strings) can be
+        // converted from a string
+        match inner_vec {
+            [field @ MoveTypeLayout::Vector(inner)]
+                if matches!(**inner, MoveTypeLayout::U8 | MoveTypeLayout::Address) =>
+            {
+                let field = Self::to_move_value(val, field, path)
+                    .map_err(|_| SuiJsonCoercionError::new(path, ty, val))?;
+                Ok(MoveValue::Struct(MoveStruct::Runtime(vec![field])))
+            }
+            _ => Err(SuiJsonCoercionError::new(path, ty, val)),
         }
     }
 
-    fn to_move_value(val: &JsonValue, ty: &MoveTypeLayout) -> Result<MoveValue, anyhow::Error> {
+    fn to_move_value(
+        val: &JsonValue,
+        ty: &MoveTypeLayout,
+        path: &str,
+    ) -> Result<MoveValue, SuiJsonCoercionError> {
+        let mismatch = || SuiJsonCoercionError::new(path, ty, val);
         Ok(match (val, ty) {
             // Bool to Bool is simple
             (JsonValue::Bool(b), MoveTypeLayout::Bool) => MoveValue::Bool(*b),
 
-            // In constructor, we have already checked that the JSON number is unsigned int of at most U64
-            // Hence it is okay to unwrap() numbers
-            (JsonValue::Number(n), MoveTypeLayout::U8) => {
-                MoveValue::U8(u8::try_from(n.as_u64().unwrap())?)
+            // Numbers are not checked when deserialized, they may be negative or floats
+            (JsonValue::Number(n), MoveTypeLayout::U8) => MoveValue::U8(
+                n.as_u64()
+                    .and_then(|n| u8::try_from(n).ok())
+                    .ok_or_else(mismatch)?,
+            ),
+            (JsonValue::Number(n), MoveTypeLayout::U64) => {
+                MoveValue::U64(n.as_u64().ok_or_else(mismatch)?)
             }
-            (JsonValue::Number(n), MoveTypeLayout::U64) => MoveValue::U64(n.as_u64().unwrap()),
 
             // u8, u64, u128 can be encoded as String
-            (JsonValue::String(s), MoveTypeLayout::U8) => {
-                MoveValue::U8(u8::try_from(convert_string_to_u128(s.as_str())?)?)
-            }
-            (JsonValue::String(s), MoveTypeLayout::U64) => {
-                MoveValue::U64(u64::try_from(convert_string_to_u128(s.as_str())?)?)
-            }
+            (JsonValue::String(s), MoveTypeLayout::U8) => MoveValue::U8(
+                convert_string_to_u128(s)
+                    .ok()
+                    .and_then(|n| u8::try_from(n).ok())
+                    .ok_or_else(mismatch)?,
+            ),
+            (JsonValue::String(s), MoveTypeLayout::U64) => MoveValue::U64(
+                convert_string_to_u128(s)
+                    .ok()
+                    .and_then(|n| u64::try_from(n).ok())
+                    .ok_or_else(mismatch)?,
+            ),
             (JsonValue::String(s), MoveTypeLayout::U128) => {
-                MoveValue::U128(convert_string_to_u128(s.as_str())?)
+                MoveValue::U128(convert_string_to_u128(s).map_err(|_| mismatch())?)
             }
-            (JsonValue::String(s), MoveTypeLayout::Struct(MoveStructLayout::Runtime(inner))) => {
-                Self::handle_inner_struct_layout(inner, val, ty, s)?
+            (JsonValue::String(_), MoveTypeLayout::Struct(MoveStructLayout::Runtime(inner))) => {
+                Self::handle_inner_struct_layout(inner, val, ty, path)?
             }
             (JsonValue::String(s), MoveTypeLayout::Vector(t)) => {
                 match &**t {
@@ -159,7 +168,7 @@ impl SuiJsonValue {
                         // Move call
                         let vec = if s.starts_with(HEX_PREFIX) {
                             // If starts with 0x, treat as hex vector
-                            hex::decode(s.trim_start_matches(HEX_PREFIX))?
+                            hex::decode(s.trim_start_matches(HEX_PREFIX)).map_err(|_| mismatch())?
                         } else {
                             // Else raw bytes
                             s.as_bytes().to_vec()
@@ -167,18 +176,18 @@ impl SuiJsonValue {
                         MoveValue::Vector(vec.iter().copied().map(MoveValue::U8).collect())
                     }
                     MoveTypeLayout::Struct(MoveStructLayout::Runtime(inner)) => {
-                        Self::handle_inner_struct_layout(inner, val, ty, s)?
+                        Self::handle_inner_struct_layout(inner, val, ty, path)?
                     }
-                    _ => bail!("Cannot convert string arg {s} to {ty}"),
+                    _ => return Err(mismatch()),
                 }
             }
 
-            // We have already checked that the array is homogeneous in the constructor
             (JsonValue::Array(a), MoveTypeLayout::Vector(inner)) => {
                 // Recursively build an IntermediateValue array
                 MoveValue::Vector(
                     a.iter()
-                        .map(|i| Self::to_move_value(i, inner))
+                        .enumerate()
+                        .map(|(i, v)| Self::to_move_value(v, inner, &format!("{path}[{i}]")))
                         .collect::<Result<Vec<_>, _>>()?,
                 )
             }
@@ -186,16 +195,92 @@ impl SuiJsonValue {
             (JsonValue::String(s), MoveTypeLayout::Address) => {
                 let s = s.trim().to_lowercase();
                 if !s.starts_with(HEX_PREFIX) {
-                    bail!("Address hex string must start with 0x.",);
+                    return Err(mismatch());
                 }
-                let r: SuiAddress = decode_bytes_hex(&s)?;
+                let r: SuiAddress = decode_bytes_hex(&s).map_err(|_| mismatch())?;
                 MoveValue::Address(r.into())
             }
-            _ => bail!("Unexpected arg {val} for expected type {ty}"),
+            _ => return Err(mismatch()),
         })
     }
 }
 
+/// A JSON value which cannot be converted to the Move type expected at `path`, e.g.
+/// `arg[2][0] expected u64 integer or string, got float 1.5`.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct SuiJsonCoercionError {
+    /// The position of the value in the arguments, e.g. `arg[2][0]` for the first element of the
+    /// vector passed as third argument. Empty for a value converted on its own.
+    pub path: String,
+    /// The JSON the Move type can be converted from.
+    pub expected: String,
+    /// The kind of JSON value found, and the value itself if it is not an array or an object.
+    pub got: String,
+}
+
+impl SuiJsonCoercionError {
+    fn new(path: &str, ty: &MoveTypeLayout, val: &JsonValue) -> Self {
+        Self::with_expected(path, expected_json(ty), val)
+    }
+
+    fn with_expected(path: &str, expected: impl Into<String>, val: &JsonValue) -> Self {
+        Self {
+            path: path.to_string(),
+            expected: expected.into(),
+            got: describe_json(val),
+        }
+    }
+}
+
+impl std::fmt::Display for SuiJsonCoercionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{} ", self.path)?;
+        }
+        write!(f, "expected {}, got {}", self.expected, self.got)
+    }
+}
+
+impl std::error::Error for SuiJsonCoercionError {}
+
+/// The JSON a value of type `ty` can be converted from, as described in coercion errors.
+fn expected_json(ty: &MoveTypeLayout) -> String {
+    match ty {
+        MoveTypeLayout::Bool => "bool".to_string(),
+        MoveTypeLayout::U8 | MoveTypeLayout::U64 => format!("{ty} integer or string"),
+        MoveTypeLayout::U128 => "u128 string".to_string(),
+        MoveTypeLayout::Address => "address hex string".to_string(),
+        MoveTypeLayout::Vector(inner) if **inner == MoveTypeLayout::U8 => {
+            "vector<u8> array, hex string or string".to_string()
+        }
+        MoveTypeLayout::Vector(_) => format!("{ty} array"),
+        MoveTypeLayout::Struct(MoveStructLayout::Runtime(fields)) => match fields.as_slice() {
+            [MoveTypeLayout::Vector(inner)] if **inner == MoveTypeLayout::U8 => {
+                "string".to_string()
+            }
+            [MoveTypeLayout::Vector(inner)] if **inner == MoveTypeLayout::Address => {
+                "ID hex string".to_string()
+            }
+            _ => ty.to_string(),
+        },
+        _ => ty.to_string(),
+    }
+}
+
+/// Describes `val` in coercion errors, e.g. `float 1.5` or `array of 3 elements`.
+fn describe_json(val: &JsonValue) -> String {
+    match val {
+        JsonValue::Null => "null".to_string(),
+        JsonValue::Bool(b) => format!("bool {b}"),
+        JsonValue::Number(n) if n.is_u64() => format!("integer {n}"),
+        JsonValue::Number(n) if n.is_i64() => format!("negative integer {n}"),
+        JsonValue::Number(n) => format!("float {n}"),
+        JsonValue::String(s) => format!("string {s:?}"),
+        JsonValue::Array(a) => format!("array of {} elements", a.len()),
+        JsonValue::Object(_) => "object".to_string(),
+    }
+}
+
 impl Debug for SuiJsonValue {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -397,43 +482,42 @@ pub fn primitive_type(
     }
 }
 
-fn resolve_object_arg(idx: usize, arg: &JsonValue) -> Result<ObjectID, anyhow::Error> {
+/// The path of the argument at position `idx` in coercion errors.
+fn arg_path(idx: usize) -> String {
+    format!("arg[{idx}]")
+}
+
+fn resolve_object_arg(path: &str, arg: &JsonValue) -> Result<ObjectID, SuiJsonCoercionError> {
     // Every elem has to be a string convertible to a ObjectID
+    let invalid = || SuiJsonCoercionError::with_expected(path, "object ID hex string", arg);
     match arg {
         JsonValue::String(s) => {
             let s = s.trim().to_lowercase();
             if !s.starts_with(HEX_PREFIX) {
-                bail!("ObjectID hex string must start with 0x.",);
+                return Err(invalid());
             }
-            Ok(ObjectID::from_hex_literal(&s)?)
+            ObjectID::from_hex_literal(&s).map_err(|_| invalid())
         }
-        _ => bail!(
-            "Unable to parse arg {:?} as ObjectID at pos {}. Expected {:?}-byte hex string \
-                prefixed with 0x.",
-            arg,
-            idx,
-            ObjectID::LENGTH,
-        ),
+        _ => Err(invalid()),
     }
 }
 
-fn resolve_object_vec_arg(idx: usize, arg: &SuiJsonValue) -> Result<Vec<ObjectID>, anyhow::Error> {
+fn resolve_object_vec_arg(
+    path: &str,
+    arg: &SuiJsonValue,
+) -> Result<Vec<ObjectID>, SuiJsonCoercionError> {
     // Every elem has to be a string convertible to a ObjectID
-    match arg.to_json_value() {
-        JsonValue::Array(a) => {
-            let mut object_ids = vec![];
-            for id in a {
-                object_ids.push(resolve_object_arg(idx, &id)?);
-            }
-            Ok(object_ids)
-        }
-        _ => bail!(
-            "Unable to parse arg {:?} as vector of ObjectIDs at pos {}. \
-             Expected a vector of {:?}-byte hex strings prefixed with 0x.",
-            arg.to_json_value(),
-            idx,
-            ObjectID::LENGTH,
-        ),
+    match &arg.0 {
+        JsonValue::Array(a) => a
+            .iter()
+            .enumerate()
+            .map(|(i, id)| resolve_object_arg(&format!("{path}[{i}]"), id))
+            .collect(),
+        _ => Err(SuiJsonCoercionError::with_expected(
+            path,
+            "array of object ID hex strings",
+            &arg.0,
+        )),
     }
 }
 
@@ -444,33 +528,18 @@ fn resolve_call_arg(
     arg: &SuiJsonValue,
     param: &SignatureToken,
 ) -> Result<SuiJsonCallArg, anyhow::Error> {
+    let path = arg_path(idx);
     let (is_primitive, layout_opt) = primitive_type(view, type_args, param);
     if is_primitive {
         match layout_opt {
-            Some(layout) => {
-                return Ok(SuiJsonCallArg::Pure(arg.to_bcs_bytes(&layout).map_err(
-                    |e| {
-                        anyhow!(
-                        "Could not serialize argument of type {:?} at {} into {}. Got error: {:?}",
-                        param,
-                        idx,
-                        layout,
-                        e
-                    )
-                    },
-                )?))
-            }
+            Some(layout) => return Ok(SuiJsonCallArg::Pure(arg.to_bcs_bytes_at(&path, &layout)?)),
             None => {
                 debug_assert!(
                     false,
                     "Should be unreachable. All primitive type function args \
                      should have a corresponding MoveLayout"
                 );
-                bail!(
-                    "Could not serialize argument of type {:?} at {}",
-                    param,
-                    idx
-                );
+                bail!("Could not serialize {path} of type {:?}", param);
             }
         }
     }
@@ -482,27 +551,24 @@ fn resolve_call_arg(
         | SignatureToken::StructInstantiation(_, _)
         | SignatureToken::TypeParameter(_)
         | SignatureToken::Reference(_)
-        | SignatureToken::MutableReference(_) => Ok(SuiJsonCallArg::Object(resolve_object_arg(
-            idx,
-            &arg.to_json_value(),
-        )?)),
+        | SignatureToken::MutableReference(_) => {
+            Ok(SuiJsonCallArg::Object(resolve_object_arg(&path, &arg.0)?))
+        }
         SignatureToken::Vector(inner) => match &**inner {
             SignatureToken::Struct(_) | SignatureToken::StructInstantiation(_, _) => {
-                Ok(SuiJsonCallArg::ObjVec(resolve_object_vec_arg(idx, arg)?))
+                Ok(SuiJsonCallArg::ObjVec(resolve_object_vec_arg(&path, arg)?))
             }
             _ => {
                 bail!(
-                    "Unexpected non-primitive vector arg {:?} at {} with value {:?}",
+                    "Unexpected non-primitive vector {path} of type {:?} with value {:?}",
                     param,
-                    idx,
                     arg
                 );
             }
         },
         _ => bail!(
-            "Unexpected non-primitive arg {:?} at {} with value {:?}",
+            "Unexpected non-primitive {path} of type {:?} with value {:?}",
             param,
-            idx,
             arg
         ),
     }
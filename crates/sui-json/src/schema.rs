@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Schemas of the arguments of entry functions, derived from their normalized signatures.
+//! A schema describes the JSON `resolve_move_function_args` converts to each parameter, so
+//! clients can validate and build arguments before submitting a transaction.
+
+use anyhow::bail;
+use move_binary_format::normalized::{Function, Type};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use serde_json::{json, Value as JsonValue};
+use sui_types::base_types::{
+    ObjectID, SUI_ADDRESS_LENGTH, TX_CONTEXT_MODULE_NAME, TX_CONTEXT_STRUCT_NAME,
+};
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+use sui_verifier::entry_points_verifier::{
+    RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_SUI_ID, RESOLVED_UTF8_STR,
+};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Unsigned integers encoded as strings, in decimal or in hex prefixed with 0x.
+const UNSIGNED_INTEGER_PATTERN: &str = "^([0-9]+|0x[0-9a-fA-F]+)$";
+
+/// The schema of the array of arguments of `function`, without the `TxContext` the runtime
+/// passes.
+pub fn entry_function_args_schema(function: &Function) -> Result<JsonValue, anyhow::Error> {
+    if !function.is_entry {
+        bail!("Only entry functions can be called with JSON arguments");
+    }
+    let parameters = match function.parameters.split_last() {
+        Some((last, parameters)) if is_tx_context(last) => parameters,
+        _ => &function.parameters[..],
+    };
+    Ok(json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "type": "array",
+        "prefixItems": parameters.iter().map(parameter_schema).collect::<Vec<_>>(),
+        "items": false,
+        "minItems": parameters.len(),
+        "maxItems": parameters.len(),
+    }))
+}
+
+fn parameter_schema(ty: &Type) -> JsonValue {
+    if let Some(schema) = pure_schema(ty) {
+        return schema;
+    }
+    // Mirrors `resolve_call_arg`: other arguments are objects, or vectors of objects
+    match ty {
+        Type::Struct {
+            address,
+            module,
+            name,
+            ..
+        } if (address, module.as_ident_str(), name.as_ident_str()) == RESOLVED_STD_OPTION => {
+            unsupported_schema("Option arguments are not supported")
+        }
+        Type::Struct { .. }
+        | Type::TypeParameter(_)
+        | Type::Reference(_)
+        | Type::MutableReference(_) => object_schema(ty),
+        Type::Vector(inner) if matches!(**inner, Type::Struct { .. }) => json!({
+            "type": "array",
+            "items": object_schema(inner),
+        }),
+        _ => unsupported_schema("Arguments of this type are not supported"),
+    }
+}
+
+/// The schema of a pure argument of type `ty`, or `None` if `ty` cannot be passed by value.
+fn pure_schema(ty: &Type) -> Option<JsonValue> {
+    Some(match ty {
+        Type::Bool => json!({ "type": "boolean" }),
+        Type::U8 => unsigned_integer_schema("u8", u8::MAX as u64),
+        Type::U64 => unsigned_integer_schema("u64", u64::MAX),
+        Type::U128 => json!({
+            "description": "u128",
+            "type": "string",
+            "pattern": UNSIGNED_INTEGER_PATTERN,
+        }),
+        Type::Address => json!({
+            "description": "address",
+            "type": "string",
+            "pattern": format!("^0x[0-9a-fA-F]{{{}}}$", SUI_ADDRESS_LENGTH * 2),
+        }),
+        Type::Vector(inner) if **inner == Type::U8 => json!({
+            "description": "vector<u8>, as an array, a hex string prefixed with 0x or a string",
+            "anyOf": [
+                { "type": "array", "items": pure_schema(inner)? },
+                { "type": "string" },
+            ],
+        }),
+        Type::Vector(inner) => json!({
+            "type": "array",
+            "items": pure_schema(inner)?,
+        }),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } if type_arguments.is_empty() => {
+            let resolved = (address, module.as_ident_str(), name.as_ident_str());
+            if resolved == RESOLVED_ASCII_STR || resolved == RESOLVED_UTF8_STR {
+                json!({
+                    "description": struct_name(address, module, name),
+                    "type": "string",
+                })
+            } else if resolved == RESOLVED_SUI_ID {
+                json!({
+                    "description": struct_name(address, module, name),
+                    "type": "string",
+                    "pattern": object_id_pattern(),
+                })
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn unsigned_integer_schema(name: &str, max: u64) -> JsonValue {
+    json!({
+        "description": name,
+        "anyOf": [
+            { "type": "integer", "minimum": 0, "maximum": max },
+            { "type": "string", "pattern": UNSIGNED_INTEGER_PATTERN },
+        ],
+    })
+}
+
+fn object_schema(ty: &Type) -> JsonValue {
+    let description = match ty {
+        Type::Reference(inner) | Type::MutableReference(inner) => return object_schema(inner),
+        Type::Struct {
+            address,
+            module,
+            name,
+            ..
+        } => format!(
+            "ID of an object of type {}",
+            struct_name(address, module, name)
+        ),
+        _ => "ID of an object".to_string(),
+    };
+    json!({
+        "description": description,
+        "type": "string",
+        "pattern": object_id_pattern(),
+    })
+}
+
+/// The schema no value matches, for parameters which cannot be passed as JSON.
+fn unsupported_schema(reason: &str) -> JsonValue {
+    json!({
+        "description": reason,
+        "not": {},
+    })
+}
+
+fn object_id_pattern() -> String {
+    format!("^0x[0-9a-fA-F]{{1,{}}}$", ObjectID::LENGTH * 2)
+}
+
+fn struct_name(address: &AccountAddress, module: &Identifier, name: &Identifier) -> String {
+    format!("{}::{module}::{name}", address.to_hex_literal())
+}
+
+fn is_tx_context(ty: &Type) -> bool {
+    match ty {
+        Type::MutableReference(inner) => matches!(
+            &**inner,
+            Type::Struct { address, module, name, .. }
+                if address == &SUI_FRAMEWORK_ADDRESS
+                    && module.as_ident_str() == TX_CONTEXT_MODULE_NAME
+                    && name.as_ident_str() == TX_CONTEXT_STRUCT_NAME
+        ),
+        _ => false,
+    }
+}
@@ -4,6 +4,10 @@
 use std::path::Path;
 use std::str::FromStr;
 
+use move_binary_format::{
+    file_format::Visibility,
+    normalized::{Function, Type},
+};
 use move_core_types::{
     account_address::AccountAddress, identifier::Identifier, value::MoveTypeLayout,
 };
@@ -12,9 +16,13 @@ use test_fuzz::runtime::num_traits::ToPrimitive;
 
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::object::Object;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
 
+use super::{
+    entry_function_args_schema, resolve_move_function_args, SuiJsonCallArg, SuiJsonCoercionError,
+    SuiJsonValue,
+};
 use super::{is_homogeneous, HEX_PREFIX};
-use super::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 
 #[test]
 fn test_json_is_homogeneous() {
@@ -591,3 +599,116 @@ fn test_from_str() {
     assert!(test.0.is_string());
     assert_eq!("Some string", test.0.as_str().unwrap())
 }
+
+#[test]
+fn test_coercion_errors_report_path() {
+    let checks = vec![
+        (
+            json!(1.5),
+            MoveTypeLayout::U64,
+            "expected u64 integer or string, got float 1.5",
+        ),
+        (
+            json!([[1, 2], [3, 300]]),
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Vector(Box::new(
+                MoveTypeLayout::U8,
+            )))),
+            "[1][1] expected u8 integer or string, got integer 300",
+        ),
+        (
+            json!("12a"),
+            MoveTypeLayout::U128,
+            "expected u128 string, got string \"12a\"",
+        ),
+        (
+            json!(true),
+            MoveTypeLayout::Address,
+            "expected address hex string, got bool true",
+        ),
+    ];
+
+    for (arg, layout, expected_error) in checks {
+        // Deserialized values are not checked by the constructor
+        let value: SuiJsonValue = serde_json::from_value(arg).unwrap();
+        let err = value.to_bcs_bytes(&layout).unwrap_err();
+        assert_eq!(err.to_string(), expected_error);
+    }
+}
+
+#[test]
+fn test_resolve_args_errors_report_path() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../sui-core/src/unit_tests/data/entry_point_vector");
+    let compiled_modules =
+        sui_framework::build_and_verify_package(&path, move_package::BuildConfig::default())
+            .unwrap();
+    let example_package = Object::new_package(compiled_modules, TransactionDigest::genesis());
+    let example_package = example_package.data.try_as_package().unwrap();
+
+    let module = Identifier::new("entry_point_vector").unwrap();
+    let function = Identifier::new("two_obj_vec_destroy").unwrap();
+
+    /*
+    Function signature:
+            public entry fun two_obj_vec_destroy(v: vector<Obj>, _: &mut TxContext)
+     */
+    let object_id = json!(format!("0x{:02x}", ObjectID::random()));
+    let args = vec![SuiJsonValue::new(json!([object_id, "not an id"])).unwrap()];
+
+    let err = resolve_move_function_args(example_package, module, function, &[], args)
+        .unwrap_err()
+        .downcast::<SuiJsonCoercionError>()
+        .unwrap();
+    assert_eq!(err.path, "arg[0][1]");
+    assert_eq!(
+        err.to_string(),
+        "arg[0][1] expected object ID hex string, got string \"not an id\""
+    );
+}
+
+#[test]
+fn test_entry_function_args_schema() {
+    let tx_context = Type::MutableReference(Box::new(Type::Struct {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: Identifier::new("tx_context").unwrap(),
+        name: Identifier::new("TxContext").unwrap(),
+        type_arguments: vec![],
+    }));
+    let object = Type::Struct {
+        address: AccountAddress::from_hex_literal("0x42").unwrap(),
+        module: Identifier::new("m").unwrap(),
+        name: Identifier::new("Obj").unwrap(),
+        type_arguments: vec![],
+    };
+    let function = Function {
+        visibility: Visibility::Public,
+        is_entry: true,
+        type_parameters: vec![],
+        parameters: vec![
+            Type::U64,
+            Type::Vector(Box::new(Type::Address)),
+            Type::Reference(Box::new(object)),
+            tx_context,
+        ],
+        return_: vec![],
+    };
+
+    let schema = entry_function_args_schema(&function).unwrap();
+    // The TxContext is not an argument
+    assert_eq!(schema["minItems"], json!(3));
+    assert_eq!(schema["maxItems"], json!(3));
+    let items = schema["prefixItems"].as_array().unwrap();
+    assert_eq!(items[0]["description"], json!("u64"));
+    assert_eq!(items[1]["type"], json!("array"));
+    assert_eq!(items[1]["items"]["description"], json!("address"));
+    assert_eq!(
+        items[2]["description"],
+        json!("ID of an object of type 0x42::m::Obj")
+    );
+
+    let function = Function {
+        is_entry: false,
+        ..function
+    };
+    assert!(entry_function_args_schema(&function).is_err());
+}
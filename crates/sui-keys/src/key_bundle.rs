@@ -0,0 +1,211 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, ensure};
+use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PrivateKey};
+use fastcrypto::secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
+use hmac::Hmac;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
+use sui_types::sui_serde::{Base64, Encoding};
+
+const BUNDLE_VERSION: u8 = 1;
+/// PBKDF2 rounds deriving the encryption key of new bundles from their passphrase.
+const KDF_ROUNDS: u32 = 100_000;
+/// Most PBKDF2 rounds of the bundles opened, which are read from untrusted files.
+const MAX_KDF_ROUNDS: u32 = 10 * KDF_ROUNDS;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Keys of a keystore encrypted with a passphrase, to move them to another keystore without
+/// copying plaintext key files. The addresses and schemes of the keys can be listed without the
+/// passphrase, and cannot be altered without failing decryption.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct EncryptedKeyBundle {
+    version: u8,
+    pub keys: Vec<KeyBundleEntry>,
+    kdf_rounds: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KeyBundleEntry {
+    pub address: SuiAddress,
+    pub scheme: SignatureScheme,
+}
+
+impl EncryptedKeyBundle {
+    /// Encrypts `keys` with a key derived from `passphrase`.
+    pub fn seal(keys: &[&SuiKeyPair], passphrase: &str) -> Result<Self, anyhow::Error> {
+        ensure!(!keys.is_empty(), "Cannot export an empty key bundle");
+        ensure!(!passphrase.is_empty(), "The passphrase cannot be empty");
+
+        let entries = keys
+            .iter()
+            .map(|key| {
+                let public = key.public();
+                KeyBundleEntry {
+                    address: (&public).into(),
+                    scheme: public.scheme(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let encoded_keys = Zeroizing::new(
+            keys.iter()
+                .map(|key| key.encode_base64())
+                .collect::<Vec<_>>(),
+        );
+        let plaintext = Zeroizing::new(serde_json::to_vec(&*encoded_keys)?);
+
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let cipher = cipher(passphrase, &salt, KDF_ROUNDS)?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &associated_data(&entries),
+                },
+            )
+            .map_err(|_| anyhow!("Failed to encrypt the key bundle"))?;
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            keys: entries,
+            kdf_rounds: KDF_ROUNDS,
+            salt: Base64::encode(salt),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the keys of the bundle, checking that they derive the addresses, and have the
+    /// schemes, the bundle lists.
+    pub fn open(&self, passphrase: &str) -> Result<Vec<SuiKeyPair>, anyhow::Error> {
+        if self.version != BUNDLE_VERSION {
+            bail!("Unsupported key bundle version {}", self.version);
+        }
+        ensure!(
+            (1..=MAX_KDF_ROUNDS).contains(&self.kdf_rounds),
+            "Invalid key bundle KDF rounds {}, at most {MAX_KDF_ROUNDS} are supported",
+            self.kdf_rounds
+        );
+        let salt = Base64::decode(&self.salt)?;
+        let nonce = Base64::decode(&self.nonce)?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid key bundle nonce");
+        let ciphertext = Base64::decode(&self.ciphertext)?;
+
+        let plaintext = cipher(passphrase, &salt, self.kdf_rounds)?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &associated_data(&self.keys),
+                },
+            )
+            .map(Zeroizing::new)
+            .map_err(|_| anyhow!("Wrong passphrase, or the key bundle was altered"))?;
+        let encoded_keys = Zeroizing::new(serde_json::from_slice::<Vec<String>>(&plaintext)?);
+        let keys = encoded_keys
+            .iter()
+            .map(|key| SuiKeyPair::decode_base64(key).map_err(|e| anyhow!(e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        ensure!(
+            keys.len() == self.keys.len(),
+            "The key bundle lists {} keys but contains {}",
+            self.keys.len(),
+            keys.len()
+        );
+        for (key, entry) in keys.iter().zip(&self.keys) {
+            let public = key.public();
+            let address = SuiAddress::from(&public);
+            ensure!(
+                address == entry.address,
+                "Key listed for address {} derives address {address}",
+                entry.address
+            );
+            ensure!(
+                public.flag() == entry.scheme.flag(),
+                "Key of address {address} is listed as a {} key but is a {} key",
+                entry.scheme,
+                public.scheme()
+            );
+        }
+        Ok(keys)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let bundle = fs::read(path)?;
+        serde_json::from_slice(&bundle)
+            .map_err(|e| anyhow!("Invalid key bundle {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Converts `key` into a key of `scheme` with the same private key bytes, e.g. to move an
+/// account to a scheme its new keystore or signer supports. A converted key derives another
+/// address than `key`, unless `key` already is of `scheme`.
+pub fn convert_key_scheme(
+    key: SuiKeyPair,
+    scheme: &SignatureScheme,
+) -> Result<SuiKeyPair, anyhow::Error> {
+    if key.public().flag() == scheme.flag() {
+        return Ok(key);
+    }
+    let private_key = Zeroizing::new(match &key {
+        SuiKeyPair::Ed25519SuiKeyPair(kp) => kp.copy().private().as_ref().to_vec(),
+        SuiKeyPair::Secp256k1SuiKeyPair(kp) => kp.copy().private().as_ref().to_vec(),
+    });
+    Ok(match scheme {
+        SignatureScheme::ED25519 => {
+            let sk = Ed25519PrivateKey::from_bytes(&private_key).map_err(|e| anyhow!(e))?;
+            SuiKeyPair::Ed25519SuiKeyPair(Ed25519KeyPair::from(sk))
+        }
+        SignatureScheme::Secp256k1 => {
+            let sk = Secp256k1PrivateKey::from_bytes(&private_key)
+                .map_err(|e| anyhow!("Key cannot be converted to a {scheme} key: {e}"))?;
+            SuiKeyPair::Secp256k1SuiKeyPair(Secp256k1KeyPair::from(sk))
+        }
+        SignatureScheme::BLS12381 => bail!("Keys cannot be converted to {scheme} keys"),
+    })
+}
+
+fn cipher(passphrase: &str, salt: &[u8], rounds: u32) -> Result<Aes256Gcm, anyhow::Error> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, rounds, &mut *key);
+    Aes256Gcm::new_from_slice(&*key).map_err(|_| anyhow!("Invalid key bundle encryption key"))
+}
+
+/// The listing of the keys, authenticated along with the encrypted keys.
+fn associated_data(entries: &[KeyBundleEntry]) -> Vec<u8> {
+    entries
+        .iter()
+        .flat_map(|entry| {
+            let mut bytes = entry.address.as_ref().to_vec();
+            bytes.push(entry.scheme.flag());
+            bytes
+        })
+        .collect()
+}
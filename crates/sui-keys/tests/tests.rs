@@ -1,12 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use std::fs;
 use std::str::FromStr;
 
 use sha3::{Digest, Sha3_256};
 use tempfile::TempDir;
 
+use sui_keys::key_bundle::{convert_key_scheme, EncryptedKeyBundle};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_types::crypto::{get_key_pair, SignatureScheme, SuiKeyPair, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -68,3 +70,55 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn key_bundle_test() {
+    let keys = vec![
+        SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1),
+        SuiKeyPair::Secp256k1SuiKeyPair(get_key_pair().1),
+    ];
+    let bundle = EncryptedKeyBundle::seal(&keys.iter().collect::<Vec<_>>(), "passphrase").unwrap();
+    assert!(EncryptedKeyBundle::seal(&[], "passphrase").is_err());
+    assert!(EncryptedKeyBundle::seal(&[&keys[0]], "").is_err());
+
+    let opened = bundle.open("passphrase").unwrap();
+    assert_eq!(opened.len(), 2);
+    for (key, opened) in keys.iter().zip(&opened) {
+        assert!(key.public() == opened.public());
+    }
+    assert!(bundle.open("another passphrase").is_err());
+
+    // The listing of the keys cannot be changed without the passphrase.
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("keys.bundle");
+    bundle.save(&path).unwrap();
+    let mut json: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    json["keys"][0]["address"] = serde_json::json!(SuiAddress::random_for_testing_only());
+    fs::write(&path, json.to_string()).unwrap();
+    let tampered = EncryptedKeyBundle::load(&path).unwrap();
+    assert!(tampered.open("passphrase").is_err());
+
+    // Bundles asking for more KDF rounds than supported are rejected before deriving any key.
+    bundle.save(&path).unwrap();
+    let mut json: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    json["kdf-rounds"] = serde_json::json!(u32::MAX);
+    fs::write(&path, json.to_string()).unwrap();
+    let tampered = EncryptedKeyBundle::load(&path).unwrap();
+    assert!(tampered.open("passphrase").is_err());
+}
+
+#[test]
+fn convert_key_scheme_test() {
+    let key = SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1);
+    let address = SuiAddress::from(&key.public());
+    let converted = convert_key_scheme(key, &SignatureScheme::Secp256k1).unwrap();
+    assert_eq!(converted.public().flag(), SignatureScheme::Secp256k1.flag());
+    assert!(SuiAddress::from(&converted.public()) != address);
+
+    // Keys of the requested scheme are kept as they are.
+    let public = converted.public();
+    let kept = convert_key_scheme(converted, &SignatureScheme::Secp256k1).unwrap();
+    assert!(kept.public() == public);
+
+    assert!(convert_key_scheme(kept, &SignatureScheme::BLS12381).is_err());
+}
@@ -4,6 +4,9 @@
 use proc_macro::TokenStream;
 use quote::quote;
 
+/// Sui addresses are 20 bytes, so hex literals of packages have at most 40 digits.
+const MAX_ADDRESS_HEX_DIGITS: usize = 40;
+
 #[proc_macro_attribute]
 pub fn init_static_initializers(_args: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = syn::parse_macro_input!(item as syn::ItemFn);
@@ -135,3 +138,175 @@ pub fn sim_test(args: TokenStream, item: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Derives `sui_sdk::events::TypedMoveEvent` for a struct mirroring a Move event struct, so that
+/// emitted events of that type can be decoded from their BCS contents. The struct must also
+/// derive `serde::Deserialize`, with its fields in the order of the Move struct.
+///
+/// The Move type is given by the `move_event` attribute:
+///
+/// ```ignore
+/// #[derive(Deserialize, TypedMoveEvent)]
+/// #[move_event(module = "devnet_nft", name = "MintNFTEvent", package = "0x2")]
+/// struct MintNftEvent { ... }
+/// ```
+///
+/// `name` defaults to the name of the Rust struct. `package` is optional, as the ID of a package
+/// is only known once it is published.
+#[proc_macro_derive(TypedMoveEvent, attributes(move_event))]
+pub fn derive_typed_move_event(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    let trait_path = quote! { ::sui_sdk::events::TypedMoveEvent };
+    match typed_move_type(&input, "move_event", trait_path, quote! {}) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derives `sui_sdk::objects::TypedMoveObject` for a struct mirroring a Move object struct, so
+/// that objects of that type can be decoded from their BCS contents. The struct must also derive
+/// `serde::Deserialize`, with the fields of the Move struct, in the same order and with the same
+/// names, starting with its `id`. The types of the fields must implement
+/// `sui_sdk::objects::MoveType`, to check them against the types of the Move fields.
+///
+/// The Move type is given by the `move_object` attribute, as for `TypedMoveEvent`:
+///
+/// ```ignore
+/// #[derive(Deserialize, TypedMoveObject)]
+/// #[move_object(module = "devnet_nft", name = "DevNetNFT", package = "0x2")]
+/// struct DevNetNft { ... }
+/// ```
+#[proc_macro_derive(TypedMoveObject, attributes(move_object))]
+pub fn derive_typed_move_object(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    match typed_move_object(&input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn typed_move_object(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    use syn::ext::IdentExt;
+
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "expected a struct with named fields",
+            ))
+        }
+    };
+    let fields = fields.iter().filter_map(|field| {
+        let name = field.ident.as_ref()?.unraw().to_string();
+        let ty = &field.ty;
+        Some(quote! {
+            ::sui_sdk::objects::MoveField {
+                name: #name,
+                matches_type: ::sui_sdk::objects::matches_field_type::<#ty>,
+            }
+        })
+    });
+    let trait_path = quote! { ::sui_sdk::objects::TypedMoveObject };
+    let items = quote! {
+        const FIELDS: &'static [::sui_sdk::objects::MoveField] = &[#(#fields),*];
+    };
+    typed_move_type(input, "move_object", trait_path, items)
+}
+
+/// Implements `trait_path`, a trait declaring the Move type of a struct, with the module, name
+/// and package given by the `attr_name` attribute of `input`, and the other `items` of the trait.
+fn typed_move_type(
+    input: &syn::DeriveInput,
+    attr_name: &str,
+    trait_path: proc_macro2::TokenStream,
+    items: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    use syn::spanned::Spanned;
+
+    let mut module = None;
+    let mut name = None;
+    let mut package = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(attr_name))
+    {
+        let list = match attr.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    format!("expected #[{attr_name}(module = \"...\", ...)]"),
+                ))
+            }
+        };
+        for nested in list.nested {
+            let (key, value) = match &nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(value),
+                    ..
+                })) => (path, value.clone()),
+                _ => return Err(syn::Error::new(nested.span(), "expected `key = \"value\"`")),
+            };
+            let slot = if key.is_ident("module") {
+                &mut module
+            } else if key.is_ident("name") {
+                &mut name
+            } else if key.is_ident("package") {
+                &mut package
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown key, expected `module`, `name` or `package`",
+                ));
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate key"));
+            }
+        }
+    }
+
+    let module = module.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            format!("missing #[{attr_name}(module = \"...\")] attribute"),
+        )
+    })?;
+    let name = name.map_or_else(|| input.ident.to_string(), |name| name.value());
+    let package = match package {
+        Some(package) => {
+            let hex = package.value();
+            let digits = hex.strip_prefix("0x").unwrap_or_default();
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(syn::Error::new(
+                    package.span(),
+                    "expected a hex address starting with 0x",
+                ));
+            }
+            if digits.len() > MAX_ADDRESS_HEX_DIGITS {
+                return Err(syn::Error::new(
+                    package.span(),
+                    format!("expected an address of at most {MAX_ADDRESS_HEX_DIGITS} hex digits"),
+                ));
+            }
+            quote! { ::core::option::Option::Some(#hex) }
+        }
+        None => quote! { ::core::option::Option::None },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            const MODULE: &'static str = #module;
+            const NAME: &'static str = #name;
+            const PACKAGE: ::core::option::Option<&'static str> = #package;
+            #items
+        }
+    })
+}
@@ -64,6 +64,15 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("transactions_info")
+                .route_name("TransactionsInfo")
+                .input_type("sui_types::messages::TransactionsInfoRequest")
+                .output_type("sui_types::messages::TransactionsInfoResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .method(
             Method::builder()
                 .name("checkpoint")
@@ -102,6 +111,33 @@ fn main() -> Result<()> {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            Method::builder()
+                .name("effects_info")
+                .route_name("EffectsInfo")
+                .input_type("sui_types::messages::EffectsInfoRequest")
+                .output_type("sui_types::messages::EffectsInfoResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("bridge_attestation")
+                .route_name("BridgeAttestation")
+                .input_type("sui_types::bridge::BridgeAttestationRequest")
+                .output_type("sui_types::bridge::BridgeAttestationResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("network_info")
+                .route_name("NetworkInfo")
+                .input_type("sui_types::network_info::NetworkInfoRequest")
+                .output_type("sui_types::network_info::NetworkInfoResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .build();
 
     Builder::new()
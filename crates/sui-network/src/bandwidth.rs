@@ -0,0 +1,156 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Counts the bytes exchanged by the clients of the validator service, by server address and
+//! gRPC method, to find the authorities and methods using the most bandwidth.
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use bytes::Buf;
+use http_body::{Body, SizeHint};
+use prometheus::{register_int_counter_vec_with_registry, IntCounter, IntCounterVec, Registry};
+use tonic::body::BoxBody;
+use tonic::codegen::http::{HeaderMap, Request, Response};
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::transport::NamedService;
+
+pub struct BandwidthMetrics {
+    bytes_sent: IntCounterVec,
+    bytes_received: IntCounterVec,
+}
+
+impl BandwidthMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            bytes_sent: register_int_counter_vec_with_registry!(
+                "network_client_bytes_sent",
+                "Number of bytes of requests sent to each authority, by method",
+                &["address", "method"],
+                registry,
+            )
+            .unwrap(),
+            bytes_received: register_int_counter_vec_with_registry!(
+                "network_client_bytes_received",
+                "Number of bytes of responses received from each authority, by method",
+                &["address", "method"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+
+    pub fn bytes_sent(&self, address: &str, method: &str) -> u64 {
+        self.bytes_sent.with_label_values(&[address, method]).get()
+    }
+
+    pub fn bytes_received(&self, address: &str, method: &str) -> u64 {
+        self.bytes_received
+            .with_label_values(&[address, method])
+            .get()
+    }
+}
+
+/// Wraps the channel of a client to count the bytes of its requests and responses.
+#[derive(Clone)]
+pub struct BandwidthService<S> {
+    inner: S,
+    address: String,
+    metrics: Arc<BandwidthMetrics>,
+}
+
+impl<S> BandwidthService<S> {
+    /// Wraps the channel of a client of the server at `address`.
+    pub fn client(inner: S, address: &str, metrics: Arc<BandwidthMetrics>) -> Self {
+        Self {
+            inner,
+            address: address.to_string(),
+            metrics,
+        }
+    }
+}
+
+impl<S, ResBody> Service<Request<BoxBody>> for BandwidthService<S>
+where
+    S: Service<Request<BoxBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<CountingBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<BoxBody>) -> Self::Future {
+        // gRPC paths are "/<service>/<method>".
+        let method = request
+            .uri()
+            .path()
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let labels = [self.address.as_str(), method.as_str()];
+        let sent = self.metrics.bytes_sent.with_label_values(&labels);
+        let received = self.metrics.bytes_received.with_label_values(&labels);
+        let request = request.map(|body| BoxBody::new(CountingBody::new(body, sent)));
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            Ok(response.map(|body| CountingBody::new(body, received)))
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for BandwidthService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+/// A body which adds the size of each of its frames to a counter as they are polled.
+pub struct CountingBody<B> {
+    inner: B,
+    counter: IntCounter,
+}
+
+impl<B> CountingBody<B> {
+    fn new(inner: B, counter: IntCounter) -> Self {
+        Self { inner, counter }
+    }
+}
+
+impl<B: Body + Unpin> Body for CountingBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_data(cx);
+        if let Poll::Ready(Some(Ok(data))) = &poll {
+            self.counter.inc_by(data.remaining() as u64);
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
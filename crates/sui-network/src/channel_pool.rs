@@ -0,0 +1,336 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of gRPC channels to the same endpoint. Requests multiplexed on a single HTTP/2
+//! connection are subject to head-of-line blocking and to the concurrent stream limit of the
+//! connection, so clients spread their requests over the channels of the pool in round-robin.
+//! The size of the pools is part of their `ChannelPoolConfig`. Endpoints with backup addresses
+//! get a pool of channels per address, see `crate::failover`.
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use http_body::{Body, SizeHint};
+use multiaddr::Multiaddr;
+use mysten_network::config::Config;
+use prometheus::IntGauge;
+use tonic::codegen::http::{HeaderMap, Request, Response};
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::transport::{Channel, NamedService};
+
+use crate::failover::{select_address, AddressHealth};
+use crate::message_size::MessageSizeLimits;
+
+/// Default number of channels per endpoint, which keeps a single connection per authority.
+pub const DEFAULT_CHANNEL_POOL_SIZE: usize = 1;
+
+/// Settings of the channel pools of a node, and of the clients using them.
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelPoolConfig {
+    /// Number of channels per address of an endpoint. Pools always have at least one channel.
+    pub size: usize,
+    /// Max sizes of the messages sent and received by the clients using the pools.
+    pub message_size_limits: MessageSizeLimits,
+}
+
+impl Default for ChannelPoolConfig {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_CHANNEL_POOL_SIZE,
+            message_size_limits: MessageSizeLimits::default(),
+        }
+    }
+}
+
+/// The channels to one of the addresses of an endpoint.
+struct AddressChannels {
+    /// The address of the channels, used to label their metrics. Empty if unknown.
+    address: String,
+    channels: Vec<Channel>,
+    health: Arc<AddressHealth>,
+}
+
+/// A channel of a pool, to be used for a single request.
+pub struct PooledChannel<'a> {
+    pub address: &'a str,
+    /// The index of the channel among the channels to `address`.
+    pub index: usize,
+    pub channel: Channel,
+    pub health: Arc<AddressHealth>,
+}
+
+pub struct ChannelPool {
+    /// The addresses of the endpoint, in order of preference. See `crate::failover`.
+    addresses: Vec<AddressChannels>,
+    next: AtomicUsize,
+    message_size_limits: MessageSizeLimits,
+}
+
+impl ChannelPool {
+    /// Creates a pool of `pool_config.size` channels to `address`, each with its own connection,
+    /// established on first use.
+    pub fn connect_lazy(
+        config: &Config,
+        pool_config: &ChannelPoolConfig,
+        address: &Multiaddr,
+    ) -> anyhow::Result<Self> {
+        Self::connect_lazy_with_failover(config, pool_config, std::slice::from_ref(address))
+    }
+
+    /// Like `connect_lazy`, to each of `addresses`, which requests fail over to in order.
+    pub fn connect_lazy_with_failover(
+        config: &Config,
+        pool_config: &ChannelPoolConfig,
+        addresses: &[Multiaddr],
+    ) -> anyhow::Result<Self> {
+        if addresses.is_empty() {
+            return Err(anyhow!("No address to connect to"));
+        }
+        let addresses = addresses
+            .iter()
+            .map(|address| {
+                let channels = (0..pool_config.size.max(1))
+                    .map(|_| {
+                        config
+                            .connect_lazy(address)
+                            .map_err(|err| anyhow!(err.to_string()))
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(AddressChannels {
+                    address: address.to_string(),
+                    channels,
+                    health: Arc::new(AddressHealth::default()),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            addresses,
+            next: AtomicUsize::new(0),
+            message_size_limits: pool_config.message_size_limits,
+        })
+    }
+
+    /// Max sizes of the messages sent and received on the channels of the pool.
+    pub fn message_size_limits(&self) -> MessageSizeLimits {
+        self.message_size_limits
+    }
+
+    /// The addresses of the pool, in order of preference.
+    pub fn addresses(&self) -> Vec<&str> {
+        self.addresses
+            .iter()
+            .map(|address| address.address.as_str())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses
+            .iter()
+            .map(|address| address.channels.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the next channel in round-robin order to the first healthy address or, if none
+    /// is healthy, to the address which failed the longest ago.
+    pub fn next_channel(&self) -> PooledChannel<'_> {
+        let healths = self.addresses.iter().map(|address| address.health.as_ref());
+        let selected = select_address(healths).expect("channel pools have at least one address");
+        let address = &self.addresses[selected];
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % address.channels.len();
+        PooledChannel {
+            address: &address.address,
+            index,
+            channel: address.channels[index].clone(),
+            health: address.health.clone(),
+        }
+    }
+}
+
+impl From<Channel> for ChannelPool {
+    fn from(channel: Channel) -> Self {
+        Self {
+            addresses: vec![AddressChannels {
+                address: String::new(),
+                channels: vec![channel],
+                health: Arc::new(AddressHealth::default()),
+            }],
+            next: AtomicUsize::new(0),
+            message_size_limits: MessageSizeLimits::default(),
+        }
+    }
+}
+
+/// Wraps a channel of a pool to count the requests in flight on it. A request is in flight until
+/// its response is received in full, or it fails.
+#[derive(Clone)]
+pub struct InflightService<S> {
+    inner: S,
+    inflight: IntGauge,
+}
+
+impl<S> InflightService<S> {
+    pub fn new(inner: S, inflight: IntGauge) -> Self {
+        Self { inner, inflight }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for InflightService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<InflightBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let guard = InflightGuard::new(self.inflight.clone());
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            Ok(response.map(|body| InflightBody {
+                inner: body,
+                _guard: guard,
+            }))
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for InflightService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+/// Counts a request as in flight until dropped.
+struct InflightGuard(IntGauge);
+
+impl InflightGuard {
+    fn new(inflight: IntGauge) -> Self {
+        inflight.inc();
+        Self(inflight)
+    }
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// The body of a response, which counts its request as in flight until it is dropped.
+pub struct InflightBody<B> {
+    inner: B,
+    _guard: InflightGuard,
+}
+
+impl<B: Body + Unpin> Body for InflightBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Pin::new(&mut self.inner).poll_data(cx)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http_body::Full;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn test_round_robin() {
+        let addresses: Vec<Multiaddr> = vec![
+            "/ip4/127.0.0.1/tcp/8080/http".parse().unwrap(),
+            "/ip4/127.0.0.1/tcp/8081/http".parse().unwrap(),
+        ];
+        let pool_config = ChannelPoolConfig {
+            size: 3,
+            ..Default::default()
+        };
+        let pool =
+            ChannelPool::connect_lazy_with_failover(&Config::new(), &pool_config, &addresses)
+                .unwrap();
+        assert_eq!(pool.len(), 6);
+
+        // Requests go to the channels of the first healthy address in turn.
+        let indexes: Vec<_> = (0..4).map(|_| pool.next_channel().index).collect();
+        assert_eq!(indexes, vec![0, 1, 2, 0]);
+        let address = pool.next_channel().address.to_string();
+        assert_eq!(address, pool.addresses()[0]);
+
+        // Pools always have at least one channel.
+        let pool_config = ChannelPoolConfig {
+            size: 0,
+            ..Default::default()
+        };
+        let pool = ChannelPool::connect_lazy(&Config::new(), &pool_config, &addresses[0]).unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+
+    /// Responds to every request with an empty body.
+    #[derive(Clone)]
+    struct EmptyResponse;
+
+    impl Service<Request<()>> for EmptyResponse {
+        type Response = Response<Full<Bytes>>;
+        type Error = Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            std::future::ready(Ok(Response::new(Full::new(Bytes::new()))))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inflight_until_response_body_dropped() {
+        let inflight = IntGauge::new("inflight", "inflight").unwrap();
+        let mut service = InflightService::new(EmptyResponse, inflight.clone());
+
+        let response = service.call(Request::new(()));
+        assert_eq!(inflight.get(), 1);
+        let response = response.await.unwrap();
+        // Streamed responses are still in flight once their headers are received.
+        assert_eq!(inflight.get(), 1);
+        drop(response);
+        assert_eq!(inflight.get(), 0);
+
+        // Requests are no longer in flight once their call is dropped.
+        let response = service.call(Request::new(()));
+        assert_eq!(inflight.get(), 1);
+        drop(response);
+        assert_eq!(inflight.get(), 0);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validators may register backup network addresses on chain next to their network address, e.g.
+//! an IPv6 address alongside an IPv4 one, which clients fail over to when the network address is
+//! unreachable.
+//!
+//! Clients dial the first healthy address. An address becomes unhealthy when a request fails to
+//! reach it, and the client fails over to the next address until a request reaches the failed
+//! address again, which is tried once `FAILED_ADDRESS_RETRY_INTERVAL` passed.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+use tonic::body::BoxBody;
+use tonic::codegen::http::Request;
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::transport::NamedService;
+
+/// How long an address is avoided after a request failed to reach it.
+pub const FAILED_ADDRESS_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The index of the address to dial among the addresses with health `healths`, in order of
+/// preference: the first healthy one or, if none is healthy, the one which failed the longest
+/// ago. None if there are no addresses.
+pub fn select_address<'a, I>(healths: I) -> Option<usize>
+where
+    I: Iterator<Item = &'a AddressHealth> + Clone,
+{
+    healths
+        .clone()
+        .position(AddressHealth::is_healthy)
+        .or_else(|| {
+            healths
+                .enumerate()
+                .min_by_key(|(_, health)| health.failed_at())
+                .map(|(index, _)| index)
+        })
+}
+
+pub struct FailoverMetrics {
+    address_healthy: IntGaugeVec,
+    address_failures: IntCounterVec,
+}
+
+impl FailoverMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            address_healthy: register_int_gauge_vec_with_registry!(
+                "network_client_address_healthy",
+                "Whether the last request to each address of the authorities reached it",
+                &["address"],
+                registry,
+            )
+            .unwrap(),
+            address_failures: register_int_counter_vec_with_registry!(
+                "network_client_address_failures",
+                "Number of requests which failed to reach each address of the authorities",
+                &["address"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn new_for_tests() -> Self {
+        Self::new(&Registry::new())
+    }
+
+    pub fn address_failures(&self, address: &str) -> u64 {
+        self.address_failures.with_label_values(&[address]).get()
+    }
+}
+
+/// The health of an address, shared by the channels to it.
+#[derive(Debug, Default)]
+pub struct AddressHealth {
+    failed_at: Mutex<Option<Instant>>,
+}
+
+impl AddressHealth {
+    /// When a request last failed to reach the address, if it was not reached since.
+    pub fn failed_at(&self) -> Option<Instant> {
+        *self.failed_at.lock().unwrap()
+    }
+
+    /// Whether the address should be dialed: it was not unreachable in the last
+    /// `FAILED_ADDRESS_RETRY_INTERVAL`.
+    pub fn is_healthy(&self) -> bool {
+        self.failed_at().map_or(true, |failed_at| {
+            failed_at.elapsed() >= FAILED_ADDRESS_RETRY_INTERVAL
+        })
+    }
+
+    pub fn record_success(&self) {
+        *self.failed_at.lock().unwrap() = None;
+    }
+
+    pub fn record_failure(&self) {
+        *self.failed_at.lock().unwrap() = Some(Instant::now());
+    }
+}
+
+/// Wraps the channel of a client to an address to track the health of the address: requests
+/// which get a response, even an error status, reached it, requests which fail in transport did
+/// not.
+#[derive(Clone)]
+pub struct FailoverService<S> {
+    inner: S,
+    address: String,
+    health: Arc<AddressHealth>,
+    metrics: Arc<FailoverMetrics>,
+}
+
+impl<S> FailoverService<S> {
+    pub fn client(
+        inner: S,
+        address: &str,
+        health: Arc<AddressHealth>,
+        metrics: Arc<FailoverMetrics>,
+    ) -> Self {
+        Self {
+            inner,
+            address: address.to_string(),
+            health,
+            metrics,
+        }
+    }
+}
+
+impl<S, Res> Service<Request<BoxBody>> for FailoverService<S>
+where
+    S: Service<Request<BoxBody>, Response = Res>,
+    S::Future: Send + 'static,
+{
+    type Response = Res;
+    type Error = S::Error;
+    type Future = BoxFuture<Res, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<BoxBody>) -> Self::Future {
+        let response = self.inner.call(request);
+        let address = self.address.clone();
+        let health = self.health.clone();
+        let metrics = self.metrics.clone();
+        Box::pin(async move {
+            let result = response.await;
+            let healthy = metrics
+                .address_healthy
+                .with_label_values(&[address.as_str()]);
+            if result.is_ok() {
+                health.record_success();
+                healthy.set(1);
+            } else {
+                health.record_failure();
+                healthy.set(0);
+                metrics
+                    .address_failures
+                    .with_label_values(&[address.as_str()])
+                    .inc();
+            }
+            result
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for FailoverService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_address() {
+        let addresses: Vec<AddressHealth> = (0..2).map(|_| AddressHealth::default()).collect();
+        let select = || select_address(addresses.iter());
+        assert_eq!(select_address(std::iter::empty()), None);
+        assert_eq!(select(), Some(0));
+
+        // Clients fail over to the next address when a request fails to reach the first one.
+        addresses[0].record_failure();
+        assert_eq!(select(), Some(1));
+
+        // If all addresses fail, the one which failed the longest ago is tried again.
+        addresses[1].record_failure();
+        assert_eq!(select(), Some(0));
+
+        // Clients come back to the first address once it is reached again.
+        addresses[0].record_success();
+        assert_eq!(select(), Some(0));
+        addresses[1].record_success();
+        assert_eq!(select(), Some(0));
+    }
+
+    #[test]
+    fn test_address_health() {
+        let health = AddressHealth::default();
+        assert!(health.is_healthy());
+        health.record_failure();
+        assert!(!health.is_healthy());
+        health.record_success();
+        assert!(health.is_healthy());
+    }
+}
@@ -5,6 +5,11 @@ use mysten_network::config::Config;
 use std::time::Duration;
 
 pub mod api;
+pub mod bandwidth;
+pub mod channel_pool;
+pub mod failover;
+pub mod message_size;
+pub mod version;
 
 pub use tonic;
 
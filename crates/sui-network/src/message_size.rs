@@ -0,0 +1,290 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bounds the size of the gRPC messages exchanged by the clients and the servers of a service.
+//! gRPC messages are framed by a 5 bytes header, a compression flag followed by the big-endian
+//! length of the message, so the bodies of the requests and responses are checked as they are
+//! streamed: a message larger than the limit fails the body as soon as its header is read,
+//! before the message itself is buffered for decoding.
+
+use std::pin::Pin;
+
+use bytes::Bytes;
+use http_body::{Body, SizeHint};
+use sui_types::messages::{MAX_PURE_ARGUMENT_SIZE, MAX_TX_BATCH_SIZE};
+use tonic::body::BoxBody;
+use tonic::codegen::http::{HeaderMap, Request, Response};
+use tonic::codegen::{BoxFuture, Context, Poll, Service, StdError};
+use tonic::transport::NamedService;
+use tonic::Status;
+
+/// Default max message size: a batch transaction with every pure argument at its maximum size,
+/// with room for the signatures and effects that come with it.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 2 * MAX_TX_BATCH_SIZE * MAX_PURE_ARGUMENT_SIZE;
+
+/// Length of the header framing each gRPC message.
+const MESSAGE_HEADER_LEN: usize = 5;
+
+/// The max sizes of the messages of a service, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageSizeLimits {
+    /// Max size of a message sent: requests for clients, responses for servers.
+    pub max_encoding_message_size: usize,
+    /// Max size of a message received: responses for clients, requests for servers.
+    pub max_decoding_message_size: usize,
+}
+
+impl MessageSizeLimits {
+    /// The given limits, or the default of those that are None.
+    pub fn new(
+        max_encoding_message_size: Option<usize>,
+        max_decoding_message_size: Option<usize>,
+    ) -> Self {
+        Self {
+            max_encoding_message_size: max_encoding_message_size
+                .unwrap_or(DEFAULT_MAX_MESSAGE_SIZE),
+            max_decoding_message_size: max_decoding_message_size
+                .unwrap_or(DEFAULT_MAX_MESSAGE_SIZE),
+        }
+    }
+}
+
+impl Default for MessageSizeLimits {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+/// Wraps a gRPC client or server to bound the size of the messages of its requests and
+/// responses.
+#[derive(Clone)]
+pub struct MessageSizeLimitService<S> {
+    inner: S,
+    max_request_message_size: usize,
+    max_response_message_size: usize,
+}
+
+impl<S> MessageSizeLimitService<S> {
+    /// Wraps the channel of a client, which encodes requests and decodes responses.
+    pub fn client(inner: S, limits: MessageSizeLimits) -> Self {
+        Self {
+            inner,
+            max_request_message_size: limits.max_encoding_message_size,
+            max_response_message_size: limits.max_decoding_message_size,
+        }
+    }
+
+    /// Wraps a server, which decodes requests and encodes responses.
+    pub fn server(inner: S, limits: MessageSizeLimits) -> Self {
+        Self {
+            inner,
+            max_request_message_size: limits.max_decoding_message_size,
+            max_response_message_size: limits.max_encoding_message_size,
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for MessageSizeLimitService<S>
+where
+    S: Service<Request<BoxBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ReqBody: Body<Data = Bytes> + Unpin + Send + 'static,
+    ReqBody::Error: Into<StdError>,
+    ResBody: Body<Data = Bytes> + Unpin + Send + 'static,
+    ResBody::Error: Into<StdError>,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let max_request_message_size = self.max_request_message_size;
+        let max_response_message_size = self.max_response_message_size;
+        let request =
+            request.map(|body| BoxBody::new(LimitedBody::new(body, max_request_message_size)));
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            Ok(
+                response
+                    .map(|body| BoxBody::new(LimitedBody::new(body, max_response_message_size))),
+            )
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for MessageSizeLimitService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+/// Reads the headers of the gRPC messages of a body as its frames go by.
+struct MessageHeaders {
+    max_message_size: usize,
+    header: [u8; MESSAGE_HEADER_LEN],
+    /// Bytes of the header of the next message read so far.
+    header_read: usize,
+    /// Bytes of the current message yet to go by.
+    message_remaining: usize,
+}
+
+impl MessageHeaders {
+    fn new(max_message_size: usize) -> Self {
+        Self {
+            max_message_size,
+            header: [0; MESSAGE_HEADER_LEN],
+            header_read: 0,
+            message_remaining: 0,
+        }
+    }
+
+    /// Reads the next frame of the body, failing on the header of a message larger than the
+    /// limit.
+    fn read(&mut self, mut frame: &[u8]) -> Result<(), Status> {
+        while !frame.is_empty() {
+            if self.message_remaining > 0 {
+                let len = self.message_remaining.min(frame.len());
+                self.message_remaining -= len;
+                frame = &frame[len..];
+                continue;
+            }
+            let len = (MESSAGE_HEADER_LEN - self.header_read).min(frame.len());
+            self.header[self.header_read..self.header_read + len].copy_from_slice(&frame[..len]);
+            self.header_read += len;
+            frame = &frame[len..];
+            if self.header_read == MESSAGE_HEADER_LEN {
+                self.header_read = 0;
+                let mut len = [0; 4];
+                len.copy_from_slice(&self.header[1..]);
+                let size = u32::from_be_bytes(len) as usize;
+                if size > self.max_message_size {
+                    return Err(Status::resource_exhausted(format!(
+                        "Message of {size} bytes is larger than the maximum of {} bytes",
+                        self.max_message_size
+                    )));
+                }
+                self.message_remaining = size;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A body which fails as soon as the header of a message larger than its limit goes by.
+pub struct LimitedBody<B> {
+    inner: B,
+    headers: MessageHeaders,
+}
+
+impl<B> LimitedBody<B> {
+    pub fn new(inner: B, max_message_size: usize) -> Self {
+        Self {
+            inner,
+            headers: MessageHeaders::new(max_message_size),
+        }
+    }
+}
+
+impl<B> Body for LimitedBody<B>
+where
+    B: Body<Data = Bytes> + Unpin,
+    B::Error: Into<StdError>,
+{
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let data = match Pin::new(&mut self.inner).poll_data(cx) {
+            Poll::Ready(Some(Ok(data))) => data,
+            Poll::Ready(Some(Err(err))) => {
+                return Poll::Ready(Some(Err(Status::from_error(err.into()))))
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(Some(self.headers.read(&data).map(|()| data)))
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner)
+            .poll_trailers(cx)
+            .map_err(|err| Status::from_error(err.into()))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    /// A gRPC message of `len` bytes, with its header.
+    fn message(len: usize) -> Vec<u8> {
+        let mut message = vec![0];
+        message.extend_from_slice(&(len as u32).to_be_bytes());
+        message.resize(MESSAGE_HEADER_LEN + len, 7);
+        message
+    }
+
+    #[test]
+    fn test_messages_within_limit() {
+        let mut headers = MessageHeaders::new(100);
+        let body = [message(100), message(0), message(42)].concat();
+        // Frames may split headers and messages anywhere.
+        for frame in body.chunks(3) {
+            headers.read(frame).unwrap();
+        }
+        assert_eq!(headers.header_read, 0);
+        assert_eq!(headers.message_remaining, 0);
+    }
+
+    #[test]
+    fn test_message_over_limit_fails_on_its_header() {
+        let mut headers = MessageHeaders::new(100);
+        headers.read(&message(10)).unwrap();
+        let large = message(101);
+        // The header alone is enough to reject the message.
+        headers.read(&large[..2]).unwrap();
+        let err = headers.read(&large[2..MESSAGE_HEADER_LEN]).unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn test_limited_body() {
+        let body = http_body::Full::new(Bytes::from(message(10)));
+        let mut body = LimitedBody::new(body, 10);
+        assert!(body.data().await.unwrap().is_ok());
+
+        let body = http_body::Full::new(Bytes::from(message(11)));
+        let mut body = LimitedBody::new(body, 10);
+        let err = body.data().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+    }
+
+    #[test]
+    fn test_default_limits() {
+        let limits = MessageSizeLimits::new(Some(1024), None);
+        assert_eq!(limits.max_encoding_message_size, 1024);
+        assert_eq!(limits.max_decoding_message_size, DEFAULT_MAX_MESSAGE_SIZE);
+        assert_eq!(
+            MessageSizeLimits::default(),
+            MessageSizeLimits::new(None, None)
+        );
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Version handshake between the clients and the servers of the validator service. Clients send
+//! their protocol and application versions in the headers of every request, and servers theirs
+//! in the headers of every response, so each side learns the versions of its peers without an
+//! extra round trip. Peers on incompatible protocol versions are counted and logged, but their
+//! requests are still served: a node must be able to talk to the network while it is upgraded.
+//! As the versions are sent by peers, the metrics only label a bounded set of them.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry, IntCounter,
+    IntGaugeVec, Registry,
+};
+use tonic::codegen::http::{HeaderMap, HeaderValue, Request, Response};
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::transport::server::TcpConnectInfo;
+use tonic::transport::NamedService;
+use tracing::warn;
+
+/// Version of the protocol spoken over the validator service, bumped on every change of its
+/// messages that older nodes cannot handle.
+pub const PROTOCOL_VERSION: u64 = 1;
+/// Oldest protocol version this node can still talk to.
+pub const MIN_SUPPORTED_PROTOCOL_VERSION: u64 = 1;
+
+pub const PROTOCOL_VERSION_HEADER: &str = "sui-protocol-version";
+pub const MIN_SUPPORTED_PROTOCOL_VERSION_HEADER: &str = "sui-min-supported-protocol-version";
+pub const APPLICATION_VERSION_HEADER: &str = "sui-application-version";
+
+/// Peers a monitor tracks the version of. Once as many are tracked, the versions of new peers
+/// are only checked for compatibility.
+const MAX_TRACKED_PEERS: usize = 1_000;
+/// Distinct versions labelling the metrics of a monitor, beyond which versions are labelled
+/// `other`.
+const MAX_VERSION_LABELS: usize = 20;
+/// Application versions longer than this, or with other characters than those of release
+/// versions, are labelled `invalid`.
+const MAX_APPLICATION_VERSION_LEN: usize = 32;
+
+const UNKNOWN: &str = "unknown";
+const INVALID: &str = "invalid";
+const OTHER: &str = "other";
+
+/// The versions a peer sent in the headers of a request or a response. Nodes predating the
+/// handshake send none.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PeerVersion {
+    pub protocol_version: Option<u64>,
+    pub min_supported_protocol_version: Option<u64>,
+    pub application_version: Option<String>,
+}
+
+impl PeerVersion {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        let protocol_version = header(PROTOCOL_VERSION_HEADER).and_then(|v| v.parse().ok());
+        Self {
+            protocol_version,
+            min_supported_protocol_version: header(MIN_SUPPORTED_PROTOCOL_VERSION_HEADER)
+                .and_then(|v| v.parse().ok())
+                .or(protocol_version),
+            application_version: header(APPLICATION_VERSION_HEADER).map(str::to_string),
+        }
+    }
+
+    /// Whether this node and the peer support a common protocol version. Peers which did not
+    /// send their version are assumed compatible.
+    pub fn is_compatible(&self) -> bool {
+        match (self.protocol_version, self.min_supported_protocol_version) {
+            (Some(version), Some(min_supported)) => {
+                min_supported <= PROTOCOL_VERSION && MIN_SUPPORTED_PROTOCOL_VERSION <= version
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Adds the versions of this node, running `application_version`, to `headers`.
+pub fn insert_version_headers(headers: &mut HeaderMap, application_version: &str) {
+    headers.insert(PROTOCOL_VERSION_HEADER, HeaderValue::from(PROTOCOL_VERSION));
+    headers.insert(
+        MIN_SUPPORTED_PROTOCOL_VERSION_HEADER,
+        HeaderValue::from(MIN_SUPPORTED_PROTOCOL_VERSION),
+    );
+    if let Ok(version) = HeaderValue::from_str(application_version) {
+        headers.insert(APPLICATION_VERSION_HEADER, version);
+    }
+}
+
+/// The metric label of an application version sent by a peer.
+fn application_version_label(version: Option<&str>) -> String {
+    match version {
+        None => UNKNOWN.to_string(),
+        Some(version)
+            if !version.is_empty()
+                && version.len() <= MAX_APPLICATION_VERSION_LEN
+                && version
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')) =>
+        {
+            version.to_string()
+        }
+        Some(_) => INVALID.to_string(),
+    }
+}
+
+/// Whether the peers observed by a monitor are the servers or the clients of this node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerRole {
+    Server,
+    Client,
+}
+
+impl PeerRole {
+    fn metric_prefix(&self) -> &'static str {
+        match self {
+            Self::Server => "validator_server_peers",
+            Self::Client => "validator_client_peers",
+        }
+    }
+}
+
+/// Records the versions of the peers of this node, warning once about each peer on an
+/// incompatible protocol version.
+pub struct PeerVersionMonitor {
+    role: PeerRole,
+    /// The version of the software running in this node, sent to its peers.
+    application_version: &'static str,
+    peers: Mutex<TrackedPeers>,
+    metrics: PeerVersionMetrics,
+}
+
+/// The protocol and application version labels of a peer in the metrics.
+type VersionLabels = (String, String);
+
+#[derive(Default)]
+struct TrackedPeers {
+    versions: HashMap<String, (PeerVersion, VersionLabels)>,
+    labels: HashSet<VersionLabels>,
+}
+
+impl TrackedPeers {
+    /// The labels of `version`, or `other` once `MAX_VERSION_LABELS` other versions are labelled.
+    fn labels(&mut self, version: &PeerVersion) -> VersionLabels {
+        let labels = (
+            version
+                .protocol_version
+                .map_or_else(|| UNKNOWN.to_string(), |v| v.to_string()),
+            application_version_label(version.application_version.as_deref()),
+        );
+        if self.labels.contains(&labels) || self.labels.len() < MAX_VERSION_LABELS {
+            self.labels.insert(labels.clone());
+            labels
+        } else {
+            (OTHER.to_string(), OTHER.to_string())
+        }
+    }
+}
+
+impl PeerVersionMonitor {
+    pub fn new(role: PeerRole, application_version: &'static str, registry: &Registry) -> Self {
+        Self {
+            role,
+            application_version,
+            peers: Mutex::new(TrackedPeers::default()),
+            metrics: PeerVersionMetrics::new(role, registry),
+        }
+    }
+
+    pub fn application_version(&self) -> &'static str {
+        self.application_version
+    }
+
+    /// Records that `peer` sent `version`.
+    pub fn observe(&self, peer: &str, version: PeerVersion) {
+        let compatible = version.is_compatible();
+        if !compatible {
+            self.metrics.incompatible_messages.inc();
+        }
+        let mut peers = self.peers.lock().unwrap();
+        match peers.versions.get(peer) {
+            Some((known, _)) if *known == version => return,
+            None if peers.versions.len() >= MAX_TRACKED_PEERS => return,
+            _ => (),
+        }
+        let (protocol_version, application_version) = peers.labels(&version);
+        self.metrics
+            .peers
+            .with_label_values(&[&protocol_version, &application_version])
+            .inc();
+        let labels = (protocol_version.clone(), application_version.clone());
+        if let Some((_, previous)) = peers.versions.insert(peer.to_string(), (version, labels)) {
+            self.metrics
+                .peers
+                .with_label_values(&[&previous.0, &previous.1])
+                .dec();
+        }
+        if !compatible {
+            warn!(
+                ?peer,
+                role = ?self.role,
+                peer_protocol_version = %protocol_version,
+                peer_application_version = %application_version,
+                protocol_version = PROTOCOL_VERSION,
+                min_supported_protocol_version = MIN_SUPPORTED_PROTOCOL_VERSION,
+                "Peer is on an incompatible protocol version"
+            );
+        }
+    }
+
+    /// Number of tracked peers last seen with the given protocol and application versions.
+    pub fn peers(&self, protocol_version: &str, application_version: &str) -> u64 {
+        self.metrics
+            .peers
+            .with_label_values(&[protocol_version, application_version])
+            .get() as u64
+    }
+
+    /// Number of messages received from peers on an incompatible protocol version.
+    pub fn incompatible_messages(&self) -> u64 {
+        self.metrics.incompatible_messages.get()
+    }
+}
+
+struct PeerVersionMetrics {
+    peers: IntGaugeVec,
+    incompatible_messages: IntCounter,
+}
+
+impl PeerVersionMetrics {
+    fn new(role: PeerRole, registry: &Registry) -> Self {
+        let prefix = role.metric_prefix();
+        Self {
+            peers: register_int_gauge_vec_with_registry!(
+                format!("{prefix}_by_version"),
+                "Number of peers by the protocol and application version they last sent",
+                &["protocol_version", "application_version"],
+                registry,
+            )
+            .unwrap(),
+            incompatible_messages: register_int_counter_with_registry!(
+                format!("{prefix}_incompatible_messages"),
+                "Number of messages received from peers on an incompatible protocol version",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Wraps a gRPC client or server to run the version handshake on each of its requests.
+#[derive(Clone)]
+pub struct VersionService<S> {
+    inner: S,
+    /// The address of the server, for clients. Servers take the address of the client from
+    /// the request.
+    server_address: Option<String>,
+    monitor: Arc<PeerVersionMonitor>,
+}
+
+impl<S> VersionService<S> {
+    /// Wraps the channel of a client of the server at `address`.
+    pub fn client(inner: S, address: &str, monitor: Arc<PeerVersionMonitor>) -> Self {
+        Self {
+            inner,
+            server_address: Some(address.to_string()),
+            monitor,
+        }
+    }
+
+    pub fn server(inner: S, monitor: Arc<PeerVersionMonitor>) -> Self {
+        Self {
+            inner,
+            server_address: None,
+            monitor,
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for VersionService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<S::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let monitor = self.monitor.clone();
+        let server_address = self.server_address.clone();
+        match &server_address {
+            Some(_) => insert_version_headers(request.headers_mut(), monitor.application_version()),
+            None => {
+                let client_address = request
+                    .extensions()
+                    .get::<TcpConnectInfo>()
+                    .and_then(TcpConnectInfo::remote_addr)
+                    .map_or_else(|| UNKNOWN.to_string(), |a| a.ip().to_string());
+                monitor.observe(
+                    &client_address,
+                    PeerVersion::from_headers(request.headers()),
+                );
+            }
+        }
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = response.await?;
+            match &server_address {
+                Some(address) => {
+                    monitor.observe(address, PeerVersion::from_headers(response.headers()))
+                }
+                None => {
+                    insert_version_headers(response.headers_mut(), monitor.application_version())
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl<S: NamedService> NamedService for VersionService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(protocol_version: u64, application_version: &str) -> PeerVersion {
+        PeerVersion {
+            protocol_version: Some(protocol_version),
+            min_supported_protocol_version: Some(protocol_version),
+            application_version: Some(application_version.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_peer_version_changes() {
+        let monitor = PeerVersionMonitor::new(PeerRole::Client, "0.13.0", &Registry::new());
+        monitor.observe("peer", version(1, "0.13.0"));
+        monitor.observe("peer", version(1, "0.13.0"));
+        assert_eq!(monitor.peers("1", "0.13.0"), 1);
+
+        // A peer is counted at the version it last sent.
+        monitor.observe("peer", version(1, "0.14.0"));
+        assert_eq!(monitor.peers("1", "0.13.0"), 0);
+        assert_eq!(monitor.peers("1", "0.14.0"), 1);
+    }
+
+    #[test]
+    fn test_version_labels_are_bounded() {
+        let monitor = PeerVersionMonitor::new(PeerRole::Client, "0.13.0", &Registry::new());
+        monitor.observe("a", version(1, "0.13.0\nforged"));
+        monitor.observe(
+            "b",
+            version(1, &"9".repeat(MAX_APPLICATION_VERSION_LEN + 1)),
+        );
+        assert_eq!(monitor.peers("1", INVALID), 2);
+
+        for i in 0..MAX_VERSION_LABELS as u64 {
+            monitor.observe(&format!("peer-{}", i), version(i + 2, "0.13.0"));
+        }
+        // One label is taken by the invalid versions above.
+        assert_eq!(monitor.peers(OTHER, OTHER), 1);
+        assert_eq!(monitor.peers("2", "0.13.0"), 1);
+
+        // Peers beyond the tracked ones are not counted.
+        for i in 0..MAX_TRACKED_PEERS {
+            monitor.observe(&format!("other-{}", i), version(2, "0.13.0"));
+        }
+        let tracked = MAX_TRACKED_PEERS - 2 - MAX_VERSION_LABELS;
+        assert_eq!(monitor.peers("2", "0.13.0"), 1 + tracked as u64);
+    }
+}
@@ -1,25 +1,150 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::http_server;
+use crate::watchdog::{action_name, Watchdog};
 use axum::{
-    extract::Extension,
-    http::StatusCode,
-    routing::{get, post},
+    body::Body,
+    extract::{Extension, Query},
+    http::{header::AUTHORIZATION, HeaderValue, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
     Router,
 };
+use chrono::{DateTime, SecondsFormat, Utc};
+use multiaddr::Multiaddr;
+use rand::{distributions::Alphanumeric, Rng};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::Write as _;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::LoggingConfig;
+use sui_core::authority::{AuthorityState, CertificateReprocessing};
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::consensus_monitor::ConsensusMonitor;
+use sui_core::deny_list::DenyListEntry;
+use sui_core::gas_profiler::GasProfiler;
+use sui_core::quorum_driver::FinalityTrace;
+use sui_core::slow_calls::CallServer;
+use sui_core::trace_sampling;
+use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_types::base_types::TransactionDigest;
 use telemetry_subscribers::FilterHandle;
-use tracing::info;
+use tracing::{info, warn};
 
 const LOGGING_ROUTE: &str = "/logging";
+const LOGGING_MODULE_ROUTE: &str = "/logging/module";
+const LOGGING_SAMPLING_ROUTE: &str = "/logging/sampling";
+const GAS_PROFILE_ROUTE: &str = "/gas-profile";
+const SYNC_STATUS_ROUTE: &str = "/sync-status";
+const ALERT_RULES_ROUTE: &str = "/alert-rules";
+const READ_ONLY_ROUTE: &str = "/read-only";
+const DENY_LIST_ROUTE: &str = "/deny-list";
+const COMMITTEES_ROUTE: &str = "/committees";
+const OBJECT_CONTENTION_ROUTE: &str = "/object-contention";
+const EPOCH_ACCOUNTING_ROUTE: &str = "/epoch-accounting";
+const SLOW_CALLS_ROUTE: &str = "/slow-calls";
+const FINALITY_TRACES_ROUTE: &str = "/finality-traces";
+const DB_ROUTE: &str = "/db";
+const DB_COMPACT_ROUTE: &str = "/db/compact";
+const PENDING_CERTIFICATES_ROUTE: &str = "/pending-certificates";
+const PENDING_CERTIFICATES_REPROCESS_ROUTE: &str = "/pending-certificates/reprocess";
+const EXECUTION_QUEUE_ROUTE: &str = "/execution-queue";
+const EPOCH_TABLES_ROUTE: &str = "/epoch-tables";
+const WATCHDOG_ROUTE: &str = "/watchdog";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// Number of functions listed by `GET /gas-profile` unless `?top=` is given.
+const DEFAULT_GAS_PROFILE_TOP: usize = 50;
+
+/// Number of objects of each kind listed by `GET /object-contention` unless `?top=` is given.
+const DEFAULT_OBJECT_CONTENTION_TOP: usize = 20;
+
+/// Thresholds of the rules served by `GET /alert-rules`.
+const CHECKPOINT_SYNC_LAG_ALERT_THRESHOLD: u64 = 10;
+const CHECKPOINT_SYNC_LAG_ALERT_FOR: &str = "5m";
+const CHECKPOINT_STALLED_ALERT_FOR: &str = "10m";
+
+/// Length of the admin token generated when none is configured.
+const GENERATED_TOKEN_LENGTH: usize = 32;
+
+type TransactionOrchestrator = Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>;
+
+/// Start the admin server on `port` of localhost. The operational routes, e.g. reprocessing
+/// certificates, always require the admin token: `token`, or else one generated now and written
+/// to `token_path`. The other routes only require it when `token` is set.
+#[allow(clippy::too_many_arguments)]
+pub fn start_admin_server(
+    port: u16,
+    token: Option<String>,
+    token_path: &Path,
+    filter_handle: FilterHandle,
+    state: Arc<AuthorityState>,
+    transaction_orchestrator: TransactionOrchestrator,
+    watchdog: Option<Arc<Watchdog>>,
+    consensus_monitor: Option<Arc<ConsensusMonitor>>,
+) -> anyhow::Result<()> {
     let filter = filter_handle.get().unwrap();
+    let gas_profiler = state.gas_profiler.clone();
+    let operational_token: Arc<str> = match &token {
+        Some(token) => token.as_str().into(),
+        None => {
+            let token = generate_token(token_path)?;
+            warn!(
+                path =% token_path.display(),
+                "No admin token configured, generated one for the operational admin routes"
+            );
+            token.into()
+        }
+    };
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(LOGGING_MODULE_ROUTE, post(set_module_level))
+        .route(LOGGING_MODULE_ROUTE, delete(reset_module_level))
+        .route(LOGGING_SAMPLING_ROUTE, get(get_span_sample_intervals))
+        .route(LOGGING_SAMPLING_ROUTE, post(set_span_sample_interval))
+        .route(GAS_PROFILE_ROUTE, get(get_gas_profile))
+        .route(GAS_PROFILE_ROUTE, post(set_gas_profile_sample_interval))
+        .route(GAS_PROFILE_ROUTE, delete(reset_gas_profile))
+        .route(SYNC_STATUS_ROUTE, get(get_sync_status))
+        .route(ALERT_RULES_ROUTE, get(get_alert_rules))
+        .route(READ_ONLY_ROUTE, get(get_read_only))
+        .route(READ_ONLY_ROUTE, post(set_read_only))
+        .route(DENY_LIST_ROUTE, get(get_deny_list))
+        .route(DENY_LIST_ROUTE, post(deny))
+        .route(DENY_LIST_ROUTE, delete(allow))
+        .route(COMMITTEES_ROUTE, get(get_committees))
+        .route(OBJECT_CONTENTION_ROUTE, get(get_object_contention))
+        .route(OBJECT_CONTENTION_ROUTE, delete(reset_object_contention))
+        .route(EPOCH_ACCOUNTING_ROUTE, get(get_epoch_accounting))
+        .route(SLOW_CALLS_ROUTE, get(get_slow_calls))
+        .route(SLOW_CALLS_ROUTE, post(set_slow_call_threshold))
+        .route(SLOW_CALLS_ROUTE, delete(clear_slow_calls))
+        .route(FINALITY_TRACES_ROUTE, get(get_finality_traces))
+        .route(FINALITY_TRACES_ROUTE, post(set_finality_trace_capacity))
+        .route(DB_ROUTE, get(get_dbs))
+        .route(DB_COMPACT_ROUTE, post(compact_db))
+        .route(WATCHDOG_ROUTE, get(get_watchdog))
+        .layer(Extension(filter_handle))
+        .layer(Extension(gas_profiler))
+        .layer(Extension(state.clone()))
+        .layer(Extension(transaction_orchestrator))
+        .layer(Extension(watchdog));
+    let app = match token {
+        Some(token) => with_token(app, token.into()),
+        None => app,
+    };
+    let app = app.merge(operational_router(
+        operational_token,
+        state,
+        consensus_monitor,
+    ));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -28,12 +153,81 @@ pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
         "starting admin server"
     );
 
-    tokio::spawn(async move {
-        axum::Server::bind(&socket_address)
-            .serve(app.into_make_service())
-            .await
-            .unwrap();
-    });
+    http_server::serve(socket_address, app, futures::future::pending());
+    Ok(())
+}
+
+/// The routes of operational actions on the node, which require `token`.
+fn operational_router(
+    token: Arc<str>,
+    state: Arc<AuthorityState>,
+    consensus_monitor: Option<Arc<ConsensusMonitor>>,
+) -> Router {
+    let app = Router::new()
+        .route(PENDING_CERTIFICATES_ROUTE, get(get_pending_certificates))
+        .route(
+            PENDING_CERTIFICATES_REPROCESS_ROUTE,
+            post(reprocess_certificate),
+        )
+        .route(EXECUTION_QUEUE_ROUTE, get(get_execution_queue))
+        .route(EPOCH_TABLES_ROUTE, get(get_epoch_tables))
+        .layer(Extension(state))
+        .layer(Extension(consensus_monitor));
+    with_token(app, token)
+}
+
+fn with_token(app: Router, token: Arc<str>) -> Router {
+    app.layer(middleware::from_fn(
+        move |request: Request<Body>, next: Next<Body>| require_token(token.clone(), request, next),
+    ))
+}
+
+/// Generate a random token and write it to `path`, only readable by the user running the node.
+fn generate_token(path: &Path) -> anyhow::Result<String> {
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_TOKEN_LENGTH)
+        .map(char::from)
+        .collect();
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files, so also restrict one left by a previous run.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(token.as_bytes())?;
+    Ok(token)
+}
+
+/// Reject requests without the `Authorization: Bearer <token>` header of the admin token.
+async fn require_token(token: Arc<str>, request: Request<Body>, next: Next<Body>) -> Response {
+    if is_authorized(request.headers().get(AUTHORIZATION), &token) {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, "missing or invalid admin token").into_response()
+    }
+}
+
+fn is_authorized(header: Option<&HeaderValue>, token: &str) -> bool {
+    let provided = match header.and_then(|header| header.as_bytes().strip_prefix(b"Bearer ")) {
+        Some(provided) => provided,
+        None => return false,
+    };
+    // Compare in constant time, so response times do not reveal how much of the token matched.
+    provided.len() == token.len()
+        && provided
+            .iter()
+            .zip(token.as_bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
 }
 
 async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (StatusCode, String) {
@@ -55,3 +249,828 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+/// Add the module levels of `config` to the log filter, and set its span sample intervals.
+pub fn apply_logging_config(
+    filter_handle: &FilterHandle,
+    config: &LoggingConfig,
+) -> anyhow::Result<()> {
+    if !config.module_levels.is_empty() {
+        let mut filter = filter_handle.get()?;
+        for (module, level) in &config.module_levels {
+            filter = with_module_level(&filter, module, Some(level));
+        }
+        filter_handle.update(&filter)?;
+    }
+    for (span, interval) in &config.span_sample_intervals {
+        trace_sampling::set_sample_interval(span, *interval);
+    }
+    Ok(())
+}
+
+/// Return `filter` with the directive for `module` replaced by `module=level`, or removed if
+/// `level` is None.
+fn with_module_level(filter: &str, module: &str, level: Option<&str>) -> String {
+    let mut directives: Vec<String> = filter
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| {
+            !directive.is_empty()
+                && directive.rsplit_once('=').map(|(target, _)| target) != Some(module)
+        })
+        .map(String::from)
+        .collect();
+    if let Some(level) = level {
+        directives.push(format!("{}={}", module, level));
+    }
+    directives.join(",")
+}
+
+/// Set the log level of a module, the request body being `<module>=<level>`.
+async fn set_module_level(
+    Extension(filter_handle): Extension<FilterHandle>,
+    directive: String,
+) -> (StatusCode, String) {
+    let (module, level) = match directive.trim().split_once('=') {
+        Some(directive) => directive,
+        None => return (StatusCode::BAD_REQUEST, "expected <module>=<level>".into()),
+    };
+    update_filter(&filter_handle, |filter| {
+        with_module_level(filter, module, Some(level))
+    })
+}
+
+/// Remove the log level of the module in the request body, which then logs at the default
+/// level.
+async fn reset_module_level(
+    Extension(filter_handle): Extension<FilterHandle>,
+    module: String,
+) -> (StatusCode, String) {
+    update_filter(&filter_handle, |filter| {
+        with_module_level(filter, module.trim(), None)
+    })
+}
+
+fn update_filter(
+    filter_handle: &FilterHandle,
+    update: impl FnOnce(&str) -> String,
+) -> (StatusCode, String) {
+    let filter = match filter_handle.get() {
+        Ok(filter) => update(&filter),
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    };
+    match filter_handle.update(&filter) {
+        Ok(()) => {
+            info!(filter =% filter, "Log filter updated");
+            (StatusCode::OK, filter)
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+/// List the sampled spans, one per line as `<span> <interval>`.
+async fn get_span_sample_intervals() -> (StatusCode, String) {
+    let mut response = String::new();
+    for (span, interval) in trace_sampling::sample_intervals() {
+        writeln!(response, "{} {}", span, interval).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// Only create one in every N spans of a name, the request body being `<span>=<N>`. An
+/// interval of 0 or 1 disables sampling.
+async fn set_span_sample_interval(body: String) -> (StatusCode, String) {
+    let (span, interval) = match body.trim().split_once('=') {
+        Some(directive) => directive,
+        None => return (StatusCode::BAD_REQUEST, "expected <span>=<interval>".into()),
+    };
+    match interval.parse() {
+        Ok(interval) => {
+            trace_sampling::set_sample_interval(span, interval);
+            info!(span, interval, "Span sample interval updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+/// List the Move functions that used the most gas in sampled transactions, one per line as
+/// `<gas> <calls> <function>`.
+async fn get_gas_profile(
+    Extension(gas_profiler): Extension<Arc<GasProfiler>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let top = match params.get("top").map(|top| top.parse()) {
+        None => DEFAULT_GAS_PROFILE_TOP,
+        Some(Ok(top)) => top,
+        Some(Err(err)) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    let mut response = format!("sample interval: {}\n", gas_profiler.sample_interval());
+    for usage in gas_profiler.top_functions(top) {
+        writeln!(response, "{} {} {}", usage.gas, usage.calls, usage.function).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// Profile one in every N executed transactions, N being the request body. 0 disables profiling.
+async fn set_gas_profile_sample_interval(
+    Extension(gas_profiler): Extension<Arc<GasProfiler>>,
+    interval: String,
+) -> (StatusCode, String) {
+    match interval.trim().parse() {
+        Ok(interval) => {
+            gas_profiler.set_sample_interval(interval);
+            info!(interval, "Gas profiler sample interval updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn reset_gas_profile(
+    Extension(gas_profiler): Extension<Arc<GasProfiler>>,
+) -> (StatusCode, String) {
+    gas_profiler.reset();
+    (StatusCode::OK, "".into())
+}
+
+/// List the most contended objects, by kind of contention, one per line as `<count> <object id>`.
+async fn get_object_contention(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let top = match params.get("top").map(|top| top.parse()) {
+        None => DEFAULT_OBJECT_CONTENTION_TOP,
+        Some(Ok(top)) => top,
+        Some(Err(err)) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    let report = state.object_contention.report(top);
+    let mut response = String::new();
+    for (title, objects) in [
+        ("lock conflicts", report.lock_conflicts),
+        (
+            "shared object transactions",
+            report.shared_object_transactions,
+        ),
+        ("shared object queue depths", report.queue_depths),
+    ] {
+        writeln!(response, "{}:", title).unwrap();
+        for (object_id, count) in objects {
+            writeln!(response, "{} {}", count, object_id).unwrap();
+        }
+    }
+    (StatusCode::OK, response)
+}
+
+async fn reset_object_contention(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> (StatusCode, String) {
+    state.object_contention.reset();
+    (StatusCode::OK, "".into())
+}
+
+/// Show the slow call thresholds, then the most recent slow calls, oldest first, one per line
+/// as `<time> <server> <method> <latency> <ok|error> <params>`.
+async fn get_slow_calls(Extension(state): Extension<Arc<AuthorityState>>) -> (StatusCode, String) {
+    let slow_calls = &state.slow_calls;
+    let mut response = String::new();
+    for server in [CallServer::JsonRpc, CallServer::Grpc] {
+        writeln!(
+            response,
+            "{} threshold: {}ms",
+            server,
+            slow_calls.threshold(server).as_millis()
+        )
+        .unwrap();
+    }
+    for call in slow_calls.calls() {
+        let finished_at: DateTime<Utc> = call.finished_at.into();
+        writeln!(
+            response,
+            "{} {} {} {}ms {} {}",
+            finished_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+            call.server,
+            call.method,
+            call.latency.as_millis(),
+            if call.success { "ok" } else { "error" },
+            call.params,
+        )
+        .unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// Set the latency threshold of a server, the request body being `<server>=<milliseconds>`
+/// with the server being `json-rpc` or `grpc`.
+async fn set_slow_call_threshold(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let (server, threshold_ms) = match body.trim().split_once('=') {
+        Some(directive) => directive,
+        None => return (StatusCode::BAD_REQUEST, "expected <server>=<ms>".into()),
+    };
+    let server: CallServer = match server.parse() {
+        Ok(server) => server,
+        Err(err) => return (StatusCode::BAD_REQUEST, err),
+    };
+    match threshold_ms.parse() {
+        Ok(threshold_ms) => {
+            state
+                .slow_calls
+                .set_threshold(server, Duration::from_millis(threshold_ms));
+            info!(%server, threshold_ms, "Slow call threshold updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn clear_slow_calls(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> (StatusCode, String) {
+    state.slow_calls.clear();
+    (StatusCode::OK, "".into())
+}
+
+/// The accounting report of an ended epoch as CSV, one validator per row. The epoch is given
+/// with `?epoch=`, and defaults to the last ended epoch.
+async fn get_epoch_accounting(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let epoch = match params.get("epoch").map(|epoch| epoch.parse()) {
+        None => match state.epoch().checked_sub(1) {
+            Some(epoch) => epoch,
+            None => return (StatusCode::NOT_FOUND, "no epoch has ended yet".into()),
+        },
+        Some(Ok(epoch)) => epoch,
+        Some(Err(err)) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    match state.get_epoch_accounting_report(epoch) {
+        Ok(Some(report)) => (StatusCode::OK, report.to_csv()),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            format!("no accounting report for epoch {}", epoch),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// Show the current epoch, the highest known and locally executed checkpoints, and the number
+/// of checkpoints the node is behind, one per line as `<name>: <value>`.
+async fn get_sync_status(Extension(state): Extension<Arc<AuthorityState>>) -> (StatusCode, String) {
+    let watermarks = state.sync_watermarks();
+    let format_seq = |seq: Option<u64>| seq.map_or_else(|| "none".to_string(), |s| s.to_string());
+    let mut response = String::new();
+    writeln!(response, "epoch: {}", watermarks.epoch).unwrap();
+    writeln!(
+        response,
+        "highest known checkpoint: {}",
+        format_seq(watermarks.highest_known_checkpoint)
+    )
+    .unwrap();
+    writeln!(
+        response,
+        "highest executed checkpoint: {}",
+        format_seq(watermarks.highest_executed_checkpoint)
+    )
+    .unwrap();
+    writeln!(response, "checkpoint lag: {}", watermarks.checkpoint_lag()).unwrap();
+    (StatusCode::OK, response)
+}
+
+/// The committees of all epochs known to this node, one validator per line as
+/// `<epoch> <name> <stake> <network address>`.
+async fn get_committees(Extension(state): Extension<Arc<AuthorityState>>) -> (StatusCode, String) {
+    let mut response = String::new();
+    for committee in state
+        .committee_store()
+        .get_all_committees_with_net_addresses()
+    {
+        for (name, stake) in &committee.committee.voting_rights {
+            let net_address = committee
+                .net_addresses
+                .get(name)
+                .and_then(|bytes| Multiaddr::try_from(bytes.clone()).ok())
+                .map_or_else(|| "unknown".to_string(), |address| address.to_string());
+            writeln!(
+                response,
+                "{} {name} {stake} {net_address}",
+                committee.committee.epoch
+            )
+            .unwrap();
+        }
+    }
+    (StatusCode::OK, response)
+}
+
+/// Prometheus alerting rules on the sync watermark metrics, ready to be loaded as a rule file.
+async fn get_alert_rules() -> (StatusCode, String) {
+    (StatusCode::OK, alert_rules())
+}
+
+async fn get_read_only(
+    Extension(transaction_orchestrator): Extension<TransactionOrchestrator>,
+) -> (StatusCode, String) {
+    match transaction_orchestrator {
+        Some(transaction_orchestrator) => (
+            StatusCode::OK,
+            transaction_orchestrator.is_read_only().to_string(),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            "transaction orchestrator is not enabled in this node".into(),
+        ),
+    }
+}
+
+/// Enter or leave read-only mode, the request body being `true` or `false`. In read-only mode
+/// transaction submissions are rejected while reads and state sync continue.
+async fn set_read_only(
+    Extension(transaction_orchestrator): Extension<TransactionOrchestrator>,
+    read_only: String,
+) -> (StatusCode, String) {
+    let transaction_orchestrator = match transaction_orchestrator {
+        Some(transaction_orchestrator) => transaction_orchestrator,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "transaction orchestrator is not enabled in this node".into(),
+            )
+        }
+    };
+    match read_only.trim().parse() {
+        Ok(read_only) => {
+            transaction_orchestrator.set_read_only(read_only);
+            info!(read_only, "Read-only mode updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+/// List the time spent in each finality stage by the most recent transactions submitted through
+/// this node, one transaction per line, or only by the transaction given by `?tx=`.
+async fn get_finality_traces(
+    Extension(transaction_orchestrator): Extension<TransactionOrchestrator>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let finality_tracker = match transaction_orchestrator {
+        Some(transaction_orchestrator) => transaction_orchestrator
+            .quorum_driver()
+            .finality_tracker()
+            .clone(),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "transaction orchestrator is not enabled in this node".into(),
+            )
+        }
+    };
+    let traces = match params.get("tx") {
+        None => finality_tracker.traces(),
+        Some(tx) => {
+            let tx_digest = match TransactionDigest::from_str(tx) {
+                Ok(tx_digest) => tx_digest,
+                Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+            };
+            match finality_tracker.get_trace(&tx_digest) {
+                Some(trace) => vec![trace],
+                None => return (StatusCode::NOT_FOUND, format!("no trace of {tx_digest:?}")),
+            }
+        }
+    };
+    let mut response = format!("capacity: {}\n", finality_tracker.trace_capacity());
+    for trace in traces {
+        writeln!(response, "{}", format_finality_trace(&trace)).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+fn format_finality_trace(trace: &FinalityTrace) -> String {
+    let recorded_at: DateTime<Utc> = trace.recorded_at.into();
+    let mut line = format!(
+        "{} {:?}",
+        recorded_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        trace.tx_digest
+    );
+    for (stage, latency) in &trace.stages {
+        write!(line, " {}={}ms", stage, latency.as_millis()).unwrap();
+    }
+    if let Some(checkpoint) = trace.checkpoint {
+        write!(line, " checkpoint={checkpoint}").unwrap();
+    }
+    line
+}
+
+/// Set the number of transactions whose finality traces are kept, the request body being the
+/// number. 0 disables the traces; the latency histograms are always recorded.
+async fn set_finality_trace_capacity(
+    Extension(transaction_orchestrator): Extension<TransactionOrchestrator>,
+    capacity: String,
+) -> (StatusCode, String) {
+    let transaction_orchestrator = match transaction_orchestrator {
+        Some(transaction_orchestrator) => transaction_orchestrator,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "transaction orchestrator is not enabled in this node".into(),
+            )
+        }
+    };
+    match capacity.trim().parse() {
+        Ok(capacity) => {
+            transaction_orchestrator
+                .quorum_driver()
+                .finality_tracker()
+                .set_trace_capacity(capacity);
+            info!(capacity, "Finality trace capacity updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+/// List the databases of the node, one per line as `<db> <column families>`.
+async fn get_dbs(Extension(state): Extension<Arc<AuthorityState>>) -> (StatusCode, String) {
+    let mut response = String::new();
+    for (db, cfs) in state.db_metrics().registered_dbs() {
+        writeln!(response, "{} {}", db, cfs.join(" ")).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// Compact a column family, given as the `db` and `cf` query parameters. Responds once the
+/// compaction is done.
+async fn compact_db(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let (db, cf) = match (params.get("db"), params.get("cf")) {
+        (Some(db), Some(cf)) => (db.clone(), cf.clone()),
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "the db and cf query parameters are required".into(),
+            )
+        }
+    };
+    info!(%db, %cf, "Compacting column family");
+    let db_metrics = state.db_metrics().clone();
+    match tokio::task::spawn_blocking(move || db_metrics.compact_cf(&db, &cf)).await {
+        Ok(Ok(())) => (StatusCode::OK, "".into()),
+        Ok(Err(err)) => (StatusCode::BAD_REQUEST, err.to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// List the certificates submitted to consensus by this node and not committed yet, oldest
+/// first, one per line as `<digest> <seconds since submitted>`.
+async fn get_pending_certificates(
+    Extension(consensus_monitor): Extension<Option<Arc<ConsensusMonitor>>>,
+) -> (StatusCode, String) {
+    let consensus_monitor = match consensus_monitor {
+        Some(consensus_monitor) => consensus_monitor,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "this node does not submit certificates to consensus".into(),
+            )
+        }
+    };
+    let mut response = String::new();
+    for (digest, pending_for) in consensus_monitor.pending_certificates() {
+        writeln!(response, "{:?} {:.3}", digest, pending_for.as_secs_f64()).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// List the certificates waiting to be executed, in queue order, one per line as
+/// `<sequence number> <digest>`.
+async fn get_execution_queue(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> (StatusCode, String) {
+    match state.get_pending_certificates() {
+        Ok(pending) => {
+            let mut response = String::new();
+            for (seq, digest) in pending {
+                writeln!(response, "{} {:?}", seq, digest).unwrap();
+            }
+            (StatusCode::OK, response)
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// Execute the certificate whose digest is the request body now, e.g. one stuck in the queue
+/// after the objects it was waiting for were synced. Responds once it is executed.
+async fn reprocess_certificate(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    digest: String,
+) -> (StatusCode, String) {
+    let digest = match TransactionDigest::from_str(digest.trim()) {
+        Ok(digest) => digest,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    info!(tx_digest = ?digest, "Reprocessing certificate");
+    match state.reprocess_certificate(&digest).await {
+        Ok(CertificateReprocessing::AlreadyExecuted) => (StatusCode::OK, "already executed".into()),
+        Ok(CertificateReprocessing::Executed) => (StatusCode::OK, "executed".into()),
+        Ok(CertificateReprocessing::Requeued) => (
+            StatusCode::ACCEPTED,
+            "certificate not found locally, queued to be downloaded".into(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// The size of the tables of the current epoch, one table per line as
+/// `<table> <property>=<value>...`.
+async fn get_epoch_tables(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> (StatusCode, String) {
+    let tables = match state.epoch_table_properties() {
+        Ok(tables) => tables,
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    };
+    let mut response = format!("epoch: {}\n", state.epoch());
+    for (table, properties) in tables {
+        write!(response, "{}", table).unwrap();
+        for (property, value) in properties {
+            write!(response, " {}={}", property, value).unwrap();
+        }
+        writeln!(response).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// List the failing watchdog checks as `failing <check>: <failure>`, then the most recent times
+/// checks started failing or passed again, oldest first, one per line as
+/// `<time> <check> failed <actions> <failure>` or `<time> <check> recovered`.
+async fn get_watchdog(
+    Extension(watchdog): Extension<Option<Arc<Watchdog>>>,
+) -> (StatusCode, String) {
+    let watchdog = match watchdog {
+        Some(watchdog) => watchdog,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "watchdog is not enabled in this node".into(),
+            )
+        }
+    };
+    let mut response = String::new();
+    for (check, failure) in watchdog.failing() {
+        writeln!(response, "failing {}: {}", check, failure).unwrap();
+    }
+    for event in watchdog.events() {
+        let at: DateTime<Utc> = event.at.into();
+        let at = at.to_rfc3339_opts(SecondsFormat::Millis, true);
+        match event.failure {
+            Some(failure) => writeln!(
+                response,
+                "{} {} failed {} {}",
+                at,
+                event.check,
+                event
+                    .actions
+                    .iter()
+                    .map(|action| action_name(*action))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                failure,
+            ),
+            None => writeln!(response, "{} {} recovered", at, event.check),
+        }
+        .unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// List the denied packages and functions, one per line.
+async fn get_deny_list(Extension(state): Extension<Arc<AuthorityState>>) -> (StatusCode, String) {
+    let mut response = String::new();
+    for entry in state.deny_list.entries() {
+        writeln!(response, "{}", entry).unwrap();
+    }
+    (StatusCode::OK, response)
+}
+
+/// Deny transactions calling the package or function in the request body, written as
+/// `<package>` or `<package>::<module>::<function>`.
+async fn deny(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    entry: String,
+) -> (StatusCode, String) {
+    match entry.parse::<DenyListEntry>() {
+        Ok(entry) => {
+            info!(entry =% entry, "Added to the deny list");
+            state.deny_list.deny(entry);
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+/// Allow transactions calling the package or function in the request body again.
+async fn allow(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    entry: String,
+) -> (StatusCode, String) {
+    match entry.parse::<DenyListEntry>() {
+        Ok(entry) if state.deny_list.allow(&entry) => {
+            info!(entry =% entry, "Removed from the deny list");
+            (StatusCode::OK, "".into())
+        }
+        Ok(entry) => (
+            StatusCode::NOT_FOUND,
+            format!("{} is not on the deny list", entry),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+fn alert_rules() -> String {
+    format!(
+        r#"groups:
+  - name: sui-node-sync
+    rules:
+      - alert: SuiNodeCheckpointSyncLag
+        expr: checkpoint_sync_lag > {lag}
+        for: {lag_for}
+        labels:
+          severity: warning
+        annotations:
+          summary: "Node is more than {lag} checkpoints behind the network"
+      - alert: SuiNodeCheckpointExecutionStalled
+        expr: changes(highest_executed_checkpoint[{stalled_for}]) == 0 and checkpoint_sync_lag > 0
+        labels:
+          severity: critical
+        annotations:
+          summary: "Node has not executed a checkpoint in {stalled_for} while behind the network"
+      - alert: SuiNodeNoKnownCheckpointProgress
+        expr: changes(highest_known_certified_checkpoint[{stalled_for}]) == 0
+        labels:
+          severity: warning
+        annotations:
+          summary: "No new certified checkpoint seen from the network in {stalled_for}"
+      - alert: SuiNodeWatchdogCheckFailing
+        expr: watchdog_check_failing > 0
+        labels:
+          severity: critical
+        annotations:
+          summary: "Watchdog check {{{{ $labels.check }}}} is failing"
+"#,
+        lag = CHECKPOINT_SYNC_LAG_ALERT_THRESHOLD,
+        lag_for = CHECKPOINT_SYNC_LAG_ALERT_FOR,
+        stalled_for = CHECKPOINT_STALLED_ALERT_FOR,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::HttpBody;
+    use std::collections::BTreeMap;
+    use sui_core::consensus_monitor::ConsensusMonitor;
+    use sui_types::committee::Committee;
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+    use tower::ServiceExt;
+
+    async fn test_state() -> Arc<AuthorityState> {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        let authorities = BTreeMap::from([(key.public().into(), 1)]);
+        let committee = Committee::new(0, authorities).unwrap();
+        let (tx_reconfigure_consensus, _rx_reconfigure_consensus) = tokio::sync::mpsc::channel(10);
+        Arc::new(
+            AuthorityState::new_for_testing(
+                committee,
+                &key,
+                None,
+                None,
+                None,
+                tx_reconfigure_consensus,
+            )
+            .await,
+        )
+    }
+
+    async fn call(app: &Router, route: &str, token: Option<&str>) -> (StatusCode, String) {
+        let mut request = Request::get(route);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let mut body = response.into_body();
+        let mut text = String::new();
+        while let Some(chunk) = body.data().await {
+            text.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+        }
+        (status, text)
+    }
+
+    #[tokio::test]
+    async fn test_operational_routes_require_token() {
+        let state = test_state().await;
+        let app = operational_router("secret".into(), state, None);
+        for route in [
+            PENDING_CERTIFICATES_ROUTE,
+            EXECUTION_QUEUE_ROUTE,
+            EPOCH_TABLES_ROUTE,
+        ] {
+            for token in [None, Some("wrong")] {
+                let (status, _) = call(&app, route, token).await;
+                assert_eq!(status, StatusCode::UNAUTHORIZED, "{}", route);
+            }
+        }
+        let (status, _) = call(&app, EXECUTION_QUEUE_ROUTE, Some("secret")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_pending_certificates() {
+        let state = test_state().await;
+        let app = operational_router("secret".into(), state.clone(), None);
+        let (status, _) = call(&app, PENDING_CERTIFICATES_ROUTE, Some("secret")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Certificates submitted to consensus are listed until they settle, unlike the
+        // certificates waiting to be executed.
+        let consensus_monitor = Arc::new(ConsensusMonitor::new_for_test());
+        let app = operational_router("secret".into(), state, Some(consensus_monitor.clone()));
+        let digest = TransactionDigest::random();
+        consensus_monitor.observe_submitted(digest);
+        let (status, body) = call(&app, PENDING_CERTIFICATES_ROUTE, Some("secret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with(&format!("{:?} ", digest)), "{}", body);
+        let (_, body) = call(&app, EXECUTION_QUEUE_ROUTE, Some("secret")).await;
+        assert_eq!(body, "");
+
+        consensus_monitor.observe_settled(&digest);
+        let (_, body) = call(&app, PENDING_CERTIFICATES_ROUTE, Some("secret")).await;
+        assert_eq!(body, "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generate_token() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("admin-token");
+        let token = generate_token(&path).unwrap();
+        assert_eq!(token.len(), GENERATED_TOKEN_LENGTH);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), token);
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // A new token is generated at each start.
+        assert_ne!(generate_token(&path).unwrap(), token);
+    }
+
+    #[test]
+    fn test_alert_rules() {
+        let rules = alert_rules();
+        assert_eq!(rules.matches("- alert:").count(), 4);
+        for metric in [
+            "checkpoint_sync_lag",
+            "highest_executed_checkpoint",
+            "highest_known_certified_checkpoint",
+            "watchdog_check_failing",
+        ] {
+            assert!(rules.contains(metric));
+        }
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let header = |value| Some(HeaderValue::from_static(value));
+        assert!(is_authorized(header("Bearer secret").as_ref(), "secret"));
+        assert!(!is_authorized(header("Bearer secreT").as_ref(), "secret"));
+        assert!(!is_authorized(header("Bearer secrets").as_ref(), "secret"));
+        assert!(!is_authorized(header("secret").as_ref(), "secret"));
+        assert!(!is_authorized(None, "secret"));
+    }
+
+    #[test]
+    fn test_with_module_level() {
+        let filter = "info,sui_core=debug,narwhal[span{id=1}]=trace";
+        assert_eq!(
+            with_module_level(filter, "sui_core", Some("trace")),
+            "info,narwhal[span{id=1}]=trace,sui_core=trace"
+        );
+        assert_eq!(
+            with_module_level(filter, "sui_network", Some("warn")),
+            "info,sui_core=debug,narwhal[span{id=1}]=trace,sui_network=warn"
+        );
+        assert_eq!(
+            with_module_level(filter, "sui_core", None),
+            "info,narwhal[span{id=1}]=trace"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves the HTTP endpoints of the node which are not part of the JSON-RPC API: the Prometheus
+//! metrics and the admin interface.
+//!
+//! axum needs difficult-to-support features such as TcpSocket::from_raw_fd(), so the simulator
+//! can't bind these endpoints. Under msim they are kept in memory instead, where simulation tests
+//! send them requests with [`request`], e.g. to check the metrics of a node after injecting
+//! failures.
+
+use axum::Router;
+use std::future::Future;
+use std::net::SocketAddr;
+
+/// Serves `app` at `addr` until `signal` completes.
+#[cfg(not(msim))]
+pub(crate) fn serve(
+    addr: SocketAddr,
+    app: Router,
+    signal: impl Future<Output = ()> + Send + 'static,
+) {
+    tokio::spawn(async move {
+        axum::Server::bind(&addr)
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(signal)
+            .await
+            .unwrap();
+    });
+}
+
+/// Serves `app` at `addr` until `signal` completes, or the simulated node which served it is
+/// killed. An endpoint served again at the same address replaces the previous one.
+#[cfg(msim)]
+pub(crate) fn serve(
+    addr: SocketAddr,
+    app: Router,
+    signal: impl Future<Output = ()> + Send + 'static,
+) {
+    let endpoint = sim::EndpointGuard::insert(addr, app);
+    // The task is dropped with the node which spawned it, if the node is killed.
+    tokio::spawn(async move {
+        signal.await;
+        drop(endpoint);
+    });
+}
+
+/// Sends a request to the endpoint served at `addr`, returning the status and the body of the
+/// response, or None if no endpoint is served there.
+#[cfg(msim)]
+pub async fn request(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    body: &str,
+) -> Option<(axum::http::StatusCode, String)> {
+    use axum::body::{Body, HttpBody};
+    use tower::ServiceExt;
+
+    let (_, app) = sim::ENDPOINTS.lock().get(&addr)?.clone();
+    let request = axum::http::Request::builder()
+        .method(method)
+        .uri(path)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let mut body = response.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+    Some((status, String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+#[cfg(msim)]
+mod sim {
+    use axum::Router;
+    use once_cell::sync::Lazy;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// The endpoints served, by address, along with the id of the guard which serves them.
+    pub(super) static ENDPOINTS: Lazy<Mutex<HashMap<SocketAddr, (u64, Router)>>> =
+        Lazy::new(Default::default);
+
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// Serves an endpoint until dropped.
+    pub(super) struct EndpointGuard {
+        addr: SocketAddr,
+        id: u64,
+    }
+
+    impl EndpointGuard {
+        pub(super) fn insert(addr: SocketAddr, app: Router) -> Self {
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            ENDPOINTS.lock().insert(addr, (id, app));
+            Self { addr, id }
+        }
+    }
+
+    impl Drop for EndpointGuard {
+        fn drop(&mut self) {
+            let mut endpoints = ENDPOINTS.lock();
+            // Leave the endpoint which replaced this one, if any.
+            if matches!(endpoints.get(&self.addr), Some((id, _)) if *id == self.id) {
+                endpoints.remove(&self.addr);
+            }
+        }
+    }
+}
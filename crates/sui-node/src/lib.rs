@@ -12,19 +12,30 @@ use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
 use parking_lot::Mutex;
 use prometheus::Registry;
+use serde::Deserialize;
+use std::future::Future;
 use std::option::Option::None;
 use std::time::Instant;
 use std::{sync::Arc, time::Duration};
 use sui_config::NodeConfig;
+use sui_core::analytics_export::AnalyticsExporter;
+use sui_core::authority::input_prefetcher::{InputPrefetchMetrics, InputPrefetcher};
+use sui_core::authority::object_cache::{ObjectCache, ObjectCacheMetrics};
 use sui_core::authority_active::checkpoint_driver::CheckpointMetrics;
 use sui_core::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use sui_core::authority_server::ValidatorService;
+use sui_core::consensus_monitor::ConsensusMonitor;
+use sui_core::execution_audit::{ExecutionAuditMetrics, ExecutionAuditor};
+use sui_core::execution_sandbox::{ExecutionSandbox, ExecutionSandboxMetrics};
+use sui_core::indexer_plugin::{IndexerPluginRunner, IndexerPlugins};
+use sui_core::read_fallback::ReadFallback;
 use sui_core::safe_client::SafeClientMetrics;
+use sui_core::slow_calls::CallServer;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_core::transaction_streamer::TransactionStreamer;
 use sui_core::{
     authority::{AuthorityState, AuthorityStore},
-    authority_active::{gossip::GossipMetrics, ActiveAuthority},
+    authority_active::{gossip::GossipMetrics, peer_monitor::PeerMonitorMetrics, ActiveAuthority},
     authority_client::{
         make_network_authority_client_sets_from_genesis,
         make_network_authority_client_sets_from_system_state, NetworkAuthorityClient,
@@ -32,21 +43,34 @@ use sui_core::{
     checkpoints::CheckpointStore,
 };
 use sui_json_rpc::bcs_api::BcsApiImpl;
-use sui_json_rpc::streaming_api::TransactionStreamingApiImpl;
+use sui_json_rpc::bridge_api::BridgeApiImpl;
+use sui_json_rpc::debug_api::DebugApiImpl;
+use sui_json_rpc::network_info_api::NetworkInfoApiImpl;
+use sui_json_rpc::streaming_api::{ObjectChangeStreamingApiImpl, TransactionStreamingApiImpl};
 use sui_json_rpc::transaction_builder_api::FullNodeTransactionBuilderApi;
 use sui_network::api::ValidatorServer;
+use sui_network::channel_pool::{ChannelPoolConfig, DEFAULT_CHANNEL_POOL_SIZE};
 use sui_network::default_mysten_network_config;
+use sui_network::message_size::{MessageSizeLimitService, MessageSizeLimits};
+use sui_network::version::{PeerRole, PeerVersionMonitor, VersionService, PROTOCOL_VERSION};
 use sui_storage::{
+    compression::{self, CfCompression},
+    db_metrics::DBMetrics,
     event_store::{EventStoreType, SqlEventStore},
     node_sync_store::NodeSyncStore,
+    submission_store::SubmissionStore,
     IndexStore,
 };
+use sui_types::base_types::ObjectID;
+use sui_types::id::UID;
 use sui_types::messages::{CertifiedTransaction, CertifiedTransactionEffects};
+use sui_types::object::ObjectRead;
 use tokio::sync::mpsc::channel;
 use tower::ServiceBuilder;
 use tracing::{error, info, warn};
 
 use crate::metrics::GrpcMetrics;
+use crate::watchdog::Watchdog;
 use sui_core::authority_client::NetworkAuthorityClientMetrics;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_json_rpc::event_api::EventReadApiImpl;
@@ -54,32 +78,71 @@ use sui_json_rpc::event_api::EventStreamingApiImpl;
 use sui_json_rpc::http_server::HttpServerHandle;
 use sui_json_rpc::read_api::FullNodeApi;
 use sui_json_rpc::read_api::ReadApi;
+use sui_json_rpc::response_limits::ResponseLimits;
 use sui_json_rpc::transaction_execution_api::FullNodeTransactionExecutionApi;
 use sui_json_rpc::ws_server::WsServerHandle;
 use sui_json_rpc::JsonRpcServerBuilder;
 use sui_types::crypto::KeypairTraits;
 
 pub mod admin;
+pub mod http_server;
 pub mod metrics;
+pub mod stats_reporting;
+pub mod watchdog;
 
 mod handle;
 pub use handle::SuiNodeHandle;
 
+/// How often the sizes of the column families of the databases are exported.
+const DB_METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the object contention metrics are exported.
+const OBJECT_CONTENTION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the analytics export looks for newly committed checkpoints.
+const ANALYTICS_EXPORT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The most transactions written to the indexes in a single batch.
+const INDEXING_MAX_BATCH_TRANSACTIONS: usize = 1000;
+
+/// How often indexing looks for newly executed transactions once caught up. Transactions are
+/// indexed after they are executed, so the indexes, and the JSON-RPC queries served from them,
+/// lag behind execution by up to this interval.
+const INDEXING_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often a full node syncs to the latest checkpoint certified by the network.
+const CHECKPOINT_SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the indexer plugins are handed newly committed transactions once caught up.
+const INDEXER_PLUGINS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct SuiNode {
     grpc_server: tokio::task::JoinHandle<Result<()>>,
-    _json_rpc_service: Option<HttpServerHandle>,
-    _ws_subscription_service: Option<WsServerHandle>,
-    _batch_subsystem_handle: tokio::task::JoinHandle<()>,
-    _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
-    _gossip_handle: Option<tokio::task::JoinHandle<()>>,
-    _execute_driver_handle: tokio::task::JoinHandle<()>,
-    _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    json_rpc_service: Option<HttpServerHandle>,
+    ws_subscription_service: Option<WsServerHandle>,
+    batch_subsystem_handle: tokio::task::JoinHandle<()>,
+    post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    indexing_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    gossip_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Shared with the watchdog, which may restart the execution driver.
+    execute_driver_handle: Arc<Mutex<tokio::task::JoinHandle<()>>>,
+    peer_monitor_handle: tokio::task::JoinHandle<()>,
+    endpoint_discovery_handle: tokio::task::JoinHandle<()>,
+    checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    analytics_export_handle: Option<tokio::task::JoinHandle<()>>,
+    indexer_plugins_handle: Option<tokio::task::JoinHandle<()>>,
+    db_metrics_handle: tokio::task::JoinHandle<()>,
+    object_contention_handle: tokio::task::JoinHandle<()>,
+    watchdog: Option<Arc<Watchdog>>,
+    watchdog_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Only validators submit certificates to consensus.
+    consensus_monitor: Option<Arc<ConsensusMonitor>>,
     state: Arc<AuthorityState>,
     active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     _prometheus_registry: Registry,
 
-    _p2p_network: anemo::Network,
+    p2p_network: anemo::Network,
 
     #[cfg(msim)]
     sim_node: sui_simulator::runtime::NodeHandle,
@@ -87,6 +150,16 @@ pub struct SuiNode {
 
 impl SuiNode {
     pub async fn start(config: &NodeConfig, prometheus_registry: Registry) -> Result<SuiNode> {
+        Self::start_with_indexer_plugins(config, prometheus_registry, IndexerPlugins::new()).await
+    }
+
+    /// Starts the node along with custom indexers, which are handed the transactions it commits
+    /// and the checkpoints it completes.
+    pub async fn start_with_indexer_plugins(
+        config: &NodeConfig,
+        prometheus_registry: Registry,
+        indexer_plugins: IndexerPlugins,
+    ) -> Result<SuiNode> {
         // TODO: maybe have a config enum that takes care of this for us.
         let is_validator = config.consensus_config().is_some();
         let is_full_node = !is_validator;
@@ -97,31 +170,79 @@ impl SuiNode {
 
         let genesis = config.genesis()?;
 
+        // The limits apply to the clients of the service created by the node as well as to its
+        // server.
+        let validator_message_size_limits = config
+            .grpc_message_size_limits
+            .as_ref()
+            .and_then(|limits| limits.validator.as_ref())
+            .map_or_else(MessageSizeLimits::default, |limits| {
+                MessageSizeLimits::new(
+                    limits.max_encoding_message_size,
+                    limits.max_decoding_message_size,
+                )
+            });
+        let channel_pool_config = ChannelPoolConfig {
+            size: config
+                .authority_channel_pool_size
+                .unwrap_or(DEFAULT_CHANNEL_POOL_SIZE),
+            message_size_limits: validator_message_size_limits,
+        };
+
+        let db_metrics = Arc::new(DBMetrics::new(&prometheus_registry));
+        for (cf, compression) in &config.db_compression {
+            compression::set_cf_compression(
+                cf,
+                Some(CfCompression {
+                    level: compression.level,
+                    max_dict_bytes: compression.dictionary_bytes,
+                    max_train_bytes: compression.training_bytes(),
+                }),
+            )?;
+        }
+        let db_options = compression::db_options();
+
         let secret = Arc::pin(config.protocol_key_pair().copy());
         let committee = genesis.committee()?;
-        let store = Arc::new(AuthorityStore::open(&config.db_path().join("store"), None)?);
+        let mut store = AuthorityStore::open(&config.db_path().join("store"), db_options.clone())?
+            .with_db_metrics(db_metrics.clone());
+        if let Some(object_cache) = &config.object_cache {
+            store = store.with_object_cache(ObjectCache::new(
+                object_cache,
+                ObjectCacheMetrics::new(&prometheus_registry),
+            ));
+        }
+        if let Some(input_prefetch) = &config.input_prefetch {
+            store = store.with_input_prefetcher(InputPrefetcher::new(
+                input_prefetch,
+                InputPrefetchMetrics::new(&prometheus_registry),
+            ));
+        }
+        let store = Arc::new(store);
         let committee_store = Arc::new(CommitteeStore::new(
             config.db_path().join("epochs"),
             &committee,
             None,
         ));
+        committee_store.insert_net_addresses(&genesis.committee_with_net_addresses()?)?;
 
         let checkpoint_store = Arc::new(Mutex::new(CheckpointStore::open(
             &config.db_path().join("checkpoints"),
-            None,
+            db_options,
             &committee,
             config.protocol_public_key(),
             secret.clone(),
             config.enable_reconfig,
         )?));
+        checkpoint_store.lock().register_db_metrics(&db_metrics);
 
         let index_store = if is_validator {
             None
         } else {
-            Some(Arc::new(IndexStore::open_tables_read_write(
+            Some(Arc::new(IndexStore::open(
                 config.db_path().join("indexes"),
                 None,
-                None,
+                db_metrics.clone(),
             )))
         };
 
@@ -163,6 +284,35 @@ impl SuiNode {
             )
             .await,
         );
+        for entry in &config.deny_list {
+            state.deny_list.deny(entry.parse()?);
+        }
+        state
+            .rpc_endpoints
+            .store(Arc::new(config.rpc_endpoints.clone()));
+        if let Some(size) = config.executed_certificate_cache_size {
+            state.executed_certificates.set_capacity(size);
+        }
+        if let Some(slow_calls) = &config.slow_calls {
+            let log = &state.slow_calls;
+            log.set_threshold(
+                CallServer::JsonRpc,
+                Duration::from_millis(slow_calls.json_rpc_threshold_ms),
+            );
+            log.set_threshold(
+                CallServer::Grpc,
+                Duration::from_millis(slow_calls.grpc_threshold_ms),
+            );
+            log.set_capacity(slow_calls.log_size);
+        }
+        if let Some(execution_audit) = &config.execution_audit {
+            state
+                .execution_auditor
+                .store(Some(Arc::new(ExecutionAuditor::new(
+                    execution_audit,
+                    ExecutionAuditMetrics::new(&prometheus_registry),
+                )?)));
+        }
         let net_config = default_mysten_network_config();
 
         let sui_system_state = state.get_sui_system_state_object().await?;
@@ -173,12 +323,14 @@ impl SuiNode {
             make_network_authority_client_sets_from_system_state(
                 &sui_system_state,
                 &net_config,
+                &channel_pool_config,
                 network_metrics.clone(),
             )
         } else {
             make_network_authority_client_sets_from_genesis(
                 genesis,
                 &net_config,
+                &channel_pool_config,
                 network_metrics.clone(),
             )
         }?;
@@ -190,23 +342,42 @@ impl SuiNode {
             Arc::new(SafeClientMetrics::new(&prometheus_registry)),
             network_metrics.clone(),
         );
+        if let Some(object_id) = config.min_supported_version_object {
+            check_min_supported_version(&net, object_id).await?;
+        }
 
         let active_authority = Arc::new(ActiveAuthority::new(
             state.clone(),
             net.clone(),
             GossipMetrics::new(&prometheus_registry),
             network_metrics.clone(),
+            channel_pool_config,
         )?);
+        // Endpoint discovery and reconfiguration only replace the client of a validator once its
+        // address differs from the one the client was created for.
+        if config.enable_reconfig && sui_system_state.epoch > 0 {
+            active_authority
+                .endpoints
+                .insert_from_system_state(&sui_system_state);
+        } else {
+            active_authority.endpoints.insert_from_genesis(genesis);
+        }
 
         let arc_net = active_authority.agg_aggregator();
 
         let transaction_orchestrator = if is_full_node {
-            Some(Arc::new(TransactiondOrchestrator::new(
+            let transaction_orchestrator = TransactiondOrchestrator::new(
                 arc_net,
                 state.clone(),
                 active_authority.clone().node_sync_handle(),
+                Some(Arc::new(SubmissionStore::open(
+                    config.db_path().join("submissions"),
+                ))),
                 &prometheus_registry,
-            )))
+            );
+            transaction_orchestrator.set_read_only(config.read_only);
+            transaction_orchestrator.set_write_through(config.write_through);
+            Some(Arc::new(transaction_orchestrator))
         } else {
             None
         };
@@ -234,6 +405,29 @@ impl SuiNode {
                 None
             };
 
+        let indexing_handle = index_store.as_ref().map(|_| {
+            let indexing_state = state.clone();
+            tokio::task::spawn(async move {
+                // The coin index is built in the background, and served once it is.
+                let now = Instant::now();
+                match indexing_state
+                    .build_coin_index(INDEXING_MAX_BATCH_TRANSACTIONS)
+                    .await
+                {
+                    Ok(0) => (),
+                    Ok(coins) => info!(
+                        "Built the coin index with {coins} coins in {:?}",
+                        now.elapsed()
+                    ),
+                    Err(e) => warn!("Couldn't build the coin index: {e}"),
+                }
+                indexing_state
+                    .run_indexing_process(INDEXING_MAX_BATCH_TRANSACTIONS, INDEXING_POLL_INTERVAL)
+                    .await
+                    .map_err(Into::into)
+            })
+        });
+
         let gossip_handle = if is_full_node {
             info!("Starting full node sync to latest checkpoint (this may take a while)");
             let now = Instant::now();
@@ -257,7 +451,17 @@ impl SuiNode {
         } else {
             None
         };
-        let execute_driver_handle = active_authority.clone().spawn_execute_process().await;
+        let execute_driver_handle = Arc::new(Mutex::new(
+            active_authority.clone().spawn_execute_process().await,
+        ));
+        let peer_monitor_handle = active_authority
+            .clone()
+            .spawn_peer_monitor_process(PeerMonitorMetrics::new(&prometheus_registry))
+            .await;
+        let endpoint_discovery_handle = active_authority
+            .clone()
+            .spawn_endpoint_discovery_process()
+            .await;
         let checkpoint_process_handle = if config.enable_checkpoint && is_validator {
             Some(
                 active_authority
@@ -265,10 +469,67 @@ impl SuiNode {
                     .spawn_checkpoint_process(CheckpointMetrics::new(&prometheus_registry))
                     .await,
             )
+        } else if config.enable_checkpoint {
+            Some(
+                active_authority
+                    .clone()
+                    .spawn_checkpoint_sync_process(CHECKPOINT_SYNC_INTERVAL)
+                    .await,
+            )
         } else {
             None
         };
 
+        let analytics_export_handle = match &config.analytics_export {
+            Some(analytics_export) => {
+                let exporter =
+                    AnalyticsExporter::new(analytics_export, state.clone(), &prometheus_registry)?;
+                Some(tokio::spawn(exporter.run(ANALYTICS_EXPORT_POLL_INTERVAL)))
+            }
+            None => None,
+        };
+
+        let indexer_plugins_handle = if indexer_plugins.is_empty() {
+            None
+        } else {
+            let runner =
+                IndexerPluginRunner::new(indexer_plugins, state.clone(), &prometheus_registry)
+                    .await;
+            Some(tokio::spawn(runner.run(INDEXER_PLUGINS_POLL_INTERVAL)))
+        };
+
+        // The column families whose compression changed are rewritten in the background.
+        let metrics = db_metrics.clone();
+        tokio::task::spawn_blocking(move || compression::migrate_registered_dbs(&metrics));
+
+        let metrics = db_metrics.clone();
+        let db_metrics_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DB_METRICS_INTERVAL);
+            loop {
+                interval.tick().await;
+                let metrics = metrics.clone();
+                if let Err(err) =
+                    tokio::task::spawn_blocking(move || metrics.report_cf_properties()).await
+                {
+                    error!("Failed to report database properties: {:?}", err);
+                }
+            }
+        });
+
+        let contention_state = state.clone();
+        let object_contention_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(OBJECT_CONTENTION_INTERVAL);
+            loop {
+                interval.tick().await;
+                let state = contention_state.clone();
+                match tokio::task::spawn_blocking(move || state.export_object_contention()).await {
+                    Ok(Ok(())) => (),
+                    Ok(Err(err)) => error!("Failed to export object contention: {:?}", err),
+                    Err(err) => error!("Failed to export object contention: {:?}", err),
+                }
+            }
+        });
+
         let registry = prometheus_registry.clone();
         let validator_service = if config.consensus_config().is_some() {
             Some(
@@ -279,6 +540,23 @@ impl SuiNode {
             None
         };
 
+        let consensus_monitor = validator_service
+            .as_ref()
+            .map(|service| service.consensus_monitor());
+        let watchdog = config.watchdog.clone().map(|watchdog| {
+            Arc::new(Watchdog::new(
+                watchdog,
+                state.clone(),
+                active_authority.clone(),
+                execute_driver_handle.clone(),
+                consensus_monitor.clone(),
+                &prometheus_registry,
+            ))
+        });
+        let watchdog_handle = watchdog
+            .clone()
+            .map(|watchdog| tokio::spawn(watchdog.run()));
+
         let grpc_server = {
             let mut server_conf = mysten_network::config::Config::new();
             server_conf.global_concurrency_limit = config.grpc_concurrency_limit;
@@ -287,8 +565,18 @@ impl SuiNode {
                 ServerBuilder::from_config(&server_conf, GrpcMetrics::new(&prometheus_registry));
 
             if let Some(validator_service) = validator_service {
-                server_builder =
-                    server_builder.add_service(ValidatorServer::new(validator_service));
+                let validator_server = VersionService::server(
+                    ValidatorServer::new(validator_service),
+                    Arc::new(PeerVersionMonitor::new(
+                        PeerRole::Client,
+                        env!("CARGO_PKG_VERSION"),
+                        &prometheus_registry,
+                    )),
+                );
+                server_builder = server_builder.add_service(MessageSizeLimitService::server(
+                    validator_server,
+                    validator_message_size_limits,
+                ));
             }
 
             let server = server_builder
@@ -351,18 +639,28 @@ impl SuiNode {
 
         let node = Self {
             grpc_server,
-            _json_rpc_service: json_rpc_service,
-            _ws_subscription_service: ws_subscription_service,
-            _gossip_handle: gossip_handle,
-            _execute_driver_handle: execute_driver_handle,
-            _checkpoint_process_handle: checkpoint_process_handle,
-            _batch_subsystem_handle: batch_subsystem_handle,
-            _post_processing_subsystem_handle: post_processing_subsystem_handle,
+            json_rpc_service,
+            ws_subscription_service,
+            gossip_handle,
+            execute_driver_handle,
+            peer_monitor_handle,
+            endpoint_discovery_handle,
+            checkpoint_process_handle,
+            analytics_export_handle,
+            indexer_plugins_handle,
+            db_metrics_handle,
+            object_contention_handle,
+            watchdog,
+            watchdog_handle,
+            consensus_monitor,
+            batch_subsystem_handle,
+            post_processing_subsystem_handle,
+            indexing_handle,
             state,
             active: active_authority,
             transaction_orchestrator,
             _prometheus_registry: prometheus_registry,
-            _p2p_network: p2p_network,
+            p2p_network,
 
             #[cfg(msim)]
             sim_node: sui_simulator::runtime::NodeHandle::current(),
@@ -377,10 +675,22 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn p2p_network(&self) -> &anemo::Network {
+        &self.p2p_network
+    }
+
     pub fn active(&self) -> &Arc<ActiveAuthority<NetworkAuthorityClient>> {
         &self.active
     }
 
+    pub fn watchdog(&self) -> Option<Arc<Watchdog>> {
+        self.watchdog.clone()
+    }
+
+    pub fn consensus_monitor(&self) -> Option<Arc<ConsensusMonitor>> {
+        self.consensus_monitor.clone()
+    }
+
     pub fn transaction_orchestrator(
         &self,
     ) -> Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>> {
@@ -403,6 +713,77 @@ impl SuiNode {
 
         Ok(())
     }
+
+    /// Run until the gRPC server exits or `signal` completes, in which case the node is shut
+    /// down gracefully, giving in-flight requests up to `timeout` to complete.
+    pub async fn wait_with_shutdown(
+        mut self,
+        signal: impl Future<Output = ()>,
+        timeout: Duration,
+    ) -> Result<()> {
+        tokio::select! {
+            result = &mut self.grpc_server => return result?,
+            _ = signal => {}
+        }
+        if tokio::time::timeout(timeout, self.shutdown(timeout / 2))
+            .await
+            .is_err()
+        {
+            warn!(?timeout, "sui-node did not shut down gracefully in time");
+        }
+        Ok(())
+    }
+
+    /// Stop accepting transactions, give the certificates pending execution up to
+    /// `pending_timeout` to be executed, wait for the certificates being executed to be committed
+    /// and batched, then stop the servers and background tasks.
+    async fn shutdown(mut self, pending_timeout: Duration) {
+        info!("Shutting down sui-node");
+        if let Some(transaction_orchestrator) = &self.transaction_orchestrator {
+            transaction_orchestrator.set_read_only(true);
+        }
+        if let Err(err) = self.state.drain(pending_timeout).await {
+            error!("Failed to drain in-flight certificates: {:?}", err);
+        }
+
+        // Dropping the handles stops the JSON-RPC servers.
+        self.json_rpc_service = None;
+        self.ws_subscription_service = None;
+        self.grpc_server.abort();
+
+        // The watchdog is stopped first, so that it does not restart what is being stopped.
+        if let Some(watchdog_handle) = &self.watchdog_handle {
+            watchdog_handle.abort();
+        }
+        self.execute_driver_handle.lock().abort();
+        self.peer_monitor_handle.abort();
+        self.endpoint_discovery_handle.abort();
+        self.db_metrics_handle.abort();
+        self.object_contention_handle.abort();
+        for handle in [
+            &self.gossip_handle,
+            &self.checkpoint_process_handle,
+            &self.analytics_export_handle,
+            // Aborting the indexer plugins stops them.
+            &self.indexer_plugins_handle,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            handle.abort();
+        }
+        for handle in [
+            &self.post_processing_subsystem_handle,
+            &self.indexing_handle,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            handle.abort();
+        }
+        self.batch_subsystem_handle.abort();
+        info!("sui-node shut down");
+    }
 }
 
 pub async fn build_http_servers(
@@ -423,11 +804,44 @@ pub async fn build_http_servers(
         return Ok((None, None));
     }
 
-    let mut server =
-        JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), false, prometheus_registry)?;
+    let limits = config
+        .rpc_response_limits
+        .as_ref()
+        .map(|limits| ResponseLimits {
+            max_response_size_bytes: limits.max_response_size_bytes,
+            max_page_size: limits.max_page_size,
+        })
+        .unwrap_or_default();
+
+    let mut server = JsonRpcServerBuilder::new(
+        env!("CARGO_PKG_VERSION"),
+        false,
+        prometheus_registry,
+        Some(state.slow_calls.clone()),
+    )?
+    .with_response_limits(&limits);
+
+    let read_fallback = match (&config.read_fallback, transaction_orchestrator) {
+        (Some(fallback_config), Some(transaction_orchestrator)) => {
+            Some(Arc::new(ReadFallback::new(
+                transaction_orchestrator.quorum_driver().clone(),
+                fallback_config,
+                prometheus_registry,
+            )))
+        }
+        _ => None,
+    };
 
-    server.register_module(ReadApi::new(state.clone()))?;
-    server.register_module(FullNodeApi::new(state.clone()))?;
+    server
+        .register_module(ReadApi::new(state.clone(), read_fallback).with_response_limits(limits))?;
+    let mut full_node_api = FullNodeApi::new(state.clone()).with_response_limits(limits);
+    if let Some(sandbox_config) = &config.dry_run_sandbox {
+        full_node_api = full_node_api.with_sandbox(Arc::new(ExecutionSandbox::new(
+            sandbox_config.clone(),
+            ExecutionSandboxMetrics::new(prometheus_registry),
+        )));
+    }
+    server.register_module(full_node_api)?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
     server.register_module(FullNodeTransactionBuilderApi::new(state.clone()))?;
 
@@ -436,10 +850,18 @@ pub async fn build_http_servers(
             transaction_orchestrator.clone(),
             state.module_cache.clone(),
         ))?;
+        server.register_module(BridgeApiImpl::new(transaction_orchestrator.clone()))?;
+        server.register_module(NetworkInfoApiImpl::new(
+            state.clone(),
+            transaction_orchestrator.clone(),
+        ))?;
+        server.register_module(DebugApiImpl::new(transaction_orchestrator.clone()))?;
     }
 
     if let Some(event_handler) = state.event_handler.clone() {
-        server.register_module(EventReadApiImpl::new(state.clone(), event_handler))?;
+        server.register_module(
+            EventReadApiImpl::new(state.clone(), event_handler).with_response_limits(limits),
+        )?;
     }
 
     let rpc_server_handle = server
@@ -450,8 +872,13 @@ pub async fn build_http_servers(
 
     let ws_server_handle = match config.websocket_address {
         Some(ws_addr) => {
-            let mut server =
-                JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), true, prometheus_registry)?;
+            let mut server = JsonRpcServerBuilder::new(
+                env!("CARGO_PKG_VERSION"),
+                true,
+                prometheus_registry,
+                Some(state.slow_calls.clone()),
+            )?
+            .with_response_limits(&limits);
             if let Some(tx_streamer) = state.transaction_streamer.clone() {
                 server.register_module(TransactionStreamingApiImpl::new(
                     state.clone(),
@@ -463,6 +890,7 @@ pub async fn build_http_servers(
             if let Some(event_handler) = state.event_handler.clone() {
                 server.register_module(EventStreamingApiImpl::new(state.clone(), event_handler))?;
             }
+            server.register_module(ObjectChangeStreamingApiImpl::new(state.clone()))?;
             Some(
                 server
                     .start(ws_addr)
@@ -475,3 +903,42 @@ pub async fn build_http_servers(
     };
     Ok((Some(rpc_server_handle), ws_server_handle))
 }
+
+/// The record of the oldest protocol version supported by the network, as laid out in the
+/// Move object configured as `min-supported-version-object`.
+#[derive(Deserialize)]
+struct MinSupportedVersion {
+    _id: UID,
+    protocol_version: u64,
+}
+
+/// Fails if the protocol version of this node is older than the one recorded in `object_id`,
+/// read from the validators.
+async fn check_min_supported_version(
+    net: &AuthorityAggregator<NetworkAuthorityClient>,
+    object_id: ObjectID,
+) -> Result<()> {
+    let object = match net.get_object_info_execute(object_id).await? {
+        ObjectRead::Exists(_, object, _) => object,
+        _ => bail!("Minimum supported version object {object_id} does not exist"),
+    };
+    let contents = object
+        .data
+        .try_as_move()
+        .ok_or_else(|| anyhow!("Minimum supported version object {object_id} is a package"))?
+        .contents();
+    let min_supported: MinSupportedVersion = bcs::from_bytes(contents)?;
+    if PROTOCOL_VERSION < min_supported.protocol_version {
+        bail!(
+            "This node speaks protocol version {PROTOCOL_VERSION}, but the network requires \
+             version {} or later: update the node to a newer release",
+            min_supported.protocol_version
+        );
+    }
+    info!(
+        protocol_version = PROTOCOL_VERSION,
+        min_supported_protocol_version = min_supported.protocol_version,
+        "Protocol version is supported by the network"
+    );
+    Ok(())
+}
@@ -8,7 +8,9 @@ use std::path::PathBuf;
 use std::time::Duration;
 use sui_config::{Config, NodeConfig};
 use sui_node::metrics;
+use sui_telemetry::node_stats::NodeType;
 use sui_telemetry::send_telemetry_event;
+use tokio::sync::oneshot;
 use tokio::task;
 use tokio::time::sleep;
 use tracing::{info, warn};
@@ -42,23 +44,34 @@ const MEMORY_INCREASE_PROFILING_RATIO: f64 = 1.2;
 const MEMORY_PROFILING_INTERVAL_SECS: u64 = 300;
 const PROF_DUMP: &[u8] = b"prof.dump\0";
 
+// File next to the node config the admin token is written to, unless one is configured.
+const ADMIN_TOKEN_FILE: &str = "admin-token";
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
     let mut config = NodeConfig::load(&args.config_path)?;
 
-    let prometheus_registry = metrics::start_prometheus_server(config.metrics_address);
+    let (metrics_shutdown_tx, metrics_shutdown_rx) = oneshot::channel::<()>();
+    let prometheus_registry =
+        metrics::start_prometheus_server_with_shutdown(config.metrics_address, async {
+            metrics_shutdown_rx.await.ok();
+        });
     info!(
         "Started Prometheus HTTP endpoint at {}",
         config.metrics_address
     );
 
     // Initialize logging
-    let (_guard, filter_handle) =
-        telemetry_subscribers::TelemetryConfig::new(env!("CARGO_BIN_NAME"))
-            .with_env()
-            .with_prom_registry(&prometheus_registry)
-            .init();
+    let logging = config.logging.clone().unwrap_or_default();
+    let mut telemetry_config = telemetry_subscribers::TelemetryConfig::new(env!("CARGO_BIN_NAME"))
+        .with_env()
+        .with_prom_registry(&prometheus_registry);
+    if logging.json {
+        telemetry_config.json_log_output = true;
+    }
+    let (_guard, filter_handle) = telemetry_config.init();
+    sui_node::admin::apply_logging_config(&filter_handle, &logging)?;
 
     if let Some(listen_address) = args.listen_address {
         config.network_address = listen_address;
@@ -130,10 +143,59 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
-
     let node = sui_node::SuiNode::start(&config, prometheus_registry).await?;
-    node.wait().await?;
+
+    if let Some(stats_reporting) = config.stats_reporting.clone() {
+        let node_type = if is_validator {
+            NodeType::Validator
+        } else {
+            NodeType::FullNode
+        };
+        sui_node::stats_reporting::spawn_stats_reporting(stats_reporting, &node, node_type);
+    }
+
+    sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        config.admin_interface_token.clone(),
+        &args.config_path.with_file_name(ADMIN_TOKEN_FILE),
+        filter_handle,
+        node.state(),
+        node.transaction_orchestrator(),
+        node.watchdog(),
+        node.consensus_monitor(),
+    )?;
+    node.wait_with_shutdown(shutdown_signal(), config.shutdown_timeout())
+        .await?;
+    metrics_shutdown_tx.send(()).ok();
 
     Ok(())
 }
+
+/// Complete on SIGINT or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for SIGINT: {}", err);
+            futures::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                warn!("Failed to listen for SIGTERM: {}", err);
+                futures::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = futures::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received SIGINT"),
+        _ = terminate => info!("Received SIGTERM"),
+    }
+}
@@ -7,34 +7,30 @@ use prometheus::{
     register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
     IntGaugeVec, Registry, TextEncoder,
 };
+use std::future::Future;
 use std::net::SocketAddr;
 use std::time::Duration;
 use sui_network::tonic::Code;
 
-use tracing::warn;
+use crate::http_server;
 
 const METRICS_ROUTE: &str = "/metrics";
 
 pub fn start_prometheus_server(addr: SocketAddr) -> Registry {
-    let registry = Registry::new();
+    start_prometheus_server_with_shutdown(addr, futures::future::pending())
+}
 
-    if cfg!(msim) {
-        // prometheus uses difficult-to-support features such as TcpSocket::from_raw_fd(), so we
-        // can't yet run it in the simulator.
-        warn!("not starting prometheus server in simulator");
-        return registry;
-    }
+/// Start the Prometheus HTTP endpoint, which stops once `signal` completes.
+pub fn start_prometheus_server_with_shutdown(
+    addr: SocketAddr,
+    signal: impl Future<Output = ()> + Send + 'static,
+) -> Registry {
+    let registry = Registry::new();
 
     let app = Router::new()
         .route(METRICS_ROUTE, get(metrics))
         .layer(Extension(registry.clone()));
-
-    tokio::spawn(async move {
-        axum::Server::bind(&addr)
-            .serve(app.into_make_service())
-            .await
-            .unwrap();
-    });
+    http_server::serve(addr, app, signal);
 
     registry
 }
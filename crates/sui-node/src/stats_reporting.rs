@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sui_config::node::StatsReportingConfig;
+use sui_core::authority::AuthorityState;
+use sui_telemetry::node_stats::{
+    NodeStatsReport, NodeStatsReporter, NodeType, NODE_STATS_SCHEMA_VERSION,
+};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::SuiNode;
+
+/// Report the statistics of `node` every `config.interval_secs` to `config.endpoint`.
+pub fn spawn_stats_reporting(
+    config: StatsReportingConfig,
+    node: &SuiNode,
+    node_type: NodeType,
+) -> JoinHandle<()> {
+    let state = node.state();
+    let network = node.p2p_network().clone();
+    let mut reporter = NodeStatsReporter::new(config.endpoint, config.buffer_size);
+    info!(
+        instance_id = reporter.instance_id(),
+        "Reporting node stats every {}s", config.interval_secs
+    );
+
+    tokio::spawn(async move {
+        let start = Instant::now();
+        let mut progress = CheckpointProgress::default();
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+        loop {
+            interval.tick().await;
+            let (checkpoint, stall) = progress.update(&state);
+            let report = NodeStatsReport {
+                schema_version: NODE_STATS_SCHEMA_VERSION,
+                instance_id: reporter.instance_id().to_string(),
+                node_type,
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                uptime_secs: start.elapsed().as_secs(),
+                checkpoint,
+                checkpoint_stall_secs: stall.map(|stall| stall.as_secs()),
+                peer_count: network.peers().len(),
+                timestamp_secs: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Now should be later than epoch!")
+                    .as_secs(),
+            };
+            reporter.report(report).await;
+        }
+    })
+}
+
+/// Tracks when the latest stored checkpoint last advanced.
+#[derive(Default)]
+struct CheckpointProgress {
+    latest: Option<(u64, Instant)>,
+}
+
+impl CheckpointProgress {
+    fn update(&mut self, state: &Arc<AuthorityState>) -> (Option<u64>, Option<Duration>) {
+        let checkpoint = state
+            .checkpoints
+            .lock()
+            .latest_stored_checkpoint()
+            .map(|checkpoint| checkpoint.sequence_number());
+        let checkpoint = match checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => return (None, None),
+        };
+        match self.latest {
+            Some((latest, _)) if latest == checkpoint => {}
+            _ => self.latest = Some((checkpoint, Instant::now())),
+        }
+        let since = self.latest.map(|(_, since)| since.elapsed());
+        (Some(checkpoint), since)
+    }
+}
@@ -0,0 +1,600 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A watchdog checking the health of the node periodically: whether the execution of
+//! certificates or consensus stalled, and whether database writes fail. When a check starts
+//! failing, the actions configured for it are taken, e.g. restarting the subsystem at fault, or
+//! halting the intake of transactions, which resumes once no failing check halts it. Every
+//! failure is counted in the metrics and listed by the admin interface.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
+use sui_config::node::{WatchdogAction, WatchdogCheckConfig, WatchdogConfig};
+use sui_core::authority::AuthorityState;
+use sui_core::authority_active::ActiveAuthority;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::consensus_monitor::ConsensusMonitor;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Number of the most recent events kept for the admin interface.
+const MAX_EVENTS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WatchdogCheck {
+    ExecutionStall,
+    ConsensusStall,
+    DbWriteErrors,
+}
+
+impl WatchdogCheck {
+    const ALL: [WatchdogCheck; 3] = [
+        WatchdogCheck::ExecutionStall,
+        WatchdogCheck::ConsensusStall,
+        WatchdogCheck::DbWriteErrors,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WatchdogCheck::ExecutionStall => "execution-stall",
+            WatchdogCheck::ConsensusStall => "consensus-stall",
+            WatchdogCheck::DbWriteErrors => "db-write-errors",
+        }
+    }
+
+    fn config(self, config: &WatchdogConfig) -> Option<&WatchdogCheckConfig> {
+        match self {
+            WatchdogCheck::ExecutionStall => config.execution_stall.as_ref(),
+            WatchdogCheck::ConsensusStall => config.consensus_stall.as_ref(),
+            WatchdogCheck::DbWriteErrors => config.db_write_errors.as_ref(),
+        }
+    }
+
+    fn halts_intake(self, config: &WatchdogConfig) -> bool {
+        self.config(config).map_or(false, |config| {
+            config.actions.contains(&WatchdogAction::HaltIntake)
+        })
+    }
+}
+
+impl Display for WatchdogCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub fn action_name(action: WatchdogAction) -> &'static str {
+    match action {
+        WatchdogAction::Alert => "alert",
+        WatchdogAction::HaltIntake => "halt-intake",
+        WatchdogAction::RestartExecution => "restart-execution",
+        WatchdogAction::RestartNodeSync => "restart-node-sync",
+    }
+}
+
+/// A check which started or stopped failing.
+#[derive(Clone, Debug)]
+pub struct WatchdogEvent {
+    pub at: SystemTime,
+    pub check: WatchdogCheck,
+    /// Why the check started failing, or None if it passes again.
+    pub failure: Option<String>,
+    /// The actions taken when the check started failing.
+    pub actions: Vec<WatchdogAction>,
+}
+
+struct WatchdogMetrics {
+    failures: IntCounterVec,
+    failing: IntGaugeVec,
+    actions: IntCounterVec,
+}
+
+impl WatchdogMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            failures: register_int_counter_vec_with_registry!(
+                "watchdog_check_failures",
+                "Number of times a watchdog check started failing, by check",
+                &["check"],
+                registry,
+            )
+            .unwrap(),
+            failing: register_int_gauge_vec_with_registry!(
+                "watchdog_check_failing",
+                "Whether a watchdog check is failing, by check",
+                &["check"],
+                registry,
+            )
+            .unwrap(),
+            actions: register_int_counter_vec_with_registry!(
+                "watchdog_actions",
+                "Number of actions taken by the watchdog, by check and action",
+                &["check", "action"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Tracks since when a value did not change while there was work to advance it.
+struct Progress {
+    value: Option<u64>,
+    since: Instant,
+}
+
+impl Progress {
+    fn new() -> Self {
+        Self {
+            value: None,
+            since: Instant::now(),
+        }
+    }
+
+    /// How long the value has been `value` while `busy`.
+    fn stalled_for(&mut self, value: u64, busy: bool) -> Duration {
+        if !busy || self.value != Some(value) {
+            self.value = Some(value);
+            self.since = Instant::now();
+        }
+        self.since.elapsed()
+    }
+}
+
+/// What the checks observed in previous rounds.
+struct Probes {
+    execution: Progress,
+    consensus: Progress,
+    db_write_errors: u64,
+}
+
+/// A failing check.
+struct Failing {
+    /// Why the check fails.
+    failure: String,
+    /// When the actions of the check were last taken.
+    acted_at: Instant,
+}
+
+/// How a check changed when it last ran.
+#[derive(Debug, PartialEq, Eq)]
+enum CheckUpdate {
+    Passing,
+    Recovered,
+    StartedFailing,
+    /// The check keeps failing, and its actions are taken again.
+    StillFailing,
+    /// The check keeps failing, and its actions were taken recently.
+    Failing,
+}
+
+struct Inner {
+    failing: BTreeMap<WatchdogCheck, Failing>,
+    events: VecDeque<WatchdogEvent>,
+}
+
+impl Inner {
+    fn new() -> Self {
+        Self {
+            failing: BTreeMap::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Records whether `check` fails at `now`, and tells whether its actions are to be taken:
+    /// when it starts failing, and again once `config.retry_interval_secs` passed since they
+    /// were last taken.
+    fn update(
+        &mut self,
+        check: WatchdogCheck,
+        config: &WatchdogCheckConfig,
+        failure: Option<String>,
+        now: Instant,
+    ) -> CheckUpdate {
+        let failure = match failure {
+            Some(failure) => failure,
+            None => {
+                if self.failing.remove(&check).is_none() {
+                    return CheckUpdate::Passing;
+                }
+                self.push_event(WatchdogEvent {
+                    at: SystemTime::now(),
+                    check,
+                    failure: None,
+                    actions: vec![],
+                });
+                return CheckUpdate::Recovered;
+            }
+        };
+        if let Some(failing) = self.failing.get_mut(&check) {
+            failing.failure = failure;
+            let retry_interval = Duration::from_secs(config.retry_interval_secs);
+            if now.saturating_duration_since(failing.acted_at) < retry_interval {
+                return CheckUpdate::Failing;
+            }
+            failing.acted_at = now;
+            return CheckUpdate::StillFailing;
+        }
+        self.failing.insert(
+            check,
+            Failing {
+                failure: failure.clone(),
+                acted_at: now,
+            },
+        );
+        self.push_event(WatchdogEvent {
+            at: SystemTime::now(),
+            check,
+            failure: Some(failure),
+            actions: config.actions.clone(),
+        });
+        CheckUpdate::StartedFailing
+    }
+
+    fn push_event(&mut self, event: WatchdogEvent) {
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    /// The failing checks which halt the intake of transactions.
+    fn halting(&self, config: &WatchdogConfig) -> Vec<WatchdogCheck> {
+        self.failing
+            .keys()
+            .copied()
+            .filter(|check| check.halts_intake(config))
+            .collect()
+    }
+}
+
+pub struct Watchdog {
+    config: WatchdogConfig,
+    state: Arc<AuthorityState>,
+    active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
+    execute_driver_handle: Arc<Mutex<JoinHandle<()>>>,
+    consensus_monitor: Option<Arc<ConsensusMonitor>>,
+    inner: Mutex<Inner>,
+    metrics: WatchdogMetrics,
+}
+
+impl Watchdog {
+    pub fn new(
+        config: WatchdogConfig,
+        state: Arc<AuthorityState>,
+        active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
+        execute_driver_handle: Arc<Mutex<JoinHandle<()>>>,
+        consensus_monitor: Option<Arc<ConsensusMonitor>>,
+        registry: &Registry,
+    ) -> Self {
+        Self {
+            config,
+            state,
+            active,
+            execute_driver_handle,
+            consensus_monitor,
+            inner: Mutex::new(Inner::new()),
+            metrics: WatchdogMetrics::new(registry),
+        }
+    }
+
+    /// The failing checks, with why they fail.
+    pub fn failing(&self) -> Vec<(WatchdogCheck, String)> {
+        self.inner
+            .lock()
+            .failing
+            .iter()
+            .map(|(check, failing)| (*check, failing.failure.clone()))
+            .collect()
+    }
+
+    /// The most recent events, oldest first.
+    pub fn events(&self) -> Vec<WatchdogEvent> {
+        self.inner.lock().events.iter().cloned().collect()
+    }
+
+    /// Run the checks every `config.check_interval_secs`, until the task is aborted.
+    pub async fn run(self: Arc<Self>) {
+        info!(
+            "Watchdog checking the node every {}s",
+            self.config.check_interval_secs
+        );
+        let mut probes = Probes {
+            execution: Progress::new(),
+            consensus: Progress::new(),
+            db_write_errors: self.state.db_metrics().write_error_count(),
+        };
+        let mut intake_halted = false;
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.check_interval_secs));
+        loop {
+            interval.tick().await;
+            for check in WatchdogCheck::ALL {
+                let config = match check.config(&self.config) {
+                    Some(config) => config,
+                    None => continue,
+                };
+                match self.probe(check, config.threshold, &mut probes) {
+                    Ok(failure) => self.update(check, config, failure).await,
+                    Err(err) => warn!(%check, "Watchdog failed to run check: {err}"),
+                }
+            }
+            intake_halted = self.update_intake_halt(intake_halted);
+        }
+    }
+
+    /// Why `check` fails, if it does.
+    fn probe(
+        &self,
+        check: WatchdogCheck,
+        threshold: u64,
+        probes: &mut Probes,
+    ) -> anyhow::Result<Option<String>> {
+        let failure = match check {
+            WatchdogCheck::ExecutionStall => {
+                // Certificates executed directly, e.g. those submitted to a validator, do not
+                // tell whether the queue of pending certificates moves.
+                let dequeued = self.state.dequeued_certificate_count();
+                let pending = self.state.pending_certificate_count();
+                let stalled_for = probes.execution.stalled_for(dequeued, pending > 0);
+                (stalled_for.as_secs() >= threshold).then(|| {
+                    format!(
+                        "{pending} certificates pending, none executed for {}s",
+                        stalled_for.as_secs()
+                    )
+                })
+            }
+            WatchdogCheck::ConsensusStall => {
+                let monitor = match &self.consensus_monitor {
+                    Some(monitor) => monitor,
+                    None => return Ok(None),
+                };
+                let round = monitor.last_committed_round().unwrap_or(0);
+                let queue_depth = monitor.queue_depth();
+                let stalled_for = probes.consensus.stalled_for(round, queue_depth > 0);
+                (stalled_for.as_secs() >= threshold).then(|| {
+                    format!(
+                        "{queue_depth} certificates submitted, no round committed for {}s",
+                        stalled_for.as_secs()
+                    )
+                })
+            }
+            WatchdogCheck::DbWriteErrors => {
+                let count = self.state.db_metrics().write_error_count();
+                let errors = count - probes.db_write_errors;
+                probes.db_write_errors = count;
+                (errors > 0 && errors >= threshold)
+                    .then(|| format!("{errors} database writes failed since the last check"))
+            }
+        };
+        Ok(failure)
+    }
+
+    /// Records whether `check` fails, taking its actions if it starts failing or keeps failing
+    /// long after they were taken.
+    async fn update(
+        &self,
+        check: WatchdogCheck,
+        config: &WatchdogCheckConfig,
+        failure: Option<String>,
+    ) {
+        let update = self
+            .inner
+            .lock()
+            .update(check, config, failure, Instant::now());
+        match update {
+            CheckUpdate::Passing | CheckUpdate::Failing => return,
+            CheckUpdate::Recovered => {
+                info!(%check, "Watchdog check passes again");
+                self.metrics
+                    .failing
+                    .with_label_values(&[check.as_str()])
+                    .set(0);
+                return;
+            }
+            CheckUpdate::StartedFailing => {
+                self.metrics
+                    .failures
+                    .with_label_values(&[check.as_str()])
+                    .inc();
+                self.metrics
+                    .failing
+                    .with_label_values(&[check.as_str()])
+                    .set(1);
+            }
+            CheckUpdate::StillFailing => {
+                info!(%check, "Watchdog check still fails, taking its actions again");
+            }
+        }
+        for action in &config.actions {
+            self.metrics
+                .actions
+                .with_label_values(&[check.as_str(), action_name(*action)])
+                .inc();
+            self.take_action(check, *action).await;
+        }
+        if !config.actions.contains(&WatchdogAction::Alert) {
+            warn!(%check, "Watchdog check failed");
+        }
+    }
+
+    async fn take_action(&self, check: WatchdogCheck, action: WatchdogAction) {
+        match action {
+            WatchdogAction::Alert => {
+                let failure = self
+                    .inner
+                    .lock()
+                    .failing
+                    .get(&check)
+                    .map(|failing| failing.failure.clone());
+                error!(%check, "Watchdog check failed: {}", failure.unwrap_or_default());
+            }
+            // Applied once all the checks ran.
+            WatchdogAction::HaltIntake => (),
+            WatchdogAction::RestartExecution => {
+                info!(%check, "Watchdog restarting the execution of pending certificates");
+                self.execute_driver_handle.lock().abort();
+                let handle = self.active.clone().spawn_execute_process().await;
+                *self.execute_driver_handle.lock() = handle;
+            }
+            WatchdogAction::RestartNodeSync => {
+                info!(%check, "Watchdog restarting node sync");
+                self.active.clone().respawn_node_sync_process().await;
+            }
+        }
+    }
+
+    /// Halts the intake of transactions while a failing check halts it, and resumes it
+    /// otherwise. Returns whether the intake is halted.
+    fn update_intake_halt(&self, halted: bool) -> bool {
+        let halting: Vec<_> = self
+            .inner
+            .lock()
+            .halting(&self.config)
+            .iter()
+            .map(|check| check.as_str())
+            .collect();
+        if halting.is_empty() {
+            if halted {
+                info!("Watchdog resuming the intake of transactions");
+                self.state.set_intake_halt(None);
+            }
+            return false;
+        }
+        if !halted {
+            warn!(checks = ?halting, "Watchdog halting the intake of transactions");
+        }
+        self.state
+            .set_intake_halt(Some(format!("failing checks {}", halting.join(", "))));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_config(actions: Vec<WatchdogAction>) -> WatchdogCheckConfig {
+        WatchdogCheckConfig {
+            threshold: 1,
+            actions,
+            retry_interval_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::new();
+        assert!(progress.stalled_for(1, true) < Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(progress.stalled_for(1, true) >= Duration::from_millis(10));
+        // Progress resets the stall, as does having nothing to do.
+        assert!(progress.stalled_for(2, true) < Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(progress.stalled_for(2, false) < Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_actions_are_taken_again_while_failing() {
+        let config = check_config(vec![WatchdogAction::RestartExecution]);
+        let check = WatchdogCheck::ExecutionStall;
+        let failure = || Some("stalled".to_string());
+        let start = Instant::now();
+        let mut inner = Inner::new();
+
+        assert_eq!(
+            inner.update(check, &config, None, start),
+            CheckUpdate::Passing
+        );
+        assert_eq!(
+            inner.update(check, &config, failure(), start),
+            CheckUpdate::StartedFailing
+        );
+        let at = |secs| start + Duration::from_secs(secs);
+        assert_eq!(
+            inner.update(check, &config, failure(), at(30)),
+            CheckUpdate::Failing
+        );
+        assert_eq!(
+            inner.update(check, &config, failure(), at(60)),
+            CheckUpdate::StillFailing
+        );
+        assert_eq!(
+            inner.update(check, &config, failure(), at(90)),
+            CheckUpdate::Failing
+        );
+        assert_eq!(
+            inner.update(check, &config, failure(), at(120)),
+            CheckUpdate::StillFailing
+        );
+        assert_eq!(
+            inner.update(check, &config, None, at(130)),
+            CheckUpdate::Recovered
+        );
+        assert!(inner.failing.is_empty());
+
+        // Only starting and stopping to fail are events.
+        let events: Vec<_> = inner
+            .events
+            .iter()
+            .map(|event| (event.failure.clone(), event.actions.clone()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (failure(), vec![WatchdogAction::RestartExecution]),
+                (None, vec![]),
+            ]
+        );
+
+        // Failing again after recovering takes the actions at once.
+        assert_eq!(
+            inner.update(check, &config, failure(), at(140)),
+            CheckUpdate::StartedFailing
+        );
+    }
+
+    #[test]
+    fn test_halting_intake() {
+        let config = WatchdogConfig {
+            execution_stall: Some(check_config(vec![WatchdogAction::HaltIntake])),
+            db_write_errors: Some(check_config(vec![WatchdogAction::Alert])),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut inner = Inner::new();
+        for check in [WatchdogCheck::ExecutionStall, WatchdogCheck::DbWriteErrors] {
+            let check_config = check.config(&config).unwrap();
+            inner.update(check, check_config, Some("failing".into()), now);
+        }
+        assert_eq!(inner.halting(&config), vec![WatchdogCheck::ExecutionStall]);
+
+        // The intake resumes once the check halting it passes, whatever the other checks.
+        let check_config = config.execution_stall.as_ref().unwrap();
+        inner.update(WatchdogCheck::ExecutionStall, check_config, None, now);
+        assert!(inner.halting(&config).is_empty());
+        assert_eq!(inner.failing.len(), 1);
+    }
+
+    #[test]
+    fn test_events_are_bounded() {
+        let config = check_config(vec![WatchdogAction::Alert]);
+        let check = WatchdogCheck::DbWriteErrors;
+        let now = Instant::now();
+        let mut inner = Inner::new();
+        for _ in 0..MAX_EVENTS {
+            inner.update(check, &config, Some("failing".into()), now);
+            inner.update(check, &config, None, now);
+        }
+        assert_eq!(inner.events.len(), MAX_EVENTS);
+        assert!(inner.events.back().unwrap().failure.is_none());
+    }
+}
@@ -491,9 +491,11 @@ impl RpcExampleProvider {
                 },
                 events: vec![sui_event],
                 dependencies: vec![],
+                command_gas_costs: vec![],
             },
             timestamp_ms: None,
             parsed_data: None,
+            balance_changes: None,
         };
 
         (data2, signature, recipient, obj_id, result, events)
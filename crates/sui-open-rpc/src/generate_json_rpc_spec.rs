@@ -11,7 +11,11 @@ use sui_core::SUI_CORE_VERSION;
 
 use sui_json_rpc::api::EventReadApiOpenRpc;
 use sui_json_rpc::api::EventStreamingApiOpenRpc;
+use sui_json_rpc::api::ObjectChangeStreamingApiOpenRpc;
 use sui_json_rpc::bcs_api::BcsApiImpl;
+use sui_json_rpc::bridge_api::BridgeApiImpl;
+use sui_json_rpc::debug_api::DebugApiImpl;
+use sui_json_rpc::network_info_api::NetworkInfoApiImpl;
 use sui_json_rpc::read_api::{FullNodeApi, ReadApi};
 use sui_json_rpc::sui_rpc_doc;
 use sui_json_rpc::transaction_builder_api::FullNodeTransactionBuilderApi;
@@ -50,9 +54,13 @@ async fn main() {
     open_rpc.add_module(FullNodeApi::rpc_doc_module());
     open_rpc.add_module(BcsApiImpl::rpc_doc_module());
     open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
+    open_rpc.add_module(ObjectChangeStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(EventReadApiOpenRpc::module_doc());
     open_rpc.add_module(FullNodeTransactionExecutionApi::rpc_doc_module());
     open_rpc.add_module(FullNodeTransactionBuilderApi::rpc_doc_module());
+    open_rpc.add_module(BridgeApiImpl::rpc_doc_module());
+    open_rpc.add_module(NetworkInfoApiImpl::rpc_doc_module());
+    open_rpc.add_module(DebugApiImpl::rpc_doc_module());
 
     open_rpc.add_examples(RpcExampleProvider::new().examples());
 
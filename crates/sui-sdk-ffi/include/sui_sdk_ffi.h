@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#ifndef SUI_SDK_FFI_H
+#define SUI_SDK_FFI_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// All arguments are NUL-terminated UTF-8 strings that must stay valid for the duration of the
+// call. Every returned string is owned by the caller and must be released with
+// sui_string_free. Functions return NULL on failure, including when the library panics;
+// call sui_last_error on the same thread for the reason.
+
+// Generate a new keypair for scheme "ed25519" or "secp256k1", encoded as base64.
+char *sui_keypair_generate(const char *scheme);
+
+// Derive the keypair for scheme from a BIP-39 mnemonic phrase, using the wallet's default
+// derivation path.
+char *sui_keypair_from_mnemonic(const char *phrase, const char *scheme);
+
+// Derive the hex Sui address of a base64 keypair.
+char *sui_keypair_address(const char *keypair);
+
+// Build base64 BCS transaction data transferring amount MIST of the SUI coin gas_object_id
+// (hex) from sender to recipient (hex addresses), paying gas with the same coin.
+// gas_object_digest is base64. A negative amount transfers the whole coin.
+char *sui_transaction_transfer_sui(const char *sender,
+                                   const char *recipient,
+                                   const char *gas_object_id,
+                                   uint64_t gas_object_version,
+                                   const char *gas_object_digest,
+                                   uint64_t gas_budget,
+                                   int64_t amount);
+
+// Sign base64 BCS transaction data with a base64 keypair. Returns the base64 encoding of
+// flag || signature || public key.
+char *sui_transaction_sign(const char *keypair, const char *tx_bytes);
+
+// Compute the base64 digest of the transaction made of tx_bytes and signature.
+char *sui_transaction_digest(const char *tx_bytes, const char *signature);
+
+// The error of the last call on this thread that returned NULL, or NULL if there is none.
+char *sui_last_error(void);
+
+// Release a string returned by this library. NULL is ignored.
+void sui_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // SUI_SDK_FFI_H
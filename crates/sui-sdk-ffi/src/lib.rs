@@ -0,0 +1,294 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! C ABI over key management, transaction building and signing, for wallets that cannot link
+//! against Rust directly (e.g. Swift and Kotlin through their C interop). The header is in
+//! `include/sui_sdk_ffi.h`.
+//!
+//! All values cross the boundary as NUL-terminated UTF-8 strings: keypairs and signatures in
+//! the base64 format used by the keystore, addresses and object ids as hex, and transaction
+//! data as base64 BCS bytes. Strings returned by this library are owned by the caller and must
+//! be released with [sui_string_free]. On failure a function returns NULL, and the error can be
+//! retrieved with [sui_last_error] on the same thread. Panics are caught and reported as
+//! errors rather than unwinding into the caller.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use bip39::{Language, Mnemonic, Seed};
+use sui_keys::key_derive::derive_key_pair_from_path;
+use sui_sdk::offline::{
+    sign_transaction, transaction_data_from_bytes, transaction_data_to_bytes, transaction_digest,
+};
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::crypto::{
+    random_key_pair_by_type, EncodeDecodeBase64, Signature, SignatureScheme, SuiKeyPair,
+    ToFromBytes,
+};
+use sui_types::messages::TransactionData;
+use sui_types::sui_serde::{Base64, Encoding};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Generate a new keypair for `scheme` ("ed25519" or "secp256k1").
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_keypair_generate(scheme: *const c_char) -> *mut c_char {
+    ffi_result(|| {
+        let scheme = SignatureScheme::from_str(read_str(scheme)?)?;
+        let (_, keypair) = random_key_pair_by_type(scheme)?;
+        Ok(keypair.encode_base64())
+    })
+}
+
+/// Derive the keypair for `scheme` from a BIP-39 mnemonic `phrase`, using the default
+/// derivation path of the Sui wallet.
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_keypair_from_mnemonic(
+    phrase: *const c_char,
+    scheme: *const c_char,
+) -> *mut c_char {
+    ffi_result(|| {
+        let mnemonic = Mnemonic::from_phrase(read_str(phrase)?, Language::English)
+            .map_err(|e| anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+        let scheme = SignatureScheme::from_str(read_str(scheme)?)?;
+        let (_, keypair) =
+            derive_key_pair_from_path(Seed::new(&mnemonic, "").as_bytes(), None, &scheme)?;
+        Ok(keypair.encode_base64())
+    })
+}
+
+/// Derive the Sui address of `keypair`.
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_keypair_address(keypair: *const c_char) -> *mut c_char {
+    ffi_result(|| {
+        let keypair = read_keypair(keypair)?;
+        Ok(SuiAddress::from(&keypair.public()).to_string())
+    })
+}
+
+/// Build a transaction transferring `amount` MIST of the SUI coin `gas_object_id` from
+/// `sender` to `recipient`, paying gas with the same coin. A negative `amount` transfers the
+/// whole coin.
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_transaction_transfer_sui(
+    sender: *const c_char,
+    recipient: *const c_char,
+    gas_object_id: *const c_char,
+    gas_object_version: u64,
+    gas_object_digest: *const c_char,
+    gas_budget: u64,
+    amount: i64,
+) -> *mut c_char {
+    ffi_result(|| {
+        let sender = SuiAddress::from_str(read_str(sender)?)?;
+        let recipient = SuiAddress::from_str(read_str(recipient)?)?;
+        let gas = (
+            ObjectID::from_str(read_str(gas_object_id)?)?,
+            SequenceNumber::from_u64(gas_object_version),
+            ObjectDigest::try_from(Base64::decode(read_str(gas_object_digest)?)?.as_slice())?,
+        );
+        let amount = u64::try_from(amount).ok();
+        let data = TransactionData::new_transfer_sui(recipient, sender, amount, gas, gas_budget);
+        Ok(Base64::encode(transaction_data_to_bytes(&data)))
+    })
+}
+
+/// Sign the base64 BCS `tx_bytes` with `keypair`. The returned base64 signature is the
+/// concatenation of the scheme flag, the signature and the public key.
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_transaction_sign(
+    keypair: *const c_char,
+    tx_bytes: *const c_char,
+) -> *mut c_char {
+    ffi_result(|| {
+        let keypair = read_keypair(keypair)?;
+        let data = read_transaction_data(tx_bytes)?;
+        let tx = sign_transaction(data, &keypair);
+        Ok(Base64::encode(&tx.signed_data.tx_signature))
+    })
+}
+
+/// Compute the digest of the transaction made of `tx_bytes` and `signature`, as base64.
+///
+/// # Safety
+/// The arguments must be NULL or NUL-terminated strings valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn sui_transaction_digest(
+    tx_bytes: *const c_char,
+    signature: *const c_char,
+) -> *mut c_char {
+    ffi_result(|| {
+        let data = read_transaction_data(tx_bytes)?;
+        let signature = Signature::from_bytes(&Base64::decode(read_str(signature)?)?)
+            .map_err(|e| anyhow!(e))?;
+        Ok(Base64::encode(transaction_digest(&data, &signature)?))
+    })
+}
+
+/// Return the error of the last call on this thread that returned NULL, or NULL if there is
+/// none. The returned string must be released with [sui_string_free].
+#[no_mangle]
+pub extern "C" fn sui_last_error() -> *mut c_char {
+    LAST_ERROR.with(|e| {
+        e.borrow()
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |e| e.clone().into_raw())
+    })
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+/// `s` must be NULL or a pointer returned by this library that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn sui_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+fn ffi_result(f: impl FnOnce() -> anyhow::Result<String>) -> *mut c_char {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(anyhow!("Unexpected panic: {message}"))
+    });
+    match result.and_then(|s| Ok(CString::new(s)?)) {
+        Ok(s) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = None);
+            s.into_raw()
+        }
+        Err(err) => {
+            let err = CString::new(err.to_string().replace('\0', ""))
+                .expect("NUL bytes have been removed");
+            LAST_ERROR.with(|e| *e.borrow_mut() = Some(err));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// `s` must be NULL or a NUL-terminated string which outlives the returned `str`.
+unsafe fn read_str<'a>(s: *const c_char) -> anyhow::Result<&'a str> {
+    if s.is_null() {
+        return Err(anyhow!("Unexpected NULL argument"));
+    }
+    Ok(CStr::from_ptr(s).to_str()?)
+}
+
+/// # Safety
+/// See [read_str].
+unsafe fn read_keypair(keypair: *const c_char) -> anyhow::Result<SuiKeyPair> {
+    SuiKeyPair::decode_base64(read_str(keypair)?).map_err(|e| anyhow!(e))
+}
+
+/// # Safety
+/// See [read_str].
+unsafe fn read_transaction_data(tx_bytes: *const c_char) -> anyhow::Result<TransactionData> {
+    transaction_data_from_bytes(&Base64::decode(read_str(tx_bytes)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take(s: *mut c_char) -> String {
+        assert!(!s.is_null(), "{}", take_error());
+        let owned = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+        unsafe { sui_string_free(s) };
+        owned
+    }
+
+    fn take_error() -> String {
+        let err = sui_last_error();
+        if err.is_null() {
+            return String::new();
+        }
+        take(err)
+    }
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_sign_transfer_sui() {
+        for scheme in ["ed25519", "secp256k1"] {
+            let keypair = take(unsafe { sui_keypair_generate(c(scheme).as_ptr()) });
+            let sender = take(unsafe { sui_keypair_address(c(&keypair).as_ptr()) });
+            let recipient = SuiAddress::random_for_testing_only().to_string();
+            let gas_digest = Base64::encode(ObjectDigest::random());
+
+            let tx_bytes = take(unsafe {
+                sui_transaction_transfer_sui(
+                    c(&sender).as_ptr(),
+                    c(&recipient).as_ptr(),
+                    c(&ObjectID::random().to_string()).as_ptr(),
+                    1,
+                    c(&gas_digest).as_ptr(),
+                    1000,
+                    100,
+                )
+            });
+            let signature =
+                take(unsafe { sui_transaction_sign(c(&keypair).as_ptr(), c(&tx_bytes).as_ptr()) });
+            let digest = take(unsafe {
+                sui_transaction_digest(c(&tx_bytes).as_ptr(), c(&signature).as_ptr())
+            });
+
+            let data = transaction_data_from_bytes(&Base64::decode(&tx_bytes).unwrap()).unwrap();
+            assert_eq!(data.sender.to_string(), sender);
+            let tx = sign_transaction(data, &SuiKeyPair::decode_base64(&keypair).unwrap());
+            assert_eq!(Base64::encode(tx.digest()), digest);
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_matches_wallet() {
+        let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+        let keypair =
+            take(unsafe { sui_keypair_from_mnemonic(c(phrase).as_ptr(), c("ed25519").as_ptr()) });
+        assert_eq!(
+            take(unsafe { sui_keypair_address(c(&keypair).as_ptr()) }),
+            "0x1a4623343cd42be47d67314fce0ad042f3c82685"
+        );
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        assert!(unsafe { sui_keypair_generate(c("bls12381").as_ptr()) }.is_null());
+        assert!(!take_error().is_empty());
+        assert!(unsafe { sui_keypair_address(std::ptr::null()) }.is_null());
+        assert!(take_error().contains("NULL"));
+    }
+
+    #[test]
+    fn test_panics_are_reported() {
+        assert!(ffi_result(|| panic!("boom")).is_null());
+        assert_eq!(take_error(), "Unexpected panic: boom");
+    }
+}
@@ -4,6 +4,7 @@
 use std::str::FromStr;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_sdk::{
+    transaction_digest,
     types::{
         base_types::{ObjectID, SuiAddress},
         messages::Transaction,
@@ -35,6 +36,11 @@ async fn main() -> Result<(), anyhow::Error> {
     let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
     let signature = keystore.sign(&my_address, &transfer_tx.to_bytes())?;
 
+    // The digest is known before submission and can be used to safely retry or look up the
+    // transaction if execution times out.
+    let tx_digest = transaction_digest(&transfer_tx, &signature)?;
+    println!("Executing transaction {:?}", tx_digest);
+
     // Execute the transaction
     let transaction_response = sui
         .quorum_driver()
@@ -0,0 +1,1207 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Debug, Write};
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures_core::Stream;
+use jsonrpsee::core::client::{ClientT, Subscription};
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::types::error::CallError;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::gateway::GatewayConfig;
+use crate::objects::{self, TypedMoveObject, TypedObject};
+use sui_core::gateway_state::{GatewayClient, GatewayState, TxSeqNumber};
+use sui_json_rpc::api::EventStreamingApiClient;
+use sui_json_rpc::api::NetworkInfoApiClient;
+use sui_json_rpc::api::ObjectChangeStreamingApiClient;
+use sui_json_rpc::api::RpcBcsApiClient;
+use sui_json_rpc::api::RpcFullNodeReadApiClient;
+use sui_json_rpc::api::RpcReadApiClient;
+use sui_json_rpc::api::TransactionExecutionApiClient;
+use sui_json_rpc_types::{
+    AddressActivityCursor, AddressActivityPage, CoinPage, GetObjectDataResponse,
+    GetPastObjectDataResponse, GetRawObjectDataResponse, ReceivedObjectsCursor,
+    ReceivedObjectsPage, SessionToken, SuiCertifiedTransaction, SuiCommittee,
+    SuiDryRunTraceResponse, SuiErrorData, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiNestedObjects, SuiNetworkInfoBundle, SuiObjectChangeEnvelope,
+    SuiObjectChangeFilter, SuiObjectInclusionProof, SuiObjectInfo, SuiObjectsAtCheckpoint,
+    SuiPackageAbi, SuiParsedTransactionResponse, SuiSupply, SuiTransactionEffects,
+    SuiTransactionEffectsDiff, SuiTransactionResponse, TransactionsPage,
+};
+use sui_transaction_builder::{DataReader, TransactionBuilder};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::epoch_accounting::EpochAccountingReport;
+use sui_types::error::{ErrorCode, SuiError, TRANSACTION_NOT_FOUND_MSG_PREFIX};
+use sui_types::messages::{
+    CommitteeInfoResponse, ExecuteTransactionRequestType, InputObjectKind, Transaction,
+    TransactionData,
+};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::network_info::TrustedNetwork;
+use sui_types::query::{Ordering, TransactionQuery};
+
+const WAIT_FOR_TX_TIMEOUT_SEC: u64 = 10;
+
+/// The code and classification of the Sui error that caused `error`, as sent by the node along
+/// with the error. None if `error` was not returned by a node, or by a node predating error
+/// codes.
+pub fn sui_error_data(error: &anyhow::Error) -> Option<SuiErrorData> {
+    rpc_error_data(error.downcast_ref()?)
+}
+
+fn rpc_error_data(error: &jsonrpsee::core::Error) -> Option<SuiErrorData> {
+    match error {
+        jsonrpsee::core::Error::Call(CallError::Custom(error)) => {
+            serde_json::from_str(error.data()?.get()).ok()
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct TransactionExecutionResult {
+    pub tx_digest: TransactionDigest,
+    pub tx_cert: Option<SuiCertifiedTransaction>,
+    pub effects: Option<SuiTransactionEffects>,
+    pub confirmed_local_execution: bool,
+    pub timestamp_ms: Option<u64>,
+    pub parsed_data: Option<SuiParsedTransactionResponse>,
+}
+
+#[derive(Clone)]
+pub struct SuiClient {
+    api: Arc<SuiClientApi>,
+    transaction_builder: TransactionBuilder,
+    read_api: Arc<ReadApi>,
+    full_node_api: FullNodeApi,
+    event_api: EventApi,
+    quorum_driver: QuorumDriver,
+    wallet_sync_api: WalletSyncApi,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum SuiClientApi {
+    Rpc(RpcClient),
+    Embedded(GatewayClient),
+}
+
+impl Debug for SuiClientApi {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuiClientApi::Rpc(rpc_client) => write!(
+                f,
+                "RPC client. Http: {:?}, Websocket: {:?}",
+                rpc_client.http, rpc_client.ws
+            ),
+            SuiClientApi::Embedded(_) => write!(f, "Embedded Gateway client."),
+        }
+    }
+}
+
+struct RpcClient {
+    http: HttpClient,
+    ws: Option<WsClient>,
+    info: ServerInfo,
+    /// Token of the latest transaction executed through this client, passed along with reads so
+    /// that they observe it.
+    session_token: Mutex<Option<SessionToken>>,
+}
+
+struct ServerInfo {
+    rpc_methods: Vec<String>,
+    subscriptions: Vec<String>,
+    version: String,
+}
+
+impl RpcClient {
+    pub async fn new(http: &str, ws: Option<&str>) -> Result<Self, anyhow::Error> {
+        let http = HttpClientBuilder::default().build(http)?;
+        let ws = if let Some(url) = ws {
+            Some(WsClientBuilder::default().build(url).await?)
+        } else {
+            None
+        };
+        let info = Self::get_server_info(&http, &ws).await?;
+        Ok(Self {
+            http,
+            ws,
+            info,
+            session_token: Mutex::new(None),
+        })
+    }
+
+    fn session_token(&self) -> Option<SessionToken> {
+        *self.session_token.lock().unwrap()
+    }
+
+    fn set_session_token(&self, session_token: Option<SessionToken>) {
+        if session_token.is_some() {
+            *self.session_token.lock().unwrap() = session_token;
+        }
+    }
+
+    async fn get_server_info(
+        http: &HttpClient,
+        ws: &Option<WsClient>,
+    ) -> Result<ServerInfo, anyhow::Error> {
+        let rpc_spec: Value = http
+            .request("rpc.discover", None)
+            .await
+            .map_err(|e| anyhow!("Fail to connect to the RPC server: {e}"))?;
+        let version = rpc_spec
+            .pointer("/info/version")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Fail parsing server version from rpc.discover endpoint."))?;
+        let rpc_methods = Self::parse_methods(&rpc_spec)?;
+
+        let subscriptions = if let Some(ws) = ws {
+            let rpc_spec: Value = ws
+                .request("rpc.discover", None)
+                .await
+                .map_err(|e| anyhow!("Fail to connect to the Websocket server: {e}"))?;
+            Self::parse_methods(&rpc_spec)?
+        } else {
+            Vec::new()
+        };
+        Ok(ServerInfo {
+            rpc_methods,
+            subscriptions,
+            version: version.to_string(),
+        })
+    }
+
+    fn parse_methods(server_spec: &Value) -> Result<Vec<String>, anyhow::Error> {
+        let methods = server_spec
+            .pointer("/methods")
+            .and_then(|methods| methods.as_array())
+            .ok_or_else(|| {
+                anyhow!("Fail parsing server information from rpc.discover endpoint.")
+            })?;
+
+        Ok(methods
+            .iter()
+            .flat_map(|method| method["name"].as_str())
+            .map(|s| s.into())
+            .collect())
+    }
+
+    fn is_gateway(&self) -> bool {
+        self.info
+            .rpc_methods
+            .contains(&"sui_syncAccountState".to_string())
+    }
+}
+
+impl SuiClient {
+    pub async fn new_rpc_client(
+        http_url: &str,
+        ws_url: Option<&str>,
+    ) -> Result<SuiClient, anyhow::Error> {
+        let rpc = RpcClient::new(http_url, ws_url).await?;
+        Ok(SuiClient::new(SuiClientApi::Rpc(rpc)))
+    }
+
+    /// Connect to a node and check that it serves the network of `trusted`, see
+    /// [SuiClient::verify_network]. Wallets should use this instead of `new_rpc_client` so that
+    /// e.g. a DNS hijack cannot redirect them to an imposter node.
+    pub async fn new_verified_rpc_client(
+        http_url: &str,
+        ws_url: Option<&str>,
+        trusted: &TrustedNetwork,
+    ) -> Result<(SuiClient, SuiNetworkInfoBundle, TrustedNetwork), anyhow::Error> {
+        let client = Self::new_rpc_client(http_url, ws_url).await?;
+        let (bundle, trusted) = client.verify_network(trusted).await?;
+        Ok((client, bundle, trusted))
+    }
+
+    /// Check the network info bundle served by the node against `trusted`, i.e. the network
+    /// started from a genesis, or described by a bundle verified earlier. Returns the verified
+    /// bundle, which the client may save to start from on its next run, and what can be trusted
+    /// about the network from then on.
+    pub async fn verify_network(
+        &self,
+        trusted: &TrustedNetwork,
+    ) -> Result<(SuiNetworkInfoBundle, TrustedNetwork), anyhow::Error> {
+        let bundle = self
+            .read_api
+            .get_network_info_bundle(Some(trusted.committee.epoch))
+            .await?
+            .to_bundle()?;
+        let verified = bundle
+            .verify(trusted)
+            .map_err(|e| anyhow!("The node does not serve the trusted network: {e}"))?;
+        // Only the BCS bytes are signed, rebuild the other fields from them.
+        Ok((SuiNetworkInfoBundle::new(&bundle)?, verified))
+    }
+
+    pub fn new_embedded_client(config: &GatewayConfig) -> Result<SuiClient, anyhow::Error> {
+        let state = GatewayState::create_client(config, None)?;
+        Ok(SuiClient::new(SuiClientApi::Embedded(state)))
+    }
+
+    fn new(api: SuiClientApi) -> Self {
+        let api = Arc::new(api);
+        let read_api = Arc::new(ReadApi { api: api.clone() });
+        let quorum_driver = QuorumDriver { api: api.clone() };
+
+        let full_node_api = FullNodeApi(api.clone());
+        let event_api = EventApi(api.clone());
+        let transaction_builder = TransactionBuilder::new(read_api.clone());
+        let wallet_sync_api = WalletSyncApi(api.clone());
+
+        SuiClient {
+            api,
+            transaction_builder,
+            read_api,
+            full_node_api,
+            event_api,
+            quorum_driver,
+            wallet_sync_api,
+        }
+    }
+
+    pub fn is_gateway(&self) -> bool {
+        match &*self.api {
+            SuiClientApi::Rpc(c) => c.is_gateway(),
+            SuiClientApi::Embedded(_) => true,
+        }
+    }
+
+    pub fn available_rpc_methods(&self) -> Vec<String> {
+        match &*self.api {
+            SuiClientApi::Rpc(c) => c.info.rpc_methods.clone(),
+            SuiClientApi::Embedded(_) => vec![],
+        }
+    }
+
+    pub fn available_subscriptions(&self) -> Vec<String> {
+        match &*self.api {
+            SuiClientApi::Rpc(c) => c.info.subscriptions.clone(),
+            SuiClientApi::Embedded(_) => vec![],
+        }
+    }
+
+    pub fn api_version(&self) -> &str {
+        match &*self.api {
+            SuiClientApi::Rpc(c) => &c.info.version,
+            SuiClientApi::Embedded(_) => env!("CARGO_PKG_VERSION"),
+        }
+    }
+
+    pub fn check_api_version(&self) -> Result<(), anyhow::Error> {
+        let server_version = self.api_version();
+        let client_version = env!("CARGO_PKG_VERSION");
+        if server_version != client_version {
+            return Err(anyhow!("Client/Server api version mismatch, client api version : {client_version}, server api version : {server_version}"));
+        };
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadApi {
+    api: Arc<SuiClientApi>,
+}
+
+impl ReadApi {
+    pub async fn get_objects_owned_by_address(
+        &self,
+        address: SuiAddress,
+    ) -> anyhow::Result<Vec<SuiObjectInfo>> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_objects_owned_by_address(address, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(c) => c.get_objects_owned_by_address(address).await?,
+        })
+    }
+
+    pub async fn get_objects_owned_by_object(
+        &self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<Vec<SuiObjectInfo>> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_objects_owned_by_object(object_id, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(c) => c.get_objects_owned_by_object(object_id).await?,
+        })
+    }
+
+    pub async fn get_objects_owned_by_object_recursive(
+        &self,
+        object_id: ObjectID,
+        max_depth: Option<u32>,
+    ) -> anyhow::Result<SuiNestedObjects> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_objects_owned_by_object_recursive(object_id, max_depth)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_parsed_object(
+        &self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<GetObjectDataResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_object(object_id, c.session_token()).await?,
+            SuiClientApi::Embedded(c) => c.get_object(object_id).await?,
+        })
+    }
+
+    pub async fn try_get_parsed_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> anyhow::Result<GetPastObjectDataResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .try_get_past_object(object_id, version, c.session_token())
+                    .await?
+            }
+            // Gateway does not support get past object
+            SuiClientApi::Embedded(_) => {
+                unimplemented!("Gateway/embedded client does not support get past object")
+            }
+        })
+    }
+
+    pub async fn get_object(
+        &self,
+        object_id: ObjectID,
+    ) -> anyhow::Result<GetRawObjectDataResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_raw_object(object_id, c.session_token()).await?,
+            SuiClientApi::Embedded(c) => c.get_raw_object(object_id).await?,
+        })
+    }
+
+    /// Fetch object `object_id` and decode its contents as a `T`, see [crate::objects]. The
+    /// layout of the Move struct of the object is fetched too, to check the fields of `T` against
+    /// it. If `version` is given, fails with `TypedObjectError::VersionMismatch` if the object is
+    /// at another version, instead of decoding other contents than expected.
+    pub async fn get_object_as<T: TypedMoveObject>(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> anyhow::Result<TypedObject<T>> {
+        let object = self.get_object(object_id).await?.into_object()?;
+        let type_ = objects::object_type(&object)?;
+        let layout = match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_normalized_move_struct(
+                        type_.address.into(),
+                        type_.module.to_string(),
+                        type_.name.to_string(),
+                    )
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        };
+        Ok(objects::parse_object(object, version, None, &layout)?)
+    }
+
+    pub async fn get_total_transaction_number(&self) -> anyhow::Result<u64> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_total_transaction_number().await?,
+            SuiClientApi::Embedded(c) => c.get_total_transaction_number()?,
+        })
+    }
+
+    pub async fn get_transactions_in_range(
+        &self,
+        start: TxSeqNumber,
+        end: TxSeqNumber,
+    ) -> anyhow::Result<Vec<TransactionDigest>> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_transactions_in_range(start, end).await?,
+            SuiClientApi::Embedded(c) => c.get_transactions_in_range(start, end)?,
+        })
+    }
+
+    pub async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_transaction(digest, c.session_token()).await?,
+            SuiClientApi::Embedded(c) => c.get_transaction(digest).await?,
+        })
+    }
+
+    /// The stake of each validator of the committee of `epoch`. `sui_getCommitteeInfo` is
+    /// deprecated, so this reads the committee from `sui_getCommittee`.
+    pub async fn get_committee_info(
+        &self,
+        epoch: Option<EpochId>,
+    ) -> anyhow::Result<CommitteeInfoResponse> {
+        Ok(self.get_committee(epoch).await?.into())
+    }
+
+    pub async fn get_committee(&self, epoch: Option<EpochId>) -> anyhow::Result<SuiCommittee> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_committee(epoch).await?,
+            SuiClientApi::Embedded(_c) => {
+                unimplemented!("Gateway/embedded client does not support get committee")
+            }
+        })
+    }
+
+    pub async fn get_network_info_bundle(
+        &self,
+        from_epoch: Option<EpochId>,
+    ) -> anyhow::Result<SuiNetworkInfoBundle> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_network_info_bundle(from_epoch).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_epoch_accounting_report(
+        &self,
+        epoch: EpochId,
+    ) -> anyhow::Result<EpochAccountingReport> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => c.http.get_epoch_accounting_report(epoch).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct FullNodeApi(Arc<SuiClientApi>);
+
+impl FullNodeApi {
+    pub async fn get_transactions(
+        &self,
+        query: TransactionQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        order: Ordering,
+    ) -> anyhow::Result<TransactionsPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_transactions(query, cursor, limit, order, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_received_objects(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ReceivedObjectsCursor>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<ReceivedObjectsPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_received_objects(address, cursor, limit, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_address_activity(
+        &self,
+        address: SuiAddress,
+        cursor: Option<AddressActivityCursor>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<AddressActivityPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_address_activity(address, cursor, limit, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<CoinPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_coins(owner, coin_type, cursor, limit, c.session_token())
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_total_supply(&self, coin_type: String) -> anyhow::Result<SuiSupply> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_total_supply(coin_type).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_object_inclusion_proof(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> anyhow::Result<SuiObjectInclusionProof> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_object_inclusion_proof(object_id, version)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Read the given objects as of `checkpoint`, or of the latest checkpoint synced by the
+    /// node, consistently with each other.
+    pub async fn get_objects_at_checkpoint(
+        &self,
+        object_ids: Vec<ObjectID>,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> anyhow::Result<SuiObjectsAtCheckpoint> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_objects_at_checkpoint(object_ids, checkpoint)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_package_abi(&self, package: ObjectID) -> anyhow::Result<SuiPackageAbi> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_package_abi(package).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_move_function_arg_schema(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> anyhow::Result<serde_json::Value> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_move_function_arg_schema(package, module.to_string(), function.to_string())
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_transaction_effects_diff(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<SuiTransactionEffectsDiff> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_transaction_effects_diff(digest).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Execute `tx` without committing its effects, e.g. to learn its gas cost before
+    /// submitting it.
+    pub async fn dry_run_transaction(
+        &self,
+        tx: Transaction,
+    ) -> anyhow::Result<SuiTransactionEffects> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                c.http
+                    .dry_run_transaction(tx_bytes, flag, signature, pub_key)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Like `dry_run_transaction`, also returning, if `tx` fails, the Move call stack where it
+    /// stopped.
+    pub async fn dry_run_transaction_with_trace(
+        &self,
+        tx: Transaction,
+    ) -> anyhow::Result<SuiDryRunTraceResponse> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                c.http
+                    .dry_run_transaction_with_trace(tx_bytes, flag, signature, pub_key)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct EventApi(Arc<SuiClientApi>);
+
+impl EventApi {
+    pub async fn subscribe_event(
+        &self,
+        filter: SuiEventFilter,
+    ) -> anyhow::Result<impl Stream<Item = Result<SuiEventEnvelope, anyhow::Error>>> {
+        match &*self.0 {
+            SuiClientApi::Rpc(RpcClient { ws: Some(c), .. }) => {
+                let subscription: Subscription<SuiEventEnvelope> =
+                    c.subscribe_event(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
+        }
+    }
+
+    pub async fn subscribe_object_changes(
+        &self,
+        filter: SuiObjectChangeFilter,
+        cursor: Option<CheckpointSequenceNumber>,
+    ) -> anyhow::Result<impl Stream<Item = Result<SuiObjectChangeEnvelope, anyhow::Error>>> {
+        match &*self.0 {
+            SuiClientApi::Rpc(RpcClient { ws: Some(c), .. }) => {
+                let subscription: Subscription<SuiObjectChangeEnvelope> =
+                    c.subscribe_object_changes(filter, cursor).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct QuorumDriver {
+    api: Arc<SuiClientApi>,
+}
+
+impl QuorumDriver {
+    /// Execute a transaction with a FullNode client or embedded Gateway.
+    /// `request_type` is ignored when the client is an embedded Gateway.
+    /// For Fullnode client, `request_type` defaults to
+    /// `ExecuteTransactionRequestType::WaitForLocalExecution`.
+    /// When `ExecuteTransactionRequestType::WaitForLocalExecution` is used,
+    /// but returned `confirmed_local_execution` is false, the client polls
+    /// the fullnode untils the fullnode recognizes this transaction, or
+    /// until times out (see WAIT_FOR_TX_TIMEOUT_SEC). If it times out, an
+    /// error is returned from this call.
+    pub async fn execute_transaction(
+        &self,
+        tx: Transaction,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> anyhow::Result<TransactionExecutionResult> {
+        self.execute_transaction_with_timeout(tx, request_type, None)
+            .await
+    }
+
+    /// Execute a transaction as `execute_transaction` does. With
+    /// `ExecuteTransactionRequestType::WaitForLocalExecution`, the fullnode waits at most
+    /// `local_execution_timeout` for its local execution, or its own default if `None`.
+    pub async fn execute_transaction_with_timeout(
+        &self,
+        tx: Transaction,
+        request_type: Option<ExecuteTransactionRequestType>,
+        local_execution_timeout: Option<Duration>,
+    ) -> anyhow::Result<TransactionExecutionResult> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                let request_type =
+                    request_type.unwrap_or(ExecuteTransactionRequestType::WaitForLocalExecution);
+                let resp = TransactionExecutionApiClient::execute_transaction(
+                    &c.http,
+                    tx_bytes,
+                    flag,
+                    signature,
+                    pub_key,
+                    request_type.clone(),
+                    local_execution_timeout.map(|timeout| timeout.as_millis() as u64),
+                )
+                .await?;
+
+                match (request_type, resp) {
+                    (
+                        ExecuteTransactionRequestType::ImmediateReturn,
+                        SuiExecuteTransactionResponse::ImmediateReturn { tx_digest },
+                    ) => TransactionExecutionResult {
+                        tx_digest,
+                        tx_cert: None,
+                        effects: None,
+                        confirmed_local_execution: false,
+                        timestamp_ms: None,
+                        parsed_data: None,
+                    },
+                    (
+                        ExecuteTransactionRequestType::WaitForTxCert,
+                        SuiExecuteTransactionResponse::TxCert { certificate },
+                    ) => TransactionExecutionResult {
+                        tx_digest: certificate.transaction_digest,
+                        tx_cert: Some(certificate),
+                        effects: None,
+                        confirmed_local_execution: false,
+                        timestamp_ms: None,
+                        parsed_data: None,
+                    },
+                    (
+                        ExecuteTransactionRequestType::WaitForEffectsCert,
+                        SuiExecuteTransactionResponse::EffectsCert {
+                            certificate,
+                            effects,
+                            confirmed_local_execution,
+                            session_token,
+                        },
+                    ) => {
+                        c.set_session_token(session_token);
+                        TransactionExecutionResult {
+                            tx_digest: certificate.transaction_digest,
+                            tx_cert: Some(certificate),
+                            effects: Some(effects.effects),
+                            confirmed_local_execution,
+                            timestamp_ms: None,
+                            parsed_data: None,
+                        }
+                    }
+                    (
+                        ExecuteTransactionRequestType::WaitForLocalExecution,
+                        SuiExecuteTransactionResponse::EffectsCert {
+                            certificate,
+                            effects,
+                            confirmed_local_execution,
+                            session_token,
+                        },
+                    ) => {
+                        c.set_session_token(session_token);
+                        if !confirmed_local_execution {
+                            Self::wait_until_fullnode_sees_tx(c, certificate.transaction_digest)
+                                .await?;
+                        }
+                        TransactionExecutionResult {
+                            tx_digest: certificate.transaction_digest,
+                            tx_cert: Some(certificate),
+                            effects: Some(effects.effects),
+                            confirmed_local_execution,
+                            timestamp_ms: None,
+                            parsed_data: None,
+                        }
+                    }
+                    (other_request_type, other_resp) => {
+                        bail!(
+                            "Invalid response type {:?} for request type: {:?}",
+                            other_resp,
+                            other_request_type
+                        );
+                    }
+                }
+            }
+            // TODO do we want to support an embedded quorum driver?
+            SuiClientApi::Embedded(c) => {
+                let resp = c.execute_transaction(tx).await?;
+                TransactionExecutionResult {
+                    tx_digest: resp.certificate.transaction_digest,
+                    tx_cert: Some(resp.certificate),
+                    effects: Some(resp.effects),
+                    confirmed_local_execution: true,
+                    timestamp_ms: resp.timestamp_ms,
+                    parsed_data: resp.parsed_data,
+                }
+            }
+        })
+    }
+
+    async fn wait_until_fullnode_sees_tx(
+        c: &RpcClient,
+        tx_digest: TransactionDigest,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        loop {
+            let resp = RpcReadApiClient::get_transaction(&c.http, tx_digest, None).await;
+            if let Err(err) = resp {
+                let not_found = match rpc_error_data(&err) {
+                    Some(data) => data.code == ErrorCode::TRANSACTION_NOT_FOUND,
+                    // Nodes predating error codes only send the message.
+                    None => err.to_string().contains(TRANSACTION_NOT_FOUND_MSG_PREFIX),
+                };
+                if not_found {
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                } else {
+                    // immediately return on other types of errors
+                    bail!(
+                        "Encountered error when confirming tx status for {:?}, err: {:?}",
+                        tx_digest,
+                        err
+                    );
+                }
+            } else {
+                return Ok(());
+            }
+            if start.elapsed().as_secs() >= WAIT_FOR_TX_TIMEOUT_SEC {
+                bail!(
+                    "Failed to confirm tx status for {:?} within {} seconds.",
+                    tx_digest,
+                    WAIT_FOR_TX_TIMEOUT_SEC
+                );
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WalletSyncApi(Arc<SuiClientApi>);
+
+impl WalletSyncApi {
+    pub async fn sync_account_state(&self, address: SuiAddress) -> anyhow::Result<()> {
+        match &*self.0 {
+            SuiClientApi::Rpc(_) => {
+                unimplemented!("Rpc SuiClient does not support WalletSyncApi");
+            }
+            SuiClientApi::Embedded(c) => c.sync_account_state(address).await?,
+        }
+        Ok(())
+    }
+}
+
+impl SuiClient {
+    pub fn transaction_builder(&self) -> &TransactionBuilder {
+        &self.transaction_builder
+    }
+    pub fn read_api(&self) -> &ReadApi {
+        &self.read_api
+    }
+    pub fn full_node_api(&self) -> &FullNodeApi {
+        &self.full_node_api
+    }
+    pub fn event_api(&self) -> &EventApi {
+        &self.event_api
+    }
+    pub fn quorum_driver(&self) -> &QuorumDriver {
+        &self.quorum_driver
+    }
+    pub fn wallet_sync_api(&self) -> &WalletSyncApi {
+        &self.wallet_sync_api
+    }
+}
+
+/// An object a transaction was built against, which another transaction consumed first.
+#[derive(Clone, Debug)]
+pub struct ObjectConflict {
+    pub object_id: ObjectID,
+    pub stale_version: SequenceNumber,
+    pub current_version: SequenceNumber,
+    /// The transaction which last modified the object, which is the one that consumed the stale
+    /// version unless the object changed more than once since. None if the object was deleted.
+    pub transaction: Option<TransactionDigest>,
+    /// The sender of `transaction`, if the node still has it.
+    pub sender: Option<SuiAddress>,
+}
+
+impl Display for ObjectConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Object {} at version {} was consumed",
+            self.object_id, self.stale_version
+        )?;
+        if let Some(transaction) = &self.transaction {
+            write!(f, " by transaction {transaction:?}")?;
+        }
+        if let Some(sender) = &self.sender {
+            write!(f, " sent by {sender}")?;
+        }
+        match self.transaction {
+            Some(_) => write!(f, ", it is now at version {}", self.current_version),
+            None => write!(f, ", it was deleted at version {}", self.current_version),
+        }
+    }
+}
+
+/// The result of a transaction executed by `SuiClient::execute_with_refresh`, along with the
+/// conflicts which made it rebuild the transaction.
+#[derive(Debug)]
+pub struct RefreshedExecution {
+    pub result: TransactionExecutionResult,
+    pub conflicts: Vec<ObjectConflict>,
+}
+
+impl SuiClient {
+    /// Build a transaction with `build` against the object references `pinned_objects`, sign it
+    /// with `sign` and execute it. While building or executing it fails because other
+    /// transactions consumed some of its objects first, it is rebuilt against their latest
+    /// versions, up to `max_attempts` attempts in total.
+    pub async fn execute_with_refresh<B, F, S>(
+        &self,
+        mut pinned_objects: Vec<ObjectRef>,
+        max_attempts: usize,
+        build: B,
+        sign: S,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> anyhow::Result<RefreshedExecution>
+    where
+        B: Fn(TransactionBuilder) -> F,
+        F: Future<Output = anyhow::Result<TransactionData>>,
+        S: Fn(TransactionData) -> anyhow::Result<Transaction>,
+    {
+        let mut conflicts = Vec::new();
+        for _ in 0..max_attempts {
+            let builder = self
+                .transaction_builder
+                .clone()
+                .with_pinned_objects(pinned_objects.clone());
+            let stale = match build(builder).await {
+                Ok(data) => {
+                    let tx = sign(data.clone())?;
+                    let digest = *tx.digest();
+                    match self
+                        .quorum_driver
+                        .execute_transaction(tx, request_type.clone())
+                        .await
+                    {
+                        Ok(result) => return Ok(RefreshedExecution { result, conflicts }),
+                        Err(error) => {
+                            // The transaction may have executed despite the error, e.g. when
+                            // waiting for the node to execute it timed out, and consumed its
+                            // own objects: it must not be sent again.
+                            if let Some(result) = self.executed_transaction(digest).await {
+                                return Ok(RefreshedExecution { result, conflicts });
+                            }
+                            // Validators report stale objects in several ways, so look for them
+                            // instead of interpreting the error.
+                            let stale = self.object_conflicts(&data).await?;
+                            let consumed_by_tx = stale
+                                .iter()
+                                .any(|conflict| conflict.transaction == Some(digest));
+                            if stale.is_empty() || consumed_by_tx {
+                                return Err(error);
+                            }
+                            stale
+                        }
+                    }
+                }
+                Err(error) => match error.downcast_ref::<SuiError>() {
+                    Some(SuiError::ObjectVersionConflict {
+                        object_id,
+                        pinned_version,
+                        ..
+                    }) => self
+                        .object_conflict(*object_id, *pinned_version)
+                        .await?
+                        .into_iter()
+                        .collect(),
+                    _ => return Err(error),
+                },
+            };
+            for conflict in stale {
+                pinned_objects.retain(|(object_id, _, _)| *object_id != conflict.object_id);
+                conflicts.push(conflict);
+            }
+        }
+        bail!(
+            "Objects of the transaction were still consumed by other transactions after {max_attempts} attempts:\n{}",
+            conflicts
+                .iter()
+                .map(|conflict| conflict.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+
+    /// The result of transaction `digest`, if the node executed it.
+    async fn executed_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Option<TransactionExecutionResult> {
+        let response = self.read_api.get_transaction(digest).await.ok()?;
+        Some(TransactionExecutionResult {
+            tx_digest: digest,
+            tx_cert: Some(response.certificate),
+            effects: Some(response.effects),
+            confirmed_local_execution: true,
+            timestamp_ms: response.timestamp_ms,
+            parsed_data: response.parsed_data,
+        })
+    }
+
+    /// The owned objects of `data` which are no longer at the version it uses.
+    pub async fn object_conflicts(
+        &self,
+        data: &TransactionData,
+    ) -> anyhow::Result<Vec<ObjectConflict>> {
+        let mut conflicts = Vec::new();
+        for input in data.input_objects()? {
+            if let InputObjectKind::ImmOrOwnedMoveObject((object_id, version, _)) = input {
+                conflicts.extend(self.object_conflict(object_id, version).await?);
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Who consumed `version` of object `object_id`, if it is no longer its latest version.
+    pub async fn object_conflict(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> anyhow::Result<Option<ObjectConflict>> {
+        let (current_version, transaction) = match self.read_api.get_object(object_id).await? {
+            GetRawObjectDataResponse::Exists(object) => {
+                (object.reference.version, Some(object.previous_transaction))
+            }
+            GetRawObjectDataResponse::Deleted(object_ref) => (object_ref.version, None),
+            GetRawObjectDataResponse::NotExists(_) => return Ok(None),
+        };
+        if current_version == version {
+            return Ok(None);
+        }
+        let sender = match transaction {
+            Some(digest) => self
+                .read_api
+                .get_transaction(digest)
+                .await
+                .ok()
+                .map(|response| response.certificate.data.sender),
+            None => None,
+        };
+        Ok(Some(ObjectConflict {
+            object_id,
+            stale_version: version,
+            current_version,
+            transaction,
+            sender,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientType {
+    Embedded(GatewayConfig),
+    RPC(
+        String,
+        #[serde(default, skip_serializing_if = "Option::is_none")] Option<String>,
+    ),
+}
+
+impl Display for ClientType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+
+        match self {
+            ClientType::Embedded(config) => {
+                writeln!(writer, "Client Type : Embedded Gateway")?;
+                writeln!(
+                    writer,
+                    "Gateway state DB folder path : {:?}",
+                    config.db_folder_path
+                )?;
+                let authorities = config
+                    .validator_set
+                    .iter()
+                    .map(|info| info.network_address());
+                write!(
+                    writer,
+                    "Authorities : {:?}",
+                    authorities.collect::<Vec<_>>()
+                )?;
+            }
+            ClientType::RPC(url, ws_url) => {
+                writeln!(writer, "Client Type : JSON-RPC")?;
+                writeln!(writer, "HTTP RPC URL : {}", url)?;
+                write!(
+                    writer,
+                    "WS RPC URL : {}",
+                    ws_url.clone().unwrap_or_else(|| "None".to_string())
+                )?;
+            }
+        }
+        write!(f, "{}", writer)
+    }
+}
+
+impl ClientType {
+    pub async fn init(&self) -> Result<SuiClient, anyhow::Error> {
+        Ok(match self {
+            ClientType::Embedded(config) => SuiClient::new_embedded_client(config)?,
+            ClientType::RPC(url, ws_url) => {
+                SuiClient::new_rpc_client(url, ws_url.as_deref()).await?
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl DataReader for ReadApi {
+    async fn get_objects_owned_by_address(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Vec<SuiObjectInfo>, anyhow::Error> {
+        self.get_objects_owned_by_address(address).await
+    }
+
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<GetRawObjectDataResponse, anyhow::Error> {
+        self.get_object(object_id).await
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of Move events into Rust structs. A struct mirroring a Move event struct derives
+//! [TypedMoveEvent] to declare the Move type it decodes, and [parse_event] then decodes the
+//! events of that type from their BCS contents:
+//!
+//! ```ignore
+//! #[derive(Deserialize, TypedMoveEvent)]
+//! #[move_event(module = "devnet_nft", name = "MintNFTEvent", package = "0x2")]
+//! struct MintNftEvent {
+//!     object_id: ObjectID,
+//!     creator: SuiAddress,
+//!     name: String,
+//! }
+//!
+//! for event in events::parse_events::<MintNftEvent>(&events, None) { ... }
+//! ```
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::base_types::ObjectID;
+use sui_types::event::Event;
+use sui_types::parse_sui_struct_tag;
+
+pub use sui_macros::TypedMoveEvent;
+
+/// A Rust struct with the BCS layout of a Move event struct. Implementations are derived with
+/// `#[derive(TypedMoveEvent)]`.
+pub trait TypedMoveEvent: DeserializeOwned {
+    /// The module defining the Move struct.
+    const MODULE: &'static str;
+    /// The name of the Move struct.
+    const NAME: &'static str;
+    /// The address of the package defining the Move struct, if known when compiling, e.g. for
+    /// framework events. Otherwise events of the module and name from any package match, unless
+    /// a package is given when parsing.
+    const PACKAGE: Option<&'static str>;
+
+    /// Whether `type_` is the Move type of this event, defined in `package` if given. Type
+    /// arguments are not checked, as they do not change the layout of phantom type parameters.
+    fn matches_type(type_: &StructTag, package: Option<ObjectID>) -> bool {
+        matches_move_type(type_, Self::MODULE, Self::NAME, Self::PACKAGE, package)
+    }
+}
+
+/// Whether `type_` is the Move struct `name` of `module`, defined in `package` if given, else in
+/// `declared_package` if known. A declared package which is not an address matches no type.
+pub(crate) fn matches_move_type(
+    type_: &StructTag,
+    module: &str,
+    name: &str,
+    declared_package: Option<&str>,
+    package: Option<ObjectID>,
+) -> bool {
+    let package = match (package, declared_package) {
+        (Some(package), _) => Some(AccountAddress::from(package)),
+        (None, Some(declared_package)) => {
+            match AccountAddress::from_hex_literal(declared_package) {
+                Ok(package) => Some(package),
+                Err(_) => return false,
+            }
+        }
+        (None, None) => None,
+    };
+    type_.module.as_str() == module
+        && type_.name.as_str() == name
+        && package.map_or(true, |package| type_.address == package)
+}
+
+/// Decodes the contents of a Move event of type `type_` as `T`. Returns None if `type_` is not
+/// the type of `T`, and an error if the contents do not have the layout of `T`.
+pub fn parse_move_event<T: TypedMoveEvent>(
+    type_: &StructTag,
+    contents: &[u8],
+    package: Option<ObjectID>,
+) -> Option<anyhow::Result<T>> {
+    if !T::matches_type(type_, package) {
+        return None;
+    }
+    Some(
+        bcs::from_bytes(contents).map_err(|e| {
+            anyhow::anyhow!("Cannot decode event of type {type_} as {}: {e}", T::NAME)
+        }),
+    )
+}
+
+/// Decodes `event` as `T` if it is a Move event of the type of `T`, see [parse_move_event]. Events
+/// whose type cannot be parsed are not of the type of `T`.
+pub fn parse_event<T: TypedMoveEvent>(
+    event: &SuiEvent,
+    package: Option<ObjectID>,
+) -> Option<anyhow::Result<T>> {
+    match event {
+        SuiEvent::MoveEvent { type_, bcs, .. } => {
+            parse_move_event(&parse_sui_struct_tag(type_).ok()?, bcs, package)
+        }
+        _ => None,
+    }
+}
+
+/// Decodes `event`, as stored by nodes, as `T` if it is a Move event of the type of `T`, see
+/// [parse_move_event].
+pub fn parse_stored_event<T: TypedMoveEvent>(
+    event: &Event,
+    package: Option<ObjectID>,
+) -> Option<anyhow::Result<T>> {
+    match event {
+        Event::MoveEvent {
+            type_, contents, ..
+        } => parse_move_event(type_, contents, package),
+        _ => None,
+    }
+}
+
+/// Decodes the events of `events` which are Move events of the type of `T`, skipping the
+/// others.
+pub fn parse_events<'a, T: TypedMoveEvent>(
+    events: impl IntoIterator<Item = &'a SuiEvent>,
+    package: Option<ObjectID>,
+) -> impl Iterator<Item = anyhow::Result<T>> {
+    events
+        .into_iter()
+        .filter_map(move |event| parse_event(event, package))
+}
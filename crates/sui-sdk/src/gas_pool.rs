@@ -0,0 +1,760 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of the gas coins of a sponsor address, leased to the transactions it sends.
+//!
+//! Transactions sent concurrently by an address must not pay for gas with the same coin:
+//! validators lock an owned object to the first transaction they sign with each of its versions,
+//! so two transactions using the same version of a gas coin equivocate, and the coin can stay
+//! locked until the end of the epoch. The pool leases each coin to one transaction at a time, and
+//! only leases it again once it knows what became of that transaction:
+//! - if it was executed, the coin is read again at the version the effects left it at,
+//! - if it was never submitted, the coin is leased again as it is,
+//! - otherwise, including when a lease expires before it is released, the coin is kept aside
+//!   until its version changes, or until the epoch of the lease ends. Transactions built for a
+//!   lease expire with its epoch, and locks are reset between epochs, so no transaction using the
+//!   coin at that version can be signed any more.
+//!
+//! The pool also keeps its coins at a target balance: coins far below or above it are paid back
+//! to the sponsor as coins of the target balance, in a single transaction.
+//!
+//! The pool needs a JSON-RPC client of a full node, from which it reads the current epoch.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+use signature::Signer;
+use sui_json_rpc_types::{GetRawObjectDataResponse, SuiData, SuiTransactionEffects};
+use sui_transaction_builder::TransactionBuilder;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::committee::EpochId;
+use sui_types::crypto::Signature;
+use sui_types::gas_coin::GasCoin;
+use sui_types::messages::{ExecuteTransactionRequestType, TransactionData, TransactionExpiration};
+use sui_types::object::Owner;
+
+use crate::offline::sign_transaction;
+use crate::{SuiClient, TransactionExecutionResult};
+
+/// The most coins drained or created by a rebalancing transaction.
+const MAX_REBALANCE_COINS: usize = 256;
+
+#[derive(Clone, Debug)]
+pub struct GasPoolConfig {
+    /// The address owning the coins of the pool, which sends the transactions they pay for.
+    pub sponsor: SuiAddress,
+    /// The balance the coins of the pool are kept at. Coins below half or above twice this
+    /// balance are rebalanced.
+    pub target_balance: u64,
+    /// The number of coins of the target balance the pool creates from larger coins, at most.
+    /// What is left of the larger coins is kept in a single coin, which is leased for budgets
+    /// no coin of the target balance covers.
+    pub target_coins: usize,
+    /// How long a lease lasts if it is not released.
+    pub lease_duration: Duration,
+    /// The gas budget of the transactions rebalancing the coins of the pool.
+    pub rebalance_gas_budget: u64,
+}
+
+impl GasPoolConfig {
+    pub fn new(sponsor: SuiAddress) -> Self {
+        Self {
+            sponsor,
+            target_balance: 1_000_000,
+            target_coins: 100,
+            lease_duration: Duration::from_secs(60),
+            rebalance_gas_budget: 100_000,
+        }
+    }
+
+    fn is_off_target(&self, balance: u64) -> bool {
+        balance < self.target_balance / 2 || balance > self.target_balance.saturating_mul(2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CoinState {
+    Available,
+    Leased {
+        lease: u64,
+        epoch: EpochId,
+        expires_at: Instant,
+    },
+    /// Leased to a transaction whose outcome is unknown, during `epoch`.
+    Unverified {
+        epoch: EpochId,
+    },
+    /// Used by a rebalancing transaction.
+    Rebalancing,
+}
+
+struct PoolCoin {
+    object_ref: ObjectRef,
+    balance: u64,
+    state: CoinState,
+}
+
+struct Inner {
+    coins: BTreeMap<ObjectID, PoolCoin>,
+    epoch: EpochId,
+    next_lease: u64,
+    last_error: Option<String>,
+}
+
+impl Inner {
+    /// Plan the transaction rebalancing the available coins far from the target balance, and
+    /// set the coins it uses aside.
+    fn plan_rebalance(&mut self, config: &GasPoolConfig) -> Option<Rebalance> {
+        let target = config.target_balance;
+        let epoch = self.epoch;
+        let pooled = self
+            .coins
+            .values()
+            .filter(|coin| {
+                !config.is_off_target(coin.balance)
+                    && !matches!(coin.state, CoinState::Unverified { .. })
+            })
+            .count();
+        let missing = config.target_coins.saturating_sub(pooled);
+
+        let available: Vec<_> = self
+            .coins
+            .values()
+            .filter(|coin| coin.state == CoinState::Available)
+            .map(|coin| (coin.object_ref, coin.balance))
+            .collect();
+        // Pay for gas with a coin of the target balance if possible.
+        let gas = available
+            .iter()
+            .filter(|(_, balance)| *balance >= config.rebalance_gas_budget)
+            .min_by_key(|(_, balance)| (config.is_off_target(*balance), *balance))
+            .copied()?;
+        let mut coins: Vec<_> = available
+            .into_iter()
+            .filter(|(object_ref, balance)| *object_ref != gas.0 && config.is_off_target(*balance))
+            .collect();
+        // The largest coins first, so that coins are created even if there are too many to
+        // drain at once.
+        coins.sort_by_key(|(_, balance)| Reverse(*balance));
+        coins.truncate(MAX_REBALANCE_COINS);
+
+        let total: u128 = coins.iter().map(|(_, balance)| *balance as u128).sum();
+        let created = (total / target as u128)
+            .min(missing as u128)
+            .min(MAX_REBALANCE_COINS as u128 - 1) as usize;
+        let remainder = u64::try_from(total - created as u128 * target as u128).ok()?;
+        // Paying nothing, or a single coin back whole, changes nothing: split the gas coin
+        // instead if it is large enough.
+        let rebalance = if total == 0 || (created == 0 && coins.len() < 2) {
+            let (coin, balance) = gas;
+            if missing == 0
+                || !config.is_off_target(balance)
+                || balance < target.saturating_add(config.rebalance_gas_budget)
+            {
+                return None;
+            }
+            Rebalance::Split { coin, epoch }
+        } else {
+            let mut amounts = vec![target; created];
+            if remainder > 0 {
+                amounts.push(remainder);
+            }
+            Rebalance::Pay {
+                coins: coins.into_iter().map(|(coin, _)| coin).collect(),
+                amounts,
+                gas: gas.0,
+                epoch,
+            }
+        };
+        for (object_id, _, _) in rebalance.coins() {
+            if let Some(coin) = self.coins.get_mut(&object_id) {
+                coin.state = CoinState::Rebalancing;
+            }
+        }
+        Some(rebalance)
+    }
+}
+
+/// A gas coin leased to a single transaction, until it is released or `expires_at`.
+#[derive(Debug)]
+pub struct GasLease {
+    id: u64,
+    pub coin: ObjectRef,
+    pub balance: u64,
+    /// The epoch the transaction must be executed in.
+    pub epoch: EpochId,
+    pub expires_at: Instant,
+}
+
+/// What became of the transaction a gas coin was leased to.
+pub enum LeaseOutcome<'a> {
+    /// The transaction was executed with these effects.
+    Executed(&'a SuiTransactionEffects),
+    /// No transaction using the coin was sent to validators.
+    NotSubmitted,
+    /// A transaction using the coin may have been sent, e.g. executing it timed out.
+    Unknown,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasPoolStats {
+    pub available_coins: usize,
+    pub available_balance: u64,
+    pub leased_coins: usize,
+    pub unverified_coins: usize,
+    /// The error of the last maintenance of the pool, if it failed.
+    pub last_error: Option<String>,
+}
+
+/// A transaction rebalancing the coins of the pool.
+enum Rebalance {
+    /// Pay `amounts` to the sponsor from `coins`, which are all drained, with `gas` paying for
+    /// gas.
+    Pay {
+        coins: Vec<ObjectRef>,
+        amounts: Vec<u64>,
+        gas: ObjectRef,
+        epoch: EpochId,
+    },
+    /// Split a coin of the target balance from `coin`, which pays for gas, as no other coin of the
+    /// pool can.
+    Split { coin: ObjectRef, epoch: EpochId },
+}
+
+impl Rebalance {
+    fn coins(&self) -> Vec<ObjectRef> {
+        match self {
+            Rebalance::Pay { coins, gas, .. } => coins.iter().chain([gas]).copied().collect(),
+            Rebalance::Split { coin, .. } => vec![*coin],
+        }
+    }
+
+    fn epoch(&self) -> EpochId {
+        match self {
+            Rebalance::Pay { epoch, .. } | Rebalance::Split { epoch, .. } => *epoch,
+        }
+    }
+}
+
+pub struct GasPool {
+    client: SuiClient,
+    signer: Arc<dyn Signer<Signature> + Send + Sync>,
+    config: GasPoolConfig,
+    inner: Mutex<Inner>,
+}
+
+impl GasPool {
+    /// Create a pool of the gas coins `config.sponsor` owns, signing with `signer`, which must
+    /// be the key of the sponsor.
+    pub async fn new(
+        client: SuiClient,
+        signer: Arc<dyn Signer<Signature> + Send + Sync>,
+        config: GasPoolConfig,
+    ) -> anyhow::Result<Self> {
+        if config.target_balance == 0 {
+            bail!("The target balance of a gas pool must not be zero");
+        }
+        let pool = Self {
+            client,
+            signer,
+            config,
+            inner: Mutex::new(Inner {
+                coins: BTreeMap::new(),
+                epoch: 0,
+                next_lease: 0,
+                last_error: None,
+            }),
+        };
+        pool.refresh().await?;
+        Ok(pool)
+    }
+
+    /// Lease the smallest available coin with a balance of at least `min_balance`, keeping
+    /// larger coins for larger budgets.
+    pub fn lease(&self, min_balance: u64) -> anyhow::Result<GasLease> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_lease;
+        let epoch = inner.epoch;
+        let expires_at = Instant::now() + self.config.lease_duration;
+        let coin = inner
+            .coins
+            .values_mut()
+            .filter(|coin| coin.state == CoinState::Available && coin.balance >= min_balance)
+            .min_by_key(|coin| coin.balance)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No gas coin of [{}] with a balance of [{min_balance}] is available",
+                    self.config.sponsor
+                )
+            })?;
+        coin.state = CoinState::Leased {
+            lease: id,
+            epoch,
+            expires_at,
+        };
+        let lease = GasLease {
+            id,
+            coin: coin.object_ref,
+            balance: coin.balance,
+            epoch,
+            expires_at,
+        };
+        inner.next_lease += 1;
+        Ok(lease)
+    }
+
+    /// A transaction builder for the transaction `lease` pays for, which must be sent by the
+    /// sponsor with the leased coin as gas. The transactions it builds expire with the epoch of
+    /// the lease, and building fails if the coin is no longer at the leased version.
+    pub fn transaction_builder(&self, lease: &GasLease) -> TransactionBuilder {
+        self.client
+            .transaction_builder()
+            .clone()
+            .with_expiration(TransactionExpiration::Epoch(lease.epoch))
+            .with_pinned_objects([lease.coin])
+    }
+
+    /// Sign `data`, built with the builder of `lease`, execute it and release the lease.
+    pub async fn execute(
+        &self,
+        lease: GasLease,
+        data: TransactionData,
+    ) -> anyhow::Result<TransactionExecutionResult> {
+        if data.signer() != self.config.sponsor
+            || data.gas() != lease.coin
+            || data.expiration() != &TransactionExpiration::Epoch(lease.epoch)
+        {
+            self.release(lease, LeaseOutcome::NotSubmitted).await;
+            bail!("The transaction was not built for the gas coin of its lease");
+        }
+        let tx = sign_transaction(data, &*self.signer);
+        let result = self
+            .client
+            .quorum_driver()
+            .execute_transaction(
+                tx,
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await;
+        match &result {
+            Ok(TransactionExecutionResult {
+                effects: Some(effects),
+                ..
+            }) => self.release(lease, LeaseOutcome::Executed(effects)).await,
+            _ => self.release(lease, LeaseOutcome::Unknown).await,
+        }
+        result
+    }
+
+    /// Return the coin of `lease` to the pool, given what became of its transaction.
+    pub async fn release(&self, lease: GasLease, outcome: LeaseOutcome<'_>) {
+        let object_id = lease.coin.0;
+        let unverified = PoolCoin {
+            object_ref: lease.coin,
+            balance: lease.balance,
+            state: CoinState::Unverified { epoch: lease.epoch },
+        };
+        let released = match outcome {
+            LeaseOutcome::Executed(effects)
+                if effects.gas_object.owner != Owner::AddressOwner(self.config.sponsor) =>
+            {
+                None
+            }
+            LeaseOutcome::Executed(effects) => match self.read_coin(object_id).await {
+                Ok(Some((object_ref, balance)))
+                    if object_ref.1 >= effects.gas_object.reference.version =>
+                {
+                    Some(PoolCoin {
+                        object_ref,
+                        balance,
+                        state: CoinState::Available,
+                    })
+                }
+                // The node has not caught up with the effects yet.
+                _ => Some(unverified),
+            },
+            LeaseOutcome::NotSubmitted => Some(PoolCoin {
+                state: CoinState::Available,
+                ..unverified
+            }),
+            LeaseOutcome::Unknown => Some(unverified),
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        let current = inner
+            .coins
+            .get(&object_id)
+            .map(|coin| (coin.object_ref, coin.state));
+        match current {
+            Some((object_ref, CoinState::Leased { lease: id, .. }))
+                if object_ref == lease.coin && id == lease.id => {}
+            // The lease expired before it was released.
+            Some((object_ref, CoinState::Unverified { epoch }))
+                if object_ref == lease.coin && epoch == lease.epoch => {}
+            // The coin left the pool, or was leased again after the epoch of the lease.
+            _ => return,
+        }
+        match released {
+            Some(coin) => inner.coins.insert(object_id, coin),
+            None => inner.coins.remove(&object_id),
+        };
+    }
+
+    /// Read the epoch and the coins of the sponsor, then rebalance the coins of the pool which
+    /// are far from the target balance, if any. Returns the effects of the rebalancing
+    /// transaction, if one was executed.
+    pub async fn maintain(&self) -> anyhow::Result<Option<SuiTransactionEffects>> {
+        self.refresh().await?;
+        let planned = self.inner.lock().unwrap().plan_rebalance(&self.config);
+        let rebalance = match planned {
+            Some(rebalance) => rebalance,
+            None => return Ok(None),
+        };
+        let result = self.rebalance(&rebalance).await;
+        {
+            let mut inner = self.inner.lock().unwrap();
+            for (object_id, _, _) in rebalance.coins() {
+                if let Some(coin) = inner.coins.get_mut(&object_id) {
+                    coin.state = match &result {
+                        Err(RebalanceError::NotSubmitted(_)) => CoinState::Available,
+                        _ => CoinState::Unverified {
+                            epoch: rebalance.epoch(),
+                        },
+                    };
+                }
+            }
+        }
+        // Pick up the coins created and changed by the transaction.
+        self.refresh().await?;
+        match result {
+            Ok(effects) => Ok(Some(effects)),
+            Err(RebalanceError::NotSubmitted(error) | RebalanceError::Unknown(error)) => Err(error),
+        }
+    }
+
+    /// Maintain the pool every `interval`, forever. The error of the last maintenance is
+    /// reported in the stats of the pool.
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        loop {
+            let error = self.maintain().await.err().map(|error| error.to_string());
+            self.inner.lock().unwrap().last_error = error;
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    pub fn stats(&self) -> GasPoolStats {
+        let inner = self.inner.lock().unwrap();
+        let mut stats = GasPoolStats {
+            last_error: inner.last_error.clone(),
+            ..Default::default()
+        };
+        for coin in inner.coins.values() {
+            match coin.state {
+                CoinState::Available => {
+                    stats.available_coins += 1;
+                    stats.available_balance += coin.balance;
+                }
+                CoinState::Leased { .. } | CoinState::Rebalancing => stats.leased_coins += 1,
+                CoinState::Unverified { .. } => stats.unverified_coins += 1,
+            }
+        }
+        stats
+    }
+
+    /// Read the epoch and the gas coins of the sponsor: coins join and leave the pool as the
+    /// sponsor receives and spends them, expired leases end, and coins kept aside are leased
+    /// again once their version changed or the epoch of their lease ended.
+    async fn refresh(&self) -> anyhow::Result<()> {
+        let epoch = self.client.read_api().get_committee(None).await?.epoch;
+        let gas_type = GasCoin::type_().to_string();
+        let owned: BTreeMap<_, _> = self
+            .client
+            .read_api()
+            .get_objects_owned_by_address(self.config.sponsor)
+            .await?
+            .into_iter()
+            .filter(|object| object.type_ == gas_type)
+            .map(|object| (object.object_id, object.version))
+            .collect();
+
+        let changed: Vec<_> = {
+            let inner = self.inner.lock().unwrap();
+            owned
+                .iter()
+                .filter(|(object_id, version)| {
+                    inner
+                        .coins
+                        .get(object_id)
+                        .map_or(true, |coin| coin.object_ref.1 != **version)
+                })
+                .map(|(object_id, _)| *object_id)
+                .collect()
+        };
+        let mut read = Vec::new();
+        for object_id in changed {
+            read.extend(self.read_coin(object_id).await?);
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.epoch = epoch;
+        let now = Instant::now();
+        for coin in inner.coins.values_mut() {
+            match coin.state {
+                CoinState::Leased {
+                    epoch: lease_epoch,
+                    expires_at,
+                    ..
+                } if expires_at <= now => coin.state = CoinState::Unverified { epoch: lease_epoch },
+                CoinState::Unverified { epoch: lease_epoch } if lease_epoch < epoch => {
+                    coin.state = CoinState::Available
+                }
+                _ => {}
+            }
+        }
+        // The owner of a lease reports what became of a leased coin.
+        inner.coins.retain(|object_id, coin| {
+            owned.contains_key(object_id) || matches!(coin.state, CoinState::Leased { .. })
+        });
+        for (object_ref, balance) in read {
+            let coin = inner.coins.entry(object_ref.0).or_insert(PoolCoin {
+                object_ref,
+                balance,
+                state: CoinState::Available,
+            });
+            let updatable = matches!(
+                coin.state,
+                CoinState::Available | CoinState::Unverified { .. }
+            );
+            if updatable && coin.object_ref.1 < object_ref.1 {
+                coin.object_ref = object_ref;
+                coin.balance = balance;
+                coin.state = CoinState::Available;
+            }
+        }
+        Ok(())
+    }
+
+    /// The reference and balance of gas coin `object_id`, if the sponsor owns it.
+    async fn read_coin(&self, object_id: ObjectID) -> anyhow::Result<Option<(ObjectRef, u64)>> {
+        let object = match self.client.read_api().get_object(object_id).await? {
+            GetRawObjectDataResponse::Exists(object) => object,
+            _ => return Ok(None),
+        };
+        if object.owner != Owner::AddressOwner(self.config.sponsor) {
+            return Ok(None);
+        }
+        match object.data.try_as_move() {
+            Some(move_object) if move_object.type_ == GasCoin::type_().to_string() => {
+                let coin: GasCoin = bcs::from_bytes(&move_object.bcs_bytes)?;
+                Ok(Some((object.reference.to_object_ref(), coin.value())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn rebalance(
+        &self,
+        rebalance: &Rebalance,
+    ) -> Result<SuiTransactionEffects, RebalanceError> {
+        let sponsor = self.config.sponsor;
+        let budget = self.config.rebalance_gas_budget;
+        let builder = self
+            .client
+            .transaction_builder()
+            .clone()
+            .with_expiration(TransactionExpiration::Epoch(rebalance.epoch()))
+            .with_pinned_objects(rebalance.coins());
+        let data = match rebalance {
+            Rebalance::Pay {
+                coins,
+                amounts,
+                gas,
+                ..
+            } => {
+                builder
+                    .pay(
+                        sponsor,
+                        coins.iter().map(|(object_id, _, _)| *object_id).collect(),
+                        vec![sponsor; amounts.len()],
+                        amounts.clone(),
+                        Some(gas.0),
+                        budget,
+                    )
+                    .await
+            }
+            Rebalance::Split { coin, .. } => {
+                let amount = Some(self.config.target_balance);
+                builder
+                    .transfer_sui(sponsor, coin.0, budget, sponsor, amount)
+                    .await
+            }
+        }
+        .map_err(RebalanceError::NotSubmitted)?;
+        let result = self
+            .client
+            .quorum_driver()
+            .execute_transaction(
+                sign_transaction(data, &*self.signer),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(RebalanceError::Unknown)?;
+        result.effects.ok_or_else(|| {
+            RebalanceError::Unknown(anyhow!(
+                "No effects were returned for rebalancing transaction {:?}",
+                result.tx_digest
+            ))
+        })
+    }
+}
+
+enum RebalanceError {
+    /// Building the transaction failed, so it was never sent.
+    NotSubmitted(anyhow::Error),
+    /// The transaction was sent, but its outcome is not known.
+    Unknown(anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::{ObjectDigest, SequenceNumber};
+
+    use super::*;
+
+    const TARGET: u64 = 1_000_000;
+
+    fn config(target_coins: usize) -> GasPoolConfig {
+        GasPoolConfig {
+            target_balance: TARGET,
+            target_coins,
+            ..GasPoolConfig::new(SuiAddress::random_for_testing_only())
+        }
+    }
+
+    /// A pool of available coins of `balances`, and the references of its coins, in order.
+    fn pool(balances: &[u64]) -> (Inner, Vec<ObjectRef>) {
+        let mut inner = Inner {
+            coins: BTreeMap::new(),
+            epoch: 1,
+            next_lease: 0,
+            last_error: None,
+        };
+        let mut refs = Vec::new();
+        for balance in balances {
+            let object_ref = (
+                ObjectID::random(),
+                SequenceNumber::from_u64(1),
+                ObjectDigest::random(),
+            );
+            inner.coins.insert(
+                object_ref.0,
+                PoolCoin {
+                    object_ref,
+                    balance: *balance,
+                    state: CoinState::Available,
+                },
+            );
+            refs.push(object_ref);
+        }
+        (inner, refs)
+    }
+
+    fn state(inner: &Inner, object_ref: &ObjectRef) -> CoinState {
+        inner.coins[&object_ref.0].state
+    }
+
+    fn assert_pay(
+        rebalance: Option<Rebalance>,
+        expected_coins: &[ObjectRef],
+        expected_amounts: &[u64],
+        expected_gas: ObjectRef,
+    ) {
+        match rebalance {
+            Some(Rebalance::Pay {
+                coins,
+                amounts,
+                gas,
+                epoch,
+            }) => {
+                assert_eq!(coins, expected_coins);
+                assert_eq!(amounts, expected_amounts);
+                assert_eq!(gas, expected_gas);
+                assert_eq!(epoch, 1);
+            }
+            Some(Rebalance::Split { .. }) => panic!("Expected a payment, not a split"),
+            None => panic!("Expected a payment, not no rebalancing"),
+        }
+    }
+
+    #[test]
+    fn test_remainder_of_drained_coins_is_paid_back() {
+        let (mut inner, refs) = pool(&[TARGET, 2_500_000]);
+        let rebalance = inner.plan_rebalance(&config(4));
+        assert_pay(rebalance, &refs[1..], &[TARGET, TARGET, 500_000], refs[0]);
+        assert_eq!(state(&inner, &refs[0]), CoinState::Rebalancing);
+        assert_eq!(state(&inner, &refs[1]), CoinState::Rebalancing);
+
+        // Only the missing coins are created, and the rest is kept in a single coin.
+        let (mut inner, refs) = pool(&[TARGET, 3_500_000]);
+        let rebalance = inner.plan_rebalance(&config(2));
+        assert_pay(rebalance, &refs[1..], &[TARGET, 2_500_000], refs[0]);
+
+        // Small coins are merged into a single coin.
+        let (mut inner, refs) = pool(&[TARGET, 100_000, 300_000]);
+        let rebalance = inner.plan_rebalance(&config(4));
+        assert_pay(rebalance, &[refs[2], refs[1]], &[400_000], refs[0]);
+    }
+
+    #[test]
+    fn test_zero_balance_coins() {
+        // Nothing is paid back from zero-balance coins alone.
+        let (mut inner, refs) = pool(&[TARGET, 0, 0]);
+        assert!(inner.plan_rebalance(&config(4)).is_none());
+        for object_ref in &refs {
+            assert_eq!(state(&inner, object_ref), CoinState::Available);
+        }
+
+        // They are drained with other coins.
+        let (mut inner, refs) = pool(&[TARGET, 0, 300_000]);
+        let rebalance = inner.plan_rebalance(&config(4));
+        assert_pay(rebalance, &[refs[2], refs[1]], &[300_000], refs[0]);
+        assert_eq!(state(&inner, &refs[1]), CoinState::Rebalancing);
+
+        // Nor do they keep the gas coin from being split.
+        let (mut inner, refs) = pool(&[0, 3 * TARGET]);
+        match inner.plan_rebalance(&config(4)) {
+            Some(Rebalance::Split { coin, .. }) => assert_eq!(coin, refs[1]),
+            _ => panic!("Expected the gas coin to be split"),
+        }
+        assert_eq!(state(&inner, &refs[0]), CoinState::Available);
+    }
+
+    #[test]
+    fn test_no_coins_missing() {
+        // A large coin is not paid back whole.
+        let (mut inner, refs) = pool(&[TARGET, 5 * TARGET]);
+        assert!(inner.plan_rebalance(&config(1)).is_none());
+        assert_eq!(state(&inner, &refs[1]), CoinState::Available);
+
+        // Small coins are still merged.
+        let (mut inner, refs) = pool(&[TARGET, 100_000, 200_000]);
+        let rebalance = inner.plan_rebalance(&config(1));
+        assert_pay(rebalance, &[refs[2], refs[1]], &[300_000], refs[0]);
+
+        // The only coin is split only if coins are missing.
+        let (mut inner, refs) = pool(&[5 * TARGET]);
+        assert!(inner.plan_rebalance(&config(0)).is_none());
+        assert_eq!(state(&inner, &refs[0]), CoinState::Available);
+        match inner.plan_rebalance(&config(1)) {
+            Some(Rebalance::Split { coin, epoch }) => {
+                assert_eq!(coin, refs[0]);
+                assert_eq!(epoch, 1);
+            }
+            _ => panic!("Expected the coin to be split"),
+        }
+        assert_eq!(state(&inner, &refs[0]), CoinState::Rebalancing);
+    }
+}
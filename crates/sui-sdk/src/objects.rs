@@ -0,0 +1,420 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of Move objects into Rust structs. A struct mirroring a Move object struct derives
+//! [TypedMoveObject] to declare the Move type it decodes, and objects of that type are then
+//! fetched with `ReadApi::get_object_as`, or decoded with [parse_object]:
+//!
+//! ```ignore
+//! #[derive(Deserialize, TypedMoveObject)]
+//! #[move_object(module = "coin", name = "Coin", package = "0x2")]
+//! struct Coin {
+//!     id: ObjectID,
+//!     balance: Balance,
+//! }
+//!
+//! let coin = client.read_api().get_object_as::<Coin>(object_id, None).await?;
+//! println!("{} at version {}", coin.contents.balance.value(), coin.reference.1);
+//! ```
+//!
+//! Objects are decoded against the layout of their Move struct: the fields of the Rust struct
+//! must have the names of the fields of the Move struct, in the same order, and types of the same
+//! Move type, see [MoveType]. Objects whose Move struct has other fields fail to decode, rather
+//! than decoding into garbage fields of the same width.
+
+use std::fmt::{Display, Formatter};
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use sui_json_rpc_types::{SuiData, SuiMoveNormalizedStruct, SuiMoveNormalizedType, SuiRawObject};
+use sui_types::balance::Balance;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::id::{ID, UID};
+use sui_types::object::{Object, Owner};
+use sui_types::{parse_sui_struct_tag, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::events::matches_move_type;
+
+pub use sui_macros::TypedMoveObject;
+
+/// A Rust struct with the BCS layout of a Move object struct. Implementations are derived with
+/// `#[derive(TypedMoveObject)]`.
+pub trait TypedMoveObject: DeserializeOwned {
+    /// The module defining the Move struct.
+    const MODULE: &'static str;
+    /// The name of the Move struct.
+    const NAME: &'static str;
+    /// The address of the package defining the Move struct, if known when compiling, e.g. for
+    /// framework objects. Otherwise objects of the module and name from any package match,
+    /// unless a package is given when parsing.
+    const PACKAGE: Option<&'static str>;
+    /// The fields of the struct, in the order of the fields of the Move struct.
+    const FIELDS: &'static [MoveField];
+
+    /// Whether `type_` is the Move type of this object, defined in `package` if given. Type
+    /// arguments are not checked, as they do not change the layout of phantom type parameters.
+    fn matches_type(type_: &StructTag, package: Option<ObjectID>) -> bool {
+        matches_move_type(type_, Self::MODULE, Self::NAME, Self::PACKAGE, package)
+    }
+}
+
+/// A field of a [TypedMoveObject], checked against the field of the Move struct it decodes.
+pub struct MoveField {
+    pub name: &'static str,
+    /// Whether a Move type has the layout of the type of the field, see [matches_field_type].
+    pub matches_type: fn(&SuiMoveNormalizedType) -> bool,
+}
+
+/// A Rust type with the BCS layout of some Move types, so that it can be the type of a field of a
+/// [TypedMoveObject]. Implement it for the Rust structs mirroring the Move structs nested in
+/// objects.
+pub trait MoveType {
+    /// Whether `type_` is one of the Move types with the layout of `Self`.
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool;
+}
+
+/// Whether the type of a field of a Move struct has the layout of `T`. The layout of a field whose
+/// type is a type parameter of the struct depends on the type arguments of the object, so that
+/// any `T` matches it.
+pub fn matches_field_type<T: MoveType>(type_: &SuiMoveNormalizedType) -> bool {
+    matches!(type_, SuiMoveNormalizedType::TypeParameter(_)) || T::is_move_type(type_)
+}
+
+/// Whether `type_` is the Move struct `name` of `module`, defined at `address`.
+fn is_struct(
+    type_: &SuiMoveNormalizedType,
+    address: AccountAddress,
+    module: &str,
+    name: &str,
+) -> bool {
+    match type_ {
+        SuiMoveNormalizedType::Struct {
+            address: struct_address,
+            module: struct_module,
+            name: struct_name,
+            ..
+        } => {
+            struct_module == module
+                && struct_name == name
+                && AccountAddress::from_hex_literal(struct_address)
+                    .map_or(false, |struct_address| struct_address == address)
+        }
+        _ => false,
+    }
+}
+
+impl MoveType for bool {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        matches!(type_, SuiMoveNormalizedType::Bool)
+    }
+}
+
+impl MoveType for u8 {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        matches!(type_, SuiMoveNormalizedType::U8)
+    }
+}
+
+impl MoveType for u64 {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        matches!(type_, SuiMoveNormalizedType::U64)
+    }
+}
+
+impl MoveType for u128 {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        matches!(type_, SuiMoveNormalizedType::U128)
+    }
+}
+
+impl MoveType for SuiAddress {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        matches!(type_, SuiMoveNormalizedType::Address)
+    }
+}
+
+/// Both a `UID` and an `ID` wrap the address of an object.
+impl MoveType for ObjectID {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        UID::is_move_type(type_) || ID::is_move_type(type_)
+    }
+}
+
+impl MoveType for UID {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        is_struct(type_, SUI_FRAMEWORK_ADDRESS, "object", "UID")
+    }
+}
+
+impl MoveType for ID {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        is_struct(type_, SUI_FRAMEWORK_ADDRESS, "object", "ID")
+    }
+}
+
+impl MoveType for Balance {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        is_struct(type_, SUI_FRAMEWORK_ADDRESS, "balance", "Balance")
+    }
+}
+
+impl MoveType for String {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        is_struct(type_, MOVE_STDLIB_ADDRESS, "string", "String")
+            || is_struct(type_, MOVE_STDLIB_ADDRESS, "ascii", "String")
+    }
+}
+
+impl<T: MoveType> MoveType for Vec<T> {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        match type_ {
+            SuiMoveNormalizedType::Vector(element) => matches_field_type::<T>(element),
+            _ => false,
+        }
+    }
+}
+
+impl<T: MoveType> MoveType for Option<T> {
+    fn is_move_type(type_: &SuiMoveNormalizedType) -> bool {
+        match type_ {
+            SuiMoveNormalizedType::Struct { type_arguments, .. } => {
+                is_struct(type_, MOVE_STDLIB_ADDRESS, "option", "Option")
+                    && type_arguments.len() == 1
+                    && matches_field_type::<T>(&type_arguments[0])
+            }
+            _ => false,
+        }
+    }
+}
+
+/// An object whose contents are decoded as a `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedObject<T> {
+    pub reference: ObjectRef,
+    pub owner: Owner,
+    pub previous_transaction: TransactionDigest,
+    pub type_: StructTag,
+    pub contents: T,
+}
+
+/// Why an object could not be decoded as a Rust struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypedObjectError {
+    /// The object is a package.
+    NotMoveObject { object_id: ObjectID },
+    /// The object is of another Move type than the struct.
+    TypeMismatch {
+        object_id: ObjectID,
+        expected: String,
+        actual: String,
+    },
+    /// The object is at another version than the one expected.
+    VersionMismatch {
+        object_id: ObjectID,
+        expected: SequenceNumber,
+        actual: SequenceNumber,
+    },
+    /// The Move struct of the object does not have the fields of the Rust struct.
+    LayoutMismatch {
+        object_id: ObjectID,
+        type_: String,
+        error: String,
+    },
+    /// The contents of the object do not have the layout of the struct.
+    Decode {
+        object_id: ObjectID,
+        type_: String,
+        error: String,
+    },
+}
+
+impl Display for TypedObjectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypedObjectError::NotMoveObject { object_id } => {
+                write!(f, "Object {object_id} is a package, not a Move object")
+            }
+            TypedObjectError::TypeMismatch {
+                object_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Object {object_id} is of type {actual}, expected type {expected}"
+            ),
+            TypedObjectError::VersionMismatch {
+                object_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Object {object_id} is at version {actual}, expected version {expected}"
+            ),
+            TypedObjectError::LayoutMismatch {
+                object_id,
+                type_,
+                error,
+            } => write!(
+                f,
+                "Object {object_id} of type {type_} does not have the fields of the struct: \
+                 {error}"
+            ),
+            TypedObjectError::Decode {
+                object_id,
+                type_,
+                error,
+            } => write!(
+                f,
+                "Cannot decode object {object_id} of type {type_}: {error}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TypedObjectError {}
+
+/// Decodes the contents of Move object `object_id` of type `type_` as `T`, defined in
+/// `package` if given. `layout` is the Move struct of `type_`, as returned by
+/// `sui_getNormalizedMoveStruct`, whose fields the fields of `T` are checked against.
+pub fn parse_move_object<T: TypedMoveObject>(
+    object_id: ObjectID,
+    type_: &StructTag,
+    contents: &[u8],
+    package: Option<ObjectID>,
+    layout: &SuiMoveNormalizedStruct,
+) -> Result<T, TypedObjectError> {
+    if !T::matches_type(type_, package) {
+        let package = package
+            .map(|package| package.to_string())
+            .or_else(|| T::PACKAGE.map(str::to_string))
+            .unwrap_or_else(|| "_".to_string());
+        return Err(TypedObjectError::TypeMismatch {
+            object_id,
+            expected: format!("{package}::{}::{}", T::MODULE, T::NAME),
+            actual: type_.to_string(),
+        });
+    }
+    check_layout::<T>(layout).map_err(|error| TypedObjectError::LayoutMismatch {
+        object_id,
+        type_: type_.to_string(),
+        error,
+    })?;
+    bcs::from_bytes(contents).map_err(|e| TypedObjectError::Decode {
+        object_id,
+        type_: type_.to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Checks that the fields of `T` have the names and the types of the fields of `layout`, in the
+/// same order.
+fn check_layout<T: TypedMoveObject>(layout: &SuiMoveNormalizedStruct) -> Result<(), String> {
+    if T::FIELDS.len() != layout.fields.len() {
+        return Err(format!(
+            "expected {} fields, found {}",
+            layout.fields.len(),
+            T::FIELDS.len()
+        ));
+    }
+    for (field, move_field) in T::FIELDS.iter().zip(&layout.fields) {
+        if field.name != move_field.name {
+            return Err(format!(
+                "expected field {}, found field {}",
+                move_field.name, field.name
+            ));
+        }
+        if !(field.matches_type)(&move_field.type_) {
+            return Err(format!(
+                "field {} does not have the layout of {}",
+                field.name, move_field.type_
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The Move type of `object`, as returned by `ReadApi::get_object`.
+pub fn object_type(object: &SuiRawObject) -> Result<StructTag, TypedObjectError> {
+    let object_id = object.reference.object_id;
+    let move_object = object
+        .data
+        .try_as_move()
+        .ok_or(TypedObjectError::NotMoveObject { object_id })?;
+    parse_sui_struct_tag(&move_object.type_).map_err(|e| TypedObjectError::Decode {
+        object_id,
+        type_: move_object.type_.clone(),
+        error: e.to_string(),
+    })
+}
+
+/// Decodes `object`, as returned by `ReadApi::get_object`, as a `T`, see [parse_move_object].
+/// If `version` is given, the object must be at that version.
+pub fn parse_object<T: TypedMoveObject>(
+    object: SuiRawObject,
+    version: Option<SequenceNumber>,
+    package: Option<ObjectID>,
+    layout: &SuiMoveNormalizedStruct,
+) -> Result<TypedObject<T>, TypedObjectError> {
+    let reference = object.reference.to_object_ref();
+    let object_id = reference.0;
+    check_version(object_id, reference.1, version)?;
+    let type_ = object_type(&object)?;
+    let move_object = object
+        .data
+        .try_as_move()
+        .ok_or(TypedObjectError::NotMoveObject { object_id })?;
+    let contents = parse_move_object(object_id, &type_, &move_object.bcs_bytes, package, layout)?;
+    Ok(TypedObject {
+        reference,
+        owner: object.owner,
+        previous_transaction: object.previous_transaction,
+        type_,
+        contents,
+    })
+}
+
+/// Decodes `object`, as stored by nodes, as a `T`, see [parse_move_object]. If `version` is
+/// given, the object must be at that version.
+pub fn parse_stored_object<T: TypedMoveObject>(
+    object: &Object,
+    version: Option<SequenceNumber>,
+    package: Option<ObjectID>,
+    layout: &SuiMoveNormalizedStruct,
+) -> Result<TypedObject<T>, TypedObjectError> {
+    let object_id = object.id();
+    check_version(object_id, object.version(), version)?;
+    let move_object = object
+        .data
+        .try_as_move()
+        .ok_or(TypedObjectError::NotMoveObject { object_id })?;
+    let contents = parse_move_object(
+        object_id,
+        &move_object.type_,
+        move_object.contents(),
+        package,
+        layout,
+    )?;
+    Ok(TypedObject {
+        reference: object.compute_object_reference(),
+        owner: object.owner,
+        previous_transaction: object.previous_transaction,
+        type_: move_object.type_.clone(),
+        contents,
+    })
+}
+
+fn check_version(
+    object_id: ObjectID,
+    actual: SequenceNumber,
+    expected: Option<SequenceNumber>,
+) -> Result<(), TypedObjectError> {
+    match expected {
+        Some(expected) if expected != actual => Err(TypedObjectError::VersionMismatch {
+            object_id,
+            expected,
+            actual,
+        }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operations that do not need a connection to a Sui node: BCS serialization, signing and
+//! digest computation of transactions, and verification of certificates against a known
+//! committee. Nothing in this module depends on the JSON-RPC client, so it is available when the
+//! crate is built with `default-features = false`.
+
+use sui_types::base_types::TransactionDigest;
+use sui_types::committee::Committee;
+use sui_types::crypto::{AuthoritySignInfoTrait, Signature, SuiSignature};
+use sui_types::messages::{
+    CertifiedTransaction, CertifiedTransactionEffects, SenderSignedData, Transaction,
+    TransactionData,
+};
+
+pub use signature::Signer;
+
+/// Serialize `data` into the bytes that are signed by the sender and sent to the network.
+pub fn transaction_data_to_bytes(data: &TransactionData) -> Vec<u8> {
+    data.to_bytes()
+}
+
+/// Deserialize transaction data produced by [transaction_data_to_bytes] or returned by the
+/// transaction builder JSON-RPC API.
+pub fn transaction_data_from_bytes(bytes: &[u8]) -> anyhow::Result<TransactionData> {
+    Ok(bcs::from_bytes(bytes)?)
+}
+
+/// Sign `data` with `signer` and wrap it in a transaction ready to be submitted.
+pub fn sign_transaction(data: TransactionData, signer: &dyn Signer<Signature>) -> Transaction {
+    let signature = Signature::new(&data, signer);
+    Transaction::new(data, signature)
+}
+
+/// Compute the digest a transaction will have once `data` is submitted with `signature`,
+/// without contacting the network. The digest can be recorded as an idempotency key before
+/// calling `QuorumDriver::execute_transaction`: resubmitting the same signed transaction after
+/// a timeout can never execute it twice, and the outcome can be looked up by digest.
+///
+/// Transactions are signed over [TransactionData::to_bytes], not over an intent message, as
+/// done by `AccountKeystore::sign`. The signature is checked here, since a signature over any
+/// other message would produce a digest that validators reject.
+pub fn transaction_digest(
+    data: &TransactionData,
+    signature: &Signature,
+) -> anyhow::Result<TransactionDigest> {
+    signature.verify(data, data.sender)?;
+    Ok(SenderSignedData {
+        data: data.clone(),
+        tx_signature: signature.clone(),
+    }
+    .digest())
+}
+
+/// Check that `certificate` is signed by a quorum of `committee`.
+pub fn verify_certificate(
+    certificate: &CertifiedTransaction,
+    committee: &Committee,
+) -> anyhow::Result<()> {
+    Ok(certificate.verify(committee)?)
+}
+
+/// Check that `effects` are signed by a quorum of `committee`.
+pub fn verify_effects_certificate(
+    effects: &CertifiedTransactionEffects,
+    committee: &Committee,
+) -> anyhow::Result<()> {
+    Ok(effects.auth_signature.verify(&effects.effects, committee)?)
+}
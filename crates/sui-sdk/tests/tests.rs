@@ -2,11 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use tempfile::TempDir;
 
+use sui_json_rpc_types::{
+    SuiEvent, SuiMoveAbility, SuiMoveAbilitySet, SuiMoveNormalizedField, SuiMoveNormalizedStruct,
+    SuiMoveNormalizedType, SuiMoveStructTypeParameter,
+};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_sdk::events::{self, TypedMoveEvent};
+use sui_sdk::objects::{self, TypedMoveObject, TypedObjectError};
+use sui_sdk::offline::{sign_transaction, transaction_data_from_bytes, transaction_data_to_bytes};
+use sui_sdk::transaction_digest;
+use sui_types::balance::Balance;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+use sui_types::crypto::{get_key_pair, AccountKeyPair, SignatureScheme, SuiSignatureInner};
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::object::Object;
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -68,3 +81,279 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn transaction_digest_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+
+    for scheme in [SignatureScheme::ED25519, SignatureScheme::Secp256k1] {
+        let (sender, _, _) = keystore.generate_new_key(scheme, None)?;
+        let gas = (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        );
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::random_for_testing_only(),
+            sender,
+            None,
+            gas,
+            1000,
+        );
+        let signature = keystore.sign(&sender, &data.to_bytes())?;
+
+        let digest = transaction_digest(&data, &signature)?;
+        assert_eq!(&digest, Transaction::new(data.clone(), signature).digest());
+
+        // Signing again yields the same digest, so a retried submission is recognized.
+        let signature = keystore.sign(&sender, &data.to_bytes())?;
+        assert_eq!(digest, transaction_digest(&data, &signature)?);
+
+        // A signature over anything other than the transaction data is rejected.
+        let signature = keystore.sign(&sender, b"not a transaction")?;
+        assert!(transaction_digest(&data, &signature).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn offline_sign_transaction_test() -> Result<(), anyhow::Error> {
+    let (sender, keypair) = get_key_pair::<AccountKeyPair>();
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        sender,
+        None,
+        gas,
+        1000,
+    );
+
+    let bytes = transaction_data_to_bytes(&data);
+    assert_eq!(transaction_data_from_bytes(&bytes)?, data);
+
+    let tx = sign_transaction(data.clone(), &keypair);
+    tx.verify_sender_signature()?;
+    assert_eq!(
+        &transaction_digest(&data, &tx.signed_data.tx_signature)?,
+        tx.digest()
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, TypedMoveEvent)]
+#[move_event(module = "devnet_nft", name = "MintNFTEvent", package = "0x2")]
+struct MintNftEvent {
+    object_id: ObjectID,
+    creator: SuiAddress,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveEvent)]
+#[move_event(module = "market")]
+struct OrderPlaced {
+    price: u64,
+}
+
+/// Implemented by hand, as the derive rejects packages which are not addresses.
+#[derive(Debug, PartialEq, Deserialize)]
+struct OverlongPackageEvent {
+    price: u64,
+}
+
+impl TypedMoveEvent for OverlongPackageEvent {
+    const MODULE: &'static str = "market";
+    const NAME: &'static str = "OrderPlaced";
+    const PACKAGE: Option<&'static str> = Some("0x000000000000000000000000000000000000000002");
+}
+
+fn move_event(type_: &str, bcs: Vec<u8>) -> SuiEvent {
+    SuiEvent::MoveEvent {
+        package_id: ObjectID::random(),
+        transaction_module: "test".to_string(),
+        sender: SuiAddress::random_for_testing_only(),
+        type_: type_.to_string(),
+        fields: None,
+        bcs,
+    }
+}
+
+#[test]
+fn typed_move_event_test() -> Result<(), anyhow::Error> {
+    let mint = MintNftEvent {
+        object_id: ObjectID::random(),
+        creator: SuiAddress::random_for_testing_only(),
+        name: "Example NFT".to_string(),
+    };
+    let package = ObjectID::random();
+    let emitted = vec![
+        move_event("0x2::devnet_nft::MintNFTEvent", bcs::to_bytes(&mint)?),
+        move_event(
+            &format!("{package}::market::OrderPlaced"),
+            bcs::to_bytes(&7u64)?,
+        ),
+        move_event("0x3::devnet_nft::MintNFTEvent", bcs::to_bytes(&mint)?),
+        SuiEvent::Publish {
+            sender: SuiAddress::random_for_testing_only(),
+            package_id: package,
+        },
+    ];
+
+    let mints =
+        events::parse_events::<MintNftEvent>(&emitted, None).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(mints, vec![mint]);
+
+    assert_eq!(
+        events::parse_event::<OrderPlaced>(&emitted[1], None).transpose()?,
+        Some(OrderPlaced { price: 7 })
+    );
+    assert!(events::parse_event::<OrderPlaced>(&emitted[1], Some(ObjectID::random())).is_none());
+    assert!(events::parse_event::<OrderPlaced>(&emitted[0], None).is_none());
+    // A declared package which is not an address matches no event.
+    assert!(events::parse_event::<OverlongPackageEvent>(&emitted[1], None).is_none());
+    // Events whose type cannot be parsed are of no type.
+    let unparsed = move_event("market::OrderPlaced", bcs::to_bytes(&7u64)?);
+    assert!(events::parse_event::<OrderPlaced>(&unparsed, None).is_none());
+
+    // Contents without the layout of the struct fail to decode.
+    let truncated = move_event(&format!("{package}::market::OrderPlaced"), vec![1, 2]);
+    assert!(matches!(
+        events::parse_event::<OrderPlaced>(&truncated, Some(package)),
+        Some(Err(_))
+    ));
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveObject)]
+#[move_object(module = "coin", name = "Coin", package = "0x2")]
+struct Coin {
+    id: ObjectID,
+    balance: Balance,
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveObject)]
+#[move_object(module = "coin", name = "Coin")]
+struct CoinWithExtraField {
+    id: ObjectID,
+    balance: Balance,
+    locked: bool,
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveObject)]
+#[move_object(module = "coin", name = "Coin")]
+struct CoinWithU64Balance {
+    id: ObjectID,
+    balance: u64,
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveObject)]
+#[move_object(module = "coin", name = "Coin")]
+struct CoinWithRenamedField {
+    id: ObjectID,
+    value: Balance,
+}
+
+#[derive(Debug, PartialEq, Deserialize, TypedMoveObject)]
+#[move_object(module = "devnet_nft", name = "DevNetNFT", package = "0x2")]
+struct DevNetNft {
+    id: ObjectID,
+    name: String,
+    description: String,
+    url: String,
+}
+
+/// The layout of `0x2::coin::Coin`, as returned by `sui_getNormalizedMoveStruct`.
+fn coin_layout() -> SuiMoveNormalizedStruct {
+    let field =
+        |name: &str, module: &str, struct_name: &str, type_arguments| SuiMoveNormalizedField {
+            name: name.to_string(),
+            type_: SuiMoveNormalizedType::Struct {
+                address: "0x2".to_string(),
+                module: module.to_string(),
+                name: struct_name.to_string(),
+                type_arguments,
+            },
+        };
+    SuiMoveNormalizedStruct {
+        abilities: SuiMoveAbilitySet {
+            abilities: vec![SuiMoveAbility::Store, SuiMoveAbility::Key],
+        },
+        type_parameters: vec![SuiMoveStructTypeParameter {
+            constraints: SuiMoveAbilitySet { abilities: vec![] },
+            is_phantom: true,
+        }],
+        fields: vec![
+            field("id", "object", "UID", vec![]),
+            field(
+                "balance",
+                "balance",
+                "Balance",
+                vec![SuiMoveNormalizedType::TypeParameter(0)],
+            ),
+        ],
+    }
+}
+
+#[test]
+fn typed_move_object_test() -> Result<(), anyhow::Error> {
+    let object = Object::with_id_owner_gas_for_testing(
+        ObjectID::random(),
+        SuiAddress::random_for_testing_only(),
+        1000,
+    );
+    let object_id = object.id();
+    let layout = coin_layout();
+
+    let coin =
+        objects::parse_stored_object::<Coin>(&object, Some(object.version()), None, &layout)?;
+    assert_eq!(
+        coin.contents,
+        Coin {
+            id: object_id,
+            balance: Balance::new(1000),
+        }
+    );
+    assert_eq!(coin.reference, object.compute_object_reference());
+    assert_eq!(coin.owner, object.owner);
+
+    let stale = SequenceNumber::from(object.version().value() + 1);
+    assert_eq!(
+        objects::parse_stored_object::<Coin>(&object, Some(stale), None, &layout).unwrap_err(),
+        TypedObjectError::VersionMismatch {
+            object_id,
+            expected: stale,
+            actual: object.version(),
+        }
+    );
+    assert!(matches!(
+        objects::parse_stored_object::<Coin>(&object, None, Some(ObjectID::random()), &layout),
+        Err(TypedObjectError::TypeMismatch { .. })
+    ));
+    assert!(matches!(
+        objects::parse_stored_object::<DevNetNft>(&object, None, None, &layout),
+        Err(TypedObjectError::TypeMismatch { .. })
+    ));
+
+    // Structs without the fields of the Move struct fail to decode, even when their contents
+    // would have the layout of the struct.
+    assert!(matches!(
+        objects::parse_stored_object::<CoinWithExtraField>(&object, None, None, &layout),
+        Err(TypedObjectError::LayoutMismatch { .. })
+    ));
+    assert!(matches!(
+        objects::parse_stored_object::<CoinWithU64Balance>(&object, None, None, &layout),
+        Err(TypedObjectError::LayoutMismatch { .. })
+    ));
+    assert!(matches!(
+        objects::parse_stored_object::<CoinWithRenamedField>(&object, None, None, &layout),
+        Err(TypedObjectError::LayoutMismatch { .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+use sui_storage::db_metrics::DBMetrics;
+use sui_storage::{IndexBatch, IndexStore};
+use sui_types::base_types::{ExecutionDigests, ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::batch::TxSequenceNumber;
+use sui_types::object::Owner;
+
+const NUM_TRANSACTIONS: usize = 1000;
+
+/// A sender, a recipient and the digests of each transaction.
+fn test_transactions() -> Vec<(SuiAddress, SuiAddress, ExecutionDigests)> {
+    (0..NUM_TRANSACTIONS)
+        .map(|_| {
+            (
+                SuiAddress::random_for_testing_only(),
+                SuiAddress::random_for_testing_only(),
+                ExecutionDigests::random(),
+            )
+        })
+        .collect()
+}
+
+/// Indexes the transactions, which transfer one object each, writing a batch every
+/// `batch_size` transactions.
+fn index_transactions(
+    store: &IndexStore,
+    transactions: &[(SuiAddress, SuiAddress, ExecutionDigests)],
+    batch_size: usize,
+) {
+    for chunk in transactions.chunks(batch_size) {
+        let mut batch = IndexBatch::new(store.watermark().unwrap());
+        for (sender, recipient, digests) in chunk {
+            let seq: TxSequenceNumber = batch.watermark().next_sequence;
+            let object_ref = (
+                ObjectID::random(),
+                SequenceNumber::from(1),
+                ObjectDigest::random(),
+            );
+            batch.add_tx(
+                *sender,
+                std::iter::once(object_ref.0),
+                std::iter::once((object_ref, Owner::AddressOwner(*recipient))),
+                std::iter::empty(),
+                vec![],
+                Owner::AddressOwner(*sender),
+                seq,
+                digests,
+                0,
+            );
+        }
+        store.write_batch(batch).unwrap();
+    }
+}
+
+/// Compares writing the indexes of each transaction on its own with writing them in batches.
+fn bench_index_batch_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IndexStore ingestion varying batch size");
+    group.throughput(Throughput::Elements(NUM_TRANSACTIONS as u64));
+    group.sample_size(10);
+
+    for batch_size in [1, 10, 100, 1000] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(batch_size),
+            &batch_size,
+            |b, &batch_size| {
+                b.iter_batched(
+                    || {
+                        let dir = tempfile::tempdir().unwrap();
+                        let store = IndexStore::open(
+                            dir.path().to_path_buf(),
+                            None,
+                            DBMetrics::new_unregistered(),
+                        );
+                        (dir, store, test_transactions())
+                    },
+                    |(_dir, store, transactions)| {
+                        index_transactions(&store, &transactions, batch_size)
+                    },
+                    criterion::BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_index_batch_size);
+criterion_main!(benches);
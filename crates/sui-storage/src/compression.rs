@@ -0,0 +1,296 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compression of the column families holding large values which are rarely read once written,
+//! e.g. certificates and effects. Their blocks are compressed by rocksdb with zstd, using a
+//! dictionary trained on blocks sampled when they are compacted, which compresses the many
+//! small and similar values of these column families far better than each block on its own.
+//! Blocks are decompressed transparently when read, whatever they were compressed with.
+//!
+//! Compression is set by column family name before the stores are opened, and applies to the
+//! files written from then on. The files written before are rewritten by [migrate], which
+//! compacts the column families whose compression changed since it last ran on the database.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::{Lazy, OnceCell};
+use rocksdb::{DBCompressionType, Options};
+use tracing::{error, info};
+
+use crate::db_metrics::{DBMetrics, RocksDB};
+use crate::default_db_options;
+
+/// Extension of the file, next to a database, recording the compression its column families
+/// were last migrated to.
+const MIGRATED_EXTENSION: &str = "compression";
+
+/// How column families without compression are recorded as migrated.
+const NO_COMPRESSION: &str = "none";
+
+/// The column families which can be compressed: only their options apply the compression set
+/// for them.
+pub const COMPRESSIBLE_CFS: &[&str] = &[
+    "transactions",
+    "certificates",
+    "effects",
+    "checkpoints",
+    "checkpoint_contents",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CfCompression {
+    /// The zstd compression level.
+    pub level: i32,
+    /// The size of the dictionary, or 0 to compress each block on its own.
+    pub max_dict_bytes: u32,
+    /// The most data sampled to train the dictionary, or 0 to use the sampled data as the
+    /// dictionary.
+    pub max_train_bytes: u32,
+}
+
+impl Display for CfCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "zstd level={} dict={} train={}",
+            self.level, self.max_dict_bytes, self.max_train_bytes
+        )
+    }
+}
+
+static COMPRESSION: Lazy<RwLock<BTreeMap<String, CfCompression>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// The options of the databases with compressed column families, shared so that the statistics
+/// of compression are collected across them.
+static DB_OPTIONS: OnceCell<Options> = OnceCell::new();
+
+/// Compresses the column families named `cf` of the stores opened from now on with
+/// `compression`, or not at all if None. Fails if `cf` is not one of `COMPRESSIBLE_CFS`.
+pub fn set_cf_compression(cf: &str, compression: Option<CfCompression>) -> Result<()> {
+    if !COMPRESSIBLE_CFS.contains(&cf) {
+        return Err(anyhow!(
+            "Column family {cf} cannot be compressed, only {COMPRESSIBLE_CFS:?} can"
+        ));
+    }
+    let mut compressions = COMPRESSION.write().unwrap();
+    match compression {
+        Some(compression) => compressions.insert(cf.to_string(), compression),
+        None => compressions.remove(cf),
+    };
+    Ok(())
+}
+
+pub fn cf_compression(cf: &str) -> Option<CfCompression> {
+    COMPRESSION.read().unwrap().get(cf).copied()
+}
+
+/// `options` with the compression set for the column families named `cf`, if any.
+pub fn with_cf_compression(cf: &str, mut options: Options) -> Options {
+    if let Some(compression) = cf_compression(cf) {
+        let max_dict_bytes = compression.max_dict_bytes as i32;
+        options.set_compression_type(DBCompressionType::Zstd);
+        // The window bits and strategy are not used by zstd.
+        options.set_compression_options(-14, compression.level, 0, max_dict_bytes);
+        options.set_zstd_max_train_bytes(compression.max_train_bytes as i32);
+    }
+    options
+}
+
+/// The options to open databases with, collecting the statistics of compression, if any column
+/// family is compressed. None otherwise, for the default options.
+pub fn db_options() -> Option<Options> {
+    if COMPRESSION.read().unwrap().is_empty() {
+        return None;
+    }
+    let options = DB_OPTIONS.get_or_init(|| {
+        let mut options = default_db_options(None, None).0;
+        options.enable_statistics();
+        options
+    });
+    Some(options.clone())
+}
+
+/// The statistics collected by rocksdb across the databases opened with [db_options].
+pub fn statistics() -> Option<String> {
+    DB_OPTIONS.get().and_then(Options::get_statistics)
+}
+
+fn migrated_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension(MIGRATED_EXTENSION)
+}
+
+/// The compression the column families were last migrated to, as recorded at `path`, one
+/// column family per line as `<cf> <compression>`.
+fn read_migrated(path: &Path) -> Result<BTreeMap<String, String>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    contents
+        .lines()
+        .map(|line| {
+            line.split_once(' ')
+                .map(|(cf, compression)| (cf.to_string(), compression.to_string()))
+                .ok_or_else(|| anyhow!("Invalid line {line:?} in {}", path.display()))
+        })
+        .collect()
+}
+
+fn write_migrated(path: &Path, migrated: &BTreeMap<String, String>) -> Result<()> {
+    let contents: String = migrated
+        .iter()
+        .map(|(cf, compression)| format!("{cf} {compression}\n"))
+        .collect();
+    // Written aside then renamed, so that a crash leaves either version of the file.
+    let tmp_path = path.with_extension("compression.tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Compacts the compressible column families of the database registered as `db` in
+/// `db_metrics` whose compression changed since they were last migrated, rewriting all their
+/// files, including those of the bottommost level, with the current compression. Blocks until
+/// done. Returns the migrated column families.
+pub fn migrate(db_metrics: &DBMetrics, db: &str) -> Result<Vec<String>> {
+    let rocksdb = db_metrics.registered_db(db)?;
+    let path = migrated_path(rocksdb.path());
+    let mut migrated = read_migrated(&path)?;
+    let mut migrated_cfs = vec![];
+    for cf in RocksDB::list_cf(&Options::default(), rocksdb.path())? {
+        if !COMPRESSIBLE_CFS.contains(&cf.as_str()) {
+            continue;
+        }
+        let compression = cf_compression(&cf)
+            .map(|compression| compression.to_string())
+            .unwrap_or_else(|| NO_COMPRESSION.to_string());
+        if migrated.get(&cf).map_or(NO_COMPRESSION, String::as_str) == compression {
+            continue;
+        }
+        info!(db, cf = %cf, "Migrating column family to compression {compression}");
+        let start = Instant::now();
+        db_metrics.rewrite_cf(db, &cf)?;
+        db_metrics.record_compression_migration(db, &cf, start.elapsed());
+        migrated.insert(cf.clone(), compression);
+        write_migrated(&path, &migrated)?;
+        migrated_cfs.push(cf);
+    }
+    Ok(migrated_cfs)
+}
+
+/// Migrates the column families of all the registered databases, see [migrate].
+pub fn migrate_registered_dbs(db_metrics: &DBMetrics) {
+    for (db, _) in db_metrics.registered_dbs() {
+        match migrate(db_metrics, &db) {
+            Ok(cfs) if !cfs.is_empty() => info!(db = %db, ?cfs, "Migrated compression"),
+            Ok(_) => (),
+            Err(err) => error!(db = %db, "Failed to migrate compression: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocksdb::ColumnFamilyDescriptor;
+    use std::sync::Arc;
+
+    fn open_db(db_metrics: &DBMetrics, path: &Path, cf_options: Options) -> Arc<RocksDB> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let descriptor = ColumnFamilyDescriptor::new("certificates", cf_options);
+        let db = RocksDB::open_cf_descriptors(&options, path, vec![descriptor]).unwrap();
+        let db = Arc::new(db);
+        db_metrics.register_db("test_migrate", &db);
+        db
+    }
+
+    fn stored_size(db: &RocksDB) -> u64 {
+        let cf = db.cf_handle("certificates").unwrap();
+        db.property_int_value_cf(&cf, "rocksdb.total-sst-files-size")
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_migrate() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        let value = |i: u32| format!("value {i}").repeat(10).into_bytes();
+        let db_metrics = DBMetrics::new_unregistered();
+
+        // Data written before the column family is compressed.
+        let mut cf_options = Options::default();
+        cf_options.set_compression_type(DBCompressionType::None);
+        let db = open_db(&db_metrics, &db_path, cf_options);
+        let cf = db.cf_handle("certificates").unwrap();
+        for i in 0u32..1000 {
+            db.put_cf(&cf, i.to_be_bytes(), value(i)).unwrap();
+        }
+        db.flush_cf(&cf).unwrap();
+        db_metrics
+            .rewrite_cf("test_migrate", "certificates")
+            .unwrap();
+        let uncompressed_size = stored_size(&db);
+        drop(cf);
+        drop(db);
+
+        set_cf_compression(
+            "certificates",
+            Some(CfCompression {
+                level: 3,
+                max_dict_bytes: 1024,
+                max_train_bytes: 0,
+            }),
+        )
+        .unwrap();
+        let cf_options = with_cf_compression("certificates", Options::default());
+        let db = open_db(&db_metrics, &db_path, cf_options);
+
+        // Only the compressed column family changed, and only once.
+        assert_eq!(
+            migrate(&db_metrics, "test_migrate").unwrap(),
+            vec!["certificates"]
+        );
+        assert!(migrate(&db_metrics, "test_migrate").unwrap().is_empty());
+        let migrated = read_migrated(&migrated_path(&db_path)).unwrap();
+        assert!(!migrated.contains_key("default"));
+        assert_eq!(migrated["certificates"], "zstd level=3 dict=1024 train=0");
+
+        // The files written before were rewritten compressed, and read back the same.
+        assert!(stored_size(&db) < uncompressed_size);
+        let cf = db.cf_handle("certificates").unwrap();
+        for i in 0u32..1000 {
+            assert_eq!(db.get_cf(&cf, i.to_be_bytes()).unwrap().unwrap(), value(i));
+        }
+
+        // Removing the compression migrates the column family back.
+        set_cf_compression("certificates", None).unwrap();
+        assert_eq!(
+            migrate(&db_metrics, "test_migrate").unwrap(),
+            vec!["certificates"]
+        );
+        assert!(migrate(&db_metrics, "unknown").is_err());
+    }
+
+    #[test]
+    fn test_only_compressible_cfs() {
+        let compression = CfCompression {
+            level: 3,
+            max_dict_bytes: 0,
+            max_train_bytes: 0,
+        };
+        assert!(set_cf_compression("objects", Some(compression)).is_err());
+        assert_eq!(cf_compression("objects"), None);
+    }
+}
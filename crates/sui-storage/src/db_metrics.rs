@@ -0,0 +1,510 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metrics per column family of the databases of a node: the number and latency of the reads,
+//! writes and deletes made by the stores, and the sizes rocksdb reports for each column family.
+//! Batches are timed as a whole, by database, while the keys they write and delete are counted
+//! by column family. Failed writes are counted per database. The savings of compression are
+//! shown by the raw and stored sizes of the data of each column family, and its cost by the
+//! amount of data rocksdb compressed and decompressed.
+//!
+//! Each node has its own `DBMetrics`, shared by its stores, which register their databases by
+//! name when opened, so that their column families can be inspected and compacted by name.
+//! Stores opened outside of a node, e.g. by tools, record into metrics which are not exported.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use prometheus::{
+    register_gauge_vec_with_registry, register_histogram_vec_with_registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, GaugeVec,
+    HistogramVec, IntCounterVec, IntGaugeVec, Registry,
+};
+use rocksdb::{
+    BottommostLevelCompaction, CompactOptions, DBWithThreadMode, MultiThreaded, Options,
+};
+use tracing::warn;
+
+use crate::compression;
+
+pub type RocksDB = DBWithThreadMode<MultiThreaded>;
+
+/// The rocksdb properties of each column family exported as gauges.
+const CF_PROPERTIES: &[&str] = &[
+    "rocksdb.estimate-live-data-size",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.estimate-num-keys",
+    "rocksdb.cur-size-all-mem-tables",
+];
+
+/// The rocksdb property listing the properties of the files of a column family summed up, as
+/// `<name>=<value>` separated by `; `.
+const AGGREGATED_TABLE_PROPERTIES: &str = "rocksdb.aggregated-table-properties";
+
+/// The rocksdb statistics of compression exported, by operation, as the statistic counting
+/// blocks and the histogram of their sizes.
+const COMPRESSION_STATISTICS: &[(&str, &str, &str)] = &[
+    (
+        "compress",
+        "rocksdb.number.block.compressed",
+        "rocksdb.bytes.compressed",
+    ),
+    (
+        "decompress",
+        "rocksdb.number.block.decompressed",
+        "rocksdb.bytes.decompressed",
+    ),
+];
+
+const LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.00001, 0.00005, 0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DBOp {
+    Read,
+    Write,
+    Delete,
+}
+
+impl DBOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DBOp::Read => "read",
+            DBOp::Write => "write",
+            DBOp::Delete => "delete",
+        }
+    }
+}
+
+pub struct DBMetrics {
+    op_count: IntCounterVec,
+    op_latency: HistogramVec,
+    batch_latency: HistogramVec,
+    cf_property: IntGaugeVec,
+    write_errors: IntCounterVec,
+    cf_data_size: IntGaugeVec,
+    compression_blocks: IntGaugeVec,
+    compression_bytes: IntGaugeVec,
+    compression_migration_sec: GaugeVec,
+    /// Failed writes across the databases.
+    write_error_count: AtomicU64,
+    databases: Mutex<BTreeMap<String, Weak<RocksDB>>>,
+}
+
+impl DBMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            op_count: register_int_counter_vec_with_registry!(
+                "db_cf_op_count",
+                "Number of keys read, written and deleted, by database and column family",
+                &["db", "cf", "op"],
+                registry,
+            )
+            .unwrap(),
+            op_latency: register_histogram_vec_with_registry!(
+                "db_cf_op_latency_sec",
+                "Latency of the reads, writes and deletes made outside of batches, by database \
+                 and column family",
+                &["db", "cf", "op"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            batch_latency: register_histogram_vec_with_registry!(
+                "db_batch_latency_sec",
+                "Latency of writing batches, by database",
+                &["db"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            cf_property: register_int_gauge_vec_with_registry!(
+                "db_cf_property",
+                "Sizes reported by rocksdb, by database and column family",
+                &["db", "cf", "property"],
+                registry,
+            )
+            .unwrap(),
+            write_errors: register_int_counter_vec_with_registry!(
+                "db_write_errors",
+                "Number of batches which failed to be written, by database",
+                &["db"],
+                registry,
+            )
+            .unwrap(),
+            cf_data_size: register_int_gauge_vec_with_registry!(
+                "db_cf_data_size",
+                "Size of the keys and values in the files of a column family, before (raw) and \
+                 after (stored) compression, by database and column family",
+                &["db", "cf", "kind"],
+                registry,
+            )
+            .unwrap(),
+            compression_blocks: register_int_gauge_vec_with_registry!(
+                "db_compression_blocks",
+                "Number of blocks compressed and decompressed since the node started, across the \
+                 databases with compressed column families",
+                &["op"],
+                registry,
+            )
+            .unwrap(),
+            compression_bytes: register_int_gauge_vec_with_registry!(
+                "db_compression_bytes",
+                "Size of the blocks compressed and decompressed since the node started, across \
+                 the databases with compressed column families",
+                &["op"],
+                registry,
+            )
+            .unwrap(),
+            compression_migration_sec: register_gauge_vec_with_registry!(
+                "db_cf_compression_migration_sec",
+                "Time taken to rewrite a column family after its compression changed, by \
+                 database and column family",
+                &["db", "cf"],
+                registry,
+            )
+            .unwrap(),
+            write_error_count: AtomicU64::new(0),
+            databases: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Metrics which are not exported, for the stores opened outside of a node.
+    pub fn new_unregistered() -> Arc<Self> {
+        Arc::new(Self::new(&Registry::new()))
+    }
+
+    /// Runs `f`, a single operation on `cf` of `db`, recording its latency.
+    pub fn metered<T>(&self, db: &str, cf: &str, op: DBOp, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        let labels = [db, cf, op.as_str()];
+        self.op_count.with_label_values(&labels).inc();
+        self.op_latency
+            .with_label_values(&labels)
+            .observe(start.elapsed().as_secs_f64());
+        result
+    }
+
+    /// Records a batch, which took `latency` to write, of operations on column families of `db`,
+    /// as `(column family, operation, number of keys)`.
+    pub fn record_batch(&self, db: &str, ops: &[(&str, DBOp, usize)], latency: Duration) {
+        for (cf, op, count) in ops {
+            if *count > 0 {
+                self.op_count
+                    .with_label_values(&[db, cf, op.as_str()])
+                    .inc_by(*count as u64);
+            }
+        }
+        self.batch_latency
+            .with_label_values(&[db])
+            .observe(latency.as_secs_f64());
+    }
+
+    /// Records that a batch failed to be written to `db`.
+    pub fn record_write_error(&self, db: &str) {
+        self.write_error_count.fetch_add(1, Ordering::Relaxed);
+        self.write_errors.with_label_values(&[db]).inc();
+    }
+
+    /// Number of batches which failed to be written since the node started, across databases.
+    pub fn write_error_count(&self) -> u64 {
+        self.write_error_count.load(Ordering::Relaxed)
+    }
+
+    /// Registers a database under `name`, replacing the database registered under that name if
+    /// any, e.g. the tables of the previous epoch.
+    pub fn register_db(&self, name: &str, db: &Arc<RocksDB>) {
+        self.databases
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), Arc::downgrade(db));
+    }
+
+    pub(crate) fn registered_db(&self, name: &str) -> Result<Arc<RocksDB>> {
+        self.databases
+            .lock()
+            .unwrap()
+            .get(name)
+            .and_then(Weak::upgrade)
+            .ok_or_else(|| anyhow!("Unknown database {name}"))
+    }
+
+    /// The registered databases which are still open, with their column families.
+    pub fn registered_dbs(&self) -> Vec<(String, Vec<String>)> {
+        let databases: Vec<_> = self
+            .databases
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(name, db)| Some((name.clone(), db.upgrade()?)))
+            .collect();
+        databases
+            .into_iter()
+            .map(|(name, db)| {
+                let cfs = RocksDB::list_cf(&Options::default(), db.path()).unwrap_or_default();
+                (name, cfs)
+            })
+            .collect()
+    }
+
+    /// Records that rewriting `cf` of `db` with its new compression took `duration`.
+    pub(crate) fn record_compression_migration(&self, db: &str, cf: &str, duration: Duration) {
+        self.compression_migration_sec
+            .with_label_values(&[db, cf])
+            .set(duration.as_secs_f64());
+    }
+
+    /// Updates the gauges of the rocksdb properties of the column families of the registered
+    /// databases, and of the statistics of compression.
+    pub fn report_cf_properties(&self) {
+        for (name, cfs) in self.registered_dbs() {
+            let db = match self.registered_db(&name) {
+                Ok(db) => db,
+                Err(_) => continue,
+            };
+            for cf_name in cfs {
+                let cf = match db.cf_handle(&cf_name) {
+                    Some(cf) => cf,
+                    None => continue,
+                };
+                for property in CF_PROPERTIES {
+                    match db.property_int_value_cf(&cf, property) {
+                        Ok(Some(value)) => self
+                            .cf_property
+                            .with_label_values(&[name.as_str(), cf_name.as_str(), property])
+                            .set(value as i64),
+                        Ok(None) => (),
+                        Err(err) => {
+                            warn!(db = %name, cf = %cf_name, "Failed to read {property}: {err}")
+                        }
+                    }
+                }
+                let sizes = match db.property_value_cf(&cf, AGGREGATED_TABLE_PROPERTIES) {
+                    Ok(properties) => properties.as_deref().and_then(data_sizes),
+                    Err(err) => {
+                        warn!(db = %name, cf = %cf_name, "Failed to read table properties: {err}");
+                        None
+                    }
+                };
+                if let Some((raw, stored)) = sizes {
+                    for (kind, size) in [("raw", raw), ("stored", stored)] {
+                        self.cf_data_size
+                            .with_label_values(&[name.as_str(), cf_name.as_str(), kind])
+                            .set(size as i64);
+                    }
+                }
+            }
+        }
+        if let Some(statistics) = compression::statistics() {
+            for (op, blocks, bytes) in COMPRESSION_STATISTICS {
+                if let Some(count) = statistic(&statistics, blocks, "COUNT") {
+                    self.compression_blocks
+                        .with_label_values(&[op])
+                        .set(count as i64);
+                }
+                if let Some(sum) = statistic(&statistics, bytes, "SUM") {
+                    self.compression_bytes
+                        .with_label_values(&[op])
+                        .set(sum as i64);
+                }
+            }
+        }
+    }
+
+    /// The rocksdb properties of each column family of the database registered as `db`, e.g.
+    /// the estimated number of keys, by column family.
+    pub fn cf_properties(&self, db: &str) -> Result<Vec<(String, Vec<(&'static str, u64)>)>> {
+        let rocksdb = self.registered_db(db)?;
+        let cfs = RocksDB::list_cf(&Options::default(), rocksdb.path())?;
+        let mut properties = Vec::with_capacity(cfs.len());
+        for cf_name in cfs {
+            let cf = match rocksdb.cf_handle(&cf_name) {
+                Some(cf) => cf,
+                None => continue,
+            };
+            let mut values = Vec::with_capacity(CF_PROPERTIES.len());
+            for property in CF_PROPERTIES {
+                if let Some(value) = rocksdb.property_int_value_cf(&cf, property)? {
+                    values.push((*property, value));
+                }
+            }
+            properties.push((cf_name, values));
+        }
+        Ok(properties)
+    }
+
+    /// Compacts the whole key range of `cf` in the database registered as `db`. Blocks until
+    /// the compaction is done.
+    pub fn compact_cf(&self, db: &str, cf: &str) -> Result<()> {
+        let rocksdb = self.registered_db(db)?;
+        let handle = rocksdb
+            .cf_handle(cf)
+            .ok_or_else(|| anyhow!("Unknown column family {cf} in database {db}"))?;
+        rocksdb.compact_range_cf(&handle, None::<&[u8]>, None::<&[u8]>);
+        Ok(())
+    }
+
+    /// Like `compact_cf`, but also compacts the files of the bottommost level, which rocksdb
+    /// skips by default, so that all the files of the column family are rewritten with its
+    /// current options.
+    pub fn rewrite_cf(&self, db: &str, cf: &str) -> Result<()> {
+        let rocksdb = self.registered_db(db)?;
+        let handle = rocksdb
+            .cf_handle(cf)
+            .ok_or_else(|| anyhow!("Unknown column family {cf} in database {db}"))?;
+        let mut options = CompactOptions::default();
+        options.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+        rocksdb.compact_range_cf_opt(&handle, None::<&[u8]>, None::<&[u8]>, &options);
+        Ok(())
+    }
+}
+
+/// The raw size of the keys and values of the files of a column family, and the size of their
+/// data blocks as stored, from their aggregated table properties.
+fn data_sizes(properties: &str) -> Option<(u64, u64)> {
+    let property = |name: &str| {
+        properties
+            .split("; ")
+            .find_map(|property| property.strip_prefix(name)?.strip_prefix('='))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    let raw = property("raw key size")? + property("raw value size")?;
+    Some((raw, property("data block size")?))
+}
+
+/// The value of `field`, e.g. `COUNT`, of the statistic `name` in the rocksdb `statistics`,
+/// listing one statistic per line as `<name> <field> : <value> ...`.
+fn statistic(statistics: &str, name: &str, field: &str) -> Option<u64> {
+    let line = statistics
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(name))?;
+    let mut tokens = line.split_whitespace();
+    tokens.find(|token| *token == field)?;
+    if tokens.next() != Some(":") {
+        return None;
+    }
+    tokens.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_db(path: &std::path::Path) -> Arc<RocksDB> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        Arc::new(RocksDB::open_cf(&options, path, ["objects"]).unwrap())
+    }
+
+    #[test]
+    fn test_registered_dbs() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(dir.path());
+        db.put_cf(&db.cf_handle("objects").unwrap(), b"key", b"value")
+            .unwrap();
+        let metrics = DBMetrics::new_unregistered();
+        metrics.register_db("store", &db);
+
+        let (_, cfs) = metrics
+            .registered_dbs()
+            .into_iter()
+            .find(|(name, _)| name == "store")
+            .unwrap();
+        assert!(cfs.contains(&"objects".to_string()));
+        let properties = metrics.cf_properties("store").unwrap();
+        let (_, objects) = properties.iter().find(|(cf, _)| cf == "objects").unwrap();
+        assert!(objects
+            .iter()
+            .any(|(property, _)| *property == "rocksdb.estimate-num-keys"));
+        assert!(metrics.cf_properties("unknown").is_err());
+        metrics.compact_cf("store", "objects").unwrap();
+        assert!(metrics.compact_cf("store", "unknown").is_err());
+
+        // Closed databases are no longer listed.
+        drop(db);
+        assert!(metrics.registered_dbs().is_empty());
+    }
+
+    #[test]
+    fn test_metrics_are_per_node() {
+        let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+        let dbs = [open_db(dirs[0].path()), open_db(dirs[1].path())];
+        let registries = [Registry::new(), Registry::new()];
+        let metrics = [
+            DBMetrics::new(&registries[0]),
+            DBMetrics::new(&registries[1]),
+        ];
+        metrics[0].register_db("store", &dbs[0]);
+        metrics[1].register_db("store", &dbs[1]);
+
+        // Each node sees its own database under the same name, and its own errors.
+        assert_eq!(
+            metrics[0].registered_db("store").unwrap().path(),
+            dirs[0].path()
+        );
+        assert_eq!(
+            metrics[1].registered_db("store").unwrap().path(),
+            dirs[1].path()
+        );
+        metrics[0].record_write_error("store");
+        assert_eq!(metrics[0].write_error_count(), 1);
+        assert_eq!(metrics[1].write_error_count(), 0);
+
+        // A batch is timed once, while its keys are counted by column family.
+        metrics[0].record_batch(
+            "store",
+            &[
+                ("objects", DBOp::Write, 3),
+                ("owner_index", DBOp::Delete, 2),
+            ],
+            Duration::from_millis(1),
+        );
+        let count = |cf: &str, op: DBOp| {
+            metrics[0]
+                .op_count
+                .with_label_values(&["store", cf, op.as_str()])
+                .get()
+        };
+        assert_eq!(count("objects", DBOp::Write), 3);
+        assert_eq!(count("owner_index", DBOp::Delete), 2);
+        let batches = |metrics: &DBMetrics| {
+            metrics
+                .batch_latency
+                .with_label_values(&["store"])
+                .get_sample_count()
+        };
+        assert_eq!(batches(&metrics[0]), 1);
+        assert_eq!(batches(&metrics[1]), 0);
+    }
+
+    #[test]
+    fn test_data_sizes() {
+        let properties = "# data blocks=3; # entries=100; raw key size=1600; \
+            raw average key size=16; raw value size=8000; raw average value size=80; \
+            data block size=2048; index block size (user-key? 0, delta-value? 0)=64";
+        assert_eq!(data_sizes(properties), Some((9600, 2048)));
+        assert_eq!(data_sizes("# entries=100"), None);
+    }
+
+    #[test]
+    fn test_statistic() {
+        let statistics = "rocksdb.number.block.compressed COUNT : 12\n\
+            rocksdb.bytes.compressed P50 : 4096.000000 P95 : 4096.000000 P99 : 4096.000000 \
+            P100 : 4096.000000 COUNT : 12 SUM : 49152\n";
+        assert_eq!(
+            statistic(statistics, "rocksdb.number.block.compressed", "COUNT"),
+            Some(12)
+        );
+        assert_eq!(
+            statistic(statistics, "rocksdb.bytes.compressed", "SUM"),
+            Some(49152)
+        );
+        assert_eq!(statistic(statistics, "rocksdb.bytes", "SUM"), None);
+    }
+}
@@ -4,24 +4,36 @@
 //! IndexStore supports creation of various ancillary indexes of state in SuiDataStore.
 //! The main user of this data is the explorer.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
 use rocksdb::Options;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tap::TapFallible;
 use typed_store::rocks::DBMap;
 use typed_store::traits::Map;
 use typed_store::traits::TypedStoreDebug;
 use typed_store_derive::DBMapUtils;
 
+use crate::db_metrics::{DBMetrics, DBOp};
+
+use sui_types::balance::{coin_balance, BalanceChange};
 use sui_types::base_types::ObjectRef;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ExecutionDigests, ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::error::SuiResult;
-use sui_types::object::Owner;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::{Object, Owner};
+use sui_types::query::AddressActivityRole;
 
 use crate::default_db_options;
 
 #[derive(DBMapUtils)]
-pub struct IndexStore {
+pub struct IndexStoreTables {
     /// Index from sui address to transactions initiated by that address.
     #[default_options_override_fn = "transactions_from_addr_table_default_config"]
     transactions_from_addr: DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
@@ -46,13 +58,54 @@ pub struct IndexStore {
     /// This is a map between the transaction digest and its timestamp (UTC timestamp in
     /// **milliseconds** since epoch 1/1/1970). A transaction digest is subjectively time stamped
     /// on a node according to the local machine time, so it varies across nodes.
-    /// The timestamping happens when the node sees a txn certificate for the first time.
+    /// The timestamping happens when the node first indexes the transaction, shortly after
+    /// executing it.
     #[default_options_override_fn = "timestamps_table_default_config"]
     timestamps: DBMap<TransactionDigest, u64>,
 
     /// Index from transaction digest to sequence number.
     #[default_options_override_fn = "transactions_seq_table_default_config"]
     transactions_seq: DBMap<TransactionDigest, TxSequenceNumber>,
+
+    /// Index from sui address to objects it received from transactions sent by other addresses,
+    /// along with the transaction that transferred them.
+    #[default_options_override_fn = "received_objects_table_default_config"]
+    received_objects:
+        DBMap<(SuiAddress, TxSequenceNumber, ObjectID), (ObjectRef, TransactionDigest)>,
+
+    /// Index from transaction digest to the balance changes made by that transaction.
+    #[default_options_override_fn = "balance_changes_table_default_config"]
+    balance_changes: DBMap<TransactionDigest, Vec<BalanceChange>>,
+
+    /// Index from sui address to the transactions it took part in, with the role it had in each.
+    #[default_options_override_fn = "address_activity_table_default_config"]
+    address_activity: DBMap<(SuiAddress, TxSequenceNumber, AddressActivityRole), ExecutionDigests>,
+
+    /// Index from sui address and coin type to the coins of that type owned by the address, with
+    /// their balance.
+    #[default_options_override_fn = "coins_table_default_config"]
+    coins: DBMap<(SuiAddress, TypeTag, ObjectID), (ObjectRef, u64)>,
+
+    /// Index from coin type to the id of the `TreasuryCap` keeping the total supply of the type.
+    treasury_caps: DBMap<TypeTag, ObjectID>,
+
+    /// How far the indexes are written, under `INDEX_WATERMARK_KEY`.
+    watermark: DBMap<u64, IndexWatermark>,
+
+    /// The sequence number of the first transaction the coin index was maintained with, once it
+    /// was built from the live coins, under `INDEX_WATERMARK_KEY`. Coins are not indexed until
+    /// the coin index is built.
+    coin_index_built_at: DBMap<u64, TxSequenceNumber>,
+
+    /// Index from transaction digest to sequence number of the transactions which could not be
+    /// indexed, so that they do not hold back the checkpoint of the watermark.
+    skipped_transactions: DBMap<TransactionDigest, TxSequenceNumber>,
+}
+
+/// The indexes, along with the metrics of the node they are recorded in.
+pub struct IndexStore {
+    tables: IndexStoreTables,
+    db_metrics: Arc<DBMetrics>,
 }
 
 // These functions are used to initialize the DB tables
@@ -77,107 +130,538 @@ fn transactions_by_move_function_table_default_config() -> Options {
 fn timestamps_table_default_config() -> Options {
     default_db_options(None, Some(1_000_000)).1
 }
+fn received_objects_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn balance_changes_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).1
+}
+fn address_activity_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn coins_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).0
+}
 
-impl IndexStore {
-    pub fn index_tx(
-        &self,
+const INDEX_WATERMARK_KEY: u64 = 0;
+
+/// Name under which the database of the indexes is registered for the storage metrics.
+const INDEXES_DB_NAME: &str = "indexes";
+
+/// The most coins written at once when building the coin index.
+const COIN_INDEX_BUILD_BATCH_SIZE: usize = 10_000;
+
+/// A coin, as recorded by the coin index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedCoin {
+    pub owner: Owner,
+    pub coin_type: TypeTag,
+    pub object_ref: ObjectRef,
+    pub balance: u64,
+}
+
+impl IndexedCoin {
+    /// Returns the coin `object` is, None if it is not a coin.
+    pub fn from_object(object: &Object) -> SuiResult<Option<Self>> {
+        Ok(coin_balance(object)?.map(|(coin_type, balance)| Self {
+            owner: object.owner,
+            coin_type,
+            object_ref: object.compute_object_reference(),
+            balance,
+        }))
+    }
+}
+
+/// How far the indexes are written: all the transactions executed before `next_sequence` are
+/// indexed, and so are all the transactions of the checkpoints up to `checkpoint`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexWatermark {
+    pub next_sequence: TxSequenceNumber,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// Index entries of consecutive transactions, accumulated in memory and written at once by
+/// `IndexStore::write_batch`. Entries are kept sorted by key, so that each table gets a sorted
+/// run of keys instead of writes scattered across its key space by every transaction.
+#[derive(Debug, Default)]
+pub struct IndexBatch {
+    transactions_seq: BTreeMap<TransactionDigest, TxSequenceNumber>,
+    transactions_from_addr: BTreeMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+    transactions_to_addr: BTreeMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
+    transactions_by_input_object_id: BTreeMap<(ObjectID, TxSequenceNumber), TransactionDigest>,
+    transactions_by_mutated_object_id: BTreeMap<(ObjectID, TxSequenceNumber), TransactionDigest>,
+    transactions_by_move_function:
+        BTreeMap<(ObjectID, String, String, TxSequenceNumber), TransactionDigest>,
+    timestamps: BTreeMap<TransactionDigest, u64>,
+    received_objects:
+        BTreeMap<(SuiAddress, TxSequenceNumber, ObjectID), (ObjectRef, TransactionDigest)>,
+    balance_changes: BTreeMap<TransactionDigest, Vec<BalanceChange>>,
+    address_activity:
+        BTreeMap<(SuiAddress, TxSequenceNumber, AddressActivityRole), ExecutionDigests>,
+    /// The latest entry of each coin changed by the batch, None if it was removed.
+    coins: BTreeMap<(SuiAddress, TypeTag, ObjectID), Option<(ObjectRef, u64)>>,
+    treasury_caps: BTreeMap<TypeTag, ObjectID>,
+    skipped_transactions: BTreeMap<TransactionDigest, TxSequenceNumber>,
+    watermark: IndexWatermark,
+}
+
+impl IndexBatch {
+    /// Starts a batch of the transactions following `watermark`.
+    pub fn new(watermark: IndexWatermark) -> Self {
+        Self {
+            watermark,
+            ..Default::default()
+        }
+    }
+
+    pub fn watermark(&self) -> IndexWatermark {
+        self.watermark
+    }
+
+    /// Records that all the transactions of the checkpoints up to `checkpoint` are indexed once
+    /// the batch is written.
+    pub fn set_checkpoint(&mut self, checkpoint: Option<CheckpointSequenceNumber>) {
+        self.watermark.checkpoint = checkpoint;
+    }
+
+    /// The number of transactions in the batch.
+    pub fn len(&self) -> usize {
+        self.transactions_seq.len() + self.skipped_transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the transaction is in the batch, indexed or skipped.
+    pub fn contains(&self, digest: &TransactionDigest) -> bool {
+        self.transactions_seq.contains_key(digest) || self.skipped_transactions.contains_key(digest)
+    }
+
+    fn advance_watermark(&mut self, sequence: TxSequenceNumber) {
+        self.watermark.next_sequence = self.watermark.next_sequence.max(sequence + 1);
+    }
+
+    /// Records a transaction which could not be indexed, so that it does not hold back the
+    /// watermark. It is not added to the indexes, not even to the sequence numbers.
+    pub fn skip_tx(&mut self, sequence: TxSequenceNumber, digest: &TransactionDigest) {
+        self.skipped_transactions.insert(*digest, sequence);
+        self.advance_watermark(sequence);
+    }
+
+    pub fn add_tx(
+        &mut self,
         sender: SuiAddress,
         active_inputs: impl Iterator<Item = ObjectID>,
         mutated_objects: impl Iterator<Item = (ObjectRef, Owner)> + Clone,
         move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        balance_changes: Vec<BalanceChange>,
+        gas_owner: Owner,
         sequence: TxSequenceNumber,
-        digest: &TransactionDigest,
+        digests: &ExecutionDigests,
         timestamp_ms: u64,
-    ) -> SuiResult {
-        let batch = self.transactions_from_addr.batch();
-
-        let batch =
-            batch.insert_batch(&self.transactions_seq, std::iter::once((*digest, sequence)))?;
+    ) {
+        let digest = &digests.transaction;
+        self.transactions_seq.insert(*digest, sequence);
+        self.advance_watermark(sequence);
 
-        let batch = batch.insert_batch(
-            &self.transactions_from_addr,
-            std::iter::once(((sender, sequence), *digest)),
-        )?;
+        self.transactions_from_addr
+            .insert((sender, sequence), *digest);
 
-        let batch = batch.insert_batch(
-            &self.transactions_by_input_object_id,
-            active_inputs.map(|id| ((id, sequence), *digest)),
-        )?;
+        self.transactions_by_input_object_id
+            .extend(active_inputs.map(|id| ((id, sequence), *digest)));
 
-        let batch = batch.insert_batch(
-            &self.transactions_by_mutated_object_id,
+        self.transactions_by_mutated_object_id.extend(
             mutated_objects
                 .clone()
                 .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
-        )?;
+        );
 
-        let batch = batch.insert_batch(
-            &self.transactions_by_move_function,
-            move_functions.map(|(obj_id, module, function)| {
+        self.transactions_by_move_function
+            .extend(move_functions.map(|(obj_id, module, function)| {
                 (
                     (obj_id, module.to_string(), function.to_string(), sequence),
                     *digest,
                 )
-            }),
-        )?;
+            }));
 
-        let batch = batch.insert_batch(
-            &self.transactions_to_addr,
-            mutated_objects.filter_map(|(_, owner)| {
+        self.transactions_to_addr
+            .extend(mutated_objects.clone().filter_map(|(_, owner)| {
                 owner
                     .get_owner_address()
                     .ok()
-                    .map(|addr| ((addr, sequence), digest))
-            }),
-        )?;
+                    .map(|addr| ((addr, sequence), *digest))
+            }));
+
+        self.received_objects
+            .extend(
+                mutated_objects
+                    .clone()
+                    .filter_map(|(obj_ref, owner)| match owner {
+                        Owner::AddressOwner(addr) if addr != sender => {
+                            Some(((addr, sequence, obj_ref.0), (obj_ref, *digest)))
+                        }
+                        _ => None,
+                    }),
+            );
+
+        let recipients: BTreeSet<_> = mutated_objects
+            .filter_map(|(_, owner)| match owner {
+                Owner::AddressOwner(addr) if addr != sender => Some(addr),
+                _ => None,
+            })
+            .collect();
+        let gas_payer = match gas_owner {
+            Owner::AddressOwner(addr) if addr != sender => Some(addr),
+            _ => None,
+        };
+        self.address_activity.extend(
+            std::iter::once((sender, AddressActivityRole::Sender))
+                .chain(
+                    recipients
+                        .into_iter()
+                        .map(|addr| (addr, AddressActivityRole::Recipient)),
+                )
+                .chain(gas_payer.map(|addr| (addr, AddressActivityRole::GasPayer)))
+                .map(|(addr, role)| ((addr, sequence, role), *digests)),
+        );
+
+        self.balance_changes.insert(*digest, balance_changes);
+        self.timestamps.insert(*digest, timestamp_ms);
+    }
+
+    /// Records the coins changed by a transaction, each as its version before and after the
+    /// transaction, None if it did not exist or was not a coin then. Must follow the changes of
+    /// the previous transactions of the batch.
+    pub fn add_coin_changes(
+        &mut self,
+        changes: impl IntoIterator<Item = (Option<IndexedCoin>, Option<IndexedCoin>)>,
+    ) {
+        for (before, after) in changes {
+            if let Some(coin) = before {
+                if let Owner::AddressOwner(owner) = coin.owner {
+                    self.coins
+                        .insert((owner, coin.coin_type, coin.object_ref.0), None);
+                }
+            }
+            if let Some(coin) = after {
+                if let Owner::AddressOwner(owner) = coin.owner {
+                    self.coins.insert(
+                        (owner, coin.coin_type, coin.object_ref.0),
+                        Some((coin.object_ref, coin.balance)),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Records the `TreasuryCap`s written by a transaction, with the coin type of each.
+    pub fn add_treasury_caps(
+        &mut self,
+        treasury_caps: impl IntoIterator<Item = (TypeTag, ObjectID)>,
+    ) {
+        self.treasury_caps.extend(treasury_caps);
+    }
+}
+
+impl IndexStore {
+    /// Opens the indexes at `path`, registering their database in `db_metrics`.
+    pub fn open(path: PathBuf, db_options: Option<Options>, db_metrics: Arc<DBMetrics>) -> Self {
+        let tables = IndexStoreTables::open_tables_read_write(path, db_options, None);
+        db_metrics.register_db(INDEXES_DB_NAME, &tables.transactions_from_addr.rocksdb);
+        Self { tables, db_metrics }
+    }
+
+    /// Returns how far the indexes are written, from which indexing resumes after a restart.
+    pub fn watermark(&self) -> SuiResult<IndexWatermark> {
+        Ok(self
+            .tables
+            .watermark
+            .get(&INDEX_WATERMARK_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Records the watermark of indexes written before watermarks were, after the highest
+    /// sequence number indexed, so that upgraded nodes do not index their history again. Does
+    /// nothing if a watermark is recorded.
+    pub fn initialize_watermark(&self) -> SuiResult {
+        if self.tables.watermark.contains_key(&INDEX_WATERMARK_KEY)? {
+            return Ok(());
+        }
+        let next_sequence = self
+            .tables
+            .transactions_seq
+            .values()
+            .max()
+            .map_or(0, |seq| seq + 1);
+        let watermark = IndexWatermark {
+            next_sequence,
+            checkpoint: None,
+        };
+        self.tables
+            .watermark
+            .insert(&INDEX_WATERMARK_KEY, &watermark)?;
+        Ok(())
+    }
+
+    /// Returns true if the transaction was indexed, or could not be and was skipped.
+    pub fn is_indexed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self.tables.transactions_seq.contains_key(digest)?
+            || self.tables.skipped_transactions.contains_key(digest)?)
+    }
 
-        let batch =
-            batch.insert_batch(&self.timestamps, std::iter::once((*digest, timestamp_ms)))?;
+    /// Writes the entries of `batch` and its watermark in a single rocksdb batch, so that the
+    /// watermark never runs ahead of the entries after a crash. Each table is written as one run
+    /// of sorted keys.
+    pub fn write_batch(&self, batch: IndexBatch) -> SuiResult {
+        // Coin changes only apply on top of a built coin index.
+        let coins = if self.is_coin_index_built()? {
+            batch.coins
+        } else {
+            BTreeMap::new()
+        };
+        let (removed_coins, coins): (Vec<_>, Vec<_>) =
+            coins.into_iter().partition(|(_, coin)| coin.is_none());
+        // Transactions keep the timestamp they were first indexed with.
+        let mut timestamps = Vec::with_capacity(batch.timestamps.len());
+        for (digest, timestamp_ms) in batch.timestamps {
+            if !self.tables.timestamps.contains_key(&digest)? {
+                timestamps.push((digest, timestamp_ms));
+            }
+        }
+        let counts = [
+            ("transactions_seq", batch.transactions_seq.len()),
+            ("transactions_from_addr", batch.transactions_from_addr.len()),
+            ("transactions_to_addr", batch.transactions_to_addr.len()),
+            (
+                "transactions_by_input_object_id",
+                batch.transactions_by_input_object_id.len(),
+            ),
+            (
+                "transactions_by_mutated_object_id",
+                batch.transactions_by_mutated_object_id.len(),
+            ),
+            (
+                "transactions_by_move_function",
+                batch.transactions_by_move_function.len(),
+            ),
+            ("timestamps", timestamps.len()),
+            ("received_objects", batch.received_objects.len()),
+            ("balance_changes", batch.balance_changes.len()),
+            ("address_activity", batch.address_activity.len()),
+            ("coins", coins.len() + removed_coins.len()),
+            ("treasury_caps", batch.treasury_caps.len()),
+            ("skipped_transactions", batch.skipped_transactions.len()),
+            ("watermark", 1),
+        ];
+        let start = Instant::now();
 
-        batch.write()?;
+        let tables = &self.tables;
+        let db_batch = tables
+            .transactions_seq
+            .batch()
+            .insert_batch(&tables.transactions_seq, batch.transactions_seq)?
+            .insert_batch(&tables.transactions_from_addr, batch.transactions_from_addr)?
+            .insert_batch(&tables.transactions_to_addr, batch.transactions_to_addr)?
+            .insert_batch(
+                &tables.transactions_by_input_object_id,
+                batch.transactions_by_input_object_id,
+            )?
+            .insert_batch(
+                &tables.transactions_by_mutated_object_id,
+                batch.transactions_by_mutated_object_id,
+            )?
+            .insert_batch(
+                &tables.transactions_by_move_function,
+                batch.transactions_by_move_function,
+            )?
+            .insert_batch(&tables.timestamps, timestamps)?
+            .insert_batch(&tables.received_objects, batch.received_objects)?
+            .insert_batch(&tables.balance_changes, batch.balance_changes)?
+            .insert_batch(&tables.address_activity, batch.address_activity)?
+            .delete_batch(&tables.coins, removed_coins.into_iter().map(|(key, _)| key))?
+            .insert_batch(
+                &tables.coins,
+                coins
+                    .into_iter()
+                    .filter_map(|(key, coin)| coin.map(|coin| (key, coin))),
+            )?
+            .insert_batch(&tables.treasury_caps, batch.treasury_caps)?
+            .insert_batch(&tables.skipped_transactions, batch.skipped_transactions)?
+            .insert_batch(
+                &tables.watermark,
+                std::iter::once((INDEX_WATERMARK_KEY, batch.watermark)),
+            )?;
+        db_batch
+            .write()
+            .tap_err(|_| self.db_metrics.record_write_error(INDEXES_DB_NAME))?;
 
+        let ops: Vec<_> = counts
+            .iter()
+            .map(|(cf, count)| (*cf, DBOp::Write, *count))
+            .collect();
+        self.db_metrics
+            .record_batch(INDEXES_DB_NAME, &ops, start.elapsed());
         Ok(())
     }
 
+    /// Returns true once the coin index was built, after which it is maintained along with the
+    /// other indexes.
+    pub fn is_coin_index_built(&self) -> SuiResult<bool> {
+        Ok(self
+            .tables
+            .coin_index_built_at
+            .contains_key(&INDEX_WATERMARK_KEY)?)
+    }
+
+    /// Replaces the content of the coin index with `coins`, the live coins once all the
+    /// transactions up to the watermark are executed, and maintains it from then on. Must not
+    /// run concurrently with indexing. The coins may include the changes of later transactions:
+    /// indexing them then sets each coin they changed to its version after the transaction, so
+    /// the index converges. Returns the number of coins indexed.
+    ///
+    /// The `TreasuryCap`s written by transactions are indexed whether the coin index is built or
+    /// not: `treasury_caps` are those written before.
+    pub fn build_coin_index(
+        &self,
+        coins: impl Iterator<Item = SuiResult<IndexedCoin>>,
+        treasury_caps: impl Iterator<Item = SuiResult<(TypeTag, ObjectID)>>,
+    ) -> SuiResult<usize> {
+        let tables = &self.tables;
+        tables.coin_index_built_at.clear()?;
+        tables.coins.clear()?;
+
+        let mut indexed = 0;
+        let mut batch = Vec::with_capacity(COIN_INDEX_BUILD_BATCH_SIZE);
+        for coin in coins {
+            let coin = coin?;
+            if let Owner::AddressOwner(owner) = coin.owner {
+                batch.push((
+                    (owner, coin.coin_type, coin.object_ref.0),
+                    (coin.object_ref, coin.balance),
+                ));
+            }
+            if batch.len() == COIN_INDEX_BUILD_BATCH_SIZE {
+                indexed += batch.len();
+                tables
+                    .coins
+                    .batch()
+                    .insert_batch(&tables.coins, batch.drain(..))?
+                    .write()?;
+            }
+        }
+        indexed += batch.len();
+        tables
+            .coins
+            .batch()
+            .insert_batch(&tables.coins, batch)?
+            .write()?;
+
+        let treasury_caps = treasury_caps.collect::<SuiResult<Vec<_>>>()?;
+        let built_at = self.watermark()?.next_sequence;
+        tables
+            .treasury_caps
+            .batch()
+            .insert_batch(&tables.treasury_caps, treasury_caps)?
+            .insert_batch(
+                &tables.coin_index_built_at,
+                std::iter::once((INDEX_WATERMARK_KEY, built_at)),
+            )?
+            .write()?;
+        Ok(indexed)
+    }
+
+    /// Runs `f`, a read of `cf`, recording its latency.
+    fn metered_read<T>(&self, cf: &str, f: impl FnOnce() -> T) -> T {
+        self.db_metrics.metered(INDEXES_DB_NAME, cf, DBOp::Read, f)
+    }
+
+    /// Returns the coins of `coin_type` owned by `owner` starting at `cursor` (inclusive), in
+    /// the order of their ids, with their balance.
+    pub fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: &TypeTag,
+        cursor: ObjectID,
+        limit: Option<usize>,
+    ) -> SuiResult<Vec<(ObjectRef, u64)>> {
+        self.metered_read("coins", || {
+            let iter = self
+                .tables
+                .coins
+                .iter()
+                .skip_to(&(owner, coin_type.clone(), cursor))?
+                .take_while(|((address, type_, _), _)| *address == owner && type_ == coin_type)
+                .map(|(_, coin)| coin);
+            Ok(if let Some(limit) = limit {
+                iter.take(limit).collect()
+            } else {
+                iter.collect()
+            })
+        })
+    }
+
+    /// Returns the id of the `TreasuryCap` of `coin_type`, if one was indexed.
+    pub fn get_treasury_cap(&self, coin_type: &TypeTag) -> SuiResult<Option<ObjectID>> {
+        self.metered_read("treasury_caps", || {
+            Ok(self.tables.treasury_caps.get(coin_type)?)
+        })
+    }
+
     /// Returns unix timestamp for a transaction if it exists
     pub fn get_timestamp_ms(
         &self,
         transaction_digest: &TransactionDigest,
     ) -> SuiResult<Option<u64>> {
-        let ts = self.timestamps.get(transaction_digest)?;
-        Ok(ts)
+        self.metered_read("timestamps", || {
+            Ok(self.tables.timestamps.get(transaction_digest)?)
+        })
+    }
+
+    /// Returns the balance changes made by a transaction if it was indexed
+    pub fn get_balance_changes(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult<Option<Vec<BalanceChange>>> {
+        self.metered_read("balance_changes", || {
+            Ok(self.tables.balance_changes.get(transaction_digest)?)
+        })
     }
 
     fn get_transactions_from_index<KeyT: Clone + Serialize + DeserializeOwned + PartialEq>(
+        &self,
+        cf: &str,
         index: &DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
         key: KeyT,
         cursor: TxSequenceNumber,
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        Ok(if reverse {
-            let iter = index
-                .iter()
-                .skip_prior_to(&(key.clone(), cursor))?
-                .reverse()
-                .take_while(|((id, _), _)| *id == key)
-                .map(|(_, digest)| digest);
-            if let Some(limit) = limit {
-                iter.take(limit).collect()
-            } else {
-                iter.collect()
-            }
-        } else {
-            let iter = index
-                .iter()
-                .skip_to(&(key.clone(), cursor))?
-                .take_while(|((id, _), _)| *id == key)
-                .map(|(_, digest)| digest);
-            if let Some(limit) = limit {
-                iter.take(limit).collect()
+        self.metered_read(cf, || {
+            Ok(if reverse {
+                let iter = index
+                    .iter()
+                    .skip_prior_to(&(key.clone(), cursor))?
+                    .reverse()
+                    .take_while(|((id, _), _)| *id == key)
+                    .map(|(_, digest)| digest);
+                if let Some(limit) = limit {
+                    iter.take(limit).collect()
+                } else {
+                    iter.collect()
+                }
             } else {
-                iter.collect()
-            }
+                let iter = index
+                    .iter()
+                    .skip_to(&(key.clone(), cursor))?
+                    .take_while(|((id, _), _)| *id == key)
+                    .map(|(_, digest)| digest);
+                if let Some(limit) = limit {
+                    iter.take(limit).collect()
+                } else {
+                    iter.collect()
+                }
+            })
         })
     }
 
@@ -188,8 +672,9 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        Self::get_transactions_from_index(
-            &self.transactions_by_input_object_id,
+        self.get_transactions_from_index(
+            "transactions_by_input_object_id",
+            &self.tables.transactions_by_input_object_id,
             input_object,
             cursor,
             limit,
@@ -204,8 +689,9 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        Self::get_transactions_from_index(
-            &self.transactions_by_mutated_object_id,
+        self.get_transactions_from_index(
+            "transactions_by_mutated_object_id",
+            &self.tables.transactions_by_mutated_object_id,
             mutated_object,
             cursor,
             limit,
@@ -220,8 +706,9 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        Self::get_transactions_from_index(
-            &self.transactions_from_addr,
+        self.get_transactions_from_index(
+            "transactions_from_addr",
+            &self.tables.transactions_from_addr,
             addr,
             cursor,
             limit,
@@ -244,36 +731,38 @@ impl IndexStore {
             function.clone().unwrap_or_default(),
             cursor,
         );
-        let iter = self.transactions_by_move_function.iter();
-        Ok(if reverse {
-            let iter = iter
-                .skip_prior_to(&key)?
-                .reverse()
-                .take_while(|((id, m, f, _), _)| {
-                    *id == package
-                        && module.as_ref().map(|x| x == m).unwrap_or(true)
-                        && function.as_ref().map(|x| x == f).unwrap_or(true)
-                })
-                .map(|(_, digest)| digest);
-            if let Some(limit) = limit {
-                iter.take(limit).collect()
-            } else {
-                iter.collect()
-            }
-        } else {
-            let iter = iter
-                .skip_to(&key)?
-                .take_while(|((id, m, f, _), _)| {
-                    *id == package
-                        && module.as_ref().map(|x| x == m).unwrap_or(true)
-                        && function.as_ref().map(|x| x == f).unwrap_or(true)
-                })
-                .map(|(_, digest)| digest);
-            if let Some(limit) = limit {
-                iter.take(limit).collect()
+        self.metered_read("transactions_by_move_function", || {
+            let iter = self.tables.transactions_by_move_function.iter();
+            Ok(if reverse {
+                let iter = iter
+                    .skip_prior_to(&key)?
+                    .reverse()
+                    .take_while(|((id, m, f, _), _)| {
+                        *id == package
+                            && module.as_ref().map(|x| x == m).unwrap_or(true)
+                            && function.as_ref().map(|x| x == f).unwrap_or(true)
+                    })
+                    .map(|(_, digest)| digest);
+                if let Some(limit) = limit {
+                    iter.take(limit).collect()
+                } else {
+                    iter.collect()
+                }
             } else {
-                iter.collect()
-            }
+                let iter = iter
+                    .skip_to(&key)?
+                    .take_while(|((id, m, f, _), _)| {
+                        *id == package
+                            && module.as_ref().map(|x| x == m).unwrap_or(true)
+                            && function.as_ref().map(|x| x == f).unwrap_or(true)
+                    })
+                    .map(|(_, digest)| digest);
+                if let Some(limit) = limit {
+                    iter.take(limit).collect()
+                } else {
+                    iter.collect()
+                }
+            })
         })
     }
 
@@ -284,13 +773,332 @@ impl IndexStore {
         limit: Option<usize>,
         reverse: bool,
     ) -> SuiResult<Vec<TransactionDigest>> {
-        Self::get_transactions_from_index(&self.transactions_to_addr, addr, cursor, limit, reverse)
+        self.get_transactions_from_index(
+            "transactions_to_addr",
+            &self.tables.transactions_to_addr,
+            addr,
+            cursor,
+            limit,
+            reverse,
+        )
+    }
+
+    /// Returns the objects received by `addr` starting at `cursor` (inclusive), in the order they
+    /// were received.
+    pub fn get_received_objects(
+        &self,
+        addr: SuiAddress,
+        cursor: (TxSequenceNumber, ObjectID),
+        limit: Option<usize>,
+    ) -> SuiResult<Vec<(TxSequenceNumber, ObjectRef, TransactionDigest)>> {
+        self.metered_read("received_objects", || {
+            let iter = self
+                .tables
+                .received_objects
+                .iter()
+                .skip_to(&(addr, cursor.0, cursor.1))?
+                .take_while(|((owner, _, _), _)| *owner == addr)
+                .map(|((_, seq, _), (obj_ref, digest))| (seq, obj_ref, digest));
+            Ok(if let Some(limit) = limit {
+                iter.take(limit).collect()
+            } else {
+                iter.collect()
+            })
+        })
+    }
+
+    /// Returns the transactions `addr` took part in starting at `cursor` (inclusive), in the
+    /// order they were executed.
+    pub fn get_address_activity(
+        &self,
+        addr: SuiAddress,
+        cursor: (TxSequenceNumber, AddressActivityRole),
+        limit: Option<usize>,
+    ) -> SuiResult<Vec<(TxSequenceNumber, AddressActivityRole, ExecutionDigests)>> {
+        self.metered_read("address_activity", || {
+            let iter = self
+                .tables
+                .address_activity
+                .iter()
+                .skip_to(&(addr, cursor.0, cursor.1))?
+                .take_while(|((address, _, _), _)| *address == addr)
+                .map(|((_, seq, role), digests)| (seq, role, digests));
+            Ok(if let Some(limit) = limit {
+                iter.take(limit).collect()
+            } else {
+                iter.collect()
+            })
+        })
     }
 
     pub fn get_transaction_seq(
         &self,
         digest: &TransactionDigest,
     ) -> SuiResult<Option<TxSequenceNumber>> {
-        Ok(self.transactions_seq.get(digest)?)
+        self.metered_read("transactions_seq", || {
+            Ok(self.tables.transactions_seq.get(digest)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber};
+
+    #[test]
+    fn test_write_batch_advances_watermark() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IndexStore::open(
+            dir.path().to_path_buf(),
+            None,
+            DBMetrics::new_unregistered(),
+        );
+        assert_eq!(store.watermark().unwrap(), IndexWatermark::default());
+
+        let sender = SuiAddress::random_for_testing_only();
+        let mut batch = IndexBatch::new(store.watermark().unwrap());
+        let digests: Vec<_> = (0..3).map(|_| ExecutionDigests::random()).collect();
+        for (seq, digests) in digests.iter().enumerate().take(2) {
+            batch.add_tx(
+                sender,
+                std::iter::empty(),
+                std::iter::empty(),
+                std::iter::empty(),
+                vec![],
+                Owner::AddressOwner(sender),
+                seq as TxSequenceNumber,
+                digests,
+                0,
+            );
+        }
+        batch.skip_tx(2, &digests[2].transaction);
+        batch.set_checkpoint(Some(0));
+        assert_eq!(batch.len(), 3);
+        store.write_batch(batch).unwrap();
+
+        assert_eq!(
+            store.watermark().unwrap(),
+            IndexWatermark {
+                next_sequence: 3,
+                checkpoint: Some(0),
+            }
+        );
+        assert!(store.is_indexed(&digests[2].transaction).unwrap());
+        assert_eq!(
+            store.get_transaction_seq(&digests[2].transaction).unwrap(),
+            None
+        );
+        assert_eq!(
+            store
+                .get_transactions_from_addr(sender, 0, None, false)
+                .unwrap(),
+            vec![digests[0].transaction, digests[1].transaction]
+        );
+    }
+
+    #[test]
+    fn test_initialize_watermark_and_keep_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IndexStore::open(
+            dir.path().to_path_buf(),
+            None,
+            DBMetrics::new_unregistered(),
+        );
+        let sender = SuiAddress::random_for_testing_only();
+        let digests = ExecutionDigests::random();
+        let index = |seq, timestamp_ms| {
+            let mut batch = IndexBatch::new(store.watermark().unwrap());
+            batch.add_tx(
+                sender,
+                std::iter::empty(),
+                std::iter::empty(),
+                std::iter::empty(),
+                vec![],
+                Owner::AddressOwner(sender),
+                seq,
+                &digests,
+                timestamp_ms,
+            );
+            batch
+        };
+
+        // Indexes written before watermarks were resume after the highest sequence number.
+        store
+            .transactions_seq
+            .insert(&digests.transaction, &7)
+            .unwrap();
+        store.initialize_watermark().unwrap();
+        assert_eq!(store.watermark().unwrap().next_sequence, 8);
+        store.write_batch(index(8, 1)).unwrap();
+        store.initialize_watermark().unwrap();
+        assert_eq!(store.watermark().unwrap().next_sequence, 9);
+
+        // Indexing a transaction again keeps its first timestamp.
+        store.write_batch(index(8, 2)).unwrap();
+        assert_eq!(
+            store.get_timestamp_ms(&digests.transaction).unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_address_activity() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IndexStore::open(
+            dir.path().to_path_buf(),
+            None,
+            DBMetrics::new_unregistered(),
+        );
+        let alice = SuiAddress::random_for_testing_only();
+        let bob = SuiAddress::random_for_testing_only();
+        let sponsor = SuiAddress::random_for_testing_only();
+        let object = |owner| {
+            let version = SequenceNumber::from_u64(1);
+            (
+                (ObjectID::random(), version, ObjectDigest::random()),
+                Owner::AddressOwner(owner),
+            )
+        };
+        let digests: Vec<_> = (0..2).map(|_| ExecutionDigests::random()).collect();
+        let mut batch = IndexBatch::new(store.watermark().unwrap());
+        // Alice sends an object to Bob and pays for the gas herself.
+        batch.add_tx(
+            alice,
+            std::iter::empty(),
+            vec![object(alice), object(bob)].into_iter(),
+            std::iter::empty(),
+            vec![],
+            Owner::AddressOwner(alice),
+            0,
+            &digests[0],
+            0,
+        );
+        // Bob sends an object to Alice, with gas paid by a sponsor.
+        batch.add_tx(
+            bob,
+            std::iter::empty(),
+            vec![object(alice)].into_iter(),
+            std::iter::empty(),
+            vec![],
+            Owner::AddressOwner(sponsor),
+            1,
+            &digests[1],
+            0,
+        );
+        store.write_batch(batch).unwrap();
+
+        // Paying for its own gas does not make the sender a gas payer.
+        assert_eq!(
+            store
+                .get_address_activity(alice, (0, AddressActivityRole::Sender), None)
+                .unwrap(),
+            vec![
+                (0, AddressActivityRole::Sender, digests[0]),
+                (1, AddressActivityRole::Recipient, digests[1]),
+            ]
+        );
+        assert_eq!(
+            store
+                .get_address_activity(bob, (0, AddressActivityRole::Sender), None)
+                .unwrap(),
+            vec![
+                (0, AddressActivityRole::Recipient, digests[0]),
+                (1, AddressActivityRole::Sender, digests[1]),
+            ]
+        );
+        assert_eq!(
+            store
+                .get_address_activity(sponsor, (0, AddressActivityRole::Sender), None)
+                .unwrap(),
+            vec![(1, AddressActivityRole::GasPayer, digests[1])]
+        );
+        // The cursor is inclusive, and the limit applies after it.
+        assert_eq!(
+            store
+                .get_address_activity(alice, (1, AddressActivityRole::Sender), Some(1))
+                .unwrap(),
+            vec![(1, AddressActivityRole::Recipient, digests[1])]
+        );
+    }
+
+    fn coin(owner: Owner, version: u64, balance: u64, id: ObjectID) -> IndexedCoin {
+        IndexedCoin {
+            owner,
+            coin_type: TypeTag::U8,
+            object_ref: (id, version.into(), ObjectDigest::random()),
+            balance,
+        }
+    }
+
+    #[test]
+    fn test_coin_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IndexStore::open(
+            dir.path().to_path_buf(),
+            None,
+            DBMetrics::new_unregistered(),
+        );
+        let alice = SuiAddress::random_for_testing_only();
+        let bob = SuiAddress::random_for_testing_only();
+        let mut ids: Vec<_> = (0..4).map(|_| ObjectID::random()).collect();
+        ids.sort();
+
+        // Coins are only indexed once the index is built, treasury caps always.
+        let cap = ObjectID::random();
+        let mut batch = IndexBatch::new(store.watermark().unwrap());
+        batch.skip_tx(0, &ExecutionDigests::random().transaction);
+        batch.add_coin_changes(vec![(
+            None,
+            Some(coin(Owner::AddressOwner(alice), 1, 5, ids[3])),
+        )]);
+        batch.add_treasury_caps(vec![(TypeTag::U8, cap)]);
+        store.write_batch(batch).unwrap();
+        assert!(!store.is_coin_index_built().unwrap());
+        assert_eq!(store.get_treasury_cap(&TypeTag::U8).unwrap(), Some(cap));
+
+        let alice_coin = coin(Owner::AddressOwner(alice), 1, 10, ids[0]);
+        let shared_coin = coin(Owner::Shared, 1, 100, ids[1]);
+        let spent_coin = coin(Owner::AddressOwner(alice), 1, 20, ids[2]);
+        let indexed = store
+            .build_coin_index(
+                vec![alice_coin.clone(), shared_coin, spent_coin.clone()]
+                    .into_iter()
+                    .map(Ok),
+                std::iter::once(Ok((TypeTag::U64, cap))),
+            )
+            .unwrap();
+        assert_eq!(indexed, 2);
+        assert!(store.is_coin_index_built().unwrap());
+        assert_eq!(store.get_treasury_cap(&TypeTag::U8).unwrap(), Some(cap));
+        assert_eq!(store.get_treasury_cap(&TypeTag::U64).unwrap(), Some(cap));
+        assert_eq!(store.get_treasury_cap(&TypeTag::Bool).unwrap(), None);
+
+        // Alice pays 15 of her second coin to Bob, and 5 of it burn as gas.
+        let bob_coin = coin(Owner::AddressOwner(bob), 2, 15, ids[3]);
+        let mut batch = IndexBatch::new(store.watermark().unwrap());
+        batch.skip_tx(1, &ExecutionDigests::random().transaction);
+        batch.add_coin_changes(vec![
+            (Some(spent_coin), None),
+            (None, Some(bob_coin.clone())),
+        ]);
+        store.write_batch(batch).unwrap();
+
+        assert_eq!(
+            store
+                .get_coins(alice, &TypeTag::U8, ObjectID::ZERO, None)
+                .unwrap(),
+            vec![(alice_coin.object_ref, 10)]
+        );
+        assert_eq!(
+            store
+                .get_coins(bob, &TypeTag::U8, ObjectID::ZERO, None)
+                .unwrap(),
+            vec![(bob_coin.object_ref, 15)]
+        );
+        assert!(store
+            .get_coins(alice, &TypeTag::U64, ObjectID::ZERO, None)
+            .unwrap()
+            .is_empty());
     }
 }
@@ -5,11 +5,14 @@ pub mod lock_service;
 pub use lock_service::LockService;
 
 pub mod indexes;
-pub use indexes::IndexStore;
+pub use indexes::{IndexBatch, IndexStore, IndexStoreTables, IndexWatermark, IndexedCoin};
 
+pub mod compression;
+pub mod db_metrics;
 pub mod event_store;
 pub mod mutex_table;
 pub mod node_sync_store;
+pub mod submission_store;
 pub mod write_ahead_log;
 
 use rocksdb::Options;
@@ -62,6 +62,12 @@ enum LockServiceCommands {
         objects: Vec<ObjectRef>,
         resp: oneshot::Sender<SuiResult>,
     },
+    ResetLocks {
+        epoch: EpochId,
+        to_reset: Vec<ObjectRef>,
+        to_delete: Vec<ObjectRef>,
+        resp: oneshot::Sender<SuiResult<(u64, u64)>>,
+    },
 }
 
 type SuiLockResult = Result<Option<Option<LockInfo>>, SuiError>;
@@ -85,6 +91,20 @@ pub struct LockInfo {
     pub tx_digest: TransactionDigest,
 }
 
+/// Outcome of resetting the lock table at the start of an epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LockTableResetStats {
+    /// Number of locks left in the table.
+    pub live_locks: u64,
+    /// Number of locks set by transactions of previous epochs that were reset to None.
+    pub reset_locks: u64,
+    /// Number of locks of object versions superseded by a newer version that were deleted.
+    pub deleted_locks: u64,
+}
+
+/// Number of lock table entries rewritten per write batch when resetting the table.
+const RESET_BATCH_SIZE: usize = 10_000;
+
 /// Inner LockService implementation that does single threaded database accesses.  Cannot be
 /// used publicly, must be wrapped in a LockService to control access.
 #[derive(Clone, DBMapUtils)]
@@ -356,6 +376,97 @@ impl LockServiceImpl {
         Ok(())
     }
 
+    /// Reset the locks set by transactions of epochs before `epoch` to None, and delete the
+    /// locks of object versions for which a lock of a newer version exists. The latter are left
+    /// behind when a node crashes between sequencing a transaction and deleting its input locks.
+    ///
+    /// Locks from previous epochs can already be overridden by any transaction, so resetting them
+    /// does not change which transactions can be signed. It keeps the table from accumulating
+    /// entries that are read and rewritten on the signing path.
+    #[cfg(test)]
+    fn reset_for_epoch(&self, epoch: EpochId) -> SuiResult<LockTableResetStats> {
+        self.scan_for_epoch_reset(epoch, |to_reset, to_delete| {
+            self.reset_locks(epoch, &to_reset, &to_delete)
+        })
+    }
+
+    /// Scans the lock table for the locks to reset and delete at the start of `epoch`, passing
+    /// them to `reset_locks` in batches. The scan only reads the table, so it runs outside of the
+    /// command loop, and `reset_locks` checks the locks again before resetting them.
+    fn scan_for_epoch_reset(
+        &self,
+        epoch: EpochId,
+        mut reset_locks: impl FnMut(Vec<ObjectRef>, Vec<ObjectRef>) -> SuiResult<(u64, u64)>,
+    ) -> SuiResult<LockTableResetStats> {
+        let mut stats = LockTableResetStats::default();
+        let mut to_reset = Vec::new();
+        let mut to_delete = Vec::new();
+        // Keys are serialized big endian, so all versions of an object are adjacent and ordered
+        // by version: an entry is superseded by the next one if it is for the same object.
+        let mut current: Option<(ObjectRef, Option<LockInfo>)> = None;
+        let mut entries = self.transaction_lock.iter();
+        loop {
+            let next = entries.next();
+            let done = next.is_none();
+            match (current, &next) {
+                (Some((obj_ref, _)), Some((next_ref, _))) if obj_ref.0 == next_ref.0 => {
+                    to_delete.push(obj_ref);
+                }
+                (Some((obj_ref, lock)), _) => {
+                    stats.live_locks += 1;
+                    if matches!(lock, Some(LockInfo { epoch: lock_epoch, .. }) if lock_epoch < epoch)
+                    {
+                        to_reset.push(obj_ref);
+                    }
+                }
+                (None, _) => (),
+            }
+            current = next;
+            if done || to_reset.len() + to_delete.len() >= RESET_BATCH_SIZE {
+                let (reset, deleted) = reset_locks(
+                    std::mem::take(&mut to_reset),
+                    std::mem::take(&mut to_delete),
+                )?;
+                stats.reset_locks += reset;
+                stats.deleted_locks += deleted;
+            }
+            if done {
+                break;
+            }
+        }
+        info!(?epoch, ?stats, "Reset lock table for new epoch");
+        Ok(stats)
+    }
+
+    /// Resets the locks of `to_reset` which are still set by transactions of epochs before
+    /// `epoch`, and deletes the locks of `to_delete`. Returns the numbers of reset and deleted
+    /// locks.
+    fn reset_locks(
+        &self,
+        epoch: EpochId,
+        to_reset: &[ObjectRef],
+        to_delete: &[ObjectRef],
+    ) -> SuiResult<(u64, u64)> {
+        // A transaction of `epoch` may have acquired the lock since the scan read it.
+        let locks = self.transaction_lock.multi_get(to_reset)?;
+        let to_reset: Vec<_> = to_reset
+            .iter()
+            .zip(locks)
+            .filter(|(_, lock)| match lock {
+                Some(Some(lock)) => lock.epoch < epoch,
+                _ => false,
+            })
+            .map(|(obj_ref, _)| (*obj_ref, None))
+            .collect();
+        let reset = to_reset.len() as u64;
+        self.transaction_lock
+            .batch()
+            .insert_batch(&self.transaction_lock, to_reset)?
+            .delete_batch(&self.transaction_lock, to_delete)?
+            .write()?;
+        Ok((reset, to_delete.len() as u64))
+    }
+
     /// Loop to continuously process mutating commands in a single thread from async senders.
     /// It terminates when the sender drops, which usually is when the containing data store is dropped.
     fn run_command_loop(&self, mut receiver: Receiver<LockServiceCommands>) {
@@ -401,6 +512,16 @@ impl LockServiceImpl {
                         warn!("Could not respond to sender!");
                     }
                 }
+                LockServiceCommands::ResetLocks {
+                    epoch,
+                    to_reset,
+                    to_delete,
+                    resp,
+                } => {
+                    if let Err(_e) = resp.send(self.reset_locks(epoch, &to_reset, &to_delete)) {
+                        warn!("Could not respond to sender!");
+                    }
+                }
             }
         }
         info!("LockService command loop stopped, the sender on other end hung up/dropped");
@@ -438,6 +559,7 @@ pub struct LockService {
 }
 
 struct LockServiceInner {
+    tables: LockServiceImpl,
     sender: Option<Sender<LockServiceCommands>>,
     query_sender: Option<Sender<LockServiceQueries>>,
     run_command_loop: Option<JoinHandle<()>>,
@@ -491,6 +613,7 @@ impl LockService {
 
         // Now, create a sync channel and spawn a thread
         let (sender, receiver) = channel(LOCKSERVICE_QUEUE_LEN);
+        let tables = inner_service.clone();
         let inner2 = inner_service.clone();
         let run_command_loop = std::thread::spawn(move || {
             inner2.run_command_loop(receiver);
@@ -503,6 +626,7 @@ impl LockService {
 
         Ok(Self {
             inner: Arc::new(LockServiceInner {
+                tables,
                 sender: Some(sender),
                 query_sender: Some(q_sender),
                 run_command_loop: Some(run_command_loop),
@@ -642,6 +766,35 @@ impl LockService {
         .await
     }
 
+    /// Reset the locks set in epochs before `epoch` and delete the locks of superseded object
+    /// versions. Meant to be called when `epoch` starts.
+    ///
+    /// The table is scanned on a blocking thread, and only the batches of locks to reset go
+    /// through the command loop, so that lock requests are not held up by the scan.
+    pub async fn reset_for_epoch(&self, epoch: EpochId) -> SuiResult<LockTableResetStats> {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            inner
+                .tables
+                .scan_for_epoch_reset(epoch, |to_reset, to_delete| {
+                    let (os_sender, os_receiver) = oneshot::channel::<SuiResult<(u64, u64)>>();
+                    inner
+                        .sender()
+                        .blocking_send(LockServiceCommands::ResetLocks {
+                            epoch,
+                            to_reset,
+                            to_delete,
+                            resp: os_sender,
+                        })
+                        .expect("Could not send message to inner LockService");
+                    futures::executor::block_on(os_receiver)
+                        .expect("Response from lockservice was cancelled, should not happen!")
+                })
+        })
+        .await
+        .expect("Lock table reset should not panic")
+    }
+
     /// Checks multiple object locks exist.
     /// Returns Err(TransactionLockDoesNotExist) if at least one object lock is not initialized.
     pub async fn locks_exist(&self, objects: Vec<ObjectRef>) -> SuiResult {
@@ -889,4 +1042,44 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_lockdb_reset_for_epoch() {
+        let ls = init_lockservice_db();
+
+        let id = ObjectID::random();
+        let old_ref: ObjectRef = (id, 1.into(), ObjectDigest::random());
+        // Only the newest version of an object keeps its lock.
+        let new_ref: ObjectRef = (id, 256.into(), ObjectDigest::random());
+        let stale_ref: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let current_ref: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        let free_ref: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+
+        ls.initialize_locks(&[old_ref, new_ref, stale_ref, current_ref, free_ref], false)
+            .unwrap();
+        ls.acquire_locks(0, &[stale_ref], TransactionDigest::random())
+            .unwrap();
+        let tx = TransactionDigest::random();
+        ls.acquire_locks(1, &[current_ref], tx).unwrap();
+
+        assert_eq!(
+            ls.reset_for_epoch(1).unwrap(),
+            LockTableResetStats {
+                live_locks: 4,
+                reset_locks: 1,
+                deleted_locks: 1,
+            }
+        );
+        assert_eq!(ls.get_lock(old_ref), Ok(None));
+        assert_eq!(ls.get_lock(new_ref), Ok(Some(None)));
+        assert_eq!(ls.get_lock(stale_ref), Ok(Some(None)));
+        assert_eq!(
+            ls.get_lock(current_ref),
+            Ok(Some(Some(LockInfo {
+                epoch: 1,
+                tx_digest: tx
+            })))
+        );
+        assert_eq!(ls.get_lock(free_ref), Ok(Some(None)));
+    }
 }
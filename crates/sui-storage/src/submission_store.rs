@@ -0,0 +1,282 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::{
+    base_types::TransactionDigest,
+    error::{SuiError, SuiResult},
+    messages::ExecuteTransactionResponse,
+};
+
+use typed_store::rocks::DBMap;
+use typed_store::traits::Map;
+use typed_store::traits::TypedStoreDebug;
+use typed_store_derive::DBMapUtils;
+
+use crate::mutex_table::{LockGuard, MutexTable};
+
+const NUM_SHARDS: usize = 128;
+const SHARD_SIZE: usize = 128;
+
+/// Max length of a client-supplied request id, in bytes.
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// How long submissions are kept, and so how long clients may retry them.
+pub const DEFAULT_SUBMISSION_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Max number of expired submissions pruned by each new submission.
+const PRUNE_BATCH_SIZE: usize = 1000;
+
+/// What is known about a submission made under a client-supplied request id.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SubmissionRecord {
+    /// The transaction the request id is bound to.
+    pub tx_digest: TransactionDigest,
+    /// When the request id was bound, in milliseconds since the unix epoch.
+    pub created_at_ms: u64,
+    /// The response returned to the first caller, once the submission completed.
+    /// `None` while the submission is in flight, or if the node restarted before it completed.
+    pub response: Option<ExecuteTransactionResponse>,
+}
+
+#[derive(DBMapUtils)]
+pub struct SubmissionStoreTables {
+    /// Maps client-supplied request ids to the transaction submitted under them.
+    submissions: DBMap<String, SubmissionRecord>,
+    /// The request ids of `submissions` ordered by the time they were bound, to prune the
+    /// expired ones.
+    submissions_by_time: DBMap<(u64, String), ()>,
+}
+
+/// SubmissionStore persists the mapping from client-supplied request ids to the transaction
+/// submitted under them and its result, so that retried submissions are answered from the store
+/// instead of being executed again, even across node restarts.
+/// Submissions are forgotten once they are older than the retention of the store, so a request
+/// id retried after that is submitted again.
+pub struct SubmissionStore {
+    tables: SubmissionStoreTables,
+    retention: Duration,
+    /// Serializes submissions that share a request id.
+    mutex_table: MutexTable<String>,
+}
+
+impl SubmissionStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Self {
+        Self::open_with_retention(path, DEFAULT_SUBMISSION_RETENTION)
+    }
+
+    pub fn open_with_retention<P: AsRef<Path>>(path: P, retention: Duration) -> Self {
+        Self {
+            tables: SubmissionStoreTables::open_tables_read_write(
+                path.as_ref().to_path_buf(),
+                None,
+                None,
+            ),
+            retention,
+            mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
+        }
+    }
+
+    /// Acquire the lock for `request_id`. The lock must be held across `begin` and `complete`.
+    pub async fn acquire_lock(&self, request_id: &str) -> LockGuard {
+        self.mutex_table.acquire_lock(request_id.to_string()).await
+    }
+
+    /// Bind `request_id` to `tx_digest` if it is not bound yet, and return the record stored
+    /// for it. Returns an error if the request id is too long, or if it is already bound to a
+    /// different transaction.
+    /// Each new submission also prunes a bounded number of expired ones.
+    pub fn begin(
+        &self,
+        request_id: &str,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<SubmissionRecord> {
+        if request_id.len() > MAX_REQUEST_ID_LEN {
+            return Err(SuiError::RequestIdTooLong {
+                len: request_id.len(),
+                max_len: MAX_REQUEST_ID_LEN,
+            });
+        }
+        match self.tables.submissions.get(&request_id.to_string())? {
+            Some(record) if record.tx_digest != *tx_digest => Err(SuiError::ConflictingRequestId {
+                request_id: request_id.to_string(),
+                tx_digest: record.tx_digest,
+            }),
+            Some(record) => Ok(record),
+            None => {
+                let now_ms = now_ms();
+                self.prune(now_ms)?;
+                let record = SubmissionRecord {
+                    tx_digest: *tx_digest,
+                    created_at_ms: now_ms,
+                    response: None,
+                };
+                self.insert(request_id, &record)?;
+                Ok(record)
+            }
+        }
+    }
+
+    /// Record the response returned for the submission made under `request_id`.
+    pub fn complete(
+        &self,
+        request_id: &str,
+        tx_digest: &TransactionDigest,
+        response: &ExecuteTransactionResponse,
+    ) -> SuiResult {
+        // The record may have been pruned if the submission took longer than the retention.
+        let created_at_ms = self
+            .get(request_id)?
+            .map_or_else(now_ms, |record| record.created_at_ms);
+        self.insert(
+            request_id,
+            &SubmissionRecord {
+                tx_digest: *tx_digest,
+                created_at_ms,
+                response: Some(response.clone()),
+            },
+        )
+    }
+
+    pub fn get(&self, request_id: &str) -> SuiResult<Option<SubmissionRecord>> {
+        Ok(self.tables.submissions.get(&request_id.to_string())?)
+    }
+
+    /// Forget up to `PRUNE_BATCH_SIZE` of the submissions bound before `now_ms` minus the
+    /// retention, oldest first. Returns the number of submissions pruned.
+    pub fn prune(&self, now_ms: u64) -> SuiResult<usize> {
+        let cutoff_ms = now_ms.saturating_sub(self.retention.as_millis() as u64);
+        let expired: Vec<_> = self
+            .tables
+            .submissions_by_time
+            .iter()
+            .take_while(|((created_at_ms, _), _)| *created_at_ms < cutoff_ms)
+            .take(PRUNE_BATCH_SIZE)
+            .map(|(key, _)| key)
+            .collect();
+        let batch = self
+            .tables
+            .submissions
+            .batch()
+            .delete_batch(
+                &self.tables.submissions,
+                expired.iter().map(|(_, request_id)| request_id),
+            )?
+            .delete_batch(&self.tables.submissions_by_time, expired.iter())?;
+        batch.write()?;
+        Ok(expired.len())
+    }
+
+    fn insert(&self, request_id: &str, record: &SubmissionRecord) -> SuiResult {
+        let batch = self
+            .tables
+            .submissions
+            .batch()
+            .insert_batch(
+                &self.tables.submissions,
+                std::iter::once((request_id.to_string(), record)),
+            )?
+            .insert_batch(
+                &self.tables.submissions_by_time,
+                std::iter::once(((record.created_at_ms, request_id.to_string()), ())),
+            )?;
+        batch.write()?;
+        Ok(())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_request_id_binding_survives_reopen() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let db_path = working_dir.path().join("submissions");
+        let request_id = "payment-42";
+        let tx1 = TransactionDigest::random();
+        let tx2 = TransactionDigest::random();
+
+        {
+            let store = SubmissionStore::open(&db_path);
+            let record = store.begin(request_id, &tx1).unwrap();
+            assert_eq!(record.tx_digest, tx1);
+            assert!(record.response.is_none());
+            store
+                .complete(
+                    request_id,
+                    &tx1,
+                    &ExecuteTransactionResponse::ImmediateReturn,
+                )
+                .unwrap();
+        }
+
+        let store = SubmissionStore::open(&db_path);
+        let record = store.begin(request_id, &tx1).unwrap();
+        assert!(matches!(
+            record.response,
+            Some(ExecuteTransactionResponse::ImmediateReturn)
+        ));
+
+        // The request id cannot be reused for another transaction.
+        assert!(matches!(
+            store.begin(request_id, &tx2),
+            Err(SuiError::ConflictingRequestId { .. })
+        ));
+        assert!(store.get("unknown").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_request_id_length_is_bounded() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let store = SubmissionStore::open(working_dir.path().join("submissions"));
+        let tx = TransactionDigest::random();
+
+        store.begin(&"a".repeat(MAX_REQUEST_ID_LEN), &tx).unwrap();
+        assert!(matches!(
+            store.begin(&"a".repeat(MAX_REQUEST_ID_LEN + 1), &tx),
+            Err(SuiError::RequestIdTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_expired_submissions_are_pruned() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let store = SubmissionStore::open(working_dir.path().join("submissions"));
+        let tx1 = TransactionDigest::random();
+        let tx2 = TransactionDigest::random();
+
+        let record = store.begin("payment-1", &tx1).unwrap();
+        store
+            .complete(
+                "payment-1",
+                &tx1,
+                &ExecuteTransactionResponse::ImmediateReturn,
+            )
+            .unwrap();
+        store.begin("payment-2", &tx2).unwrap();
+
+        // Nothing is pruned within the retention.
+        let retention_ms = DEFAULT_SUBMISSION_RETENTION.as_millis() as u64;
+        assert_eq!(store.prune(record.created_at_ms + retention_ms).unwrap(), 0);
+        assert!(store.get("payment-1").unwrap().is_some());
+
+        assert_eq!(store.prune(u64::MAX).unwrap(), 2);
+        assert!(store.get("payment-1").unwrap().is_none());
+        assert!(store.get("payment-2").unwrap().is_none());
+        assert_eq!(store.tables.submissions_by_time.iter().count(), 0);
+
+        // Once pruned, the request id may be bound again.
+        let record = store.begin("payment-1", &tx2).unwrap();
+        assert_eq!(record.tx_digest, tx2);
+    }
+}
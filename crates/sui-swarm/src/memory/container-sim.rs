@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use futures::FutureExt;
-use prometheus::Registry;
 use std::net::{IpAddr, SocketAddr};
 use sui_config::NodeConfig;
 use sui_node::SuiNode;
@@ -65,7 +64,9 @@ impl Container {
             .build();
 
         let task_handle = node.spawn(async move {
-            let _server = SuiNode::start(&config, Registry::new()).await.unwrap();
+            // Served in memory, for simulation tests to check the metrics of the node.
+            let registry = sui_node::metrics::start_prometheus_server(config.metrics_address);
+            let _server = SuiNode::start(&config, registry).await.unwrap();
             // Notify that we've successfully started the node
             error!("node started, sending oneshot");
             let _ = startup_sender.send(());
@@ -47,6 +47,10 @@ impl Node {
         self.config.json_rpc_address
     }
 
+    pub fn metrics_address(&self) -> std::net::SocketAddr {
+        self.config.metrics_address
+    }
+
     /// Start this Node, returning a handle that will resolve when the node has completed starting
     /// up.
     pub fn spawn(&mut self) -> Result<tokio::sync::oneshot::Receiver<()>> {
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod node_stats;
+
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::process::Command;
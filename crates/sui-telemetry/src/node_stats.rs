@@ -0,0 +1,135 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in reporting of anonymized node health statistics to an endpoint chosen by the operator,
+//! to follow the health of the fleet during upgrades. Reports carry no addresses or keys: nodes
+//! are identified by a random id drawn at every start. Reports that cannot be sent are kept in a
+//! bounded buffer and sent with the next report.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+use tracing::{debug, trace};
+
+/// Version of the [NodeStatsReport] schema, incremented on every change of its fields.
+pub const NODE_STATS_SCHEMA_VERSION: u32 = 1;
+
+/// How long to wait for the endpoint to accept a report before keeping it for the next one.
+const REPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeType {
+    Validator,
+    FullNode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeStatsReport {
+    pub schema_version: u32,
+    /// Random id of this run of the node.
+    pub instance_id: String,
+    pub node_type: NodeType,
+    pub version: String,
+    pub uptime_secs: u64,
+    /// Sequence number of the latest checkpoint stored by the node.
+    pub checkpoint: Option<u64>,
+    /// Seconds since the latest stored checkpoint last advanced.
+    pub checkpoint_stall_secs: Option<u64>,
+    pub peer_count: usize,
+    pub timestamp_secs: u64,
+}
+
+pub struct NodeStatsReporter {
+    endpoint: String,
+    instance_id: String,
+    buffer_size: usize,
+    buffer: VecDeque<NodeStatsReport>,
+    client: reqwest::Client,
+}
+
+impl NodeStatsReporter {
+    pub fn new(endpoint: String, buffer_size: usize) -> Self {
+        Self {
+            endpoint,
+            instance_id: format!("{:032x}", rand::thread_rng().gen::<u128>()),
+            buffer_size: buffer_size.max(1),
+            buffer: VecDeque::new(),
+            client: reqwest::Client::builder()
+                .timeout(REPORT_TIMEOUT)
+                .build()
+                .expect("Failed to build the HTTP client of the node stats reporter"),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Send `report`, along with the reports that could not be sent before, as a JSON array.
+    /// The oldest reports are dropped when more than `buffer_size` are pending.
+    pub async fn report(&mut self, report: NodeStatsReport) {
+        self.buffer.push_back(report);
+        while self.buffer.len() > self.buffer_size {
+            self.buffer.pop_front();
+        }
+
+        let result = self
+            .client
+            .post(&self.endpoint)
+            .json(&self.buffer)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match result {
+            Ok(_) => {
+                trace!(reports = self.buffer.len(), "Sent node stats");
+                self.buffer.clear();
+            }
+            Err(error) => {
+                debug!(
+                    ?error,
+                    pending = self.buffer.len(),
+                    "Failed to send node stats"
+                );
+            }
+        }
+    }
+
+    pub fn pending_reports(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(reporter: &NodeStatsReporter, uptime_secs: u64) -> NodeStatsReport {
+        NodeStatsReport {
+            schema_version: NODE_STATS_SCHEMA_VERSION,
+            instance_id: reporter.instance_id().to_string(),
+            node_type: NodeType::FullNode,
+            version: "0.1.0".into(),
+            uptime_secs,
+            checkpoint: Some(10),
+            checkpoint_stall_secs: Some(3),
+            peer_count: 4,
+            timestamp_secs: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsent_reports_are_buffered() {
+        // Nothing listens on port 1.
+        let mut reporter = NodeStatsReporter::new("http://127.0.0.1:1".into(), 2);
+        for uptime in 0..3 {
+            let report = report(&reporter, uptime);
+            reporter.report(report).await;
+        }
+        assert_eq!(reporter.pending_reports(), 2);
+        assert_eq!(reporter.buffer.front().unwrap().uptime_secs, 1);
+    }
+}
@@ -0,0 +1,418 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of a range of checkpoints into files which can be analyzed without a node. Each file
+//! holds a checkpoint summary, its contents, the certificates and effects of its transactions,
+//! and the committee of its epoch, so that it can be verified on its own. Consecutive files are
+//! chained by the digest of the previous summary in each summary, and the committees they are
+//! signed by are chained to the genesis committee through the last checkpoint of each epoch.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use sui_config::genesis::Genesis;
+use sui_core::analytics_export::{CheckpointData, CheckpointSource};
+use sui_core::authority::AuthorityStore;
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_types::committee::Committee;
+use sui_types::messages::{CertifiedTransaction, TransactionEffects};
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+};
+use typed_store::Map;
+
+/// Version of the format of the files, bumped on incompatible changes.
+pub const CHECKPOINT_EXPORT_VERSION: u32 = 1;
+
+const CHECKPOINT_FILE_EXTENSION: &str = "chk";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointExport {
+    pub version: u32,
+    /// The committee of the epoch of the checkpoint, which signed it.
+    pub committee: Committee,
+    pub checkpoint: AuthenticatedCheckpoint,
+    pub contents: CheckpointContents,
+    /// The certificates and effects of the transactions of the checkpoint, in the order of
+    /// its contents.
+    pub transactions: Vec<CertifiedTransaction>,
+    pub effects: Vec<TransactionEffects>,
+}
+
+impl CheckpointExport {
+    pub fn sequence_number(&self) -> CheckpointSequenceNumber {
+        self.checkpoint.sequence_number()
+    }
+
+    /// Checks that the checkpoint is certified by a quorum of `committee`, and that its
+    /// contents, certificates and effects match the digests they are committed to by the
+    /// checkpoint. The committee carried by the file is not trusted.
+    pub fn verify(&self) -> Result<()> {
+        ensure!(
+            self.version == CHECKPOINT_EXPORT_VERSION,
+            "Unsupported checkpoint export version {}",
+            self.version
+        );
+        ensure!(
+            matches!(self.checkpoint, AuthenticatedCheckpoint::Certified(_)),
+            "Checkpoint {} is not certified by a quorum",
+            self.sequence_number()
+        );
+        self.checkpoint
+            .verify(&self.committee, Some(&self.contents))?;
+        ensure!(
+            self.transactions.len() == self.contents.size()
+                && self.effects.len() == self.contents.size(),
+            "Expected {} transactions and effects, found {} and {}",
+            self.contents.size(),
+            self.transactions.len(),
+            self.effects.len()
+        );
+        for ((digests, transaction), effects) in self
+            .contents
+            .iter()
+            .zip(&self.transactions)
+            .zip(&self.effects)
+        {
+            ensure!(
+                *transaction.digest() == digests.transaction,
+                "Transaction {:?} does not match the contents",
+                transaction.digest()
+            );
+            ensure!(
+                effects.digest() == digests.effects
+                    && effects.transaction_digest == digests.transaction,
+                "Effects of transaction {:?} do not match the contents",
+                digests.transaction
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Parses a range of checkpoints given as `<start>..<end>`, the end being excluded.
+pub fn parse_checkpoint_range(range: &str) -> Result<Range<CheckpointSequenceNumber>> {
+    let (start, end) = range
+        .split_once("..")
+        .ok_or_else(|| anyhow!("Expected a range <start>..<end>, got {range}"))?;
+    let range = start.parse()?..end.parse()?;
+    ensure!(!range.is_empty(), "The range {range:?} is empty");
+    Ok(range)
+}
+
+fn checkpoint_file(dir: &Path, seq: CheckpointSequenceNumber) -> PathBuf {
+    dir.join(format!("{seq:020}.{CHECKPOINT_FILE_EXTENSION}"))
+}
+
+pub fn write_checkpoint_file(dir: &Path, export: &CheckpointExport) -> Result<PathBuf> {
+    let path = checkpoint_file(dir, export.sequence_number());
+    fs::write(&path, bcs::to_bytes(export)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Exports the checkpoints of `range` from the database of a stopped node, at `db_path`, into
+/// one file per checkpoint in `out`. Returns the number of exported checkpoints.
+pub fn export_checkpoints(
+    db_path: &Path,
+    genesis: &Genesis,
+    range: Range<CheckpointSequenceNumber>,
+    out: &Path,
+) -> Result<usize> {
+    let store = AuthorityStore::open(&db_path.join("store"), None)?;
+    let committee_store = CommitteeStore::new(db_path.join("epochs"), &genesis.committee()?, None);
+    let checkpoints =
+        CheckpointStoreTables::open_tables_read_write(db_path.join("checkpoints"), None, None);
+    fs::create_dir_all(out)?;
+
+    let mut exported = 0;
+    for seq in range {
+        let checkpoint = checkpoints
+            .checkpoints
+            .get(&seq)?
+            .ok_or_else(|| anyhow!("Checkpoint {seq} is not stored"))?;
+        let contents = checkpoints
+            .checkpoint_contents
+            .get(&seq)?
+            .ok_or_else(|| anyhow!("Contents of checkpoint {seq} are not stored"))?;
+        if !matches!(checkpoint, AuthenticatedCheckpoint::Certified(_)) {
+            bail!("Checkpoint {seq} is not certified yet");
+        }
+        let epoch = checkpoint.epoch();
+        let committee = committee_store
+            .get_committee(&epoch)?
+            .ok_or_else(|| anyhow!("Committee of epoch {epoch} is not stored"))?;
+        let mut transactions = Vec::with_capacity(contents.size());
+        let mut effects = Vec::with_capacity(contents.size());
+        for digests in contents.iter() {
+            transactions.push(
+                store
+                    .get_certified_transaction(&digests.transaction)?
+                    .ok_or_else(|| {
+                        anyhow!("Transaction {:?} is not stored", digests.transaction)
+                    })?,
+            );
+            effects.push(store.get_effects(&digests.transaction)?);
+        }
+        write_checkpoint_file(
+            out,
+            &CheckpointExport {
+                version: CHECKPOINT_EXPORT_VERSION,
+                committee,
+                checkpoint,
+                contents,
+                transactions,
+                effects,
+            },
+        )?;
+        exported += 1;
+    }
+    Ok(exported)
+}
+
+/// Verifies checkpoint exports one after the other. The first one must be signed by the genesis
+/// committee, and the following ones must be chained to it and signed by the committee the last
+/// checkpoint of the previous epoch announced.
+pub struct CheckpointVerifier {
+    committee: Committee,
+    previous: Option<CheckpointSummary>,
+}
+
+impl CheckpointVerifier {
+    pub fn new(genesis_committee: Committee) -> Self {
+        Self {
+            committee: genesis_committee,
+            previous: None,
+        }
+    }
+
+    /// The sequence number of the checkpoint to verify next, if any was verified yet.
+    pub fn next_sequence_number(&self) -> Option<CheckpointSequenceNumber> {
+        self.previous
+            .as_ref()
+            .map(|previous| previous.sequence_number + 1)
+    }
+
+    pub fn verify(&mut self, export: &CheckpointExport) -> Result<()> {
+        let seq = export.sequence_number();
+        ensure!(
+            export.committee == self.committee,
+            "Checkpoint {seq} of epoch {} is not signed by the committee of epoch {} descending \
+             from genesis",
+            export.checkpoint.epoch(),
+            self.committee.epoch
+        );
+        export.verify()?;
+        let summary = export.checkpoint.summary();
+        if let Some(previous) = &self.previous {
+            ensure!(
+                seq == previous.sequence_number + 1,
+                "Checkpoint {seq} does not follow checkpoint {}",
+                previous.sequence_number
+            );
+            ensure!(
+                summary.previous_digest == Some(previous.digest()),
+                "Checkpoint {seq} is not chained to the previous checkpoint"
+            );
+        }
+        if let Some(next_epoch_committee) = &summary.next_epoch_committee {
+            self.committee = Committee::new(
+                summary.epoch + 1,
+                next_epoch_committee.iter().cloned().collect(),
+            )?;
+        }
+        self.previous = Some(summary.clone());
+        Ok(())
+    }
+}
+
+fn checkpoint_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some(CHECKPOINT_FILE_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    // File names are zero padded sequence numbers.
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_checkpoint_file(path: &Path) -> Result<CheckpointExport> {
+    bcs::from_bytes(&fs::read(path)?)
+        .with_context(|| format!("Failed to decode {}", path.display()))
+}
+
+/// Reads and verifies the checkpoint files in `dir`, which must be consecutive and chained to
+/// `genesis_committee`. The files are read one at a time, in order, as the iterator advances.
+pub fn import_checkpoints(
+    dir: &Path,
+    genesis_committee: Committee,
+) -> Result<impl Iterator<Item = Result<CheckpointExport>>> {
+    let mut verifier = CheckpointVerifier::new(genesis_committee);
+    Ok(checkpoint_paths(dir)?.into_iter().map(move |path| {
+        let export = read_checkpoint_file(&path)?;
+        verifier
+            .verify(&export)
+            .with_context(|| format!("Failed to verify {}", path.display()))?;
+        Ok(export)
+    }))
+}
+
+/// The checkpoint files in a directory, as a source of checkpoints for the analytics export.
+/// Checkpoints are read and verified when requested, in order and once each.
+pub struct CheckpointArchive {
+    dir: PathBuf,
+    first_checkpoint: CheckpointSequenceNumber,
+    verifier: Mutex<CheckpointVerifier>,
+}
+
+impl CheckpointArchive {
+    pub fn new(dir: &Path, genesis_committee: Committee) -> Result<Self> {
+        let first = checkpoint_paths(dir)?
+            .first()
+            .map(|path| read_checkpoint_file(path))
+            .transpose()?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            first_checkpoint: first.map_or(0, |export| export.sequence_number()),
+            verifier: Mutex::new(CheckpointVerifier::new(genesis_committee)),
+        })
+    }
+
+    pub fn first_checkpoint(&self) -> CheckpointSequenceNumber {
+        self.first_checkpoint
+    }
+}
+
+impl CheckpointSource for CheckpointArchive {
+    fn get_checkpoint_data(&self, seq: CheckpointSequenceNumber) -> Result<Option<CheckpointData>> {
+        let mut verifier = self.verifier.lock().unwrap();
+        let next = verifier
+            .next_sequence_number()
+            .unwrap_or(self.first_checkpoint);
+        ensure!(seq >= next, "Checkpoint {seq} was already read");
+        // The checkpoints before `seq` are verified too, when the export resumes after them.
+        for current in next..=seq {
+            let path = checkpoint_file(&self.dir, current);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let export = read_checkpoint_file(&path)?;
+            ensure!(
+                export.sequence_number() == current,
+                "{} holds checkpoint {}",
+                path.display(),
+                export.sequence_number()
+            );
+            verifier
+                .verify(&export)
+                .with_context(|| format!("Failed to verify {}", path.display()))?;
+            if current == seq {
+                return Ok(Some(CheckpointData {
+                    summary: export.checkpoint.summary().clone(),
+                    transactions: export.transactions,
+                    effects: export.effects,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, SignedCheckpointSummary};
+
+    #[test]
+    fn test_parse_checkpoint_range() {
+        assert_eq!(parse_checkpoint_range("3..10").unwrap(), 3..10);
+        assert!(parse_checkpoint_range("10..3").is_err());
+        assert!(parse_checkpoint_range("10").is_err());
+    }
+
+    #[test]
+    fn test_import_checks_chain() {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        let name = key.public().into();
+        let committee = Committee::new(0, BTreeMap::from([(name, 1)])).unwrap();
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let signed = |seq, previous_digest| {
+            SignedCheckpointSummary::new(
+                0,
+                seq,
+                name,
+                &key,
+                &contents,
+                previous_digest,
+                GasCostSummary::default(),
+                None,
+                [0; 32],
+            )
+        };
+        let export = |checkpoint| CheckpointExport {
+            version: CHECKPOINT_EXPORT_VERSION,
+            committee: committee.clone(),
+            checkpoint,
+            contents: contents.clone(),
+            transactions: vec![],
+            effects: vec![],
+        };
+        let certified = |seq, previous_digest| {
+            export(AuthenticatedCheckpoint::Certified(
+                CertifiedCheckpointSummary::aggregate(
+                    vec![signed(seq, previous_digest)],
+                    &committee,
+                )
+                .unwrap(),
+            ))
+        };
+        let import = |dir: &Path, genesis_committee: &Committee| {
+            import_checkpoints(dir, genesis_committee.clone())
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let first = certified(0, None);
+        write_checkpoint_file(dir.path(), &first).unwrap();
+        let second = certified(1, Some(first.checkpoint.summary().digest()));
+        write_checkpoint_file(dir.path(), &second).unwrap();
+        assert_eq!(import(dir.path(), &committee).unwrap().len(), 2);
+
+        // Checkpoints signed by a committee not descending from genesis are rejected.
+        let (_, other_key): (_, AuthorityKeyPair) = get_key_pair();
+        let other_committee =
+            Committee::new(0, BTreeMap::from([(other_key.public().into(), 1)])).unwrap();
+        assert!(import(dir.path(), &other_committee).is_err());
+
+        // Checkpoints can be read from a later one, the earlier ones being verified too.
+        let archive = CheckpointArchive::new(dir.path(), committee.clone()).unwrap();
+        assert_eq!(archive.first_checkpoint(), 0);
+        assert!(archive.get_checkpoint_data(1).unwrap().is_some());
+        assert!(archive.get_checkpoint_data(0).is_err());
+        assert!(archive.get_checkpoint_data(2).unwrap().is_none());
+
+        // A checkpoint signed by a single validator only is rejected.
+        let third = export(AuthenticatedCheckpoint::Signed(signed(
+            2,
+            Some(second.checkpoint.summary().digest()),
+        )));
+        write_checkpoint_file(dir.path(), &third).unwrap();
+        assert!(import(dir.path(), &committee).is_err());
+
+        // A checkpoint which does not follow the previous one is rejected.
+        write_checkpoint_file(dir.path(), &certified(2, None)).unwrap();
+        assert!(import(dir.path(), &committee).is_err());
+    }
+}
@@ -3,12 +3,18 @@
 
 use anyhow::Result;
 use futures::future::join_all;
+use prometheus::Registry;
 use std::cmp::min;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::genesis::Genesis;
+use sui_config::node::{AnalyticsExportConfig, AnalyticsFileFormat};
+use sui_core::analytics_export::AnalyticsExporter;
 use sui_network::default_mysten_network_config;
+use sui_tool::checkpoint_export::{
+    export_checkpoints, import_checkpoints, parse_checkpoint_range, CheckpointArchive,
+};
 use sui_tool::db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand};
 
 use sui_core::authority_client::{
@@ -20,7 +26,9 @@ use anyhow::anyhow;
 use futures::stream::StreamExt;
 
 use clap::*;
-use sui_core::authority::MAX_ITEMS_LIMIT;
+use sui_core::authority::{AuthorityStore, MAX_ITEMS_LIMIT};
+use sui_storage::db_metrics::DBMetrics;
+use sui_storage::IndexStore;
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
@@ -137,6 +145,69 @@ pub enum ToolCommand {
         )]
         sequence_number: Option<CheckpointSequenceNumber>,
     },
+
+    /// Export a range of checkpoints from the DB of a stopped node into self-contained files,
+    /// one per checkpoint, holding its summary, contents, transactions and effects.
+    #[clap(name = "export-checkpoints")]
+    ExportCheckpoints {
+        /// Path of the DB to read
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(
+            long,
+            help = "Checkpoints to export, as <start>..<end> with <end> excluded"
+        )]
+        range: String,
+        #[clap(long, help = "Directory to write the checkpoint files to")]
+        out: PathBuf,
+    },
+
+    /// Export the checkpoint files written by export-checkpoints into analytics tables. The
+    /// export resumes after the checkpoints already exported to the output directory.
+    #[clap(name = "analytics-export")]
+    AnalyticsExport {
+        #[clap(long, help = "Directory of the checkpoint files")]
+        archive: PathBuf,
+        #[clap(
+            long = "genesis",
+            help = "Genesis the checkpoints are verified against"
+        )]
+        genesis: PathBuf,
+        #[clap(long, help = "Directory to write the tables to")]
+        out: PathBuf,
+        #[clap(long, default_value_t = 1000)]
+        checkpoints_per_file: u64,
+        #[clap(
+            long,
+            default_value = "csv",
+            help = "Format of the tables, csv or parquet"
+        )]
+        format: String,
+    },
+
+    /// Verify the checkpoint files written by export-checkpoints and print a summary of them.
+    #[clap(name = "import-checkpoints")]
+    ImportCheckpoints {
+        #[clap(long, help = "Directory of the checkpoint files")]
+        dir: PathBuf,
+        #[clap(
+            long = "genesis",
+            help = "Genesis the checkpoints are verified against"
+        )]
+        genesis: PathBuf,
+    },
+
+    /// Rebuild the coin index of a stopped full node from its live coins. Full nodes build it on
+    /// startup when it is missing, this replaces it e.g. after a bug corrupted it. The indexes
+    /// of the node must be caught up with its executed transactions.
+    #[clap(name = "build-coin-index")]
+    BuildCoinIndex {
+        /// Path of the DB to read
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+    },
 }
 
 fn make_clients(genesis: &Genesis) -> Result<BTreeMap<AuthorityName, NetworkAuthorityClient>> {
@@ -549,6 +620,78 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ExportCheckpoints {
+                db_path,
+                genesis,
+                range,
+                out,
+            } => {
+                let genesis = Genesis::load(genesis)?;
+                let range = parse_checkpoint_range(&range)?;
+                let exported = export_checkpoints(&db_path, &genesis, range, &out)?;
+                println!("Exported {} checkpoints to {}", exported, out.display());
+            }
+            ToolCommand::AnalyticsExport {
+                archive,
+                genesis,
+                out,
+                checkpoints_per_file,
+                format,
+            } => {
+                let format = match format.as_str() {
+                    "csv" => AnalyticsFileFormat::Csv,
+                    "parquet" => AnalyticsFileFormat::Parquet,
+                    _ => return Err(anyhow!("Unknown format {format}, expected csv or parquet")),
+                };
+                let genesis = Genesis::load(genesis)?;
+                let archive = CheckpointArchive::new(&archive, genesis.committee()?)?;
+                let start_checkpoint = archive.first_checkpoint();
+                let config = AnalyticsExportConfig {
+                    output_dir: out,
+                    format,
+                    checkpoints_per_file,
+                    start_checkpoint,
+                };
+                let mut exporter =
+                    AnalyticsExporter::new(&config, Arc::new(archive), &Registry::new())?;
+                let exported = exporter.export_available(true)?;
+                println!(
+                    "Exported {} checkpoints, next checkpoint to export is {}",
+                    exported,
+                    exporter.next_checkpoint()
+                );
+            }
+            ToolCommand::BuildCoinIndex { db_path } => {
+                let store = AuthorityStore::open(&db_path.join("store"), None)?;
+                let indexes =
+                    IndexStore::open(db_path.join("indexes"), None, DBMetrics::new_unregistered());
+                let next_sequence = store.next_sequence_number()?;
+                let watermark = indexes.watermark()?;
+                if watermark.next_sequence != next_sequence {
+                    return Err(anyhow!(
+                        "The indexes are written up to transaction {}, but transactions up to {} \
+                         were executed. Start the node to let its indexes catch up.",
+                        watermark.next_sequence,
+                        next_sequence
+                    ));
+                }
+                let coins = indexes
+                    .build_coin_index(store.iter_live_coins(), store.iter_live_treasury_caps())?;
+                println!("Indexed {coins} coins owned by addresses");
+            }
+            ToolCommand::ImportCheckpoints { dir, genesis } => {
+                let genesis = Genesis::load(genesis)?;
+                for export in import_checkpoints(&dir, genesis.committee()?)? {
+                    let export = export?;
+                    println!(
+                        "checkpoint {}: epoch {}, {} transactions, digest {:?}",
+                        export.sequence_number(),
+                        export.checkpoint.epoch(),
+                        export.transactions.len(),
+                        export.checkpoint.summary().digest()
+                    );
+                }
+            }
         };
         Ok(())
     }
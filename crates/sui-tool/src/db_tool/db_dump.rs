@@ -12,7 +12,9 @@ use sui_core::authority::authority_store_tables::{AuthorityEpochTables, Authorit
 use sui_core::checkpoints::CheckpointStoreTables;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_storage::default_db_options;
-use sui_storage::{lock_service::LockServiceImpl, node_sync_store::NodeSyncStore, IndexStore};
+use sui_storage::{
+    lock_service::LockServiceImpl, node_sync_store::NodeSyncStore, IndexStoreTables,
+};
 use sui_types::{
     base_types::EpochId,
     crypto::{AuthoritySignInfo, EmptySignInfo},
@@ -96,7 +98,7 @@ pub fn dump_table(
                 )
             }
         }
-        StoreName::Index => IndexStore::get_read_only_handle(db_path, None, None).dump(
+        StoreName::Index => IndexStoreTables::get_read_only_handle(db_path, None, None).dump(
             table_name,
             page_size,
             page_number,
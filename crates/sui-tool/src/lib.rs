@@ -2,4 +2,5 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod checkpoint_export;
 pub mod db_tool;
@@ -16,17 +16,17 @@ use sui_adapter::adapter::resolve_and_type_check;
 use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_types::GetRawObjectDataResponse;
 use sui_json_rpc_types::SuiObjectInfo;
-use sui_json_rpc_types::{RPCTransactionRequestParams, SuiData, SuiTypeTag};
-use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_json_rpc_types::{RPCTransactionRequestParams, SuiData, SuiPackageAbi, SuiTypeTag};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
 use sui_types::error::SuiError;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
     CallArg, InputObjectKind, MoveCall, ObjectArg, SingleTransactionKind, TransactionData,
-    TransactionKind, TransferObject,
+    TransactionExpiration, TransactionKind, TransferObject, MAX_GAS_PAYMENT_OBJECTS,
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
-use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_OBJECT_ID};
+use sui_types::{coin, fp_ensure, transaction_checks, SUI_FRAMEWORK_OBJECT_ID};
 
 #[async_trait]
 pub trait DataReader {
@@ -42,34 +42,152 @@ pub trait DataReader {
 }
 
 #[derive(Clone)]
-pub struct TransactionBuilder(pub Arc<dyn DataReader + Sync + Send>);
+pub struct TransactionBuilder {
+    pub reader: Arc<dyn DataReader + Sync + Send>,
+    /// The expiration of the transactions built.
+    expiration: TransactionExpiration,
+    /// The references objects are pinned at, see [Self::with_pinned_objects].
+    pinned_objects: BTreeMap<ObjectID, ObjectRef>,
+}
 
 impl TransactionBuilder {
+    pub fn new(reader: Arc<dyn DataReader + Sync + Send>) -> Self {
+        Self {
+            reader,
+            expiration: TransactionExpiration::None,
+            pinned_objects: BTreeMap::new(),
+        }
+    }
+
+    /// Build transactions which validators only sign until `expiration`.
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    /// Build transactions against the given references of objects instead of their latest
+    /// ones, e.g. the references a previous transaction of the sender created, which the node
+    /// read from may not have seen yet. Building fails with `SuiError::ObjectVersionConflict`
+    /// if the node has seen one of them modified since.
+    pub fn with_pinned_objects(mut self, objects: impl IntoIterator<Item = ObjectRef>) -> Self {
+        self.pinned_objects
+            .extend(objects.into_iter().map(|object| (object.0, object)));
+        self
+    }
+
+    pub fn pinned_objects(&self) -> &BTreeMap<ObjectID, ObjectRef> {
+        &self.pinned_objects
+    }
+
+    /// The reference to build against for an object whose latest reference known to the node
+    /// is `obj_ref`: the one it is pinned at, if any, unless the object was modified since.
+    fn check_pinned_version(
+        &self,
+        obj_ref: ObjectRef,
+        previous_transaction: TransactionDigest,
+    ) -> Result<ObjectRef, SuiError> {
+        let (object_id, current_version, _) = obj_ref;
+        match self.pinned_objects.get(&object_id) {
+            Some((_, pinned_version, _)) if *pinned_version < current_version => {
+                Err(SuiError::ObjectVersionConflict {
+                    object_id,
+                    pinned_version: *pinned_version,
+                    current_version,
+                    previous_transaction,
+                })
+            }
+            Some(pinned) => Ok(*pinned),
+            None => Ok(obj_ref),
+        }
+    }
+
+    /// Select the coins paying for gas: `input_gas` if given, else a coin of the signer with
+    /// enough balance for the budget, else several coins which have enough balance together,
+    /// which are merged into the first one at execution. Returns the first coin and the coins
+    /// merged into it.
     async fn select_gas(
         &self,
         signer: SuiAddress,
         input_gas: Option<ObjectID>,
         budget: u64,
         input_objects: Vec<ObjectID>,
-    ) -> Result<ObjectRef, anyhow::Error> {
+    ) -> Result<(ObjectRef, Vec<ObjectRef>), anyhow::Error> {
         if let Some(gas) = input_gas {
-            self.get_object_ref(gas).await
-        } else {
-            let objs = self.0.get_objects_owned_by_address(signer).await?;
-            let gas_objs = objs
-                .iter()
-                .filter(|obj| obj.type_ == GasCoin::type_().to_string());
-
-            for obj in gas_objs {
-                let response = self.0.get_object(obj.object_id).await?;
-                let obj = response.object()?;
-                let gas: GasCoin = bcs::from_bytes(&obj.data.try_as_move().unwrap().bcs_bytes)?;
-                if !input_objects.contains(&obj.id()) && gas.value() >= budget {
-                    return Ok(obj.reference.to_object_ref());
-                }
+            return Ok((self.get_object_ref(gas).await?, vec![]));
+        }
+        let objs = self.reader.get_objects_owned_by_address(signer).await?;
+        let gas_objs = objs
+            .iter()
+            .filter(|obj| obj.type_ == GasCoin::type_().to_string());
+
+        let mut coins = Vec::new();
+        for obj in gas_objs {
+            let response = self.reader.get_object(obj.object_id).await?;
+            let obj = response.object()?;
+            let gas: GasCoin = bcs::from_bytes(&obj.data.try_as_move().unwrap().bcs_bytes)?;
+            if input_objects.contains(&obj.id()) {
+                continue;
+            }
+            let gas_ref =
+                self.check_pinned_version(obj.reference.to_object_ref(), obj.previous_transaction)?;
+            if gas.value() >= budget {
+                return Ok((gas_ref, vec![]));
+            }
+            coins.push((gas.value(), gas_ref));
+        }
+
+        // No coin is enough on its own: pay with the fewest coins, largest first.
+        coins.sort_by(|(a, _), (b, _)| b.cmp(a));
+        let mut total = 0;
+        for (num_coins, (value, _)) in coins.iter().take(MAX_GAS_PAYMENT_OBJECTS).enumerate() {
+            total += *value as u128;
+            if total >= budget as u128 {
+                let mut gas_coins = coins[..=num_coins].iter().map(|(_, coin)| *coin);
+                let gas = gas_coins.next().unwrap();
+                return Ok((gas, gas_coins.collect()));
+            }
+        }
+        Err(anyhow!("Cannot find gas coins for signer address [{signer}] with amount sufficient for the budget [{budget}]."))
+    }
+
+    /// Select SUI coins of `signer`, largest first, with a total balance of at least `amount`,
+    /// leaving out the gas coin `gas`.
+    async fn select_sui_coins(
+        &self,
+        signer: SuiAddress,
+        amount: u128,
+        gas: Option<ObjectID>,
+    ) -> Result<Vec<ObjectRef>, anyhow::Error> {
+        let objs = self.reader.get_objects_owned_by_address(signer).await?;
+        let mut coins = Vec::new();
+        for obj in objs
+            .iter()
+            .filter(|obj| obj.type_ == GasCoin::type_().to_string() && Some(obj.object_id) != gas)
+        {
+            let response = self.reader.get_object(obj.object_id).await?;
+            let obj = response.object()?;
+            let coin: GasCoin = bcs::from_bytes(&obj.data.try_as_move().unwrap().bcs_bytes)?;
+            coins.push((
+                coin.value(),
+                self.check_pinned_version(obj.reference.to_object_ref(), obj.previous_transaction)?,
+            ));
+        }
+
+        coins.sort_by(|(a, _), (b, _)| b.cmp(a));
+        let mut total = 0;
+        let mut selected = Vec::new();
+        for (value, coin) in coins {
+            if total >= amount {
+                break;
             }
-            Err(anyhow!("Cannot find gas coin for signer address [{signer}] with amount sufficient for the budget [{budget}]."))
+            total += value as u128;
+            selected.push(coin);
         }
+        fp_ensure!(
+            total >= amount && !selected.is_empty(),
+            anyhow!("Cannot find SUI coins for signer address [{signer}] with a total balance of [{amount}].")
+        );
+        Ok(selected)
     }
 
     pub async fn transfer_object(
@@ -81,15 +199,19 @@ impl TransactionBuilder {
         recipient: SuiAddress,
     ) -> anyhow::Result<TransactionData> {
         let single_transfer = self.single_transfer_object(object_id, recipient).await?;
-        let gas = self
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, vec![object_id])
             .await?;
-        Ok(TransactionData::new(
-            TransactionKind::Single(single_transfer),
-            signer,
-            gas,
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new(
+                TransactionKind::Single(single_transfer),
+                signer,
+                gas,
+                gas_budget,
+            )
+            .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     async fn single_transfer_object(
@@ -112,11 +234,15 @@ impl TransactionBuilder {
         amount: Option<u64>,
     ) -> anyhow::Result<TransactionData> {
         let object = self.get_object_ref(sui_object_id).await?;
-        Ok(TransactionData::new_transfer_sui(
+        self.check_transaction(TransactionData::new_transfer_sui(
             recipient, signer, amount, object, gas_budget,
         ))
+        .await
     }
 
+    /// Pay `amounts[i]` to `recipients[i]` from `input_coins`, in a single transaction. If no
+    /// input coins are given, SUI coins of the signer covering the total amount are selected.
+    /// What is left of the input coins stays with the signer as change.
     pub async fn pay(
         &self,
         signer: SuiAddress,
@@ -131,21 +257,35 @@ impl TransactionBuilder {
                 return Err(anyhow!("Gas coin is in input coins of Pay transaction, use PaySui transaction instead!"));
             }
         }
+        fp_ensure!(
+            recipients.len() == amounts.len(),
+            anyhow!(
+                "Found {} recipient addresses, but {} recipient amounts",
+                recipients.len(),
+                amounts.len()
+            )
+        );
 
-        let handles: Vec<_> = input_coins
-            .iter()
-            .map(|id| self.get_object_ref(*id))
-            .collect();
-        let coins = join_all(handles)
-            .await
-            .into_iter()
-            .map(|c| c.unwrap())
-            .collect();
-        let gas = self
+        let coins = if input_coins.is_empty() {
+            let total = amounts.iter().map(|amount| *amount as u128).sum();
+            self.select_sui_coins(signer, total, gas).await?
+        } else {
+            let handles: Vec<_> = input_coins
+                .iter()
+                .map(|id| self.get_object_ref(*id))
+                .collect();
+            join_all(handles)
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let input_coins = coins.iter().map(|(id, _, _)| *id).collect();
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, input_coins)
             .await?;
-        let data = TransactionData::new_pay(signer, coins, recipients, amounts, gas, gas_budget);
-        Ok(data)
+        let data = TransactionData::new_pay(signer, coins, recipients, amounts, gas, gas_budget)
+            .with_merged_gas_payment(merged_gas);
+        self.check_transaction(data).await
     }
 
     pub async fn move_call(
@@ -171,16 +311,20 @@ impl TransactionBuilder {
             })
             .collect();
 
-        let gas = self
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, input_objects)
             .await?;
 
-        Ok(TransactionData::new(
-            TransactionKind::Single(single_move_call),
-            signer,
-            gas,
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new(
+                TransactionKind::Single(single_move_call),
+                signer,
+                gas,
+                gas_budget,
+            )
+            .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     async fn single_move_call(
@@ -224,12 +368,14 @@ impl TransactionBuilder {
         id: ObjectID,
         objects: &mut BTreeMap<ObjectID, Object>,
     ) -> Result<ObjectArg, anyhow::Error> {
-        let response = self.0.get_object(id).await?;
+        let response = self.reader.get_object(id).await?;
         let obj: Object = response.into_object()?.try_into()?;
         let obj_ref = obj.compute_object_reference();
+        let previous_transaction = obj.previous_transaction;
         let owner = obj.owner;
         objects.insert(id, obj);
         Ok(match owner {
+            // Transactions only name the initial version of shared objects, so pins do not apply.
             Owner::Shared {
                 initial_shared_version,
             } => ObjectArg::SharedObject {
@@ -237,7 +383,9 @@ impl TransactionBuilder {
                 initial_shared_version,
             },
             Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
-                ObjectArg::ImmOrOwnedObject(obj_ref)
+                ObjectArg::ImmOrOwnedObject(
+                    self.check_pinned_version(obj_ref, previous_transaction)?,
+                )
             }
         })
     }
@@ -250,7 +398,7 @@ impl TransactionBuilder {
         type_args: &[TypeTag],
         json_args: Vec<SuiJsonValue>,
     ) -> Result<Vec<CallArg>, anyhow::Error> {
-        let package = self.0.get_object(package_id).await?.into_object()?;
+        let package = self.reader.get_object(package_id).await?.into_object()?;
         let package = package
             .data
             .try_as_package()
@@ -258,6 +406,20 @@ impl TransactionBuilder {
             .ok_or_else(|| anyhow!("Object [{}] is not a move package.", package_id))?;
         let package: MovePackage = MovePackage::new(package.id, &package.module_map);
 
+        // Check the call against the interface of the package first, to reject calls to missing
+        // or non-entry functions, or with the wrong number of arguments, with a clear error.
+        let modules = package
+            .normalize()?
+            .into_iter()
+            .map(|(name, normalized)| (name, normalized.into()))
+            .collect();
+        SuiPackageAbi::new(package_id, modules).check_move_call(
+            module.as_str(),
+            function.as_str(),
+            type_args.len(),
+            json_args.len(),
+        )?;
+
         let json_args = resolve_move_function_args(
             &package,
             module.clone(),
@@ -304,13 +466,12 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let gas = self.select_gas(sender, gas, gas_budget, vec![]).await?;
-        Ok(TransactionData::new_module(
-            sender,
-            gas,
-            compiled_modules,
-            gas_budget,
-        ))
+        let (gas, merged_gas) = self.select_gas(sender, gas, gas_budget, vec![]).await?;
+        self.check_transaction(
+            TransactionData::new_module(sender, gas, compiled_modules, gas_budget)
+                .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     pub async fn split_coin(
@@ -321,27 +482,36 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let coin = self.0.get_object(coin_object_id).await?.into_object()?;
-        let coin_object_ref = coin.reference.to_object_ref();
+        let coin = self
+            .reader
+            .get_object(coin_object_id)
+            .await?
+            .into_object()?;
+        let coin_object_ref =
+            self.check_pinned_version(coin.reference.to_object_ref(), coin.previous_transaction)?;
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas = self
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id])
             .await?;
 
-        Ok(TransactionData::new_move_call(
-            signer,
-            self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
-            coin::PAY_MODULE_NAME.to_owned(),
-            coin::PAY_SPLIT_VEC_FUNC_NAME.to_owned(),
-            type_args,
-            gas,
-            vec![
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
-                CallArg::Pure(bcs::to_bytes(&split_amounts)?),
-            ],
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new_move_call(
+                signer,
+                self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+                coin::PAY_MODULE_NAME.to_owned(),
+                coin::PAY_SPLIT_VEC_FUNC_NAME.to_owned(),
+                type_args,
+                gas,
+                vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
+                    CallArg::Pure(bcs::to_bytes(&split_amounts)?),
+                ],
+                gas_budget,
+            )
+            .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     pub async fn split_coin_equal(
@@ -352,27 +522,36 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let coin = self.0.get_object(coin_object_id).await?.into_object()?;
-        let coin_object_ref = coin.reference.to_object_ref();
+        let coin = self
+            .reader
+            .get_object(coin_object_id)
+            .await?
+            .into_object()?;
+        let coin_object_ref =
+            self.check_pinned_version(coin.reference.to_object_ref(), coin.previous_transaction)?;
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas = self
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id])
             .await?;
 
-        Ok(TransactionData::new_move_call(
-            signer,
-            self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
-            coin::PAY_MODULE_NAME.to_owned(),
-            coin::PAY_SPLIT_N_FUNC_NAME.to_owned(),
-            type_args,
-            gas,
-            vec![
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
-                CallArg::Pure(bcs::to_bytes(&split_count)?),
-            ],
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new_move_call(
+                signer,
+                self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+                coin::PAY_MODULE_NAME.to_owned(),
+                coin::PAY_SPLIT_N_FUNC_NAME.to_owned(),
+                type_args,
+                gas,
+                vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_object_ref)),
+                    CallArg::Pure(bcs::to_bytes(&split_count)?),
+                ],
+                gas_budget,
+            )
+            .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     pub async fn merge_coins(
@@ -383,28 +562,33 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let coin = self.0.get_object(primary_coin).await?.into_object()?;
-        let primary_coin_ref = coin.reference.to_object_ref();
+        let coin = self.reader.get_object(primary_coin).await?.into_object()?;
+        let primary_coin_ref =
+            self.check_pinned_version(coin.reference.to_object_ref(), coin.previous_transaction)?;
         let coin_to_merge_ref = self.get_object_ref(coin_to_merge).await?;
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas = self
+        let (gas, merged_gas) = self
             .select_gas(signer, gas, gas_budget, vec![primary_coin, coin_to_merge])
             .await?;
 
-        Ok(TransactionData::new_move_call(
-            signer,
-            self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
-            coin::PAY_MODULE_NAME.to_owned(),
-            coin::PAY_JOIN_FUNC_NAME.to_owned(),
-            type_args,
-            gas,
-            vec![
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(primary_coin_ref)),
-                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_to_merge_ref)),
-            ],
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new_move_call(
+                signer,
+                self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+                coin::PAY_MODULE_NAME.to_owned(),
+                coin::PAY_JOIN_FUNC_NAME.to_owned(),
+                type_args,
+                gas,
+                vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(primary_coin_ref)),
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_to_merge_ref)),
+                ],
+                gas_budget,
+            )
+            .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     pub async fn batch_transaction(
@@ -455,23 +639,38 @@ impl TransactionBuilder {
             })
             .collect();
 
-        let gas = self.select_gas(signer, gas, gas_budget, inputs).await?;
+        let (gas, merged_gas) = self.select_gas(signer, gas, gas_budget, inputs).await?;
 
-        Ok(TransactionData::new(
-            TransactionKind::Batch(tx_kinds),
-            signer,
-            gas,
-            gas_budget,
-        ))
+        self.check_transaction(
+            TransactionData::new(TransactionKind::Batch(tx_kinds), signer, gas, gas_budget)
+                .with_merged_gas_payment(merged_gas),
+        )
+        .await
     }
 
     async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
-        Ok(self
-            .0
-            .get_object(object_id)
-            .await?
-            .object()?
-            .reference
-            .to_object_ref())
+        let response = self.reader.get_object(object_id).await?;
+        let object = response.object()?;
+        Ok(self.check_pinned_version(
+            object.reference.to_object_ref(),
+            object.previous_transaction,
+        )?)
+    }
+
+    /// Run the checks validators apply before locking the input objects, so that malformed
+    /// transactions are rejected locally with the same error instead of after a round trip.
+    async fn check_transaction(&self, data: TransactionData) -> anyhow::Result<TransactionData> {
+        let data = data.with_expiration(self.expiration);
+        transaction_checks::check_transaction_data(&data)?;
+        for (object_id, _, _) in data.gas_coins() {
+            let gas_object: Object = self
+                .reader
+                .get_object(*object_id)
+                .await?
+                .into_object()?
+                .try_into()?;
+            transaction_checks::check_gas_object(&data, &gas_object)?;
+        }
+        Ok(data)
     }
 }
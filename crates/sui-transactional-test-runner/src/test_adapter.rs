@@ -39,6 +39,8 @@ use std::{
 use sui_adapter::{adapter::new_move_vm, genesis};
 use sui_core::{execution_engine, test_utils::to_sender_signed_transaction};
 use sui_framework::DEFAULT_FRAMEWORK_PATH;
+use sui_network::version::PROTOCOL_VERSION;
+use sui_types::execution_limits::ExecutionLimits;
 use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::temporary_store::TemporaryStore;
 use sui_types::{
@@ -491,6 +493,7 @@ impl<'a> SuiTestAdapter<'a> {
                 ..
             },
             execution_error,
+            _,
         ) = execution_engine::execute_transaction_to_effects(
             shared_object_refs,
             temporary_store,
@@ -500,8 +503,10 @@ impl<'a> SuiTestAdapter<'a> {
             &self.vm,
             &self.native_functions,
             gas_status,
+            &ExecutionLimits::for_protocol_version(PROTOCOL_VERSION),
             // TODO: Support different epochs in transactional tests.
             0,
+            false,
         );
         let created_set: BTreeSet<_> = created.iter().map(|((id, _, _), _)| *id).collect();
         let mut created_ids: Vec<_> = created_set.iter().copied().collect();
@@ -1,7 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::error::{ExecutionError, ExecutionErrorKind};
+use crate::base_types::{ObjectID, SequenceNumber};
+use crate::coin::Coin;
+use crate::error::{ExecutionError, ExecutionErrorKind, SuiResult};
+use crate::messages::TransactionEffects;
+use crate::object::{Data, Object, Owner};
+use crate::object_change::changed_objects;
 use crate::SUI_FRAMEWORK_ADDRESS;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
@@ -10,6 +15,11 @@ use move_core_types::value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
+#[cfg(test)]
+#[path = "unit_tests/balance_tests.rs"]
+mod balance_tests;
+
 pub const BALANCE_MODULE_NAME: &IdentStr = ident_str!("balance");
 pub const BALANCE_STRUCT_NAME: &IdentStr = ident_str!("Balance");
 
@@ -67,3 +77,91 @@ impl Balance {
         }
     }
 }
+
+/// The net change of the balance of an owner in one coin type, made by a transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct BalanceChange {
+    pub owner: Owner,
+    pub coin_type: TypeTag,
+    pub amount: i128,
+}
+
+impl BalanceChange {
+    /// Computes the balance changes made by the transaction of `effects`, from the objects it
+    /// changed as returned by `get_object` at their versions before and after the transaction.
+    pub fn compute(
+        effects: &TransactionEffects,
+        get_object: impl Fn(&ObjectID, SequenceNumber) -> SuiResult<Option<Object>>,
+    ) -> SuiResult<Vec<Self>> {
+        let objects = read_changed_objects(effects, get_object)?;
+        Ok(Self::from_objects(
+            objects
+                .iter()
+                .map(|(before, after)| (before.as_ref(), after.as_ref())),
+        )?)
+    }
+
+    /// Sums the balances of the coins among `objects`, each the version of an object before and
+    /// after a transaction, into the net change per owner and coin type. Owners whose balance
+    /// did not change are left out.
+    pub fn from_objects<'a>(
+        objects: impl IntoIterator<Item = (Option<&'a Object>, Option<&'a Object>)>,
+    ) -> Result<Vec<Self>, ExecutionError> {
+        let mut balances: BTreeMap<(Owner, TypeTag), i128> = BTreeMap::new();
+        for (before, after) in objects {
+            for (object, sign) in [(before, -1), (after, 1)] {
+                if let Some(object) = object {
+                    if let Some((coin_type, balance)) = coin_balance(object)? {
+                        *balances.entry((object.owner, coin_type)).or_default() +=
+                            sign * balance as i128;
+                    }
+                }
+            }
+        }
+        Ok(balances
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(|((owner, coin_type), amount)| Self {
+                owner,
+                coin_type,
+                amount,
+            })
+            .collect())
+    }
+}
+
+/// The objects changed by the transaction of `effects`, each as returned by `get_object` at its
+/// versions before and after the transaction, None for objects created, unwrapped, deleted or
+/// wrapped by the transaction on the side where they did not exist.
+pub fn read_changed_objects(
+    effects: &TransactionEffects,
+    get_object: impl Fn(&ObjectID, SequenceNumber) -> SuiResult<Option<Object>>,
+) -> SuiResult<Vec<(Option<Object>, Option<Object>)>> {
+    let read = |id, version: Option<SequenceNumber>| match version {
+        Some(version) => get_object(id, version),
+        None => Ok(None),
+    };
+    changed_objects(effects)
+        .iter()
+        .map(|change| {
+            Ok((
+                read(&change.object_ref.0, change.before)?,
+                read(&change.object_ref.0, change.after)?,
+            ))
+        })
+        .collect()
+}
+
+/// The coin type and balance of `object`, if it is a coin.
+pub fn coin_balance(object: &Object) -> Result<Option<(TypeTag, u64)>, ExecutionError> {
+    let coin_type = match &object.data {
+        Data::Move(move_obj) if Coin::is_coin(&move_obj.type_) => {
+            match move_obj.type_.type_params.first() {
+                Some(coin_type) => coin_type.clone(),
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Coin::extract_balance_if_coin(object)?.map(|balance| (coin_type, balance)))
+}
@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::Bytes;
+
+use crate::base_types::{AuthorityName, ObjectID, SuiAddress, TransactionDigest};
+use crate::committee::{Committee, EpochId};
+use crate::crypto::{
+    sha3_hash, AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature,
+    AuthorityStrongQuorumSignInfo, SuiAuthoritySignature,
+};
+use crate::error::{SuiError, SuiResult};
+use crate::event::Event;
+use crate::fp_ensure;
+use crate::messages::{ExecutionStatus, TransactionEffects};
+
+#[cfg(test)]
+#[path = "unit_tests/bridge_tests.rs"]
+mod bridge_tests;
+
+/// Version of the [BridgeMessage] layout. Verifiers on external chains must reject messages
+/// with a version they do not know how to decode.
+pub const BRIDGE_MESSAGE_VERSION: u8 = 1;
+
+pub type BridgeMessageDigest = [u8; 32];
+
+/// A Move event emitted by a finalized transaction, in the format validators attest to for
+/// consumption by external chains.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeMessage {
+    pub version: u8,
+    pub tx_digest: TransactionDigest,
+    /// Index of the event in the events emitted by the transaction.
+    pub event_seq: u64,
+    pub package_id: ObjectID,
+    pub sender: SuiAddress,
+    pub type_: StructTag,
+    /// BCS serialized event payload.
+    #[serde_as(as = "Bytes")]
+    pub contents: Vec<u8>,
+}
+
+impl BridgeMessage {
+    /// Build the message for the `event_seq`-th event emitted by a transaction. Only Move
+    /// events of successfully executed transactions can be bridged.
+    pub fn from_effects(effects: &TransactionEffects, event_seq: u64) -> SuiResult<Self> {
+        fp_ensure!(
+            matches!(effects.status, ExecutionStatus::Success),
+            SuiError::BridgeAttestationError {
+                error: format!("transaction {:?} failed", effects.transaction_digest),
+            }
+        );
+        match effects.events.get(event_seq as usize) {
+            Some(Event::MoveEvent {
+                package_id,
+                sender,
+                type_,
+                contents,
+                ..
+            }) => Ok(Self {
+                version: BRIDGE_MESSAGE_VERSION,
+                tx_digest: effects.transaction_digest,
+                event_seq,
+                package_id: *package_id,
+                sender: *sender,
+                type_: type_.clone(),
+                contents: contents.clone(),
+            }),
+            Some(_) => Err(SuiError::BridgeAttestationError {
+                error: format!("event {event_seq} is not a Move event"),
+            }),
+            None => Err(SuiError::BridgeAttestationError {
+                error: format!(
+                    "transaction {:?} emitted no event {event_seq}",
+                    effects.transaction_digest
+                ),
+            }),
+        }
+    }
+
+    pub fn digest(&self) -> BridgeMessageDigest {
+        sha3_hash(self)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BridgeAttestationEnvelope<S> {
+    pub message: BridgeMessage,
+    pub auth_signature: S,
+}
+
+/// A bridge message signed by a single validator.
+pub type SignedBridgeAttestation = BridgeAttestationEnvelope<AuthoritySignInfo>;
+
+impl SignedBridgeAttestation {
+    pub fn new(
+        epoch: EpochId,
+        message: BridgeMessage,
+        authority: AuthorityName,
+        signer: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let signature = AuthoritySignature::new(&message, signer);
+        Self {
+            message,
+            auth_signature: AuthoritySignInfo {
+                epoch,
+                authority,
+                signature,
+            },
+        }
+    }
+
+    pub fn authority(&self) -> &AuthorityName {
+        &self.auth_signature.authority
+    }
+
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.message, committee)
+    }
+}
+
+/// A bridge message signed by a quorum of validators. This is what relayers submit to the
+/// external chain, which checks it against the Sui committee it tracks.
+pub type CertifiedBridgeAttestation = BridgeAttestationEnvelope<AuthorityStrongQuorumSignInfo>;
+
+impl CertifiedBridgeAttestation {
+    pub fn new(
+        message: BridgeMessage,
+        signatures: Vec<(AuthorityName, AuthoritySignature)>,
+        committee: &Committee,
+    ) -> SuiResult<Self> {
+        Ok(Self {
+            message,
+            auth_signature: AuthorityStrongQuorumSignInfo::new_with_signatures(
+                signatures, committee,
+            )?,
+        })
+    }
+
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.message, committee)
+    }
+}
+
+/// Ask a validator to attest to an event emitted by a transaction it executed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BridgeAttestationRequest {
+    pub tx_digest: TransactionDigest,
+    pub event_seq: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BridgeAttestationResponse {
+    pub attestation: SignedBridgeAttestation,
+}
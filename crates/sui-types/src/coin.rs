@@ -19,6 +19,7 @@ use schemars::JsonSchema;
 
 pub const COIN_MODULE_NAME: &IdentStr = ident_str!("coin");
 pub const COIN_STRUCT_NAME: &IdentStr = ident_str!("Coin");
+pub const TREASURY_CAP_STRUCT_NAME: &IdentStr = ident_str!("TreasuryCap");
 
 pub const PAY_MODULE_NAME: &IdentStr = ident_str!("pay");
 pub const PAY_JOIN_FUNC_NAME: &IdentStr = ident_str!("join");
@@ -117,3 +118,32 @@ pub struct TreasuryCap {
     pub id: UID,
     pub total_supply: Supply,
 }
+
+impl TreasuryCap {
+    /// Is this other StructTag representing a TreasuryCap?
+    pub fn is_treasury_cap(other: &StructTag) -> bool {
+        other.address == SUI_FRAMEWORK_ADDRESS
+            && other.module.as_ident_str() == COIN_MODULE_NAME
+            && other.name.as_ident_str() == TREASURY_CAP_STRUCT_NAME
+    }
+
+    /// Returns the coin type of the TreasuryCap `object`, None if it is not a TreasuryCap.
+    pub fn coin_type_of(object: &Object) -> Option<TypeTag> {
+        match &object.data {
+            Data::Move(move_obj) if Self::is_treasury_cap(&move_obj.type_) => {
+                move_obj.type_.type_params.first().cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Create a TreasuryCap from BCS bytes
+    pub fn from_bcs_bytes(content: &[u8]) -> Result<Self, ExecutionError> {
+        bcs::from_bytes(content).map_err(|err| {
+            ExecutionError::new_with_source(
+                ExecutionErrorKind::InvalidCoinObject,
+                format!("Unable to deserialize treasury cap object: {:?}", err),
+            )
+        })
+    }
+}
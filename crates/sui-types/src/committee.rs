@@ -299,6 +299,9 @@ impl Display for Committee {
 pub struct CommitteeWithNetAddresses {
     pub committee: Committee,
     pub net_addresses: BTreeMap<AuthorityName, Vec<u8>>,
+    /// Other network addresses of each validator, which clients dial in order when the network
+    /// address is unreachable.
+    pub backup_net_addresses: BTreeMap<AuthorityName, Vec<Vec<u8>>>,
 }
 
 impl CommitteeWithNetAddresses {
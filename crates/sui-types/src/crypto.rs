@@ -1286,6 +1286,7 @@ mod bcs_signable {
 
     pub trait BcsSignable: serde::Serialize + serde::de::DeserializeOwned {}
     impl BcsSignable for crate::batch::TransactionBatch {}
+    impl BcsSignable for crate::bridge::BridgeMessage {}
     impl BcsSignable for crate::batch::AuthorityBatch {}
     impl BcsSignable for crate::committee::Committee {}
     impl BcsSignable for crate::committee::CommitteeWithNetAddresses {}
@@ -1294,6 +1295,7 @@ mod bcs_signable {
     impl BcsSignable for crate::messages_checkpoint::CheckpointProposalContents {}
     impl BcsSignable for crate::messages_checkpoint::CheckpointProposalSummary {}
     impl BcsSignable for crate::messages::CommitteeInfoResponse {}
+    impl BcsSignable for crate::network_info::NetworkInfo {}
     impl BcsSignable for crate::messages::TransactionEffects {}
     impl BcsSignable for crate::messages::TransactionData {}
     impl BcsSignable for crate::messages::SenderSignedData {}
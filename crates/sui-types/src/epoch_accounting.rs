@@ -0,0 +1,176 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Accounting of the gas fees collected during an epoch, and of how the advance epoch
+//! transaction distributed them between the storage fund, validators and delegators.
+
+use std::fmt::Write;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::{EpochId, SuiAddress};
+use crate::messages::{ChangeEpoch, SingleTransactionKind, TransactionData, TransactionKind};
+use crate::object::Object;
+use crate::sui_system_state::{SuiSystemState, Validator};
+use crate::temporary_store::InnerTemporaryStore;
+use crate::SUI_SYSTEM_STATE_OBJECT_ID;
+
+#[cfg(test)]
+#[path = "unit_tests/epoch_accounting_tests.rs"]
+mod epoch_accounting_tests;
+
+/// Columns of the CSV export, matching the fields of `ValidatorAccounting`.
+const CSV_HEADER: &str = "epoch,sui_address,name,stake_before,stake_after,validator_reward,\
+delegation_pool_before,delegation_pool_after,delegator_reward";
+
+/// The rewards and stake changes of one validator at the end of an epoch.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorAccounting {
+    pub sui_address: SuiAddress,
+    pub name: String,
+    /// Stake of the validator during the epoch
+    pub stake_before: u64,
+    /// Stake of the validator during the next epoch, once pending stake and withdrawals
+    /// are applied
+    pub stake_after: u64,
+    /// Computation fees paid to the validator, added to its pending stake
+    pub validator_reward: u64,
+    /// SUI balance of the validator's delegation staking pool during the epoch
+    pub delegation_pool_before: u64,
+    /// SUI balance of the delegation staking pool once rewards and new delegations are added
+    pub delegation_pool_after: u64,
+    /// Computation fees paid to the delegators of the validator, compounded into the pool
+    pub delegator_reward: u64,
+}
+
+/// The gas fees collected during an epoch and where the advance epoch transaction sent them.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochAccountingReport {
+    /// The epoch that ended
+    pub epoch: EpochId,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+    pub storage_fund_before: u64,
+    pub storage_fund_after: u64,
+    /// The validators active during the epoch
+    pub validators: Vec<ValidatorAccounting>,
+}
+
+impl EpochAccountingReport {
+    /// Build the report of the epoch ended by `change_epoch`, from the system state before and
+    /// after the advance epoch transaction.
+    pub fn new(
+        change_epoch: &ChangeEpoch,
+        before: &SuiSystemState,
+        after: &SuiSystemState,
+    ) -> Self {
+        let validators = before
+            .validators
+            .active_validators
+            .iter()
+            .map(|validator| {
+                let next = after
+                    .validators
+                    .active_validators
+                    .iter()
+                    .find(|v| v.metadata.sui_address == validator.metadata.sui_address);
+                ValidatorAccounting::new(validator, next)
+            })
+            .collect();
+        Self {
+            epoch: before.epoch,
+            storage_charge: change_epoch.storage_charge,
+            computation_charge: change_epoch.computation_charge,
+            storage_rebate: change_epoch.storage_rebate,
+            storage_fund_before: before.storage_fund.value(),
+            storage_fund_after: after.storage_fund.value(),
+            validators,
+        }
+    }
+
+    /// Build the report of the epoch ended by `transaction` from the objects it read and wrote,
+    /// or return None if it is not an advance epoch transaction.
+    pub fn from_execution(
+        transaction: &TransactionData,
+        inner_temporary_store: &InnerTemporaryStore,
+    ) -> Option<Self> {
+        let change_epoch = match &transaction.kind {
+            TransactionKind::Single(SingleTransactionKind::ChangeEpoch(change_epoch)) => {
+                change_epoch
+            }
+            _ => return None,
+        };
+        let before = inner_temporary_store
+            .objects
+            .get(&SUI_SYSTEM_STATE_OBJECT_ID)
+            .and_then(system_state)?;
+        let after = inner_temporary_store
+            .get_written_object(&SUI_SYSTEM_STATE_OBJECT_ID)
+            .and_then(system_state)?;
+        Some(Self::new(change_epoch, &before, &after))
+    }
+
+    /// The report as CSV, one validator per row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        for v in &self.validators {
+            writeln!(
+                csv,
+                "{},{},\"{}\",{},{},{},{},{},{}",
+                self.epoch,
+                v.sui_address,
+                v.name.replace('"', "\"\""),
+                v.stake_before,
+                v.stake_after,
+                v.validator_reward,
+                v.delegation_pool_before,
+                v.delegation_pool_after,
+                v.delegator_reward,
+            )
+            .unwrap();
+        }
+        csv
+    }
+}
+
+impl ValidatorAccounting {
+    /// `next` is the validator as of the next epoch, or None if it left the active set.
+    fn new(validator: &Validator, next: Option<&Validator>) -> Self {
+        let pool = &validator.delegation_staking_pool;
+        let pending_delegations: u64 = pool
+            .pending_delegations
+            .iter()
+            .map(|entry| entry.sui_amount)
+            .sum();
+        let (stake_after, validator_reward, delegation_pool_after) = match next {
+            // Stake adjustments happen before rewards are distributed, so the pending stake of
+            // the next epoch only holds the reward.
+            Some(next) => (
+                next.stake_amount,
+                next.pending_stake,
+                next.delegation_staking_pool.sui_balance,
+            ),
+            None => (0, 0, 0),
+        };
+        Self {
+            sui_address: validator.metadata.sui_address.into(),
+            name: String::from_utf8_lossy(&validator.metadata.name).into_owned(),
+            stake_before: validator.stake_amount,
+            stake_after,
+            validator_reward,
+            delegation_pool_before: pool.sui_balance,
+            delegation_pool_after,
+            delegator_reward: delegation_pool_after
+                .saturating_sub(pool.sui_balance)
+                .saturating_sub(pending_delegations),
+        }
+    }
+}
+
+fn system_state(object: &Object) -> Option<SuiSystemState> {
+    bcs::from_bytes(object.data.try_as_move()?.contents()).ok()
+}
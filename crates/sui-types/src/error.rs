@@ -8,9 +8,14 @@ use move_core_types::vm_status::{StatusCode, StatusType};
 use narwhal_executor::SubscriberError;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::time::Duration;
 use thiserror::Error;
 use typed_store::rocks::TypedStoreError;
 
+#[cfg(test)]
+#[path = "unit_tests/error_tests.rs"]
+mod error_tests;
+
 pub const TRANSACTION_NOT_FOUND_MSG_PREFIX: &str = "Could not find the referenced transaction";
 
 #[macro_export]
@@ -47,6 +52,10 @@ const VALIDATOR_HALTED_ERROR_MSG: &str =
     "Validator temporarily stopped processing transactions due to epoch change";
 const MISSING_COMMITTEE_ERROR_MSG: &str = "Missing committee information for epoch";
 
+/// The longest a node may ask clients to back off for, so that a faulty node cannot keep them
+/// away indefinitely.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
 /// Custom error type for Sui.
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Error, Hash)]
 #[allow(clippy::large_enum_variant)]
@@ -76,6 +85,23 @@ pub enum SuiError {
     SharedObjectLockNotSetError,
     #[error("Invalid Batch Transaction: {}", error)]
     InvalidBatchTransaction { error: String },
+    #[error(
+        "Transaction references {num_inputs} input objects, the maximum is {max_inputs}. \
+        Split it into several smaller transactions."
+    )]
+    TooManyInputObjects {
+        num_inputs: usize,
+        max_inputs: usize,
+    },
+    #[error(
+        "Argument {arg_idx} of {function} is {size} bytes, the maximum pure argument size is {max_size} bytes."
+    )]
+    PureArgumentTooLarge {
+        function: String,
+        arg_idx: usize,
+        size: usize,
+        max_size: usize,
+    },
     #[error(
         "Object {child_id:?} is owned by object {parent_id:?}. \
         Objects owned by other objects cannot be used as input arguments."
@@ -211,6 +237,12 @@ pub enum SuiError {
     SubscriptionServiceClosed,
     #[error("Checkpointing error: {}", error)]
     CheckpointingError { error: String },
+    #[error("Invalid object inclusion proof: {}", error)]
+    InvalidObjectInclusionProof { error: String },
+    #[error("Cannot attest bridge message: {}", error)]
+    BridgeAttestationError { error: String },
+    #[error("Invalid network info: {}", error)]
+    InvalidNetworkInfo { error: String },
     #[error(
         "ExecutionDriver error for {:?}: {} - Caused by : {}",
         digest,
@@ -269,7 +301,16 @@ pub enum SuiError {
         current_module: String,
     },
 
+    #[error("Argument {arg_idx} of {function} is an empty pure argument, which is not valid BCS.")]
+    EmptyPureArgument { function: String, arg_idx: usize },
+    #[error("Argument {arg_idx} of {function} is an empty vector of objects.")]
+    EmptyObjectVectorArgument { function: String, arg_idx: usize },
+
     // Gas related errors
+    #[error("Gas object {object_id} cannot also be used as an input object of the transaction.")]
+    GasObjectUsedAsInput { object_id: ObjectID },
+    #[error("Invalid gas object {object_id}: {error}.")]
+    InvalidGasObject { object_id: ObjectID, error: String },
     #[error("Gas budget set higher than max: {error:?}.")]
     GasBudgetTooHigh { error: String },
     #[error("Insufficient gas: {error:?}.")]
@@ -316,6 +357,8 @@ pub enum SuiError {
     },
     #[error("Object deleted at reference {:?}.", object_ref)]
     ObjectDeleted { object_ref: ObjectRef },
+    #[error("Object wrapped at reference {:?}.", object_ref)]
+    ObjectWrapped { object_ref: ObjectRef },
     #[error("Object ID did not have the expected type")]
     BadObjectType { error: String },
     #[error("Move Execution failed")]
@@ -436,8 +479,9 @@ pub enum SuiError {
     InvalidPrivateKey,
 
     // Epoch related errors.
-    #[error("{VALIDATOR_HALTED_ERROR_MSG}")]
-    ValidatorHaltedAtEpochEnd,
+    // Rejected while the validator reconfigures, until the committee of `until_epoch` runs.
+    #[error("{VALIDATOR_HALTED_ERROR_MSG}, until epoch {until_epoch}")]
+    ValidatorHalted { until_epoch: EpochId },
     #[error("Inconsistent state detected during epoch change: {:?}", error)]
     InconsistentEpochState { error: String },
     #[error("Error when advancing epoch: {:?}", error)]
@@ -447,6 +491,16 @@ pub enum SuiError {
     // Tonic::Status
     #[error("{1} - {0}")]
     RpcError(String, &'static str),
+    // An error returned over RPC by a node that sent its code and classification along.
+    #[error("{error}")]
+    RemoteError {
+        code: ErrorCode,
+        retryable: bool,
+        client_fault: bool,
+        error: String,
+        // How long the node asked to wait before sending it more requests, if it is overloaded.
+        retry_after_ms: Option<u64>,
+    },
 
     #[error("Error when calling executeTransaction rpc endpoint: {:?}", error)]
     RpcExecuteTransactionError { error: String },
@@ -454,9 +508,27 @@ pub enum SuiError {
     #[error("Use of disabled feature: {:?}", error)]
     UnsupportedFeatureError { error: String },
 
+    #[error("Node is in read-only mode and does not accept transactions, retry on another node")]
+    NodeReadOnly,
+    #[error("Validator is shutting down and does not accept transactions, retry on another node")]
+    ValidatorShuttingDown,
+
+    #[error("Transaction calls package {package}, which is on the deny list of this node")]
+    DeniedPackage { package: ObjectID },
+    #[error("Transaction calls function {function}, which is on the deny list of this node")]
+    DeniedFunction { function: String },
+
     #[error("Unable to communicate with the Quorum Driver channel: {:?}", error)]
     QuorumDriverCommunicationError { error: String },
 
+    #[error("Request id {request_id} was already used to submit transaction {tx_digest:?}")]
+    ConflictingRequestId {
+        request_id: String,
+        tx_digest: TransactionDigest,
+    },
+    #[error("Request id of {len} bytes is longer than the maximum of {max_len} bytes")]
+    RequestIdTooLong { len: usize, max_len: usize },
+
     #[error("Operation timed out")]
     TimeoutError,
 
@@ -471,10 +543,239 @@ pub enum SuiError {
 
     #[error("Failed to get supermajority's consensus on committee information for minimal epoch: {minimal_epoch}")]
     FailedToGetAgreedCommitteeFromMajority { minimal_epoch: EpochId },
+
+    #[error("Request for {num_digests} transactions, the maximum is {max_digests}")]
+    TooManyTransactionDigests {
+        num_digests: usize,
+        max_digests: usize,
+    },
+
+    #[error("Validator is overloaded: {reason}")]
+    ValidatorOverloaded {
+        reason: String,
+        // How long clients should wait before sending the validator more requests, if known.
+        retry_after_ms: Option<u64>,
+    },
+
+    #[error("Gas paid with {num_objects} coins, the maximum is {max_objects}")]
+    TooManyGasPaymentObjects {
+        num_objects: usize,
+        max_objects: usize,
+    },
+
+    #[error("Transaction expired at the end of epoch {expiration_epoch}, the current epoch is {current_epoch}")]
+    TransactionExpired {
+        expiration_epoch: EpochId,
+        current_epoch: EpochId,
+    },
+
+    #[error("Object {object_id} was pinned at version {pinned_version} but is at version {current_version}, last modified by transaction {previous_transaction:?}")]
+    ObjectVersionConflict {
+        object_id: ObjectID,
+        pinned_version: SequenceNumber,
+        current_version: SequenceNumber,
+        previous_transaction: TransactionDigest,
+    },
 }
 
 pub type SuiResult<T = ()> = Result<T, SuiError>;
 
+/// Stable numeric code of a kind of `SuiError`. Codes are preserved when errors are sent over
+/// RPC, so that callers can tell errors apart without matching on their shape or message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ErrorCode(pub u32);
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! error_codes {
+    ($($variant:ident => $name:ident = $code:literal,)*) => {
+        impl ErrorCode {
+            $(pub const $name: Self = Self($code);)*
+        }
+
+        impl SuiError {
+            /// The stable code of this error. Errors received over RPC keep the code they were
+            /// sent with.
+            pub fn code(&self) -> ErrorCode {
+                match self {
+                    Self::RemoteError { code, .. } => *code,
+                    $(Self::$variant { .. } => ErrorCode::$name,)*
+                }
+            }
+        }
+    };
+}
+
+// Codes are never changed or reused: new variants are added at the end with the next code.
+error_codes! {
+    ObjectErrors => OBJECT_ERRORS = 1,
+    TransferUnownedError => TRANSFER_UNOWNED_ERROR = 2,
+    TransferObjectWithoutPublicTransferError => TRANSFER_OBJECT_WITHOUT_PUBLIC_TRANSFER_ERROR = 3,
+    MoveObjectAsPackage => MOVE_OBJECT_AS_PACKAGE = 4,
+    TransferInsufficientBalance => TRANSFER_INSUFFICIENT_BALANCE = 5,
+    MovePackageAsObject => MOVE_PACKAGE_AS_OBJECT = 6,
+    UnexpectedOwnerType => UNEXPECTED_OWNER_TYPE = 7,
+    UnsupportedSharedObjectError => UNSUPPORTED_SHARED_OBJECT_ERROR = 8,
+    NotSharedObjectError => NOT_SHARED_OBJECT_ERROR = 9,
+    DeleteObjectOwnedObject => DELETE_OBJECT_OWNED_OBJECT = 10,
+    SharedObjectLockNotSetError => SHARED_OBJECT_LOCK_NOT_SET_ERROR = 11,
+    InvalidBatchTransaction => INVALID_BATCH_TRANSACTION = 12,
+    TooManyInputObjects => TOO_MANY_INPUT_OBJECTS = 13,
+    PureArgumentTooLarge => PURE_ARGUMENT_TOO_LARGE = 14,
+    InvalidChildObjectArgument => INVALID_CHILD_OBJECT_ARGUMENT = 15,
+    InvalidSignature => INVALID_SIGNATURE = 16,
+    SenderSigUnbatchable => SENDER_SIG_UNBATCHABLE = 17,
+    IncorrectSigner => INCORRECT_SIGNER = 18,
+    UnknownSigner => UNKNOWN_SIGNER = 19,
+    WrongEpoch => WRONG_EPOCH = 20,
+    CertificateRequiresQuorum => CERTIFICATE_REQUIRES_QUORUM = 21,
+    CertificateSyncError => CERTIFICATE_SYNC_ERROR = 22,
+    UnexpectedSequenceNumber => UNEXPECTED_SEQUENCE_NUMBER = 23,
+    InvalidAuthorityBitmap => INVALID_AUTHORITY_BITMAP = 24,
+    ErrorWhileProcessingTransactionTransaction => ERROR_WHILE_PROCESSING_TRANSACTION_TRANSACTION = 25,
+    ErrorWhileProcessingConfirmationTransaction => ERROR_WHILE_PROCESSING_CONFIRMATION_TRANSACTION = 26,
+    QuorumFailedToExecuteCertificate => QUORUM_FAILED_TO_EXECUTE_CERTIFICATE = 27,
+    ErrorWhileProcessingPublish => ERROR_WHILE_PROCESSING_PUBLISH = 28,
+    ErrorWhileProcessingMoveCall => ERROR_WHILE_PROCESSING_MOVE_CALL = 29,
+    ErrorWhileRequestingInformation => ERROR_WHILE_REQUESTING_INFORMATION = 30,
+    ObjectFetchFailed => OBJECT_FETCH_FAILED = 31,
+    MissingEarlierConfirmations => MISSING_EARLIER_CONFIRMATIONS = 32,
+    InvalidSystemTransaction => INVALID_SYSTEM_TRANSACTION = 33,
+    UnexpectedTransactionIndex => UNEXPECTED_TRANSACTION_INDEX = 34,
+    ConcurrentIteratorError => CONCURRENT_ITERATOR_ERROR = 35,
+    ClosedNotifierError => CLOSED_NOTIFIER_ERROR = 36,
+    CertificateNotfound => CERTIFICATE_NOTFOUND = 37,
+    ParentNotfound => PARENT_NOTFOUND = 38,
+    UnknownSenderAccount => UNKNOWN_SENDER_ACCOUNT = 39,
+    CertificateAuthorityReuse => CERTIFICATE_AUTHORITY_REUSE = 40,
+    InvalidSequenceNumber => INVALID_SEQUENCE_NUMBER = 41,
+    SequenceOverflow => SEQUENCE_OVERFLOW = 42,
+    SequenceUnderflow => SEQUENCE_UNDERFLOW = 43,
+    WrongShard => WRONG_SHARD = 44,
+    InvalidCrossShardUpdate => INVALID_CROSS_SHARD_UPDATE = 45,
+    InvalidAuthenticator => INVALID_AUTHENTICATOR = 46,
+    InvalidAddress => INVALID_ADDRESS = 47,
+    InvalidTransactionDigest => INVALID_TRANSACTION_DIGEST = 48,
+    InvalidObjectDigest => INVALID_OBJECT_DIGEST = 49,
+    InvalidDecoding => INVALID_DECODING = 50,
+    UnexpectedMessage => UNEXPECTED_MESSAGE = 51,
+    DuplicateObjectRefInput => DUPLICATE_OBJECT_REF_INPUT = 52,
+    ClientIoError => CLIENT_IO_ERROR = 53,
+    TransferImmutableError => TRANSFER_IMMUTABLE_ERROR = 54,
+    SharedObjectStartingVersionMismatch => SHARED_OBJECT_STARTING_VERSION_MISMATCH = 55,
+    InvalidSequenceRangeError => INVALID_SEQUENCE_RANGE_ERROR = 56,
+    NoBatchesFoundError => NO_BATCHES_FOUND_ERROR = 57,
+    CannotSendClientMessageError => CANNOT_SEND_CLIENT_MESSAGE_ERROR = 58,
+    SubscriptionItemsDroppedError => SUBSCRIPTION_ITEMS_DROPPED_ERROR = 59,
+    SubscriptionServiceClosed => SUBSCRIPTION_SERVICE_CLOSED = 60,
+    CheckpointingError => CHECKPOINTING_ERROR = 61,
+    InvalidObjectInclusionProof => INVALID_OBJECT_INCLUSION_PROOF = 62,
+    BridgeAttestationError => BRIDGE_ATTESTATION_ERROR = 63,
+    ExecutionDriverError => EXECUTION_DRIVER_ERROR = 64,
+    ModuleLoadFailure => MODULE_LOAD_FAILURE = 65,
+    ModuleVerificationFailure => MODULE_VERIFICATION_FAILURE = 66,
+    ModuleDeserializationFailure => MODULE_DESERIALIZATION_FAILURE = 67,
+    ModulePublishFailure => MODULE_PUBLISH_FAILURE = 68,
+    ModuleBuildFailure => MODULE_BUILD_FAILURE = 69,
+    DependentPackageNotFound => DEPENDENT_PACKAGE_NOT_FOUND = 70,
+    MoveUnitTestFailure => MOVE_UNIT_TEST_FAILURE = 71,
+    FunctionNotFound => FUNCTION_NOT_FOUND = 72,
+    ModuleNotFound => MODULE_NOT_FOUND = 73,
+    InvalidFunctionSignature => INVALID_FUNCTION_SIGNATURE = 74,
+    InvalidNonEntryFunction => INVALID_NON_ENTRY_FUNCTION = 75,
+    TypeError => TYPE_ERROR = 76,
+    AbortedExecution => ABORTED_EXECUTION = 77,
+    InvalidMoveEvent => INVALID_MOVE_EVENT = 78,
+    CircularObjectOwnership => CIRCULAR_OBJECT_OWNERSHIP = 79,
+    InvalidSharedChildUse => INVALID_SHARED_CHILD_USE = 80,
+    EmptyPureArgument => EMPTY_PURE_ARGUMENT = 81,
+    EmptyObjectVectorArgument => EMPTY_OBJECT_VECTOR_ARGUMENT = 82,
+    GasObjectUsedAsInput => GAS_OBJECT_USED_AS_INPUT = 83,
+    InvalidGasObject => INVALID_GAS_OBJECT = 84,
+    GasBudgetTooHigh => GAS_BUDGET_TOO_HIGH = 85,
+    InsufficientGas => INSUFFICIENT_GAS = 86,
+    InvalidTxUpdate => INVALID_TX_UPDATE = 87,
+    ObjectLockAlreadyInitialized => OBJECT_LOCK_ALREADY_INITIALIZED = 88,
+    ObjectLockUninitialized => OBJECT_LOCK_UNINITIALIZED = 89,
+    ObjectLockConflict => OBJECT_LOCK_CONFLICT = 90,
+    ObjectLockedAtFutureEpoch => OBJECT_LOCKED_AT_FUTURE_EPOCH = 91,
+    TransactionNotFound => TRANSACTION_NOT_FOUND = 92,
+    ObjectNotFound => OBJECT_NOT_FOUND = 93,
+    ObjectVersionNotFound => OBJECT_VERSION_NOT_FOUND = 94,
+    ObjectSequenceNumberTooHigh => OBJECT_SEQUENCE_NUMBER_TOO_HIGH = 95,
+    ObjectDeleted => OBJECT_DELETED = 96,
+    BadObjectType => BAD_OBJECT_TYPE = 97,
+    MoveExecutionFailure => MOVE_EXECUTION_FAILURE = 98,
+    ObjectInputArityViolation => OBJECT_INPUT_ARITY_VIOLATION = 99,
+    ExecutionInvariantViolation => EXECUTION_INVARIANT_VIOLATION = 100,
+    AuthorityInformationUnavailable => AUTHORITY_INFORMATION_UNAVAILABLE = 101,
+    AuthorityUpdateFailure => AUTHORITY_UPDATE_FAILURE = 102,
+    ByzantineAuthoritySuspicion => BYZANTINE_AUTHORITY_SUSPICION = 103,
+    PairwiseSyncFailed => PAIRWISE_SYNC_FAILED = 104,
+    StorageError => STORAGE_ERROR = 105,
+    GenericStorageError => GENERIC_STORAGE_ERROR = 106,
+    InvalidChildObjectAccess => INVALID_CHILD_OBJECT_ACCESS = 107,
+    StorageMissingFieldError => STORAGE_MISSING_FIELD_ERROR = 108,
+    StorageCorruptedFieldError => STORAGE_CORRUPTED_FIELD_ERROR = 109,
+    BatchErrorSender => BATCH_ERROR_SENDER = 110,
+    GenericAuthorityError => GENERIC_AUTHORITY_ERROR = 111,
+    EventFailedToDispatch => EVENT_FAILED_TO_DISPATCH = 112,
+    OwnerFailedToSerialize => OWNER_FAILED_TO_SERIALIZE = 113,
+    ExtraFieldFailedToDeserialize => EXTRA_FIELD_FAILED_TO_DESERIALIZE = 114,
+    TransactionOrchestratorLocalExecutionError => TRANSACTION_ORCHESTRATOR_LOCAL_EXECUTION_ERROR = 115,
+    QuorumNotReached => QUORUM_NOT_REACHED = 116,
+    ObjectSerializationError => OBJECT_SERIALIZATION_ERROR = 117,
+    NoEventStore => NO_EVENT_STORE = 118,
+    ConcurrentTransactionError => CONCURRENT_TRANSACTION_ERROR = 119,
+    IncorrectRecipientError => INCORRECT_RECIPIENT_ERROR = 120,
+    TooManyIncorrectAuthorities => TOO_MANY_INCORRECT_AUTHORITIES = 121,
+    InconsistentGatewayResult => INCONSISTENT_GATEWAY_RESULT = 122,
+    GatewayInvalidTxRangeQuery => GATEWAY_INVALID_TX_RANGE_QUERY = 123,
+    GatewayTransactionPrepError => GATEWAY_TRANSACTION_PREP_ERROR = 124,
+    OnlyOneConsensusClientPermitted => ONLY_ONE_CONSENSUS_CLIENT_PERMITTED = 125,
+    ConsensusConnectionBroken => CONSENSUS_CONNECTION_BROKEN = 126,
+    FailedToHearBackFromConsensus => FAILED_TO_HEAR_BACK_FROM_CONSENSUS = 127,
+    HandleConsensusTransactionFailure => HANDLE_CONSENSUS_TRANSACTION_FAILURE = 128,
+    ListenerCapacityExceeded => LISTENER_CAPACITY_EXCEEDED = 129,
+    ConsensusSuiSerializationError => CONSENSUS_SUI_SERIALIZATION_ERROR = 130,
+    NotASharedObjectTransaction => NOT_ASHARED_OBJECT_TRANSACTION = 131,
+    SignatureSeedInvalidLength => SIGNATURE_SEED_INVALID_LENGTH = 132,
+    HkdfError => HKDF_ERROR = 133,
+    SignatureKeyGenError => SIGNATURE_KEY_GEN_ERROR = 134,
+    KeyConversionError => KEY_CONVERSION_ERROR = 135,
+    InvalidPrivateKey => INVALID_PRIVATE_KEY = 136,
+    ValidatorHalted => VALIDATOR_HALTED_AT_EPOCH_END = 137,
+    InconsistentEpochState => INCONSISTENT_EPOCH_STATE = 138,
+    AdvanceEpochError => ADVANCE_EPOCH_ERROR = 139,
+    RpcError => RPC_ERROR = 140,
+    RpcExecuteTransactionError => RPC_EXECUTE_TRANSACTION_ERROR = 141,
+    UnsupportedFeatureError => UNSUPPORTED_FEATURE_ERROR = 142,
+    NodeReadOnly => NODE_READ_ONLY = 143,
+    DeniedPackage => DENIED_PACKAGE = 144,
+    DeniedFunction => DENIED_FUNCTION = 145,
+    QuorumDriverCommunicationError => QUORUM_DRIVER_COMMUNICATION_ERROR = 146,
+    ConflictingRequestId => CONFLICTING_REQUEST_ID = 147,
+    TimeoutError => TIMEOUT_ERROR = 148,
+    ExecutionError => EXECUTION_ERROR = 149,
+    InvalidCommittee => INVALID_COMMITTEE = 150,
+    MissingCommitteeAtEpoch => MISSING_COMMITTEE_AT_EPOCH = 151,
+    FailedToGetAgreedCommitteeFromMajority => FAILED_TO_GET_AGREED_COMMITTEE_FROM_MAJORITY = 152,
+    TooManyTransactionDigests => TOO_MANY_TRANSACTION_DIGESTS = 153,
+    ValidatorOverloaded => VALIDATOR_OVERLOADED = 154,
+    TooManyGasPaymentObjects => TOO_MANY_GAS_PAYMENT_OBJECTS = 155,
+    TransactionExpired => TRANSACTION_EXPIRED = 156,
+    ObjectVersionConflict => OBJECT_VERSION_CONFLICT = 157,
+    InvalidNetworkInfo => INVALID_NETWORK_INFO = 158,
+    ValidatorShuttingDown => VALIDATOR_SHUTTING_DOWN = 159,
+    ObjectWrapped => OBJECT_WRAPPED = 160,
+    RequestIdTooLong => REQUEST_ID_TOO_LONG = 161,
+}
+
 // TODO these are both horribly wrong, categorization needs to be considered
 impl From<PartialVMError> for SuiError {
     fn from(error: PartialVMError) -> Self {
@@ -504,9 +805,84 @@ impl From<SubscriberError> for SuiError {
     }
 }
 
+const ERROR_CODE_METADATA_KEY: &str = "sui-error-code";
+const ERROR_RETRYABLE_METADATA_KEY: &str = "sui-error-retryable";
+const ERROR_CLIENT_FAULT_METADATA_KEY: &str = "sui-error-client-fault";
+const RETRY_AFTER_MS_METADATA_KEY: &str = "sui-retry-after-ms";
+const HALTED_UNTIL_EPOCH_METADATA_KEY: &str = "sui-halted-until-epoch";
+
 impl From<tonic::Status> for SuiError {
     fn from(status: tonic::Status) -> Self {
-        Self::RpcError(status.message().to_owned(), status.code().description())
+        let metadata = |key: &str| {
+            status
+                .metadata()
+                .get(key)
+                .and_then(|value| value.to_str().ok())
+        };
+        // Nodes predating error codes only send the message.
+        let code = metadata(ERROR_CODE_METADATA_KEY).and_then(|code| code.parse().ok());
+        let until_epoch = metadata(HALTED_UNTIL_EPOCH_METADATA_KEY)
+            .and_then(|until_epoch| until_epoch.parse().ok());
+        match (code, until_epoch) {
+            // Halted validators are told apart to wait for the epoch they halted until.
+            (Some(code), Some(until_epoch))
+                if ErrorCode(code) == ErrorCode::VALIDATOR_HALTED_AT_EPOCH_END =>
+            {
+                Self::ValidatorHalted { until_epoch }
+            }
+            (Some(code), _) => Self::RemoteError {
+                code: ErrorCode(code),
+                retryable: metadata(ERROR_RETRYABLE_METADATA_KEY) == Some("true"),
+                client_fault: metadata(ERROR_CLIENT_FAULT_METADATA_KEY) == Some("true"),
+                error: status.message().to_owned(),
+                retry_after_ms: metadata(RETRY_AFTER_MS_METADATA_KEY)
+                    .and_then(|retry_after| retry_after.parse().ok()),
+            },
+            (None, _) => Self::RpcError(status.message().to_owned(), status.code().description()),
+        }
+    }
+}
+
+impl From<SuiError> for tonic::Status {
+    fn from(error: SuiError) -> Self {
+        let code = if error.is_client_fault() {
+            tonic::Code::InvalidArgument
+        } else if matches!(error, SuiError::ValidatorOverloaded { .. }) {
+            tonic::Code::ResourceExhausted
+        } else if error.is_retryable() {
+            tonic::Code::Unavailable
+        } else {
+            tonic::Code::Internal
+        };
+        let mut status = tonic::Status::new(code, error.to_string());
+        let metadata = status.metadata_mut();
+        metadata.insert(ERROR_CODE_METADATA_KEY, error.code().0.into());
+        metadata.insert(
+            ERROR_RETRYABLE_METADATA_KEY,
+            tonic::metadata::MetadataValue::from_static(bool_str(error.is_retryable())),
+        );
+        metadata.insert(
+            ERROR_CLIENT_FAULT_METADATA_KEY,
+            tonic::metadata::MetadataValue::from_static(bool_str(error.is_client_fault())),
+        );
+        if let Some(retry_after) = error.retry_after() {
+            metadata.insert(
+                RETRY_AFTER_MS_METADATA_KEY,
+                (retry_after.as_millis() as u64).into(),
+            );
+        }
+        if let SuiError::ValidatorHalted { until_epoch } = error {
+            metadata.insert(HALTED_UNTIL_EPOCH_METADATA_KEY, until_epoch.into());
+        }
+        status
+    }
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
     }
 }
 
@@ -526,10 +902,205 @@ impl From<&str> for SuiError {
 
 impl SuiError {
     pub fn indicates_epoch_change(&self) -> bool {
+        if matches!(
+            self.code(),
+            ErrorCode::VALIDATOR_HALTED_AT_EPOCH_END | ErrorCode::MISSING_COMMITTEE_AT_EPOCH
+        ) {
+            return true;
+        }
         let err_str = self.to_string();
         err_str.contains(VALIDATOR_HALTED_ERROR_MSG)
             || err_str.contains(MISSING_COMMITTEE_ERROR_MSG)
     }
+
+    /// The epoch whose committee will take the request, if it was rejected because validators
+    /// halted to reconfigure. Errors aggregated from several authorities are only due to the
+    /// reconfiguration if some of them halted and retrying can fix all the others.
+    pub fn halted_until_epoch(&self) -> Option<EpochId> {
+        let aggregated = |errors: Vec<&SuiError>| {
+            if errors.iter().all(|error| error.is_retryable()) {
+                errors
+                    .iter()
+                    .filter_map(|error| error.halted_until_epoch())
+                    .max()
+            } else {
+                None
+            }
+        };
+        match self {
+            Self::ValidatorHalted { until_epoch } => Some(*until_epoch),
+            Self::ObjectErrors { errors }
+            | Self::QuorumFailedToExecuteCertificate { errors }
+            | Self::QuorumNotReached { errors } => aggregated(errors.iter().collect()),
+            Self::TooManyIncorrectAuthorities { errors, .. } => {
+                aggregated(errors.iter().map(|(_, error)| error).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if retried later or on another node, e.g. because
+    /// the node was busy, lagging behind or changing epoch. Errors aggregated from several
+    /// authorities are retryable if all of them are.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RemoteError { retryable, .. } => *retryable,
+            // Failures of the transport, or of nodes predating error codes.
+            Self::RpcError(_, description) => [
+                tonic::Code::Unavailable,
+                tonic::Code::ResourceExhausted,
+                tonic::Code::DeadlineExceeded,
+            ]
+            .iter()
+            .any(|code| code.description() == *description),
+            Self::ObjectErrors { errors }
+            | Self::QuorumFailedToExecuteCertificate { errors }
+            | Self::QuorumNotReached { errors } => {
+                !errors.is_empty() && errors.iter().all(Self::is_retryable)
+            }
+            Self::TooManyIncorrectAuthorities { errors, .. } => {
+                !errors.is_empty() && errors.iter().all(|(_, error)| error.is_retryable())
+            }
+            Self::SharedObjectLockNotSetError
+            | Self::WrongEpoch { .. }
+            | Self::MissingEarlierConfirmations { .. }
+            | Self::ClientIoError { .. }
+            | Self::NoBatchesFoundError
+            | Self::SubscriptionItemsDroppedError(_)
+            | Self::AuthorityInformationUnavailable
+            | Self::ConsensusConnectionBroken(_)
+            | Self::FailedToHearBackFromConsensus(_)
+            | Self::ListenerCapacityExceeded(_)
+            | Self::ValidatorHalted { .. }
+            | Self::NodeReadOnly
+            | Self::ValidatorShuttingDown
+            | Self::QuorumDriverCommunicationError { .. }
+            | Self::TimeoutError
+            | Self::MissingCommitteeAtEpoch(_)
+            | Self::FailedToGetAgreedCommitteeFromMajority { .. }
+            | Self::ValidatorOverloaded { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// How long the node which returned this error asked to wait before sending it more
+    /// requests, if it is overloaded, up to `MAX_RETRY_AFTER`. Errors aggregated from several
+    /// authorities ask to wait for the longest of their hints.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after_hints().into_iter().max()
+    }
+
+    /// The back-off hints of every node which returned this error, up to `MAX_RETRY_AFTER`.
+    pub fn retry_after_hints(&self) -> Vec<Duration> {
+        match self {
+            Self::RemoteError { retry_after_ms, .. }
+            | Self::ValidatorOverloaded { retry_after_ms, .. } => retry_after_ms
+                .map(|ms| Duration::from_millis(ms).min(MAX_RETRY_AFTER))
+                .into_iter()
+                .collect(),
+            Self::ObjectErrors { errors }
+            | Self::QuorumFailedToExecuteCertificate { errors }
+            | Self::QuorumNotReached { errors } => {
+                errors.iter().flat_map(Self::retry_after_hints).collect()
+            }
+            Self::TooManyIncorrectAuthorities { errors, .. } => errors
+                .iter()
+                .flat_map(|(_, error)| error.retry_after_hints())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Whether the request itself is invalid, e.g. badly signed, malformed or spending objects
+    /// the sender does not own, so that retrying it unchanged cannot succeed. Errors aggregated
+    /// from several authorities are client faults if all of them are.
+    pub fn is_client_fault(&self) -> bool {
+        match self {
+            Self::RemoteError { client_fault, .. } => *client_fault,
+            Self::ObjectErrors { errors }
+            | Self::QuorumFailedToExecuteCertificate { errors }
+            | Self::QuorumNotReached { errors } => {
+                !errors.is_empty() && errors.iter().all(Self::is_client_fault)
+            }
+            Self::TooManyIncorrectAuthorities { errors, .. } => {
+                !errors.is_empty() && errors.iter().all(|(_, error)| error.is_client_fault())
+            }
+            Self::TransferUnownedError
+            | Self::TransferObjectWithoutPublicTransferError
+            | Self::MoveObjectAsPackage { .. }
+            | Self::TransferInsufficientBalance { .. }
+            | Self::MovePackageAsObject { .. }
+            | Self::UnexpectedOwnerType
+            | Self::UnsupportedSharedObjectError
+            | Self::NotSharedObjectError
+            | Self::DeleteObjectOwnedObject
+            | Self::InvalidBatchTransaction { .. }
+            | Self::TooManyInputObjects { .. }
+            | Self::PureArgumentTooLarge { .. }
+            | Self::InvalidChildObjectArgument { .. }
+            | Self::InvalidSignature { .. }
+            | Self::SenderSigUnbatchable
+            | Self::IncorrectSigner { .. }
+            | Self::UnknownSigner
+            | Self::CertificateRequiresQuorum
+            | Self::UnexpectedSequenceNumber { .. }
+            | Self::InvalidAuthorityBitmap { .. }
+            | Self::InvalidSystemTransaction
+            | Self::CertificateAuthorityReuse
+            | Self::InvalidSequenceNumber
+            | Self::InvalidAuthenticator
+            | Self::InvalidAddress
+            | Self::InvalidTransactionDigest
+            | Self::InvalidObjectDigest { .. }
+            | Self::InvalidDecoding
+            | Self::DuplicateObjectRefInput
+            | Self::TransferImmutableError
+            | Self::SharedObjectStartingVersionMismatch
+            | Self::InvalidSequenceRangeError
+            | Self::InvalidObjectInclusionProof { .. }
+            | Self::ModuleLoadFailure { .. }
+            | Self::ModuleVerificationFailure { .. }
+            | Self::ModuleDeserializationFailure { .. }
+            | Self::ModulePublishFailure { .. }
+            | Self::DependentPackageNotFound { .. }
+            | Self::FunctionNotFound { .. }
+            | Self::ModuleNotFound { .. }
+            | Self::InvalidFunctionSignature { .. }
+            | Self::InvalidNonEntryFunction { .. }
+            | Self::TypeError { .. }
+            | Self::CircularObjectOwnership
+            | Self::InvalidSharedChildUse { .. }
+            | Self::EmptyPureArgument { .. }
+            | Self::EmptyObjectVectorArgument { .. }
+            | Self::GasObjectUsedAsInput { .. }
+            | Self::InvalidGasObject { .. }
+            | Self::GasBudgetTooHigh { .. }
+            | Self::InsufficientGas { .. }
+            | Self::ObjectLockConflict { .. }
+            | Self::ObjectLockedAtFutureEpoch { .. }
+            | Self::ObjectDeleted { .. }
+            | Self::ObjectWrapped { .. }
+            | Self::BadObjectType { .. }
+            | Self::ObjectInputArityViolation
+            | Self::InvalidChildObjectAccess { .. }
+            | Self::IncorrectRecipientError
+            | Self::GatewayInvalidTxRangeQuery { .. }
+            | Self::GatewayTransactionPrepError { .. }
+            | Self::NotASharedObjectTransaction
+            | Self::SignatureSeedInvalidLength(_)
+            | Self::KeyConversionError(_)
+            | Self::InvalidPrivateKey
+            | Self::UnsupportedFeatureError { .. }
+            | Self::DeniedPackage { .. }
+            | Self::DeniedFunction { .. }
+            | Self::ConflictingRequestId { .. }
+            | Self::RequestIdTooLong { .. }
+            | Self::TooManyTransactionDigests { .. }
+            | Self::TooManyGasPaymentObjects { .. }
+            | Self::TransactionExpired { .. } => true,
+            _ => false,
+        }
+    }
 }
 
 type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -569,6 +1140,11 @@ impl ExecutionError {
     pub fn to_execution_status(&self) -> ExecutionFailureStatus {
         self.kind().clone()
     }
+
+    /// The Move VM error execution failed with, if it failed in the VM.
+    pub fn vm_error(&self) -> Option<&VMError> {
+        self.inner.source.as_ref()?.downcast_ref()
+    }
 }
 
 impl std::fmt::Display for ExecutionError {
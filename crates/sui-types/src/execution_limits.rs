@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limits on what a single transaction can write, checked once it executed successfully. A
+//! transaction exceeding a limit fails with `ExecutionFailureStatus::ExecutionLimitExceeded`
+//! and its changes are rolled back, so pathological transactions cannot bloat the state or the
+//! event store of validators.
+//!
+//! The limits decide the effects of transactions, so all validators must apply the same ones:
+//! they are only changed along with the protocol version, and validators apply the limits of the
+//! protocol version of the epoch, not of the one their build speaks.
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::ObjectID;
+use crate::committee::EpochId;
+
+#[cfg(test)]
+#[path = "unit_tests/execution_limits_tests.rs"]
+mod execution_limits_tests;
+
+/// The limit a transaction exceeded.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
+pub enum ExecutionLimit {
+    /// Number of objects created by the transaction.
+    CreatedObjects,
+    /// Size in bytes of the contents of a Move object written by the transaction.
+    ObjectSize,
+    /// Number of child objects, e.g. dynamic fields, added to or removed from objects by the
+    /// transaction.
+    DynamicFieldChanges,
+    /// Number of events emitted by the transaction.
+    EventCount,
+    /// Size in bytes of the contents of a Move event emitted by the transaction.
+    EventSize,
+}
+
+impl ExecutionLimit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionLimit::CreatedObjects => "created_objects",
+            ExecutionLimit::ObjectSize => "object_size",
+            ExecutionLimit::DynamicFieldChanges => "dynamic_field_changes",
+            ExecutionLimit::EventCount => "event_count",
+            ExecutionLimit::EventSize => "event_size",
+        }
+    }
+}
+
+impl Display for ExecutionLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The limit a transaction exceeded, the value it reached and the maximum allowed.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
+pub struct ExecutionLimitExceeded {
+    pub limit: ExecutionLimit,
+    pub value: u64,
+    pub max: u64,
+    /// The object or, for events, the package emitting the event, for per-item limits.
+    pub object: Option<ObjectID>,
+}
+
+impl Display for ExecutionLimitExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ExecutionLimitExceeded {
+            limit,
+            value,
+            max,
+            object,
+        } = self;
+        write!(f, "Limit {limit} is {max}, the transaction reached {value}")?;
+        if let Some(object) = object {
+            write!(f, " with {object}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct ExecutionLimits {
+    pub max_created_objects: u64,
+    pub max_object_size: u64,
+    pub max_dynamic_field_changes: u64,
+    pub max_event_count: u64,
+    pub max_event_size: u64,
+}
+
+/// The limits of protocol version 1.
+const PROTOCOL_V1_LIMITS: ExecutionLimits = ExecutionLimits {
+    max_created_objects: 2048,
+    max_object_size: 256 * 1024,
+    max_dynamic_field_changes: 1000,
+    max_event_count: 256,
+    max_event_size: 250 * 1024,
+};
+
+/// The limits of each protocol version, as (first protocol version, limits), in increasing
+/// order of versions. Versions without an entry apply the limits of the version before.
+const PROTOCOL_LIMITS: &[(u64, ExecutionLimits)] = &[(1, PROTOCOL_V1_LIMITS)];
+
+/// The protocol version of the epochs, as (first epoch, protocol version), in increasing order.
+/// A protocol version changing the execution of transactions activates in its first epoch on
+/// all validators at once: a build adding one must be rolled out to the validators before then,
+/// and keeps executing transactions as the builds before it until then.
+const PROTOCOL_VERSION_ACTIVATIONS: &[(EpochId, u64)] = &[(0, 1)];
+
+/// The protocol version transactions execute with in `epoch`.
+pub fn epoch_protocol_version(epoch: EpochId) -> u64 {
+    activated(PROTOCOL_VERSION_ACTIVATIONS, epoch)
+}
+
+/// The value of the last entry of `activations` activated at `at`, or of the first entry.
+fn activated<T: Copy>(activations: &[(u64, T)], at: u64) -> T {
+    activations
+        .iter()
+        .rev()
+        .find(|(since, _)| *since <= at)
+        .unwrap_or(&activations[0])
+        .1
+}
+
+impl ExecutionLimits {
+    /// The limits applied by validators executing transactions with protocol `version`.
+    pub fn for_protocol_version(version: u64) -> Self {
+        activated(PROTOCOL_LIMITS, version)
+    }
+
+    /// The limits applied by validators executing transactions in `epoch`.
+    pub fn for_epoch(epoch: EpochId) -> Self {
+        Self::for_protocol_version(epoch_protocol_version(epoch))
+    }
+
+    /// Checks `value` against the `max` of `limit`.
+    pub fn check(
+        limit: ExecutionLimit,
+        value: u64,
+        max: u64,
+        object: Option<ObjectID>,
+    ) -> Result<(), ExecutionLimitExceeded> {
+        if value > max {
+            return Err(ExecutionLimitExceeded {
+                limit,
+                value,
+                max,
+                object,
+            });
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeSet;
+
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use serde_json::Value;
@@ -10,12 +12,17 @@ use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
 use crate::messages::TxCertAndSignedEffects;
 use crate::object::Owner;
+use crate::object_change::ObjectChange;
 use crate::ObjectID;
 
 #[cfg(test)]
 #[path = "unit_tests/event_filter_tests.rs"]
 mod event_filter_tests;
 
+#[cfg(test)]
+#[path = "unit_tests/object_change_filter_tests.rs"]
+mod object_change_filter_tests;
+
 #[derive(Clone, Debug)]
 pub enum EventFilter {
     Package(ObjectID),
@@ -89,6 +96,31 @@ impl Filter<TxCertAndSignedEffects> for TransactionFilter {
     }
 }
 
+#[derive(Clone, Debug)]
+pub enum ObjectChangeFilter {
+    ObjectIds(BTreeSet<ObjectID>),
+    /// Objects owned by the address, directly or as the ID of a parent object.
+    Owner(SuiAddress),
+    StructType(StructTag),
+    MatchAll(Vec<ObjectChangeFilter>),
+    MatchAny(Vec<ObjectChangeFilter>),
+}
+
+impl Filter<ObjectChange> for ObjectChangeFilter {
+    fn matches(&self, item: &ObjectChange) -> bool {
+        match self {
+            ObjectChangeFilter::ObjectIds(ids) => ids.contains(&item.object_ref.0),
+            ObjectChangeFilter::Owner(owner) => matches!(
+                item.owner,
+                Some(Owner::AddressOwner(address) | Owner::ObjectOwner(address)) if &address == owner
+            ),
+            ObjectChangeFilter::StructType(type_) => item.object_type.as_ref() == Some(type_),
+            ObjectChangeFilter::MatchAll(filters) => filters.iter().all(|f| f.matches(item)),
+            ObjectChangeFilter::MatchAny(filters) => filters.iter().any(|f| f.matches(item)),
+        }
+    }
+}
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
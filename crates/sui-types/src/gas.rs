@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     convert::TryFrom,
     ops::{Add, Deref, Mul},
+    sync::Arc,
 };
 use sui_cost_tables::{
     bytecode_tables::{GasStatus, INITIAL_COST_SCHEDULE},
@@ -28,6 +29,8 @@ use sui_cost_tables::{
     units_types::GasUnit,
 };
 
+pub use sui_cost_tables::bytecode_tables::GasObserver;
+
 pub type GasUnits = GasQuantity<GasUnit>;
 pub enum GasPriceUnit {}
 pub enum SuiGasUnit {}
@@ -214,6 +217,11 @@ impl<'a> SuiGasStatus<'a> {
         &mut self.gas_status
     }
 
+    /// Report the gas charged for executing Move code to `observer`.
+    pub fn set_gas_observer(&mut self, observer: Arc<dyn GasObserver>) {
+        self.gas_status.set_observer(observer)
+    }
+
     pub fn charge_vm_gas(&mut self) -> Result<(), ExecutionError> {
         // Disable flat fee for now
         // self.deduct_computation_cost(&VM_FLAT_FEE.to_unit())
@@ -355,21 +363,36 @@ impl<'a> SuiGasStatus<'a> {
     }
 }
 
-/// Check whether the given gas_object and gas_budget is legit:
-/// 1. If the gas object has an address owner.
+/// Check whether the given gas_objects and gas_budget is legit:
+/// 1. If the gas objects have an address owner.
 /// 2. If it's enough to pay the flat minimum transaction fee
 /// 3. If it's less than the max gas budget allowed
-/// 4. If the gas_object actually has enough balance to pay for the budget.
+/// 4. If the gas_objects together have enough balance to pay for the budget.
 pub fn check_gas_balance(
-    gas_object: &Object,
+    gas_objects: &[Object],
     gas_budget: u64,
     gas_price: u64,
     extra_amount: u64,
 ) -> SuiResult {
+    let mut balance = 0u128;
+    for gas_object in gas_objects {
+        ok_or_gas_error!(
+            matches!(gas_object.owner, Owner::AddressOwner(_)),
+            "Gas object must be owned Move object".to_owned()
+        )?;
+        balance += get_gas_balance(gas_object)? as u128;
+    }
+    check_gas_budget(gas_budget)?;
+
+    let total_amount = (gas_budget as u128) * (gas_price as u128) + extra_amount as u128;
     ok_or_gas_error!(
-        matches!(gas_object.owner, Owner::AddressOwner(_)),
-        "Gas object must be owned Move object".to_owned()
-    )?;
+        balance >= total_amount,
+        format!("Gas balance is {balance}, not enough to pay {total_amount} with gas price of {gas_price}")
+    )
+}
+
+/// Check that `gas_budget` is within the limits of the protocol.
+pub fn check_gas_budget(gas_budget: u64) -> SuiResult {
     ok_or_gas_error!(
         gas_budget <= *MAX_GAS_BUDGET,
         format!("Gas budget set too high; maximum is {}", *MAX_GAS_BUDGET)
@@ -380,13 +403,6 @@ pub fn check_gas_balance(
             "Gas budget is {}, smaller than minimum requirement {}",
             gas_budget, *MIN_GAS_BUDGET
         )
-    )?;
-
-    let balance = get_gas_balance(gas_object)?;
-    let total_amount = (gas_budget as u128) * (gas_price as u128) + extra_amount as u128;
-    ok_or_gas_error!(
-        (balance as u128) >= total_amount,
-        format!("Gas balance is {balance}, not enough to pay {total_amount} with gas price of {gas_price}")
     )
 }
 
@@ -22,27 +22,35 @@ pub mod error;
 pub mod balance;
 pub mod base_types;
 pub mod batch;
+pub mod bridge;
 pub mod coin;
 pub mod collection_types;
 pub mod committee;
 pub mod crypto;
+pub mod epoch_accounting;
 pub mod event;
+pub mod execution_limits;
 pub mod gas;
 pub mod gas_coin;
 pub mod id;
 pub mod in_memory_storage;
 pub mod intent;
+pub mod light_client;
 pub mod message_envelope;
 pub mod messages;
 pub mod messages_checkpoint;
 pub mod move_package;
+pub mod network_info;
 pub mod object;
+pub mod object_change;
 pub mod query;
 pub mod signature_seed;
+pub mod state_accumulator;
 pub mod storage;
 pub mod sui_serde;
 pub mod sui_system_state;
 pub mod temporary_store;
+pub mod transaction_checks;
 pub mod waypoint;
 
 pub mod filter;
@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification utilities for clients that follow the chain without running a node, such as
+//! bridges and custody systems. Starting from a trusted genesis committee, a client verifies the
+//! last checkpoint of every epoch to learn the committee of the next one, and can then check any
+//! certified checkpoint of that epoch.
+
+use crate::committee::Committee;
+use crate::error::{SuiError, SuiResult};
+use crate::fp_ensure;
+use crate::messages_checkpoint::CertifiedCheckpointSummary;
+
+#[cfg(test)]
+#[path = "unit_tests/light_client_tests.rs"]
+mod light_client_tests;
+
+/// Verifies that `end_of_epoch_checkpoint` is certified by `old_committee` and announces the
+/// end of its epoch, and returns the committee of the next epoch.
+pub fn verify_committee_transition(
+    old_committee: &Committee,
+    end_of_epoch_checkpoint: &CertifiedCheckpointSummary,
+) -> SuiResult<Committee> {
+    end_of_epoch_checkpoint.verify(old_committee, None)?;
+
+    let summary = &end_of_epoch_checkpoint.summary;
+    let next_committee = summary.next_epoch_committee.as_ref().ok_or_else(|| {
+        SuiError::InvalidCommittee(format!(
+            "checkpoint {} is not the last checkpoint of epoch {}",
+            summary.sequence_number, summary.epoch
+        ))
+    })?;
+    // Honest authorities sign the committee sorted by name, without duplicates. Anything else
+    // would not be the committee they agreed on.
+    fp_ensure!(
+        next_committee.windows(2).all(|pair| pair[0].0 < pair[1].0),
+        SuiError::InvalidCommittee(format!(
+            "committee announced by checkpoint {} is not sorted by authority",
+            summary.sequence_number
+        ))
+    );
+    Committee::new(
+        old_committee.epoch + 1,
+        next_committee.iter().cloned().collect(),
+    )
+}
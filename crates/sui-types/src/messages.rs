@@ -9,6 +9,7 @@ use crate::crypto::{
     SignatureScheme, SuiAuthoritySignature, SuiSignature, SuiSignatureInner, ToFromBytes,
     VerificationObligation,
 };
+use crate::execution_limits::ExecutionLimitExceeded;
 use crate::gas::GasCostSummary;
 use crate::messages_checkpoint::{
     AuthenticatedCheckpoint, CheckpointFragment, CheckpointSequenceNumber,
@@ -46,6 +47,14 @@ use tracing::debug;
 #[path = "unit_tests/messages_tests.rs"]
 mod messages_tests;
 
+/// Maximum number of input objects a single transaction may reference, counting the packages of
+/// its Move calls but not its gas object.
+pub const MAX_INPUT_OBJECTS: usize = 2048;
+/// Maximum size in bytes of a single pure argument to a Move call.
+pub const MAX_PURE_ARGUMENT_SIZE: usize = 16 * 1024;
+/// Maximum number of single transactions allowed in a batch.
+pub const MAX_TX_BATCH_SIZE: usize = 1024;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum CallArg {
     // contains no structs or objects
@@ -364,7 +373,7 @@ pub enum TransactionKind {
     Single(SingleTransactionKind),
     /// A batch of single transactions.
     Batch(Vec<SingleTransactionKind>),
-    // .. more transaction types go here
+    // .. more transaction types go here, encoded from `TransactionDataEncoding::V2` on only
 }
 
 impl TransactionKind {
@@ -459,6 +468,47 @@ impl TransactionKind {
                 | SingleTransactionKind::ChangeEpoch(_) => (),
             },
         }
+        self.limits_check()
+    }
+
+    /// Check the transaction against the size limits enforced by validators.
+    /// Clients can call this before submission to fail fast with a precise error.
+    pub fn limits_check(&self) -> SuiResult {
+        if let Self::Batch(b) = self {
+            fp_ensure!(
+                b.len() <= MAX_TX_BATCH_SIZE,
+                SuiError::InvalidBatchTransaction {
+                    error: format!(
+                        "Batch Transaction contains {} transactions, the maximum is {}",
+                        b.len(),
+                        MAX_TX_BATCH_SIZE
+                    ),
+                }
+            );
+        }
+        for call in self.single_transactions().filter_map(|s| s.move_call()) {
+            for (arg_idx, arg) in call.arguments.iter().enumerate() {
+                if let CallArg::Pure(bytes) = arg {
+                    fp_ensure!(
+                        bytes.len() <= MAX_PURE_ARGUMENT_SIZE,
+                        SuiError::PureArgumentTooLarge {
+                            function: format!("{}::{}", call.module, call.function),
+                            arg_idx,
+                            size: bytes.len(),
+                            max_size: MAX_PURE_ARGUMENT_SIZE,
+                        }
+                    );
+                }
+            }
+        }
+        let num_inputs = self.input_objects()?.len();
+        fp_ensure!(
+            num_inputs <= MAX_INPUT_OBJECTS,
+            SuiError::TooManyInputObjects {
+                num_inputs,
+                max_inputs: MAX_INPUT_OBJECTS,
+            }
+        );
         Ok(())
     }
 }
@@ -482,13 +532,186 @@ impl Display for TransactionKind {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+/// Maximum number of coins a transaction can pay gas with.
+pub const MAX_GAS_PAYMENT_OBJECTS: usize = 256;
+
+/// The last epoch in which validators sign a transaction.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum TransactionExpiration {
+    /// The transaction never expires.
+    None,
+    /// Validators refuse to sign the transaction after this epoch ends.
+    Epoch(EpochId),
+}
+
+impl Default for TransactionExpiration {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl TransactionExpiration {
+    /// Whether the transaction can no longer be signed in `epoch`.
+    pub fn is_expired(&self, epoch: EpochId) -> bool {
+        match self {
+            Self::None => false,
+            Self::Epoch(expiration) => *expiration < epoch,
+        }
+    }
+}
+
+/// The data of a transaction signed by its sender. See [TransactionDataEncoding] for how it is
+/// serialized.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct TransactionData {
     pub kind: TransactionKind,
     sender: SuiAddress,
     gas_payment: ObjectRef,
+    /// More coins paying for gas, merged into `gas_payment` before execution, so that senders
+    /// with many small coins need not merge them in a transaction of their own first.
+    merged_gas_payment: Vec<ObjectRef>,
     pub gas_price: u64,
     pub gas_budget: u64,
+    /// Bounds the epochs in which the transaction can be signed, so that a captured signed
+    /// transaction cannot be submitted long after its sender meant it to run.
+    expiration: TransactionExpiration,
+}
+
+/// The fields of the first version of [TransactionData] following its kind.
+#[derive(Serialize, Deserialize)]
+struct TransactionDataV1 {
+    sender: SuiAddress,
+    gas_payment: ObjectRef,
+    gas_price: u64,
+    gas_budget: u64,
+}
+
+/// [TransactionData] with the fields added since the first version. Also the layout of
+/// [TransactionData] in human-readable formats.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TransactionData")]
+struct TransactionDataV2 {
+    kind: TransactionKind,
+    sender: SuiAddress,
+    gas_payment: ObjectRef,
+    merged_gas_payment: Vec<ObjectRef>,
+    gas_price: u64,
+    gas_budget: u64,
+    expiration: TransactionExpiration,
+}
+
+impl From<TransactionDataV2> for TransactionData {
+    fn from(data: TransactionDataV2) -> Self {
+        Self {
+            kind: data.kind,
+            sender: data.sender,
+            gas_payment: data.gas_payment,
+            merged_gas_payment: data.merged_gas_payment,
+            gas_price: data.gas_price,
+            gas_budget: data.gas_budget,
+            expiration: data.expiration,
+        }
+    }
+}
+
+impl From<TransactionData> for TransactionDataV2 {
+    fn from(data: TransactionData) -> Self {
+        Self {
+            kind: data.kind,
+            sender: data.sender,
+            gas_payment: data.gas_payment,
+            merged_gas_payment: data.merged_gas_payment,
+            gas_price: data.gas_price,
+            gas_budget: data.gas_budget,
+            expiration: data.expiration,
+        }
+    }
+}
+
+/// The versions of the binary encoding of [TransactionData], which senders sign. The first
+/// version predates versioning: it has a variant per kind of transaction, holding the kind
+/// followed by the other fields, so that its variants are laid out exactly as the transactions
+/// were before, and keep their digests and signatures. Each transaction is encoded with the
+/// first version able to carry it, and decoding rejects any other, so that a transaction has a
+/// single encoding.
+#[derive(Deserialize)]
+#[serde(rename = "TransactionData")]
+enum TransactionDataEncoding {
+    V1Single(SingleTransactionKind, TransactionDataV1),
+    V1Batch(Vec<SingleTransactionKind>, TransactionDataV1),
+    V2(TransactionDataV2),
+}
+
+impl Serialize for TransactionData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTupleVariant;
+
+        if serializer.is_human_readable() {
+            return TransactionDataV2::from(self.clone()).serialize(serializer);
+        }
+        if !self.is_first_version() {
+            let data = TransactionDataV2::from(self.clone());
+            return serializer.serialize_newtype_variant("TransactionData", 2, "V2", &data);
+        }
+        let data = TransactionDataV1 {
+            sender: self.sender,
+            gas_payment: self.gas_payment,
+            gas_price: self.gas_price,
+            gas_budget: self.gas_budget,
+        };
+        match &self.kind {
+            TransactionKind::Single(kind) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("TransactionData", 0, "V1Single", 2)?;
+                state.serialize_field(kind)?;
+                state.serialize_field(&data)?;
+                state.end()
+            }
+            TransactionKind::Batch(kinds) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("TransactionData", 1, "V1Batch", 2)?;
+                state.serialize_field(kinds)?;
+                state.serialize_field(&data)?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return TransactionDataV2::deserialize(deserializer).map(Self::from);
+        }
+        let (kind, data) = match TransactionDataEncoding::deserialize(deserializer)? {
+            TransactionDataEncoding::V1Single(kind, data) => (TransactionKind::Single(kind), data),
+            TransactionDataEncoding::V1Batch(kinds, data) => (TransactionKind::Batch(kinds), data),
+            TransactionDataEncoding::V2(data) => {
+                let data = Self::from(data);
+                if data.is_first_version() {
+                    return Err(serde::de::Error::custom(
+                        "Transaction data of the first version encoded with a later one",
+                    ));
+                }
+                return Ok(data);
+            }
+        };
+        Ok(Self {
+            kind,
+            sender: data.sender,
+            gas_payment: data.gas_payment,
+            merged_gas_payment: vec![],
+            gas_price: data.gas_price,
+            gas_budget: data.gas_budget,
+            expiration: TransactionExpiration::None,
+        })
+    }
 }
 
 impl TransactionData {
@@ -504,7 +727,9 @@ impl TransactionData {
             // TODO: Update local-txn-data-serializer.ts if `gas_price` is changed
             gas_price: 1,
             gas_payment,
+            merged_gas_payment: vec![],
             gas_budget,
+            expiration: TransactionExpiration::None,
         }
     }
 
@@ -520,7 +745,9 @@ impl TransactionData {
             sender,
             gas_price,
             gas_payment,
+            merged_gas_payment: vec![],
             gas_budget,
+            expiration: TransactionExpiration::None,
         }
     }
 
@@ -600,6 +827,19 @@ impl TransactionData {
         Self::new(kind, sender, gas_payment, gas_budget)
     }
 
+    /// Also pay gas with the coins `merged_gas_payment`: they are merged into the gas payment
+    /// before execution and deleted.
+    pub fn with_merged_gas_payment(mut self, merged_gas_payment: Vec<ObjectRef>) -> Self {
+        self.merged_gas_payment = merged_gas_payment;
+        self
+    }
+
+    /// Only let validators sign the transaction until `expiration`.
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
     /// Returns the transaction kind as a &str (variant name, no fields)
     pub fn kind_as_str(&self) -> &'static str {
         self.kind.variant_name()
@@ -627,6 +867,26 @@ impl TransactionData {
         &self.gas_payment
     }
 
+    /// The coins merged into the gas payment before execution.
+    pub fn merged_gas_payment(&self) -> &[ObjectRef] {
+        &self.merged_gas_payment
+    }
+
+    pub fn expiration(&self) -> &TransactionExpiration {
+        &self.expiration
+    }
+
+    /// Whether the transaction uses none of the fields added since the first version of
+    /// [TransactionDataEncoding].
+    fn is_first_version(&self) -> bool {
+        self.merged_gas_payment.is_empty() && self.expiration == TransactionExpiration::None
+    }
+
+    /// All the coins paying for gas, starting with the one charged.
+    pub fn gas_coins(&self) -> impl Iterator<Item = &ObjectRef> {
+        std::iter::once(&self.gas_payment).chain(&self.merged_gas_payment)
+    }
+
     pub fn move_calls(&self) -> Vec<&MoveCall> {
         self.kind
             .single_transactions()
@@ -638,9 +898,10 @@ impl TransactionData {
         let mut inputs = self.kind.input_objects()?;
 
         if !self.kind.is_system_tx() {
-            inputs.push(InputObjectKind::ImmOrOwnedMoveObject(
-                *self.gas_payment_object_ref(),
-            ));
+            inputs.extend(
+                self.gas_coins()
+                    .map(|object_ref| InputObjectKind::ImmOrOwnedMoveObject(*object_ref)),
+            );
         }
         Ok(inputs)
     }
@@ -682,6 +943,16 @@ pub struct SenderSignedData {
     pub tx_signature: Signature,
 }
 
+impl SenderSignedData {
+    /// The transaction digest commits to the sender signature as well as the data, so it is
+    /// known as soon as the transaction is signed and before it is submitted. Since signing is
+    /// deterministic for all supported schemes, signing the same data again with the same key
+    /// yields the same digest.
+    pub fn digest(&self) -> TransactionDigest {
+        TransactionDigest::new(sha3_hash(self))
+    }
+}
+
 impl<S> TransactionEnvelope<S> {
     #[allow(dead_code)]
     fn add_sender_sig_to_verification_obligation(
@@ -732,7 +1003,7 @@ impl<S> TransactionEnvelope<S> {
     /// Get the transaction digest and write it to the cache
     pub fn digest(&self) -> &TransactionDigest {
         self.transaction_digest
-            .get_or_init(|| TransactionDigest::new(sha3_hash(&self.signed_data)))
+            .get_or_init(|| self.signed_data.digest())
     }
 
     pub fn input_objects_in_compiled_modules(
@@ -1019,6 +1290,48 @@ pub enum ObjectInfoRequestKind {
     /// we stop storing all historic versions of every object.
     /// No production code should depend on this kind.
     PastObjectInfoDebug(SequenceNumber, Option<ObjectFormatOptions>),
+    /// Request the latest object state, with the given content only.
+    LatestObjectContent(ObjectContentOptions),
+}
+
+/// What to return along with the latest version of an object. Callers which do not read the
+/// layout, lock or parent certificate of the object can leave them out of the response.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum ObjectContentOptions {
+    /// The object, its lock and its parent certificate, without the layout of the object.
+    BcsOnly,
+    /// As BcsOnly, with the layout of the object including its field names only.
+    WithDisplayLayout,
+    /// As BcsOnly, with the layout of the object including the type of each field.
+    WithTypeLayout,
+    /// The object only, e.g. to check its owner, without its lock or parent certificate.
+    WithOwnerOnly,
+}
+
+impl ObjectContentOptions {
+    /// The format of the layout to return, if any.
+    pub fn layout_format(&self) -> Option<ObjectFormatOptions> {
+        match self {
+            Self::BcsOnly | Self::WithOwnerOnly => None,
+            Self::WithDisplayLayout => Some(ObjectFormatOptions::with_fields_only()),
+            Self::WithTypeLayout => Some(ObjectFormatOptions::with_types()),
+        }
+    }
+
+    /// Whether to return the lock and parent certificate of the object.
+    pub fn with_lock_and_certificate(&self) -> bool {
+        !matches!(self, Self::WithOwnerOnly)
+    }
+}
+
+impl From<Option<ObjectFormatOptions>> for ObjectContentOptions {
+    fn from(layout: Option<ObjectFormatOptions>) -> Self {
+        match layout {
+            None => Self::BcsOnly,
+            Some(format) if format.include_types() => Self::WithTypeLayout,
+            Some(_) => Self::WithDisplayLayout,
+        }
+    }
 }
 
 /// A request for information about an object and optionally its
@@ -1048,6 +1361,27 @@ impl ObjectInfoRequest {
             request_kind: ObjectInfoRequestKind::LatestObjectInfo(layout),
         }
     }
+
+    pub fn latest_object_content_request(
+        object_id: ObjectID,
+        content: ObjectContentOptions,
+    ) -> Self {
+        ObjectInfoRequest {
+            object_id,
+            request_kind: ObjectInfoRequestKind::LatestObjectContent(content),
+        }
+    }
+
+    /// The content requested with the latest version of the object, if the request is for the
+    /// latest version.
+    pub fn latest_content(&self) -> Option<ObjectContentOptions> {
+        match &self.request_kind {
+            ObjectInfoRequestKind::LatestObjectInfo(layout) => Some(layout.clone().into()),
+            ObjectInfoRequestKind::LatestObjectContent(content) => Some(*content),
+            ObjectInfoRequestKind::PastObjectInfo(_)
+            | ObjectInfoRequestKind::PastObjectInfoDebug(..) => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
@@ -1117,6 +1451,56 @@ pub struct TransactionInfoResponse {
     pub signed_effects: Option<SignedTransactionEffects>,
 }
 
+/// Maximum number of transactions whose info can be requested at once.
+pub const MAX_TRANSACTIONS_INFO_REQUEST_DIGESTS: usize = 512;
+
+/// Size in bytes above which validators stop adding transactions to a [TransactionsInfoResponse],
+/// to keep it well within the message size limit of the network whatever the size of the
+/// requested transactions.
+pub const MAX_TRANSACTIONS_INFO_RESPONSE_SIZE: usize = 8 * 1024 * 1024;
+
+/// Request for the info of several transactions at once, e.g. to resolve the digests listed in
+/// batches without a round trip per digest.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct TransactionsInfoRequest {
+    pub transaction_digests: Vec<TransactionDigest>,
+}
+
+impl From<Vec<TransactionDigest>> for TransactionsInfoRequest {
+    fn from(transaction_digests: Vec<TransactionDigest>) -> Self {
+        TransactionsInfoRequest {
+            transaction_digests,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionsInfoResponse {
+    // The info of the requested transactions, in the order of the request. Only the first
+    // transactions are included when the info of all of them would be too large: the others are
+    // to be requested again.
+    pub responses: Vec<TransactionInfoResponse>,
+}
+
+/// Request for the effects with the given digest, e.g. listed in a checkpoint, when the digest
+/// of their transaction is not known.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct EffectsInfoRequest {
+    pub effects_digest: TransactionEffectsDigest,
+}
+
+impl From<TransactionEffectsDigest> for EffectsInfoRequest {
+    fn from(effects_digest: TransactionEffectsDigest) -> Self {
+        EffectsInfoRequest { effects_digest }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EffectsInfoResponse {
+    // None if the authority has not executed the transaction.
+    pub signed_effects: Option<SignedTransactionEffects>,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum CallResult {
     Bool(bool),
@@ -1216,6 +1600,12 @@ pub enum ExecutionFailureStatus {
     MoveAbort(ModuleId, u64), // TODO func def + offset?
     VMVerificationOrDeserializationError,
     VMInvariantViolation,
+
+    //
+    // Execution limit errors
+    //
+    /// The writes or events of the transaction exceed one of its `ExecutionLimits`
+    ExecutionLimitExceeded(ExecutionLimitExceeded),
 }
 
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
@@ -1395,6 +1785,9 @@ impl Display for ExecutionFailureStatus {
             ExecutionFailureStatus::VMInvariantViolation => {
                 write!(f, "MOVE VM INVARIANT VIOLATION.")
             }
+            ExecutionFailureStatus::ExecutionLimitExceeded(data) => {
+                write!(f, "Execution Limit Exceeded. {data}.")
+            }
         }
     }
 }
@@ -1525,6 +1918,12 @@ impl From<InvalidSharedByValue> for ExecutionFailureStatus {
     }
 }
 
+impl From<ExecutionLimitExceeded> for ExecutionFailureStatus {
+    fn from(error: ExecutionLimitExceeded) -> Self {
+        Self::ExecutionLimitExceeded(error)
+    }
+}
+
 /// The response from processing a transaction or a certified transaction
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionEffects {
@@ -1818,6 +2217,18 @@ impl InputObjects {
         self.objects.is_empty()
     }
 
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.objects.iter().map(|(_, object)| object)
+    }
+
+    /// Approximate size in bytes of the input objects, as metered for gas.
+    pub fn size_for_gas_metering(&self) -> usize {
+        self.objects
+            .iter()
+            .map(|(_, object)| object.object_size_for_gas_metering())
+            .sum()
+    }
+
     pub fn filter_owned_objects(&self) -> Vec<ObjectRef> {
         let owned_objects: Vec<_> = self
             .objects
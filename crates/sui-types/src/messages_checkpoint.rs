@@ -11,6 +11,7 @@ use crate::crypto::{AuthoritySignInfo, AuthoritySignInfoTrait, AuthorityWeakQuor
 use crate::error::SuiResult;
 use crate::gas::GasCostSummary;
 use crate::messages::CertifiedTransaction;
+use crate::state_accumulator::StateDigest;
 use crate::waypoint::{Waypoint, WaypointDiff};
 use crate::{
     base_types::AuthorityName,
@@ -94,6 +95,14 @@ impl CheckpointRequest {
         }
     }
 
+    /// Create a request for the checkpoint which includes the transaction `digests`
+    pub fn containing_transaction(digests: ExecutionDigests, detail: bool) -> CheckpointRequest {
+        CheckpointRequest {
+            request_type: CheckpointRequestType::TransactionCheckpoint(digests),
+            detail,
+        }
+    }
+
     pub fn authenticated(seq: Option<CheckpointSequenceNumber>, detail: bool) -> CheckpointRequest {
         CheckpointRequest {
             request_type: CheckpointRequestType::AuthenticatedCheckpoint(seq),
@@ -110,6 +119,8 @@ pub enum CheckpointRequestType {
     AuthenticatedCheckpoint(Option<CheckpointSequenceNumber>),
     /// Request the current checkpoint proposal.
     CheckpointProposal,
+    /// Request the stored checkpoint which includes the given transaction, if any.
+    TransactionCheckpoint(ExecutionDigests),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -191,6 +202,9 @@ pub struct CheckpointSummary {
     /// TODO: If desired, we could also commit to the previous last checkpoint cert so that
     /// they form a hash chain.
     pub next_epoch_committee: Option<Vec<(AuthorityName, StakeUnit)>>,
+    /// Commitment to the live object set after all transactions of this checkpoint are
+    /// executed. See `state_accumulator` for verification utilities.
+    pub state_digest: StateDigest,
 }
 
 impl CheckpointSummary {
@@ -201,6 +215,7 @@ impl CheckpointSummary {
         previous_digest: Option<CheckpointDigest>,
         gas_cost_summary: GasCostSummary,
         next_epoch_committee: Option<Committee>,
+        state_digest: StateDigest,
     ) -> CheckpointSummary {
         let mut waypoint = Box::new(Waypoint::default());
         transactions.iter().for_each(|tx| {
@@ -216,6 +231,7 @@ impl CheckpointSummary {
             previous_digest,
             gas_cost_summary,
             next_epoch_committee: next_epoch_committee.map(|c| c.voting_rights),
+            state_digest,
         }
     }
 
@@ -269,6 +285,7 @@ impl SignedCheckpointSummary {
         previous_digest: Option<CheckpointDigest>,
         gas_cost_summary: GasCostSummary,
         next_epoch_committee: Option<Committee>,
+        state_digest: StateDigest,
     ) -> SignedCheckpointSummary {
         let checkpoint = CheckpointSummary::new(
             epoch,
@@ -277,6 +294,7 @@ impl SignedCheckpointSummary {
             previous_digest,
             gas_cost_summary,
             next_epoch_committee,
+            state_digest,
         );
         SignedCheckpointSummary::new_from_summary(checkpoint, authority, signer)
     }
@@ -396,13 +414,53 @@ impl CertifiedCheckpointSummary {
         obligation.verify_all()?;
 
         if let Some(contents) = contents {
-            let content_digest = contents.digest();
+            self.verify_contents(contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies several certificates of the epoch of `committee`, and their contents if
+    /// provided, with a single batched verification of all their signatures, which is much
+    /// cheaper than verifying them one by one. When this fails, the certificates need to be
+    /// verified one by one to find out which are invalid.
+    pub fn verify_batch<'a>(
+        checkpoints: impl IntoIterator<
+            Item = (
+                &'a CertifiedCheckpointSummary,
+                Option<&'a CheckpointContents>,
+            ),
+        >,
+        committee: &Committee,
+    ) -> SuiResult {
+        let mut obligation = VerificationObligation::default();
+        for (checkpoint, contents) in checkpoints {
             fp_ensure!(
-                content_digest == self.summary.content_digest,
-                SuiError::GenericAuthorityError{error:format!("Checkpoint contents digest mismatch: summary={:?}, content digest = {:?}, transactions {}", self.summary, content_digest, contents.size())}
+                checkpoint.summary.epoch == committee.epoch,
+                SuiError::from("Epoch in the summary doesn't match with the committee")
             );
+            let idx = obligation.add_message(&checkpoint.summary);
+            checkpoint.auth_signature.add_to_verification_obligation(
+                committee,
+                &mut obligation,
+                idx,
+            )?;
+            if let Some(contents) = contents {
+                checkpoint.verify_contents(contents)?;
+            }
+        }
+        if obligation.messages.is_empty() {
+            return Ok(());
         }
+        obligation.verify_all()
+    }
 
+    fn verify_contents(&self, contents: &CheckpointContents) -> SuiResult {
+        let content_digest = contents.digest();
+        fp_ensure!(
+            content_digest == self.summary.content_digest,
+            SuiError::GenericAuthorityError{error:format!("Checkpoint contents digest mismatch: summary={:?}, content digest = {:?}, transactions {}", self.summary, content_digest, contents.size())}
+        );
         Ok(())
     }
 }
@@ -747,6 +805,7 @@ mod tests {
                     None,
                     GasCostSummary::default(),
                     None,
+                    StateDigest::default(),
                 )
             })
             .collect();
@@ -784,6 +843,7 @@ mod tests {
                     None,
                     GasCostSummary::default(),
                     None,
+                    StateDigest::default(),
                 )
             })
             .collect();
@@ -812,6 +872,7 @@ mod tests {
                     None,
                     GasCostSummary::default(),
                     None,
+                    StateDigest::default(),
                 )
             })
             .collect();
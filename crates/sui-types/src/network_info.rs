@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Network info bundles let clients check that the node they connect to serves the network they
+//! expect, e.g. that a wallet was not redirected to an imposter full node at the DNS level. The
+//! validators of the current epoch sign what clients need to know about the network, and clients
+//! check the signatures against the committee of their trusted genesis, or of a bundle they
+//! verified earlier, following the committee changes announced by end-of-epoch checkpoints.
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::AuthorityName;
+use crate::committee::{Committee, EpochId, StakeUnit};
+use crate::crypto::{
+    sha3_hash, AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature,
+    AuthorityStrongQuorumSignInfo, SuiAuthoritySignature,
+};
+use crate::error::{SuiError, SuiResult};
+use crate::fp_ensure;
+use crate::light_client::verify_committee_transition;
+use crate::messages_checkpoint::CertifiedCheckpointSummary;
+
+#[cfg(test)]
+#[path = "unit_tests/network_info_tests.rs"]
+mod network_info_tests;
+
+/// Version of the [NetworkInfo] layout.
+pub const NETWORK_INFO_VERSION: u8 = 1;
+
+pub type NetworkInfoDigest = [u8; 32];
+
+/// What a client needs to trust about a network to talk to it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub version: u8,
+    /// The SHA3 digest of the genesis the network started from.
+    pub genesis_digest: [u8; 32],
+    pub epoch: EpochId,
+    /// The stake of each validator of the epoch, sorted by authority.
+    pub committee: Vec<(AuthorityName, StakeUnit)>,
+    /// The protocol version transactions execute with in the epoch.
+    pub protocol_version: u64,
+    /// The JSON-RPC endpoints the validators vouch for, sorted.
+    pub rpc_endpoints: Vec<String>,
+}
+
+impl NetworkInfo {
+    pub fn new(
+        genesis_digest: [u8; 32],
+        committee: &Committee,
+        protocol_version: u64,
+        mut rpc_endpoints: Vec<String>,
+    ) -> Self {
+        // Validators configured with the same endpoints in a different order must sign the
+        // same message.
+        rpc_endpoints.sort();
+        rpc_endpoints.dedup();
+        Self {
+            version: NETWORK_INFO_VERSION,
+            genesis_digest,
+            epoch: committee.epoch,
+            committee: committee.voting_rights.clone(),
+            protocol_version,
+            rpc_endpoints,
+        }
+    }
+
+    pub fn digest(&self) -> NetworkInfoDigest {
+        sha3_hash(self)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkInfoEnvelope<S> {
+    pub info: NetworkInfo,
+    pub auth_signature: S,
+}
+
+/// Network info signed by a single validator.
+pub type SignedNetworkInfo = NetworkInfoEnvelope<AuthoritySignInfo>;
+
+impl SignedNetworkInfo {
+    pub fn new(
+        info: NetworkInfo,
+        authority: AuthorityName,
+        signer: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let signature = AuthoritySignature::new(&info, signer);
+        Self {
+            auth_signature: AuthoritySignInfo {
+                epoch: info.epoch,
+                authority,
+                signature,
+            },
+            info,
+        }
+    }
+
+    pub fn authority(&self) -> &AuthorityName {
+        &self.auth_signature.authority
+    }
+
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.info, committee)
+    }
+}
+
+/// Network info signed by a quorum of the validators of its epoch.
+pub type CertifiedNetworkInfo = NetworkInfoEnvelope<AuthorityStrongQuorumSignInfo>;
+
+impl CertifiedNetworkInfo {
+    pub fn new(
+        info: NetworkInfo,
+        signatures: Vec<(AuthorityName, AuthoritySignature)>,
+        committee: &Committee,
+    ) -> SuiResult<Self> {
+        Ok(Self {
+            info,
+            auth_signature: AuthorityStrongQuorumSignInfo::new_with_signatures(
+                signatures, committee,
+            )?,
+        })
+    }
+
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.info, committee)
+    }
+}
+
+/// What a client trusts about a network: the digest of its genesis and the committee of an
+/// epoch.
+#[derive(Clone, Debug)]
+pub struct TrustedNetwork {
+    pub genesis_digest: [u8; 32],
+    pub committee: Committee,
+}
+
+/// Certified network info, with the last checkpoint of every epoch from the epoch a client
+/// trusts the committee of, so that the client can learn the committees up to the epoch of the
+/// info.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkInfoBundle {
+    pub info: CertifiedNetworkInfo,
+    /// Certified end-of-epoch checkpoints, in epoch order.
+    pub epoch_changes: Vec<CertifiedCheckpointSummary>,
+}
+
+impl NetworkInfoBundle {
+    /// Checks that the bundle describes the network of `trusted`, at its epoch or a later one,
+    /// and returns what can be trusted about the network from then on.
+    pub fn verify(&self, trusted: &TrustedNetwork) -> SuiResult<TrustedNetwork> {
+        let info = &self.info.info;
+        fp_ensure!(
+            info.genesis_digest == trusted.genesis_digest,
+            SuiError::InvalidNetworkInfo {
+                error: "the network was started from another genesis".to_string(),
+            }
+        );
+        fp_ensure!(
+            info.epoch >= trusted.committee.epoch,
+            SuiError::InvalidNetworkInfo {
+                error: format!(
+                    "the network info of epoch {} is older than the trusted epoch {}",
+                    info.epoch, trusted.committee.epoch
+                ),
+            }
+        );
+
+        let trusted_epoch = trusted.committee.epoch;
+        let mut committee = trusted.committee.clone();
+        for checkpoint in self
+            .epoch_changes
+            .iter()
+            .skip_while(|checkpoint| checkpoint.summary.epoch < trusted_epoch)
+            .take_while(|checkpoint| checkpoint.summary.epoch < info.epoch)
+        {
+            committee = verify_committee_transition(&committee, checkpoint)?;
+        }
+        fp_ensure!(
+            committee.epoch == info.epoch,
+            SuiError::InvalidNetworkInfo {
+                error: format!(
+                    "missing the end-of-epoch checkpoint of epoch {}",
+                    committee.epoch
+                ),
+            }
+        );
+        fp_ensure!(
+            committee.voting_rights == info.committee,
+            SuiError::InvalidNetworkInfo {
+                error: format!("the committee of epoch {} does not match", info.epoch),
+            }
+        );
+        self.info.verify(&committee)?;
+
+        Ok(TrustedNetwork {
+            genesis_digest: info.genesis_digest,
+            committee,
+        })
+    }
+
+    /// What a client trusts about the network once it verified this bundle, e.g. when loading a
+    /// bundle it saved after verifying it.
+    pub fn trusted_network(&self) -> SuiResult<TrustedNetwork> {
+        let info = &self.info.info;
+        Ok(TrustedNetwork {
+            genesis_digest: info.genesis_digest,
+            committee: Committee::new(info.epoch, info.committee.iter().cloned().collect())?,
+        })
+    }
+}
+
+/// Ask a validator to sign the network info of its current epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkInfoRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkInfoResponse {
+    pub info: SignedNetworkInfo,
+}
@@ -635,6 +635,26 @@ impl ObjectRead {
     }
 }
 
+impl ObjectFormatOptions {
+    /// Include the type of each object as well as its fields.
+    pub fn with_types() -> Self {
+        ObjectFormatOptions {
+            include_types: true,
+        }
+    }
+
+    /// Include field names only.
+    pub fn with_fields_only() -> Self {
+        ObjectFormatOptions {
+            include_types: false,
+        }
+    }
+
+    pub fn include_types(&self) -> bool {
+        self.include_types
+    }
+}
+
 impl Default for ObjectFormatOptions {
     fn default() -> Self {
         ObjectFormatOptions {
@@ -667,6 +687,8 @@ pub enum PastObjectRead {
     ObjectNotExists(ObjectID),
     /// The object is found to be deleted with this version
     ObjectDeleted(ObjectRef),
+    /// The object is found to be wrapped into another object with this version
+    ObjectWrapped(ObjectRef),
     /// The object exists and is found with this version
     VersionFound(ObjectRef, Object, Option<MoveStructLayout>),
     /// The object exists but not found with this version
@@ -684,6 +706,7 @@ impl PastObjectRead {
     pub fn into_object(self) -> Result<Object, SuiError> {
         match self {
             Self::ObjectDeleted(oref) => Err(SuiError::ObjectDeleted { object_ref: oref }),
+            Self::ObjectWrapped(oref) => Err(SuiError::ObjectWrapped { object_ref: oref }),
             Self::ObjectNotExists(id) => Err(SuiError::ObjectNotFound { object_id: id }),
             Self::VersionFound(_, o, _) => Ok(o),
             Self::VersionNotFound(object_id, version) => {
@@ -708,6 +731,9 @@ impl Display for PastObjectRead {
             Self::ObjectDeleted(oref) => {
                 write!(f, "PastObjectRead::ObjectDeleted ({:?})", oref)
             }
+            Self::ObjectWrapped(oref) => {
+                write!(f, "PastObjectRead::ObjectWrapped ({:?})", oref)
+            }
             Self::ObjectNotExists(id) => {
                 write!(f, "PastObjectRead::ObjectNotExists ({:?})", id)
             }
@@ -0,0 +1,109 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Object changes of transactions: the objects changed by the effects of a transaction with
+//! their versions before and after it, and the changes of checkpointed transactions streamed to
+//! subscribers who track objects without polling them.
+
+use move_core_types::language_storage::StructTag;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::{ObjectID, ObjectRef, SequenceNumber, TransactionDigest};
+use crate::messages::TransactionEffects;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
+use crate::object::Owner;
+
+/// How a transaction changed an object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    Unwrapped,
+    Deleted,
+    Wrapped,
+}
+
+impl ObjectChangeKind {
+    /// Whether the object no longer exists as such after the change.
+    pub fn is_removal(&self) -> bool {
+        matches!(self, ObjectChangeKind::Wrapped | ObjectChangeKind::Deleted)
+    }
+}
+
+/// An object changed by a transaction, with the versions it had before and after it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChangedObject {
+    pub kind: ObjectChangeKind,
+    /// The reference the effects give the object.
+    pub object_ref: ObjectRef,
+    /// The owner the effects give the object, None for wrapped and deleted objects.
+    pub owner: Option<Owner>,
+    /// None if the object did not exist as such before the transaction.
+    pub before: Option<SequenceNumber>,
+    /// None if the object does not exist as such after the transaction.
+    pub after: Option<SequenceNumber>,
+}
+
+impl ChangedObject {
+    pub fn object_id(&self) -> ObjectID {
+        self.object_ref.0
+    }
+}
+
+/// Lists the objects changed by `effects`, in the order of the effects. Versions are sequential,
+/// so the version an object had before the transaction is the one preceding the version the
+/// effects give it.
+pub fn changed_objects(effects: &TransactionEffects) -> Vec<ChangedObject> {
+    let written = |refs: &[(ObjectRef, Owner)], kind, existed_before| {
+        refs.iter()
+            .map(|(object_ref, owner)| ChangedObject {
+                kind,
+                object_ref: *object_ref,
+                owner: Some(*owner),
+                before: if existed_before {
+                    object_ref.1.decrement().ok()
+                } else {
+                    None
+                },
+                after: Some(object_ref.1),
+            })
+            .collect::<Vec<_>>()
+    };
+    let removed = |refs: &[ObjectRef], kind| {
+        refs.iter()
+            .map(|object_ref| ChangedObject {
+                kind,
+                object_ref: *object_ref,
+                owner: None,
+                before: object_ref.1.decrement().ok(),
+                after: None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut changes = written(&effects.created, ObjectChangeKind::Created, false);
+    changes.extend(written(&effects.mutated, ObjectChangeKind::Mutated, true));
+    changes.extend(written(
+        &effects.unwrapped,
+        ObjectChangeKind::Unwrapped,
+        false,
+    ));
+    changes.extend(removed(&effects.wrapped, ObjectChangeKind::Wrapped));
+    changes.extend(removed(&effects.deleted, ObjectChangeKind::Deleted));
+    changes
+}
+
+/// A change made to an object by a transaction included in a checkpoint.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObjectChange {
+    /// The checkpoint including the transaction, which subscribers resume from.
+    pub checkpoint: CheckpointSequenceNumber,
+    pub tx_digest: TransactionDigest,
+    pub kind: ObjectChangeKind,
+    pub object_ref: ObjectRef,
+    /// The owner of the object after the change, or before it for wrapped and deleted objects.
+    /// None if the node pruned that version.
+    pub owner: Option<Owner>,
+    /// The type of the object, None for packages or if the node pruned the version.
+    pub object_type: Option<StructTag>,
+}
@@ -33,3 +33,16 @@ pub enum Ordering {
     // Descending order (latest transaction first), transactions are causal ordered.
     Descending,
 }
+
+/// How an address took part in a transaction.
+#[derive(
+    Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum AddressActivityRole {
+    // The address signed the transaction.
+    Sender,
+    // The address owns an object written by the transaction, and did not send it.
+    Recipient,
+    // The address owns the gas object of the transaction, and did not send it.
+    GasPayer,
+}
@@ -0,0 +1,167 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+use crate::base_types::{ExecutionDigests, ObjectRef};
+use crate::committee::Committee;
+use crate::error::{SuiError, SuiResult};
+use crate::fp_ensure;
+use crate::messages::TransactionEffects;
+use crate::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary,
+};
+use crate::waypoint::{Accumulator, IntoPoint};
+
+#[cfg(test)]
+#[path = "unit_tests/state_accumulator_tests.rs"]
+mod state_accumulator_tests;
+
+/// The digest of a [StateAccumulator], as committed to in checkpoint summaries.
+pub type StateDigest = [u8; 32];
+
+impl IntoPoint for ObjectRef {
+    fn into_point(&self) -> RistrettoPoint {
+        let mut data = Vec::with_capacity(64);
+        data.extend_from_slice(self.0.as_ref());
+        data.extend_from_slice(&self.1.value().to_le_bytes());
+        data.extend_from_slice(self.2.as_ref());
+        RistrettoPoint::hash_from_bytes::<Sha512>(&data)
+    }
+}
+
+/*
+    A StateAccumulator commits to the set of live object references
+    (the latest version of every object that is neither deleted nor
+    wrapped). Since the underlying accumulator is homomorphic, the
+    state after a checkpoint is obtained from the state before it by
+    adding the references written by the checkpoint transactions and
+    removing the references they replaced, without enumerating the
+    full object set.
+*/
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateAccumulator(Accumulator);
+
+impl StateAccumulator {
+    /// Build the accumulator of a full set of live object references.
+    pub fn from_objects<'a>(objects: impl IntoIterator<Item = &'a ObjectRef>) -> Self {
+        let mut accumulator = Accumulator::default();
+        accumulator.insert_all(objects);
+        Self(accumulator)
+    }
+
+    /// Recover the accumulator committed to by a state digest.
+    pub fn from_digest(digest: &StateDigest) -> SuiResult<Self> {
+        Accumulator::from_digest(digest)
+            .map(Self)
+            .ok_or_else(|| SuiError::CheckpointingError {
+                error: format!("Invalid state digest: {}", hex::encode(digest)),
+            })
+    }
+
+    pub fn digest(&self) -> StateDigest {
+        self.0.digest()
+    }
+
+    /// Apply the changes made to the live object set: `added` are references that became
+    /// live, `removed` are references that stopped being live.
+    pub fn apply_changes<'a>(
+        &mut self,
+        added: impl IntoIterator<Item = &'a ObjectRef>,
+        removed: impl IntoIterator<Item = &'a ObjectRef>,
+    ) {
+        self.0.insert_all(added);
+        self.0.remove_all(removed);
+    }
+}
+
+/// Check that `objects` is exactly the live object set committed to by the checkpoint
+/// summary. Used to validate state snapshots against a certified checkpoint.
+pub fn verify_live_object_set<'a>(
+    summary: &CheckpointSummary,
+    objects: impl IntoIterator<Item = &'a ObjectRef>,
+) -> SuiResult {
+    let digest = StateAccumulator::from_objects(objects).digest();
+    fp_ensure!(
+        digest == summary.state_digest,
+        SuiError::CheckpointingError {
+            error: format!(
+                "Object set does not match the state digest of checkpoint {}",
+                summary.sequence_number
+            ),
+        }
+    );
+    Ok(())
+}
+
+/// Check that applying the given changes to the state of checkpoint `from` results in the
+/// state of checkpoint `to`. This allows a client holding a verified object set to follow
+/// the chain without downloading the full state again.
+pub fn verify_state_transition<'a>(
+    from: &CheckpointSummary,
+    to: &CheckpointSummary,
+    added: impl IntoIterator<Item = &'a ObjectRef>,
+    removed: impl IntoIterator<Item = &'a ObjectRef>,
+) -> SuiResult {
+    let mut state = StateAccumulator::from_digest(&from.state_digest)?;
+    state.apply_changes(added, removed);
+    fp_ensure!(
+        state.digest() == to.state_digest,
+        SuiError::CheckpointingError {
+            error: format!(
+                "Object changes do not lead from the state of checkpoint {} to checkpoint {}",
+                from.sequence_number, to.sequence_number
+            ),
+        }
+    );
+    Ok(())
+}
+
+/// Proof that an object version was written by a transaction included in a certified
+/// checkpoint. It only requires the committee of the checkpoint epoch to check, so light clients
+/// and bridges do not need to hold any object state. The proof is not checked against the state
+/// digest of the checkpoint: it does not prove that the version is still live, as later
+/// transactions of the checkpoint, or of later ones, may have written or deleted the object.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectInclusionProof {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// Effects of the transaction that wrote the object version.
+    pub effects: TransactionEffects,
+}
+
+impl ObjectInclusionProof {
+    /// Check the proof for `object_ref` against the committee of the checkpoint epoch.
+    pub fn verify(&self, object_ref: &ObjectRef, committee: &Committee) -> SuiResult {
+        self.checkpoint
+            .verify(committee, Some(&self.contents))
+            .map_err(|e| SuiError::InvalidObjectInclusionProof {
+                error: format!("invalid checkpoint certificate: {e}"),
+            })?;
+
+        let digests = ExecutionDigests::new(self.effects.transaction_digest, self.effects.digest());
+        fp_ensure!(
+            self.contents.iter().any(|d| d == &digests),
+            SuiError::InvalidObjectInclusionProof {
+                error: format!(
+                    "transaction {:?} is not part of checkpoint {}",
+                    digests.transaction, self.checkpoint.summary.sequence_number
+                ),
+            }
+        );
+        fp_ensure!(
+            self.effects
+                .all_mutated()
+                .any(|(written, _, _)| written == object_ref),
+            SuiError::InvalidObjectInclusionProof {
+                error: format!(
+                    "object {:?} is not written by transaction {:?}",
+                    object_ref, digests.transaction
+                ),
+            }
+        );
+        Ok(())
+    }
+}
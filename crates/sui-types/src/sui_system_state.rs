@@ -46,6 +46,7 @@ pub struct ValidatorMetadata {
     pub proof_of_possession_bytes: Vec<u8>,
     pub name: Vec<u8>,
     pub net_address: Vec<u8>,
+    pub backup_net_addresses: Vec<Vec<u8>>,
     pub next_epoch_stake: u64,
     pub next_epoch_delegation: u64,
     pub next_epoch_gas_price: u64,
@@ -76,13 +77,14 @@ pub struct Validator {
 impl Validator {
     pub fn to_current_epoch_committee_with_net_addresses(
         &self,
-    ) -> (AuthorityName, StakeUnit, Vec<u8>) {
+    ) -> (AuthorityName, StakeUnit, Vec<u8>, Vec<Vec<u8>>) {
         (
             // TODO: Make sure we are actually verifying this on-chain.
             AuthorityPublicKeyBytes::from_bytes(self.metadata.pubkey_bytes.as_ref())
                 .expect("Validity of public key bytes should be verified on-chain"),
             self.stake_amount + self.delegation_staking_pool.epoch_starting_sui_balance,
             self.metadata.net_address.clone(),
+            self.metadata.backup_net_addresses.clone(),
         )
     }
 }
@@ -157,14 +159,45 @@ impl SuiSystemState {
         .unwrap()
     }
 
+    /// The committee of the next epoch, with the network addresses its validators registered.
+    pub fn get_next_epoch_committee_with_net_addresses(&self) -> CommitteeWithNetAddresses {
+        CommitteeWithNetAddresses {
+            committee: self.get_next_epoch_committee(),
+            net_addresses: self
+                .validators
+                .next_epoch_validators
+                .iter()
+                .map(|validator| {
+                    (
+                        validator.to_next_epoch_validator_and_stake_pair().0,
+                        validator.net_address.clone(),
+                    )
+                })
+                .collect(),
+            backup_net_addresses: self
+                .validators
+                .next_epoch_validators
+                .iter()
+                .map(|validator| {
+                    (
+                        validator.to_next_epoch_validator_and_stake_pair().0,
+                        validator.backup_net_addresses.clone(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
     pub fn get_current_epoch_committee(&self) -> CommitteeWithNetAddresses {
         let mut voting_rights = BTreeMap::new();
         let mut net_addresses = BTreeMap::new();
+        let mut backup_net_addresses = BTreeMap::new();
         for validator in &self.validators.active_validators {
-            let (name, voting_stake, net_address) =
+            let (name, voting_stake, net_address, backups) =
                 validator.to_current_epoch_committee_with_net_addresses();
             voting_rights.insert(name, voting_stake);
             net_addresses.insert(name, net_address);
+            backup_net_addresses.insert(name, backups);
         }
         CommitteeWithNetAddresses {
             committee: Committee::new(self.epoch, voting_rights)
@@ -172,6 +205,7 @@ impl SuiSystemState {
                 // TODO: Make sure we actually verify it.
                 .unwrap(),
             net_addresses,
+            backup_net_addresses,
         }
     }
 }
@@ -7,6 +7,7 @@ use crate::{
     },
     error::{ExecutionError, SuiError, SuiResult},
     event::Event,
+    execution_limits::{ExecutionLimit, ExecutionLimitExceeded, ExecutionLimits},
     fp_bail,
     gas::{GasCostSummary, SuiGasStatus},
     messages::{ExecutionStatus, InputObjects, TransactionEffects},
@@ -383,6 +384,92 @@ impl<S> TemporaryStore<S> {
         self.events.push(event)
     }
 
+    /// Checks the objects written and deleted and the events emitted so far against `limits`.
+    pub fn check_execution_limits(&self, limits: &ExecutionLimits) -> Result<(), ExecutionError> {
+        self.check_limits(limits).map_err(|exceeded| {
+            ExecutionError::new_with_source(exceeded.into(), exceeded.to_string())
+        })
+    }
+
+    fn check_limits(&self, limits: &ExecutionLimits) -> Result<(), ExecutionLimitExceeded> {
+        let created = self
+            ._written
+            .values()
+            .filter(|(_, kind)| *kind == WriteKind::Create)
+            .count();
+        ExecutionLimits::check(
+            ExecutionLimit::CreatedObjects,
+            created as u64,
+            limits.max_created_objects,
+            None,
+        )?;
+
+        for (id, (object, _)) in &self._written {
+            if let Some(move_object) = object.data.try_as_move() {
+                ExecutionLimits::check(
+                    ExecutionLimit::ObjectSize,
+                    move_object.contents().len() as u64,
+                    limits.max_object_size,
+                    Some(*id),
+                )?;
+            }
+        }
+
+        // Children added to an object: created, unwrapped or transferred to it.
+        let added = self
+            ._written
+            .iter()
+            .filter(|(id, (object, kind))| match object.owner {
+                Owner::ObjectOwner(_) => {
+                    *kind != WriteKind::Mutate
+                        || self
+                            .input_objects
+                            .get(id)
+                            .map_or(false, |input| input.owner != object.owner)
+                }
+                _ => false,
+            })
+            .count();
+        // Children removed from an object: objects deleted or wrapped which are not inputs were
+        // loaded as children, unless they were wrapped in the first place.
+        let removed = self
+            .deleted
+            .iter()
+            .filter(|(id, (_, kind))| {
+                *kind != DeleteKind::UnwrapThenDelete && !self.input_objects.contains_key(id)
+            })
+            .count();
+        ExecutionLimits::check(
+            ExecutionLimit::DynamicFieldChanges,
+            (added + removed) as u64,
+            limits.max_dynamic_field_changes,
+            None,
+        )?;
+
+        ExecutionLimits::check(
+            ExecutionLimit::EventCount,
+            self.events.len() as u64,
+            limits.max_event_count,
+            None,
+        )?;
+        for event in &self.events {
+            if let Event::MoveEvent {
+                package_id,
+                contents,
+                ..
+            } = event
+            {
+                ExecutionLimits::check(
+                    ExecutionLimit::EventSize,
+                    contents.len() as u64,
+                    limits.max_event_size,
+                    Some(*package_id),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn read_object(&self, id: &ObjectID) -> Option<&Object> {
         // there should be no read after delete
         debug_assert!(self.deleted.get(id).is_none());
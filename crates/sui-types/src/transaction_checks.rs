@@ -0,0 +1,128 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks of a transaction that need nothing but its data and its gas object. Clients run them
+//! before signing and validators before locking the input objects, so a malformed transaction
+//! is rejected with the same error wherever it is caught.
+
+use crate::committee::EpochId;
+use crate::error::{SuiError, SuiResult};
+use crate::fp_ensure;
+use crate::gas;
+use crate::gas_coin::GasCoin;
+use crate::messages::{
+    CallArg, InputObjectKind, TransactionData, TransactionExpiration, MAX_GAS_PAYMENT_OBJECTS,
+};
+use crate::object::{Object, Owner};
+use std::collections::HashSet;
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_checks_tests.rs"]
+mod transaction_checks_tests;
+
+/// Check the invariants of `data` that do not depend on the state of its input objects: the
+/// transaction kind is valid and within size limits, the gas budget is within protocol limits,
+/// pure arguments are well-formed and the gas objects are distinct and not also used as inputs.
+pub fn check_transaction_data(data: &TransactionData) -> SuiResult {
+    data.kind.validity_check()?;
+    if data.kind.is_system_tx() {
+        return Ok(());
+    }
+    gas::check_gas_budget(data.gas_budget)?;
+
+    for call in data
+        .kind
+        .single_transactions()
+        .filter_map(|s| s.move_call())
+    {
+        for (arg_idx, arg) in call.arguments.iter().enumerate() {
+            // Every BCS-encoded value takes at least one byte.
+            fp_ensure!(
+                !matches!(arg, CallArg::Pure(bytes) if bytes.is_empty()),
+                SuiError::EmptyPureArgument {
+                    function: format!("{}::{}", call.module, call.function),
+                    arg_idx,
+                }
+            );
+            fp_ensure!(
+                !matches!(arg, CallArg::ObjVec(objects) if objects.is_empty()),
+                SuiError::EmptyObjectVectorArgument {
+                    function: format!("{}::{}", call.module, call.function),
+                    arg_idx,
+                }
+            );
+        }
+    }
+
+    let num_gas_objects = data.merged_gas_payment().len() + 1;
+    fp_ensure!(
+        num_gas_objects <= MAX_GAS_PAYMENT_OBJECTS,
+        SuiError::TooManyGasPaymentObjects {
+            num_objects: num_gas_objects,
+            max_objects: MAX_GAS_PAYMENT_OBJECTS,
+        }
+    );
+    let mut gas_object_ids = HashSet::new();
+    for (object_id, _, _) in data.gas_coins() {
+        fp_ensure!(
+            gas_object_ids.insert(*object_id),
+            SuiError::InvalidGasObject {
+                object_id: *object_id,
+                error: "used more than once in the gas payment".to_string(),
+            }
+        );
+    }
+
+    // Duplicates within a single transaction kind are rejected by `validity_check`. The gas
+    // objects are always mutated or deleted, so they cannot be used by any of them either.
+    for input in data.kind.input_objects()? {
+        if let InputObjectKind::ImmOrOwnedMoveObject((object_id, _, _)) = input {
+            fp_ensure!(
+                !gas_object_ids.contains(&object_id),
+                SuiError::GasObjectUsedAsInput { object_id }
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Check that `gas_object`, fetched for the gas payment of `data`, is a SUI coin owned by the
+/// sender.
+pub fn check_gas_object(data: &TransactionData, gas_object: &Object) -> SuiResult {
+    let object_id = gas_object.id();
+    fp_ensure!(
+        data.gas_coins().any(|(id, _, _)| *id == object_id),
+        SuiError::InvalidGasObject {
+            object_id,
+            error: "not the gas payment of the transaction".to_string(),
+        }
+    );
+    fp_ensure!(
+        gas_object.owner == Owner::AddressOwner(data.signer()),
+        SuiError::InvalidGasObject {
+            object_id,
+            error: format!("not owned by the sender {}", data.signer()),
+        }
+    );
+    fp_ensure!(
+        gas_object.type_() == Some(&GasCoin::type_()),
+        SuiError::InvalidGasObject {
+            object_id,
+            error: "not a SUI coin".to_string(),
+        }
+    );
+    Ok(())
+}
+
+/// Check that `data` can still be signed in `epoch`.
+pub fn check_transaction_expiration(data: &TransactionData, epoch: EpochId) -> SuiResult {
+    match data.expiration() {
+        TransactionExpiration::Epoch(expiration_epoch) if data.expiration().is_expired(epoch) => {
+            Err(SuiError::TransactionExpired {
+                expiration_epoch: *expiration_epoch,
+                current_epoch: epoch,
+            })
+        }
+        _ => Ok(()),
+    }
+}
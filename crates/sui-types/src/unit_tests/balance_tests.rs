@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::*;
+use crate::base_types::{ObjectDigest, SuiAddress, TransactionDigest};
+use crate::gas_coin::GasCoin;
+
+#[test]
+fn test_compute_balance_changes() {
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let gas_id = ObjectID::random();
+    let created_id = ObjectID::random();
+    let wrapped_id = ObjectID::random();
+    let before = SequenceNumber::from_u64(4);
+    let after = SequenceNumber::from_u64(5);
+
+    let effects = TransactionEffects {
+        transaction_digest: TransactionDigest::random(),
+        created: vec![(
+            (created_id, after, ObjectDigest::random()),
+            Owner::AddressOwner(recipient),
+        )],
+        mutated: vec![(
+            (gas_id, after, ObjectDigest::random()),
+            Owner::AddressOwner(sender),
+        )],
+        wrapped: vec![(wrapped_id, after, ObjectDigest::OBJECT_DIGEST_WRAPPED)],
+        ..Default::default()
+    };
+    let objects: HashMap<_, _> = [
+        ((gas_id, before), (sender, 100)),
+        ((gas_id, after), (sender, 60)),
+        ((created_id, after), (recipient, 30)),
+        ((wrapped_id, before), (recipient, 10)),
+    ]
+    .into_iter()
+    .map(|(key, (owner, value))| {
+        (
+            key,
+            Object::with_id_owner_gas_for_testing(key.0, owner, value),
+        )
+    })
+    .collect();
+
+    let changes = BalanceChange::compute(&effects, |id, version| {
+        Ok(objects.get(&(*id, version)).cloned())
+    })
+    .unwrap();
+    let sui = GasCoin::type_().type_params[0].clone();
+    let mut expected = vec![
+        BalanceChange {
+            owner: Owner::AddressOwner(sender),
+            coin_type: sui.clone(),
+            amount: -40,
+        },
+        BalanceChange {
+            owner: Owner::AddressOwner(recipient),
+            coin_type: sui,
+            amount: 20,
+        },
+    ];
+    expected.sort_by_key(|change| change.owner);
+    assert_eq!(changes, expected);
+
+    // Versions missing from the store do not count.
+    let changes = BalanceChange::compute(&effects, |_, _| Ok(None)).unwrap();
+    assert!(changes.is_empty());
+}
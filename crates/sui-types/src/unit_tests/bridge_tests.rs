@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use move_core_types::ident_str;
+use move_core_types::identifier::Identifier;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::base_types::ObjectID;
+use crate::messages::ExecutionFailureStatus;
+use crate::utils::make_committee_key;
+
+fn bridge_effects() -> TransactionEffects {
+    TransactionEffects {
+        transaction_digest: TransactionDigest::random(),
+        events: vec![
+            Event::Publish {
+                sender: SuiAddress::default(),
+                package_id: ObjectID::random(),
+            },
+            Event::MoveEvent {
+                package_id: ObjectID::random(),
+                transaction_module: Identifier::from(ident_str!("bridge")),
+                sender: SuiAddress::default(),
+                type_: StructTag {
+                    address: ObjectID::random().into(),
+                    module: Identifier::from(ident_str!("bridge")),
+                    name: Identifier::from(ident_str!("Deposit")),
+                    type_params: vec![],
+                },
+                contents: vec![1, 2, 3],
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bridge_message_from_effects() {
+    let effects = bridge_effects();
+    let message = BridgeMessage::from_effects(&effects, 1).unwrap();
+    assert_eq!(message.version, BRIDGE_MESSAGE_VERSION);
+    assert_eq!(message.tx_digest, effects.transaction_digest);
+    assert_eq!(message.contents, vec![1, 2, 3]);
+
+    // Only Move events can be bridged.
+    assert!(BridgeMessage::from_effects(&effects, 0).is_err());
+    assert!(BridgeMessage::from_effects(&effects, 2).is_err());
+
+    let mut failed = effects;
+    failed.status = ExecutionStatus::new_failure(ExecutionFailureStatus::InsufficientGas);
+    assert!(BridgeMessage::from_effects(&failed, 1).is_err());
+}
+
+#[test]
+fn test_certified_bridge_attestation() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let (_, other_committee) = make_committee_key(&mut rng);
+    let message = BridgeMessage::from_effects(&bridge_effects(), 1).unwrap();
+
+    let signed: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            SignedBridgeAttestation::new(committee.epoch, message.clone(), k.public().into(), k)
+        })
+        .collect();
+    for attestation in &signed {
+        attestation.verify(&committee).unwrap();
+        assert!(attestation.verify(&other_committee).is_err());
+    }
+
+    let signatures: Vec<_> = signed
+        .iter()
+        .map(|s| (*s.authority(), s.auth_signature.signature.clone()))
+        .collect();
+    // A single signature is not a quorum.
+    let partial =
+        CertifiedBridgeAttestation::new(message.clone(), signatures[..1].to_vec(), &committee)
+            .unwrap();
+    assert!(partial.verify(&committee).is_err());
+    let certified =
+        CertifiedBridgeAttestation::new(message.clone(), signatures, &committee).unwrap();
+    certified.verify(&committee).unwrap();
+
+    let mut tampered = certified;
+    tampered.message.contents = vec![4, 5, 6];
+    assert!(tampered.verify(&committee).is_err());
+}
@@ -0,0 +1,151 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::balance::{Balance, Supply};
+use crate::collection_types::VecMap;
+use crate::id::UID;
+use crate::sui_system_state::{
+    PendingDelegationEntry, StakingPool, SystemParameters, ValidatorMetadata, ValidatorSet,
+};
+use crate::SUI_SYSTEM_STATE_OBJECT_ID;
+use move_core_types::account_address::AccountAddress;
+
+fn validator(
+    address: u8,
+    name: &str,
+    stake_amount: u64,
+    pending_stake: u64,
+    pool_balance: u64,
+    pending_delegations: Vec<u64>,
+) -> Validator {
+    let sui_address = AccountAddress::new([address; AccountAddress::LENGTH]);
+    Validator {
+        metadata: ValidatorMetadata {
+            sui_address,
+            pubkey_bytes: vec![],
+            network_pubkey_bytes: vec![],
+            proof_of_possession_bytes: vec![],
+            name: name.as_bytes().to_vec(),
+            net_address: vec![],
+            backup_net_addresses: vec![],
+            next_epoch_stake: 0,
+            next_epoch_delegation: 0,
+            next_epoch_gas_price: 1,
+        },
+        stake_amount,
+        pending_stake,
+        pending_withdraw: 0,
+        gas_price: 1,
+        delegation_staking_pool: StakingPool {
+            validator_address: sui_address,
+            starting_epoch: 0,
+            epoch_starting_sui_balance: pool_balance,
+            epoch_starting_delegation_token_supply: 0,
+            sui_balance: pool_balance,
+            rewards_pool: Balance::new(0),
+            delegation_token_supply: Supply { value: 0 },
+            pending_delegations: pending_delegations
+                .into_iter()
+                .map(|sui_amount| PendingDelegationEntry {
+                    delegator: AccountAddress::ZERO,
+                    sui_amount,
+                })
+                .collect(),
+        },
+    }
+}
+
+fn system_state(epoch: EpochId, storage_fund: u64, validators: Vec<Validator>) -> SuiSystemState {
+    SuiSystemState {
+        info: UID::new(SUI_SYSTEM_STATE_OBJECT_ID),
+        epoch,
+        validators: ValidatorSet {
+            validator_stake: 0,
+            delegation_stake: 0,
+            quorum_stake_threshold: 0,
+            active_validators: validators,
+            pending_validators: vec![],
+            pending_removals: vec![],
+            next_epoch_validators: vec![],
+        },
+        treasury_cap: Supply { value: 0 },
+        storage_fund: Balance::new(storage_fund),
+        parameters: SystemParameters {
+            min_validator_stake: 1,
+            max_validator_candidate_count: 100,
+            storage_gas_price: 1,
+        },
+        reference_gas_price: 1,
+        validator_report_records: VecMap { contents: vec![] },
+    }
+}
+
+#[test]
+fn test_epoch_accounting_report() {
+    let change_epoch = ChangeEpoch {
+        epoch: 4,
+        storage_charge: 300,
+        computation_charge: 100,
+        storage_rebate: 50,
+    };
+    let before = system_state(
+        3,
+        1000,
+        vec![
+            validator(1, "alpha", 500, 20, 200, vec![30]),
+            validator(2, "beta, \"b\"", 300, 0, 0, vec![]),
+            validator(3, "gamma", 100, 0, 0, vec![]),
+        ],
+    );
+    // Pending stake of the epoch is applied, and rewards become the new pending stake.
+    let after = system_state(
+        4,
+        1252,
+        vec![
+            validator(1, "alpha", 520, 40, 250, vec![]),
+            validator(2, "beta, \"b\"", 300, 8, 0, vec![]),
+        ],
+    );
+
+    let report = EpochAccountingReport::new(&change_epoch, &before, &after);
+    assert_eq!(report.epoch, 3);
+    assert_eq!(report.storage_charge, 300);
+    assert_eq!(report.computation_charge, 100);
+    assert_eq!(report.storage_rebate, 50);
+    assert_eq!(report.storage_fund_before, 1000);
+    assert_eq!(report.storage_fund_after, 1252);
+    assert_eq!(report.validators.len(), 3);
+
+    let alpha = &report.validators[0];
+    assert_eq!(alpha.name, "alpha");
+    assert_eq!((alpha.stake_before, alpha.stake_after), (500, 520));
+    assert_eq!(alpha.validator_reward, 40);
+    assert_eq!(
+        (alpha.delegation_pool_before, alpha.delegation_pool_after),
+        (200, 250)
+    );
+    // New delegations are not part of the reward.
+    assert_eq!(alpha.delegator_reward, 20);
+
+    assert_eq!(report.validators[1].validator_reward, 8);
+    assert_eq!(report.validators[1].delegator_reward, 0);
+
+    // A validator that left the active set has nothing after the epoch.
+    let gamma = &report.validators[2];
+    assert_eq!((gamma.stake_before, gamma.stake_after), (100, 0));
+    assert_eq!(gamma.validator_reward, 0);
+
+    let csv = report.to_csv();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], CSV_HEADER);
+    assert_eq!(
+        lines[1],
+        format!(
+            "3,{},\"alpha\",500,520,40,200,250,20",
+            SuiAddress::from(AccountAddress::new([1; AccountAddress::LENGTH]))
+        )
+    );
+    assert!(lines[2].contains(",\"beta, \"\"b\"\"\",300,300,8,0,0,0"));
+}
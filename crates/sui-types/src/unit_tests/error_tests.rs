@@ -0,0 +1,131 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_error_codes_are_stable() {
+    assert_eq!(
+        SuiError::ObjectErrors { errors: vec![] }.code(),
+        ErrorCode(1)
+    );
+    assert_eq!(
+        SuiError::TransactionNotFound {
+            digest: TransactionDigest::random()
+        }
+        .code(),
+        ErrorCode::TRANSACTION_NOT_FOUND
+    );
+    assert_eq!(ErrorCode::TRANSACTION_NOT_FOUND, ErrorCode(92));
+    assert_eq!(
+        SuiError::ValidatorHalted { until_epoch: 1 }.code(),
+        ErrorCode::VALIDATOR_HALTED_AT_EPOCH_END
+    );
+}
+
+#[test]
+fn test_error_classification() {
+    assert!(SuiError::TimeoutError.is_retryable());
+    assert!(!SuiError::TimeoutError.is_client_fault());
+    assert!(SuiError::UnknownSigner.is_client_fault());
+    assert!(!SuiError::UnknownSigner.is_retryable());
+    assert!(!SuiError::ExecutionInvariantViolation.is_retryable());
+    assert!(!SuiError::ExecutionInvariantViolation.is_client_fault());
+    assert!(SuiError::ValidatorShuttingDown.is_retryable());
+    assert!(!SuiError::ValidatorShuttingDown.indicates_epoch_change());
+
+    let retryable = SuiError::QuorumNotReached {
+        errors: vec![
+            SuiError::TimeoutError,
+            SuiError::ValidatorHalted { until_epoch: 1 },
+        ],
+    };
+    assert!(retryable.is_retryable());
+    assert_eq!(retryable.halted_until_epoch(), Some(1));
+    let mixed = SuiError::QuorumNotReached {
+        errors: vec![SuiError::TimeoutError, SuiError::UnknownSigner],
+    };
+    assert!(!mixed.is_retryable());
+    assert!(!mixed.is_client_fault());
+    assert_eq!(mixed.halted_until_epoch(), None);
+    let halted_and_invalid = SuiError::QuorumNotReached {
+        errors: vec![
+            SuiError::ValidatorHalted { until_epoch: 1 },
+            SuiError::UnknownSigner,
+        ],
+    };
+    assert_eq!(halted_and_invalid.halted_until_epoch(), None);
+    assert!(!SuiError::QuorumNotReached { errors: vec![] }.is_retryable());
+}
+
+#[test]
+fn test_error_over_grpc() {
+    let error = SuiError::DeniedPackage {
+        package: ObjectID::random(),
+    };
+    let status = tonic::Status::from(error.clone());
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    let received = SuiError::from(status);
+    assert_eq!(received.code(), error.code());
+    assert!(received.is_client_fault());
+    assert!(!received.is_retryable());
+    assert_eq!(received.to_string(), error.to_string());
+
+    let halted = SuiError::ValidatorHalted { until_epoch: 3 };
+    let received = SuiError::from(tonic::Status::from(halted.clone()));
+    assert_eq!(received, halted);
+    assert_eq!(received.halted_until_epoch(), Some(3));
+    assert!(received.is_retryable());
+    assert!(received.indicates_epoch_change());
+
+    // Statuses without a code are received as before.
+    let received = SuiError::from(tonic::Status::internal("boom"));
+    assert!(matches!(received, SuiError::RpcError(..)));
+    assert_eq!(received.code(), ErrorCode::RPC_ERROR);
+    assert!(!received.is_retryable());
+    assert!(SuiError::from(tonic::Status::unavailable("overloaded")).is_retryable());
+}
+
+#[test]
+fn test_retry_after_over_grpc() {
+    let error = SuiError::ValidatorOverloaded {
+        reason: "too many requests".to_string(),
+        retry_after_ms: Some(1500),
+    };
+    let status = tonic::Status::from(error.clone());
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    let received = SuiError::from(status);
+    assert_eq!(received.code(), ErrorCode::VALIDATOR_OVERLOADED);
+    assert!(received.is_retryable());
+    assert_eq!(received.retry_after(), Some(Duration::from_millis(1500)));
+
+    // Errors aggregated from several authorities wait for the longest hint.
+    let aggregated = SuiError::QuorumNotReached {
+        errors: vec![
+            SuiError::TimeoutError,
+            received,
+            SuiError::ValidatorOverloaded {
+                reason: "consensus backlog".to_string(),
+                retry_after_ms: Some(500),
+            },
+        ],
+    };
+    assert_eq!(aggregated.retry_after(), Some(Duration::from_millis(1500)));
+    assert_eq!(aggregated.retry_after_hints().len(), 2);
+
+    // Nodes cannot ask to back off for longer than the limit.
+    let overloaded = SuiError::ValidatorOverloaded {
+        reason: "too many requests".to_string(),
+        retry_after_ms: Some(u64::MAX),
+    };
+    assert_eq!(overloaded.retry_after(), Some(MAX_RETRY_AFTER));
+
+    // Overloaded validators need not send a hint.
+    let received = SuiError::from(tonic::Status::from(SuiError::ValidatorOverloaded {
+        reason: "too many requests".to_string(),
+        retry_after_ms: None,
+    }));
+    assert!(received.is_retryable());
+    assert_eq!(received.retry_after(), None);
+    assert_eq!(SuiError::TimeoutError.retry_after(), None);
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::identifier::Identifier;
+
+use crate::base_types::{SequenceNumber, SuiAddress};
+use crate::event::Event;
+use crate::execution_limits::{
+    epoch_protocol_version, ExecutionLimit, ExecutionLimitExceeded, ExecutionLimits,
+};
+use crate::gas_coin::GasCoin;
+use crate::messages::ExecutionFailureStatus;
+use crate::object::{Object, Owner};
+use crate::storage::{DeleteKind, WriteKind};
+use crate::temporary_store::empty_for_testing;
+use crate::ObjectID;
+
+const LIMITS: ExecutionLimits = ExecutionLimits {
+    max_created_objects: 2,
+    max_object_size: 1024,
+    max_dynamic_field_changes: 1,
+    max_event_count: 1,
+    max_event_size: 8,
+};
+
+fn exceeded(
+    limit: ExecutionLimit,
+    value: u64,
+    max: u64,
+    object: Option<ObjectID>,
+) -> ExecutionFailureStatus {
+    ExecutionLimitExceeded {
+        limit,
+        value,
+        max,
+        object,
+    }
+    .into()
+}
+
+fn move_event(package_id: ObjectID, size: usize) -> Event {
+    Event::MoveEvent {
+        package_id,
+        transaction_module: Identifier::new("m").unwrap(),
+        sender: SuiAddress::random_for_testing_only(),
+        type_: GasCoin::type_(),
+        contents: vec![0; size],
+    }
+}
+
+#[test]
+fn test_created_objects_limit() {
+    let owner = SuiAddress::random_for_testing_only();
+    let mut store = empty_for_testing();
+    for _ in 0..2 {
+        store.write_object(Object::with_owner_for_testing(owner), WriteKind::Create);
+    }
+    store.check_execution_limits(&LIMITS).unwrap();
+
+    store.write_object(Object::with_owner_for_testing(owner), WriteKind::Create);
+    let error = store.check_execution_limits(&LIMITS).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &exceeded(ExecutionLimit::CreatedObjects, 3, 2, None)
+    );
+}
+
+#[test]
+fn test_dynamic_field_changes_limit() {
+    let parent = ObjectID::random();
+    let mut store = empty_for_testing();
+    let mut child = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+    child.owner = Owner::ObjectOwner(parent.into());
+    store.write_object(child, WriteKind::Create);
+    // Deleting an object which was wrapped does not remove a child.
+    store.delete_object(
+        &ObjectID::random(),
+        SequenceNumber::from_u64(1),
+        DeleteKind::UnwrapThenDelete,
+    );
+    store.check_execution_limits(&LIMITS).unwrap();
+
+    // Objects deleted which are not inputs were loaded as children.
+    store.delete_object(
+        &ObjectID::random(),
+        SequenceNumber::from_u64(1),
+        DeleteKind::Normal,
+    );
+    let error = store.check_execution_limits(&LIMITS).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &exceeded(ExecutionLimit::DynamicFieldChanges, 2, 1, None)
+    );
+}
+
+#[test]
+fn test_event_limits() {
+    let package = ObjectID::random();
+    let mut store = empty_for_testing();
+    store.log_event(move_event(package, 9));
+    let error = store.check_execution_limits(&LIMITS).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &exceeded(ExecutionLimit::EventSize, 9, 8, Some(package))
+    );
+
+    store.reset();
+    store.log_event(move_event(package, 8));
+    store.check_execution_limits(&LIMITS).unwrap();
+    store.log_event(move_event(package, 8));
+    let error = store.check_execution_limits(&LIMITS).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &exceeded(ExecutionLimit::EventCount, 2, 1, None)
+    );
+}
+
+#[test]
+fn test_object_size_limit() {
+    let mut store = empty_for_testing();
+    let object = Object::with_owner_for_testing(SuiAddress::random_for_testing_only());
+    let id = object.id();
+    // A coin holds its id and its balance.
+    let size = (ObjectID::LENGTH + 8) as u64;
+    assert_eq!(
+        object.data.try_as_move().unwrap().contents().len() as u64,
+        size
+    );
+    store.write_object(object, WriteKind::Mutate);
+    let limits = ExecutionLimits {
+        max_object_size: 8,
+        ..LIMITS
+    };
+    let error = store.check_execution_limits(&limits).unwrap_err();
+    assert_eq!(
+        error.kind(),
+        &exceeded(ExecutionLimit::ObjectSize, size, 8, Some(id))
+    );
+}
+
+#[test]
+fn test_protocol_limits() {
+    // Every epoch executes with a protocol version which has limits, the ones of its version.
+    for epoch in [0, 1, 1000] {
+        let version = epoch_protocol_version(epoch);
+        assert!(version >= 1);
+        assert_eq!(
+            ExecutionLimits::for_epoch(epoch),
+            ExecutionLimits::for_protocol_version(version)
+        );
+    }
+    assert_eq!(
+        ExecutionLimits::for_protocol_version(0),
+        ExecutionLimits::for_protocol_version(1)
+    );
+    assert_eq!(
+        ExecutionLimits::for_protocol_version(1).max_created_objects,
+        2048
+    );
+}
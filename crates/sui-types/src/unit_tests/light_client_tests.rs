@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::crypto::AuthorityKeyPair;
+use crate::gas::GasCostSummary;
+use crate::messages_checkpoint::{CheckpointContents, SignedCheckpointSummary};
+use crate::state_accumulator::StateDigest;
+use crate::utils::make_committee_key;
+
+fn certify(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    sequence_number: u64,
+    next_epoch_committee: Option<Committee>,
+) -> CertifiedCheckpointSummary {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([].into_iter());
+    let signed_checkpoints: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            SignedCheckpointSummary::new(
+                committee.epoch,
+                sequence_number,
+                k.public().into(),
+                k,
+                &contents,
+                None,
+                GasCostSummary::default(),
+                next_epoch_committee.clone(),
+                StateDigest::default(),
+            )
+        })
+        .collect();
+    CertifiedCheckpointSummary::aggregate(signed_checkpoints, committee).unwrap()
+}
+
+#[test]
+fn test_verify_committee_transition() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (genesis_keys, genesis_committee) = make_committee_key(&mut rng);
+    let (next_keys, next_committee) = make_committee_key(&mut rng);
+
+    let end_of_epoch = certify(
+        &genesis_keys,
+        &genesis_committee,
+        10,
+        Some(next_committee.clone()),
+    );
+    let committee = verify_committee_transition(&genesis_committee, &end_of_epoch).unwrap();
+    assert_eq!(committee.epoch, 1);
+    assert_eq!(committee.voting_rights, next_committee.voting_rights);
+
+    // The new committee certifies the checkpoints of the next epoch.
+    certify(&next_keys, &committee, 11, None)
+        .verify(&committee, None)
+        .unwrap();
+
+    // A checkpoint in the middle of the epoch does not change the committee.
+    let checkpoint = certify(&genesis_keys, &genesis_committee, 5, None);
+    assert!(verify_committee_transition(&genesis_committee, &checkpoint).is_err());
+
+    // The end of epoch checkpoint must be certified by the old committee.
+    assert!(verify_committee_transition(&next_committee, &end_of_epoch).is_err());
+}
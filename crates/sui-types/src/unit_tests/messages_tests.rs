@@ -16,6 +16,7 @@ use crate::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPub
 use crate::messages_checkpoint::CheckpointContents;
 use crate::messages_checkpoint::CheckpointSummary;
 use crate::object::Owner;
+use crate::state_accumulator::StateDigest;
 
 use super::*;
 fn random_object_ref() -> ObjectRef {
@@ -569,6 +570,7 @@ fn test_user_signature_committed_in_checkpoints() {
         None,
         effects_a.gas_used,
         None,
+        StateDigest::default(),
     );
     let checkpoint_summary_b = CheckpointSummary::new(
         0,
@@ -579,6 +581,7 @@ fn test_user_signature_committed_in_checkpoints() {
         None,
         effects_b.gas_used,
         None,
+        StateDigest::default(),
     );
 
     assert_ne!(checkpoint_summary_a.digest(), checkpoint_summary_b.digest());
@@ -674,3 +677,190 @@ fn verify_sender_signature_correctly_with_flag() {
         .verify(&transaction.signed_data, &committee)
         .is_err());
 }
+
+#[test]
+fn test_transaction_limits_check() {
+    let call = |arguments| {
+        SingleTransactionKind::Call(MoveCall {
+            package: random_object_ref(),
+            module: Identifier::new("m").unwrap(),
+            function: Identifier::new("f").unwrap(),
+            type_arguments: vec![],
+            arguments,
+        })
+    };
+
+    let ok = TransactionKind::Single(call(vec![CallArg::Pure(vec![0; MAX_PURE_ARGUMENT_SIZE])]));
+    assert!(ok.validity_check().is_ok());
+
+    let large_pure = TransactionKind::Single(call(vec![
+        CallArg::Pure(vec![]),
+        CallArg::Pure(vec![0; MAX_PURE_ARGUMENT_SIZE + 1]),
+    ]));
+    assert!(matches!(
+        large_pure.validity_check(),
+        Err(SuiError::PureArgumentTooLarge { arg_idx: 1, .. })
+    ));
+
+    // The package of the call counts as an input object.
+    let objects = |count: usize| {
+        (0..count)
+            .map(|_| ObjectArg::ImmOrOwnedObject(random_object_ref()))
+            .collect()
+    };
+    let max_inputs = objects(MAX_INPUT_OBJECTS - 1);
+    let max_inputs = TransactionKind::Single(call(vec![CallArg::ObjVec(max_inputs)]));
+    assert!(max_inputs.validity_check().is_ok());
+    let too_many_inputs = objects(MAX_INPUT_OBJECTS);
+    let too_many_inputs = TransactionKind::Single(call(vec![CallArg::ObjVec(too_many_inputs)]));
+    assert!(matches!(
+        too_many_inputs.validity_check(),
+        Err(SuiError::TooManyInputObjects { .. })
+    ));
+
+    let too_large_batch =
+        TransactionKind::Batch((0..=MAX_TX_BATCH_SIZE).map(|_| call(vec![])).collect());
+    assert!(matches!(
+        too_large_batch.validity_check(),
+        Err(SuiError::InvalidBatchTransaction { .. })
+    ));
+}
+
+#[test]
+fn test_verify_checkpoint_certificates_in_batch() {
+    use crate::messages_checkpoint::{CertifiedCheckpointSummary, SignedCheckpointSummary};
+
+    let keys: Vec<AuthorityKeyPair> = (0..3).map(|_| get_key_pair().1).collect();
+    let committee = Committee::new(
+        0,
+        keys.iter()
+            .map(|key| (AuthorityPublicKeyBytes::from(key.public()), 1))
+            .collect(),
+    )
+    .unwrap();
+
+    let checkpoints: Vec<_> = (0..4)
+        .map(|seq| {
+            let contents = CheckpointContents::new_with_causally_ordered_transactions(
+                [ExecutionDigests::random()].into_iter(),
+            );
+            let signed = keys
+                .iter()
+                .map(|key| {
+                    SignedCheckpointSummary::new(
+                        0,
+                        seq,
+                        key.public().into(),
+                        key,
+                        &contents,
+                        None,
+                        GasCostSummary::default(),
+                        None,
+                        StateDigest::default(),
+                    )
+                })
+                .collect();
+            let cert = CertifiedCheckpointSummary::aggregate(signed, &committee).unwrap();
+            (cert, contents)
+        })
+        .collect();
+
+    CertifiedCheckpointSummary::verify_batch(
+        checkpoints
+            .iter()
+            .map(|(cert, contents)| (cert, Some(contents))),
+        &committee,
+    )
+    .unwrap();
+
+    // Contents not matching their certificate are rejected.
+    assert!(CertifiedCheckpointSummary::verify_batch(
+        checkpoints
+            .iter()
+            .map(|(cert, _)| (cert, Some(&checkpoints[0].1))),
+        &committee,
+    )
+    .is_err());
+
+    // So is a certificate whose signature is not over its summary.
+    let mut forged = checkpoints[1].0.clone();
+    forged.auth_signature = checkpoints[2].0.auth_signature.clone();
+    assert!(CertifiedCheckpointSummary::verify_batch(
+        checkpoints
+            .iter()
+            .map(|(cert, _)| cert)
+            .chain(std::iter::once(&forged))
+            .map(|cert| (cert, None)),
+        &committee,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_transaction_data_encoding() {
+    // The layout of transactions before the encoding was versioned.
+    #[derive(Serialize)]
+    struct UnversionedTransactionData {
+        kind: TransactionKind,
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        gas_price: u64,
+        gas_budget: u64,
+    }
+
+    let sender = dbg_addr(1);
+    let data = TransactionData::new_transfer(
+        dbg_addr(2),
+        random_object_ref(),
+        sender,
+        random_object_ref(),
+        10000,
+    );
+    let unversioned = UnversionedTransactionData {
+        kind: data.kind.clone(),
+        sender,
+        gas_payment: data.gas(),
+        gas_price: data.gas_price,
+        gas_budget: data.gas_budget,
+    };
+
+    // Transactions using none of the later fields keep their bytes, and their signing name.
+    let bytes = bcs::to_bytes(&data).unwrap();
+    assert_eq!(bytes, bcs::to_bytes(&unversioned).unwrap());
+    assert_eq!(
+        bincode::serialize(&data).unwrap(),
+        bincode::serialize(&unversioned).unwrap()
+    );
+    assert!(data.to_bytes().starts_with(b"TransactionData::"));
+    assert_eq!(bcs::from_bytes::<TransactionData>(&bytes).unwrap(), data);
+
+    // The others are encoded with the second version.
+    let expiring = data
+        .clone()
+        .with_expiration(TransactionExpiration::Epoch(5));
+    let bytes = bcs::to_bytes(&expiring).unwrap();
+    assert_eq!(bytes[0], 2);
+    assert_eq!(
+        bcs::from_bytes::<TransactionData>(&bytes).unwrap(),
+        expiring
+    );
+    let merged = data
+        .clone()
+        .with_merged_gas_payment(vec![random_object_ref()]);
+    let bytes = bcs::to_bytes(&merged).unwrap();
+    assert_eq!(bcs::from_bytes::<TransactionData>(&bytes).unwrap(), merged);
+
+    // Which cannot encode a transaction of the first version.
+    let unused_v2 = TransactionDataV2::from(data.clone());
+    let mut bytes = vec![2];
+    bytes.extend(bcs::to_bytes(&unused_v2).unwrap());
+    assert!(bcs::from_bytes::<TransactionData>(&bytes).is_err());
+
+    // Human-readable formats show the fields of all versions.
+    let json = serde_json::to_value(&data).unwrap();
+    assert_eq!(json["expiration"], "None");
+    assert_eq!(
+        serde_json::from_value::<TransactionData>(json).unwrap(),
+        data
+    );
+}
@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::crypto::AuthorityKeyPair;
+use crate::gas::GasCostSummary;
+use crate::messages_checkpoint::{CheckpointContents, SignedCheckpointSummary};
+use crate::state_accumulator::StateDigest;
+use crate::utils::make_committee_key;
+
+const GENESIS_DIGEST: [u8; 32] = [7; 32];
+
+fn certify_info(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    info: &NetworkInfo,
+) -> CertifiedNetworkInfo {
+    let signatures = keys
+        .iter()
+        .map(|k| {
+            let signed = SignedNetworkInfo::new(info.clone(), k.public().into(), k);
+            (*signed.authority(), signed.auth_signature.signature)
+        })
+        .collect();
+    CertifiedNetworkInfo::new(info.clone(), signatures, committee).unwrap()
+}
+
+fn end_of_epoch(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    next_epoch_committee: &Committee,
+) -> CertifiedCheckpointSummary {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([].into_iter());
+    let signed_checkpoints: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            SignedCheckpointSummary::new(
+                committee.epoch,
+                committee.epoch * 10,
+                k.public().into(),
+                k,
+                &contents,
+                None,
+                GasCostSummary::default(),
+                Some(next_epoch_committee.clone()),
+                StateDigest::default(),
+            )
+        })
+        .collect();
+    CertifiedCheckpointSummary::aggregate(signed_checkpoints, committee).unwrap()
+}
+
+#[test]
+fn test_network_info_endpoints_are_sorted() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (_, committee) = make_committee_key(&mut rng);
+    let info = NetworkInfo::new(
+        GENESIS_DIGEST,
+        &committee,
+        1,
+        vec![
+            "https://b".to_string(),
+            "https://a".to_string(),
+            "https://b".to_string(),
+        ],
+    );
+    let other = NetworkInfo::new(
+        GENESIS_DIGEST,
+        &committee,
+        1,
+        vec!["https://a".to_string(), "https://b".to_string()],
+    );
+    assert_eq!(info.rpc_endpoints, vec!["https://a", "https://b"]);
+    assert_eq!(info.digest(), other.digest());
+}
+
+#[test]
+fn test_verify_network_info_bundle() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (genesis_keys, genesis_committee) = make_committee_key(&mut rng);
+    let (next_keys, next_committee) = make_committee_key(&mut rng);
+    let next_committee =
+        Committee::new(1, next_committee.voting_rights.into_iter().collect()).unwrap();
+    let trusted = TrustedNetwork {
+        genesis_digest: GENESIS_DIGEST,
+        committee: genesis_committee.clone(),
+    };
+
+    // Network info of the genesis epoch is checked against the genesis committee.
+    let info = NetworkInfo::new(GENESIS_DIGEST, &genesis_committee, 1, vec![]);
+    let bundle = NetworkInfoBundle {
+        info: certify_info(&genesis_keys, &genesis_committee, &info),
+        epoch_changes: vec![],
+    };
+    bundle.verify(&trusted).unwrap();
+
+    // Later epochs are reached through the end-of-epoch checkpoints.
+    let info = NetworkInfo::new(
+        GENESIS_DIGEST,
+        &next_committee,
+        1,
+        vec!["https://fullnode.example".to_string()],
+    );
+    let bundle = NetworkInfoBundle {
+        info: certify_info(&next_keys, &next_committee, &info),
+        epoch_changes: vec![end_of_epoch(
+            &genesis_keys,
+            &genesis_committee,
+            &next_committee,
+        )],
+    };
+    let verified = bundle.verify(&trusted).unwrap();
+    assert_eq!(verified.committee.epoch, 1);
+    // A client which verified the bundle trusts its committee from then on.
+    bundle.verify(&verified).unwrap();
+    let saved = bundle.trusted_network().unwrap();
+    assert_eq!(
+        saved.committee.voting_rights,
+        verified.committee.voting_rights
+    );
+    bundle.verify(&saved).unwrap();
+
+    // The end-of-epoch checkpoints are needed to learn the new committee.
+    let missing_epoch_change = NetworkInfoBundle {
+        info: bundle.info.clone(),
+        epoch_changes: vec![],
+    };
+    assert!(missing_epoch_change.verify(&trusted).is_err());
+
+    // Another network, e.g. an imposter started from its own genesis, is rejected.
+    let other_genesis = TrustedNetwork {
+        genesis_digest: [8; 32],
+        committee: genesis_committee.clone(),
+    };
+    assert!(bundle.verify(&other_genesis).is_err());
+
+    // Info signed by validators outside of the committee is rejected.
+    let forged = NetworkInfoBundle {
+        info: certify_info(&genesis_keys, &genesis_committee, &info),
+        epoch_changes: bundle.epoch_changes.clone(),
+    };
+    assert!(forged.verify(&trusted).is_err());
+
+    // Tampering with the signed info invalidates the signatures.
+    let mut tampered = bundle;
+    tampered.info.info.rpc_endpoints = vec!["https://imposter.example".to_string()];
+    assert!(tampered.verify(&trusted).is_err());
+}
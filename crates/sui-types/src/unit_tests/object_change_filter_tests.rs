@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use crate::base_types::{ObjectDigest, SequenceNumber, SuiAddress, TransactionDigest};
+use crate::filter::{Filter, ObjectChangeFilter};
+use crate::gas_coin::GasCoin;
+use crate::messages::TransactionEffects;
+use crate::object::Owner;
+use crate::object_change::{changed_objects, ChangedObject, ObjectChange, ObjectChangeKind};
+use crate::ObjectID;
+
+fn object_ref(version: u64) -> (ObjectID, SequenceNumber, ObjectDigest) {
+    (
+        ObjectID::random(),
+        SequenceNumber::from_u64(version),
+        ObjectDigest::random(),
+    )
+}
+
+fn change(owner: Option<Owner>) -> ObjectChange {
+    ObjectChange {
+        checkpoint: 3,
+        tx_digest: TransactionDigest::random(),
+        kind: ObjectChangeKind::Mutated,
+        object_ref: object_ref(2),
+        owner,
+        object_type: Some(GasCoin::type_()),
+    }
+}
+
+#[test]
+fn test_object_change_filter() {
+    let address = SuiAddress::random_for_testing_only();
+    let owned = change(Some(Owner::AddressOwner(address)));
+    let child = change(Some(Owner::ObjectOwner(address)));
+    let shared = change(Some(Owner::Shared {
+        initial_shared_version: SequenceNumber::from_u64(1),
+    }));
+    let pruned = change(None);
+
+    let by_owner = ObjectChangeFilter::Owner(address);
+    assert!(by_owner.matches(&owned));
+    assert!(by_owner.matches(&child));
+    assert!(!by_owner.matches(&shared));
+    assert!(!by_owner.matches(&pruned));
+
+    let by_id = ObjectChangeFilter::ObjectIds(BTreeSet::from([shared.object_ref.0]));
+    assert!(by_id.matches(&shared));
+    assert!(!by_id.matches(&owned));
+
+    let by_type = ObjectChangeFilter::StructType(GasCoin::type_());
+    assert!(by_type.matches(&pruned));
+    let mut package = change(None);
+    package.object_type = None;
+    assert!(!by_type.matches(&package));
+
+    let filter = ObjectChangeFilter::MatchAll(vec![by_type.clone(), by_owner.clone()]);
+    assert!(filter.matches(&owned));
+    assert!(!filter.matches(&shared));
+    let filter = ObjectChangeFilter::MatchAny(vec![by_id, by_owner]);
+    assert!(filter.matches(&shared));
+    assert!(filter.matches(&owned));
+    assert!(!filter.matches(&pruned));
+    // An empty conjunction matches every change.
+    assert!(ObjectChangeFilter::MatchAll(vec![]).matches(&package));
+}
+
+#[test]
+fn test_changed_objects() {
+    let owner = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+    let created = object_ref(1);
+    let mutated = object_ref(5);
+    let deleted = object_ref(5);
+    let effects = TransactionEffects {
+        created: vec![(created, owner)],
+        mutated: vec![(mutated, owner)],
+        deleted: vec![deleted],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        changed_objects(&effects),
+        vec![
+            ChangedObject {
+                kind: ObjectChangeKind::Created,
+                object_ref: created,
+                owner: Some(owner),
+                before: None,
+                after: Some(created.1),
+            },
+            ChangedObject {
+                kind: ObjectChangeKind::Mutated,
+                object_ref: mutated,
+                owner: Some(owner),
+                before: Some(SequenceNumber::from_u64(4)),
+                after: Some(mutated.1),
+            },
+            // A deleted object had the version preceding the one the effects give it.
+            ChangedObject {
+                kind: ObjectChangeKind::Deleted,
+                object_ref: deleted,
+                owner: None,
+                before: Some(SequenceNumber::from_u64(4)),
+                after: None,
+            },
+        ]
+    );
+}
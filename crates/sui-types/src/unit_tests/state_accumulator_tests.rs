@@ -0,0 +1,127 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::traits::KeyPair;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use crate::gas::GasCostSummary;
+use crate::messages_checkpoint::SignedCheckpointSummary;
+use crate::object::Owner;
+use crate::utils::make_committee_key;
+
+fn random_object_ref() -> ObjectRef {
+    (
+        ObjectID::random(),
+        SequenceNumber::from(1),
+        ObjectDigest::random(),
+    )
+}
+
+fn summary(sequence_number: u64, state: &StateAccumulator) -> CheckpointSummary {
+    CheckpointSummary::new(
+        0,
+        sequence_number,
+        &CheckpointContents::new_with_causally_ordered_transactions([].into_iter()),
+        None,
+        GasCostSummary::default(),
+        None,
+        state.digest(),
+    )
+}
+
+#[test]
+fn test_state_accumulator_digest_roundtrip() {
+    let objects: Vec<_> = (0..10).map(|_| random_object_ref()).collect();
+    let state = StateAccumulator::from_objects(&objects);
+    assert_eq!(
+        StateAccumulator::from_digest(&state.digest()).unwrap(),
+        state
+    );
+    // The empty set is the identity point.
+    assert_eq!(StateAccumulator::default().digest(), StateDigest::default());
+}
+
+#[test]
+fn test_state_accumulator_changes() {
+    let objects: Vec<_> = (0..10).map(|_| random_object_ref()).collect();
+    let mut state = StateAccumulator::from_objects(&objects);
+    let from = summary(0, &state);
+
+    // Mutate the first object and delete the second.
+    let (id, version, _) = objects[0];
+    let mutated = (id, version.increment(), ObjectDigest::random());
+    let created = random_object_ref();
+    let added = vec![mutated, created];
+    let removed = vec![objects[0], objects[1]];
+    state.apply_changes(&added, &removed);
+    let to = summary(1, &state);
+
+    let mut live: Vec<_> = objects[2..].to_vec();
+    live.extend(added.iter().copied());
+    // Order does not matter.
+    live.reverse();
+    verify_live_object_set(&to, &live).unwrap();
+    verify_state_transition(&from, &to, &added, &removed).unwrap();
+
+    assert!(verify_live_object_set(&to, &objects).is_err());
+    assert!(verify_state_transition(&from, &to, &added, &[]).is_err());
+}
+
+#[test]
+fn test_object_inclusion_proof() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let (_, other_committee) = make_committee_key(&mut rng);
+
+    let object_ref = random_object_ref();
+    let effects = TransactionEffects {
+        transaction_digest: TransactionDigest::random(),
+        gas_object: (
+            random_object_ref(),
+            Owner::AddressOwner(SuiAddress::default()),
+        ),
+        created: vec![(object_ref, Owner::AddressOwner(SuiAddress::default()))],
+        ..Default::default()
+    };
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [
+            ExecutionDigests::random(),
+            ExecutionDigests::new(effects.transaction_digest, effects.digest()),
+        ]
+        .into_iter(),
+    );
+    let signed_checkpoints: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            SignedCheckpointSummary::new(
+                committee.epoch,
+                1,
+                k.public().into(),
+                k,
+                &contents,
+                None,
+                GasCostSummary::default(),
+                None,
+                StateDigest::default(),
+            )
+        })
+        .collect();
+    let proof = ObjectInclusionProof {
+        checkpoint: CertifiedCheckpointSummary::aggregate(signed_checkpoints, &committee).unwrap(),
+        contents,
+        effects,
+    };
+
+    proof.verify(&object_ref, &committee).unwrap();
+    assert!(proof.verify(&random_object_ref(), &committee).is_err());
+    assert!(proof.verify(&object_ref, &other_committee).is_err());
+
+    // Effects that are not part of the checkpoint are rejected.
+    let forged_ref = random_object_ref();
+    let mut forged = proof;
+    forged.effects.created.push((forged_ref, Owner::Immutable));
+    assert!(forged.verify(&forged_ref, &committee).is_err());
+}
@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+
+use super::*;
+use crate::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use crate::coin::Coin;
+use crate::gas::{MAX_GAS_BUDGET, MIN_GAS_BUDGET};
+use crate::gas_coin::GAS;
+use crate::messages::{CallArg, ObjectArg, TransactionExpiration};
+use crate::SUI_FRAMEWORK_OBJECT_ID;
+
+fn object_ref(object_id: ObjectID) -> ObjectRef {
+    (object_id, SequenceNumber::new(), ObjectDigest::random())
+}
+
+fn move_call(sender: SuiAddress, gas: ObjectID, arguments: Vec<CallArg>) -> TransactionData {
+    TransactionData::new_move_call(
+        sender,
+        object_ref(SUI_FRAMEWORK_OBJECT_ID),
+        "coin".parse().unwrap(),
+        "join".parse().unwrap(),
+        vec![],
+        object_ref(gas),
+        arguments,
+        *MIN_GAS_BUDGET,
+    )
+}
+
+#[test]
+fn test_check_transaction_data() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = ObjectID::random();
+    let coin = CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref(ObjectID::random())));
+    assert!(check_transaction_data(&move_call(
+        sender,
+        gas,
+        vec![coin.clone(), CallArg::Pure(vec![1])]
+    ))
+    .is_ok());
+
+    let mut data = move_call(sender, gas, vec![coin.clone()]);
+    data.gas_budget = *MAX_GAS_BUDGET + 1;
+    assert!(matches!(
+        check_transaction_data(&data),
+        Err(SuiError::InsufficientGas { .. })
+    ));
+    data.gas_budget = *MIN_GAS_BUDGET - 1;
+    assert!(matches!(
+        check_transaction_data(&data),
+        Err(SuiError::InsufficientGas { .. })
+    ));
+
+    assert_eq!(
+        check_transaction_data(&move_call(
+            sender,
+            gas,
+            vec![coin.clone(), CallArg::Pure(vec![])]
+        )),
+        Err(SuiError::EmptyPureArgument {
+            function: "coin::join".to_string(),
+            arg_idx: 1,
+        })
+    );
+    assert_eq!(
+        check_transaction_data(&move_call(sender, gas, vec![CallArg::ObjVec(vec![])])),
+        Err(SuiError::EmptyObjectVectorArgument {
+            function: "coin::join".to_string(),
+            arg_idx: 0,
+        })
+    );
+
+    let gas_as_input = CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref(gas)));
+    assert_eq!(
+        check_transaction_data(&move_call(sender, gas, vec![coin, gas_as_input])),
+        Err(SuiError::GasObjectUsedAsInput { object_id: gas })
+    );
+}
+
+#[test]
+fn test_check_gas_object() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas_id = ObjectID::random();
+    let data = move_call(sender, gas_id, vec![CallArg::Pure(vec![1])]);
+
+    let gas = Object::with_id_owner_for_testing(gas_id, sender);
+    assert!(check_gas_object(&data, &gas).is_ok());
+
+    let other = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    assert!(matches!(
+        check_gas_object(&data, &other),
+        Err(SuiError::InvalidGasObject { .. })
+    ));
+
+    let not_owned =
+        Object::with_id_owner_for_testing(gas_id, SuiAddress::random_for_testing_only());
+    assert!(matches!(
+        check_gas_object(&data, &not_owned),
+        Err(SuiError::InvalidGasObject { .. })
+    ));
+
+    let mut not_sui = gas;
+    not_sui.data.try_as_move_mut().unwrap().type_ = Coin::type_(StructTag {
+        name: "OTHER".parse().unwrap(),
+        ..GAS::type_()
+    });
+    assert!(matches!(
+        check_gas_object(&data, &not_sui),
+        Err(SuiError::InvalidGasObject { .. })
+    ));
+}
+
+#[test]
+fn test_check_gas_coins() {
+    let sender = SuiAddress::random_for_testing_only();
+    let (gas, merged) = (ObjectID::random(), ObjectID::random());
+    let coin = CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref(ObjectID::random())));
+    let with_merged_gas = |arguments: Vec<CallArg>, merged_gas: Vec<ObjectID>| {
+        move_call(sender, gas, arguments)
+            .with_merged_gas_payment(merged_gas.into_iter().map(object_ref).collect())
+    };
+
+    let data = with_merged_gas(vec![coin.clone()], vec![merged]);
+    assert!(check_transaction_data(&data).is_ok());
+    assert_eq!(data.gas_payment_object_ref().0, gas);
+    assert_eq!(data.merged_gas_payment().len(), 1);
+    let inputs = data.input_objects().unwrap();
+    for gas_coin in data.gas_coins() {
+        assert!(inputs.contains(&InputObjectKind::ImmOrOwnedMoveObject(*gas_coin)));
+    }
+    // All the gas coins are checked as gas objects.
+    let merged_object = Object::with_id_owner_for_testing(merged, sender);
+    assert!(check_gas_object(&data, &merged_object).is_ok());
+
+    assert!(matches!(
+        check_transaction_data(&with_merged_gas(vec![coin.clone()], vec![merged, gas])),
+        Err(SuiError::InvalidGasObject { object_id, .. }) if object_id == gas
+    ));
+    assert_eq!(
+        check_transaction_data(&with_merged_gas(
+            vec![coin],
+            (0..MAX_GAS_PAYMENT_OBJECTS)
+                .map(|_| ObjectID::random())
+                .collect()
+        )),
+        Err(SuiError::TooManyGasPaymentObjects {
+            num_objects: MAX_GAS_PAYMENT_OBJECTS + 1,
+            max_objects: MAX_GAS_PAYMENT_OBJECTS,
+        })
+    );
+
+    let merged_as_input = CallArg::Object(ObjectArg::ImmOrOwnedObject(object_ref(merged)));
+    assert_eq!(
+        check_transaction_data(&with_merged_gas(vec![merged_as_input], vec![merged])),
+        Err(SuiError::GasObjectUsedAsInput { object_id: merged })
+    );
+}
+
+#[test]
+fn test_check_transaction_expiration() {
+    let data = move_call(
+        SuiAddress::random_for_testing_only(),
+        ObjectID::random(),
+        vec![],
+    );
+    assert!(check_transaction_expiration(&data, 100).is_ok());
+
+    let data = data.with_expiration(TransactionExpiration::Epoch(5));
+    assert!(check_transaction_expiration(&data, 4).is_ok());
+    assert!(check_transaction_expiration(&data, 5).is_ok());
+    assert_eq!(
+        check_transaction_expiration(&data, 6),
+        Err(SuiError::TransactionExpired {
+            expiration_epoch: 5,
+            current_epoch: 6,
+        })
+    );
+}
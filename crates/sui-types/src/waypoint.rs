@@ -8,7 +8,7 @@ use thiserror::Error;
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 
 use crate::committee::StakeUnit;
 use crate::{base_types::AuthorityName, committee::Committee};
@@ -82,6 +82,38 @@ impl Accumulator {
             self.insert(i);
         }
     }
+
+    /// Remove one item from the accumulator
+    pub fn remove<I>(&mut self, item: &I)
+    where
+        I: IntoPoint,
+    {
+        let point: RistrettoPoint = item.into_point();
+        self.accumulator -= point;
+    }
+
+    // Remove all items from an iterator from the accumulator
+    pub fn remove_all<'a, I, It>(&'a mut self, items: It)
+    where
+        It: 'a + IntoIterator<Item = &'a I>,
+        I: 'a + IntoPoint,
+    {
+        for i in items {
+            self.remove(i);
+        }
+    }
+
+    /// A compact, canonical encoding of the accumulator value.
+    pub fn digest(&self) -> [u8; 32] {
+        self.accumulator.compress().to_bytes()
+    }
+
+    /// Recover an accumulator from its digest, or None if the digest is not a valid encoding.
+    pub fn from_digest(digest: &[u8; 32]) -> Option<Accumulator> {
+        CompressedRistretto(*digest)
+            .decompress()
+            .map(|accumulator| Accumulator { accumulator })
+    }
 }
 
 impl Debug for Accumulator {
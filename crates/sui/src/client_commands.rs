@@ -3,8 +3,9 @@
 
 use core::fmt;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
+    fs,
     path::{Path, PathBuf},
     time::Instant,
 };
@@ -14,27 +15,37 @@ use bip32::DerivationPath;
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
+use move_binary_format::CompiledModule;
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig;
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 use tracing::info;
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig};
-use sui_framework::build_move_package_to_bytes;
+use crate::config::{AddressOrAlias, Config, PersistedConfig, SuiClientConfig, SuiEnv};
+use crate::framework_verifier::{verify_framework, FrameworkVerification};
+use crate::source_registry::{SourceRegistry, SourceVerification};
+use sui_framework::compiled_package_to_bytes;
+use sui_framework_build::build_move_package_with_deps;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiObjectInfo, SuiParsedObject, SuiTransactionResponse,
+    GetObjectDataResponse, SuiObjectInfo, SuiPackageAbi, SuiParsedObject,
+    SuiParsedTransactionResponse, SuiTransactionResponse,
+};
+use sui_json_rpc_types::{
+    GetRawObjectDataResponse, SuiData, SuiObject, SuiRawData, SuiRawMovePackage,
+};
+use sui_json_rpc_types::{
+    SuiCertifiedTransaction, SuiDryRunTraceResponse, SuiExecutionStatus, SuiTransactionEffects,
 };
-use sui_json_rpc_types::{GetRawObjectDataResponse, SuiData};
-use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::TransactionExecutionResult;
 use sui_sdk::{ClientType, SuiClient};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
     gas_coin::GasCoin,
     messages::Transaction,
+    move_package::disassemble_modules,
     object::Owner,
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
@@ -58,10 +69,10 @@ pub enum SuiClientCommands {
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
-        /// An Sui address to be used as the active address for subsequent
+        /// An Sui address, or its alias, to be used as the active address for subsequent
         /// commands.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long, value_hint = ValueHint::Url)]
@@ -69,12 +80,47 @@ pub enum SuiClientCommands {
         /// The pubsub Websocket server URL
         #[clap(long, value_hint = ValueHint::Url)]
         ws: Option<String>,
+        /// Alias of an environment, added with `new-env`, to connect to
+        #[clap(long, conflicts_with_all = &["rpc", "ws"])]
+        env: Option<String>,
     },
 
     /// Default address used for commands when none specified
     #[clap(name = "active-address")]
     ActiveAddress,
 
+    /// Add an environment the client can switch to with `switch --env`
+    #[clap(name = "new-env")]
+    NewEnv {
+        /// Name of the environment, e.g. devnet or localnet
+        #[clap(long)]
+        alias: String,
+        /// The RPC server URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        rpc: String,
+        /// The pubsub Websocket server URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        ws: Option<String>,
+        /// The faucet URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        faucet: Option<String>,
+        /// Path of the keystore used in the environment, instead of the one in use
+        #[clap(long, value_hint = ValueHint::FilePath)]
+        keystore: Option<PathBuf>,
+    },
+
+    /// Remove an environment
+    #[clap(name = "remove-env")]
+    RemoveEnv {
+        /// Name of the environment
+        #[clap(long)]
+        alias: String,
+    },
+
+    /// List the environments, marking the active one
+    #[clap(name = "envs")]
+    Envs,
+
     /// Get object info
     #[clap(name = "object")]
     Object {
@@ -83,6 +129,22 @@ pub enum SuiClientCommands {
         id: ObjectID,
     },
 
+    /// Download the bytecode modules of an on-chain package
+    #[clap(name = "fetch-package")]
+    FetchPackage {
+        /// Object ID of the package to fetch
+        #[clap(long)]
+        id: ObjectID,
+
+        /// Directory to write the package to, created if it does not exist
+        #[clap(long, parse(from_os_str), default_value = ".")]
+        output_dir: PathBuf,
+
+        /// Also write the disassembly of each module
+        #[clap(long)]
+        disassemble: bool,
+    },
+
     /// Publish Move modules
     #[clap(name = "publish")]
     Publish {
@@ -108,6 +170,47 @@ pub enum SuiClientCommands {
         /// Gas budget for running module initializers
         #[clap(long)]
         gas_budget: u64,
+
+        /// Directory of a source registry to record the source maps and source digests of the
+        /// published package in
+        #[clap(long, parse(from_os_str))]
+        source_registry: Option<PathBuf>,
+    },
+
+    /// Check that a source tree matches the sources registered for a package
+    #[clap(name = "verify-source")]
+    VerifySource {
+        /// Object ID of the published package
+        #[clap(long)]
+        package_id: ObjectID,
+
+        /// Directory of the source registry the package was registered in
+        #[clap(long, parse(from_os_str))]
+        source_registry: PathBuf,
+
+        /// Path to directory containing the Move package
+        #[clap(long = "path", short = 'p', parse(from_os_str), default_value = ".")]
+        package_path: PathBuf,
+    },
+
+    /// Check that a local checkout of the Sui framework matches the system packages deployed on
+    /// the active network
+    #[clap(name = "verify-framework")]
+    VerifyFramework {
+        /// Path to the Sui framework package, with the Move standard library under deps/move-stdlib
+        #[clap(long = "path", short = 'p', parse(from_os_str))]
+        framework_path: PathBuf,
+    },
+
+    /// List the entry functions of a package, with the types of their parameters
+    #[clap(name = "functions")]
+    Functions {
+        /// Object ID of the package
+        #[clap(long)]
+        package: ObjectID,
+        /// Only list the functions of this module
+        #[clap(long)]
+        module: Option<String>,
     },
 
     /// Call Move function
@@ -142,14 +245,32 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+
+        /// Only estimate the gas fee of the call, without executing it
+        #[clap(long)]
+        dry_run: bool,
+
+        /// With --dry-run, show the Move call stack where the call stopped if it fails
+        #[clap(long, requires = "dry-run")]
+        trace: bool,
+
+        /// With --trace, directory of the source registry the package was registered in, to
+        /// locate the frames of the trace in the sources of the package
+        #[clap(long, requires = "trace", parse(from_os_str))]
+        source_registry: Option<PathBuf>,
+
+        /// Path to directory containing the Move package, to read its sources with
+        /// --source-registry
+        #[clap(long = "path", short = 'p', parse(from_os_str), default_value = ".")]
+        package_path: PathBuf,
     },
 
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -169,9 +290,9 @@ pub enum SuiClientCommands {
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -187,15 +308,17 @@ pub enum SuiClientCommands {
     },
     /// Pay SUI to recipients following specified amounts, with input coins.
     /// Length of recipients must be the same as that of amounts.
+    /// What is left of the input coins stays with the sender.
     #[clap(name = "pay")]
     Pay {
         /// The input coins to be used for pay recipients, following the specified amounts.
+        /// If not provided, SUI coins of the active address covering the amounts are selected
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient addresses, must be of same length as amounts
+        /// The recipient addresses, or their aliases, must be of same length as amounts
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<AddressOrAlias>,
 
         /// The amounts to be transferred, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -209,18 +332,59 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
+
+        /// Only estimate the gas fee of the transaction, without executing it
+        #[clap(long)]
+        dry_run: bool,
     },
     /// Synchronize client state with authorities.
     #[clap(name = "sync")]
     SyncClientState {
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Obtain the Addresses managed by the client.
     #[clap(name = "addresses")]
     Addresses,
 
+    /// Name an address, so that the alias can be given wherever an address is expected
+    #[clap(name = "add-alias")]
+    AddAlias {
+        /// Name of letters, digits, '-' and '_', starting with a letter
+        #[clap(long)]
+        alias: String,
+        /// The address named
+        #[clap(long)]
+        address: SuiAddress,
+    },
+
+    /// Remove an address alias
+    #[clap(name = "remove-alias")]
+    RemoveAlias {
+        #[clap(long)]
+        alias: String,
+    },
+
+    /// List the address aliases
+    #[clap(name = "aliases")]
+    Aliases,
+
+    /// Write the address aliases to a JSON file, to be imported by another client
+    #[clap(name = "export-aliases")]
+    ExportAliases {
+        #[clap(long, parse(from_os_str), value_hint = ValueHint::FilePath)]
+        path: PathBuf,
+    },
+
+    /// Add the address aliases of a JSON file written by `export-aliases`. Nothing is imported
+    /// if an alias of the file names another address than the same alias of the client.
+    #[clap(name = "import-aliases")]
+    ImportAliases {
+        #[clap(long, parse(from_os_str), value_hint = ValueHint::FilePath)]
+        path: PathBuf,
+    },
+
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or m/54'/784'/0'/0/0 for secp256k1.
     #[clap(name = "new-address")]
@@ -232,17 +396,17 @@ pub enum SuiClientCommands {
     /// Obtain all objects owned by the address.
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address owning the objects, or its alias
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Split a coin object into multiple coins.
@@ -311,9 +475,9 @@ pub enum SuiClientCommands {
     /// Serialize a transfer that can be signed. This is useful when user prefers to take the data to sign elsewhere.
     #[clap(name = "serialize-transfer-sui")]
     SerializeTransferSui {
-        /// Recipient address
+        /// Recipient address, or its alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -359,11 +523,15 @@ impl SuiClientCommands {
                 gas,
                 build_config,
                 gas_budget,
+                source_registry,
             } => {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
-                let compiled_modules = build_move_package_to_bytes(&package_path, build_config)?;
+                // The package is compiled with its dependencies once, for both its modules and the
+                // source maps the registry needs.
+                let compiled_package = build_move_package_with_deps(&package_path, build_config)?;
+                let compiled_modules = compiled_package_to_bytes(&compiled_package)?;
                 let data = context
                     .client
                     .transaction_builder()
@@ -374,14 +542,71 @@ impl SuiClientCommands {
                     .execute_transaction(Transaction::new(data, signature))
                     .await?;
 
+                if let Some(registry) = source_registry {
+                    let package_id = match &response.parsed_data {
+                        Some(SuiParsedTransactionResponse::Publish(publish)) => {
+                            publish.package.object_id
+                        }
+                        _ => return Err(anyhow!("Publish response is missing the package")),
+                    };
+                    SourceRegistry::new(registry).register(
+                        package_id,
+                        &package_path,
+                        &compiled_package,
+                    )?;
+                }
+
                 SuiClientCommandResult::Publish(response)
             }
 
+            SuiClientCommands::VerifySource {
+                package_id,
+                source_registry,
+                package_path,
+            } => SuiClientCommandResult::VerifySource(
+                SourceRegistry::new(source_registry).verify(&package_id, &package_path)?,
+            ),
+
+            SuiClientCommands::VerifyFramework { framework_path } => {
+                SuiClientCommandResult::VerifyFramework(
+                    verify_framework(&framework_path, &context.client).await?,
+                )
+            }
+
             SuiClientCommands::Object { id } => {
                 // Fetch the object ref
                 let object_read = context.client.read_api().get_parsed_object(id).await?;
                 SuiClientCommandResult::Object(object_read)
             }
+            SuiClientCommands::FetchPackage {
+                id,
+                output_dir,
+                disassemble,
+            } => {
+                let object_read = context.client.read_api().get_object(id).await?;
+                let object = object_read.object()?;
+                let package = match &object.data {
+                    SuiRawData::Package(package) => package,
+                    SuiRawData::MoveObject(_) => {
+                        return Err(anyhow!("Object {id} is not a package"));
+                    }
+                };
+                let manifest = PackageManifest::new(object, package)?;
+                write_package(&output_dir, package, &manifest, disassemble)?;
+                SuiClientCommandResult::FetchPackage(output_dir, manifest)
+            }
+            SuiClientCommands::Functions { package, module } => {
+                let mut abi = context.client.read_api().get_package_abi(package).await?;
+                if let Some(module) = module {
+                    abi.modules.retain(|name, _| *name == module);
+                    ensure!(
+                        !abi.modules.is_empty(),
+                        "Package {package} has no module {module}"
+                    );
+                }
+                SuiClientCommandResult::Functions(abi)
+            }
+
             SuiClientCommands::Call {
                 package,
                 module,
@@ -390,7 +615,56 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
                 args,
+                dry_run,
+                trace,
+                source_registry,
+                package_path,
             } => {
+                if dry_run {
+                    let transaction = move_call_transaction(
+                        package, &module, &function, type_args, gas, gas_budget, args, context,
+                    )
+                    .await?;
+                    if trace {
+                        let response = context
+                            .client
+                            .full_node_api()
+                            .dry_run_transaction_with_trace(transaction)
+                            .await?;
+                        let source_locations = match (&response.trace, source_registry) {
+                            (Some(trace), Some(registry)) => {
+                                let registry = SourceRegistry::new(registry);
+                                trace
+                                    .stack
+                                    .iter()
+                                    .map(|frame| {
+                                        registry
+                                            .source_location(
+                                                &frame.package,
+                                                &frame.module,
+                                                frame.function_index,
+                                                frame.instruction,
+                                                &package_path,
+                                            )
+                                            .ok()
+                                            .flatten()
+                                    })
+                                    .collect()
+                            }
+                            _ => vec![],
+                        };
+                        return Ok(SuiClientCommandResult::DryRunTrace(
+                            response,
+                            source_locations,
+                        ));
+                    }
+                    let effects = context
+                        .client
+                        .full_node_api()
+                        .dry_run_transaction(transaction)
+                        .await?;
+                    return Ok(SuiClientCommandResult::DryRun(effects));
+                }
                 let (cert, effects) = call_move(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
@@ -404,6 +678,7 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let time_start = Instant::now();
 
@@ -432,6 +707,7 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let data = context
@@ -458,11 +734,8 @@ impl SuiClientCommands {
                 amounts,
                 gas,
                 gas_budget,
+                dry_run,
             } => {
-                ensure!(
-                    !input_coins.is_empty(),
-                    "Pay transaction requires a non-empty list of input coins"
-                );
                 ensure!(
                     !recipients.is_empty(),
                     "Pay transaction requires a non-empty list of recipient addresses"
@@ -475,13 +748,28 @@ impl SuiClientCommands {
                         amounts.len()
                     ),
                 );
-                let from = context.get_object_owner(&input_coins[0]).await?;
+                let recipients = recipients
+                    .iter()
+                    .map(|recipient| context.config.resolve_address(recipient))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let from = match input_coins.first() {
+                    Some(coin) => context.get_object_owner(coin).await?,
+                    None => context.active_address()?,
+                };
                 let data = context
                     .client
                     .transaction_builder()
                     .pay(from, input_coins, recipients, amounts, gas, gas_budget)
                     .await?;
                 let signature = context.config.keystore.sign(&from, &data.to_bytes())?;
+                if dry_run {
+                    let effects = context
+                        .client
+                        .full_node_api()
+                        .dry_run_transaction(Transaction::new(data, signature))
+                        .await?;
+                    return Ok(SuiClientCommandResult::DryRun(effects));
+                }
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
                     .await?;
@@ -500,8 +788,36 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Addresses(context.config.keystore.addresses())
             }
 
+            SuiClientCommands::AddAlias { alias, address } => {
+                context.config.add_alias(alias.clone(), address)?;
+                context.config.save()?;
+                SuiClientCommandResult::AddAlias(alias, address)
+            }
+            SuiClientCommands::RemoveAlias { alias } => {
+                let address = context.config.remove_alias(&alias)?;
+                context.config.save()?;
+                SuiClientCommandResult::RemoveAlias(alias, address)
+            }
+            SuiClientCommands::Aliases => {
+                SuiClientCommandResult::Aliases(context.config.aliases.clone())
+            }
+            SuiClientCommands::ExportAliases { path } => {
+                fs::write(
+                    &path,
+                    serde_json::to_string_pretty(&context.config.aliases)?,
+                )?;
+                SuiClientCommandResult::ExportAliases(path, context.config.aliases.len())
+            }
+            SuiClientCommands::ImportAliases { path } => {
+                let aliases = serde_json::from_slice(&fs::read(&path)?)
+                    .map_err(|e| anyhow!("Invalid alias file {}: {e}", path.display()))?;
+                let num_imported = context.config.import_aliases(aliases)?;
+                context.config.save()?;
+                SuiClientCommandResult::ImportAliases(num_imported)
+            }
+
             SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 let mut address_object = context
                     .client
                     .read_api()
@@ -518,7 +834,7 @@ impl SuiClientCommands {
             }
 
             SuiClientCommands::SyncClientState { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 context
                     .client
                     .wallet_sync_api()
@@ -538,7 +854,7 @@ impl SuiClientCommands {
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
             SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -603,7 +919,20 @@ impl SuiClientCommands {
 
                 SuiClientCommandResult::MergeCoin(response)
             }
-            SuiClientCommands::Switch { address, rpc, ws } => {
+            SuiClientCommands::Switch {
+                address,
+                rpc,
+                ws,
+                env,
+            } => {
+                // Switched first, as the keystore of the environment may manage other addresses.
+                if let Some(env) = &env {
+                    context.switch_env(env).await?;
+                }
+
+                let address = address
+                    .map(|address| context.config.resolve_address(&address))
+                    .transpose()?;
                 if let Some(addr) = address {
                     if !context.config.keystore.addresses().contains(&addr) {
                         return Err(anyhow!("Address {} not managed by wallet", addr));
@@ -613,14 +942,50 @@ impl SuiClientCommands {
 
                 Self::switch_server(&mut context.config, &rpc, &ws)?;
 
-                if Option::is_none(&address) && Option::is_none(&rpc) && Option::is_none(&ws) {
+                if Option::is_none(&address)
+                    && Option::is_none(&rpc)
+                    && Option::is_none(&ws)
+                    && Option::is_none(&env)
+                {
                     return Err(anyhow!(
-                        "No address or RPC url specified. Please Specify one."
+                        "No address, RPC url or environment specified. Please Specify one."
                     ));
                 }
                 context.config.save()?;
-                SuiClientCommandResult::Switch(SwitchResponse { address, rpc, ws })
+                SuiClientCommandResult::Switch(SwitchResponse {
+                    address,
+                    rpc,
+                    ws,
+                    env,
+                })
+            }
+            SuiClientCommands::NewEnv {
+                alias,
+                rpc,
+                ws,
+                faucet,
+                keystore,
+            } => {
+                let env = SuiEnv {
+                    alias,
+                    rpc,
+                    ws,
+                    faucet,
+                    keystore,
+                };
+                context.config.add_env(env.clone())?;
+                context.config.save()?;
+                SuiClientCommandResult::NewEnv(env)
             }
+            SuiClientCommands::RemoveEnv { alias } => {
+                let env = context.config.remove_env(&alias)?;
+                context.config.save()?;
+                SuiClientCommandResult::RemoveEnv(env)
+            }
+            SuiClientCommands::Envs => SuiClientCommandResult::Envs(
+                context.config.envs.clone(),
+                context.config.active_env.clone(),
+            ),
             SuiClientCommands::ActiveAddress => {
                 SuiClientCommandResult::ActiveAddress(context.active_address().ok())
             }
@@ -667,6 +1032,7 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let data = context
@@ -725,6 +1091,11 @@ impl SuiClientCommands {
             };
             config.client_type = ClientType::RPC(rpc, Some(ws.clone()));
         }
+
+        // The client is no longer connected to the environment it was switched to.
+        if rpc.is_some() || ws.is_some() {
+            config.active_env = None;
+        }
         Ok(())
     }
 }
@@ -749,6 +1120,34 @@ impl WalletContext {
         Ok(context)
     }
 
+    /// The environment the client is connected to, if it was switched to one.
+    pub fn active_env(&self) -> Option<&SuiEnv> {
+        self.config
+            .active_env
+            .as_ref()
+            .and_then(|alias| self.config.get_env(alias))
+    }
+
+    /// Connects the client to the environment `alias` of the config, and saves the config.
+    pub async fn switch_env(&mut self, alias: &str) -> Result<(), anyhow::Error> {
+        self.config.switch_env(alias)?;
+        // Fails before saving the config if the environment is unreachable.
+        self.client = self.config.client_type.init().await?;
+        self.config.save()?;
+        Ok(())
+    }
+
+    /// The address `address` stands for if given, else the active address.
+    pub fn address_or_active(
+        &mut self,
+        address: Option<AddressOrAlias>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            Some(address) => self.config.resolve_address(&address),
+            None => self.active_address(),
+        }
+    }
+
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
         if self.config.keystore.addresses().is_empty() {
             return Err(anyhow!(
@@ -875,6 +1274,7 @@ impl WalletContext {
                 effects: effects.unwrap(), // check is done in execute_transaction, safe to unwrap
                 timestamp_ms,
                 parsed_data,
+                balance_changes: None,
             }),
             Err(err) => Err(anyhow!(
                 "Failed to execute transaction {tx_digest:?} with error {err:?}"
@@ -905,6 +1305,41 @@ impl Display for SuiClientCommandResult {
                 let object = unwrap_err_to_string(|| Ok(object_read.object()?));
                 writeln!(writer, "{}", object)?;
             }
+            SuiClientCommandResult::VerifySource(verification) => {
+                writeln!(writer, "{}", verification)?;
+            }
+            SuiClientCommandResult::VerifyFramework(verification) => {
+                writeln!(writer, "{}", verification)?;
+            }
+            SuiClientCommandResult::FetchPackage(output_dir, manifest) => {
+                writeln!(
+                    writer,
+                    "Wrote {} modules of package {} (version {}) to {}",
+                    manifest.modules.len(),
+                    manifest.package_id,
+                    manifest.version,
+                    output_dir.display()
+                )?;
+            }
+            SuiClientCommandResult::Functions(abi) => {
+                for (module, module_abi) in &abi.modules {
+                    for (function, function_abi) in &module_abi.entry_functions {
+                        write!(writer, "{module}::{function}")?;
+                        if !function_abi.type_parameters.is_empty() {
+                            let type_parameters = (0..function_abi.type_parameters.len())
+                                .map(|i| format!("T{i}"))
+                                .collect::<Vec<_>>();
+                            write!(writer, "<{}>", type_parameters.join(", "))?;
+                        }
+                        let parameters = function_abi
+                            .parameters
+                            .iter()
+                            .map(|parameter| parameter.to_string())
+                            .collect::<Vec<_>>();
+                        writeln!(writer, "({})", parameters.join(", "))?;
+                    }
+                }
+            }
             SuiClientCommandResult::Call(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
@@ -918,12 +1353,56 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Pay(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
+            SuiClientCommandResult::DryRun(effects) => {
+                write!(writer, "{}", write_dry_run_effects(effects)?)?;
+            }
+            SuiClientCommandResult::DryRunTrace(response, source_locations) => {
+                write!(writer, "{}", write_dry_run_effects(&response.effects)?)?;
+                if let Some(trace) = &response.trace {
+                    writeln!(writer, "{}", "----- Execution Trace ----".bold())?;
+                    writeln!(writer, "Error : {}", trace.error)?;
+                    for (i, frame) in trace.stack.iter().enumerate() {
+                        match source_locations.get(i).and_then(Option::as_ref) {
+                            Some(location) => writeln!(writer, "  at {frame} [{location}]")?,
+                            None => writeln!(writer, "  at {frame}")?,
+                        }
+                    }
+                    for call in &trace.calls {
+                        writeln!(
+                            writer,
+                            "Call : {}::{}::{} {:?}",
+                            call.package.object_id, call.module, call.function, call.arguments
+                        )?;
+                    }
+                }
+            }
             SuiClientCommandResult::Addresses(addresses) => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
                 for address in addresses {
                     writeln!(writer, "{}", address)?;
                 }
             }
+            SuiClientCommandResult::AddAlias(alias, address) => {
+                writeln!(writer, "Added alias {alias} of address {address}")?;
+            }
+            SuiClientCommandResult::RemoveAlias(alias, address) => {
+                writeln!(writer, "Removed alias {alias} of address {address}")?;
+            }
+            SuiClientCommandResult::Aliases(aliases) => {
+                for (alias, address) in aliases {
+                    writeln!(writer, "{alias} : {address}")?;
+                }
+            }
+            SuiClientCommandResult::ExportAliases(path, num_aliases) => {
+                writeln!(
+                    writer,
+                    "Exported {num_aliases} aliases to {}",
+                    path.display()
+                )?;
+            }
+            SuiClientCommandResult::ImportAliases(num_aliases) => {
+                writeln!(writer, "Imported {num_aliases} new aliases")?;
+            }
             SuiClientCommandResult::Objects(object_refs) => {
                 writeln!(
                     writer,
@@ -1001,6 +1480,22 @@ impl Display for SuiClientCommandResult {
                     None => write!(writer, "None")?,
                 };
             }
+            SuiClientCommandResult::NewEnv(env) => {
+                writeln!(writer, "Added environment {}", env)?;
+            }
+            SuiClientCommandResult::RemoveEnv(env) => {
+                writeln!(writer, "Removed environment {}", env)?;
+            }
+            SuiClientCommandResult::Envs(envs, active_env) => {
+                for env in envs {
+                    let marker = if active_env.as_ref() == Some(&env.alias) {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    writeln!(writer, "{marker} {env}")?;
+                }
+            }
             SuiClientCommandResult::CreateExampleNFT(object_read) => {
                 // TODO: display the content of the object
                 let object = unwrap_err_to_string(|| Ok(object_read.object()?));
@@ -1035,6 +1530,33 @@ pub async fn call_move(
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let transaction = move_call_transaction(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+
+    let response = context.execute_transaction(transaction).await?;
+    let cert = response.certificate;
+    let effects = response.effects;
+
+    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+    }
+    Ok((cert, effects))
+}
+
+/// Builds and signs a call of `package::module::function`, sent by the owner of `gas` or the
+/// active address.
+async fn move_call_transaction(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<Transaction, anyhow::Error> {
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
@@ -1056,16 +1578,7 @@ pub async fn call_move(
         )
         .await?;
     let signature = context.config.keystore.sign(&sender, &data.to_bytes())?;
-    let transaction = Transaction::new(data, signature);
-
-    let response = context.execute_transaction(transaction).await?;
-    let cert = response.certificate;
-    let effects = response.effects;
-
-    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
-        return Err(anyhow!("Error calling module: {:#?}", effects.status));
-    }
-    Ok((cert, effects))
+    Ok(Transaction::new(data, signature))
 }
 
 fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
@@ -1075,6 +1588,26 @@ fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     }
 }
 
+fn write_dry_run_effects(effects: &SuiTransactionEffects) -> Result<String, fmt::Error> {
+    let mut writer = String::new();
+    let gas = &effects.gas_used;
+    writeln!(
+        writer,
+        "{}",
+        "Dry run, the transaction was not executed".bold()
+    )?;
+    writeln!(writer, "{}", effects)?;
+    writeln!(
+        writer,
+        "Estimated gas fee : {} (computation {}, storage {}, storage rebate {})",
+        (gas.computation_cost + gas.storage_cost).saturating_sub(gas.storage_rebate),
+        gas.computation_cost,
+        gas.storage_cost,
+        gas.storage_rebate
+    )?;
+    Ok(writer)
+}
+
 fn write_cert_and_effects(
     cert: &SuiCertifiedTransaction,
     effects: &SuiTransactionEffects,
@@ -1128,6 +1661,7 @@ impl SuiClientCommandResult {
 pub enum SuiClientCommandResult {
     Publish(SuiTransactionResponse),
     Object(GetObjectDataResponse),
+    Functions(SuiPackageAbi),
     Call(SuiCertifiedTransaction, SuiTransactionEffects),
     Transfer(
         // Skipping serialisation for elapsed time.
@@ -1137,7 +1671,15 @@ pub enum SuiClientCommandResult {
     ),
     TransferSui(SuiCertifiedTransaction, SuiTransactionEffects),
     Pay(SuiCertifiedTransaction, SuiTransactionEffects),
+    DryRun(SuiTransactionEffects),
+    /// A traced dry run, with the source locations of the frames of its trace, if known
+    DryRunTrace(SuiDryRunTraceResponse, Vec<Option<String>>),
     Addresses(Vec<SuiAddress>),
+    AddAlias(String, SuiAddress),
+    RemoveAlias(String, SuiAddress),
+    Aliases(BTreeMap<String, SuiAddress>),
+    ExportAliases(#[serde(skip)] PathBuf, usize),
+    ImportAliases(usize),
     Objects(Vec<SuiObjectInfo>),
     SyncClientState,
     NewAddress((SuiAddress, String, SignatureScheme)),
@@ -1146,9 +1688,91 @@ pub enum SuiClientCommandResult {
     MergeCoin(SuiTransactionResponse),
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
+    NewEnv(SuiEnv),
+    RemoveEnv(SuiEnv),
+    Envs(Vec<SuiEnv>, Option<String>),
     CreateExampleNFT(GetObjectDataResponse),
     SerializeTransferSui(String),
     ExecuteSignedTx(SuiTransactionResponse),
+    FetchPackage(#[serde(skip)] PathBuf, PackageManifest),
+    VerifySource(SourceVerification),
+    VerifyFramework(FrameworkVerification),
+}
+
+/// Describes a package downloaded by `fetch-package`, written next to its modules.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageManifest {
+    pub package_id: ObjectID,
+    pub version: SequenceNumber,
+    /// The transaction that published the package
+    pub previous_transaction: TransactionDigest,
+    pub modules: Vec<String>,
+    /// The modules used by the package, by address, including its own modules
+    pub addresses: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl PackageManifest {
+    fn new(
+        object: &SuiObject<SuiRawData>,
+        package: &SuiRawMovePackage,
+    ) -> Result<Self, anyhow::Error> {
+        let mut addresses: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (name, bytecode) in &package.module_map {
+            let module = CompiledModule::deserialize(bytecode)
+                .map_err(|e| anyhow!("Cannot deserialize module {name}: {e}"))?;
+            for handle in module.module_handles() {
+                addresses
+                    .entry(
+                        module
+                            .address_identifier_at(handle.address)
+                            .to_hex_literal(),
+                    )
+                    .or_default()
+                    .insert(module.identifier_at(handle.name).to_string());
+            }
+        }
+        Ok(Self {
+            package_id: package.id,
+            version: object.reference.version,
+            previous_transaction: object.previous_transaction,
+            modules: package.module_map.keys().cloned().collect(),
+            addresses,
+        })
+    }
+}
+
+/// Writes the modules of `package` as `bytecode_modules/<module>.mv` under `output_dir`, the
+/// way the Move compiler lays them out, along with `manifest.json` and, if `disassemble` is set,
+/// `disassembly/<module>.asm`.
+fn write_package(
+    output_dir: &Path,
+    package: &SuiRawMovePackage,
+    manifest: &PackageManifest,
+    disassemble: bool,
+) -> Result<(), anyhow::Error> {
+    let modules_dir = output_dir.join("bytecode_modules");
+    fs::create_dir_all(&modules_dir)?;
+    for (name, bytecode) in &package.module_map {
+        fs::write(modules_dir.join(format!("{name}.mv")), bytecode)?;
+    }
+    fs::write(
+        output_dir.join("manifest.json"),
+        serde_json::to_string_pretty(manifest)?,
+    )?;
+
+    if disassemble {
+        let disassembly_dir = output_dir.join("disassembly");
+        fs::create_dir_all(&disassembly_dir)?;
+        for (name, disassembly) in disassemble_modules(package.module_map.values())? {
+            let disassembly = match disassembly {
+                Value::String(disassembly) => disassembly,
+                other => other.to_string(),
+            };
+            fs::write(disassembly_dir.join(format!("{name}.asm")), disassembly)?;
+        }
+    }
+    Ok(())
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -1157,6 +1781,7 @@ pub struct SwitchResponse {
     pub address: Option<SuiAddress>,
     pub rpc: Option<String>,
     pub ws: Option<String>,
+    pub env: Option<String>,
 }
 
 impl Display for SwitchResponse {
@@ -1172,6 +1797,9 @@ impl Display for SwitchResponse {
         if let Some(ws) = &self.ws {
             writeln!(writer, "Active Websocket server switched to {}", ws)?;
         }
+        if let Some(env) = &self.env {
+            writeln!(writer, "Active environment switched to {}", env)?;
+        }
         write!(f, "{}", writer)
     }
 }
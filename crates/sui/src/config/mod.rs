@@ -2,11 +2,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{anyhow, bail};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use sui_config::SUI_DEV_NET_URL;
 use sui_keys::keystore::AccountKeystore;
-use sui_keys::keystore::Keystore;
+use sui_keys::keystore::{FileBasedKeystore, Keystore};
 use sui_types::base_types::*;
 
 pub use sui_config::Config;
@@ -21,10 +26,238 @@ pub struct SuiClientConfig {
     pub keystore: Keystore,
     pub client_type: ClientType,
     pub active_address: Option<SuiAddress>,
+    /// Networks the client can switch to, by alias.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub envs: Vec<SuiEnv>,
+    /// Alias of the environment the client is connected to, if it was switched to one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_env: Option<String>,
+    /// Human-readable names of addresses, accepted wherever the CLI expects an address.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, SuiAddress>,
 }
 
 impl Config for SuiClientConfig {}
 
+impl SuiClientConfig {
+    /// The address `address` stands for, looking up aliases in the alias book.
+    pub fn resolve_address(&self, address: &AddressOrAlias) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            AddressOrAlias::Address(address) => Ok(*address),
+            AddressOrAlias::Alias(alias) => self
+                .aliases
+                .get(alias)
+                .copied()
+                .ok_or_else(|| anyhow!("Unknown address alias {alias}")),
+        }
+    }
+
+    /// The aliases of `address`, in alphabetical order.
+    pub fn aliases_of(&self, address: &SuiAddress) -> Vec<&str> {
+        self.aliases
+            .iter()
+            .filter(|(_, a)| *a == address)
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+
+    /// Names `address` `alias`, failing if `alias` is not a valid alias or already names an
+    /// address.
+    pub fn add_alias(&mut self, alias: String, address: SuiAddress) -> Result<(), anyhow::Error> {
+        check_alias(&alias)?;
+        if let Some(existing) = self.aliases.get(&alias) {
+            bail!("Alias {alias} already names address {existing}");
+        }
+        self.aliases.insert(alias, address);
+        Ok(())
+    }
+
+    pub fn remove_alias(&mut self, alias: &str) -> Result<SuiAddress, anyhow::Error> {
+        self.aliases
+            .remove(alias)
+            .ok_or_else(|| anyhow!("Unknown address alias {alias}"))
+    }
+
+    /// Adds the aliases of `aliases`, e.g. exported from another client, and returns how many
+    /// are new. Aliases already naming the same address are skipped. Nothing is imported if an
+    /// alias is invalid or names another address in the alias book.
+    pub fn import_aliases(
+        &mut self,
+        aliases: BTreeMap<String, SuiAddress>,
+    ) -> Result<usize, anyhow::Error> {
+        for (alias, address) in &aliases {
+            check_alias(alias)?;
+            match self.aliases.get(alias) {
+                Some(existing) if existing != address => {
+                    bail!("Alias {alias} names address {address}, but already names address {existing}")
+                }
+                _ => {}
+            }
+        }
+        let num_aliases = self.aliases.len();
+        self.aliases.extend(aliases);
+        Ok(self.aliases.len() - num_aliases)
+    }
+
+    pub fn get_env(&self, alias: &str) -> Option<&SuiEnv> {
+        self.envs.iter().find(|env| env.alias == alias)
+    }
+
+    /// Adds `env`, failing if an environment with the same alias exists.
+    pub fn add_env(&mut self, env: SuiEnv) -> Result<(), anyhow::Error> {
+        if self.get_env(&env.alias).is_some() {
+            bail!("Environment {} already exists", env.alias);
+        }
+        self.envs.push(env);
+        Ok(())
+    }
+
+    /// Removes the environment `alias`, unless it is the active one.
+    pub fn remove_env(&mut self, alias: &str) -> Result<SuiEnv, anyhow::Error> {
+        if self.active_env.as_deref() == Some(alias) {
+            bail!("Cannot remove the active environment {alias}");
+        }
+        let index = self
+            .envs
+            .iter()
+            .position(|env| env.alias == alias)
+            .ok_or_else(|| anyhow!("Environment {alias} not found"))?;
+        Ok(self.envs.remove(index))
+    }
+
+    /// Connects the client to the environment `alias`, and uses its keystore if it has one.
+    /// Environments without a keystore keep the keystore in use.
+    pub fn switch_env(&mut self, alias: &str) -> Result<(), anyhow::Error> {
+        let env = self
+            .get_env(alias)
+            .ok_or_else(|| anyhow!("Environment {alias} not found"))?;
+        let keystore = env
+            .keystore
+            .as_ref()
+            .map(|path| FileBasedKeystore::new(path).map(Keystore::from))
+            .transpose()?;
+        self.client_type = env.client_type();
+        if let Some(keystore) = keystore {
+            // The active address may not be managed by the keystore of the environment.
+            if !matches!(self.active_address, Some(address) if keystore.addresses().contains(&address))
+            {
+                self.active_address = keystore.addresses().first().copied();
+            }
+            self.keystore = keystore;
+        }
+        self.active_env = Some(alias.to_string());
+        Ok(())
+    }
+}
+
+/// Aliases are names of letters, digits, `-` and `_`, starting with a letter, which cannot be
+/// mistaken for an address.
+fn check_alias(alias: &str) -> Result<(), anyhow::Error> {
+    let mut chars = alias.chars();
+    let valid = chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid address alias {alias:?}: aliases are letters, digits, '-' and '_', starting with a letter");
+    }
+    if SuiAddress::from_str(alias).is_ok() {
+        bail!("Invalid address alias {alias:?}: aliases cannot be addresses");
+    }
+    Ok(())
+}
+
+/// An address given on the command line: either the address itself, or an alias of the alias
+/// book of the client config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressOrAlias {
+    Address(SuiAddress),
+    Alias(String),
+}
+
+impl FromStr for AddressOrAlias {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = SuiAddress::from_str(s) {
+            return Ok(Self::Address(address));
+        }
+        check_alias(s).map_err(|_| anyhow!("{s:?} is neither an address nor an address alias"))?;
+        Ok(Self::Alias(s.to_string()))
+    }
+}
+
+impl From<SuiAddress> for AddressOrAlias {
+    fn from(address: SuiAddress) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl Display for AddressOrAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{address}"),
+            Self::Alias(alias) => write!(f, "{alias}"),
+        }
+    }
+}
+
+/// A network the client can connect to, e.g. devnet or a local network, known by its alias.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SuiEnv {
+    pub alias: String,
+    pub rpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet: Option<String>,
+    /// Path of the keystore used in this environment, if it differs from the one in use when
+    /// switching to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<PathBuf>,
+}
+
+impl SuiEnv {
+    pub fn devnet() -> Self {
+        Self {
+            alias: "devnet".to_string(),
+            rpc: SUI_DEV_NET_URL.to_string(),
+            ws: None,
+            faucet: Some("https://faucet.devnet.sui.io:443".to_string()),
+            keystore: None,
+        }
+    }
+
+    /// The network started by `sui start`, with the default addresses of its full node.
+    pub fn localnet() -> Self {
+        Self {
+            alias: "localnet".to_string(),
+            rpc: "http://127.0.0.1:9000".to_string(),
+            ws: Some("ws://127.0.0.1:9001".to_string()),
+            faucet: None,
+            keystore: None,
+        }
+    }
+
+    pub fn client_type(&self) -> ClientType {
+        ClientType::RPC(self.rpc.clone(), self.ws.clone())
+    }
+}
+
+impl Display for SuiEnv {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} : {}", self.alias, self.rpc)?;
+        if let Some(ws) = &self.ws {
+            write!(f, ", WS {ws}")?;
+        }
+        if let Some(faucet) = &self.faucet {
+            write!(f, ", faucet {faucet}")?;
+        }
+        if let Some(keystore) = &self.keystore {
+            write!(f, ", keystore {}", keystore.display())?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for SuiClientConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
@@ -40,6 +273,9 @@ impl Display for SuiClientConfig {
             None => writeln!(writer, "None")?,
         };
         writeln!(writer, "{}", self.keystore)?;
+        if let Some(env) = &self.active_env {
+            writeln!(writer, "Active environment : {env}")?;
+        }
         write!(writer, "{}", self.client_type)?;
         write!(f, "{}", writer)
     }
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::io::{stderr, Write};
 use std::ops::Deref;
 
@@ -137,8 +138,30 @@ async fn handle_command(
     completion_cache: CompletionCache,
 ) -> Result<bool, anyhow::Error> {
     let wallet_opts = wallet_opts?;
+    let package = match &wallet_opts.command {
+        SuiClientCommands::Call { package, .. } | SuiClientCommands::Functions { package, .. } => {
+            Some(*package)
+        }
+        _ => None,
+    };
     let result = wallet_opts.command.execute(context).await?;
 
+    // Remember the modules and functions of packages the console has seen, to complete later calls
+    // to them. Completion is best effort, so failing to fetch them is not an error.
+    let fetched_abi = match (&result, package) {
+        (SuiClientCommandResult::Functions(_), _) | (_, None) => None,
+        (_, Some(package)) => context
+            .client
+            .read_api()
+            .get_package_abi(package)
+            .await
+            .ok(),
+    };
+    let abi = match &result {
+        SuiClientCommandResult::Functions(abi) => Some(abi),
+        _ => fetched_abi.as_ref(),
+    };
+
     // Update completion cache
     // TODO: Completion data are keyed by strings, are there ways to make it more error proof?
     if let Ok(mut cache) = completion_cache.write() {
@@ -162,6 +185,25 @@ async fn handle_command(
             }
             _ => {}
         }
+        if let Some(abi) = abi {
+            extend_completions(
+                &mut cache,
+                CacheKey::flag("--package"),
+                [abi.package_id.to_string()],
+            );
+            extend_completions(
+                &mut cache,
+                CacheKey::flag("--module"),
+                abi.modules.keys().cloned(),
+            );
+            extend_completions(
+                &mut cache,
+                CacheKey::new("call", "--function"),
+                abi.modules
+                    .values()
+                    .flat_map(|module| module.entry_functions.keys().cloned()),
+            );
+        }
     }
     result.print(!wallet_opts.json);
 
@@ -169,9 +211,23 @@ async fn handle_command(
     if matches!(
         result,
         SuiClientCommandResult::Switch(SwitchResponse { rpc: Some(_), .. })
+            | SuiClientCommandResult::Switch(SwitchResponse { env: Some(_), .. })
     ) {
         println!("RPC server switch completed, please restart Sui console.");
         return Ok(true);
     }
     Ok(false)
 }
+
+fn extend_completions(
+    cache: &mut BTreeMap<CacheKey, Vec<String>>,
+    key: CacheKey,
+    completions: impl IntoIterator<Item = String>,
+) {
+    let entry = cache.entry(key).or_default();
+    for completion in completions {
+        if !entry.contains(&completion) {
+            entry.push(completion);
+        }
+    }
+}
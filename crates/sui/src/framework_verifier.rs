@@ -0,0 +1,201 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks a local checkout of the Sui framework against the system packages deployed on a
+//! network. The Move standard library and the Sui framework are published at genesis to the
+//! reserved addresses 0x1 and 0x2 rather than to fresh package IDs, so they are built from the
+//! checkout and compared module by module with the packages at those addresses.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+use std::path::Path;
+
+use anyhow::anyhow;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig;
+use serde::Serialize;
+use sui_framework_build::{
+    build_move_package_with_deps, build_move_stdlib_modules, filter_package_modules,
+};
+use sui_json_rpc_types::SuiRawData;
+use sui_sdk::SuiClient;
+use sui_types::base_types::ObjectID;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+#[cfg(test)]
+#[path = "unit_tests/framework_verifier_tests.rs"]
+mod framework_verifier_tests;
+
+const MOVE_STDLIB_PACKAGE_NAME: &str = "MoveStdlib";
+const SUI_FRAMEWORK_PACKAGE_NAME: &str = "Sui";
+
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ModuleStatus {
+    /// The local module is identical to the deployed one
+    Matching,
+    /// The local module differs from the deployed one
+    Mismatched,
+    /// The modules are identical but for the bytecode version they were compiled to
+    VersionSkew { local: u32, on_chain: u32 },
+    /// The module exists locally but is not part of the deployed package
+    MissingOnChain,
+    /// The module is part of the deployed package but does not exist locally
+    MissingLocally,
+}
+
+impl Display for ModuleStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Matching => write!(f, "matching"),
+            Self::Mismatched => write!(f, "mismatched"),
+            Self::VersionSkew { local, on_chain } => write!(
+                f,
+                "bytecode version skew (local {local}, on chain {on_chain})"
+            ),
+            Self::MissingOnChain => write!(f, "missing on chain"),
+            Self::MissingLocally => write!(f, "missing locally"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageVerification {
+    pub name: String,
+    pub package_id: ObjectID,
+    pub modules: BTreeMap<String, ModuleStatus>,
+}
+
+impl PackageVerification {
+    pub fn is_verified(&self) -> bool {
+        self.modules
+            .values()
+            .all(|status| *status == ModuleStatus::Matching)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkVerification {
+    pub packages: Vec<PackageVerification>,
+}
+
+impl FrameworkVerification {
+    pub fn is_verified(&self) -> bool {
+        self.packages.iter().all(PackageVerification::is_verified)
+    }
+}
+
+impl Display for FrameworkVerification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        for package in &self.packages {
+            let summary = if package.is_verified() {
+                "matches the network"
+            } else {
+                "does not match the network"
+            };
+            writeln!(
+                writer,
+                "{} ({}) {summary}",
+                package.name, package.package_id
+            )?;
+            for (module, status) in &package.modules {
+                writeln!(writer, "  {module}: {status}")?;
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+/// Builds the Move standard library and the Sui framework from the framework checkout at
+/// `path` and compares them with the system packages deployed on the network `client` is
+/// connected to.
+pub async fn verify_framework(
+    path: &Path,
+    client: &SuiClient,
+) -> Result<FrameworkVerification, anyhow::Error> {
+    let sui_framework = build_move_package_with_deps(path, BuildConfig::default())?;
+    let sui_framework = filter_package_modules(&sui_framework)?;
+    let move_stdlib = build_move_stdlib_modules(&path.join("deps").join("move-stdlib"))?;
+
+    let mut packages = vec![];
+    for (name, address, local) in [
+        (MOVE_STDLIB_PACKAGE_NAME, MOVE_STDLIB_ADDRESS, move_stdlib),
+        (
+            SUI_FRAMEWORK_PACKAGE_NAME,
+            SUI_FRAMEWORK_ADDRESS,
+            sui_framework,
+        ),
+    ] {
+        let package_id = ObjectID::from(address);
+        let object_read = client.read_api().get_object(package_id).await?;
+        let on_chain = match &object_read.object()?.data {
+            SuiRawData::Package(package) => package.module_map.clone(),
+            SuiRawData::MoveObject(_) => {
+                return Err(anyhow!("Object {package_id} is not a package"));
+            }
+        };
+        packages.push(compare_package(name, address, &local, &on_chain)?);
+    }
+    Ok(FrameworkVerification { packages })
+}
+
+/// Compares the locally built modules of the system package at `address` with its deployed
+/// modules, given as serialized bytecode by module name.
+pub fn compare_package(
+    name: &str,
+    address: AccountAddress,
+    local: &[CompiledModule],
+    on_chain: &BTreeMap<String, Vec<u8>>,
+) -> Result<PackageVerification, anyhow::Error> {
+    let mut on_chain = on_chain
+        .iter()
+        .map(|(module, bytes)| {
+            CompiledModule::deserialize(bytes)
+                .map(|compiled| (module.clone(), compiled))
+                .map_err(|e| anyhow!("Cannot deserialize module {module} of {name}: {e}"))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+    let mut modules = BTreeMap::new();
+    for module in local {
+        let id = module.self_id();
+        if *id.address() != address {
+            return Err(anyhow!(
+                "Module {id} of {name} is not published at {}",
+                address.to_hex_literal()
+            ));
+        }
+        let status = match on_chain.remove(id.name().as_str()) {
+            None => ModuleStatus::MissingOnChain,
+            Some(deployed) if deployed == *module => ModuleStatus::Matching,
+            Some(mut deployed) => {
+                let on_chain_version = deployed.version;
+                deployed.version = module.version;
+                if deployed == *module {
+                    ModuleStatus::VersionSkew {
+                        local: module.version,
+                        on_chain: on_chain_version,
+                    }
+                } else {
+                    ModuleStatus::Mismatched
+                }
+            }
+        };
+        modules.insert(id.name().to_string(), status);
+    }
+    modules.extend(
+        on_chain
+            .into_keys()
+            .map(|module| (module, ModuleStatus::MissingLocally)),
+    );
+
+    Ok(PackageVerification {
+        name: name.to_string(),
+        package_id: ObjectID::from(address),
+        modules,
+    })
+}
@@ -59,6 +59,10 @@ pub enum CeremonyCommand {
         network_key_file: PathBuf,
         #[clap(long)]
         network_address: Multiaddr,
+        /// Other addresses of the validator, e.g. an IPv6 or a backup address, which clients
+        /// dial in order when the network address is unreachable.
+        #[clap(long)]
+        backup_network_address: Vec<Multiaddr>,
         #[clap(long)]
         narwhal_primary_address: Multiaddr,
         #[clap(long)]
@@ -107,6 +111,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             account_key_file,
             network_key_file,
             network_address,
+            backup_network_address,
             narwhal_primary_address,
             narwhal_worker_address,
             narwhal_consensus_address,
@@ -117,7 +122,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             let worker_keypair: NetworkKeyPair = read_network_keypair_from_file(worker_key_file)?;
             let network_keypair: NetworkKeyPair = read_network_keypair_from_file(network_key_file)?;
             let pop = generate_proof_of_possession(&keypair, (&account_keypair.public()).into());
-            builder = builder.add_validator(
+            builder = builder.add_validator_with_backup_addresses(
                 sui_config::ValidatorInfo {
                     name,
                     protocol_key: keypair.public().into(),
@@ -133,6 +138,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
                     narwhal_consensus_address,
                 },
                 pop,
+                backup_network_address,
             );
             builder.save(dir)?;
         }
@@ -352,6 +358,7 @@ mod test {
                     network_key_file: network_key_file.into(),
                     account_key_file: account_key_file.into(),
                     network_address: validator.network_address().to_owned(),
+                    backup_network_address: vec![],
                     narwhal_primary_address: validator.narwhal_primary_address.clone(),
                     narwhal_worker_address: validator.narwhal_worker_address.clone(),
                     narwhal_consensus_address: validator.narwhal_consensus_address.clone(),
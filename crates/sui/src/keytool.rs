@@ -1,16 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::{env, fs};
 
 use anyhow::anyhow;
 use bip32::{DerivationPath, Mnemonic};
 use clap::*;
+use console::Term;
 use fastcrypto::traits::{ToFromBytes, VerifyingKey};
 use signature::rand_core::OsRng;
+use sui_keys::key_bundle::{convert_key_scheme, EncryptedKeyBundle};
 use sui_keys::key_derive::derive_key_pair_from_path;
 use tracing::info;
+use zeroize::Zeroizing;
 
 use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey};
 use sui_keys::keystore::{AccountKeystore, Keystore};
@@ -22,6 +25,8 @@ use sui_types::crypto::{
 };
 use sui_types::sui_serde::{Base64, Encoding};
 
+const KEY_BUNDLE_PASSPHRASE_ENV: &str = "SUI_KEY_BUNDLE_PASSPHRASE";
+
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
@@ -65,6 +70,26 @@ pub enum KeyToolCommand {
     LoadKeypair {
         file: PathBuf,
     },
+    /// Export keys of the keystore into a passphrase-encrypted bundle, to import them into
+    /// another keystore with `import-bundle`. The passphrase is prompted for, or read from
+    /// SUI_KEY_BUNDLE_PASSPHRASE when set.
+    ExportBundle {
+        /// File to write the bundle to
+        #[clap(long, parse(from_os_str))]
+        output: PathBuf,
+        /// Addresses of the keys to export, all keys of the keystore if none are given
+        #[clap(long, parse(try_from_str = decode_bytes_hex), multiple_values = true)]
+        addresses: Vec<SuiAddress>,
+    },
+    /// Import the keys of a bundle written by `export-bundle` into the keystore. The passphrase
+    /// is prompted for, or read from SUI_KEY_BUNDLE_PASSPHRASE when set.
+    ImportBundle {
+        file: PathBuf,
+        /// Convert the keys to this key scheme {ed25519 | secp256k1} with the same private key
+        /// bytes. Converted keys derive new addresses.
+        #[clap(long)]
+        key_scheme: Option<SignatureScheme>,
+    },
 }
 
 impl KeyToolCommand {
@@ -176,12 +201,98 @@ impl KeyToolCommand {
                     }
                 }
             }
+
+            KeyToolCommand::ExportBundle { output, addresses } => {
+                let passphrase = bundle_passphrase(true)?;
+                let bundle = export_bundle(keystore, addresses, &output, &passphrase)?;
+                println!(
+                    "Exported {} keys to {}",
+                    bundle.keys.len(),
+                    output.display()
+                );
+            }
+
+            KeyToolCommand::ImportBundle { file, key_scheme } => {
+                let passphrase = bundle_passphrase(false)?;
+                import_bundle(keystore, &file, &passphrase, key_scheme.as_ref())?;
+            }
         }
 
         Ok(())
     }
 }
 
+/// Writes the keys of `addresses`, or all keys of `keystore` if none are given, to a bundle at
+/// `output` encrypted with `passphrase`.
+fn export_bundle(
+    keystore: &Keystore,
+    addresses: Vec<SuiAddress>,
+    output: &Path,
+    passphrase: &str,
+) -> Result<EncryptedKeyBundle, anyhow::Error> {
+    let addresses = if addresses.is_empty() {
+        keystore.addresses()
+    } else {
+        addresses
+    };
+    let keys = addresses
+        .iter()
+        .map(|address| keystore.get_key(address))
+        .collect::<Result<Vec<_>, _>>()?;
+    let bundle = EncryptedKeyBundle::seal(&keys, passphrase)?;
+    bundle.save(output)?;
+    Ok(bundle)
+}
+
+/// Adds the keys of the bundle at `file` to `keystore`, converted to `key_scheme` if given.
+/// Returns the addresses of the keys added.
+fn import_bundle(
+    keystore: &mut Keystore,
+    file: &Path,
+    passphrase: &str,
+    key_scheme: Option<&SignatureScheme>,
+) -> Result<Vec<SuiAddress>, anyhow::Error> {
+    let bundle = EncryptedKeyBundle::load(file)?;
+    let mut addresses = Vec::new();
+    for key in bundle.open(passphrase)? {
+        let address = SuiAddress::from(&key.public());
+        let key = match key_scheme {
+            Some(key_scheme) => convert_key_scheme(key, key_scheme)?,
+            None => key,
+        };
+        let imported_address = SuiAddress::from(&key.public());
+        keystore.add_key(key)?;
+        if imported_address == address {
+            info!("Key imported for address [{address}]");
+        } else {
+            println!(
+                "Key of address [{address}] imported as a key of address [{imported_address}]"
+            );
+        }
+        addresses.push(imported_address);
+    }
+    Ok(addresses)
+}
+
+/// Reads the passphrase of a key bundle from SUI_KEY_BUNDLE_PASSPHRASE, or prompts for it
+/// without echoing it, twice if `confirm`.
+fn bundle_passphrase(confirm: bool) -> Result<Zeroizing<String>, anyhow::Error> {
+    if let Ok(passphrase) = env::var(KEY_BUNDLE_PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    let term = Term::stderr();
+    term.write_str("Key bundle passphrase: ")?;
+    let passphrase = Zeroizing::new(term.read_secure_line()?);
+    if confirm {
+        term.write_str("Confirm the passphrase: ")?;
+        let confirmation = Zeroizing::new(term.read_secure_line()?);
+        if confirmation != passphrase {
+            return Err(anyhow!("The passphrases do not match"));
+        }
+    }
+    Ok(passphrase)
+}
+
 fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
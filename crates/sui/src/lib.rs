@@ -5,8 +5,10 @@
 pub mod client_commands;
 pub mod config;
 pub mod console;
+pub mod framework_verifier;
 pub mod keytool;
 pub mod shell;
+pub mod source_registry;
 pub mod sui_commands;
 pub mod sui_move;
 
@@ -0,0 +1,257 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An off-chain registry of the sources of published packages, kept as a directory. Publishing
+//! with a registry records, under the ID of the new package, the source maps of its modules and
+//! the digests of its source files. Anyone holding a source tree can then check that it hashes
+//! to the digests registered for a package, to show which sources the package was built from.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_package::compilation::compiled_package::CompiledPackage;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sui_types::base_types::ObjectID;
+
+#[cfg(test)]
+#[path = "unit_tests/source_registry_tests.rs"]
+mod source_registry_tests;
+
+const SOURCE_MAPS_DIR: &str = "source_maps";
+const SOURCES_FILE: &str = "sources.json";
+const MANIFEST_FILE: &str = "Move.toml";
+const SOURCES_DIR: &str = "sources";
+
+/// The digests of the source files of a package, by path relative to the package root.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceDigests {
+    pub package_id: ObjectID,
+    pub files: BTreeMap<String, String>,
+}
+
+/// The outcome of checking a source tree against the digests registered for a package.
+#[derive(Serialize, Debug, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceVerification {
+    pub package_id: Option<ObjectID>,
+    /// Files whose contents hash to the registered digest
+    pub matching: Vec<String>,
+    /// Files whose contents differ from the registered ones
+    pub mismatched: Vec<String>,
+    /// Registered files missing from the source tree
+    pub missing: Vec<String>,
+    /// Files of the source tree that were not registered
+    pub unregistered: Vec<String>,
+}
+
+impl SourceVerification {
+    pub fn is_verified(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty() && self.unregistered.is_empty()
+    }
+}
+
+impl Display for SourceVerification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        let package = self.package_id.map(|id| id.to_string()).unwrap_or_default();
+        if self.is_verified() {
+            writeln!(
+                writer,
+                "Sources of package {package} verified ({} files)",
+                self.matching.len()
+            )?;
+        } else {
+            writeln!(
+                writer,
+                "Sources of package {package} do not match the registry"
+            )?;
+        }
+        for (label, files) in [
+            ("Mismatched", &self.mismatched),
+            ("Missing", &self.missing),
+            ("Unregistered", &self.unregistered),
+        ] {
+            for file in files {
+                writeln!(writer, "  {label}: {file}")?;
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+pub struct SourceRegistry {
+    root: PathBuf,
+}
+
+impl SourceRegistry {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn package_dir(&self, package_id: &ObjectID) -> PathBuf {
+        self.root.join(package_id.to_string())
+    }
+
+    /// Records the source maps of the modules of `package`, built from `package_path` and
+    /// published as `package_id`, along with the digests of its source files.
+    pub fn register(
+        &self,
+        package_id: ObjectID,
+        package_path: &Path,
+        package: &CompiledPackage,
+    ) -> Result<SourceDigests, anyhow::Error> {
+        let package_dir = self.package_dir(&package_id);
+        let source_maps_dir = package_dir.join(SOURCE_MAPS_DIR);
+        fs::create_dir_all(&source_maps_dir)?;
+        for unit in &package.root_compiled_units {
+            fs::write(
+                source_maps_dir.join(format!("{}.mvsm", unit.unit.name())),
+                unit.unit.serialize_source_map(),
+            )?;
+        }
+
+        let digests = SourceDigests {
+            package_id,
+            files: hash_sources(package_path)?,
+        };
+        fs::write(
+            package_dir.join(SOURCES_FILE),
+            serde_json::to_string_pretty(&digests)?,
+        )?;
+        Ok(digests)
+    }
+
+    pub fn digests(&self, package_id: &ObjectID) -> Result<SourceDigests, anyhow::Error> {
+        let path = self.package_dir(package_id).join(SOURCES_FILE);
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Package {package_id} is not registered: {e}"))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Returns the BCS serialized source map of `module`, as written by the compiler.
+    pub fn source_map(
+        &self,
+        package_id: &ObjectID,
+        module: &str,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let path = self
+            .package_dir(package_id)
+            .join(SOURCE_MAPS_DIR)
+            .join(format!("{module}.mvsm"));
+        fs::read(&path).map_err(|e| anyhow!("No source map for module {module}: {e}"))
+    }
+
+    /// Locates an instruction of a function of `module` in the sources under `package_path`, as
+    /// `<file>:<line>:<column>`. Returns None if no source file matches the one the package was
+    /// built from.
+    pub fn source_location(
+        &self,
+        package_id: &ObjectID,
+        module: &str,
+        function_index: u16,
+        instruction: u16,
+        package_path: &Path,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let source_map: SourceMap = bcs::from_bytes(&self.source_map(package_id, module)?)?;
+        let loc = source_map
+            .get_code_location(FunctionDefinitionIndex(function_index), instruction)
+            .map_err(|e| {
+                anyhow!("No source location for {module} instruction {instruction}: {e}")
+            })?;
+
+        let mut files = vec![];
+        collect_move_files(&package_path.join(SOURCES_DIR), &mut files)?;
+        for file in files {
+            let contents = fs::read_to_string(&file)?;
+            if FileHash::new(&contents) != loc.file_hash() {
+                continue;
+            }
+            let before = match contents.get(..loc.start() as usize) {
+                Some(before) => before,
+                None => return Ok(None),
+            };
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            return Ok(Some(format!(
+                "{}:{}:{}",
+                relative_path(package_path, &file)?,
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Checks that the source files under `package_path` hash to the digests registered for
+    /// `package_id`.
+    pub fn verify(
+        &self,
+        package_id: &ObjectID,
+        package_path: &Path,
+    ) -> Result<SourceVerification, anyhow::Error> {
+        let mut registered = self.digests(package_id)?.files;
+        let mut verification = SourceVerification {
+            package_id: Some(*package_id),
+            ..Default::default()
+        };
+        for (file, digest) in hash_sources(package_path)? {
+            match registered.remove(&file) {
+                Some(expected) if expected == digest => verification.matching.push(file),
+                Some(_) => verification.mismatched.push(file),
+                None => verification.unregistered.push(file),
+            }
+        }
+        verification.missing = registered.into_keys().collect();
+        Ok(verification)
+    }
+}
+
+/// Hashes the manifest and the Move sources of the package at `package_path`, by path relative
+/// to it.
+pub fn hash_sources(package_path: &Path) -> Result<BTreeMap<String, String>, anyhow::Error> {
+    let mut files = vec![package_path.join(MANIFEST_FILE)];
+    collect_move_files(&package_path.join(SOURCES_DIR), &mut files)?;
+
+    let mut digests = BTreeMap::new();
+    for file in files {
+        let contents = fs::read(&file)
+            .map_err(|e| anyhow!("Cannot read source file {}: {e}", file.display()))?;
+        digests.insert(
+            relative_path(package_path, &file)?,
+            hex::encode(Sha3_256::digest(&contents)),
+        );
+    }
+    Ok(digests)
+}
+
+/// The path of `file` relative to `package_path`, with `/` separators.
+fn relative_path(package_path: &Path, file: &Path) -> Result<String, anyhow::Error> {
+    Ok(file
+        .strip_prefix(package_path)?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+fn collect_move_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), anyhow::Error> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_move_files(&path, files)?;
+        } else if path.extension().map_or(false, |ext| ext == "move") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -26,7 +26,7 @@ use sui_swarm::memory::Swarm;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 
 use crate::client_commands::{SuiClientCommands, WalletContext};
-use crate::config::SuiClientConfig;
+use crate::config::{SuiClientConfig, SuiEnv};
 use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
@@ -287,6 +287,10 @@ impl SuiCommand {
                     keystore: Keystore::from(keystore),
                     client_type: ClientType::Embedded(wallet_gateway_config),
                     active_address,
+                    // The full node of the network, once started.
+                    envs: vec![SuiEnv::localnet()],
+                    active_env: None,
+                    aliases: Default::default(),
                 };
 
                 wallet_config.save(&client_path)?;
@@ -327,9 +331,12 @@ impl SuiCommand {
                 prompt_if_no_config(&config_path).await?;
 
                 // Server switch need to happen before context creation, or else it might fail due to previously misconfigured url.
-                if let Some(SuiClientCommands::Switch { rpc, ws, .. }) = &cmd {
+                if let Some(SuiClientCommands::Switch { rpc, ws, env, .. }) = &cmd {
                     let config: SuiClientConfig = PersistedConfig::read(&config_path)?;
                     let mut config = config.persisted(&config_path);
+                    if let Some(env) = env {
+                        config.switch_env(env)?;
+                    }
                     SuiClientCommands::switch_server(&mut config, rpc, ws)?;
                     // This will init the client to check if the urls are correct and reachable
                     config.client_type.init().await?;
@@ -340,7 +347,11 @@ impl SuiCommand {
 
                 if let Some(cmd) = cmd {
                     // Do not sync if command is a gateway switch, as the current gateway might be unreachable and causes sync to panic.
-                    if !matches!(cmd, SuiClientCommands::Switch { rpc: Some(_), .. }) {
+                    if !matches!(
+                        cmd,
+                        SuiClientCommands::Switch { rpc: Some(_), .. }
+                            | SuiClientCommands::Switch { env: Some(_), .. }
+                    ) {
                         sync_accounts(&mut context).await?;
                     }
                     if let Err(e) = context.client.check_api_version() {
@@ -370,7 +381,7 @@ async fn sync_accounts(context: &mut WalletContext) -> Result<(), anyhow::Error>
     if context.client.is_gateway() {
         for address in context.config.keystore.addresses().clone() {
             SuiClientCommands::SyncClientState {
-                address: Some(address),
+                address: Some(address.into()),
             }
             .execute(context)
             .await?;
@@ -405,7 +416,18 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
         };
 
         if let Some(url) = url {
-            let client = ClientType::RPC(url, None);
+            let env = if url == SUI_DEV_NET_URL {
+                SuiEnv::devnet()
+            } else {
+                SuiEnv {
+                    alias: "custom".to_string(),
+                    rpc: url,
+                    ws: None,
+                    faucet: None,
+                    keystore: None,
+                }
+            };
+            let client = env.client_type();
             // Check url is valid
             client.init().await?;
             let keystore_path = wallet_conf_path
@@ -428,6 +450,9 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
                 keystore,
                 client_type: client,
                 active_address: Some(new_address),
+                active_env: Some(env.alias.clone()),
+                aliases: Default::default(),
+                envs: vec![env],
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
+use std::{collections::BTreeMap, fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
 
 use anyhow::anyhow;
 use move_package::BuildConfig;
@@ -10,21 +10,22 @@ use serde_json::json;
 use sui::client_commands::SwitchResponse;
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
+    config::{AddressOrAlias, SuiClientConfig, SuiEnv},
     sui_commands::SuiCommand,
 };
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
 use sui_config::{
-    Config, NetworkConfig, PersistedConfig, ValidatorInfo, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
-    SUI_GATEWAY_CONFIG, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG,
+    Config, NetworkConfig, PersistedConfig, ValidatorInfo, SUI_CLIENT_CONFIG, SUI_DEV_NET_URL,
+    SUI_FULLNODE_CONFIG, SUI_GATEWAY_CONFIG, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME,
+    SUI_NETWORK_CONFIG,
 };
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiData, SuiObject, SuiParsedData, SuiParsedObject,
+    GetObjectDataResponse, SuiData, SuiExecutionStatus, SuiObject, SuiParsedData, SuiParsedObject,
     SuiTransactionEffects,
 };
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_sdk::ClientType;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
@@ -32,7 +33,10 @@ use sui_types::crypto::{
     Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
-use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
+use sui_types::{
+    parse_sui_type_tag, sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS,
+    SUI_FRAMEWORK_OBJECT_ID,
+};
 use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::init_cluster_builder_env_aware;
 
@@ -142,6 +146,9 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
             ..Default::default()
         }),
         active_address: None,
+        envs: vec![],
+        active_env: None,
+        aliases: Default::default(),
     };
     let wallet_conf_path = working_dir.join(SUI_CLIENT_CONFIG);
     let wallet_config = wallet_config.persisted(&wallet_conf_path);
@@ -174,7 +181,7 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -249,7 +256,7 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -282,6 +289,64 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_fetch_package_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+    let output_dir = tempfile::tempdir()?;
+
+    let resp = SuiClientCommands::FetchPackage {
+        id: SUI_FRAMEWORK_OBJECT_ID,
+        output_dir: output_dir.path().to_path_buf(),
+        disassemble: true,
+    }
+    .execute(context)
+    .await?;
+    resp.print(true);
+
+    let manifest = match resp {
+        SuiClientCommandResult::FetchPackage(_, manifest) => manifest,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(manifest.package_id, SUI_FRAMEWORK_OBJECT_ID);
+    assert!(manifest.modules.contains(&"coin".to_string()));
+    // The framework depends on the standard library.
+    assert!(manifest.addresses.contains_key("0x1"));
+    for module in &manifest.modules {
+        assert!(output_dir
+            .path()
+            .join(format!("bytecode_modules/{module}.mv"))
+            .exists());
+        assert!(output_dir
+            .path()
+            .join(format!("disassembly/{module}.asm"))
+            .exists());
+    }
+    assert!(output_dir.path().join("manifest.json").exists());
+
+    // Objects that are not packages are rejected.
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let object_id = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .first()
+        .unwrap()
+        .object_id;
+    assert!(SuiClientCommands::FetchPackage {
+        id: object_id,
+        output_dir: output_dir.path().to_path_buf(),
+        disassemble: false,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_gas_command() -> Result<(), anyhow::Error> {
     let mut test_cluster = init_cluster_builder_env_aware().build().await?;
@@ -298,7 +363,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -308,7 +373,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: SuiAddress::random_for_testing_only(),
+        to: SuiAddress::random_for_testing_only().into(),
         object_id: object_to_send,
         gas: Some(object_id),
         gas_budget: 50000,
@@ -318,7 +383,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -351,6 +416,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         build_config,
         gas: Some(gas_obj_id),
         gas_budget: 20_000,
+        source_registry: None,
     }
     .execute(context)
     .await?;
@@ -368,7 +434,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address1`
     SuiClientCommands::Objects {
-        address: Some(address1),
+        address: Some(address1.into()),
     }
     .execute(context)
     .await?
@@ -403,6 +469,10 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         gas: None,
         gas_budget: 20_000,
+        dry_run: false,
+        trace: false,
+        source_registry: None,
+        package_path: PathBuf::from("."),
     }
     .execute(context)
     .await?;
@@ -443,6 +513,10 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        dry_run: false,
+        trace: false,
+        source_registry: None,
+        package_path: PathBuf::from("."),
     }
     .execute(context)
     .await;
@@ -467,6 +541,10 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        dry_run: false,
+        trace: false,
+        source_registry: None,
+        package_path: PathBuf::from("."),
     }
     .execute(context)
     .await;
@@ -492,6 +570,10 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 20_000,
+        dry_run: false,
+        trace: false,
+        source_registry: None,
+        package_path: PathBuf::from("."),
     }
     .execute(context)
     .await?;
@@ -524,6 +606,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         build_config,
         gas: Some(gas_obj_id),
         gas_budget: 20_000,
+        source_registry: None,
     }
     .execute(context)
     .await?;
@@ -558,6 +641,186 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_pay_with_selected_coins() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+    let recipients = vec![
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    ];
+
+    // A dry run estimates the fee without executing the transaction
+    let resp = SuiClientCommands::Pay {
+        input_coins: vec![],
+        recipients: recipients.iter().map(|r| (*r).into()).collect(),
+        amounts: vec![100, 200],
+        gas: None,
+        gas_budget: 50000,
+        dry_run: true,
+    }
+    .execute(context)
+    .await?;
+    let effects = if let SuiClientCommandResult::DryRun(effects) = resp {
+        effects
+    } else {
+        panic!("Expected a dry run, got {resp:?}")
+    };
+    assert_eq!(effects.created.len(), 2);
+    assert!(effects.gas_used.computation_cost > 0);
+    for recipient in &recipients {
+        assert!(context
+            .client
+            .read_api()
+            .get_objects_owned_by_address(*recipient)
+            .await?
+            .is_empty());
+    }
+
+    let resp = SuiClientCommands::Pay {
+        input_coins: vec![],
+        recipients: recipients.iter().map(|r| (*r).into()).collect(),
+        amounts: vec![100, 200],
+        gas: None,
+        gas_budget: 50000,
+        dry_run: false,
+    }
+    .execute(context)
+    .await?;
+    let effects = if let SuiClientCommandResult::Pay(_, effects) = resp {
+        effects
+    } else {
+        panic!("Expected a pay transaction, got {resp:?}")
+    };
+    assert_eq!(effects.created.len(), 2);
+    for recipient in &recipients {
+        assert_eq!(
+            context
+                .client
+                .read_api()
+                .get_objects_owned_by_address(*recipient)
+                .await?
+                .len(),
+            1
+        );
+    }
+    Ok(())
+}
+
+#[sim_test]
+async fn test_functions_and_dry_run_call() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let resp = SuiClientCommands::Functions {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: Some("devnet_nft".to_string()),
+    }
+    .execute(context)
+    .await?;
+    assert!(resp
+        .to_string()
+        .contains("devnet_nft::mint(vector<u8>, vector<u8>, vector<u8>)"));
+    let abi = if let SuiClientCommandResult::Functions(abi) = resp {
+        abi
+    } else {
+        panic!("Expected functions, got {resp:?}")
+    };
+    assert_eq!(abi.modules.keys().collect::<Vec<_>>(), vec!["devnet_nft"]);
+
+    assert!(SuiClientCommands::Functions {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: Some("no_such_module".to_string()),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // A dry run of the call creates nothing
+    let objects_before = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .len();
+    let resp = SuiClientCommands::Call {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: "devnet_nft".to_string(),
+        function: "mint".to_string(),
+        type_args: vec![],
+        args: vec![
+            SuiJsonValue::new(json!("name"))?,
+            SuiJsonValue::new(json!("description"))?,
+            SuiJsonValue::new(json!("https://sui.io"))?,
+        ],
+        gas: None,
+        gas_budget: 20_000,
+        dry_run: true,
+        trace: false,
+        source_registry: None,
+        package_path: PathBuf::from("."),
+    }
+    .execute(context)
+    .await?;
+    let effects = if let SuiClientCommandResult::DryRun(effects) = resp {
+        effects
+    } else {
+        panic!("Expected a dry run, got {resp:?}")
+    };
+    assert_eq!(effects.created.len(), 1);
+    assert_eq!(
+        context
+            .client
+            .read_api()
+            .get_objects_owned_by_address(address)
+            .await?
+            .len(),
+        objects_before
+    );
+
+    // A traced dry run of a failing call shows where it aborted
+    let coins = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let resp = SuiClientCommands::Call {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: "pay".to_string(),
+        function: "split".to_string(),
+        type_args: vec![parse_sui_type_tag("0x2::sui::SUI")?],
+        args: vec![
+            SuiJsonValue::new(json!(coins[0].object_id))?,
+            SuiJsonValue::new(json!(u64::MAX.to_string()))?,
+        ],
+        gas: Some(coins[1].object_id),
+        gas_budget: 20_000,
+        dry_run: true,
+        trace: true,
+        source_registry: None,
+        package_path: PathBuf::from("."),
+    }
+    .execute(context)
+    .await?;
+    assert!(resp.to_string().contains("Execution Trace"));
+    let response = if let SuiClientCommandResult::DryRunTrace(response, _) = resp {
+        response
+    } else {
+        panic!("Expected a traced dry run, got {resp:?}")
+    };
+    assert!(matches!(
+        response.effects.status,
+        SuiExecutionStatus::Failure { .. }
+    ));
+    let trace = response.trace.unwrap();
+    assert_eq!(trace.stack[0].module, "balance");
+    assert_eq!(trace.stack[0].function.as_deref(), Some("split"));
+    assert_eq!(trace.calls.len(), 1);
+    assert_eq!(trace.calls[0].function, "split");
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[sim_test]
 async fn test_native_transfer() -> Result<(), anyhow::Error> {
@@ -579,7 +842,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: Some(gas_obj_id),
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
     }
@@ -647,7 +910,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: None,
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
     }
@@ -717,9 +980,10 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
 
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -732,7 +996,8 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(addr2),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None,
             })
         )
     );
@@ -756,9 +1021,10 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Check that we can switch to this address
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
+        address: Some(new_addr.into()),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -770,7 +1036,8 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(new_addr),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None,
             })
         )
     );
@@ -837,9 +1104,10 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     let addr2 = context.config.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -850,13 +1118,108 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(addr2),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None,
             })
         )
     );
     Ok(())
 }
 
+#[test]
+fn test_client_config_envs() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let keystore_path = temp_dir.path().join(SUI_KEYSTORE_FILENAME);
+    let mut env_keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let (env_address, _, _) = env_keystore.generate_new_key(SignatureScheme::ED25519, None)?;
+
+    let keystore = Keystore::from(InMemKeystore::new(1));
+    let address = keystore.addresses()[0];
+    let mut config = SuiClientConfig {
+        keystore,
+        client_type: ClientType::RPC("http://127.0.0.1:9000".to_string(), None),
+        active_address: Some(address),
+        envs: vec![],
+        active_env: None,
+        aliases: Default::default(),
+    };
+    config.add_env(SuiEnv::devnet())?;
+    // Aliases are unique
+    assert!(config.add_env(SuiEnv::devnet()).is_err());
+    config.add_env(SuiEnv {
+        alias: "custom".to_string(),
+        rpc: "http://127.0.0.1:9100".to_string(),
+        ws: None,
+        faucet: None,
+        keystore: Some(keystore_path),
+    })?;
+
+    config.switch_env("devnet")?;
+    assert_eq!(config.active_env.as_deref(), Some("devnet"));
+    assert!(matches!(&config.client_type, ClientType::RPC(url, None) if url == SUI_DEV_NET_URL));
+    assert_eq!(config.active_address, Some(address));
+
+    // The keystore of the environment replaces the one in use, with its addresses
+    config.switch_env("custom")?;
+    assert_eq!(config.keystore.addresses(), vec![env_address]);
+    assert_eq!(config.active_address, Some(env_address));
+
+    assert!(config.remove_env("custom").is_err());
+    assert_eq!(config.remove_env("devnet")?, SuiEnv::devnet());
+    assert!(config.switch_env("devnet").is_err());
+    assert!(config.switch_env("unknown").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_client_config_aliases() -> Result<(), anyhow::Error> {
+    let keystore = Keystore::from(InMemKeystore::new(2));
+    let (address1, address2) = (keystore.addresses()[0], keystore.addresses()[1]);
+    let mut config = SuiClientConfig {
+        keystore,
+        client_type: ClientType::RPC("http://127.0.0.1:9000".to_string(), None),
+        active_address: Some(address1),
+        envs: vec![],
+        active_env: None,
+        aliases: Default::default(),
+    };
+    config.add_alias("alice".to_string(), address1)?;
+    config.add_alias("alice-2".to_string(), address1)?;
+    assert!(config.add_alias("alice".to_string(), address2).is_err());
+    // Aliases cannot be mistaken for addresses
+    assert!(config.add_alias("0xalice".to_string(), address2).is_err());
+    assert!(config.add_alias("a".repeat(40), address2).is_err());
+    assert_eq!(config.aliases_of(&address1), vec!["alice", "alice-2"]);
+
+    assert_eq!(
+        config.resolve_address(&"alice".parse::<AddressOrAlias>()?)?,
+        address1
+    );
+    assert_eq!(
+        config.resolve_address(&address2.to_string().parse::<AddressOrAlias>()?)?,
+        address2
+    );
+    assert!(config
+        .resolve_address(&"bob".parse::<AddressOrAlias>()?)
+        .is_err());
+
+    // Imports are all or nothing
+    let exported = config.aliases.clone();
+    let conflicting = BTreeMap::from([
+        ("bob".to_string(), address2),
+        ("alice".to_string(), address2),
+    ]);
+    assert!(config.import_aliases(conflicting).is_err());
+    assert_eq!(config.aliases, exported);
+    let mut imported = exported;
+    imported.insert("bob".to_string(), address2);
+    assert_eq!(config.import_aliases(imported)?, 1);
+
+    assert_eq!(config.remove_alias("alice")?, address1);
+    assert!(config.remove_alias("alice").is_err());
+    Ok(())
+}
+
 fn get_gas_value(o: &SuiParsedObject) -> u64 {
     GasCoin::try_from(o).unwrap().value()
 }
@@ -1223,7 +1586,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let coin = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::SerializeTransferSui {
-        to: address1,
+        to: address1.into(),
         sui_coin_object_id: coin,
         gas_budget: 1000,
         amount: Some(1),
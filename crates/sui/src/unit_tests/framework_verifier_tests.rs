@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_framework::get_sui_framework;
+
+fn serialize(modules: &[CompiledModule]) -> BTreeMap<String, Vec<u8>> {
+    modules
+        .iter()
+        .map(|module| {
+            let mut bytes = vec![];
+            module.serialize(&mut bytes).unwrap();
+            (module.self_id().name().to_string(), bytes)
+        })
+        .collect()
+}
+
+#[test]
+fn test_compare_framework_package() {
+    let framework = get_sui_framework();
+    let mut on_chain = serialize(&framework);
+
+    let verification =
+        compare_package("Sui", SUI_FRAMEWORK_ADDRESS, &framework, &on_chain).unwrap();
+    assert!(verification.is_verified());
+    assert_eq!(
+        verification.package_id,
+        ObjectID::from(SUI_FRAMEWORK_ADDRESS)
+    );
+    assert_eq!(verification.modules.len(), framework.len());
+
+    // Swap the bytecode of two modules, drop a third from the deployed package and deploy one
+    // that does not exist locally.
+    let balance = on_chain.insert("balance".to_string(), on_chain["coin"].clone());
+    on_chain.insert("coin".to_string(), balance.unwrap());
+    on_chain.remove("bag");
+    on_chain.insert("legacy".to_string(), on_chain["object"].clone());
+
+    // Lower the bytecode version of a fourth module, keeping its contents.
+    let mut skewed = framework
+        .iter()
+        .find(|m| m.self_id().name().as_str() == "tx_context")
+        .unwrap()
+        .clone();
+    let local_version = skewed.version;
+    skewed.version -= 1;
+    on_chain.extend(serialize(&[skewed]));
+
+    let verification =
+        compare_package("Sui", SUI_FRAMEWORK_ADDRESS, &framework, &on_chain).unwrap();
+    assert!(!verification.is_verified());
+    assert_eq!(verification.modules["balance"], ModuleStatus::Mismatched);
+    assert_eq!(verification.modules["coin"], ModuleStatus::Mismatched);
+    assert_eq!(verification.modules["bag"], ModuleStatus::MissingOnChain);
+    assert_eq!(verification.modules["legacy"], ModuleStatus::MissingLocally);
+    assert_eq!(verification.modules["object"], ModuleStatus::Matching);
+    assert_eq!(
+        verification.modules["tx_context"],
+        ModuleStatus::VersionSkew {
+            local: local_version,
+            on_chain: local_version - 1,
+        }
+    );
+}
+
+#[test]
+fn test_compare_package_at_wrong_address() {
+    let framework = get_sui_framework();
+    let on_chain = serialize(&framework);
+    assert!(compare_package("MoveStdlib", MOVE_STDLIB_ADDRESS, &framework, &on_chain).is_err());
+}
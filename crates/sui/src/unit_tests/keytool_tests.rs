@@ -6,6 +6,7 @@ use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
+use super::{export_bundle, import_bundle};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
@@ -280,3 +281,69 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
     .is_ok());
     Ok(())
 }
+
+#[test]
+fn test_export_import_bundle() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut keystore = Keystore::from(InMemKeystore::new(2));
+    keystore.add_key(SuiKeyPair::Secp256k1SuiKeyPair(get_key_pair().1))?;
+    let addresses = keystore.addresses();
+    let bundle = temp_dir.path().join("keys.bundle");
+
+    // Export all keys but the first one
+    export_bundle(
+        &keystore,
+        addresses[1..].to_vec(),
+        &bundle,
+        "correct horse battery staple",
+    )?;
+
+    let mut other_keystore = Keystore::from(InMemKeystore::new(0));
+    assert!(import_bundle(&mut other_keystore, &bundle, "wrong passphrase", None).is_err());
+    assert!(other_keystore.addresses().is_empty());
+
+    let imported = import_bundle(
+        &mut other_keystore,
+        &bundle,
+        "correct horse battery staple",
+        None,
+    )?;
+    assert_eq!(imported, addresses[1..].to_vec());
+    assert_eq!(other_keystore.addresses(), addresses[1..].to_vec());
+    for address in &addresses[1..] {
+        assert!(other_keystore.get_key(address)?.public() == keystore.get_key(address)?.public());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_import_bundle_converting_key_scheme() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut keystore = Keystore::from(InMemKeystore::new(0));
+    keystore.add_key(SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1))?;
+    keystore.add_key(SuiKeyPair::Secp256k1SuiKeyPair(get_key_pair().1))?;
+    let addresses = keystore.addresses();
+    let bundle = temp_dir.path().join("keys.bundle");
+    export_bundle(&keystore, vec![], &bundle, "passphrase")?;
+
+    let mut other_keystore = Keystore::from(InMemKeystore::new(0));
+    let imported = import_bundle(
+        &mut other_keystore,
+        &bundle,
+        "passphrase",
+        Some(&SignatureScheme::Secp256k1),
+    )?;
+    assert_eq!(imported.len(), 2);
+    for (address, imported) in addresses.iter().zip(&imported) {
+        // The Ed25519 key is converted and derives a new address, the Secp256k1 key is kept.
+        let original = keystore.get_key(address)?.public();
+        let key = other_keystore.get_key(imported)?.public();
+        assert_eq!(key.flag(), SignatureScheme::Secp256k1.flag());
+        assert_eq!(SuiAddress::from(&key), *imported);
+        assert_eq!(
+            imported == address,
+            original.flag() == SignatureScheme::Secp256k1.flag()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::BuildConfig;
+use sui_framework_build::build_move_package_with_deps;
+
+use super::*;
+
+fn example_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../sui_programmability/examples")
+        .join(name)
+}
+
+#[test]
+fn test_register_and_verify_sources() {
+    let registry_dir = tempfile::tempdir().unwrap();
+    let registry = SourceRegistry::new(registry_dir.path());
+    let package_id = ObjectID::random();
+    let package_path = example_path("basics");
+    let package = build_move_package_with_deps(&package_path, BuildConfig::default()).unwrap();
+
+    let digests = registry
+        .register(package_id, &package_path, &package)
+        .unwrap();
+    assert!(digests.files.contains_key("Move.toml"));
+    assert!(digests.files.contains_key("sources/counter.move"));
+    assert_eq!(registry.digests(&package_id).unwrap(), digests);
+    assert!(!registry
+        .source_map(&package_id, "counter")
+        .unwrap()
+        .is_empty());
+
+    let verification = registry.verify(&package_id, &package_path).unwrap();
+    assert!(verification.is_verified(), "{verification}");
+    assert_eq!(verification.matching.len(), digests.files.len());
+
+    // Another source tree does not verify.
+    let verification = registry.verify(&package_id, &example_path("nfts")).unwrap();
+    assert!(!verification.is_verified());
+    assert_eq!(verification.mismatched, vec!["Move.toml".to_string()]);
+    assert!(verification
+        .missing
+        .contains(&"sources/counter.move".to_string()));
+
+    // Instructions are located in the registered sources.
+    let location = registry
+        .source_location(&package_id, "counter", 0, 0, &package_path)
+        .unwrap()
+        .unwrap();
+    assert!(location.starts_with("sources/counter.move:"), "{location}");
+    assert_eq!(
+        registry
+            .source_location(&package_id, "counter", 0, 0, &example_path("nfts"))
+            .unwrap(),
+        None
+    );
+
+    // Unknown packages are not registered.
+    assert!(registry.verify(&ObjectID::random(), &package_path).is_err());
+}
@@ -18,14 +18,17 @@ use tokio::time::{sleep, Duration};
 
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
 use sui_json_rpc_types::{
-    SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse, SuiExecutionStatus,
-    SuiMoveStruct, SuiMoveValue, SuiTransactionFilter, SuiTransactionResponse,
+    CoinPage, GetObjectDataResponse, SuiEvent, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiExecutionStatus, SuiMoveStruct, SuiMoveValue, SuiSupply,
+    SuiTransactionFilter, SuiTransactionResponse,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::*;
 use sui_node::SuiNode;
 use sui_types::base_types::{ObjectRef, SequenceNumber};
+use sui_types::error::SuiError;
 use sui_types::event::TransferType;
+use sui_types::gas_coin::GAS;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
 };
@@ -737,6 +740,51 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+// Test fullnode serves the coin index and the total supplies over jsonrpc
+#[tokio::test]
+async fn test_full_node_coin_index_rpc_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let jsonrpc_client = &test_cluster.fullnode_handle.as_ref().unwrap().rpc_client;
+
+    let (transferred_object, _, receiver, digest) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    // The coin index is built in the background, and the transfer indexed after a poll.
+    let coins = timeout(Duration::from_secs(30), async {
+        loop {
+            let coins: Result<CoinPage, _> = jsonrpc_client
+                .request("sui_getCoins", rpc_params![receiver])
+                .await;
+            if let Ok(coins) = coins {
+                let object_ids: Vec<_> = coins.data.iter().map(|c| c.reference.object_id).collect();
+                if object_ids.contains(&transferred_object) {
+                    break coins;
+                }
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await?;
+    let sui = GAS::type_tag().to_string();
+    assert!(coins.data.iter().all(|coin| coin.coin_type == sui));
+
+    let supply: SuiSupply = jsonrpc_client
+        .request("sui_getTotalSupply", rpc_params![sui])
+        .await?;
+    let system_state = node.state().get_sui_system_state_object().await?;
+    assert_eq!(supply.value, system_state.treasury_cap.value);
+
+    // No TreasuryCap of an unknown coin type is indexed.
+    let unknown: Result<SuiSupply, _> = jsonrpc_client
+        .request("sui_getTotalSupply", rpc_params!["0x2::unknown::UNKNOWN"])
+        .await;
+    assert!(unknown.is_err());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_full_node_transaction_orchestrator_basic() -> Result<(), anyhow::Error> {
     let mut test_cluster = init_cluster_builder_env_aware().build().await?;
@@ -931,10 +979,15 @@ async fn test_full_node_transaction_orchestrator_rpc_ok() -> Result<(), anyhow::
         certificate,
         effects: _,
         confirmed_local_execution,
+        session_token,
     } = response
     {
         assert_eq!(&certificate.transaction_digest, tx_digest);
         assert!(confirmed_local_execution);
+        assert_eq!(
+            session_token.map(|token| *token.tx_digest()),
+            Some(*tx_digest)
+        );
     } else {
         panic!("Expect EffectsCert but got {:?}", response);
     }
@@ -944,7 +997,10 @@ async fn test_full_node_transaction_orchestrator_rpc_ok() -> Result<(), anyhow::
         .await
         .unwrap();
 
-    // Test request with ExecuteTransactionRequestType::WaitForEffectsCert
+    // Test request with ExecuteTransactionRequestType::WaitForEffectsCert, with a transaction
+    // the node has not executed yet.
+    let txn = txns.swap_remove(0);
+    let tx_digest = txn.digest();
     let (tx_bytes, flag, signature, pub_key) = txn.to_network_data_for_execution();
     let params = rpc_params![
         tx_bytes,
@@ -960,12 +1016,32 @@ async fn test_full_node_transaction_orchestrator_rpc_ok() -> Result<(), anyhow::
 
     if let SuiExecuteTransactionResponse::EffectsCert {
         certificate,
-        effects: _,
+        effects,
         confirmed_local_execution,
+        session_token,
     } = response
     {
         assert_eq!(&certificate.transaction_digest, tx_digest);
         assert!(!confirmed_local_execution);
+
+        // Reads given the session token observe the transaction: the gas object is at the
+        // version it mutated it to.
+        let gas = effects.effects.gas_object;
+        let response: GetObjectDataResponse = jsonrpc_client
+            .request(
+                "sui_getObject",
+                rpc_params![gas.reference.object_id, session_token],
+            )
+            .await
+            .unwrap();
+        match response {
+            GetObjectDataResponse::Exists(object) => {
+                assert_eq!(object.reference, gas.reference);
+                assert_eq!(object.owner, gas.owner);
+                assert_eq!(object.previous_transaction, *tx_digest);
+            }
+            response => panic!("Expect the gas object but got {:?}", response),
+        }
     } else {
         panic!("Expect EffectsCert but got {:?}", response);
     }
@@ -1020,6 +1096,44 @@ async fn test_full_node_transaction_orchestrator_rpc_ok() -> Result<(), anyhow::
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_read_only_mode() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let node = start_a_fullnode(&test_cluster.swarm, false).await?;
+    let transaction_orchestrator = node
+        .transaction_orchestrator()
+        .expect("Fullnode should have transaction orchestrator toggled on.");
+
+    let context = &mut test_cluster.wallet;
+    let mut txns = make_transactions_with_wallet_context(context, 2).await;
+
+    transaction_orchestrator.set_read_only(true);
+    let txn = txns.swap_remove(0);
+    let digest = *txn.digest();
+    let err = transaction_orchestrator
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: txn.clone(),
+            request_type: ExecuteTransactionRequestType::WaitForLocalExecution,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err, SuiError::NodeReadOnly);
+    // Reads are still served.
+    let gas_id = txn.gas_payment_object_ref().0;
+    get_obj_read_from_node(&node, gas_id, None).await?;
+
+    transaction_orchestrator.set_read_only(false);
+    transaction_orchestrator
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: txn,
+            request_type: ExecuteTransactionRequestType::WaitForLocalExecution,
+        })
+        .await?;
+    node.state().get_transaction(digest).await?;
+
+    Ok(())
+}
+
 async fn get_obj_read_from_node(
     node: &SuiNode,
     object_id: ObjectID,
@@ -352,6 +352,7 @@ async fn create_and_register_new_validator(
                     .unwrap(),
             ),
             CallArg::Pure(bcs::to_bytes(&new_validator.network_address).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&Vec::<Vec<u8>>::new()).unwrap()),
             CallArg::Object(ObjectArg::ImmOrOwnedObject(validator_stake)),
             CallArg::Pure(bcs::to_bytes(&new_validator.gas_price()).unwrap()),
         ],
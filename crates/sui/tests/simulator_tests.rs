@@ -138,3 +138,43 @@ async fn test_net_determinism() {
 
     wait_for_tx(digest, node.state().clone()).await;
 }
+
+// Test that the metrics of the nodes can be read in the simulator, and that they reflect the
+// failure of a validator.
+#[cfg(msim)]
+#[sim_test]
+async fn test_metrics_endpoint() {
+    use sui_node::http_server::request;
+
+    let mut test_cluster = init_cluster_builder_env_aware().build().await.unwrap();
+    let stopped = test_cluster.swarm.validators_mut().next().unwrap();
+    let stopped_name = stopped.name();
+    let stopped_address = stopped.metrics_address();
+    let (status, _) = request(stopped_address, "GET", "/metrics", "")
+        .await
+        .unwrap();
+    assert!(status.is_success());
+    stopped.stop();
+    assert!(request(stopped_address, "GET", "/metrics", "")
+        .await
+        .is_none());
+
+    // The other validators still form a quorum, and execute the transaction.
+    let context = &mut test_cluster.wallet;
+    transfer_coin(context).await.unwrap();
+
+    for validator in test_cluster.swarm.validators() {
+        if validator.name() == stopped_name {
+            continue;
+        }
+        let (status, metrics) = request(validator.metrics_address(), "GET", "/metrics", "")
+            .await
+            .unwrap();
+        assert!(status.is_success());
+        let certs = metrics
+            .lines()
+            .find_map(|line| line.strip_prefix("total_transaction_certificates "))
+            .unwrap();
+        assert_ne!(certs, "0");
+    }
+}
@@ -4,10 +4,11 @@
 use prometheus::Registry;
 use sui::client_commands::WalletContext;
 use sui_core::authority_client::NetworkAuthorityClient;
-use sui_core::transaction_orchestrator::TransactiondOrchestrator;
+use sui_core::transaction_orchestrator::{TransactiondOrchestrator, LOCAL_EXECUTION_TIMEOUT};
 use sui_keys::keystore::AccountKeystore;
 use sui_node::SuiNode;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::error::SuiError;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
     QuorumDriverRequest, QuorumDriverRequestType, Transaction,
@@ -34,7 +35,7 @@ async fn test_blocking_execution() -> Result<(), anyhow::Error> {
     let net = active.agg_aggregator();
     let node_sync_handle = active.clone().node_sync_handle();
     let orchestrator =
-        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, &Registry::new());
+        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, None, &Registry::new());
 
     let txn_count = 4;
     let mut txns = make_transactions_with_wallet_context(context, txn_count).await;
@@ -94,7 +95,7 @@ async fn test_non_blocking_execution() -> Result<(), anyhow::Error> {
     let net = active.agg_aggregator();
     let node_sync_handle = active.clone().node_sync_handle();
     let orchestrator =
-        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, &Registry::new());
+        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, None, &Registry::new());
 
     let txn_count = 4;
     let mut txns = make_transactions_with_wallet_context(context, txn_count).await;
@@ -140,6 +141,49 @@ async fn test_non_blocking_execution() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_write_through_execution() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+
+    let active = node.active();
+
+    // Disable node sync process
+    active.cancel_node_sync_process_for_tests().await;
+
+    let net = active.agg_aggregator();
+    let node_sync_handle = active.clone().node_sync_handle();
+    let orchestrator =
+        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, None, &Registry::new());
+    orchestrator.set_write_through(true);
+
+    let mut txns = make_transactions_with_wallet_context(context, 1).await;
+    let txn = txns.swap_remove(0);
+    let digest = *txn.digest();
+
+    // Even an ImmediateReturn request is executed locally before the response is returned.
+    let res = execute_with_orchestrator(
+        &orchestrator,
+        txn,
+        ExecuteTransactionRequestType::ImmediateReturn,
+    )
+    .await;
+    let (_, effects_cert, executed_locally) = match res {
+        ExecuteTransactionResponse::EffectsCert(result) => *result,
+        _ => panic!("Expect EffectsCert for {:?}", digest),
+    };
+    assert!(executed_locally);
+
+    // The response carries the effects and events this node executed.
+    let (_, effects) = node.state().get_transaction(digest).await?;
+    assert_eq!(effects.digest(), *effects_cert.digest());
+    assert!(!effects_cert.effects.events.is_empty());
+    assert_eq!(effects.events, effects_cert.effects.events);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_local_execution_with_missing_parents() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -158,7 +202,7 @@ async fn test_local_execution_with_missing_parents() -> Result<(), anyhow::Error
     let net = active.agg_aggregator();
     let node_sync_handle = active.clone().node_sync_handle();
     let orchestrator =
-        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, &Registry::new());
+        TransactiondOrchestrator::new(net, node.state(), node_sync_handle, None, &Registry::new());
 
     let signer = context.config.keystore.addresses().get(0).cloned().unwrap();
     let (pkg_ref, counter_id) = publish_basics_package_and_make_counter(context, signer).await;
@@ -253,6 +297,65 @@ async fn test_local_execution_with_missing_parents() -> Result<(), anyhow::Error
     Ok(())
 }
 
+#[tokio::test]
+async fn test_execute_with_request_id() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let orchestrator = node.transaction_orchestrator().unwrap();
+
+    let mut txns = make_transactions_with_wallet_context(context, 2).await;
+    let txn = txns.swap_remove(0);
+    let digest = *txn.digest();
+    let request = ExecuteTransactionRequest {
+        transaction: txn,
+        request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+    };
+
+    let res = orchestrator
+        .execute_transaction_with_request_id(
+            "payment-1".to_string(),
+            request.clone(),
+            LOCAL_EXECUTION_TIMEOUT,
+        )
+        .await?;
+    let first = match res {
+        ExecuteTransactionResponse::EffectsCert(result) => result,
+        _ => panic!("Expect EffectsCert for {:?}", digest),
+    };
+
+    // A retry with the same request id returns the stored response.
+    let res = orchestrator
+        .execute_transaction_with_request_id(
+            "payment-1".to_string(),
+            request,
+            LOCAL_EXECUTION_TIMEOUT,
+        )
+        .await?;
+    let second = match res {
+        ExecuteTransactionResponse::EffectsCert(result) => result,
+        _ => panic!("Expect EffectsCert for {:?}", digest),
+    };
+    assert_eq!(first.1.digest(), second.1.digest());
+
+    // The request id cannot be reused for a different transaction.
+    let other = ExecuteTransactionRequest {
+        transaction: txns.swap_remove(0),
+        request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+    };
+    let err = orchestrator
+        .execute_transaction_with_request_id(
+            "payment-1".to_string(),
+            other,
+            LOCAL_EXECUTION_TIMEOUT,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, SuiError::ConflictingRequestId { .. }));
+
+    Ok(())
+}
+
 async fn increment(
     context: &WalletContext,
     signer: &SuiAddress,
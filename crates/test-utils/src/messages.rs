@@ -52,7 +52,7 @@ pub async fn get_account_and_gas_coins(
     let accounts = context.config.keystore.addresses();
     for address in accounts {
         let result = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await?;
@@ -9,7 +9,7 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use sui::{
     client_commands::{SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
+    config::{SuiClientConfig, SuiEnv},
 };
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis_config::GenesisConfig;
@@ -167,7 +167,7 @@ impl TestClusterBuilder {
 
         if use_embedded_gateway {
             SuiClientCommands::SyncClientState {
-                address: Some(cluster.get_address_0()),
+                address: Some(cluster.get_address_0().into()),
             }
             .execute(cluster.wallet_mut())
             .await?;
@@ -203,8 +203,14 @@ impl TestClusterBuilder {
             )
             .await?;
             if !self.use_embedded_gateway {
-                wallet_conf.client_type =
-                    ClientType::RPC(handle.rpc_url.clone(), handle.ws_url.clone());
+                wallet_conf.add_env(SuiEnv {
+                    alias: "localnet".to_string(),
+                    rpc: handle.rpc_url.clone(),
+                    ws: handle.ws_url.clone(),
+                    faucet: None,
+                    keystore: None,
+                })?;
+                wallet_conf.switch_env("localnet")?;
             }
             Some(handle)
         };
@@ -216,7 +222,14 @@ impl TestClusterBuilder {
             let url = format!("http://{}", handle.local_addr());
             let http_client = HttpClientBuilder::default().build(url.clone())?;
             if !self.use_embedded_gateway {
-                wallet_conf.client_type = ClientType::RPC(url.clone(), None);
+                wallet_conf.add_env(SuiEnv {
+                    alias: "gateway".to_string(),
+                    rpc: url.clone(),
+                    ws: None,
+                    faucet: None,
+                    keystore: None,
+                })?;
+                wallet_conf.switch_env("gateway")?;
             }
             Some(GatewayHandle {
                 handle,
@@ -292,6 +305,9 @@ impl TestClusterBuilder {
                 ..Default::default()
             }),
             active_address,
+            envs: vec![],
+            active_env: None,
+            aliases: Default::default(),
         }
         .save(&wallet_path)?;
 
@@ -29,6 +29,7 @@ pub fn test_validatdor_metadata(
         proof_of_possession_bytes: vec![],
         name: to_bytes("zero_commission").unwrap(),
         net_address,
+        backup_net_addresses: vec![],
         next_epoch_stake: 1,
         next_epoch_delegation: 1,
         next_epoch_gas_price: 1,
@@ -277,7 +277,7 @@ pub async fn transfer_sui(
         .unwrap();
 
     let res = SuiClientCommands::TransferSui {
-        to: receiver,
+        to: receiver.into(),
         amount: None,
         sui_coin_object_id: gas_ref.0,
         gas_budget: 50000,
@@ -313,7 +313,7 @@ pub async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
         gas_budget: 50000,
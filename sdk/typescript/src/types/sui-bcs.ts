@@ -313,6 +313,62 @@ bcs.registerStructType('TransactionData', {
   gasBudget: 'u64',
 });
 
+/**
+ * The last epoch in which validators sign a transaction.
+ */
+export type TransactionExpiration = { None: null } | { Epoch: number };
+
+bcs.registerEnumType('TransactionExpiration', {
+  None: null,
+  Epoch: 'u64',
+});
+
+/**
+ * TransactionData with the fields added since its encoding was versioned:
+ * more coins merged into the gas payment, and an expiration.
+ */
+export type TransactionDataV2 = TransactionData & {
+  mergedGasPayment: SuiObjectRef[];
+  expiration: TransactionExpiration;
+};
+
+/**
+ * The versioned encoding of TransactionData. The first version has a variant
+ * per transaction kind, laid out exactly as `TransactionData`, which keeps
+ * encoding the transactions using none of the fields of `TransactionDataV2`.
+ * The others must be encoded as `{ V2: data }` with `VersionedTransactionData`,
+ * and only those: nodes reject a first version transaction encoded as V2.
+ */
+bcs
+  .registerStructType('TransactionDataV1Single', {
+    kind: 'Transaction',
+    sender: 'SuiAddress',
+    gasPayment: 'SuiObjectRef',
+    gasPrice: 'u64',
+    gasBudget: 'u64',
+  })
+  .registerStructType('TransactionDataV1Batch', {
+    kind: 'vector<Transaction>',
+    sender: 'SuiAddress',
+    gasPayment: 'SuiObjectRef',
+    gasPrice: 'u64',
+    gasBudget: 'u64',
+  })
+  .registerStructType('TransactionDataV2', {
+    kind: 'TransactionKind',
+    sender: 'SuiAddress',
+    gasPayment: 'SuiObjectRef',
+    mergedGasPayment: 'vector<SuiObjectRef>',
+    gasPrice: 'u64',
+    gasBudget: 'u64',
+    expiration: 'TransactionExpiration',
+  })
+  .registerEnumType('VersionedTransactionData', {
+    V1Single: 'TransactionDataV1Single',
+    V1Batch: 'TransactionDataV1Batch',
+    V2: 'TransactionDataV2',
+  });
+
 // ========== Deprecated ===========
 
 /**
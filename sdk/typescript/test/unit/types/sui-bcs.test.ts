@@ -0,0 +1,54 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+import { describe, it, expect } from 'vitest';
+import { bcs } from '../../../src/index';
+
+const ref = (byte: number) => ({
+  objectId: `0x${byte.toString(16).padStart(40, '0')}`,
+  version: byte,
+  digest: 'AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=',
+});
+
+describe('TransactionData encoding', () => {
+  const transfer = {
+    TransferObject: {
+      recipient: `0x${'2'.repeat(40)}`,
+      object_ref: ref(1),
+    },
+  };
+  const fields = {
+    sender: `0x${'1'.repeat(40)}`,
+    gasPayment: ref(2),
+    gasPrice: 1,
+    gasBudget: 10000,
+  };
+
+  it('keeps the layout of transactions of the first version', () => {
+    const data = bcs
+      .ser('TransactionData', { kind: { Single: transfer }, ...fields })
+      .toBytes();
+    const versioned = bcs
+      .ser('VersionedTransactionData', {
+        V1Single: { kind: transfer, ...fields },
+      })
+      .toBytes();
+    expect(versioned).toEqual(data);
+  });
+
+  it('encodes the fields of the second version after its variant', () => {
+    const data = {
+      kind: { Single: transfer },
+      ...fields,
+      mergedGasPayment: [ref(3)],
+      expiration: { Epoch: 5 },
+    };
+    const versioned = bcs
+      .ser('VersionedTransactionData', { V2: data })
+      .toBytes();
+    expect(versioned[0]).toEqual(2);
+    expect(versioned.slice(1)).toEqual(
+      bcs.ser('TransactionDataV2', data).toBytes()
+    );
+  });
+});